@@ -0,0 +1,382 @@
+//! Keyword alerts
+//!
+//! Listens for completed transcriptions, checks each transcript against the
+//! enabled alert rules scoped to its call, records matches in the alert
+//! history, and notifies each matching rule's webhook (a JSON POST) and email
+//! address (through the configured SMTP relay), and posts it to the chat
+//! channels subscribed to alerts. Notifications are attempted once; the
+//! webhook and email outcomes are stored with the alert. Calls that complete
+//! while the API server is down are not checked.
+
+use crate::mail::{self, BodyFormat};
+use crate::notifications::{Notification, Notifications};
+use crate::progress::ProgressEvents;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::alerts::{AlertMatcher, PatternKind};
+use sdrtrunk_protocol::config::{AlertsConfig, SmtpConfig};
+use sdrtrunk_storage::models::RadioCallDb;
+use sdrtrunk_storage::{
+    Alert, AlertDelivery, AlertQueries, AlertRule, NewAlert, PgPool, ProgressStage,
+};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Notification delivered
+const SENT: &str = "sent";
+/// Notification attempted and failed
+const FAILED: &str = "failed";
+/// Email not attempted because no SMTP relay is configured
+const SKIPPED: &str = "skipped";
+
+/// Alert payload posted to webhooks and summarised in emails
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertNotification {
+    /// Alert ID
+    pub alert_id: Uuid,
+    /// Rule that matched
+    pub rule_id: Option<Uuid>,
+    /// Rule name
+    pub rule_name: String,
+    /// Call whose transcript matched
+    pub call_id: Uuid,
+    /// When the call was recorded
+    pub call_timestamp: DateTime<Utc>,
+    /// System of the call
+    pub system_id: SystemId,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Talkgroup of the call
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Text the rule matched
+    pub matched_text: String,
+    /// Full transcript
+    pub transcript: String,
+}
+
+impl AlertNotification {
+    /// Build the payload for an alert raised on `call`
+    #[must_use]
+    pub fn new(alert: &Alert, call: &RadioCallDb) -> Self {
+        Self {
+            alert_id: alert.id,
+            rule_id: alert.rule_id,
+            rule_name: alert.rule_name.clone(),
+            call_id: alert.call_id,
+            call_timestamp: call.call_timestamp,
+            system_id: alert.system_id.clone(),
+            system_label: call.system_label.clone(),
+            talkgroup_id: alert.talkgroup_id,
+            talkgroup_label: call.talkgroup_label.clone(),
+            matched_text: alert.matched_text.clone(),
+            transcript: alert.transcript.clone(),
+        }
+    }
+
+    /// Talkgroup as shown to people: its label, else its ID
+    fn talkgroup_name(&self) -> String {
+        match (&self.talkgroup_label, self.talkgroup_id) {
+            (Some(label), _) => label.clone(),
+            (None, Some(id)) => format!("TG {id}"),
+            (None, None) => "unknown talkgroup".to_string(),
+        }
+    }
+
+    /// Email subject line
+    fn email_subject(&self) -> String {
+        format!(
+            "[SDRTrunk alert] {}: {}",
+            self.rule_name,
+            self.talkgroup_name()
+        )
+    }
+
+    /// Plain-text email body
+    fn email_body(&self) -> String {
+        let system = self
+            .system_label
+            .as_deref()
+            .unwrap_or(self.system_id.as_str());
+        format!(
+            "Rule: {}\nMatched: {}\nSystem: {system}\nTalkgroup: {}\nTime: {}\nCall: {}\n\n{}\n",
+            self.rule_name,
+            self.matched_text,
+            self.talkgroup_name(),
+            self.call_timestamp.to_rfc3339(),
+            self.call_id,
+            self.transcript
+        )
+    }
+}
+
+/// Sends alert webhooks, emails, and chat messages
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    http: reqwest::Client,
+    smtp: Option<SmtpConfig>,
+    timeout: Duration,
+    chat: Option<Arc<Notifications>>,
+}
+
+impl Notifier {
+    /// Create a notifier from the alert settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(config: &AlertsConfig) -> Result<Self> {
+        let timeout = Duration::from_secs(config.webhook_timeout_seconds.max(1));
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build webhook client")?;
+        Ok(Self {
+            http,
+            smtp: config.smtp.clone(),
+            timeout,
+            chat: None,
+        })
+    }
+
+    /// Also post alerts to the chat channels subscribed to them
+    #[must_use]
+    pub fn with_chat(mut self, chat: Option<Arc<Notifications>>) -> Self {
+        self.chat = chat;
+        self
+    }
+
+    /// Notify a rule's webhook and email address, returning the outcomes,
+    /// and post to the chat channels
+    pub async fn deliver(
+        &self,
+        rule: &AlertRule,
+        notification: &AlertNotification,
+    ) -> AlertDelivery {
+        let mut delivery = AlertDelivery::default();
+        let mut errors = Vec::new();
+
+        if let Some(url) = rule.webhook_url.as_deref() {
+            let outcome = self.post_webhook(url, notification).await;
+            delivery.webhook_status = Some(record_outcome("webhook", outcome, &mut errors));
+        }
+        if let Some(to) = rule.email_to.as_deref() {
+            delivery.email_status = Some(match &self.smtp {
+                Some(smtp) => {
+                    let outcome = tokio::time::timeout(
+                        self.timeout,
+                        mail::send_email(
+                            smtp,
+                            to,
+                            &notification.email_subject(),
+                            &notification.email_body(),
+                            BodyFormat::Text,
+                        ),
+                    )
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow!("timed out")));
+                    record_outcome("email", outcome, &mut errors)
+                }
+                None => SKIPPED.to_string(),
+            });
+        }
+
+        if let Some(chat) = &self.chat {
+            let _ = chat.notify(&Notification::alert(notification)).await;
+        }
+
+        delivery.error = (!errors.is_empty()).then(|| errors.join("; "));
+        delivery
+    }
+
+    /// POST the notification as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or gets a non-2xx response.
+    async fn post_webhook(&self, url: &str, notification: &AlertNotification) -> Result<()> {
+        let _ = self
+            .http
+            .post(url)
+            .json(notification)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+fn record_outcome(channel: &str, outcome: Result<()>, errors: &mut Vec<String>) -> String {
+    match outcome {
+        Ok(()) => SENT.to_string(),
+        Err(e) => {
+            warn!("Alert {channel} failed: {e:#}");
+            errors.push(format!("{channel}: {e:#}"));
+            FAILED.to_string()
+        }
+    }
+}
+
+/// Spawn the task checking completed transcriptions if alerts are enabled
+#[must_use]
+pub fn spawn_alert_task(
+    pool: PgPool,
+    mut progress: ProgressEvents,
+    config: &AlertsConfig,
+    notifications: Option<&Arc<Notifications>>,
+) -> Option<JoinHandle<()>> {
+    if !config.enabled {
+        return None;
+    }
+    let notifier = match Notifier::new(config) {
+        Ok(notifier) => Arc::new(notifier.with_chat(notifications.cloned())),
+        Err(e) => {
+            warn!("Keyword alerts disabled: {e:#}");
+            return None;
+        }
+    };
+
+    Some(tokio::spawn(async move {
+        info!("Checking completed transcriptions for keyword alerts");
+        while let Some(event) = progress.recv().await {
+            if matches!(event.stage, ProgressStage::Completed { .. }) {
+                // Notifications can be slow; keep receiving meanwhile
+                drop(tokio::spawn(check_call_logged(
+                    pool.clone(),
+                    Arc::clone(&notifier),
+                    event.call_id,
+                )));
+            }
+        }
+    }))
+}
+
+async fn check_call_logged(pool: PgPool, notifier: Arc<Notifier>, call_id: Uuid) {
+    if let Err(e) = check_call(&pool, &notifier, call_id).await {
+        warn!("Failed to check call {call_id} for alerts: {e:#}");
+    }
+}
+
+/// Check a call's transcript against its alert rules and notify on matches
+///
+/// Returns the number of alerts raised. A rule that already raised an alert
+/// for the call is not raised again.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn check_call(pool: &PgPool, notifier: &Notifier, call_id: Uuid) -> Result<usize> {
+    let Some(call) = sdrtrunk_storage::get_radio_call(pool, call_id).await? else {
+        return Ok(0);
+    };
+    let Some(transcript) = call
+        .transcription_text
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+    else {
+        return Ok(0);
+    };
+
+    let rules = AlertQueries::rules_for_call(pool, &call.system_id, call.talkgroup_id).await?;
+    let mut raised = 0;
+    for (rule, matched_text) in matching_rules(&rules, transcript) {
+        let new_alert = NewAlert {
+            rule,
+            call_id,
+            system_id: &call.system_id,
+            talkgroup_id: call.talkgroup_id,
+            matched_text,
+            transcript,
+        };
+        let Some(alert) = AlertQueries::record(pool, &new_alert).await? else {
+            continue;
+        };
+        info!(
+            "Alert '{}' matched \"{matched_text}\" on call {call_id}",
+            rule.name
+        );
+        raised += 1;
+
+        let delivery = notifier
+            .deliver(rule, &AlertNotification::new(&alert, &call))
+            .await;
+        if delivery != AlertDelivery::default() {
+            AlertQueries::set_delivery(pool, alert.id, &delivery).await?;
+        }
+    }
+    Ok(raised)
+}
+
+/// Rules whose pattern occurs in `transcript`, with the matched text
+///
+/// Rules with a pattern that no longer compiles are skipped.
+fn matching_rules<'r, 't>(
+    rules: &'r [AlertRule],
+    transcript: &'t str,
+) -> Vec<(&'r AlertRule, &'t str)> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let matcher = rule
+                .pattern_type
+                .parse::<PatternKind>()
+                .and_then(|kind| AlertMatcher::new(kind, &rule.pattern));
+            match matcher {
+                Ok(matcher) => matcher.find(transcript).map(|text| (rule, text)),
+                Err(e) => {
+                    warn!("Skipping alert rule '{}' ({}): {e}", rule.name, rule.id);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, pattern_type: &str, pattern: &str) -> AlertRule {
+        AlertRule {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            pattern_type: pattern_type.to_string(),
+            system_id: None,
+            talkgroup_id: None,
+            webhook_url: None,
+            email_to: None,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_matching_rules() {
+        let rules = [
+            rule("Fire", "keyword", "structure fire"),
+            rule("Codes", "regex", r"code \d"),
+            rule("Broken", "regex", "(unclosed"),
+            rule("Quiet", "keyword", "shots fired"),
+        ];
+        let matches = matching_rules(&rules, "Engine 4, Structure Fire, respond code 3");
+
+        let names: Vec<(&str, &str)> = matches.iter().map(|(r, t)| (r.name.as_str(), *t)).collect();
+        assert_eq!(names, [("Fire", "Structure Fire"), ("Codes", "code 3")]);
+    }
+}
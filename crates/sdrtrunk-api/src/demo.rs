@@ -0,0 +1,203 @@
+//! Demo mode: synthetic data and a mock transcriber
+//!
+//! Started with `sdrtrunk-api-server --demo`. Seeds the database with generated
+//! systems, talkgroups, and transcribed calls, and runs an in-process mock
+//! transcriber so uploads complete without a Whisper worker.
+
+use crate::progress::publish_progress;
+use sdrtrunk_protocol::{Config, config::TranscriptionConfig};
+use sdrtrunk_storage::{
+    JobQueue, JobResult, PgPool, ProgressStage,
+    demo::{DEMO_TRANSCRIPTS, DemoSeedSummary, seed_demo_data},
+    queries::{RadioCallQueries, TranscriptionUpdate},
+};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Command-line flag enabling demo mode
+pub const DEMO_FLAG: &str = "--demo";
+
+/// Worker ID used by the mock transcriber when claiming jobs
+pub const MOCK_WORKER_ID: &str = "demo-mock-transcriber";
+
+/// Calls generated per demo talkgroup
+const DEMO_CALLS_PER_TALKGROUP: usize = 25;
+
+/// Poll interval for the mock transcriber
+const MOCK_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Check whether demo mode was requested on the command line
+#[must_use]
+pub fn demo_requested<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref() == DEMO_FLAG)
+}
+
+/// Adjust configuration for demo mode
+///
+/// Enables transcription with the mock service so uploads are queued and
+/// picked up by [`spawn_mock_transcriber`].
+pub fn apply_demo_config(config: &mut Config) {
+    let transcription = config
+        .transcription
+        .get_or_insert_with(TranscriptionConfig::default);
+    transcription.enabled = true;
+    transcription.service = "mock".to_string();
+}
+
+/// Seed the database with demo data
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be seeded.
+pub async fn seed(pool: &PgPool) -> sdrtrunk_storage::Result<DemoSeedSummary> {
+    let summary = seed_demo_data(pool, DEMO_CALLS_PER_TALKGROUP).await?;
+    if summary.skipped {
+        info!("Demo data already present, skipping seed");
+    } else {
+        info!(
+            "Seeded {} demo systems with {} calls",
+            summary.systems, summary.calls
+        );
+    }
+    Ok(summary)
+}
+
+/// Build the canned result the mock transcriber reports for a call
+#[must_use]
+pub fn mock_result(call_id: Uuid) -> JobResult {
+    let index = usize::from(call_id.as_bytes()[0]) % DEMO_TRANSCRIPTS.len();
+    JobResult {
+        text: DEMO_TRANSCRIPTS.get(index).map(|t| (*t).to_string()),
+        confidence: Some(0.9),
+        language: Some("en".to_string()),
+        speaker_segments: None,
+        speaker_count: Some(1),
+        error: None,
+        processing_time_ms: 250,
+    }
+}
+
+/// Spawn the mock transcriber, which completes queued jobs with canned text
+#[must_use]
+pub fn spawn_mock_transcriber(pool: PgPool) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("Demo mock transcriber started");
+        loop {
+            match JobQueue::claim(&pool, MOCK_WORKER_ID).await {
+                Ok(Some(job)) => {
+                    publish_progress(
+                        &pool,
+                        job.call_id,
+                        Some(job.id),
+                        ProgressStage::Processing {
+                            worker_id: MOCK_WORKER_ID.to_string(),
+                        },
+                    )
+                    .await;
+                    complete_mock_job(&pool, job.id, job.call_id).await;
+                }
+                Ok(None) => tokio::time::sleep(MOCK_POLL_INTERVAL).await,
+                Err(e) => {
+                    warn!("Demo mock transcriber failed to claim job: {e}");
+                    tokio::time::sleep(MOCK_POLL_INTERVAL).await;
+                }
+            }
+        }
+    })
+}
+
+async fn complete_mock_job(pool: &PgPool, job_id: Uuid, call_id: Uuid) {
+    let result = mock_result(call_id);
+
+    if let Err(e) = JobQueue::complete(pool, job_id, &result).await {
+        error!("Demo mock transcriber failed to complete job {job_id}: {e}");
+        return;
+    }
+
+    let update = TranscriptionUpdate {
+        id: call_id,
+        status: "completed",
+        text: result.text.as_deref(),
+        confidence: result.confidence,
+        error: None,
+        speaker_segments: None,
+        speaker_count: result.speaker_count,
+        language: result.language.as_deref(),
+        raw_text: None,
+        text_encrypted: None,
+    };
+    if let Err(e) = RadioCallQueries::update_transcription_status(pool, update).await {
+        error!("Demo mock transcriber failed to update call {call_id}: {e}");
+        return;
+    }
+
+    let stage = ProgressStage::Completed {
+        text: result.text.clone(),
+        processing_time_ms: result.processing_time_ms,
+    };
+    publish_progress(pool, call_id, Some(job_id), stage).await;
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::field_reassign_with_default
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_requested() {
+        assert!(demo_requested(["sdrtrunk-api-server", "--demo"]));
+        assert!(!demo_requested(["sdrtrunk-api-server"]));
+        assert!(!demo_requested(["sdrtrunk-api-server", "--demo-mode"]));
+    }
+
+    #[test]
+    fn test_apply_demo_config() {
+        let mut config = Config::default();
+        config.transcription = None;
+        apply_demo_config(&mut config);
+
+        let transcription = config.transcription.unwrap();
+        assert!(transcription.enabled);
+        assert_eq!(transcription.service, "mock");
+    }
+
+    #[test]
+    fn test_apply_demo_config_keeps_existing_settings() {
+        let mut config = Config::default();
+        config.transcription = Some(TranscriptionConfig {
+            enabled: false,
+            timeout_seconds: 42,
+            ..TranscriptionConfig::default()
+        });
+        apply_demo_config(&mut config);
+
+        let transcription = config.transcription.unwrap();
+        assert!(transcription.enabled);
+        assert_eq!(transcription.timeout_seconds, 42);
+    }
+
+    #[test]
+    fn test_mock_result_is_canned_and_stable() {
+        let call_id = Uuid::new_v4();
+        let first = mock_result(call_id);
+        let second = mock_result(call_id);
+
+        let text = first.text.unwrap();
+        assert!(DEMO_TRANSCRIPTS.contains(&text.as_str()));
+        assert_eq!(second.text.as_deref(), Some(text.as_str()));
+        assert_eq!(first.language.as_deref(), Some("en"));
+    }
+}
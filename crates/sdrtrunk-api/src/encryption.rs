@@ -0,0 +1,427 @@
+//! Encryption of transcripts and talker aliases at rest
+//!
+//! With `encryption.enabled` set, the fields listed in `encryption.fields`
+//! are sealed with the configured key before they are stored, and opened
+//! again when a caller whose role meets `encryption.decrypt_role` reads a
+//! call. Other callers see the fields as missing. Encrypted transcripts take
+//! the text of their timed segments with them.
+//!
+//! Rotating the key is a two-step affair: make the new key current, with the
+//! old one in `previous_keys`, then run `sdrtrunk-api-server
+//! --rotate-encryption-key` or `POST /api/admin/encryption/rotate`. Both
+//! re-seal every value sealed with an older key, and seal values stored
+//! before their field was encrypted. Once they finish, the old key can be
+//! dropped.
+
+use anyhow::{Result, anyhow};
+use sdrtrunk_protocol::config::{EncryptedField, EncryptionConfig};
+use sdrtrunk_storage::models::RadioCallDb;
+use sdrtrunk_storage::{
+    CallToReseal, EncryptionQueries, KeyRing, PgPool, PlaintextFields, ResealedFields,
+    SegmentToReseal,
+};
+use sdrtrunk_types::UserRole;
+use serde::Serialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Command-line flag requesting a key rotation
+pub const ROTATE_FLAG: &str = "--rotate-encryption-key";
+
+/// Keys and policy for the encrypted call fields
+#[derive(Debug)]
+pub struct ColumnEncryption {
+    keys: KeyRing,
+    fields: PlaintextFields,
+    decrypt_role: UserRole,
+    batch_size: i64,
+}
+
+/// Counts from a key rotation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RotationReport {
+    /// Calls re-sealed with the current key
+    pub resealed: usize,
+    /// Transcript segments re-sealed with the current key
+    pub segments_resealed: usize,
+    /// Calls and segments that changed while they were being re-sealed,
+    /// left for the next run
+    pub skipped: usize,
+    /// Calls and segments with a value no key in the ring opens
+    pub failed: usize,
+}
+
+impl ColumnEncryption {
+    /// Encryption for `config`, or `None` when it is off
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no key is configured or a key is invalid.
+    pub fn from_config(config: &EncryptionConfig) -> sdrtrunk_storage::Result<Option<Self>> {
+        let Some(keys) = KeyRing::from_config(config)? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            keys,
+            fields: PlaintextFields {
+                transcription_text: config.encrypts(EncryptedField::TranscriptionText),
+                talker_alias: config.encrypts(EncryptedField::TalkerAlias),
+            },
+            decrypt_role: config.decrypt_role,
+            batch_size: i64::from(config.rotate_batch_size.max(1)),
+        }))
+    }
+
+    /// Whether `field` is stored sealed
+    #[must_use]
+    pub const fn encrypts(&self, field: EncryptedField) -> bool {
+        match field {
+            EncryptedField::TranscriptionText => self.fields.transcription_text,
+            EncryptedField::TalkerAlias => self.fields.talker_alias,
+        }
+    }
+
+    /// Whether callers with `role` see the sealed fields in the clear
+    #[must_use]
+    pub fn may_decrypt(&self, role: UserRole) -> bool {
+        role.allows(self.decrypt_role)
+    }
+
+    /// Keys sealing transcript segment text, when transcripts are encrypted
+    #[must_use]
+    pub const fn transcript_keys(&self) -> Option<&KeyRing> {
+        if self.fields.transcription_text {
+            Some(&self.keys)
+        } else {
+            None
+        }
+    }
+
+    /// Keys opening sealed transcript segments for a caller with `role`
+    #[must_use]
+    pub fn segment_keys(&self, role: UserRole) -> Option<&KeyRing> {
+        self.may_decrypt(role).then_some(&self.keys)
+    }
+
+    /// Seal a value with the current key
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sealing fails.
+    pub fn seal(&self, plaintext: &str) -> sdrtrunk_storage::Result<Vec<u8>> {
+        self.keys.seal(plaintext)
+    }
+
+    /// Fill in the sealed fields of `calls` for a caller with `role`
+    ///
+    /// Callers without the decrypt role, and values that fail to open, are
+    /// left with the fields missing.
+    pub async fn reveal(&self, pool: &PgPool, role: UserRole, calls: &mut [RadioCallDb]) {
+        if !self.may_decrypt(role) || calls.is_empty() {
+            return;
+        }
+        let ids: Vec<Uuid> = calls.iter().map(|call| call.id).collect();
+        let sealed = match EncryptionQueries::sealed_fields(pool, &ids).await {
+            Ok(sealed) => sealed,
+            Err(e) => {
+                warn!("Failed to load encrypted call fields: {e}");
+                return;
+            }
+        };
+        for fields in sealed {
+            let Some(call) = calls.iter_mut().find(|call| call.id == fields.id) else {
+                continue;
+            };
+            if let Some(text) = fields.transcription_text.as_deref() {
+                call.transcription_text = self.open(call.id, text);
+            }
+            if let Some(alias) = fields.talker_alias.as_deref() {
+                call.talker_alias = self.open(call.id, alias);
+            }
+        }
+    }
+
+    fn open(&self, call_id: Uuid, sealed: &[u8]) -> Option<String> {
+        self.keys
+            .open(sealed)
+            .inspect_err(|e| warn!("Failed to decrypt a field of call {call_id}: {e}"))
+            .ok()
+    }
+
+    /// Re-seal every value sealed with an older key, and seal plaintext
+    /// values of the encrypted fields
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn rotate(&self, pool: &PgPool) -> sdrtrunk_storage::Result<RotationReport> {
+        let key_id = self.keys.current_key_id();
+        let mut report = RotationReport::default();
+        let mut after = None;
+        loop {
+            let calls = EncryptionQueries::calls_to_reseal(
+                pool,
+                &key_id,
+                self.fields,
+                after,
+                self.batch_size,
+            )
+            .await?;
+            let Some(last) = calls.last() else {
+                break;
+            };
+            after = Some(last.id);
+
+            for call in &calls {
+                let (text, alias) = match self.reseal_values(call) {
+                    Ok(values) => values,
+                    Err(e) => {
+                        warn!("Failed to re-seal call {}: {e}", call.id);
+                        report.failed += 1;
+                        continue;
+                    }
+                };
+                let fields = ResealedFields {
+                    transcription_text: text.as_deref(),
+                    talker_alias: alias.as_deref(),
+                };
+                if EncryptionQueries::reseal(pool, call, fields).await? {
+                    report.resealed += 1;
+                } else {
+                    report.skipped += 1;
+                }
+            }
+            info!("Re-sealed {} calls so far", report.resealed);
+        }
+        self.rotate_segments(pool, &mut report).await?;
+        Ok(report)
+    }
+
+    /// Re-seal transcript segments sealed with an older key, and seal
+    /// plaintext ones when transcripts are encrypted
+    async fn rotate_segments(
+        &self,
+        pool: &PgPool,
+        report: &mut RotationReport,
+    ) -> sdrtrunk_storage::Result<()> {
+        let key_id = self.keys.current_key_id();
+        let mut after = None;
+        loop {
+            let segments = EncryptionQueries::segments_to_reseal(
+                pool,
+                &key_id,
+                self.fields.transcription_text,
+                after,
+                self.batch_size,
+            )
+            .await?;
+            let Some(last) = segments.last() else {
+                break;
+            };
+            after = Some((last.call_id, last.segment_index));
+
+            for segment in &segments {
+                let sealed = match self.reseal_segment_text(segment) {
+                    Ok(sealed) => sealed,
+                    Err(e) => {
+                        warn!(
+                            "Failed to re-seal segment {} of call {}: {e}",
+                            segment.segment_index, segment.call_id
+                        );
+                        report.failed += 1;
+                        continue;
+                    }
+                };
+                if EncryptionQueries::reseal_segment(pool, segment, &sealed).await? {
+                    report.segments_resealed += 1;
+                } else {
+                    report.skipped += 1;
+                }
+            }
+            info!(
+                "Re-sealed {} transcript segments so far",
+                report.segments_resealed
+            );
+        }
+        Ok(())
+    }
+
+    /// Text of `segment` sealed with the current key
+    fn reseal_segment_text(&self, segment: &SegmentToReseal) -> sdrtrunk_storage::Result<Vec<u8>> {
+        let text = match segment.text_encrypted.as_deref() {
+            Some(sealed) => self.keys.open(sealed)?,
+            None => segment.text.clone().unwrap_or_default(),
+        };
+        self.keys.seal(&text)
+    }
+
+    /// New sealed transcript and talker alias for `call`, `None` where a
+    /// field is left as it is
+    fn reseal_values(
+        &self,
+        call: &CallToReseal,
+    ) -> sdrtrunk_storage::Result<(Option<Vec<u8>>, Option<Vec<u8>>)> {
+        let text = self.reseal_value(
+            call.transcription_text_encrypted.as_deref(),
+            call.transcription_text
+                .as_deref()
+                .filter(|_| self.fields.transcription_text),
+        )?;
+        let alias = self.reseal_value(
+            call.talker_alias_encrypted.as_deref(),
+            call.talker_alias
+                .as_deref()
+                .filter(|_| self.fields.talker_alias),
+        )?;
+        Ok((text, alias))
+    }
+
+    /// `sealed` re-sealed, or `plaintext` sealed, with the current key
+    fn reseal_value(
+        &self,
+        sealed: Option<&[u8]>,
+        plaintext: Option<&str>,
+    ) -> sdrtrunk_storage::Result<Option<Vec<u8>>> {
+        let value = match (sealed, plaintext) {
+            (Some(sealed), _) if sealed.starts_with(&self.keys.current_key_id()) => {
+                return Ok(None);
+            }
+            (Some(sealed), _) => self.keys.open(sealed)?,
+            (None, Some(plaintext)) => plaintext.to_string(),
+            (None, None) => return Ok(None),
+        };
+        self.keys.seal(&value).map(Some)
+    }
+}
+
+/// Check whether a key rotation was requested on the command line
+#[must_use]
+pub fn rotate_requested<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref() == ROTATE_FLAG)
+}
+
+/// Re-seal the encrypted fields of every call with the current key
+///
+/// # Errors
+///
+/// Returns an error if encryption is off or misconfigured, or a database
+/// query fails.
+pub async fn run_rotation(config: &EncryptionConfig, pool: &PgPool) -> Result<RotationReport> {
+    let encryption = ColumnEncryption::from_config(config)?
+        .ok_or_else(|| anyhow!("encryption.enabled is not set"))?;
+    info!("Re-sealing encrypted call fields with the current key");
+    let report = encryption.rotate(pool).await?;
+    info!(
+        "Key rotation finished: {} calls and {} segments re-sealed, {} skipped, {} failed",
+        report.resealed, report.segments_resealed, report.skipped, report.failed
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+
+    fn encryption(fields: Vec<EncryptedField>) -> ColumnEncryption {
+        ColumnEncryption::from_config(&EncryptionConfig {
+            enabled: true,
+            key: Some(STANDARD.encode([5u8; 32])),
+            fields,
+            ..EncryptionConfig::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn call(text: Option<&str>, alias: Option<&str>) -> CallToReseal {
+        CallToReseal {
+            id: Uuid::new_v4(),
+            transcription_text: text.map(str::to_string),
+            transcription_text_encrypted: None,
+            talker_alias: alias.map(str::to_string),
+            talker_alias_encrypted: None,
+        }
+    }
+
+    #[test]
+    fn test_rotate_requested() {
+        assert!(rotate_requested([
+            "sdrtrunk-api-server",
+            "--rotate-encryption-key"
+        ]));
+        assert!(!rotate_requested(["sdrtrunk-api-server", "--demo"]));
+    }
+
+    #[test]
+    fn test_decrypt_role() {
+        let encryption = encryption(vec![EncryptedField::TranscriptionText]);
+        assert!(encryption.may_decrypt(UserRole::Admin));
+        assert!(encryption.may_decrypt(UserRole::Analyst));
+        assert!(!encryption.may_decrypt(UserRole::ReadOnly));
+    }
+
+    #[test]
+    fn test_reseal_values_seals_encrypted_fields_only() {
+        let encryption = encryption(vec![EncryptedField::TalkerAlias]);
+        assert!(!encryption.encrypts(EncryptedField::TranscriptionText));
+        let (text, alias) = encryption
+            .reseal_values(&call(Some("engine 5 on scene"), Some("ENGINE 5")))
+            .unwrap();
+        assert!(text.is_none());
+        assert_eq!(encryption.keys.open(&alias.unwrap()).unwrap(), "ENGINE 5");
+    }
+
+    #[test]
+    fn test_transcript_keys() {
+        let alias_only = encryption(vec![EncryptedField::TalkerAlias]);
+        assert!(alias_only.transcript_keys().is_none());
+        assert!(alias_only.segment_keys(UserRole::Admin).is_some());
+        assert!(alias_only.segment_keys(UserRole::ReadOnly).is_none());
+
+        let transcripts = encryption(vec![EncryptedField::TranscriptionText]);
+        assert!(transcripts.transcript_keys().is_some());
+    }
+
+    #[test]
+    fn test_reseal_segment_text() {
+        let encryption = encryption(vec![EncryptedField::TranscriptionText]);
+        let mut segment = SegmentToReseal {
+            call_id: Uuid::new_v4(),
+            segment_index: 0,
+            text: Some("engine 5 on scene".to_string()),
+            text_encrypted: None,
+        };
+        let sealed = encryption.reseal_segment_text(&segment).unwrap();
+        assert_eq!(encryption.keys.open(&sealed).unwrap(), "engine 5 on scene");
+
+        segment.text = None;
+        segment.text_encrypted = Some(vec![0; 40]);
+        assert!(encryption.reseal_segment_text(&segment).is_err());
+    }
+
+    #[test]
+    fn test_reseal_values_skips_current_key() {
+        let encryption = encryption(vec![EncryptedField::TalkerAlias]);
+        let mut call = call(None, None);
+        call.talker_alias_encrypted = Some(encryption.seal("ENGINE 5").unwrap());
+        assert_eq!(encryption.reseal_values(&call).unwrap(), (None, None));
+
+        // Values no key opens are reported, not dropped
+        call.talker_alias_encrypted = Some(vec![0; 40]);
+        assert!(encryption.reseal_values(&call).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Errors returned by the API
+//!
+//! Handlers, middleware, and extractors all fail with [`ApiError`]. Each
+//! variant fixes the HTTP status, and every error carries a stable
+//! machine-readable code (e.g. `CALL_NOT_FOUND`, `DATABASE_ERROR`) that
+//! clients can match on instead of parsing the message. The response body is
+//! always an [`ErrorResponse`]:
+//!
+//! ```json
+//! { "success": false, "error": "Call 550e... not found", "code": "CALL_NOT_FOUND" }
+//! ```
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// An API failure with its status and stable error code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// The request is malformed or fails validation (400)
+    BadRequest {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+        /// What failed validation, when known
+        details: Option<serde_json::Value>,
+    },
+    /// No usable credentials were presented (401)
+    Unauthorized {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+    /// The caller may not do this (403)
+    Forbidden {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+    /// The resource does not exist or is outside the caller's systems (404)
+    NotFound {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+    /// The request conflicts with what is already stored (409)
+    Conflict {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+    /// The caller is over its rate limit (429)
+    TooManyRequests {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+    /// The server failed to handle a valid request (500)
+    Internal {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+    /// The server cannot take the request right now (503)
+    ServiceUnavailable {
+        /// Stable error code
+        code: &'static str,
+        /// Human-readable message
+        message: String,
+    },
+}
+
+impl ApiError {
+    /// A 400 error
+    pub fn bad_request(code: &'static str, message: impl Into<String>) -> Self {
+        Self::BadRequest {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// A 401 error
+    pub fn unauthorized(code: &'static str, message: impl Into<String>) -> Self {
+        Self::Unauthorized {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 403 error
+    pub fn forbidden(code: &'static str, message: impl Into<String>) -> Self {
+        Self::Forbidden {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 404 error
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        Self::NotFound {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 409 error
+    pub fn conflict(code: &'static str, message: impl Into<String>) -> Self {
+        Self::Conflict {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 429 error
+    pub fn too_many_requests(code: &'static str, message: impl Into<String>) -> Self {
+        Self::TooManyRequests {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 500 error
+    pub fn internal(code: &'static str, message: impl Into<String>) -> Self {
+        Self::Internal {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 503 error
+    pub fn service_unavailable(code: &'static str, message: impl Into<String>) -> Self {
+        Self::ServiceUnavailable {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A 500 error for a failed database query
+    pub fn database(message: impl Into<String>) -> Self {
+        Self::internal("DATABASE_ERROR", message)
+    }
+
+    /// Attach validation details to a bad request
+    ///
+    /// Other errors are returned unchanged.
+    #[must_use]
+    pub fn with_details(self, details: serde_json::Value) -> Self {
+        match self {
+            Self::BadRequest { code, message, .. } => Self::BadRequest {
+                code,
+                message,
+                details: Some(details),
+            },
+            other => other,
+        }
+    }
+
+    /// HTTP status of the error
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            Self::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            Self::Forbidden { .. } => StatusCode::FORBIDDEN,
+            Self::NotFound { .. } => StatusCode::NOT_FOUND,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// Stable machine-readable error code
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest { code, .. }
+            | Self::Unauthorized { code, .. }
+            | Self::Forbidden { code, .. }
+            | Self::NotFound { code, .. }
+            | Self::Conflict { code, .. }
+            | Self::TooManyRequests { code, .. }
+            | Self::Internal { code, .. }
+            | Self::ServiceUnavailable { code, .. } => code,
+        }
+    }
+
+    /// Human-readable message
+    #[must_use]
+    pub fn message(&self) -> &str {
+        match self {
+            Self::BadRequest { message, .. }
+            | Self::Unauthorized { message, .. }
+            | Self::Forbidden { message, .. }
+            | Self::NotFound { message, .. }
+            | Self::Conflict { message, .. }
+            | Self::TooManyRequests { message, .. }
+            | Self::Internal { message, .. }
+            | Self::ServiceUnavailable { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Body of every error response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Always false
+    pub success: bool,
+    /// Human-readable message
+    pub error: String,
+    /// Stable machine-readable error code
+    pub code: String,
+    /// What failed validation, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl From<ApiError> for ErrorResponse {
+    fn from(error: ApiError) -> Self {
+        let code = error.code().to_string();
+        let (error, details) = match error {
+            ApiError::BadRequest {
+                message, details, ..
+            } => (message, details),
+            ApiError::Unauthorized { message, .. }
+            | ApiError::Forbidden { message, .. }
+            | ApiError::NotFound { message, .. }
+            | ApiError::Conflict { message, .. }
+            | ApiError::TooManyRequests { message, .. }
+            | ApiError::Internal { message, .. }
+            | ApiError::ServiceUnavailable { message, .. } => (message, None),
+        };
+        Self {
+            success: false,
+            error,
+            code,
+            details,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(ErrorResponse::from(self))).into_response()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_and_code() {
+        let cases = [
+            (
+                ApiError::bad_request("BAD_REQUEST", "x"),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                ApiError::unauthorized("UNAUTHORIZED", "x"),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (ApiError::forbidden("FORBIDDEN", "x"), StatusCode::FORBIDDEN),
+            (
+                ApiError::not_found("CALL_NOT_FOUND", "x"),
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                ApiError::conflict("DUPLICATE_CALL", "x"),
+                StatusCode::CONFLICT,
+            ),
+            (
+                ApiError::too_many_requests("RATE_LIMITED", "x"),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (ApiError::database("x"), StatusCode::INTERNAL_SERVER_ERROR),
+            (
+                ApiError::service_unavailable("QUEUE_FULL", "x"),
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+        ];
+        for (error, status) in cases {
+            assert_eq!(error.status(), status, "{error}");
+        }
+        assert_eq!(ApiError::database("x").code(), "DATABASE_ERROR");
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ApiError::not_found("CALL_NOT_FOUND", "Call 1 not found");
+        assert_eq!(error.to_string(), "CALL_NOT_FOUND: Call 1 not found");
+        assert_eq!(error.message(), "Call 1 not found");
+    }
+
+    #[test]
+    fn test_details_only_on_bad_request() {
+        let details = serde_json::json!({"limit": "too large"});
+        let body = ErrorResponse::from(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(details.clone()),
+        );
+        assert_eq!(body.details, Some(details.clone()));
+
+        let body = ErrorResponse::from(ApiError::database("Failed").with_details(details));
+        assert!(body.details.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_into_response() {
+        let response = ApiError::forbidden("INSUFFICIENT_ROLE", "Admins only").into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "success": false,
+                "error": "Admins only",
+                "code": "INSUFFICIENT_ROLE",
+            })
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! API key extractor for authenticated requests
 
-use crate::extractors::ExtractorError;
+use crate::error::ApiError;
 use axum::{
     async_trait,
     extract::{FromRequestParts, Request},
@@ -20,7 +20,7 @@ impl<S> FromRequestParts<S> for ApiKeyInfo
 where
     S: Send + Sync,
 {
-    type Rejection = ExtractorError;
+    type Rejection = ApiError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         // Get API key from request extensions (added by auth middleware)
@@ -29,7 +29,7 @@ where
             .get::<ApiKeyDb>()
             .cloned()
             .ok_or_else(|| {
-                ExtractorError::unauthorized("Valid API key required")
+                ApiError::unauthorized("UNAUTHORIZED", "Valid API key required")
             })?;
         
         Ok(ApiKeyInfo { api_key })
@@ -124,8 +124,8 @@ impl OptionalApiKeyInfo {
     }
     
     /// Convert to required API key info, returning error if not present
-    pub fn require(self) -> Result<ApiKeyInfo, ExtractorError> {
-        self.0.ok_or_else(|| ExtractorError::unauthorized("API key required"))
+    pub fn require(self) -> Result<ApiKeyInfo, ApiError> {
+        self.0.ok_or_else(|| ApiError::unauthorized("UNAUTHORIZED", "API key required"))
     }
 }
 
@@ -163,14 +163,13 @@ impl ApiKeyPermissions {
     }
     
     /// Check if all permissions are satisfied
-    pub fn check(&self) -> Result<(), ExtractorError> {
+    pub fn check(&self) -> Result<(), ApiError> {
         // Check system access if required
         if let Some(required_system) = &self.required_system {
             if !self.api_key.can_access_system(required_system) {
-                return Err(ExtractorError::new(
-                    format!("API key does not have access to system: {}", required_system),
-                    axum::http::StatusCode::FORBIDDEN,
+                return Err(ApiError::forbidden(
                     "INSUFFICIENT_SYSTEM_ACCESS",
+                    format!("API key does not have access to system: {}", required_system),
                 ));
             }
         }
@@ -178,21 +177,19 @@ impl ApiKeyPermissions {
         // Check IP access if client IP is provided
         if let Some(client_ip) = &self.client_ip {
             if !self.api_key.can_access_from_ip(client_ip) {
-                return Err(ExtractorError::new(
-                    format!("API key does not allow access from IP: {}", client_ip),
-                    axum::http::StatusCode::FORBIDDEN,
+                return Err(ApiError::forbidden(
                     "IP_ACCESS_DENIED",
+                    format!("API key does not allow access from IP: {}", client_ip),
                 ));
             }
         }
         
         // Check if key is expired
         if self.api_key.is_expired() {
-            return Err(ExtractorError::new(
-                "API key has expired",
-                axum::http::StatusCode::UNAUTHORIZED,
-                "API_KEY_EXPIRED",
-            ));
+            return Err(ApiError::unauthorized(
+                    "API_KEY_EXPIRED",
+                    "API key has expired",
+                ));
         }
         
         Ok(())
@@ -448,8 +445,8 @@ mod tests {
         assert!(result.is_err());
         
         let error = result.unwrap_err();
-        assert_eq!(error.status_code, axum::http::StatusCode::FORBIDDEN);
-        assert!(error.message.contains("IP"));
+        assert_eq!(error.status(), axum::http::StatusCode::FORBIDDEN);
+        assert!(error.message().contains("IP"));
     }
 
     #[test]
@@ -1,6 +1,6 @@
 //! Pagination extractor for query parameters
 
-use crate::extractors::ExtractorError;
+use crate::error::ApiError;
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -98,7 +98,7 @@ impl<S> FromRequestParts<S> for Pagination
 where
     S: Send + Sync,
 {
-    type Rejection = ExtractorError;
+    type Rejection = ApiError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         let query = parts
@@ -124,24 +124,21 @@ where
                 "page" => {
                     page = value.parse::<u32>().ok();
                     if page.is_none() && !value.is_empty() {
-                        return Err(ExtractorError::bad_request(
-                            format!("Invalid page value: {}", value)
+                        return Err(ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid page value: {}", value)
                         ));
                     }
                 }
                 "limit" => {
                     limit = value.parse::<u32>().ok();
                     if limit.is_none() && !value.is_empty() {
-                        return Err(ExtractorError::bad_request(
-                            format!("Invalid limit value: {}", value)
+                        return Err(ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid limit value: {}", value)
                         ));
                     }
                 }
                 "offset" => {
                     offset = value.parse::<u32>().ok();
                     if offset.is_none() && !value.is_empty() {
-                        return Err(ExtractorError::bad_request(
-                            format!("Invalid offset value: {}", value)
+                        return Err(ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid offset value: {}", value)
                         ));
                     }
                 }
@@ -167,7 +164,7 @@ where
 
         // Validate pagination parameters
         if let Err(validation_errors) = pagination.validate() {
-            return Err(ExtractorError::bad_request(format!(
+            return Err(ApiError::bad_request("INVALID_PARAMETERS", format!(
                 "Invalid pagination parameters: {:?}",
                 validation_errors
             )));
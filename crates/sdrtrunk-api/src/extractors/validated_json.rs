@@ -1,6 +1,6 @@
 //! Validated JSON extractor with comprehensive validation
 
-use crate::extractors::ExtractorError;
+use crate::error::ApiError;
 use axum::{
     async_trait,
     extract::{FromRequest, Request},
@@ -19,20 +19,20 @@ where
     T: DeserializeOwned + Validate + Send,
     S: Send + Sync,
 {
-    type Rejection = ExtractorError;
+    type Rejection = ApiError;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
         // First extract JSON
         let Json(data) = Json::<T>::from_request(req, state)
             .await
             .map_err(|err| {
-                ExtractorError::bad_request(format!("Invalid JSON: {}", err))
+                ApiError::bad_request("INVALID_REQUEST", format!("Invalid JSON: {}", err))
             })?;
         
         // Then validate the data
         data.validate()
             .map_err(|validation_errors| {
-                ExtractorError::bad_request(format!("Validation failed: {:?}", validation_errors))
+                ApiError::bad_request("INVALID_REQUEST", format!("Validation failed: {:?}", validation_errors))
             })?;
         
         Ok(ValidatedJson(data))
@@ -67,7 +67,7 @@ where
     T: DeserializeOwned + Validate + Send,
     S: Send + Sync,
 {
-    type Rejection = ExtractorError;
+    type Rejection = ApiError;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
         // Extract JSON with detailed error information
@@ -79,9 +79,7 @@ where
                     "error_type": "deserialization_failed"
                 });
                 
-                let mut error = ExtractorError::bad_request("Invalid JSON format");
-                // Note: We would need to modify ExtractorError to support details
-                error
+                ApiError::bad_request("INVALID_REQUEST", "Invalid JSON format").with_details(details)
             })?;
         
         // Validate with detailed error reporting
@@ -96,9 +94,7 @@ where
                     "error_type": "validation_failed"
                 });
                 
-                Err(ExtractorError::bad_request(
-                    format!("Validation failed: {:?}", validation_errors)
-                ))
+                Err(ApiError::bad_request("INVALID_REQUEST", "Validation failed").with_details(details))
             }
         }
     }
@@ -0,0 +1,259 @@
+//! Runtime feature flags for experimental endpoints
+
+use crate::error::ApiError;
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use sdrtrunk_protocol::config::FeaturesConfig;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `GraphQL` endpoint flag
+pub const GRAPHQL: &str = "graphql";
+
+/// Live audio listening flag
+pub const LIVE_LISTEN: &str = "live_listen";
+
+/// Call summarization flag
+pub const SUMMARIZATION: &str = "summarization";
+
+/// Feature flag state combining configured defaults with admin overrides
+///
+/// Overrides live in memory only and are lost on restart, at which point the
+/// configured values apply again.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureFlags {
+    config: FeaturesConfig,
+    overrides: Arc<DashMap<String, bool>>,
+}
+
+/// Current state of a single feature flag
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FeatureState {
+    /// Flag name
+    pub name: String,
+    /// Effective value
+    pub enabled: bool,
+    /// Value from configuration
+    pub configured: bool,
+    /// Admin override, if set
+    pub override_value: Option<bool>,
+}
+
+impl FeatureFlags {
+    /// Create feature flags from configuration
+    #[must_use]
+    pub fn new(config: FeaturesConfig) -> Self {
+        Self {
+            config,
+            overrides: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Check whether a flag is enabled
+    ///
+    /// Unknown flags are always disabled.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.overrides
+            .get(name)
+            .map_or_else(|| self.config.get(name).unwrap_or(false), |v| *v)
+    }
+
+    /// Override a flag at runtime
+    ///
+    /// Returns `false` if the flag is not known.
+    #[must_use]
+    pub fn set_override(&self, name: &str, enabled: bool) -> bool {
+        if self.config.get(name).is_none() {
+            return false;
+        }
+        let _ = self.overrides.insert(name.to_string(), enabled);
+        true
+    }
+
+    /// Remove a runtime override, returning the previous override value
+    #[must_use]
+    pub fn clear_override(&self, name: &str) -> Option<bool> {
+        self.overrides.remove(name).map(|(_, v)| v)
+    }
+
+    /// Get the state of a single flag, or `None` if it is not known
+    #[must_use]
+    pub fn state(&self, name: &str) -> Option<FeatureState> {
+        let configured = self.config.get(name)?;
+        let override_value = self.overrides.get(name).map(|v| *v);
+        Some(FeatureState {
+            name: name.to_string(),
+            enabled: override_value.unwrap_or(configured),
+            configured,
+            override_value,
+        })
+    }
+
+    /// Get the state of all known flags
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<FeatureState> {
+        FeaturesConfig::NAMES
+            .iter()
+            .filter_map(|name| self.state(name))
+            .collect()
+    }
+
+    /// Reject the request unless the flag is enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FeatureDisabled`] if the flag is disabled or unknown.
+    pub fn require(&self, name: &str) -> Result<(), FeatureDisabled> {
+        if self.is_enabled(name) {
+            Ok(())
+        } else {
+            Err(FeatureDisabled {
+                feature: name.to_string(),
+            })
+        }
+    }
+}
+
+/// Error returned when a gated endpoint is called with its flag disabled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureDisabled {
+    /// Name of the disabled flag
+    pub feature: String,
+}
+
+impl From<FeatureDisabled> for ApiError {
+    fn from(disabled: FeatureDisabled) -> Self {
+        Self::not_found(
+            "FEATURE_DISABLED",
+            format!("Feature '{}' is not enabled", disabled.feature),
+        )
+    }
+}
+
+impl IntoResponse for FeatureDisabled {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::cognitive_complexity,
+    clippy::too_many_lines,
+    clippy::unreadable_literal,
+    clippy::redundant_clone,
+    clippy::missing_panics_doc,
+    clippy::missing_errors_doc,
+    clippy::needless_pass_by_value,
+    clippy::uninlined_format_args,
+    unused_qualifications,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap,
+    clippy::items_after_statements,
+    clippy::float_cmp,
+    clippy::redundant_closure_for_method_calls,
+    clippy::fn_params_excessive_bools,
+    clippy::similar_names,
+    clippy::map_unwrap_or,
+    clippy::unused_async,
+    clippy::case_sensitive_file_extension_comparisons,
+    clippy::manual_string_new,
+    clippy::no_effect_underscore_binding,
+    clippy::option_if_let_else,
+    clippy::single_char_pattern,
+    clippy::ip_constant,
+    clippy::or_fun_call,
+    clippy::cast_lossless,
+    clippy::needless_collect,
+    clippy::single_match_else,
+    clippy::needless_raw_string_hashes,
+    clippy::match_same_arms
+)]
+mod tests {
+    use super::*;
+
+    fn flags() -> FeatureFlags {
+        FeatureFlags::new(FeaturesConfig {
+            graphql: true,
+            live_listen: false,
+            summarization: false,
+        })
+    }
+
+    #[test]
+    fn test_configured_values() {
+        let flags = flags();
+        assert!(flags.is_enabled(GRAPHQL));
+        assert!(!flags.is_enabled(LIVE_LISTEN));
+        assert!(!flags.is_enabled(SUMMARIZATION));
+        assert!(!flags.is_enabled("unknown"));
+    }
+
+    #[test]
+    fn test_override_and_clear() {
+        let flags = flags();
+
+        assert!(flags.set_override(LIVE_LISTEN, true));
+        assert!(flags.is_enabled(LIVE_LISTEN));
+        assert!(flags.set_override(GRAPHQL, false));
+        assert!(!flags.is_enabled(GRAPHQL));
+
+        assert_eq!(flags.clear_override(GRAPHQL), Some(false));
+        assert!(flags.is_enabled(GRAPHQL));
+        assert_eq!(flags.clear_override(GRAPHQL), None);
+    }
+
+    #[test]
+    fn test_unknown_flag_override_rejected() {
+        let flags = flags();
+        assert!(!flags.set_override("unknown", true));
+        assert!(!flags.is_enabled("unknown"));
+        assert!(flags.state("unknown").is_none());
+    }
+
+    #[test]
+    fn test_overrides_shared_between_clones() {
+        let flags = flags();
+        let clone = flags.clone();
+        assert!(clone.set_override(SUMMARIZATION, true));
+        assert!(flags.is_enabled(SUMMARIZATION));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let flags = flags();
+        assert!(flags.set_override(LIVE_LISTEN, true));
+
+        let snapshot = flags.snapshot();
+        assert_eq!(snapshot.len(), FeaturesConfig::NAMES.len());
+
+        let live = snapshot.iter().find(|s| s.name == LIVE_LISTEN).unwrap();
+        assert!(live.enabled);
+        assert!(!live.configured);
+        assert_eq!(live.override_value, Some(true));
+
+        let graphql = snapshot.iter().find(|s| s.name == GRAPHQL).unwrap();
+        assert!(graphql.enabled);
+        assert_eq!(graphql.override_value, None);
+    }
+
+    #[test]
+    fn test_require() {
+        let flags = flags();
+        assert!(flags.require(GRAPHQL).is_ok());
+
+        let err = flags.require(SUMMARIZATION).unwrap_err();
+        assert_eq!(err.feature, SUMMARIZATION);
+        assert_eq!(
+            err.into_response().status(),
+            axum::http::StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! Acoustic fingerprints for spotting simulcast echoes
+//!
+//! Recordings are decoded like waveforms (8 kHz mono) and cut into
+//! overlapping [`FRAME_LEN`]-sample frames every [`HOP_LEN`] samples. Each
+//! frame's energy is measured in 33 log-spaced bands across the voice band,
+//! and each frame gets a 32-bit sub-fingerprint whose bit `m` says whether the
+//! energy difference between bands `m` and `m + 1` grew since the previous
+//! frame (after Haitsma and Kalker). The bits follow the spectrum's shape
+//! rather than its level, so the same transmission recorded by two receivers
+//! matches even after different gain and encoding; unrelated audio differs in
+//! about half the bits.
+//!
+//! The upload handler fingerprints every new call in the background and
+//! compares it with calls that started within
+//! [`window_seconds`](sdrtrunk_protocol::config::FingerprintConfig::window_seconds) of
+//! it. The closest match under the configured bit error rate is stored as the
+//! call's `echo_of`.
+
+use crate::handlers::calls::TranscodeInput;
+use crate::waveform::{self, DECODE_SAMPLE_RATE};
+use anyhow::{Context, Result};
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::config::FingerprintConfig;
+use sdrtrunk_storage::{FingerprintCandidate, FingerprintQueries, PgPool};
+use std::f32::consts::PI;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Samples per analysis frame (64 ms at 8 kHz)
+pub const FRAME_LEN: usize = 512;
+
+/// Samples between frame starts (32 ms at 8 kHz)
+pub const HOP_LEN: usize = 256;
+
+/// Energy bands per frame; adjacent pairs give the 32 bits
+const BAND_COUNT: usize = 33;
+
+/// Lowest frequency covered, in Hz
+const LOW_HZ: f32 = 300.0;
+
+/// Highest frequency covered, in Hz
+const HIGH_HZ: f32 = 3400.0;
+
+/// Recordings whose loudest sample is below this are treated as silence
+const SILENCE_PEAK: i16 = 64;
+
+/// Fewest overlapping frames (about half a second) for a comparison to count
+const MIN_OVERLAP_FRAMES: usize = 16;
+
+/// Largest start offset, in frames (about two seconds), tried when aligning
+/// two fingerprints
+const MAX_OFFSET_FRAMES: usize = 64;
+
+/// How closely two fingerprints match at their best alignment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FingerprintMatch {
+    /// Share of differing bits over the overlap, from 0.0 to 1.0
+    pub bit_error_rate: f32,
+    /// Frames the second recording starts after the first (negative when
+    /// before)
+    pub offset_frames: i32,
+}
+
+impl FingerprintMatch {
+    /// Start offset in seconds at the decode sample rate
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn offset_seconds(&self) -> f64 {
+        f64::from(self.offset_frames) * HOP_LEN as f64 / f64::from(DECODE_SAMPLE_RATE)
+    }
+}
+
+/// Fingerprint little-endian 16-bit mono PCM
+///
+/// Returns one sub-fingerprint per frame after the first, or nothing for
+/// silence and recordings shorter than two frames.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn from_pcm(pcm: &[u8], sample_rate: u32) -> Vec<u32> {
+    let samples: Vec<i16> = pcm
+        .chunks_exact(2)
+        .filter_map(|pair| pair.try_into().ok())
+        .map(i16::from_le_bytes)
+        .collect();
+    let loudest = samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+    if samples.len() < FRAME_LEN + HOP_LEN || loudest < SILENCE_PEAK.unsigned_abs() {
+        return Vec::new();
+    }
+
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| 0.5_f32.mul_add(-(2.0 * PI * i as f32 / (FRAME_LEN - 1) as f32).cos(), 0.5))
+        .collect();
+    // DFT bins summed into each band, at least one per band
+    let bin_hz = sample_rate.max(1) as f32 / FRAME_LEN as f32;
+    let edge = |band: usize| {
+        let hz = LOW_HZ * (HIGH_HZ / LOW_HZ).powf(band as f32 / BAND_COUNT as f32);
+        (hz / bin_hz).round() as usize
+    };
+    let bands: Vec<(usize, usize)> = (0..BAND_COUNT)
+        .map(|band| {
+            let low = edge(band);
+            (low, edge(band + 1).max(low + 1))
+        })
+        .collect();
+
+    let mut frame = vec![0.0_f32; FRAME_LEN];
+    // Energy difference between each pair of adjacent bands, per frame
+    let band_deltas: Vec<Vec<f32>> = samples
+        .windows(FRAME_LEN)
+        .step_by(HOP_LEN)
+        .map(|frame_samples| {
+            for ((out, &sample), w) in frame.iter_mut().zip(frame_samples).zip(&window) {
+                *out = f32::from(sample) * w;
+            }
+            let energy: Vec<f32> = bands
+                .iter()
+                .map(|&(low, high)| (low..high).map(|bin| bin_power(&frame, bin)).sum())
+                .collect();
+            energy
+                .iter()
+                .zip(energy.iter().skip(1))
+                .map(|(lower, upper)| lower - upper)
+                .collect()
+        })
+        .collect();
+
+    band_deltas
+        .iter()
+        .zip(band_deltas.iter().skip(1))
+        .map(|(previous, current)| {
+            current
+                .iter()
+                .zip(previous)
+                .enumerate()
+                .filter(|(_, (now, before))| *now - *before > 0.0)
+                .fold(0_u32, |bits, (m, _)| bits | (1 << m))
+        })
+        .collect()
+}
+
+/// Power of DFT bin `bin` of `frame` (Goertzel)
+#[allow(clippy::cast_precision_loss)]
+fn bin_power(frame: &[f32], bin: usize) -> f32 {
+    let coeff = 2.0 * (2.0 * PI * bin as f32 / frame.len() as f32).cos();
+    let (s1, s2) = frame.iter().fold((0.0_f32, 0.0_f32), |(s1, s2), &x| {
+        (coeff.mul_add(s1, x) - s2, s1)
+    });
+    coeff.mul_add(-s1 * s2, s1.mul_add(s1, s2 * s2))
+}
+
+/// Best alignment of two fingerprints within [`MAX_OFFSET_FRAMES`]
+///
+/// Only alignments overlapping at least [`MIN_OVERLAP_FRAMES`] frames and
+/// half the shorter fingerprint are considered; `None` if there are none.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn compare(first: &[u32], second: &[u32]) -> Option<FingerprintMatch> {
+    let shortest = first.len().min(second.len());
+    let min_overlap = MIN_OVERLAP_FRAMES.max(shortest.div_ceil(2));
+    let max_offset = i32::try_from(MAX_OFFSET_FRAMES).unwrap_or(i32::MAX);
+
+    (-max_offset..=max_offset)
+        .filter_map(|offset| {
+            // Frame i of `first` lines up with frame i - offset of `second`
+            let skip = usize::try_from(offset.unsigned_abs()).ok()?;
+            let (ours, theirs) = if offset >= 0 {
+                (first.get(skip..)?, second)
+            } else {
+                (first, second.get(skip..)?)
+            };
+            let overlap = ours.len().min(theirs.len());
+            if overlap < min_overlap {
+                return None;
+            }
+            let errors: u32 = ours
+                .iter()
+                .zip(theirs)
+                .map(|(x, y)| (x ^ y).count_ones())
+                .sum();
+            Some(FingerprintMatch {
+                bit_error_rate: errors as f32 / (32 * overlap) as f32,
+                offset_frames: -offset,
+            })
+        })
+        .min_by(|x, y| x.bit_error_rate.total_cmp(&y.bit_error_rate))
+}
+
+/// Candidates whose fingerprint matches `fingerprint` with at most
+/// `max_bit_error_rate` differing bits, closest first
+#[must_use]
+pub fn near_duplicates(
+    fingerprint: &[u32],
+    candidates: Vec<FingerprintCandidate>,
+    max_bit_error_rate: f32,
+) -> Vec<(FingerprintCandidate, FingerprintMatch)> {
+    let mut matches: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let other: Vec<u32> = candidate.fingerprint.iter().map(|&v| to_bits(v)).collect();
+            compare(fingerprint, &other)
+                .filter(|m| m.bit_error_rate <= max_bit_error_rate)
+                .map(|m| (candidate, m))
+        })
+        .collect();
+    matches.sort_by(|(_, x), (_, y)| x.bit_error_rate.total_cmp(&y.bit_error_rate));
+    matches
+}
+
+/// Stored form of a sub-fingerprint
+#[must_use]
+pub const fn to_stored(bits: u32) -> i32 {
+    i32::from_ne_bytes(bits.to_ne_bytes())
+}
+
+/// Sub-fingerprint from its stored form
+#[must_use]
+pub const fn to_bits(stored: i32) -> u32 {
+    u32::from_ne_bytes(stored.to_ne_bytes())
+}
+
+/// Fingerprint a call's recording, store it, and record the call as an echo
+/// of the closest earlier match
+///
+/// Returns the call it echoes, if any.
+///
+/// # Errors
+///
+/// Returns an error if decoding fails or the database cannot be queried
+pub(crate) async fn fingerprint_and_match(
+    pool: &PgPool,
+    config: &FingerprintConfig,
+    call_id: Uuid,
+    call_timestamp: DateTime<Utc>,
+    input: TranscodeInput<'_>,
+) -> Result<Option<Uuid>> {
+    let pcm = waveform::decode(input).await?;
+    let fingerprint = from_pcm(&pcm, DECODE_SAMPLE_RATE);
+    if fingerprint.is_empty() {
+        debug!("Call {call_id} is too short or quiet to fingerprint");
+        return Ok(None);
+    }
+    let stored: Vec<i32> = fingerprint.iter().map(|&bits| to_stored(bits)).collect();
+    FingerprintQueries::save(pool, call_id, &stored)
+        .await
+        .context("failed to store fingerprint")?;
+
+    let candidates =
+        FingerprintQueries::candidates(pool, call_id, call_timestamp, config.window_seconds)
+            .await
+            .context("failed to look up fingerprints")?;
+    // Point at the first call heard, and never back at this one
+    let Some((original, best)) =
+        near_duplicates(&fingerprint, candidates, config.max_bit_error_rate)
+            .into_iter()
+            .map(|(candidate, m)| (candidate.echo_of.unwrap_or(candidate.call_id), m))
+            .find(|(original, _)| *original != call_id)
+    else {
+        return Ok(None);
+    };
+
+    FingerprintQueries::mark_echo(pool, call_id, original, best.bit_error_rate)
+        .await
+        .context("failed to record echo")?;
+    info!(
+        "Call {call_id} echoes call {original} ({:.0}% of fingerprint bits differ)",
+        best.bit_error_rate * 100.0
+    );
+    Ok(Some(original))
+}
+
+/// Fingerprint an uploaded call in the background, logging failures
+///
+/// With `suppress_echoes` set, an echo's transcription is cancelled if no
+/// worker has claimed it yet.
+pub fn spawn_fingerprint(
+    pool: &PgPool,
+    config: &FingerprintConfig,
+    call_id: Uuid,
+    call_timestamp: DateTime<Utc>,
+    audio: Bytes,
+) {
+    let pool = pool.clone();
+    let config = config.clone();
+    drop(tokio::spawn(async move {
+        let input = TranscodeInput::Memory(audio);
+        match fingerprint_and_match(&pool, &config, call_id, call_timestamp, input).await {
+            Ok(Some(_)) if config.suppress_echoes => {
+                match FingerprintQueries::cancel_pending_transcription(&pool, call_id).await {
+                    Ok(true) => info!("Cancelled transcription of echo call {call_id}"),
+                    Ok(false) => {}
+                    Err(e) => warn!("Failed to cancel transcription of echo call {call_id}: {e}"),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to fingerprint call {call_id}: {e:#}"),
+        }
+    }));
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    /// Two seconds of tones that change every 31 ms, picked by `seed`
+    fn speech_like(seed: u64, gain: f32) -> Vec<f32> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as f32 / (1_u64 << 31) as f32
+        };
+        let mut samples = Vec::new();
+        for _ in 0..64 {
+            let tones: Vec<(f32, f32)> = (0..3)
+                .map(|_| (next().mul_add(HIGH_HZ - LOW_HZ, LOW_HZ), 0.2 + next()))
+                .collect();
+            for i in 0..250 {
+                let t = (samples.len() + i) as f32 / 8000.0;
+                let value: f32 = tones
+                    .iter()
+                    .map(|(hz, amplitude)| amplitude * (2.0 * PI * hz * t).sin())
+                    .sum();
+                samples.push(value * gain);
+            }
+        }
+        samples
+    }
+
+    fn pcm(samples: &[f32]) -> Vec<u8> {
+        samples
+            .iter()
+            .flat_map(|s| ((s * 6000.0) as i16).to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_same_audio_matches_despite_gain_and_delay() {
+        let original = from_pcm(&pcm(&speech_like(1, 1.0)), 8000);
+        assert!(!original.is_empty());
+
+        // Quieter copy starting three frames later
+        let mut delayed = vec![0.0; 3 * HOP_LEN];
+        delayed.extend(speech_like(1, 0.4));
+        let echo = from_pcm(&pcm(&delayed), 8000);
+
+        let m = compare(&original, &echo).unwrap();
+        assert!(m.bit_error_rate < 0.2, "{m:?}");
+        assert_eq!(m.offset_frames, 3);
+        assert!((m.offset_seconds() - 0.096).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_different_audio_does_not_match() {
+        let first = from_pcm(&pcm(&speech_like(1, 1.0)), 8000);
+        let second = from_pcm(&pcm(&speech_like(2, 1.0)), 8000);
+        let m = compare(&first, &second).unwrap();
+        assert!(m.bit_error_rate > 0.35, "{m:?}");
+    }
+
+    #[test]
+    fn test_silence_and_short_audio_have_no_fingerprint() {
+        assert!(from_pcm(&pcm(&vec![0.0; 8000]), 8000).is_empty());
+        assert!(from_pcm(&pcm(&speech_like(1, 1.0)[..FRAME_LEN]), 8000).is_empty());
+    }
+
+    #[test]
+    fn test_compare_needs_overlap() {
+        assert!(compare(&[0; 4], &[0; 4]).is_none());
+        let m = compare(&[u32::MAX; 40], &[0; 40]).unwrap();
+        assert!((m.bit_error_rate - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_stored_round_trip() {
+        for bits in [0, 1, 0x8000_0000, u32::MAX] {
+            assert_eq!(to_bits(to_stored(bits)), bits);
+        }
+        assert_eq!(to_stored(u32::MAX), -1);
+    }
+}
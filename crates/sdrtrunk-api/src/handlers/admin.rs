@@ -1,17 +1,33 @@
 //! Admin API handlers for system administration
 
-use crate::state::AppState;
+use crate::{
+    encryption::RotationReport,
+    error::ApiError,
+    features::FeatureState,
+    retention::{self, RecordingOutcome, RetentionReport},
+    state::AppState,
+};
 use axum::{
-    Json,
-    extract::{Path, State},
+    Extension, Json,
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::{IntoResponse, Response},
 };
-use sdrtrunk_storage::queries::{ApiKeyQueries, CreateApiKeyParams};
+use sdrtrunk_storage::{
+    BulkAction, BulkFilter, BulkOperation, BulkQueries, CallPartition, DataPurge, IngestKey,
+    IngestKeyQueries, MaintenanceQueries, NewBulkOperation, NewIngestKey, PartitionQueries,
+    PurgeFilter, PurgeQueries, PurgedCall, ScheduleQueries, ScheduledJob, StorageError, TableBloat,
+    legacy::refresh_system_stats,
+    models::ApiKeyDb,
+    queries::{ApiKeyQueries, CreateApiKeyParams, RadioCallQueries},
+    users::User,
+};
+use sdrtrunk_types::{RadioId, SystemId, TalkgroupId};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use uuid::Uuid;
 
 /// Request to create a new API key
 #[derive(Debug, Deserialize)]
@@ -73,21 +89,312 @@ pub struct DeleteApiKeyResponse {
     pub message: String,
 }
 
-/// Error response
+/// Request to create an ingest key
+#[derive(Debug, Deserialize)]
+pub struct CreateIngestKeyRequest {
+    /// System the key may upload to
+    pub system_id: SystemId,
+    /// Description, e.g. the recorder the key is issued to
+    pub description: Option<String>,
+    /// Optional expiration date (RFC 3339)
+    pub expires_at: Option<String>,
+}
+
+/// Response for a newly created ingest key
+#[derive(Debug, Serialize)]
+pub struct CreateIngestKeyResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The ingest key (only shown once)
+    pub ingest_key: String,
+    /// The stored key
+    pub key: IngestKey,
+    /// Message
+    pub message: String,
+}
+
+/// Query parameters for listing ingest keys
+#[derive(Debug, Default, Deserialize)]
+pub struct IngestKeyListQuery {
+    /// Only keys for this system
+    pub system_id: Option<SystemId>,
+}
+
+/// Ingest keys, revoked ones included
+#[derive(Debug, Serialize)]
+pub struct IngestKeyListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Keys, oldest first
+    pub keys: Vec<IngestKey>,
+}
+
+/// Request to override a feature flag at runtime
+#[derive(Debug, Deserialize)]
+pub struct SetFeatureRequest {
+    /// Whether the feature should be enabled
+    pub enabled: bool,
+}
+
+/// Response listing feature flag state
+#[derive(Debug, Serialize)]
+pub struct FeaturesResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// State of every known flag
+    pub features: Vec<FeatureState>,
+}
+
+/// Maintenance statistics for one table
+#[derive(Debug, Serialize)]
+pub struct TableMaintenanceStats {
+    /// Row, size, and vacuum/analyze statistics
+    #[serde(flatten)]
+    pub stats: TableBloat,
+    /// Fraction of tuples that are dead
+    pub dead_ratio: f64,
+    /// Whether the table has drifted past the configured analyze thresholds
+    pub needs_analyze: bool,
+}
+
+/// Response listing table maintenance statistics
+#[derive(Debug, Serialize)]
+pub struct TableMaintenanceResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Whether automatic analyze is enabled
+    pub auto_analyze: bool,
+    /// Statistics for every table, largest first
+    pub tables: Vec<TableMaintenanceStats>,
+}
+
+/// Response listing the monthly partitions of `radio_calls`
+#[derive(Debug, Serialize)]
+pub struct PartitionsResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Months of partitions kept ahead of the current month
+    pub premake_months: u32,
+    /// Partitions, oldest month first and the default partition last
+    pub partitions: Vec<CallPartition>,
+}
+
+/// Original transcript of an anonymized call
+#[derive(Debug, Serialize)]
+pub struct RawTranscriptResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Call the transcript belongs to
+    pub call_id: Uuid,
+    /// Transcript as recognized, before personal details were scrubbed
+    pub text: String,
+}
+
+/// Request to run ANALYZE
+#[derive(Debug, Default, Deserialize)]
+pub struct AnalyzeRequest {
+    /// Table to analyze; defaults to every table past the configured thresholds
+    pub table: Option<String>,
+}
+
+/// Response for an ANALYZE run
+#[derive(Debug, Serialize)]
+pub struct AnalyzeResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Tables that were analyzed
+    pub analyzed: Vec<String>,
+}
+
+/// Response for a retention purge
 #[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    /// Whether the operation was successful (always false)
+pub struct RetentionRunResponse {
+    /// Whether the operation was successful
     pub success: bool,
-    /// Error message
-    pub error: String,
+    /// What the purge removed
+    pub report: RetentionReport,
+}
+
+/// Response for an encryption key rotation
+#[derive(Debug, Serialize)]
+pub struct EncryptionRotateResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// What the rotation re-sealed
+    pub report: RotationReport,
+}
+
+/// Filters for a targeted purge; at least one is required
+#[derive(Debug, Deserialize)]
+pub struct PurgeParams {
+    /// Erase calls from this system
+    pub system_id: Option<String>,
+    /// Erase calls on this talkgroup
+    pub talkgroup_id: Option<i32>,
+    /// Erase calls transmitted by this radio
+    pub radio_id: Option<i32>,
+    /// Why the data is erased, kept in the audit log
+    pub reason: Option<String>,
 }
 
-impl IntoResponse for ErrorResponse {
-    fn into_response(self) -> Response {
-        (StatusCode::BAD_REQUEST, Json(self)).into_response()
+impl PurgeParams {
+    /// Validated purge filter
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if a filter is invalid or none is given
+    fn filter(&self) -> Result<PurgeFilter, String> {
+        let filter = PurgeFilter {
+            system_id: self
+                .system_id
+                .as_deref()
+                .map(SystemId::new)
+                .transpose()
+                .map_err(|e| format!("Invalid system_id: {e}"))?,
+            talkgroup_id: self
+                .talkgroup_id
+                .map(TalkgroupId::new)
+                .transpose()
+                .map_err(|e| format!("Invalid talkgroup_id: {e}"))?,
+            source_radio_id: self
+                .radio_id
+                .map(RadioId::new)
+                .transpose()
+                .map_err(|e| format!("Invalid radio_id: {e}"))?,
+        };
+        if filter.is_empty() {
+            return Err("At least one of system_id, talkgroup_id, radio_id is required".into());
+        }
+        Ok(filter)
     }
 }
 
+/// Response for a targeted purge
+#[derive(Debug, Serialize)]
+pub struct PurgeResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Audit record of what was erased
+    pub purge: DataPurge,
+}
+
+/// How long a bulk operation's confirmation token stays valid
+const CONFIRMATION_TTL_MINUTES: i64 = 10;
+
+/// Default number of bulk operations listed
+const DEFAULT_BULK_OPERATIONS: i64 = 50;
+
+/// Request to archive or delete calls in bulk; at least one filter is
+/// required
+///
+/// Sent without `confirmation_token`, it reports how many calls match and
+/// returns a token; sent again unchanged with the token, it is carried out.
+#[derive(Debug, Deserialize)]
+pub struct BulkCallsRequest {
+    /// Calls from this system
+    pub system_id: Option<String>,
+    /// Calls on this talkgroup
+    pub talkgroup_id: Option<i32>,
+    /// Calls transmitted by this radio
+    pub radio_id: Option<i32>,
+    /// Calls at or after this time (ISO 8601)
+    pub from_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Calls at or before this time (ISO 8601)
+    pub to_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Why, kept in the audit log
+    pub reason: Option<String>,
+    /// Token returned by the unconfirmed request
+    pub confirmation_token: Option<String>,
+}
+
+impl BulkCallsRequest {
+    /// Validated bulk filter
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if a filter is invalid or none is given
+    fn filter(&self) -> Result<BulkFilter, String> {
+        let filter = BulkFilter {
+            system_id: self
+                .system_id
+                .as_deref()
+                .map(SystemId::new)
+                .transpose()
+                .map_err(|e| format!("Invalid system_id: {e}"))?,
+            talkgroup_id: self
+                .talkgroup_id
+                .map(TalkgroupId::new)
+                .transpose()
+                .map_err(|e| format!("Invalid talkgroup_id: {e}"))?,
+            source_radio_id: self
+                .radio_id
+                .map(RadioId::new)
+                .transpose()
+                .map_err(|e| format!("Invalid radio_id: {e}"))?,
+            from_date: self.from_date,
+            to_date: self.to_date,
+        };
+        if filter.is_empty() {
+            return Err(
+                "At least one of system_id, talkgroup_id, radio_id, from_date, to_date is required"
+                    .into(),
+            );
+        }
+        if let (Some(from), Some(to)) = (filter.from_date, filter.to_date)
+            && from > to
+        {
+            return Err("from_date must not be after to_date".into());
+        }
+        Ok(filter)
+    }
+}
+
+/// Response for a bulk archive or delete
+#[derive(Debug, Serialize)]
+pub struct BulkCallsResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Whether the operation was carried out (false until confirmed)
+    pub confirmed: bool,
+    /// Audit record: calls matched and, once confirmed, affected
+    pub operation: BulkOperation,
+    /// Send back as `confirmation_token` to carry out the operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_token: Option<String>,
+}
+
+/// Query parameters for listing bulk operations
+#[derive(Debug, Deserialize)]
+pub struct BulkOperationsQuery {
+    /// Operations to return (default 50, max 1000)
+    pub limit: Option<i64>,
+}
+
+/// Response listing bulk operations
+#[derive(Debug, Serialize)]
+pub struct BulkOperationsResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Operations, newest first
+    pub operations: Vec<BulkOperation>,
+}
+
+/// Response listing scheduled jobs
+#[derive(Debug, Serialize)]
+pub struct ScheduledJobsResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Jobs with their schedules and last runs, by name
+    pub jobs: Vec<ScheduledJob>,
+}
+
+/// Hash an API key the way it is stored in `api_keys.key_hash` (hex SHA-256)
+pub(crate) fn hash_api_key(api_key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(api_key.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
 /// Create a new API key
 ///
 /// # Errors
@@ -96,29 +403,27 @@ impl IntoResponse for ErrorResponse {
 pub async fn create_api_key(
     State(state): State<Arc<AppState>>,
     Json(request): Json<CreateApiKeyRequest>,
-) -> Result<Json<CreateApiKeyResponse>, ErrorResponse> {
+) -> Result<Json<CreateApiKeyResponse>, ApiError> {
     info!(
         "Creating new API key with description: {:?}",
         request.description
     );
 
     // Generate a random API key (32 bytes = 64 hex characters)
-    let api_key = uuid::Uuid::new_v4().to_string().replace('-', "");
+    let api_key = Uuid::new_v4().to_string().replace('-', "");
 
     // Hash the API key for storage using SHA-256 (cryptographically secure)
-    let mut hasher = Sha256::new();
-    hasher.update(api_key.as_bytes());
-    let key_hash = format!("{:x}", hasher.finalize());
+    let key_hash = hash_api_key(&api_key);
 
     // Parse expiration date if provided
     let expires_at = if let Some(expires_str) = request.expires_at {
         match chrono::DateTime::parse_from_rfc3339(&expires_str) {
             Ok(dt) => Some(dt.with_timezone(&chrono::Utc)),
             Err(e) => {
-                return Err(ErrorResponse {
-                    success: false,
-                    error: format!("Invalid expiration date format: {e}"),
-                });
+                return Err(ApiError::bad_request(
+                    "INVALID_PARAMETERS",
+                    format!("Invalid expiration date format: {e}"),
+                ));
             }
         }
     } else {
@@ -149,10 +454,7 @@ pub async fn create_api_key(
         }
         Err(e) => {
             error!("Failed to create API key: {e}");
-            Err(ErrorResponse {
-                success: false,
-                error: format!("Failed to create API key: {e}"),
-            })
+            Err(ApiError::database(format!("Failed to create API key: {e}")))
         }
     }
 }
@@ -165,7 +467,7 @@ pub async fn create_api_key(
 pub async fn get_api_key_details(
     State(state): State<Arc<AppState>>,
     Path(key_id): Path<String>,
-) -> Result<Json<ApiKeyDetailsResponse>, ErrorResponse> {
+) -> Result<Json<ApiKeyDetailsResponse>, ApiError> {
     info!("Fetching API key details for: {key_id}");
 
     match ApiKeyQueries::find_by_id(&state.pool, &key_id).await {
@@ -181,12 +483,13 @@ pub async fn get_api_key_details(
             last_used: api_key.last_used.map(|dt| dt.to_rfc3339()),
             total_requests: api_key.total_requests,
         })),
+        Err(StorageError::NotFound { .. }) => Err(ApiError::not_found(
+            "API_KEY_NOT_FOUND",
+            format!("API key {key_id} not found"),
+        )),
         Err(e) => {
             error!("Failed to fetch API key {key_id}: {e}");
-            Err(ErrorResponse {
-                success: false,
-                error: format!("API key not found: {e}"),
-            })
+            Err(ApiError::database(format!("Failed to fetch API key: {e}")))
         }
     }
 }
@@ -199,7 +502,7 @@ pub async fn get_api_key_details(
 pub async fn delete_api_key(
     State(state): State<Arc<AppState>>,
     Path(key_id): Path<String>,
-) -> Result<Json<DeleteApiKeyResponse>, ErrorResponse> {
+) -> Result<Json<DeleteApiKeyResponse>, ApiError> {
     info!("Deleting API key: {key_id}");
 
     match ApiKeyQueries::delete(&state.pool, &key_id).await {
@@ -212,10 +515,7 @@ pub async fn delete_api_key(
         }
         Err(e) => {
             error!("Failed to delete API key {key_id}: {e}");
-            Err(ErrorResponse {
-                success: false,
-                error: format!("Failed to delete API key: {e}"),
-            })
+            Err(ApiError::database(format!("Failed to delete API key: {e}")))
         }
     }
 }
@@ -227,7 +527,7 @@ pub async fn delete_api_key(
 /// Returns error if database operation fails
 pub async fn list_api_keys(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<ApiKeyDetailsResponse>>, ErrorResponse> {
+) -> Result<Json<Vec<ApiKeyDetailsResponse>>, ApiError> {
     info!("Listing all active API keys");
 
     match ApiKeyQueries::get_all_active(&state.pool).await {
@@ -249,12 +549,753 @@ pub async fn list_api_keys(
         )),
         Err(e) => {
             error!("Failed to list API keys: {e}");
-            Err(ErrorResponse {
-                success: false,
-                error: format!("Failed to list API keys: {e}"),
+            Err(ApiError::database(format!("Failed to list API keys: {e}")))
+        }
+    }
+}
+
+/// Create an upload-only key bound to one system
+///
+/// # Errors
+///
+/// Returns error if the expiration date is invalid or the database operation fails
+pub async fn create_ingest_key(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateIngestKeyRequest>,
+) -> Result<Json<CreateIngestKeyResponse>, ApiError> {
+    info!("Creating ingest key for system {}", request.system_id);
+
+    let expires_at = match request
+        .expires_at
+        .as_deref()
+        .map(chrono::DateTime::parse_from_rfc3339)
+        .transpose()
+    {
+        Ok(expires_at) => expires_at.map(|dt| dt.with_timezone(&chrono::Utc)),
+        Err(e) => {
+            return Err(ApiError::bad_request(
+                "INVALID_PARAMETERS",
+                format!("Invalid expiration date format: {e}"),
+            ));
+        }
+    };
+
+    let ingest_key = Uuid::new_v4().to_string().replace('-', "");
+    let key_hash = hash_api_key(&ingest_key);
+    match IngestKeyQueries::create(
+        &state.pool,
+        &NewIngestKey {
+            key_hash: &key_hash,
+            system_id: &request.system_id,
+            description: request.description.as_deref(),
+            expires_at,
+        },
+    )
+    .await
+    {
+        Ok(key) => {
+            info!("Successfully created ingest key: {}", key.id);
+            Ok(Json(CreateIngestKeyResponse {
+                success: true,
+                ingest_key,
+                key,
+                message: "Ingest key created successfully. Store this key securely - it will not be shown again.".to_string(),
+            }))
+        }
+        Err(e) => {
+            error!("Failed to create ingest key: {e}");
+            Err(ApiError::database(format!(
+                "Failed to create ingest key: {e}"
+            )))
+        }
+    }
+}
+
+/// List ingest keys, optionally for one system
+///
+/// # Errors
+///
+/// Returns error if database operation fails
+pub async fn list_ingest_keys(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<IngestKeyListQuery>,
+) -> Result<Json<IngestKeyListResponse>, ApiError> {
+    match IngestKeyQueries::list(&state.pool, query.system_id.as_ref()).await {
+        Ok(keys) => Ok(Json(IngestKeyListResponse {
+            success: true,
+            keys,
+        })),
+        Err(e) => {
+            error!("Failed to list ingest keys: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list ingest keys: {e}"
+            )))
+        }
+    }
+}
+
+/// Revoke an ingest key
+///
+/// # Errors
+///
+/// Returns error if the key is unknown or already revoked, or the database
+/// operation fails
+pub async fn revoke_ingest_key(
+    State(state): State<Arc<AppState>>,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<DeleteApiKeyResponse>, ApiError> {
+    info!("Revoking ingest key: {key_id}");
+
+    match IngestKeyQueries::revoke(&state.pool, key_id).await {
+        Ok(true) => Ok(Json(DeleteApiKeyResponse {
+            success: true,
+            message: format!("Ingest key {key_id} has been revoked"),
+        })),
+        Ok(false) => Err(ApiError::not_found(
+            "INGEST_KEY_NOT_FOUND",
+            format!("No active ingest key {key_id}"),
+        )),
+        Err(e) => {
+            error!("Failed to revoke ingest key {key_id}: {e}");
+            Err(ApiError::database(format!(
+                "Failed to revoke ingest key: {e}"
+            )))
+        }
+    }
+}
+
+/// List feature flags with their configured and effective values
+pub async fn list_features(State(state): State<Arc<AppState>>) -> Json<FeaturesResponse> {
+    Json(FeaturesResponse {
+        success: true,
+        features: state.features.snapshot(),
+    })
+}
+
+/// Override a feature flag until the next restart
+///
+/// # Errors
+///
+/// Returns error if the feature flag is not known
+pub async fn set_feature(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(request): Json<SetFeatureRequest>,
+) -> Result<Json<FeatureState>, ApiError> {
+    if !state.features.set_override(&name, request.enabled) {
+        return Err(unknown_feature(&name));
+    }
+
+    info!("Feature flag {name} overridden to {}", request.enabled);
+    state
+        .features
+        .state(&name)
+        .map(Json)
+        .ok_or_else(|| unknown_feature(&name))
+}
+
+/// Remove a feature flag override, reverting to the configured value
+///
+/// # Errors
+///
+/// Returns error if the feature flag is not known
+pub async fn clear_feature_override(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<FeatureState>, ApiError> {
+    if state.features.clear_override(&name).is_some() {
+        info!("Feature flag {name} override cleared");
+    }
+
+    state
+        .features
+        .state(&name)
+        .map(Json)
+        .ok_or_else(|| unknown_feature(&name))
+}
+
+/// Report table bloat and analyze status
+///
+/// # Errors
+///
+/// Returns error if table statistics cannot be read
+pub async fn get_table_maintenance(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<TableMaintenanceResponse>, ApiError> {
+    let config = &state.config.maintenance;
+    let tables = MaintenanceQueries::table_bloat(&state.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to read table statistics: {e}");
+            ApiError::database(format!("Failed to read table statistics: {e}"))
+        })?;
+
+    Ok(Json(TableMaintenanceResponse {
+        success: true,
+        auto_analyze: config.auto_analyze,
+        tables: tables
+            .into_iter()
+            .map(|stats| TableMaintenanceStats {
+                dead_ratio: stats.dead_ratio(),
+                needs_analyze: stats.needs_analyze(config.analyze_min_rows, config.analyze_ratio),
+                stats,
             })
+            .collect(),
+    }))
+}
+
+/// List the monthly partitions of `radio_calls` with their size
+///
+/// # Errors
+///
+/// Returns error if the partitions cannot be read
+pub async fn get_partitions(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<PartitionsResponse>, ApiError> {
+    let partitions = PartitionQueries::list(&state.pool).await.map_err(|e| {
+        error!("Failed to list call partitions: {e}");
+        ApiError::database(format!("Failed to list call partitions: {e}"))
+    })?;
+
+    Ok(Json(PartitionsResponse {
+        success: true,
+        premake_months: state.config.maintenance.partition_premake_months,
+        partitions,
+    }))
+}
+
+/// Run ANALYZE on one table, or on every table past the configured thresholds
+///
+/// Intended to be called after bulk imports or purges.
+///
+/// # Errors
+///
+/// Returns error if the table is unknown or ANALYZE fails
+pub async fn run_analyze(
+    State(state): State<Arc<AppState>>,
+    request: Option<Json<AnalyzeRequest>>,
+) -> Result<Json<AnalyzeResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let config = &state.config.maintenance;
+
+    let result = match request.table {
+        Some(table) => analyze_known_table(&state, table).await,
+        None => {
+            MaintenanceQueries::analyze_stale(
+                &state.pool,
+                config.analyze_min_rows,
+                config.analyze_ratio,
+            )
+            .await
+        }
+    };
+
+    match result {
+        Ok(analyzed) => {
+            info!("Manual ANALYZE completed for {} tables", analyzed.len());
+            Ok(Json(AnalyzeResponse {
+                success: true,
+                analyzed,
+            }))
+        }
+        Err(StorageError::NotFound { id, .. }) => Err(ApiError::not_found(
+            "TABLE_NOT_FOUND",
+            format!("Unknown table: {id}"),
+        )),
+        Err(e) => {
+            error!("ANALYZE failed: {e}");
+            Err(ApiError::database(format!("ANALYZE failed: {e}")))
+        }
+    }
+}
+
+/// Purge data past the configured retention periods now
+///
+/// Uses the `retention` settings even when the scheduled task is disabled.
+///
+/// # Errors
+///
+/// Returns error if a purge query fails
+pub async fn run_retention(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<RetentionRunResponse>, ApiError> {
+    let config = state.retention.borrow().clone();
+    match retention::run_retention(&state.pool, &config, state.audio_storage.as_ref()).await {
+        Ok(report) => {
+            retention::log_report(&report);
+            state.stats_cache.invalidate_all();
+            Ok(Json(RetentionRunResponse {
+                success: true,
+                report,
+            }))
+        }
+        Err(e) => {
+            error!("Retention purge failed: {e}");
+            Err(ApiError::database(format!("Retention purge failed: {e}")))
+        }
+    }
+}
+
+/// Re-seal encrypted call fields with the current key
+///
+/// Values sealed with a key in `encryption.previous_keys`, and plaintext
+/// values of fields that are now encrypted, are sealed with the current key.
+/// Runs until every call is done, so large archives are better rotated with
+/// `--rotate-encryption-key`.
+///
+/// # Errors
+///
+/// Returns error if encryption is not enabled or a database query fails
+pub async fn rotate_encryption_key(
+    State(state): State<Arc<AppState>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    user: Option<Extension<User>>,
+) -> Result<Json<EncryptionRotateResponse>, ApiError> {
+    let Some(encryption) = state.encryption.as_deref() else {
+        return Err(ApiError::conflict(
+            "ENCRYPTION_DISABLED",
+            "Column encryption is not enabled",
+        ));
+    };
+    info!(
+        "Encryption key rotation started by {}",
+        requester(api_key, user)
+    );
+    match encryption.rotate(&state.pool).await {
+        Ok(report) => {
+            info!(
+                "Key rotation finished: {} calls and {} segments re-sealed, {} skipped, {} failed",
+                report.resealed, report.segments_resealed, report.skipped, report.failed
+            );
+            Ok(Json(EncryptionRotateResponse {
+                success: true,
+                report,
+            }))
+        }
+        Err(e) => {
+            error!("Encryption key rotation failed: {e}");
+            Err(ApiError::database(format!(
+                "Encryption key rotation failed: {e}"
+            )))
+        }
+    }
+}
+
+/// Erase every call matching the given system, talkgroup, and/or radio
+///
+/// Calls, transcripts, and the upload log entries and webhook deliveries
+/// referring to them are deleted in one transaction that also writes the
+/// audit record; recordings are removed from storage once it commits.
+///
+/// # Errors
+///
+/// Returns error if no valid filter is given or the purge fails, in which
+/// case nothing is erased
+pub async fn purge_data(
+    State(state): State<Arc<AppState>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    user: Option<Extension<User>>,
+    Query(params): Query<PurgeParams>,
+) -> Result<Json<PurgeResponse>, ApiError> {
+    let filter = params
+        .filter()
+        .map_err(|error| ApiError::bad_request("INVALID_PARAMETERS", error))?;
+    let requested_by = requester(api_key, user);
+
+    let (mut purge, calls) = PurgeQueries::purge(
+        &state.pool,
+        &filter,
+        &requested_by,
+        params.reason.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        error!("Data purge failed: {e}");
+        ApiError::database(format!("Data purge failed: {e}"))
+    })?;
+    info!(
+        "Data purge {} by {requested_by} erased {} calls",
+        purge.id, purge.calls_deleted
+    );
+
+    clean_up_purge(&state, &mut purge, calls).await;
+
+    Ok(Json(PurgeResponse {
+        success: true,
+        purge,
+    }))
+}
+
+/// Remove a purge's recordings, record how that went, and refresh the
+/// affected systems' statistics
+async fn clean_up_purge(state: &AppState, purge: &mut DataPurge, calls: Vec<PurgedCall>) {
+    let (deleted, failed) = remove_recordings(state, &calls).await;
+    if let Err(e) = PurgeQueries::record_files(&state.pool, purge.id, deleted, failed).await {
+        warn!(
+            "Failed to record recordings removed by purge {}: {e}",
+            purge.id
+        );
+    }
+    purge.audio_files_deleted = i32::try_from(deleted).unwrap_or(i32::MAX);
+    purge.audio_files_failed = i32::try_from(failed).unwrap_or(i32::MAX);
+
+    refresh_stats(state, calls).await;
+}
+
+/// Archive calls matching a filter, after confirmation
+///
+/// # Errors
+///
+/// Returns error if no valid filter is given, the confirmation token is not
+/// valid for this request, or a database query fails
+pub async fn archive_calls(
+    State(state): State<Arc<AppState>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    user: Option<Extension<User>>,
+    Json(request): Json<BulkCallsRequest>,
+) -> Result<(StatusCode, Json<BulkCallsResponse>), ApiError> {
+    bulk_calls(
+        &state,
+        BulkAction::Archive,
+        requester(api_key, user),
+        request,
+    )
+    .await
+}
+
+/// Delete calls matching a filter, after confirmation
+///
+/// Calls are erased with their transcripts the way a targeted purge erases
+/// them, and their recordings are removed from storage afterwards.
+///
+/// # Errors
+///
+/// Returns error if no valid filter is given, the confirmation token is not
+/// valid for this request, or a database query fails
+pub async fn delete_calls(
+    State(state): State<Arc<AppState>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    user: Option<Extension<User>>,
+    Json(request): Json<BulkCallsRequest>,
+) -> Result<(StatusCode, Json<BulkCallsResponse>), ApiError> {
+    bulk_calls(
+        &state,
+        BulkAction::Delete,
+        requester(api_key, user),
+        request,
+    )
+    .await
+}
+
+/// List recent bulk archives and deletes, pending or carried out
+///
+/// # Errors
+///
+/// Returns error if the limit is out of range or the database query fails
+pub async fn list_bulk_operations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BulkOperationsQuery>,
+) -> Result<Json<BulkOperationsResponse>, ApiError> {
+    let limit = query.limit.unwrap_or(DEFAULT_BULK_OPERATIONS);
+    if !(1..=1000).contains(&limit) {
+        return Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            "limit must be 1 to 1000",
+        ));
+    }
+    match BulkQueries::list(&state.pool, limit).await {
+        Ok(operations) => Ok(Json(BulkOperationsResponse {
+            success: true,
+            operations,
+        })),
+        Err(e) => {
+            error!("Failed to list bulk operations: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list bulk operations: {e}"
+            )))
+        }
+    }
+}
+
+/// Decrypt the original transcript kept for an anonymized call
+///
+/// Every request is logged with who made it.
+///
+/// # Errors
+///
+/// Returns error if the call is unknown, no original was kept for it,
+/// anonymization has no raw text key, or the original cannot be decrypted
+pub async fn get_raw_transcript(
+    State(state): State<Arc<AppState>>,
+    Path(call_id): Path<Uuid>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    user: Option<Extension<User>>,
+) -> Result<Json<RawTranscriptResponse>, ApiError> {
+    let Some(redactor) = state.redactor.as_deref() else {
+        return Err(ApiError::conflict(
+            "ANONYMIZATION_DISABLED",
+            "Transcript anonymization is not enabled",
+        ));
+    };
+    let raw = match RadioCallQueries::find_raw_transcription(&state.pool, call_id).await {
+        Ok(Some(raw)) => raw,
+        Ok(None) => {
+            return Err(ApiError::not_found(
+                "RAW_TRANSCRIPT_NOT_FOUND",
+                format!("No original transcript is kept for call {call_id}"),
+            ));
+        }
+        Err(StorageError::NotFound { .. }) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to read original transcript of call {call_id}: {e}");
+            return Err(ApiError::database(format!(
+                "Failed to read original transcript: {e}"
+            )));
+        }
+    };
+    let text = redactor.reveal(&raw).map_err(|e| {
+        error!("Failed to decrypt original transcript of call {call_id}: {e}");
+        ApiError::internal(
+            "RAW_TRANSCRIPT_UNREADABLE",
+            format!("Failed to decrypt original transcript: {e}"),
+        )
+    })?;
+    info!(
+        "Original transcript of call {call_id} revealed to {}",
+        requester(api_key, user)
+    );
+
+    Ok(Json(RawTranscriptResponse {
+        success: true,
+        call_id,
+        text,
+    }))
+}
+
+/// Prepare a bulk operation, or carry it out when the request confirms one
+///
+/// # Errors
+///
+/// Returns error if no valid filter is given, the confirmation token is not
+/// valid for this request, or a database query fails
+async fn bulk_calls(
+    state: &AppState,
+    action: BulkAction,
+    requested_by: String,
+    request: BulkCallsRequest,
+) -> Result<(StatusCode, Json<BulkCallsResponse>), ApiError> {
+    let filter = request
+        .filter()
+        .map_err(|error| ApiError::bad_request("INVALID_PARAMETERS", error))?;
+    let database_error = |e: StorageError| {
+        error!("Bulk {} failed: {e}", action.as_str());
+        ApiError::database(format!("Bulk {} failed: {e}", action.as_str()))
+    };
+
+    let Some(token) = request.confirmation_token.as_deref() else {
+        let (operation, token) = prepare_bulk(
+            state,
+            action,
+            &filter,
+            &requested_by,
+            request.reason.as_deref(),
+        )
+        .await
+        .map_err(database_error)?;
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(BulkCallsResponse {
+                success: true,
+                confirmed: false,
+                operation,
+                confirmation_token: Some(token),
+            }),
+        ));
+    };
+
+    let Some((mut operation, calls)) = BulkQueries::execute(
+        &state.pool,
+        &hash_api_key(token),
+        action,
+        &filter,
+        &requested_by,
+    )
+    .await
+    .map_err(database_error)?
+    else {
+        return Err(ApiError::bad_request(
+            "INVALID_CONFIRMATION_TOKEN",
+            "Confirmation token is unknown, expired, already used, or was issued for a \
+             different request",
+        ));
+    };
+    info!(
+        "Bulk {} {} by {requested_by} affected {} calls",
+        action.as_str(),
+        operation.id,
+        operation.affected_calls.unwrap_or_default()
+    );
+
+    if !calls.is_empty() {
+        clean_up_bulk_delete(state, &mut operation, calls).await;
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(BulkCallsResponse {
+            success: true,
+            confirmed: true,
+            operation,
+            confirmation_token: None,
+        }),
+    ))
+}
+
+/// Record a pending bulk operation, returning it with its confirmation token
+///
+/// # Errors
+///
+/// Returns error if the database query fails
+async fn prepare_bulk(
+    state: &AppState,
+    action: BulkAction,
+    filter: &BulkFilter,
+    requested_by: &str,
+    reason: Option<&str>,
+) -> Result<(BulkOperation, String), StorageError> {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let operation = BulkQueries::prepare(
+        &state.pool,
+        &NewBulkOperation {
+            action,
+            filter,
+            requested_by,
+            reason,
+            token_hash: &hash_api_key(&token),
+            expires_at: chrono::Utc::now() + chrono::Duration::minutes(CONFIRMATION_TTL_MINUTES),
+        },
+    )
+    .await?;
+    info!(
+        "Bulk {} {} by {requested_by} matches {} calls, awaiting confirmation",
+        action.as_str(),
+        operation.id,
+        operation.matched_calls
+    );
+    Ok((operation, token))
+}
+
+/// Remove a bulk delete's recordings, record how that went, and refresh the
+/// affected systems' statistics
+async fn clean_up_bulk_delete(
+    state: &AppState,
+    operation: &mut BulkOperation,
+    calls: Vec<PurgedCall>,
+) {
+    let (deleted, failed) = remove_recordings(state, &calls).await;
+    if let Err(e) = BulkQueries::record_files(&state.pool, operation.id, deleted, failed).await {
+        warn!(
+            "Failed to record recordings removed by bulk delete {}: {e}",
+            operation.id
+        );
+    }
+    operation.audio_files_deleted = i32::try_from(deleted).unwrap_or(i32::MAX);
+    operation.audio_files_failed = i32::try_from(failed).unwrap_or(i32::MAX);
+
+    refresh_stats(state, calls).await;
+}
+
+/// Who is making an admin request, as recorded in audit logs
+fn requester(api_key: Option<Extension<ApiKeyDb>>, user: Option<Extension<User>>) -> String {
+    match (api_key, user) {
+        (Some(Extension(key)), _) => format!("api_key:{}", key.id),
+        (None, Some(Extension(user))) => format!("user:{}", user.username),
+        (None, None) => "anonymous".to_string(),
+    }
+}
+
+/// Remove deleted calls' recordings, returning how many were removed and how
+/// many could not be
+async fn remove_recordings(state: &AppState, calls: &[PurgedCall]) -> (usize, usize) {
+    let (mut deleted, mut failed) = (0, 0);
+    for location in calls
+        .iter()
+        .filter_map(|call| call.audio_file_path.as_deref())
+    {
+        match retention::remove_recording(location, state.audio_storage.as_ref()).await {
+            RecordingOutcome::Deleted(_) => deleted += 1,
+            RecordingOutcome::Failed => failed += 1,
+            RecordingOutcome::Missing | RecordingOutcome::Skipped => {}
         }
     }
+    (deleted, failed)
+}
+
+/// Refresh the statistics of the systems deleted calls belonged to
+async fn refresh_stats(state: &AppState, calls: Vec<PurgedCall>) {
+    let systems: BTreeSet<SystemId> = calls.into_iter().map(|call| call.system_id).collect();
+    for system_id in &systems {
+        if let Err(e) = refresh_system_stats(&state.pool, system_id).await {
+            warn!("Failed to refresh stats for {system_id} after deleting calls: {e}");
+        }
+        state.stats_cache.invalidate_system(system_id);
+    }
+}
+
+/// List scheduled background jobs, when they run next, and how their last
+/// run went
+///
+/// # Errors
+///
+/// Returns error if database operation fails
+pub async fn list_scheduled_jobs(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ScheduledJobsResponse>, ApiError> {
+    match ScheduleQueries::list(&state.pool).await {
+        Ok(jobs) => Ok(Json(ScheduledJobsResponse {
+            success: true,
+            jobs,
+        })),
+        Err(e) => {
+            error!("Failed to list scheduled jobs: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list scheduled jobs: {e}"
+            )))
+        }
+    }
+}
+
+/// Analyze a single table after checking it is a known user table
+///
+/// # Errors
+///
+/// Returns error if the table is unknown or ANALYZE fails
+async fn analyze_known_table(
+    state: &AppState,
+    table: String,
+) -> sdrtrunk_storage::Result<Vec<String>> {
+    let known = MaintenanceQueries::table_bloat(&state.pool)
+        .await?
+        .iter()
+        .any(|t| t.table_name == table);
+    if !known {
+        return Err(StorageError::NotFound {
+            entity: "table".to_string(),
+            id: table,
+        });
+    }
+
+    MaintenanceQueries::analyze(&state.pool, &table).await?;
+    Ok(vec![table])
+}
+
+fn unknown_feature(name: &str) -> ApiError {
+    ApiError::not_found("UNKNOWN_FEATURE", format!("Unknown feature flag: {name}"))
 }
 
 #[cfg(test)]
@@ -298,6 +1339,7 @@ pub async fn list_api_keys(
 )]
 mod tests {
     use super::*;
+    use axum::http::StatusCode;
 
     #[test]
     fn test_create_api_key_request_deserialization() {
@@ -308,13 +1350,96 @@ mod tests {
     }
 
     #[test]
-    fn test_error_response_serialization() {
-        let error = ErrorResponse {
-            success: false,
-            error: "Test error".to_string(),
+    fn test_set_feature_request_deserialization() {
+        let request: SetFeatureRequest = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert!(request.enabled);
+        assert!(serde_json::from_str::<SetFeatureRequest>("{}").is_err());
+    }
+
+    #[test]
+    fn test_hash_api_key() {
+        let hash = hash_api_key("secret");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_api_key("secret"));
+        assert_ne!(hash, hash_api_key("Secret"));
+    }
+
+    #[test]
+    fn test_analyze_request_deserialization() {
+        let request: AnalyzeRequest = serde_json::from_str(r#"{"table":"radio_calls"}"#).unwrap();
+        assert_eq!(request.table.as_deref(), Some("radio_calls"));
+
+        let request: AnalyzeRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.table.is_none());
+    }
+
+    #[test]
+    fn test_purge_params_filter() {
+        let params: PurgeParams =
+            serde_json::from_str(r#"{"system_id":"metro","radio_id":1234,"reason":"erasure"}"#)
+                .unwrap();
+        let filter = params.filter().unwrap();
+        assert_eq!(filter.system_id, Some(SystemId::new("metro").unwrap()));
+        assert_eq!(filter.talkgroup_id, None);
+        assert_eq!(filter.source_radio_id, Some(RadioId::new(1234).unwrap()));
+
+        let params: PurgeParams = serde_json::from_str(r#"{"reason":"everything"}"#).unwrap();
+        assert!(params.filter().is_err());
+        let params: PurgeParams = serde_json::from_str(r#"{"talkgroup_id":-1}"#).unwrap();
+        assert!(params.filter().unwrap_err().contains("talkgroup_id"));
+    }
+
+    #[test]
+    fn test_bulk_calls_request_filter() {
+        let request: BulkCallsRequest = serde_json::from_str(
+            r#"{"system_id":"metro","to_date":"2024-01-01T00:00:00Z","confirmation_token":"abc"}"#,
+        )
+        .unwrap();
+        let filter = request.filter().unwrap();
+        assert_eq!(filter.system_id, Some(SystemId::new("metro").unwrap()));
+        assert!(filter.from_date.is_none());
+        assert!(filter.to_date.is_some());
+        assert_eq!(request.confirmation_token.as_deref(), Some("abc"));
+
+        let request: BulkCallsRequest = serde_json::from_str(r#"{"reason":"cleanup"}"#).unwrap();
+        assert!(request.filter().is_err());
+        let request: BulkCallsRequest = serde_json::from_str(
+            r#"{"from_date":"2024-02-01T00:00:00Z","to_date":"2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert!(request.filter().unwrap_err().contains("from_date"));
+    }
+
+    #[test]
+    fn test_table_maintenance_stats_serialization() {
+        let entry = TableMaintenanceStats {
+            stats: TableBloat {
+                table_name: "radio_calls".to_string(),
+                live_rows: 900,
+                dead_rows: 100,
+                modified_since_analyze: 0,
+                total_bytes: 65_536,
+                last_analyze: None,
+                last_autoanalyze: None,
+                last_vacuum: None,
+                last_autovacuum: None,
+            },
+            dead_ratio: 0.1,
+            needs_analyze: false,
         };
-        let json = serde_json::to_string(&error).unwrap();
-        assert!(json.contains("Test error"));
-        assert!(json.contains("false"));
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["table_name"], "radio_calls");
+        assert_eq!(json["dead_rows"], 100);
+        assert_eq!(json["dead_ratio"], 0.1);
+        assert_eq!(json["needs_analyze"], false);
+    }
+
+    #[test]
+    fn test_unknown_feature_error() {
+        let error = unknown_feature("warp_drive");
+        assert_eq!(error.code(), "UNKNOWN_FEATURE");
+        assert!(error.message().contains("warp_drive"));
+        assert_eq!(error.status(), StatusCode::NOT_FOUND);
     }
 }
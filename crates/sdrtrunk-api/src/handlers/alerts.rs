@@ -0,0 +1,391 @@
+//! Keyword alert handlers
+//!
+//! Manages alert rules and exposes the history of alerts raised when a
+//! completed transcription matched a rule.
+
+use crate::{error::ApiError, mail::is_valid_email, state::AppState, tenant::TenantScope};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::alerts::{AlertMatcher, PatternKind};
+use sdrtrunk_storage::{Alert, AlertFilter, AlertQueries, AlertRule, NewAlertRule};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Default number of alerts per page
+const DEFAULT_LIMIT: i64 = 50;
+/// Largest page of alerts
+const MAX_LIMIT: i64 = 1000;
+
+/// Request to create an alert rule
+#[derive(Debug, Deserialize)]
+pub struct CreateAlertRuleRequest {
+    /// Display name
+    pub name: String,
+    /// Keyword, phrase, or regex
+    pub pattern: String,
+    /// How `pattern` is interpreted (default `keyword`)
+    #[serde(default)]
+    pub pattern_type: PatternKind,
+    /// Only match calls from this system
+    pub system_id: Option<SystemId>,
+    /// Only match calls on this talkgroup
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// URL to POST alerts to
+    pub webhook_url: Option<String>,
+    /// Address to email alerts to
+    pub email: Option<String>,
+    /// Whether the rule is checked (default true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+const fn default_enabled() -> bool {
+    true
+}
+
+impl CreateAlertRuleRequest {
+    /// Validate the request into the rule to store
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid field
+    pub fn into_rule(self) -> Result<NewAlertRule, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() || name.len() > 255 {
+            return Err("name must be 1 to 255 characters".to_string());
+        }
+        let pattern = self.pattern.trim().to_string();
+        let _ = AlertMatcher::new(self.pattern_type, &pattern).map_err(|e| e.to_string())?;
+
+        let webhook_url = self.webhook_url.filter(|u| !u.trim().is_empty());
+        if let Some(url) = &webhook_url {
+            let valid = reqwest::Url::parse(url.trim())
+                .is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.has_host());
+            if !valid {
+                return Err(format!("invalid webhook_url '{url}'"));
+            }
+        }
+        let email_to = self
+            .email
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty());
+        if let Some(email) = &email_to
+            && !is_valid_email(email)
+        {
+            return Err(format!("invalid email '{email}'"));
+        }
+
+        Ok(NewAlertRule {
+            name,
+            pattern,
+            pattern_type: self.pattern_type.as_str().to_string(),
+            system_id: self.system_id,
+            talkgroup_id: self.talkgroup_id,
+            webhook_url: webhook_url.map(|u| u.trim().to_string()),
+            email_to,
+            enabled: self.enabled,
+        })
+    }
+}
+
+/// Query parameters for the alert history
+#[derive(Debug, Default, Deserialize)]
+pub struct AlertHistoryQuery {
+    /// Only alerts raised by this rule
+    pub rule_id: Option<Uuid>,
+    /// Only alerts for this system (accepts both `system_id` and `system`)
+    #[serde(alias = "system")]
+    pub system_id: Option<SystemId>,
+    /// Only alerts for this talkgroup
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only alerts raised at or after this time (ISO 8601)
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only alerts raised before this time (ISO 8601)
+    pub to_date: Option<DateTime<Utc>>,
+    /// Alerts per page (default 50, max 1000)
+    pub limit: Option<i64>,
+    /// Alerts to skip
+    pub offset: Option<i64>,
+}
+
+/// A page of alert history
+#[derive(Debug, Serialize)]
+pub struct AlertListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Alerts, newest first
+    pub alerts: Vec<Alert>,
+    /// Alerts matching the filters
+    pub total: i64,
+    /// Page size used
+    pub limit: i64,
+    /// Offset used
+    pub offset: i64,
+}
+
+/// A single alert rule
+#[derive(Debug, Serialize)]
+pub struct AlertRuleResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The rule
+    pub rule: AlertRule,
+}
+
+/// All alert rules
+#[derive(Debug, Serialize)]
+pub struct AlertRuleListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Rules, oldest first
+    pub rules: Vec<AlertRule>,
+}
+
+/// Result of deleting an alert rule
+#[derive(Debug, Serialize)]
+pub struct DeleteAlertRuleResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Success message
+    pub message: String,
+}
+
+/// Check that a rule is visible to `scope`
+///
+/// Keys limited to specific systems only see rules scoped to one of them, since
+/// a rule without a system would notify about every system's calls. Rules
+/// outside the scope are reported as missing.
+///
+/// # Errors
+///
+/// Returns error if the rule is missing or outside the scope, or the database
+/// query fails
+async fn check_rule_scope(
+    state: &AppState,
+    scope: &TenantScope,
+    rule_id: Uuid,
+) -> Result<(), ApiError> {
+    if !scope.is_restricted() {
+        return Ok(());
+    }
+    match AlertQueries::get_rule(&state.pool, rule_id).await {
+        Ok(Some(rule)) if scope.covers(rule.system_id.as_ref()) => Ok(()),
+        Ok(_) => Err(ApiError::not_found(
+            "ALERT_RULE_NOT_FOUND",
+            format!("Alert rule {rule_id} not found"),
+        )),
+        Err(e) => {
+            error!("Failed to look up alert rule {rule_id}: {e}");
+            Err(ApiError::database(format!(
+                "Failed to look up alert rule: {e}"
+            )))
+        }
+    }
+}
+
+/// List raised alerts, newest first
+///
+/// # Errors
+///
+/// Returns error if the paging parameters are invalid or the query fails
+pub async fn list_alerts(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<AlertHistoryQuery>,
+) -> Result<Json<AlertListResponse>, ApiError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    if !(1..=MAX_LIMIT).contains(&limit) || offset < 0 {
+        return Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            format!("limit must be 1 to {MAX_LIMIT} and offset non-negative"),
+        ));
+    }
+    let filter = AlertFilter {
+        rule_id: query.rule_id,
+        system_id: query.system_id,
+        allowed_systems: scope.systems().map(<[SystemId]>::to_vec),
+        talkgroup_id: query.talkgroup_id,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
+
+    let result = async {
+        let alerts = AlertQueries::list(&state.pool, &filter, limit, offset).await?;
+        let total = AlertQueries::count(&state.pool, &filter).await?;
+        Ok::<_, sdrtrunk_storage::StorageError>((alerts, total))
+    }
+    .await;
+    match result {
+        Ok((alerts, total)) => Ok(Json(AlertListResponse {
+            success: true,
+            alerts,
+            total,
+            limit,
+            offset,
+        })),
+        Err(e) => {
+            error!("Failed to list alerts: {e}");
+            Err(ApiError::database(format!("Failed to list alerts: {e}")))
+        }
+    }
+}
+
+/// List alert rules
+///
+/// # Errors
+///
+/// Returns error if the database query fails
+pub async fn list_alert_rules(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+) -> Result<Json<AlertRuleListResponse>, ApiError> {
+    match AlertQueries::list_rules(&state.pool).await {
+        Ok(rules) => Ok(Json(AlertRuleListResponse {
+            success: true,
+            rules: rules
+                .into_iter()
+                .filter(|rule| scope.covers(rule.system_id.as_ref()))
+                .collect(),
+        })),
+        Err(e) => {
+            error!("Failed to list alert rules: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list alert rules: {e}"
+            )))
+        }
+    }
+}
+
+/// Create an alert rule
+///
+/// # Errors
+///
+/// Returns error if the rule is invalid, outside the API key's systems, or the
+/// database query fails
+pub async fn create_alert_rule(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Json(request): Json<CreateAlertRuleRequest>,
+) -> Result<Json<AlertRuleResponse>, ApiError> {
+    let rule = request
+        .into_rule()
+        .map_err(|error| ApiError::bad_request("INVALID_PARAMETERS", error))?;
+    if !scope.covers(rule.system_id.as_ref()) {
+        return Err(ApiError::forbidden(
+            "SYSTEM_NOT_ALLOWED",
+            "API key may only create rules for one of its allowed systems; set system_id",
+        ));
+    }
+
+    match AlertQueries::create_rule(&state.pool, &rule).await {
+        Ok(rule) => {
+            info!("Created alert rule '{}' ({})", rule.name, rule.id);
+            Ok(Json(AlertRuleResponse {
+                success: true,
+                rule,
+            }))
+        }
+        Err(e) => {
+            error!("Failed to create alert rule: {e}");
+            Err(ApiError::database(format!(
+                "Failed to create alert rule: {e}"
+            )))
+        }
+    }
+}
+
+/// Delete an alert rule; alerts it raised stay in the history
+///
+/// # Errors
+///
+/// Returns error if the rule does not exist or the database query fails
+pub async fn delete_alert_rule(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(rule_id): Path<Uuid>,
+) -> Result<Json<DeleteAlertRuleResponse>, ApiError> {
+    check_rule_scope(&state, &scope, rule_id).await?;
+
+    match AlertQueries::delete_rule(&state.pool, rule_id).await {
+        Ok(true) => {
+            info!("Deleted alert rule {rule_id}");
+            Ok(Json(DeleteAlertRuleResponse {
+                success: true,
+                message: format!("Alert rule {rule_id} deleted"),
+            }))
+        }
+        Ok(false) => Err(ApiError::not_found(
+            "ALERT_RULE_NOT_FOUND",
+            format!("Alert rule {rule_id} not found"),
+        )),
+        Err(e) => {
+            error!("Failed to delete alert rule {rule_id}: {e}");
+            Err(ApiError::database(format!(
+                "Failed to delete alert rule: {e}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> CreateAlertRuleRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_create_rule_defaults() {
+        let rule = request(serde_json::json!({
+            "name": " Fire ",
+            "pattern": "structure fire",
+            "webhook_url": "https://hooks.example.com/alert",
+        }))
+        .into_rule()
+        .unwrap();
+
+        assert_eq!(rule.name, "Fire");
+        assert_eq!(rule.pattern_type, "keyword");
+        assert!(rule.enabled);
+        assert_eq!(
+            rule.webhook_url.as_deref(),
+            Some("https://hooks.example.com/alert")
+        );
+        assert!(rule.email_to.is_none());
+    }
+
+    #[test]
+    fn test_create_rule_validation() {
+        let invalid = [
+            serde_json::json!({ "name": "", "pattern": "fire" }),
+            serde_json::json!({ "name": "x", "pattern": "(", "pattern_type": "regex" }),
+            serde_json::json!({ "name": "x", "pattern": "fire", "webhook_url": "ftp://host/x" }),
+            serde_json::json!({ "name": "x", "pattern": "fire", "email": "not-an-address" }),
+        ];
+        for json in invalid {
+            assert!(request(json.clone()).into_rule().is_err(), "{json}");
+        }
+        assert!(
+            serde_json::from_value::<CreateAlertRuleRequest>(serde_json::json!({
+                "name": "x", "pattern": "fire", "pattern_type": "glob"
+            }))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Login session handlers
+//!
+//! Users sign in with a username and password and get back a session token,
+//! presented like an API key (`Authorization: Bearer` or `X-API-Key`) until
+//! it expires after `security.session_ttl_hours` or they sign out.
+
+use crate::{
+    error::ApiError,
+    handlers::admin::{DeleteApiKeyResponse, hash_api_key},
+    handlers::keys::presented_api_key,
+    state::AppState,
+    tenant::TenantScope,
+};
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{SaltString, rand_core::OsRng},
+};
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::HeaderMap,
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{User, UserQueries};
+use sdrtrunk_types::{SystemId, UserRole};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Request to sign in
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    /// Login name
+    pub username: String,
+    /// Password
+    pub password: String,
+}
+
+/// Response for a successful sign-in
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Session token to present as `Authorization: Bearer <token>`
+    pub token: String,
+    /// When the session ends
+    pub expires_at: DateTime<Utc>,
+    /// The signed-in user
+    pub user: User,
+}
+
+/// Who a request is acting as
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The signed-in user, when a session token was presented
+    pub user: Option<User>,
+    /// The request's role
+    pub role: UserRole,
+    /// Systems the request may see (`None` for every system)
+    #[serde(default)]
+    pub allowed_systems: Option<Vec<SystemId>>,
+}
+
+/// Hash a password as an Argon2id PHC string
+///
+/// # Errors
+///
+/// Returns error if hashing fails
+pub(crate) fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// Whether `password` matches the stored PHC string
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+/// Sign in and start a session
+///
+/// # Errors
+///
+/// Returns 401 for an unknown user, a wrong password, or a disabled account,
+/// and 500 if the database operation fails
+pub async fn login(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let user = check_credentials(&state, &request).await?;
+
+    // Two v4 UUIDs give 244 random bits
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let ttl_hours = i64::try_from(state.config.security.session_ttl_hours).unwrap_or(i64::MAX);
+    let expires_at = Utc::now() + chrono::Duration::hours(ttl_hours.min(24 * 365));
+    if let Err(e) =
+        UserQueries::create_session(&state.pool, user.id, &hash_api_key(&token), expires_at).await
+    {
+        error!("Failed to start session for user {}: {e}", user.username);
+        return Err(ApiError::database(format!("Failed to sign in: {e}")));
+    }
+
+    info!("User {} signed in", user.username);
+    Ok(Json(LoginResponse {
+        success: true,
+        token,
+        expires_at,
+        user,
+    }))
+}
+
+/// Find the active user the request's username and password belong to
+///
+/// # Errors
+///
+/// Returns the rejection for bad credentials, or when the lookup
+/// fails
+async fn check_credentials(state: &AppState, request: &LoginRequest) -> Result<User, ApiError> {
+    let user = match UserQueries::find_by_username(&state.pool, &request.username).await {
+        Ok(user) => user,
+        Err(e) => {
+            error!("Failed to look up user {}: {e}", request.username);
+            return Err(ApiError::database(format!("Failed to sign in: {e}")));
+        }
+    };
+
+    user.filter(|user| user.active && verify_password(&request.password, &user.password_hash))
+        .ok_or_else(|| {
+            warn!("Failed sign-in for user {}", request.username);
+            ApiError::unauthorized("INVALID_CREDENTIALS", "Invalid username or password")
+        })
+}
+
+/// End the presented session
+///
+/// # Errors
+///
+/// Returns error if no session token was presented or the database operation
+/// fails
+pub async fn logout(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<DeleteApiKeyResponse>, ApiError> {
+    let Some(token) = presented_api_key(&headers) else {
+        return Err(ApiError::unauthorized(
+            "UNAUTHORIZED",
+            "No session token presented",
+        ));
+    };
+
+    match UserQueries::delete_session(&state.pool, &hash_api_key(token)).await {
+        Ok(true) => Ok(Json(DeleteApiKeyResponse {
+            success: true,
+            message: "Signed out".to_string(),
+        })),
+        Ok(false) => Err(ApiError::not_found(
+            "SESSION_NOT_FOUND",
+            "No active session for this token",
+        )),
+        Err(e) => {
+            error!("Failed to end session: {e}");
+            Err(ApiError::database(format!("Failed to sign out: {e}")))
+        }
+    }
+}
+
+/// Report the signed-in user, role, and system scope of the request
+pub async fn current_session(
+    user: Option<Extension<User>>,
+    role: Option<Extension<UserRole>>,
+    scope: Option<Extension<TenantScope>>,
+) -> Json<SessionResponse> {
+    Json(SessionResponse {
+        success: true,
+        user: user.map(|Extension(user)| user),
+        role: role.map_or(UserRole::ReadOnly, |Extension(role)| role),
+        allowed_systems: scope.and_then(|Extension(scope)| scope.systems().map(<[_]>::to_vec)),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_hash_roundtrip() {
+        let hash = hash_password("correct horse battery staple").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("correct horse battery staple", &hash));
+        assert!(!verify_password("wrong", &hash));
+        assert!(!verify_password("anything", "not a phc string"));
+    }
+}
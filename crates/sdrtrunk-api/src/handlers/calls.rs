@@ -1,41 +1,175 @@
 //! Call listing and retrieval endpoints
 
-use crate::state::AppState;
+use crate::{
+    error::{ApiError, ErrorResponse},
+    middleware::caching::{Validators, not_modified},
+    state::AppState,
+    subtitles,
+    tenant::TenantScope,
+    waveform,
+};
+use axum::body::Bytes;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    Extension,
+    body::Body,
+    extract::{Path, Query, Request, State},
+    http::{HeaderValue, header},
+    response::{IntoResponse, Json, Response},
+};
+use sdrtrunk_protocol::call_query::CallQuery;
+use sdrtrunk_storage::{
+    AudioStorage, CallCursor, CallEvent, CallEventQueries, CallWaveform, SegmentQueries,
+    SpeakerSegment, SpeakerTalkTime, SummaryQueries, TagQueries, TranscriptionSegment, User,
+    WaveformQueries,
+    models::{ApiKeyDb, RadioCallDb},
+    queries::RadioCallQueries,
 };
-use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId, UserRole};
 use serde::{Deserialize, Serialize};
+use std::path::Path as FsPath;
+use std::process::Stdio;
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
 use tracing::{error, info, warn};
+use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
+/// `Cache-Control` for recordings, which never change once stored
+const AUDIO_CACHE_CONTROL: &str = "private, max-age=86400";
+
+/// Browser-playable formats a recording can be transcoded to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// MPEG audio layer III
+    Mp3,
+    /// Opus in an Ogg container
+    Ogg,
+    /// 16-bit PCM WAV
+    Wav,
+}
+
+impl AudioFormat {
+    /// MIME type of audio in this format
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Mp3 => "audio/mpeg",
+            Self::Ogg => "audio/ogg",
+            Self::Wav => "audio/wav",
+        }
+    }
+
+    /// File extension of audio in this format
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Ogg => "ogg",
+            Self::Wav => "wav",
+        }
+    }
+
+    /// Whether a stored file is already in this format, judged by extension
+    #[must_use]
+    pub fn matches(self, path: &FsPath) -> bool {
+        let extensions: &[&str] = match self {
+            Self::Mp3 => &["mp3"],
+            Self::Ogg => &["ogg", "opus"],
+            Self::Wav => &["wav"],
+        };
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+    }
+
+    /// ffmpeg output options producing this format
+    const fn ffmpeg_args(self) -> &'static [&'static str] {
+        match self {
+            Self::Mp3 => &["-codec:a", "libmp3lame", "-q:a", "4", "-f", "mp3"],
+            Self::Ogg => &["-codec:a", "libopus", "-b:a", "32k", "-f", "ogg"],
+            Self::Wav => &["-codec:a", "pcm_s16le", "-f", "wav"],
+        }
+    }
+}
+
+/// Query parameters for fetching call audio
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CallAudioQuery {
+    /// Transcode to this format instead of serving the stored file
+    pub format: Option<AudioFormat>,
+}
+
+/// Formats a call transcript can be exported in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// Segments as JSON
+    #[default]
+    Json,
+    /// `SubRip` subtitles
+    Srt,
+    /// `WebVTT` subtitles
+    Vtt,
+}
+
+/// Query parameters for fetching a call transcript
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CallTranscriptQuery {
+    /// Output format (default `json`)
+    pub format: Option<TranscriptFormat>,
+}
+
 /// Query parameters for listing calls
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListCallsQuery {
     /// Number of calls to return (max 1000)
     #[validate(range(min = 1, max = 1000))]
+    #[param(minimum = 1, maximum = 1000, default = 50)]
     pub limit: Option<i64>,
 
-    /// Offset for pagination
-    #[validate(range(min = 0))]
-    pub offset: Option<i64>,
+    /// Resume after this call: the previous page's `next_cursor`
+    /// (`<timestamp>,<call id>`)
+    pub after: Option<String>,
 
     /// Filter by system ID (accepts both `system_id` and `system` query params)
     #[serde(alias = "system")]
-    #[validate(length(max = 50))]
-    pub system_id: Option<String>,
+    #[param(value_type = Option<String>)]
+    pub system_id: Option<SystemId>,
 
     /// Filter by talkgroup ID
-    pub talkgroup_id: Option<i32>,
+    #[param(value_type = Option<i32>)]
+    pub talkgroup_id: Option<TalkgroupId>,
 
-    /// Filter by transcription status (pending, processing, completed, failed)
+    /// Filter by transcription status (pending, processing, completed, `needs_review`, failed,
+    /// skipped)
     #[validate(custom(function = "validate_transcription_status"))]
+    #[param(pattern = "^(pending|processing|completed|needs_review|failed)$")]
     pub transcription_status: Option<String>,
 
+    /// Only calls carrying this tag (case-insensitive)
+    pub tag: Option<String>,
+
+    /// Search expression, e.g. `system:butler AND tg:1234..1299 AND text~"pursuit"`;
+    /// plain words match transcript or summary text (case-insensitive; accepts both
+    /// `q` and `search`)
+    #[serde(alias = "search")]
+    pub q: Option<String>,
+
+    /// Only calls transcribed in this language, e.g. `en` (case-insensitive;
+    /// also matches regional codes such as `en-US`)
+    #[validate(length(max = 10))]
+    pub language: Option<String>,
+
+    /// List archived calls instead of live ones
+    pub archived: Option<bool>,
+
     /// Filter calls from this date (ISO 8601 format)
     pub from_date: Option<chrono::DateTime<chrono::Utc>>,
 
@@ -51,7 +185,7 @@ pub struct ListCallsQuery {
 }
 
 /// Response for listing calls
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ListCallsResponse {
     /// List of radio calls
     pub calls: Vec<CallSummary>,
@@ -62,31 +196,22 @@ pub struct ListCallsResponse {
     /// Number of calls returned
     pub count: i64,
 
-    /// Current offset
-    pub offset: i64,
-
     /// Pagination info
     pub pagination: PaginationInfo,
 }
 
 /// Pagination information
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PaginationInfo {
     /// Whether there are more results
     pub has_next: bool,
 
-    /// Whether there are previous results  
-    pub has_prev: bool,
-
-    /// Next page offset
-    pub next_offset: Option<i64>,
-
-    /// Previous page offset
-    pub prev_offset: Option<i64>,
+    /// Pass as `after` to get the next page; stays valid as new calls arrive
+    pub next_cursor: Option<String>,
 }
 
 /// Simplified call information for listings
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CallSummary {
     /// Call ID
     pub id: Uuid,
@@ -95,11 +220,13 @@ pub struct CallSummary {
     pub call_timestamp: chrono::DateTime<chrono::Utc>,
 
     /// System information
+    #[schema(value_type = String)]
     pub system_id: SystemId,
     /// System display name or label
     pub system_label: Option<String>,
 
     /// Talkgroup information
+    #[schema(value_type = Option<i32>)]
     pub talkgroup_id: Option<TalkgroupId>,
     /// Talkgroup display name or label
     pub talkgroup_label: Option<String>,
@@ -109,6 +236,7 @@ pub struct CallSummary {
     pub talkgroup_tag: Option<String>,
 
     /// Radio information
+    #[schema(value_type = Option<i32>)]
     pub source_radio_id: Option<RadioId>,
     /// Radio user's alias or call sign
     pub talker_alias: Option<String>,
@@ -124,17 +252,49 @@ pub struct CallSummary {
     pub transcription_status: Option<String>,
     /// Confidence score for transcription (0.0-1.0)
     pub transcription_confidence: Option<rust_decimal::Decimal>,
+    /// Language the transcription was made in (e.g. `en`)
+    pub transcription_language: Option<String>,
 
     /// Transcription text (only if requested)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transcription_text: Option<String>,
 
     /// Frequency
+    #[schema(value_type = Option<i64>)]
     pub frequency: Option<Frequency>,
+
+    /// Tags analysts attached to the call, in alphabetical order
+    pub tags: Vec<String>,
+}
+
+impl From<RadioCallDb> for CallSummary {
+    fn from(call: RadioCallDb) -> Self {
+        Self {
+            id: call.id,
+            call_timestamp: call.call_timestamp,
+            system_id: call.system_id,
+            system_label: call.system_label,
+            talkgroup_id: call.talkgroup_id,
+            talkgroup_label: call.talkgroup_label,
+            talkgroup_group: call.talkgroup_group,
+            talkgroup_tag: call.talkgroup_tag,
+            source_radio_id: call.source_radio_id,
+            talker_alias: call.talker_alias,
+            audio_filename: call.audio_filename,
+            audio_size_bytes: call.audio_size_bytes,
+            duration_seconds: call.duration_seconds,
+            transcription_status: call.transcription_status,
+            transcription_confidence: call.transcription_confidence,
+            transcription_language: call.transcription_language,
+            transcription_text: call.transcription_text,
+            frequency: call.frequency,
+            tags: Vec::new(),
+        }
+    }
 }
 
 /// Detailed call information
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CallDetail {
     /// Call ID
     pub id: Uuid,
@@ -145,11 +305,13 @@ pub struct CallDetail {
     pub call_timestamp: chrono::DateTime<chrono::Utc>,
 
     /// System information
+    #[schema(value_type = String)]
     pub system_id: SystemId,
     /// System display name or label
     pub system_label: Option<String>,
 
     /// Talkgroup information
+    #[schema(value_type = Option<i32>)]
     pub talkgroup_id: Option<TalkgroupId>,
     /// Talkgroup display name or label
     pub talkgroup_label: Option<String>,
@@ -159,6 +321,7 @@ pub struct CallDetail {
     pub talkgroup_tag: Option<String>,
 
     /// Radio information
+    #[schema(value_type = Option<i32>)]
     pub source_radio_id: Option<RadioId>,
     /// Radio user's alias or call sign
     pub talker_alias: Option<String>,
@@ -188,6 +351,7 @@ pub struct CallDetail {
     pub speaker_count: Option<i32>,
 
     /// Radio frequency used for the call
+    #[schema(value_type = Option<i64>)]
     pub frequency: Option<Frequency>,
     /// Patch group information
     pub patches: Option<String>,
@@ -199,30 +363,219 @@ pub struct CallDetail {
     /// When the call was uploaded
     pub upload_timestamp: chrono::DateTime<chrono::Utc>,
     /// IP address of the uploader
+    #[schema(value_type = Option<String>)]
     pub upload_ip: Option<sqlx::types::ipnetwork::IpNetwork>,
     /// API key used for the upload
     pub upload_api_key_id: Option<String>,
+
+    /// Latitude where the call was heard (WGS 84 degrees)
+    pub latitude: Option<f64>,
+    /// Longitude where the call was heard
+    pub longitude: Option<f64>,
+}
+
+/// One speaker turn within a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SpeakerSegmentInfo {
+    /// Speaker label, unique within the call (e.g. `SPEAKER_00`)
+    pub speaker: String,
+    /// Start of the turn in seconds
+    pub start: f64,
+    /// End of the turn in seconds
+    pub end: f64,
+    /// Length of the turn in seconds
+    pub duration: f64,
+    /// Diarization confidence, when reported
+    pub confidence: Option<f64>,
+}
+
+/// Talk time of one speaker within a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SpeakerInfo {
+    /// Speaker label
+    pub speaker: String,
+    /// Number of turns taken
+    pub segment_count: usize,
+    /// Total speaking time in seconds
+    pub talk_seconds: f64,
+}
+
+/// Speaker diarization result for a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallSpeakersResponse {
+    /// Call ID
+    pub call_id: Uuid,
+    /// Current transcription processing status
+    pub transcription_status: Option<String>,
+    /// Number of unique speakers detected
+    pub speaker_count: Option<i32>,
+    /// Per-speaker talk time, most talkative first
+    pub speakers: Vec<SpeakerInfo>,
+    /// Speaker turns in start order
+    pub segments: Vec<SpeakerSegmentInfo>,
+}
+
+/// One timed piece of a call transcript
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TranscriptSegmentInfo {
+    /// Start of the segment in seconds
+    pub start: f64,
+    /// End of the segment in seconds
+    pub end: f64,
+    /// Transcribed text
+    pub text: String,
+    /// Speaker label, when diarized
+    pub speaker: Option<String>,
+    /// Transcription confidence, when reported
+    pub confidence: Option<f32>,
+}
+
+impl From<TranscriptionSegment> for TranscriptSegmentInfo {
+    fn from(segment: TranscriptionSegment) -> Self {
+        Self {
+            start: segment.start_seconds,
+            end: segment.end_seconds,
+            text: segment.text,
+            speaker: segment.speaker,
+            confidence: segment.confidence,
+        }
+    }
+}
+
+/// Timed transcript of a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallTranscriptResponse {
+    /// Call ID
+    pub call_id: Uuid,
+    /// Current transcription processing status
+    pub transcription_status: Option<String>,
+    /// Transcription language
+    pub language: Option<String>,
+    /// Segments in start order
+    pub segments: Vec<TranscriptSegmentInfo>,
+}
+
+/// One processing step of a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallEventInfo {
+    /// `received`, `stored`, `queued`, `claimed`, `transcribed`, or `failed`
+    pub event: String,
+    /// Step-specific detail such as the job ID, worker, or error
+    pub detail: Option<String>,
+    /// When the step happened
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
 }
 
-/// Error response structure
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    /// Error message
-    pub error: String,
-    /// Error code  
-    pub code: String,
-    /// Additional details
-    pub details: Option<serde_json::Value>,
+impl From<CallEvent> for CallEventInfo {
+    fn from(event: CallEvent) -> Self {
+        Self {
+            event: event.event,
+            detail: event.detail,
+            occurred_at: event.occurred_at,
+        }
+    }
+}
+
+/// Processing timeline of a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallEventsResponse {
+    /// Call ID
+    pub call_id: Uuid,
+    /// Current transcription processing status
+    pub transcription_status: Option<String>,
+    /// Events, oldest first
+    pub events: Vec<CallEventInfo>,
+}
+
+/// A call cleared from the review queue
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewCallResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Call ID
+    pub call_id: Uuid,
+    /// Transcription status after the review (`completed`)
+    pub transcription_status: String,
+}
+
+/// Waveform peaks of a call's recording
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallWaveformResponse {
+    /// Call ID
+    pub call_id: Uuid,
+    /// Length of the recording in seconds
+    pub duration_seconds: f64,
+    /// Loudest sample of each equal slice of the recording, scaled so the
+    /// loudest overall is 1.0
+    pub peaks: Vec<f32>,
+}
+
+impl From<CallWaveform> for CallWaveformResponse {
+    fn from(waveform: CallWaveform) -> Self {
+        Self {
+            call_id: waveform.call_id,
+            duration_seconds: waveform.duration_seconds,
+            peaks: waveform.peaks.0,
+        }
+    }
+}
+
+/// LLM-written summary of a call's transcript
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TranscriptSummaryResponse {
+    /// Call ID
+    pub call_id: Uuid,
+    /// One or two sentences saying what happened
+    pub summary: String,
+    /// Units, people, locations, vehicles, and incidents the call names
+    pub entities: Vec<SummaryEntityInfo>,
+    /// Model that wrote the summary
+    pub model: String,
+    /// When the summary was written
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Something a call mentions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SummaryEntityInfo {
+    /// What the entity is (`unit`, `person`, `location`, `vehicle`,
+    /// `incident`, or `other`)
+    pub kind: String,
+    /// The entity as named in the call
+    pub text: String,
+}
+
+impl From<sdrtrunk_storage::CallSummary> for TranscriptSummaryResponse {
+    fn from(summary: sdrtrunk_storage::CallSummary) -> Self {
+        Self {
+            call_id: summary.call_id,
+            summary: summary.summary,
+            entities: summary
+                .entities
+                .0
+                .into_iter()
+                .map(|entity| SummaryEntityInfo {
+                    kind: entity.kind,
+                    text: entity.text,
+                })
+                .collect(),
+            model: summary.model,
+            created_at: summary.created_at,
+        }
+    }
 }
 
 /// List radio calls with filtering and pagination
 ///
 /// This endpoint provides paginated access to radio calls with comprehensive filtering options.
 /// Supports filtering by system, talkgroup, date ranges, and optional transcription inclusion.
+/// Requests made with an API key only see calls from the key's allowed systems.
 ///
 /// # Arguments
 ///
 /// * `state` - Application state containing database pool and configuration
+/// * `scope` - Systems the caller's API key may access
+/// * `role` - Caller's role, deciding whether encrypted fields are decrypted
 /// * `query` - Query parameters for filtering and pagination
 ///
 /// # Returns
@@ -238,8 +591,22 @@ pub struct ErrorResponse {
 /// # Example
 ///
 /// ```text
-/// GET /api/calls?system_id=police&limit=50&offset=0&include_transcription=true
+/// GET /api/calls?system_id=police&limit=50&after=2025-03-01T12:30:45.123456Z,6f1c...&include_transcription=true
 /// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls",
+    tag = "Calls",
+    summary = "List radio calls",
+    description = "Paginated radio calls, newest first. API keys limited to specific systems only see calls from those systems.",
+    params(ListCallsQuery),
+    responses(
+        (status = 200, description = "List of calls", body = ListCallsResponse),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
 #[allow(
     clippy::too_many_lines,
     clippy::cognitive_complexity,
@@ -247,66 +614,105 @@ pub struct ErrorResponse {
 )]
 pub async fn list_calls(
     State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    role: Option<Extension<UserRole>>,
     Query(query): Query<ListCallsQuery>,
-) -> Result<Json<ListCallsResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<ListCallsResponse>, ApiError> {
     // Validate query parameters
     if let Err(validation_errors) = query.validate() {
         warn!("Invalid query parameters: {:?}", validation_errors);
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Invalid query parameters".to_string(),
-                code: "INVALID_PARAMETERS".to_string(),
-                details: Some(serde_json::json!(validation_errors)),
-            }),
-        ));
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
     }
 
     let limit = query.limit.unwrap_or(50).min(1000); // Default 50, max 1000
-    let offset = query.offset.unwrap_or(0);
     let include_transcription = query.include_transcription.unwrap_or(false);
+    let after = match query
+        .after
+        .as_deref()
+        .map(str::parse::<CallCursor>)
+        .transpose()
+    {
+        Ok(after) => after,
+        Err(e) => {
+            warn!("Invalid call cursor: {}", e);
+            return Err(ApiError::bad_request(
+                "INVALID_PARAMETERS",
+                "Invalid cursor; pass a next_cursor from a previous page",
+            ));
+        }
+    };
 
     info!(
-        "Listing calls: limit={}, offset={}, system_id={:?}",
-        limit, offset, query.system_id
+        "Listing calls: limit={}, after={:?}, system_id={:?}",
+        limit, query.after, query.system_id
     );
-
-    // Build query with filters
+    let tag = query.tag.as_deref().map(|t| t.trim().to_lowercase());
+    let search = match query
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(str::parse::<CallQuery>)
+        .transpose()
+    {
+        Ok(search) => search,
+        Err(e) => {
+            warn!("Invalid search expression: {}", e);
+            return Err(ApiError::bad_request("INVALID_QUERY", e.to_string()));
+        }
+    };
+    let language = query
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|l| !l.is_empty());
+
+    // Build query with filters, fetching one extra call to see whether
+    // another page follows
     let filter = sdrtrunk_storage::RadioCallFilter {
-        system_id: query.system_id.as_deref(),
+        system_id: query.system_id.as_ref(),
+        allowed_systems: scope.systems(),
         talkgroup_id: query.talkgroup_id,
         transcription_status: query.transcription_status.as_deref(),
+        tag: tag.as_deref(),
+        keyword: None,
+        language,
+        search: search.as_ref(),
+        archived: query.archived.unwrap_or(false),
         from_date: query.from_date,
         to_date: query.to_date,
-        limit,
-        offset,
-    };
-    let calls = match sdrtrunk_storage::list_radio_calls_filtered(&state.pool, filter).await {
-        Ok(calls) => calls,
-        Err(e) => {
-            error!("Failed to list calls: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve calls".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                    details: None,
-                }),
-            ));
-        }
+        limit: limit + 1,
+        after,
     };
+    let mut calls =
+        match sdrtrunk_storage::list_radio_calls_filtered(&state.read_pool, filter).await {
+            Ok(calls) => calls,
+            Err(e) => {
+                error!("Failed to list calls: {}", e);
+                return Err(ApiError::database("Failed to retrieve calls"));
+            }
+        };
 
     // Get total count for pagination
     let filter = sdrtrunk_storage::RadioCallFilter {
-        system_id: query.system_id.as_deref(),
+        system_id: query.system_id.as_ref(),
+        allowed_systems: scope.systems(),
         talkgroup_id: query.talkgroup_id,
         transcription_status: query.transcription_status.as_deref(),
+        tag: tag.as_deref(),
+        keyword: None,
+        language,
+        search: search.as_ref(),
+        archived: query.archived.unwrap_or(false),
         from_date: query.from_date,
         to_date: query.to_date,
-        limit: 0,  // Not used for count
-        offset: 0, // Not used for count
+        limit: 0,    // Not used for count
+        after: None, // Not used for count
     };
-    let total = match sdrtrunk_storage::count_radio_calls_filtered(&state.pool, filter).await {
+    let total = match sdrtrunk_storage::count_radio_calls_filtered(&state.read_pool, filter).await {
         Ok(count) => count,
         Err(e) => {
             warn!("Failed to get total count: {}", e);
@@ -314,64 +720,69 @@ pub async fn list_calls(
         }
     };
 
+    let has_next = calls.len() as i64 > limit;
+    calls.truncate(usize::try_from(limit).unwrap_or_default());
+    let next_cursor = calls
+        .last()
+        .filter(|_| has_next)
+        .map(|call| CallCursor::after(call).to_string());
+    if let Some(encryption) = state.encryption.as_deref() {
+        encryption
+            .reveal(&state.read_pool, caller_role(role), &mut calls)
+            .await;
+    }
+
     // Convert to summary format
-    let call_summaries: Vec<CallSummary> = calls
+    let mut call_summaries: Vec<CallSummary> = calls
         .into_iter()
-        .map(|call| CallSummary {
-            id: call.id,
-            call_timestamp: call.call_timestamp,
-            system_id: call.system_id,
-            system_label: call.system_label,
-            talkgroup_id: call.talkgroup_id,
-            talkgroup_label: call.talkgroup_label,
-            talkgroup_group: call.talkgroup_group,
-            talkgroup_tag: call.talkgroup_tag,
-            source_radio_id: call.source_radio_id,
-            talker_alias: call.talker_alias,
-            audio_filename: call.audio_filename,
-            audio_size_bytes: call.audio_size_bytes,
-            duration_seconds: call.duration_seconds,
-            transcription_status: call.transcription_status,
-            transcription_confidence: call.transcription_confidence,
-            transcription_text: if include_transcription {
-                call.transcription_text
-            } else {
-                None
-            },
-            frequency: call.frequency,
+        .map(|call| {
+            let mut summary = CallSummary::from(call);
+            if !include_transcription {
+                summary.transcription_text = None;
+            }
+            summary
         })
         .collect();
+    attach_tags(&state, &mut call_summaries).await;
 
     let count = call_summaries.len() as i64;
 
-    // Calculate pagination info
-    let pagination = PaginationInfo {
-        has_next: offset + limit < total,
-        has_prev: offset > 0,
-        next_offset: if offset + limit < total {
-            Some(offset + limit)
-        } else {
-            None
-        },
-        prev_offset: if offset > 0 {
-            Some((offset - limit).max(0))
-        } else {
-            None
-        },
-    };
-
     let response = ListCallsResponse {
         calls: call_summaries,
         total,
         count,
-        offset,
-        pagination,
+        pagination: PaginationInfo {
+            has_next,
+            next_cursor,
+        },
     };
 
     info!("Returned {} calls out of {} total", count, total);
     Ok(Json(response))
 }
 
+/// Role of the caller, or the least privileged one if none was resolved
+pub(crate) fn caller_role(role: Option<Extension<UserRole>>) -> UserRole {
+    role.map_or(UserRole::ReadOnly, |Extension(role)| role)
+}
+
+/// Fill in the tags of listed calls, leaving them empty if the lookup fails
+async fn attach_tags(state: &AppState, calls: &mut [CallSummary]) {
+    let ids: Vec<Uuid> = calls.iter().map(|call| call.id).collect();
+    let tags = match TagQueries::names_for_calls(&state.read_pool, &ids).await {
+        Ok(tags) => tags,
+        Err(e) => {
+            warn!("Failed to load call tags: {}", e);
+            return;
+        }
+    };
+    for (call_id, tag) in tags {
+        if let Some(call) = calls.iter_mut().find(|call| call.id == call_id) {
+            call.tags.push(tag);
+        }
+    }
+}
+
 /// Get detailed information for a specific radio call
 ///
 /// Retrieves complete details for a radio call including audio metadata, transcription
@@ -380,6 +791,8 @@ pub async fn list_calls(
 /// # Arguments
 ///
 /// * `state` - Application state containing database pool
+/// * `scope` - Systems the caller's API key may access
+/// * `role` - Caller's role, deciding whether encrypted fields are decrypted
 /// * `call_id` - UUID of the radio call to retrieve
 ///
 /// # Returns
@@ -389,7 +802,7 @@ pub async fn list_calls(
 ///
 /// # Errors
 ///
-/// * `NOT_FOUND` - Call with specified ID does not exist
+/// * `NOT_FOUND` - Call with specified ID does not exist or is outside the API key's systems
 /// * `INTERNAL_SERVER_ERROR` - Database query failure
 ///
 /// # Example
@@ -397,38 +810,53 @@ pub async fn list_calls(
 /// ```text
 /// GET /api/calls/550e8400-e29b-41d4-a716-446655440000
 /// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}",
+    tag = "Calls",
+    summary = "Get call details",
+    description = "Full details of a radio call, including transcription and speaker segments.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Call details", body = CallDetail),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
 #[allow(clippy::cognitive_complexity)]
 pub async fn get_call(
     State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    role: Option<Extension<UserRole>>,
     Path(call_id): Path<Uuid>,
-) -> Result<Json<CallDetail>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<CallDetail>, ApiError> {
     info!("Retrieving call: {}", call_id);
 
-    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
-        Ok(Some(call)) => call,
-        Ok(None) => {
+    // Calls outside the key's systems are reported as missing
+    let mut call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => call,
+        Ok(_) => {
             info!("Call not found: {}", call_id);
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Call {call_id} not found"),
-                    code: "CALL_NOT_FOUND".to_string(),
-                    details: None,
-                }),
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
             ));
         }
         Err(e) => {
             error!("Failed to retrieve call {}: {}", call_id, e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve call".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                    details: None,
-                }),
-            ));
+            return Err(ApiError::database("Failed to retrieve call"));
         }
     };
+    if let Some(encryption) = state.encryption.as_deref() {
+        encryption
+            .reveal(
+                &state.pool,
+                caller_role(role),
+                std::slice::from_mut(&mut call),
+            )
+            .await;
+    }
 
     // Convert to detailed format
     let call_detail = CallDetail {
@@ -461,12 +889,824 @@ pub async fn get_call(
         upload_timestamp: call.upload_timestamp,
         upload_ip: call.upload_ip,
         upload_api_key_id: call.upload_api_key_id,
+        latitude: call.latitude,
+        longitude: call.longitude,
     };
 
     info!("Successfully retrieved call: {}", call_id);
     Ok(Json(call_detail))
 }
 
+/// Get the speaker diarization result for a call
+///
+/// Returns the call's speaker turns together with each speaker's total talk
+/// time. Calls that have not been diarized return empty lists. When the
+/// stored `speaker_count` is missing it is derived from the segments.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist or is outside the API key's systems
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/speakers
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/speakers",
+    tag = "Calls",
+    summary = "Get call speakers",
+    description = "Speaker diarization segments and per-speaker talk time for a call.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Speaker segments", body = CallSpeakersResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_speakers(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<CallSpeakersResponse>, ApiError> {
+    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => call,
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to retrieve call {}: {}", call_id, e);
+            return Err(ApiError::database("Failed to retrieve call"));
+        }
+    };
+
+    Ok(Json(call_speakers(
+        call.id,
+        call.transcription_status,
+        call.speaker_count,
+        call.speaker_segments.as_ref(),
+    )))
+}
+
+/// Build the speaker response from a call's stored diarization columns
+pub(crate) fn call_speakers(
+    call_id: Uuid,
+    transcription_status: Option<String>,
+    speaker_count: Option<i32>,
+    speaker_segments: Option<&serde_json::Value>,
+) -> CallSpeakersResponse {
+    let segments = speaker_segments
+        .map(SpeakerSegment::parse_all)
+        .unwrap_or_default();
+    let speakers = SpeakerTalkTime::summarize(&segments);
+    let speaker_count = speaker_count.or_else(|| {
+        (!speakers.is_empty()).then(|| i32::try_from(speakers.len()).unwrap_or(i32::MAX))
+    });
+
+    CallSpeakersResponse {
+        call_id,
+        transcription_status,
+        speaker_count,
+        speakers: speakers
+            .into_iter()
+            .map(|s| SpeakerInfo {
+                speaker: s.speaker,
+                segment_count: s.segment_count,
+                talk_seconds: s.talk_seconds,
+            })
+            .collect(),
+        segments: segments
+            .into_iter()
+            .map(|s| SpeakerSegmentInfo {
+                duration: s.duration(),
+                speaker: s.speaker,
+                start: s.start,
+                end: s.end,
+                confidence: s.confidence,
+            })
+            .collect(),
+    }
+}
+
+/// Get the timed transcript of a call, as JSON or subtitles
+///
+/// Calls transcribed before segments were stored, or by a backend that
+/// reports none, get a single segment spanning the recording. Calls without a
+/// transcript return no segments.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist or is outside the API key's systems
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/transcript?format=vtt
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/transcript",
+    tag = "Calls",
+    summary = "Get call transcript",
+    description = "Timed transcript segments of a call as JSON, SubRip (`format=srt`), or WebVTT (`format=vtt`) subtitles.",
+    params(("id" = Uuid, Path, description = "Call UUID"), CallTranscriptQuery),
+    responses(
+        (status = 200, description = "Transcript segments or subtitle file", body = CallTranscriptResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_transcript(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    role: Option<Extension<UserRole>>,
+    Path(call_id): Path<Uuid>,
+    Query(query): Query<CallTranscriptQuery>,
+) -> Result<Response, ApiError> {
+    let database_error = |e: sdrtrunk_storage::StorageError| {
+        error!("Failed to retrieve transcript of call {}: {}", call_id, e);
+        ApiError::database("Failed to retrieve call transcript")
+    };
+    let mut call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => call,
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let role = caller_role(role);
+    if let Some(encryption) = state.encryption.as_deref() {
+        encryption
+            .reveal(&state.pool, role, std::slice::from_mut(&mut call))
+            .await;
+    }
+    let keys = state
+        .encryption
+        .as_deref()
+        .and_then(|encryption| encryption.segment_keys(role));
+    let stored = SegmentQueries::for_call(&state.pool, call_id, keys)
+        .await
+        .map_err(database_error)?;
+    let segments = transcript_segments(
+        stored,
+        call.transcription_text.as_deref(),
+        call.duration_seconds,
+    );
+
+    let (content_type, body) = match query.format.unwrap_or_default() {
+        TranscriptFormat::Srt => (subtitles::SRT_CONTENT_TYPE, subtitles::srt(&segments)),
+        TranscriptFormat::Vtt => (subtitles::VTT_CONTENT_TYPE, subtitles::vtt(&segments)),
+        TranscriptFormat::Json => {
+            return Ok(Json(CallTranscriptResponse {
+                call_id: call.id,
+                transcription_status: call.transcription_status,
+                language: call.transcription_language,
+                segments: segments.into_iter().map(Into::into).collect(),
+            })
+            .into_response());
+        }
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+/// Get the processing timeline of a radio call
+///
+/// Lists when the call was received, stored, queued for transcription,
+/// claimed by a worker, and transcribed or failed, to find where a call got
+/// stuck. Calls uploaded before events were recorded have none.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist or is outside the API key's systems
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/events
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/events",
+    tag = "Calls",
+    summary = "Get call processing events",
+    description = "Timestamped processing events of a call (received, stored, queued, claimed, transcribed, failed, reviewed), oldest first.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Call processing events", body = CallEventsResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_events(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<CallEventsResponse>, ApiError> {
+    let database_error = |e: sdrtrunk_storage::StorageError| {
+        error!("Failed to retrieve events of call {}: {}", call_id, e);
+        ApiError::database("Failed to retrieve call events")
+    };
+    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => call,
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => return Err(database_error(e)),
+    };
+    let events = CallEventQueries::for_call(&state.pool, call_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(CallEventsResponse {
+        call_id: call.id,
+        transcription_status: call.transcription_status,
+        events: events.into_iter().map(CallEventInfo::from).collect(),
+    }))
+}
+
+/// Mark a low-confidence transcription as reviewed
+///
+/// Moves a call flagged `needs_review` to `completed`, removing it from the
+/// review queue, and records a `reviewed` event naming who checked it.
+/// Corrections to the transcript itself go through transcription feedback.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist or is outside the API key's systems
+/// * `CONFLICT` - Call is not flagged for review
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// POST /api/calls/550e8400-e29b-41d4-a716-446655440000/review
+/// ```
+#[utoipa::path(
+    post,
+    path = "/api/calls/{id}/review",
+    tag = "Calls",
+    summary = "Mark call reviewed",
+    description = "Accept a transcription flagged `needs_review` (confidence below `transcription.min_confidence`), setting it to `completed` (analyst or admin).",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Call marked reviewed", body = ReviewCallResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 409, description = "Call is not flagged for review", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn review_call(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    api_key: Option<Extension<ApiKeyDb>>,
+    user: Option<Extension<User>>,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<ReviewCallResponse>, ApiError> {
+    let database_error = |e: sdrtrunk_storage::StorageError| {
+        error!("Failed to mark call {} reviewed: {}", call_id, e);
+        ApiError::database("Failed to mark call reviewed")
+    };
+    match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => {}
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => return Err(database_error(e)),
+    }
+
+    let reviewer = match (api_key, user) {
+        (Some(Extension(key)), _) => format!("api_key:{}", key.id),
+        (None, Some(Extension(user))) => format!("user:{}", user.username),
+        (None, None) => "anonymous".to_string(),
+    };
+    let cleared = RadioCallQueries::mark_reviewed(&state.pool, call_id, Some(&reviewer))
+        .await
+        .map_err(database_error)?;
+    if !cleared {
+        return Err(ApiError::conflict(
+            "NOT_FLAGGED_FOR_REVIEW",
+            format!("Call {call_id} is not flagged for review"),
+        ));
+    }
+    info!("Call {} marked reviewed by {}", call_id, reviewer);
+
+    Ok(Json(ReviewCallResponse {
+        success: true,
+        call_id,
+        transcription_status: "completed".to_string(),
+    }))
+}
+
+/// A call's stored transcript segments, or one segment covering the whole
+/// recording when only the transcript text is known
+pub(crate) fn transcript_segments(
+    stored: Vec<TranscriptionSegment>,
+    transcription_text: Option<&str>,
+    duration_seconds: Option<rust_decimal::Decimal>,
+) -> Vec<TranscriptionSegment> {
+    if !stored.is_empty() {
+        return stored;
+    }
+    let text = transcription_text
+        .map(str::trim)
+        .filter(|text| !text.is_empty());
+    let duration = duration_seconds.and_then(|d| f64::try_from(d).ok());
+    match (text, duration) {
+        (Some(text), Some(duration)) => vec![TranscriptionSegment {
+            start_seconds: 0.0,
+            end_seconds: duration,
+            text: text.to_string(),
+            speaker: None,
+            confidence: None,
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Get the audio recording for a radio call
+///
+/// Streams the stored file with HTTP Range support so browser players can
+/// seek; recordings in object storage are sent whole. The content type comes
+/// from the uploaded MIME type when it is an audio type, otherwise from the
+/// file extension. With `?format=mp3|ogg|wav`
+/// the recording is transcoded through ffmpeg instead; transcoded responses
+/// are sent whole (`Accept-Ranges: none`). Asking for the format the file is
+/// already in serves the stored file.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist, is outside the API key's systems, or has
+///   no readable recording
+/// * `INTERNAL_SERVER_ERROR` - Database query or transcoding failure
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/audio
+/// Range: bytes=0-65535
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/audio",
+    tag = "Calls",
+    summary = "Get call audio",
+    description = "Stream the call's recording. Supports HTTP Range requests for locally stored recordings; `format` transcodes to a browser-friendly format (sent whole, without range support).",
+    params(
+        ("id" = Uuid, Path, description = "Call UUID"),
+        CallAudioQuery,
+        ("Range" = Option<String>, Header, description = "Byte range, e.g. `bytes=0-65535`"),
+    ),
+    responses(
+        (status = 200, description = "Full recording", content_type = "audio/*", body = Vec<u8>),
+        (status = 206, description = "Requested byte range", content_type = "audio/*", body = Vec<u8>),
+        (status = 404, description = "Call or recording not found", body = ErrorResponse),
+        (status = 416, description = "Range not satisfiable"),
+        (status = 500, description = "Database or transcoding failure", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_audio(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+    Query(query): Query<CallAudioQuery>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => call,
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to retrieve call {}: {}", call_id, e);
+            return Err(ApiError::database("Failed to retrieve call"));
+        }
+    };
+
+    let Some(location) = call.audio_file_path else {
+        return Err(ApiError::not_found(
+            "NO_AUDIO_FILE",
+            "No audio file associated with this call",
+        ));
+    };
+    let content_type = call.audio_content_type.as_deref();
+
+    // Recordings never change, so a client holding this one needs nothing sent
+    let digest = call.audio_sha256.as_deref().map(|sha| {
+        query.format.map_or_else(
+            || sha.to_string(),
+            |format| format!("{sha}-{}", format.extension()),
+        )
+    });
+    let validators = Validators::new(digest.as_deref(), call.upload_timestamp);
+    if let Some(response) = not_modified(request.headers(), &validators) {
+        return Ok(response);
+    }
+
+    // A call record must not be able to expose files outside recording storage
+    let mut response = match state.audio_storage.local_path(&location) {
+        Some(path) => serve_local_audio(&path, query.format, content_type, request).await?,
+        None => {
+            serve_stored_audio(
+                state.audio_storage.as_ref(),
+                &location,
+                query.format,
+                content_type,
+            )
+            .await?
+        }
+    };
+    validators.apply(&mut response, AUDIO_CACHE_CONTROL);
+    Ok(response)
+}
+
+/// Get the LLM-written summary of a call
+///
+/// Summaries are written in the background after transcription when
+/// `summarizer.enabled` is set.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist, is outside the API key's systems, or has
+///   not been summarized
+/// * `INTERNAL_SERVER_ERROR` - Database query failure
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/summary
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/summary",
+    tag = "Calls",
+    summary = "Get call summary",
+    description = "Short summary of the call's transcript and the entities it mentions, written by the configured LLM summarizer.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Call summary", body = TranscriptSummaryResponse),
+        (status = 404, description = "Call or summary not found", body = ErrorResponse),
+        (status = 500, description = "Database failure", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_summary(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<TranscriptSummaryResponse>, ApiError> {
+    match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => {}
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to retrieve call {}: {}", call_id, e);
+            return Err(ApiError::database("Failed to retrieve call"));
+        }
+    }
+
+    match SummaryQueries::get(&state.pool, call_id).await {
+        Ok(Some(summary)) => Ok(Json(summary.into())),
+        Ok(None) => Err(ApiError::not_found(
+            "SUMMARY_NOT_FOUND",
+            format!("Call {call_id} has not been summarized"),
+        )),
+        Err(e) => {
+            error!("Failed to retrieve summary for call {}: {}", call_id, e);
+            Err(ApiError::database("Failed to retrieve summary"))
+        }
+    }
+}
+
+/// Get waveform peaks for a call's recording
+///
+/// Returns [`PEAK_COUNT`](crate::waveform::PEAK_COUNT) peaks for the web
+/// player to draw a seekable waveform. Peaks are generated in the background
+/// after upload; calls without them are decoded on first request and the
+/// result is stored.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist, is outside the API key's systems, or has
+///   no readable recording
+/// * `INTERNAL_SERVER_ERROR` - Database query or decoding failure
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/waveform
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/waveform",
+    tag = "Calls",
+    summary = "Get call waveform",
+    description = "Peak amplitudes of the call's recording, scaled so the loudest is 1.0, for drawing a seekable waveform.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Waveform peaks", body = CallWaveformResponse),
+        (status = 404, description = "Call or recording not found", body = ErrorResponse),
+        (status = 500, description = "Database or decoding failure", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_waveform(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<CallWaveformResponse>, ApiError> {
+    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => call,
+        Ok(_) => {
+            return Err(ApiError::not_found(
+                "CALL_NOT_FOUND",
+                format!("Call {call_id} not found"),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to retrieve call {}: {}", call_id, e);
+            return Err(ApiError::database("Failed to retrieve call"));
+        }
+    };
+
+    match WaveformQueries::get(&state.pool, call_id).await {
+        Ok(Some(waveform)) => return Ok(Json(waveform.into())),
+        Ok(None) => {}
+        Err(e) => {
+            error!("Failed to retrieve waveform for call {}: {}", call_id, e);
+            return Err(ApiError::database("Failed to retrieve waveform"));
+        }
+    }
+
+    let Some(location) = call.audio_file_path else {
+        return Err(ApiError::not_found(
+            "NO_AUDIO_FILE",
+            "No audio file associated with this call",
+        ));
+    };
+    generate_waveform(&state, call_id, &location)
+        .await
+        .map(|waveform| Json(waveform.into()))
+}
+
+/// Decode a call's recording and store its waveform
+///
+/// # Errors
+///
+/// Returns `NOT_FOUND` if the recording is gone, `INTERNAL_SERVER_ERROR` if
+/// fetching, decoding, or storing fails
+async fn generate_waveform(
+    state: &AppState,
+    call_id: Uuid,
+    location: &str,
+) -> Result<CallWaveform, ApiError> {
+    let generated = if let Some(path) = state.audio_storage.local_path(location) {
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            warn!("Audio file is not readable: {}", path.display());
+            return Err(audio_not_found());
+        }
+        waveform::generate_and_store(&state.pool, call_id, TranscodeInput::File(&path)).await
+    } else {
+        let data = fetch_stored_audio(state.audio_storage.as_ref(), location).await?;
+        waveform::generate_and_store(&state.pool, call_id, TranscodeInput::Memory(data)).await
+    };
+
+    generated.map_err(|e| {
+        error!("Failed to generate waveform for call {}: {:#}", call_id, e);
+        ApiError::internal("WAVEFORM_FAILED", "Failed to generate waveform")
+    })
+}
+
+/// Serve or transcode a recording on local disk
+///
+/// # Errors
+///
+/// Returns `NOT_FOUND` if the file is gone, or a transcoding error
+async fn serve_local_audio(
+    path: &FsPath,
+    format: Option<AudioFormat>,
+    content_type: Option<&str>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    let location = path.to_string_lossy();
+    if !tokio::fs::try_exists(path).await.unwrap_or(false) {
+        warn!("Audio file is not readable: {}", location);
+        return Err(audio_not_found());
+    }
+    match format {
+        Some(format) if !format.matches(path) => {
+            transcode_audio(TranscodeInput::File(path), format, &location).await
+        }
+        _ => Ok(serve_audio_file(path, content_type, request).await),
+    }
+}
+
+/// Fetch a recording from object storage and serve or transcode it
+///
+/// # Errors
+///
+/// Returns `NOT_FOUND` if the recording is gone or outside the storage,
+/// `INTERNAL_SERVER_ERROR` if fetching or transcoding fails
+async fn serve_stored_audio(
+    storage: &dyn AudioStorage,
+    location: &str,
+    format: Option<AudioFormat>,
+    content_type: Option<&str>,
+) -> Result<Response, ApiError> {
+    let data = fetch_stored_audio(storage, location).await?;
+    match format {
+        Some(format) if !format.matches(FsPath::new(location)) => {
+            transcode_audio(TranscodeInput::Memory(data), format, location).await
+        }
+        _ => Ok(serve_audio_bytes(data, location, content_type)),
+    }
+}
+
+/// Fetch a recording from object storage
+///
+/// # Errors
+///
+/// Returns `NOT_FOUND` if the recording is gone or outside the storage,
+/// `INTERNAL_SERVER_ERROR` if fetching fails
+async fn fetch_stored_audio(storage: &dyn AudioStorage, location: &str) -> Result<Bytes, ApiError> {
+    if !storage.contains(location) {
+        warn!("Audio is outside recording storage: {}", location);
+        return Err(audio_not_found());
+    }
+    match storage.get(location).await {
+        Ok(Some(data)) => Ok(data),
+        Ok(None) => {
+            warn!("Audio is missing from recording storage: {}", location);
+            Err(audio_not_found())
+        }
+        Err(e) => {
+            error!("Failed to fetch audio {}: {}", location, e);
+            Err(ApiError::internal("STORAGE_ERROR", "Failed to fetch audio"))
+        }
+    }
+}
+
+fn audio_not_found() -> ApiError {
+    ApiError::not_found("AUDIO_FILE_NOT_FOUND", "Audio file not found")
+}
+
+/// Serve a recording, honouring `Range` and conditional request headers
+async fn serve_audio_file(path: &FsPath, content_type: Option<&str>, request: Request) -> Response {
+    let mut response = match ServeFile::new(path).oneshot(request).await {
+        Ok(response) => response.map(Body::new),
+        Err(infallible) => match infallible {},
+    };
+
+    // The uploader's MIME type beats a guess from the extension
+    let uploaded_type = content_type
+        .filter(|t| t.starts_with("audio/"))
+        .and_then(|t| HeaderValue::from_str(t).ok());
+    if let Some(value) = uploaded_type
+        && response.status().is_success()
+    {
+        let _ = response.headers_mut().insert(header::CONTENT_TYPE, value);
+    }
+    response
+}
+
+/// Serve a recording fetched from object storage, whole
+///
+/// The content type comes from the uploaded MIME type, then the extension.
+fn serve_audio_bytes(data: Bytes, location: &str, content_type: Option<&str>) -> Response {
+    let path = FsPath::new(location);
+    let content_type = content_type
+        .filter(|t| t.starts_with("audio/"))
+        .or_else(|| {
+            [AudioFormat::Mp3, AudioFormat::Ogg, AudioFormat::Wav]
+                .into_iter()
+                .find(|format| format.matches(path))
+                .map(AudioFormat::content_type)
+        })
+        .and_then(|t| HeaderValue::from_str(t).ok())
+        .unwrap_or_else(|| HeaderValue::from_static("application/octet-stream"));
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::ACCEPT_RANGES, HeaderValue::from_static("none")),
+        ],
+        data,
+    )
+        .into_response()
+}
+
+/// Recording handed to ffmpeg
+pub(crate) enum TranscodeInput<'a> {
+    /// File on local disk
+    File(&'a FsPath),
+    /// Recording fetched from object storage, piped through stdin
+    Memory(Bytes),
+}
+
+/// Transcode a recording with ffmpeg and return it whole
+///
+/// # Errors
+///
+/// Returns `INTERNAL_SERVER_ERROR` if ffmpeg is missing or fails
+async fn transcode_audio(
+    input: TranscodeInput<'_>,
+    format: AudioFormat,
+    location: &str,
+) -> Result<Response, ApiError> {
+    match run_ffmpeg(input, format.ffmpeg_args()).await {
+        Ok(output) if output.status.success() => Ok((
+            [
+                (header::CONTENT_TYPE, format.content_type()),
+                (header::ACCEPT_RANGES, "none"),
+            ],
+            output.stdout,
+        )
+            .into_response()),
+        Ok(output) => {
+            error!(
+                "ffmpeg failed to transcode {}: {}",
+                location,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            Err(ApiError::internal(
+                "TRANSCODE_FAILED",
+                "Failed to transcode audio",
+            ))
+        }
+        Err(e) => {
+            error!("Failed to run ffmpeg: {}", e);
+            Err(ApiError::internal(
+                "TRANSCODE_FAILED",
+                "Failed to transcode audio",
+            ))
+        }
+    }
+}
+
+/// Run ffmpeg over a recording with the given output options, collecting
+/// the transcoded output
+///
+/// # Errors
+///
+/// Returns an error if ffmpeg cannot be started
+pub(crate) async fn run_ffmpeg(
+    input: TranscodeInput<'_>,
+    output_args: &[&str],
+) -> std::io::Result<std::process::Output> {
+    let mut command = tokio::process::Command::new("ffmpeg");
+    let _ = command.args(["-loglevel", "error"]);
+    let _ = match &input {
+        TranscodeInput::File(path) => command
+            .args(["-nostdin", "-i"])
+            .arg(path)
+            .stdin(Stdio::null()),
+        TranscodeInput::Memory(_) => command.args(["-i", "pipe:0"]).stdin(Stdio::piped()),
+    };
+    let mut child = command
+        .arg("-vn")
+        .args(output_args)
+        .arg("pipe:1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    // Feed stdin concurrently so ffmpeg never blocks on a full stdout pipe
+    if let (TranscodeInput::Memory(data), Some(mut stdin)) = (input, child.stdin.take()) {
+        drop(tokio::spawn(async move {
+            // ffmpeg closing stdin early surfaces as its own error below
+            let _ = stdin.write_all(&data).await;
+        }));
+    }
+    child.wait_with_output().await
+}
+
 /// Validates sort order parameter values
 ///
 /// Ensures that sort order is either "asc" (ascending) or "desc" (descending).
@@ -490,7 +1730,8 @@ pub async fn get_call(
 /// Returns a validation error if the status is not one of the accepted values.
 fn validate_transcription_status(status: &str) -> Result<(), validator::ValidationError> {
     match status {
-        "pending" | "processing" | "completed" | "failed" => Ok(()),
+        "pending" | "processing" | "completed" | "needs_review" | "failed" | "skipped"
+        | "awaiting_audio" | "deferred" => Ok(()),
         _ => Err(validator::ValidationError::new(
             "invalid_transcription_status",
         )),
@@ -550,6 +1791,7 @@ fn validate_sort_order(sort: &str) -> Result<(), validator::ValidationError> {
 )]
 mod tests {
     use super::*;
+    use axum::http::StatusCode;
     use chrono::Utc;
     use rust_decimal::Decimal;
     use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
@@ -571,15 +1813,26 @@ mod tests {
         assert!(validate_sort_order("").is_err());
     }
 
+    fn parse_query(uri: &str) -> Result<ListCallsQuery, String> {
+        let uri: axum::http::Uri = uri.parse().unwrap();
+        Query::<ListCallsQuery>::try_from_uri(&uri)
+            .map(|Query(query)| query)
+            .map_err(|e| e.to_string())
+    }
+
     #[test]
     fn test_list_calls_query_validation() {
         // Valid query
         let valid_query = ListCallsQuery {
             limit: Some(50),
-            offset: Some(0),
-            system_id: Some("police".to_string()),
-            talkgroup_id: Some(12345),
+            after: None,
+            system_id: Some(SystemId::new("police").unwrap()),
+            talkgroup_id: Some(TalkgroupId::new(12345).unwrap()),
             transcription_status: None,
+            tag: None,
+            q: None,
+            language: None,
+            archived: None,
             from_date: Some(Utc::now() - chrono::Duration::hours(24)),
             to_date: Some(Utc::now()),
             sort: Some("desc".to_string()),
@@ -590,10 +1843,14 @@ mod tests {
         // Invalid limit (too high)
         let invalid_limit = ListCallsQuery {
             limit: Some(2000), // Over max of 1000
-            offset: Some(0),
+            after: None,
             system_id: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            q: None,
+            language: None,
+            archived: None,
             from_date: None,
             to_date: None,
             sort: None,
@@ -601,41 +1858,21 @@ mod tests {
         };
         assert!(invalid_limit.validate().is_err());
 
-        // Invalid offset (negative)
-        let invalid_offset = ListCallsQuery {
-            limit: Some(50),
-            offset: Some(-1),
-            system_id: None,
-            talkgroup_id: None,
-            transcription_status: None,
-            from_date: None,
-            to_date: None,
-            sort: None,
-            include_transcription: None,
-        };
-        assert!(invalid_offset.validate().is_err());
-
-        // Invalid system_id (too long)
-        let invalid_system_id = ListCallsQuery {
-            limit: Some(50),
-            offset: Some(0),
-            system_id: Some("a".repeat(51)), // Over max of 50
-            talkgroup_id: None,
-            transcription_status: None,
-            from_date: None,
-            to_date: None,
-            sort: None,
-            include_transcription: None,
-        };
-        assert!(invalid_system_id.validate().is_err());
+        // Invalid system_id (too long) is rejected when the query is parsed
+        let too_long = format!("/api/calls?system_id={}", "a".repeat(51));
+        assert!(parse_query(&too_long).is_err());
 
         // Invalid sort order
         let invalid_sort = ListCallsQuery {
             limit: Some(50),
-            offset: Some(0),
+            after: None,
             system_id: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            q: None,
+            language: None,
+            archived: None,
             from_date: None,
             to_date: None,
             sort: Some("invalid".to_string()),
@@ -644,6 +1881,18 @@ mod tests {
         assert!(invalid_sort.validate().is_err());
     }
 
+    #[test]
+    fn test_list_calls_query_parses_typed_ids() {
+        let query = parse_query("/api/calls?system=police&talkgroup_id=12345").unwrap();
+        assert_eq!(query.system_id.unwrap().as_str(), "police");
+        assert_eq!(query.talkgroup_id.unwrap().as_i32(), 12345);
+
+        // Swapped or out-of-range IDs no longer reach the query layer
+        assert!(parse_query("/api/calls?talkgroup_id=0").is_err());
+        assert!(parse_query("/api/calls?talkgroup_id=police").is_err());
+        assert!(parse_query("/api/calls?system_id=").is_err());
+    }
+
     #[test]
     fn test_call_summary_serialization() {
         let call_id = Uuid::new_v4();
@@ -665,8 +1914,10 @@ mod tests {
             duration_seconds: Some(Decimal::from_str("15.5").unwrap()),
             transcription_status: Some("completed".to_string()),
             transcription_confidence: Some(Decimal::from_str("0.95").unwrap()),
+            transcription_language: None,
             transcription_text: Some("This is a test call".to_string()),
             frequency: Some(Frequency::new(154250000).unwrap()),
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&call_summary).expect("Failed to serialize");
@@ -698,8 +1949,10 @@ mod tests {
             duration_seconds: Some(Decimal::from_str("8.2").unwrap()),
             transcription_status: Some("pending".to_string()),
             transcription_confidence: None,
+            transcription_language: None,
             transcription_text: None, // Should be omitted from JSON
             frequency: Some(Frequency::new(460125000).unwrap()),
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&call_summary).expect("Failed to serialize");
@@ -747,6 +2000,8 @@ mod tests {
             upload_timestamp,
             upload_ip: None,
             upload_api_key_id: Some("api-key-123".to_string()),
+            latitude: Some(35.7796),
+            longitude: Some(-78.6382),
         };
 
         let json = serde_json::to_string(&call_detail).expect("Failed to serialize");
@@ -778,20 +2033,19 @@ mod tests {
             duration_seconds: Some(Decimal::from_str("10.0").unwrap()),
             transcription_status: Some("pending".to_string()),
             transcription_confidence: None,
+            transcription_language: None,
             transcription_text: None,
             frequency: Some(Frequency::new(150000000).unwrap()),
+            tags: Vec::new(),
         };
 
         let response = ListCallsResponse {
             calls: vec![call],
             total: 1,
             count: 1,
-            offset: 0,
             pagination: PaginationInfo {
                 has_next: false,
-                has_prev: false,
-                next_offset: None,
-                prev_offset: None,
+                next_cursor: None,
             },
         };
 
@@ -805,48 +2059,44 @@ mod tests {
     #[test]
     fn test_pagination_info() {
         // Test pagination with next page
+        let cursor = "2025-03-01T12:30:45.123456Z,123e4567-e89b-12d3-a456-426614174000";
         let pagination = PaginationInfo {
             has_next: true,
-            has_prev: false,
-            next_offset: Some(50),
-            prev_offset: None,
+            next_cursor: Some(cursor.to_string()),
         };
 
         let json = serde_json::to_string(&pagination).expect("Failed to serialize");
         assert!(json.contains("\"has_next\":true"));
-        assert!(json.contains("\"has_prev\":false"));
-        assert!(json.contains("\"next_offset\":50"));
+        assert!(json.contains(&format!("\"next_cursor\":\"{cursor}\"")));
 
-        // Test pagination with previous page
+        // Test last page
         let pagination = PaginationInfo {
             has_next: false,
-            has_prev: true,
-            next_offset: None,
-            prev_offset: Some(0),
+            next_cursor: None,
         };
 
         let json = serde_json::to_string(&pagination).expect("Failed to serialize");
         assert!(json.contains("\"has_next\":false"));
-        assert!(json.contains("\"has_prev\":true"));
-        assert!(json.contains("\"prev_offset\":0"));
-
-        // Test pagination in middle
-        let pagination = PaginationInfo {
-            has_next: true,
-            has_prev: true,
-            next_offset: Some(100),
-            prev_offset: Some(0),
-        };
+        assert!(json.contains("\"next_cursor\":null"));
+    }
 
-        assert!(pagination.has_next);
-        assert!(pagination.has_prev);
-        assert_eq!(pagination.next_offset, Some(100));
-        assert_eq!(pagination.prev_offset, Some(0));
+    #[test]
+    fn test_list_calls_query_parses_cursor() {
+        let query = parse_query(
+            "/api/calls?after=2025-03-01T12:30:45.123456Z,123e4567-e89b-12d3-a456-426614174000",
+        )
+        .unwrap();
+        let cursor: CallCursor = query.after.as_deref().unwrap().parse().unwrap();
+        assert_eq!(
+            cursor.id.to_string(),
+            "123e4567-e89b-12d3-a456-426614174000"
+        );
     }
 
     #[test]
     fn test_error_response_serialization() {
         let error = ErrorResponse {
+            success: false,
             error: "Call not found".to_string(),
             code: "CALL_NOT_FOUND".to_string(),
             details: Some(serde_json::json!({"call_id": "123e4567-e89b-12d3-a456-426614174000"})),
@@ -858,14 +2108,79 @@ mod tests {
         assert!(json.contains("call_id"));
     }
 
+    #[test]
+    fn test_call_speakers() {
+        let call_id = Uuid::new_v4();
+        let segments = serde_json::json!([
+            {"speaker": "SPEAKER_01", "start": 3.0, "end": 4.5, "confidence": 0.9},
+            {"speaker": "SPEAKER_00", "start": 0.0, "end": 3.0},
+            {"speaker": "SPEAKER_00", "start": 4.5, "end": 5.0}
+        ]);
+
+        let response = call_speakers(
+            call_id,
+            Some("completed".to_string()),
+            None,
+            Some(&segments),
+        );
+        assert_eq!(response.call_id, call_id);
+        assert_eq!(response.speaker_count, Some(2));
+        assert_eq!(response.segments.len(), 3);
+        assert_eq!(response.segments[0].speaker, "SPEAKER_00");
+        assert!((response.segments[1].duration - 1.5).abs() < f64::EPSILON);
+        assert_eq!(response.speakers[0].speaker, "SPEAKER_00");
+        assert_eq!(response.speakers[0].segment_count, 2);
+        assert!((response.speakers[0].talk_seconds - 3.5).abs() < f64::EPSILON);
+
+        // Stored speaker counts win, and undiarized calls have no speakers
+        assert_eq!(
+            call_speakers(call_id, None, Some(3), Some(&segments)).speaker_count,
+            Some(3)
+        );
+        let empty = call_speakers(call_id, Some("pending".to_string()), None, None);
+        assert!(empty.speakers.is_empty());
+        assert!(empty.segments.is_empty());
+        assert_eq!(empty.speaker_count, None);
+    }
+
+    #[test]
+    fn test_transcript_segments() {
+        let stored = vec![TranscriptionSegment {
+            start_seconds: 0.5,
+            end_seconds: 2.0,
+            text: "Engine 5".to_string(),
+            speaker: None,
+            confidence: None,
+        }];
+        let duration = Some(Decimal::from_str("8.2").unwrap());
+
+        // Stored segments win over the plain transcript
+        assert_eq!(
+            transcript_segments(stored.clone(), Some("Engine 5"), duration),
+            stored
+        );
+
+        let whole = transcript_segments(Vec::new(), Some(" Engine 5 responding "), duration);
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].text, "Engine 5 responding");
+        assert!((whole[0].end_seconds - 8.2).abs() < f64::EPSILON);
+
+        assert!(transcript_segments(Vec::new(), Some("  "), duration).is_empty());
+        assert!(transcript_segments(Vec::new(), Some("Engine 5"), None).is_empty());
+    }
+
     #[test]
     fn test_query_parameter_defaults() {
         let query = ListCallsQuery {
             limit: None,
-            offset: None,
+            after: None,
             system_id: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            q: None,
+            language: None,
+            archived: None,
             from_date: None,
             to_date: None,
             sort: None,
@@ -894,8 +2209,10 @@ mod tests {
             duration_seconds: Some(Decimal::from_str("123.456789").unwrap()),
             transcription_status: None,
             transcription_confidence: Some(Decimal::from_str("0.987654321").unwrap()),
+            transcription_language: None,
             transcription_text: None,
             frequency: None,
+            tags: Vec::new(),
         };
 
         // Should serialize without losing precision
@@ -909,10 +2226,14 @@ mod tests {
         // Test with exact limits
         let query_max_limit = ListCallsQuery {
             limit: Some(1000), // Exactly at max
-            offset: Some(0),
-            system_id: Some("a".repeat(50)), // Exactly at max length
+            after: None,
+            system_id: Some(SystemId::new("a".repeat(50)).unwrap()), // Exactly at max length
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            q: None,
+            language: None,
+            archived: None,
             from_date: None,
             to_date: None,
             sort: None,
@@ -922,11 +2243,15 @@ mod tests {
 
         // Test with boundary values
         let query_min_values = ListCallsQuery {
-            limit: Some(1),                   // Minimum valid
-            offset: Some(0),                  // Minimum valid
-            system_id: Some("x".to_string()), // Minimum length
-            talkgroup_id: Some(i32::MIN),     // Test extreme value
+            limit: Some(1), // Minimum valid
+            after: None,
+            system_id: Some(SystemId::new("x").unwrap()), // Minimum length
+            talkgroup_id: Some(TalkgroupId::new(1).unwrap()), // Minimum valid
             transcription_status: None,
+            tag: None,
+            q: None,
+            language: None,
+            archived: None,
             from_date: None,
             to_date: None,
             sort: Some("asc".to_string()),
@@ -954,8 +2279,10 @@ mod tests {
             duration_seconds: None,
             transcription_status: None,
             transcription_confidence: None,
+            transcription_language: None,
             transcription_text: None,
             frequency: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&call_summary).expect("Failed to serialize");
@@ -987,96 +2314,83 @@ mod tests {
             duration_seconds: None,
             transcription_status: None,
             transcription_confidence: None,
+            transcription_language: None,
             transcription_text: None,
             frequency: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&call_summary).expect("Failed to serialize");
         // Should contain ISO 8601 formatted timestamp
         assert!(json.contains("2024-01-15T14:30:00Z"));
     }
-}
-
-/* Get call audio file (streaming endpoint) - Disabled for minimal build
-pub async fn get_call_audio(
-    State(state): State<Arc<AppState>>,
-    Path(call_id): Path<Uuid>,
-) -> Result<axum::response::Response, (StatusCode, Json<ErrorResponse>)> {
-    info!("Retrieving audio for call: {}", call_id);
 
-    // First, get the call to find the audio file path
-    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
-        Ok(Some(call)) => call,
-        Ok(None) => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Call {call_id} not found"),
-                    code: "CALL_NOT_FOUND".to_string(),
-                    details: None,
-                }),
-            ));
-        }
-        Err(e) => {
-            error!("Failed to retrieve call {}: {}", call_id, e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve call".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                    details: None,
-                }),
-            ));
-        }
-    };
+    #[test]
+    fn test_audio_format() {
+        let query = parse_audio_query("/?format=ogg");
+        assert_eq!(query.format, Some(AudioFormat::Ogg));
+        assert!(parse_audio_query("/").format.is_none());
+        assert!(Query::<CallAudioQuery>::try_from_uri(&"/?format=flac".parse().unwrap()).is_err());
+
+        assert!(AudioFormat::Mp3.matches(FsPath::new("/data/call.MP3")));
+        assert!(AudioFormat::Ogg.matches(FsPath::new("/data/call.opus")));
+        assert!(!AudioFormat::Wav.matches(FsPath::new("/data/call.mp3")));
+        assert!(!AudioFormat::Wav.matches(FsPath::new("/data/call")));
+        assert_eq!(AudioFormat::Wav.content_type(), "audio/wav");
+    }
 
-    // Check if audio file exists
-    let audio_path = match call.audio_file_path {
-        Some(path) => std::path::PathBuf::from(path),
-        None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: "No audio file associated with this call".to_string(),
-                    code: "NO_AUDIO_FILE".to_string(),
-                    details: None,
-                }),
-            ));
-        }
-    };
+    fn parse_audio_query(uri: &str) -> CallAudioQuery {
+        let Query(query) = Query::<CallAudioQuery>::try_from_uri(&uri.parse().unwrap()).unwrap();
+        query
+    }
 
-    if !audio_path.exists() {
-        warn!("Audio file does not exist: {:?}", audio_path);
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "Audio file not found on disk".to_string(),
-                code: "AUDIO_FILE_NOT_FOUND".to_string(),
-                details: Some(serde_json::json!({ "path": audio_path.display().to_string() })),
-            }),
-        ));
+    #[test]
+    fn test_serve_audio_bytes_content_type() {
+        let data = Bytes::from_static(b"0123");
+        let response = serve_audio_bytes(data.clone(), "s3://radio/police/call.ogg", None);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/ogg");
+        assert_eq!(response.headers()[header::ACCEPT_RANGES], "none");
+
+        let response = serve_audio_bytes(data.clone(), "s3://radio/call.mp3", Some("audio/x-mp3"));
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/x-mp3");
+
+        let response = serve_audio_bytes(data, "s3://radio/call.m4a", Some("text/plain"));
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/octet-stream"
+        );
     }
 
-    // Serve the file using tower_http::services::ServeFile
-    match tower_http::services::ServeFile::new(&audio_path).try_call(
-        axum::http::Request::builder()
-            .method("GET")
-            .uri("/")
-            .body(axum::body::Body::empty())
-            .unwrap(),
-    ).await {
-        Ok(response) => Ok(response),
-        Err(e) => {
-            error!("Failed to serve audio file: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to serve audio file".to_string(),
-                    code: "FILE_SERVE_ERROR".to_string(),
-                    details: None,
-                }),
-            ))
+    async fn serve(path: &FsPath, content_type: Option<&str>, range: Option<&str>) -> Response {
+        let mut request = axum::http::Request::builder().uri("/");
+        if let Some(range) = range {
+            request = request.header(header::RANGE, range);
         }
+        serve_audio_file(path, content_type, request.body(Body::empty()).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn test_serve_audio_file_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("call.mp3");
+        std::fs::write(&path, b"0123456789").unwrap();
+
+        let full = serve(&path, None, None).await;
+        assert_eq!(full.status(), StatusCode::OK);
+        assert_eq!(full.headers()[header::CONTENT_TYPE], "audio/mpeg");
+        assert_eq!(full.headers()[header::ACCEPT_RANGES], "bytes");
+
+        let partial = serve(&path, Some("audio/x-mp3"), Some("bytes=2-5")).await;
+        assert_eq!(partial.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(partial.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+        assert_eq!(partial.headers()[header::CONTENT_TYPE], "audio/x-mp3");
+        let body = axum::body::to_bytes(partial.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"2345");
+
+        let unsatisfiable = serve(&path, None, Some("bytes=50-")).await;
+        assert_eq!(unsatisfiable.status(), StatusCode::RANGE_NOT_SATISFIABLE);
     }
 }
-*/
@@ -0,0 +1,290 @@
+//! Side-by-side call comparison
+//!
+//! Returns several calls at once, with their metadata, timed transcripts, and
+//! speaker turns, for analysts comparing how the same transmission was heard
+//! through different simulcast sites or receivers.
+
+use crate::{
+    error::{ApiError, ErrorResponse},
+    handlers::{
+        calls::{
+            SpeakerSegmentInfo, TranscriptSegmentInfo, call_speakers, caller_role,
+            transcript_segments,
+        },
+        feedback::scoped_call,
+    },
+    state::AppState,
+    tenant::TenantScope,
+};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{
+    FingerprintQueries, SegmentQueries, TranscriptionSegment, models::RadioCallDb,
+};
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId, UserRole};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Most calls compared at once
+pub const MAX_COMPARED_CALLS: usize = 10;
+
+/// Query parameters for comparing calls
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CompareCallsQuery {
+    /// Comma-separated IDs of the calls to compare (2 to 10)
+    pub ids: String,
+}
+
+/// One call in a comparison
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComparedCall {
+    /// Call ID
+    pub id: Uuid,
+    /// When the call started
+    pub call_timestamp: DateTime<Utc>,
+    /// Seconds the call started after the earliest compared call
+    pub offset_seconds: f64,
+    /// System the call was heard on
+    #[schema(value_type = String)]
+    pub system_id: SystemId,
+    /// System display name or label
+    pub system_label: Option<String>,
+    /// Talkgroup
+    #[schema(value_type = Option<i32>)]
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name or label
+    pub talkgroup_label: Option<String>,
+    /// Transmitting radio
+    #[schema(value_type = Option<i32>)]
+    pub source_radio_id: Option<RadioId>,
+    /// Radio user's alias or call sign
+    pub talker_alias: Option<String>,
+    /// Radio frequency used for the call
+    #[schema(value_type = Option<i64>)]
+    pub frequency: Option<Frequency>,
+    /// Duration of the recording in seconds
+    pub duration_seconds: Option<rust_decimal::Decimal>,
+    /// Latitude where the call was heard (WGS 84 degrees)
+    pub latitude: Option<f64>,
+    /// Longitude where the call was heard
+    pub longitude: Option<f64>,
+    /// Call this one was recorded as an echo of
+    pub echo_of: Option<Uuid>,
+    /// Current transcription processing status
+    pub transcription_status: Option<String>,
+    /// Confidence score for the transcription (0.0-1.0)
+    pub transcription_confidence: Option<rust_decimal::Decimal>,
+    /// Transcription language
+    pub transcription_language: Option<String>,
+    /// Transcription text
+    pub transcription_text: Option<String>,
+    /// Timed transcript segments in start order
+    pub transcript: Vec<TranscriptSegmentInfo>,
+    /// Number of unique speakers detected
+    pub speaker_count: Option<i32>,
+    /// Speaker turns in start order
+    pub speakers: Vec<SpeakerSegmentInfo>,
+}
+
+/// Calls side by side, in the order requested
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallComparisonResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Compared calls
+    pub calls: Vec<ComparedCall>,
+}
+
+/// Compare calls side by side
+///
+/// Returns each call's metadata, timed transcript, and speaker turns, with
+/// its start relative to the earliest of them, so the same transmission heard
+/// on several sites can be lined up. Repeated IDs are compared once.
+///
+/// # Errors
+///
+/// * `BAD_REQUEST` - An ID is not a UUID, or fewer than 2 or more than
+///   [`MAX_COMPARED_CALLS`] calls were given
+/// * `NOT_FOUND` - A call does not exist or is outside the API key's systems
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/compare?ids=550e8400-e29b-41d4-a716-446655440000,6ba7b810-9dad-11d1-80b4-00c04fd430c8
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/compare",
+    tag = "Calls",
+    summary = "Compare calls",
+    description = "Metadata, timed transcripts, and speaker turns of 2 to 10 calls side by side, each with its start offset from the earliest, for comparing simulcast coverage.",
+    params(CompareCallsQuery),
+    responses(
+        (status = 200, description = "Compared calls", body = CallComparisonResponse),
+        (status = 400, description = "Invalid call IDs", body = ErrorResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn compare_calls(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    role: Option<Extension<UserRole>>,
+    Query(query): Query<CompareCallsQuery>,
+) -> Result<Json<CallComparisonResponse>, ApiError> {
+    let ids = parse_call_ids(&query.ids)?;
+    let database_error = |e: sdrtrunk_storage::StorageError| {
+        error!("Failed to load compared calls: {e}");
+        ApiError::database("Failed to load compared calls")
+    };
+
+    let mut calls = Vec::with_capacity(ids.len());
+    for id in ids {
+        calls.push(scoped_call(&state, &scope, id).await?);
+    }
+    let role = caller_role(role);
+    if let Some(encryption) = state.encryption.as_deref() {
+        encryption.reveal(&state.pool, role, &mut calls).await;
+    }
+    let keys = state
+        .encryption
+        .as_deref()
+        .and_then(|encryption| encryption.segment_keys(role));
+
+    let earliest = calls
+        .iter()
+        .map(|call| call.call_timestamp)
+        .min()
+        .unwrap_or_default();
+    let mut compared = Vec::with_capacity(calls.len());
+    for call in calls {
+        let segments = SegmentQueries::for_call(&state.pool, call.id, keys)
+            .await
+            .map_err(database_error)?;
+        let echo_of = FingerprintQueries::get(&state.pool, call.id)
+            .await
+            .map_err(database_error)?
+            .and_then(|fingerprint| fingerprint.echo_of);
+        compared.push(compared_call(call, segments, echo_of, earliest));
+    }
+
+    Ok(Json(CallComparisonResponse {
+        success: true,
+        calls: compared,
+    }))
+}
+
+/// The distinct call IDs in a comma-separated list, in order
+///
+/// # Errors
+///
+/// Returns `BAD_REQUEST` if an ID is not a UUID or the list does not name
+/// 2 to [`MAX_COMPARED_CALLS`] distinct calls.
+fn parse_call_ids(ids: &str) -> Result<Vec<Uuid>, ApiError> {
+    let mut parsed = Vec::new();
+    for id in ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        let id = Uuid::parse_str(id).map_err(|_| {
+            ApiError::bad_request("INVALID_CALL_ID", format!("'{id}' is not a call ID"))
+        })?;
+        if !parsed.contains(&id) {
+            parsed.push(id);
+        }
+    }
+    if !(2..=MAX_COMPARED_CALLS).contains(&parsed.len()) {
+        return Err(ApiError::bad_request(
+            "INVALID_CALL_COUNT",
+            format!("Compare between 2 and {MAX_COMPARED_CALLS} distinct calls"),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// A call's comparison entry, timed from `earliest`
+#[allow(clippy::cast_precision_loss)]
+fn compared_call(
+    call: RadioCallDb,
+    segments: Vec<TranscriptionSegment>,
+    echo_of: Option<Uuid>,
+    earliest: DateTime<Utc>,
+) -> ComparedCall {
+    let transcript = transcript_segments(
+        segments,
+        call.transcription_text.as_deref(),
+        call.duration_seconds,
+    );
+    let speakers = call_speakers(
+        call.id,
+        None,
+        call.speaker_count,
+        call.speaker_segments.as_ref(),
+    );
+    ComparedCall {
+        id: call.id,
+        call_timestamp: call.call_timestamp,
+        offset_seconds: (call.call_timestamp - earliest).num_milliseconds() as f64 / 1000.0,
+        system_id: call.system_id,
+        system_label: call.system_label,
+        talkgroup_id: call.talkgroup_id,
+        talkgroup_label: call.talkgroup_label,
+        source_radio_id: call.source_radio_id,
+        talker_alias: call.talker_alias,
+        frequency: call.frequency,
+        duration_seconds: call.duration_seconds,
+        latitude: call.latitude,
+        longitude: call.longitude,
+        echo_of,
+        transcription_status: call.transcription_status,
+        transcription_confidence: call.transcription_confidence,
+        transcription_language: call.transcription_language,
+        transcription_text: call.transcription_text,
+        transcript: transcript.into_iter().map(Into::into).collect(),
+        speaker_count: speakers.speaker_count,
+        speakers: speakers.segments,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc
+)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = "550e8400-e29b-41d4-a716-446655440000";
+    const SECOND: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+    #[test]
+    fn test_parse_call_ids() {
+        let ids = parse_call_ids(&format!(" {FIRST}, {SECOND},{FIRST},")).unwrap();
+        assert_eq!(
+            ids,
+            vec![
+                Uuid::parse_str(FIRST).unwrap(),
+                Uuid::parse_str(SECOND).unwrap()
+            ]
+        );
+
+        let error = parse_call_ids(&format!("{FIRST},{FIRST}")).unwrap_err();
+        assert!(error.message().contains("between 2 and 10"));
+
+        let too_many = (0..=MAX_COMPARED_CALLS)
+            .map(|_| Uuid::new_v4().to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(parse_call_ids(&too_many).is_err());
+
+        let error = parse_call_ids(&format!("{FIRST},not-a-call")).unwrap_err();
+        assert!(error.message().contains("not-a-call"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Conversation handlers
+//!
+//! Lists the conversations uploaded calls were grouped into and returns a
+//! conversation's calls in the order they were heard.
+
+use crate::{error::ApiError, handlers::calls::CallSummary, state::AppState, tenant::TenantScope};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{Conversation, ConversationFilter, ConversationQueries};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use uuid::Uuid;
+
+/// Default number of conversations per page
+const DEFAULT_LIMIT: i64 = 50;
+/// Largest page of conversations
+const MAX_LIMIT: i64 = 1000;
+
+/// Query parameters for listing conversations
+#[derive(Debug, Default, Deserialize)]
+pub struct ConversationListQuery {
+    /// Only conversations on this system (accepts both `system_id` and `system`)
+    #[serde(alias = "system")]
+    pub system_id: Option<SystemId>,
+    /// Only conversations on this talkgroup
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only conversations still going at or after this time (ISO 8601)
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only conversations started before this time (ISO 8601)
+    pub to_date: Option<DateTime<Utc>>,
+    /// Conversations per page (default 50, max 1000)
+    pub limit: Option<i64>,
+    /// Conversations to skip
+    pub offset: Option<i64>,
+}
+
+/// A page of conversations
+#[derive(Debug, Serialize)]
+pub struct ConversationListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Conversations, most recently active first
+    pub conversations: Vec<Conversation>,
+    /// Conversations matching the filters
+    pub total: i64,
+    /// Page size used
+    pub limit: i64,
+    /// Offset used
+    pub offset: i64,
+}
+
+/// A conversation and its calls
+#[derive(Debug, Serialize)]
+pub struct ConversationResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The conversation
+    pub conversation: Conversation,
+    /// Calls, oldest first
+    pub calls: Vec<CallSummary>,
+}
+
+/// List conversations, most recently active first
+///
+/// # Errors
+///
+/// Returns error if the paging parameters are invalid or the query fails
+pub async fn list_conversations(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<ConversationListQuery>,
+) -> Result<Json<ConversationListResponse>, ApiError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    if !(1..=MAX_LIMIT).contains(&limit) || offset < 0 {
+        return Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            format!("limit must be 1 to {MAX_LIMIT} and offset non-negative"),
+        ));
+    }
+    let filter = ConversationFilter {
+        system_id: query.system_id,
+        allowed_systems: scope.systems().map(<[SystemId]>::to_vec),
+        talkgroup_id: query.talkgroup_id,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
+
+    let result = async {
+        let conversations =
+            ConversationQueries::list(&state.read_pool, &filter, limit, offset).await?;
+        let total = ConversationQueries::count(&state.read_pool, &filter).await?;
+        Ok::<_, sdrtrunk_storage::StorageError>((conversations, total))
+    }
+    .await;
+    match result {
+        Ok((conversations, total)) => Ok(Json(ConversationListResponse {
+            success: true,
+            conversations,
+            total,
+            limit,
+            offset,
+        })),
+        Err(e) => {
+            error!("Failed to list conversations: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list conversations: {e}"
+            )))
+        }
+    }
+}
+
+/// Get a conversation with its calls
+///
+/// Conversations on systems outside the API key's scope are reported as
+/// missing.
+///
+/// # Errors
+///
+/// Returns error if the conversation is missing or the database query fails
+pub async fn get_conversation(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<ConversationResponse>, ApiError> {
+    let result = async {
+        let Some(conversation) = ConversationQueries::get(&state.pool, conversation_id).await?
+        else {
+            return Ok(None);
+        };
+        let calls = ConversationQueries::calls(&state.pool, conversation_id).await?;
+        Ok::<_, sdrtrunk_storage::StorageError>(Some((conversation, calls)))
+    }
+    .await;
+    match result {
+        Ok(Some((conversation, calls))) if scope.allows(&conversation.system_id) => {
+            Ok(Json(ConversationResponse {
+                success: true,
+                conversation,
+                calls: calls.into_iter().map(CallSummary::from).collect(),
+            }))
+        }
+        Ok(_) => Err(ApiError::not_found(
+            "CONVERSATION_NOT_FOUND",
+            format!("Conversation {conversation_id} not found"),
+        )),
+        Err(e) => {
+            error!("Failed to get conversation {conversation_id}: {e}");
+            Err(ApiError::database(format!(
+                "Failed to get conversation: {e}"
+            )))
+        }
+    }
+}
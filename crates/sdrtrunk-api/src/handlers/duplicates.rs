@@ -0,0 +1,139 @@
+//! Near-duplicate call handlers
+//!
+//! Lists calls whose recording repeats a call's audio, such as the same
+//! transmission heard through another simulcast site, by comparing the
+//! acoustic fingerprints computed at upload (see [`crate::fingerprint`]).
+
+use crate::{
+    error::{ApiError, ErrorResponse},
+    fingerprint::{near_duplicates, to_bits},
+    handlers::feedback::scoped_call,
+    state::AppState,
+    tenant::TenantScope,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::FingerprintQueries;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A call repeating another call's audio
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateCallInfo {
+    /// Call ID
+    pub call_id: Uuid,
+    /// System the call was heard on
+    pub system_id: String,
+    /// When the call started
+    pub call_timestamp: DateTime<Utc>,
+    /// Share of fingerprint bits that differ, from 0.0 to 1.0
+    pub bit_error_rate: f32,
+    /// Seconds the shared audio starts later in this call's recording than in
+    /// the requested call's (negative when earlier)
+    pub offset_seconds: f64,
+    /// Call this one was recorded as an echo of
+    pub echo_of: Option<Uuid>,
+}
+
+/// Near-duplicates of a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallDuplicatesResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Call ID
+    pub call_id: Uuid,
+    /// Earlier call this one was recorded as an echo of
+    pub echo_of: Option<Uuid>,
+    /// Calls repeating this call's audio, closest match first
+    pub duplicates: Vec<DuplicateCallInfo>,
+}
+
+/// List calls whose recording repeats a call's audio
+///
+/// Compares the call's fingerprint with those of calls that started within
+/// `fingerprints.window_seconds` of it, on any system the caller may see.
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist, is outside the API key's systems, or
+///   has not been fingerprinted
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// GET /api/calls/550e8400-e29b-41d4-a716-446655440000/duplicates
+/// ```
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/duplicates",
+    tag = "Calls",
+    summary = "List near-duplicate calls",
+    description = "Calls whose recording repeats this call's audio (e.g. simulcast echoes), found by acoustic fingerprint among calls that started within fingerprints.window_seconds of it.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "Near-duplicate calls", body = CallDuplicatesResponse),
+        (status = 404, description = "Call not found or not fingerprinted", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn get_call_duplicates(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<CallDuplicatesResponse>, ApiError> {
+    let call = scoped_call(&state, &scope, call_id).await?;
+    let database_error = |e: sdrtrunk_storage::StorageError| {
+        error!("Failed to look up duplicates of call {call_id}: {e}");
+        ApiError::database("Failed to look up duplicate calls")
+    };
+
+    let Some(stored) = FingerprintQueries::get(&state.pool, call_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Err(ApiError::not_found(
+            "FINGERPRINT_NOT_FOUND",
+            format!("Call {call_id} has no audio fingerprint"),
+        ));
+    };
+    let config = &state.config.fingerprints;
+    let candidates = FingerprintQueries::candidates(
+        &state.pool,
+        call_id,
+        call.call_timestamp,
+        config.window_seconds,
+    )
+    .await
+    .map_err(database_error)?
+    .into_iter()
+    .filter(|candidate| scope.allows(&candidate.system_id))
+    .collect();
+
+    let fingerprint: Vec<u32> = stored.fingerprint.iter().map(|&v| to_bits(v)).collect();
+    let duplicates = near_duplicates(&fingerprint, candidates, config.max_bit_error_rate)
+        .into_iter()
+        .map(|(candidate, m)| DuplicateCallInfo {
+            call_id: candidate.call_id,
+            system_id: candidate.system_id.to_string(),
+            call_timestamp: candidate.call_timestamp,
+            bit_error_rate: m.bit_error_rate,
+            offset_seconds: m.offset_seconds(),
+            echo_of: candidate.echo_of,
+        })
+        .collect();
+
+    Ok(Json(CallDuplicatesResponse {
+        success: true,
+        call_id,
+        echo_of: stored.echo_of,
+        duplicates,
+    }))
+}
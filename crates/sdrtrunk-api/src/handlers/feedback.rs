@@ -0,0 +1,383 @@
+//! Transcription quality feedback handlers
+//!
+//! Users rate a call's transcription and/or submit a corrected transcript;
+//! operators export the collected feedback as JSON Lines to build fine-tuning
+//! datasets.
+
+use crate::{error::ApiError, state::AppState, tenant::TenantScope};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{
+    FeedbackFilter, FeedbackQueries, NewTranscriptionFeedback, TranscriptionFeedback,
+    models::{ApiKeyDb, RadioCallDb},
+};
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Longest accepted corrected transcript, in characters
+const MAX_TEXT_CHARS: usize = 10_000;
+/// Longest accepted comment, in characters
+const MAX_COMMENT_CHARS: usize = 2_000;
+
+/// Feedback on a call's transcription
+#[derive(Debug, Deserialize)]
+pub struct SubmitFeedbackRequest {
+    /// Corrected transcript
+    pub corrected_text: Option<String>,
+    /// Quality rating from 1 (unusable) to 5 (perfect)
+    pub rating: Option<i16>,
+    /// Free-form remarks
+    pub comment: Option<String>,
+}
+
+impl SubmitFeedbackRequest {
+    /// Validate the request into the feedback to store for `call`
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid field
+    pub fn into_feedback(
+        self,
+        call: &RadioCallDb,
+        api_key_id: Option<String>,
+    ) -> Result<NewTranscriptionFeedback, String> {
+        let corrected_text = self
+            .corrected_text
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        if corrected_text.is_none() && self.rating.is_none() {
+            return Err("corrected_text or rating is required".to_string());
+        }
+        if corrected_text
+            .as_ref()
+            .is_some_and(|t| t.chars().count() > MAX_TEXT_CHARS)
+        {
+            return Err(format!(
+                "corrected_text must be at most {MAX_TEXT_CHARS} characters"
+            ));
+        }
+        if let Some(rating) = self.rating
+            && !(1..=5).contains(&rating)
+        {
+            return Err("rating must be 1 to 5".to_string());
+        }
+        let comment = self
+            .comment
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        if comment
+            .as_ref()
+            .is_some_and(|c| c.chars().count() > MAX_COMMENT_CHARS)
+        {
+            return Err(format!(
+                "comment must be at most {MAX_COMMENT_CHARS} characters"
+            ));
+        }
+
+        Ok(NewTranscriptionFeedback {
+            call_id: call.id,
+            original_text: call.transcription_text.clone(),
+            corrected_text,
+            rating: self.rating,
+            comment,
+            api_key_id,
+        })
+    }
+}
+
+/// Stored feedback
+#[derive(Debug, Serialize)]
+pub struct FeedbackResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The feedback
+    pub feedback: TranscriptionFeedback,
+}
+
+/// All feedback on a call
+#[derive(Debug, Serialize)]
+pub struct FeedbackListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Feedback, oldest first
+    pub feedback: Vec<TranscriptionFeedback>,
+}
+
+/// Query parameters for the feedback export
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedbackExportQuery {
+    /// Only feedback on calls from this system (accepts both `system_id` and
+    /// `system`)
+    #[serde(alias = "system")]
+    pub system_id: Option<SystemId>,
+    /// Only feedback rated at least this
+    pub min_rating: Option<i16>,
+    /// Only feedback with a corrected transcript
+    #[serde(default)]
+    pub corrected_only: bool,
+    /// Only feedback submitted at or after this time (ISO 8601)
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only feedback submitted before this time (ISO 8601)
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// Look up a call visible to `scope`
+///
+/// # Errors
+///
+/// Returns error if the call is missing or outside the scope, or the database
+/// query fails
+pub(crate) async fn scoped_call(
+    state: &AppState,
+    scope: &TenantScope,
+    call_id: Uuid,
+) -> Result<RadioCallDb, ApiError> {
+    match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if scope.allows(&call.system_id) => Ok(call),
+        Ok(_) => Err(ApiError::not_found(
+            "CALL_NOT_FOUND",
+            format!("Call {call_id} not found"),
+        )),
+        Err(e) => {
+            error!("Failed to retrieve call {}: {}", call_id, e);
+            Err(ApiError::database("Failed to retrieve call"))
+        }
+    }
+}
+
+/// Submit a correction and/or rating for a call's transcription
+///
+/// # Errors
+///
+/// Returns error if the call does not exist, the feedback is invalid, or the
+/// database query fails
+pub async fn submit_feedback(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    api_key: Option<Extension<ApiKeyDb>>,
+    Path(call_id): Path<Uuid>,
+    Json(request): Json<SubmitFeedbackRequest>,
+) -> Result<(StatusCode, Json<FeedbackResponse>), ApiError> {
+    let call = scoped_call(&state, &scope, call_id).await?;
+    let feedback = request
+        .into_feedback(&call, api_key.map(|Extension(key)| key.id))
+        .map_err(|e| ApiError::bad_request("INVALID_FEEDBACK", e))?;
+
+    match FeedbackQueries::insert(&state.pool, &feedback).await {
+        Ok(feedback) => {
+            info!(
+                "Transcription feedback {} recorded for call {call_id}",
+                feedback.id
+            );
+            Ok((
+                StatusCode::CREATED,
+                Json(FeedbackResponse {
+                    success: true,
+                    feedback,
+                }),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to store feedback for call {call_id}: {e}");
+            Err(ApiError::database("Failed to store feedback"))
+        }
+    }
+}
+
+/// List the feedback given on a call
+///
+/// # Errors
+///
+/// Returns error if the call does not exist or the database query fails
+pub async fn list_call_feedback(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<FeedbackListResponse>, ApiError> {
+    let call = scoped_call(&state, &scope, call_id).await?;
+    match FeedbackQueries::list_for_call(&state.pool, call.id).await {
+        Ok(feedback) => Ok(Json(FeedbackListResponse {
+            success: true,
+            feedback,
+        })),
+        Err(e) => {
+            error!("Failed to list feedback for call {call_id}: {e}");
+            Err(ApiError::database("Failed to list feedback"))
+        }
+    }
+}
+
+/// Export feedback as JSON Lines, one entry per line, oldest first
+///
+/// Each entry carries the call's recording location, language, and `text`:
+/// the correction, or the original transcript when the feedback is only a
+/// rating.
+///
+/// # Errors
+///
+/// Returns error if the filters are invalid or the database query fails
+pub async fn export_feedback(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<FeedbackExportQuery>,
+) -> Result<Response, ApiError> {
+    if query.min_rating.is_some_and(|r| !(1..=5).contains(&r)) {
+        return Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            "min_rating must be 1 to 5",
+        ));
+    }
+    let filter = FeedbackFilter {
+        system_id: query.system_id,
+        allowed_systems: scope.systems().map(<[SystemId]>::to_vec),
+        min_rating: query.min_rating,
+        corrected_only: query.corrected_only,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
+
+    let rows = FeedbackQueries::export(&state.pool, &filter)
+        .await
+        .map_err(|e| {
+            error!("Failed to export transcription feedback: {e}");
+            ApiError::database("Failed to export feedback")
+        })?;
+
+    let mut body = String::new();
+    for row in &rows {
+        let line = serde_json::to_string(row).map_err(|e| {
+            ApiError::internal(
+                "SERIALIZATION_ERROR",
+                format!("Failed to serialize feedback: {e}"),
+            )
+        })?;
+        body.push_str(&line);
+        body.push('\n');
+    }
+
+    info!("Exported {} transcription feedback entries", rows.len());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"transcription-feedback.jsonl\"",
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn call() -> RadioCallDb {
+        let now = Utc::now();
+        RadioCallDb {
+            id: Uuid::new_v4(),
+            created_at: now,
+            call_timestamp: now,
+            system_id: SystemId::new("metro").unwrap(),
+            system_label: None,
+            frequency: None,
+            talkgroup_id: None,
+            talkgroup_label: None,
+            talkgroup_group: None,
+            talkgroup_tag: None,
+            source_radio_id: None,
+            talker_alias: None,
+            audio_filename: None,
+            audio_file_path: None,
+            audio_size_bytes: None,
+            audio_content_type: None,
+            audio_sha256: None,
+            duration_seconds: None,
+            transcription_text: Some("engine to on scene".to_string()),
+            transcription_confidence: None,
+            transcription_language: None,
+            transcription_status: Some("completed".to_string()),
+            speaker_segments: None,
+            speaker_count: None,
+            patches: None,
+            frequencies: None,
+            sources: None,
+            upload_ip: None,
+            upload_timestamp: now,
+            upload_api_key_id: None,
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    fn request(json: serde_json::Value) -> SubmitFeedbackRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_into_feedback() {
+        let call = call();
+        let feedback = request(serde_json::json!({
+            "corrected_text": "  Engine 2 on scene ",
+            "rating": 3,
+            "comment": " "
+        }))
+        .into_feedback(&call, Some("key-1".to_string()))
+        .unwrap();
+
+        assert_eq!(feedback.call_id, call.id);
+        assert_eq!(
+            feedback.original_text.as_deref(),
+            Some("engine to on scene")
+        );
+        assert_eq!(
+            feedback.corrected_text.as_deref(),
+            Some("Engine 2 on scene")
+        );
+        assert_eq!(feedback.rating, Some(3));
+        assert!(feedback.comment.is_none());
+        assert_eq!(feedback.api_key_id.as_deref(), Some("key-1"));
+
+        // A rating alone is enough
+        assert!(
+            request(serde_json::json!({"rating": 5}))
+                .into_feedback(&call, None)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_into_feedback_validation() {
+        let call = call();
+        for json in [
+            serde_json::json!({}),
+            serde_json::json!({"corrected_text": "   "}),
+            serde_json::json!({"rating": 0}),
+            serde_json::json!({"rating": 6}),
+            serde_json::json!({"corrected_text": "x".repeat(MAX_TEXT_CHARS + 1)}),
+            serde_json::json!({"rating": 4, "comment": "x".repeat(MAX_COMMENT_CHARS + 1)}),
+        ] {
+            assert!(
+                request(json.clone()).into_feedback(&call, None).is_err(),
+                "{json} should be rejected"
+            );
+        }
+    }
+}
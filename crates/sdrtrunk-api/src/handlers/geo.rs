@@ -0,0 +1,194 @@
+//! Call map handlers
+//!
+//! Serves located calls as a `GeoJSON` feature collection, one point per call,
+//! for map views and GIS tools.
+
+use crate::{error::ApiError, state::AppState, tenant::TenantScope};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{CallLocation, GeoFilter, GeoQueries};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use uuid::Uuid;
+
+/// Default number of calls returned
+const DEFAULT_LIMIT: i64 = 500;
+/// Most calls returned at once
+const MAX_LIMIT: i64 = 5000;
+
+/// Query parameters for the call map
+#[derive(Debug, Default, Deserialize)]
+pub struct GeoCallsQuery {
+    /// Only calls on this system (accepts both `system_id` and `system`)
+    #[serde(alias = "system")]
+    pub system_id: Option<SystemId>,
+    /// Only calls on this talkgroup
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only calls at or after this time (ISO 8601)
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only calls before this time (ISO 8601)
+    pub to_date: Option<DateTime<Utc>>,
+    /// Most calls to return, newest first (default 500, max 5000)
+    pub limit: Option<i64>,
+}
+
+/// `GeoJSON` feature collection of calls
+#[derive(Debug, Serialize)]
+pub struct CallFeatureCollection {
+    /// Always `FeatureCollection`
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// One feature per call, newest first
+    pub features: Vec<CallFeature>,
+}
+
+/// `GeoJSON` feature for one call
+#[derive(Debug, Serialize)]
+pub struct CallFeature {
+    /// Always `Feature`
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Call ID
+    pub id: Uuid,
+    /// Where the call was heard
+    pub geometry: PointGeometry,
+    /// Call details
+    pub properties: CallFeatureProperties,
+}
+
+/// `GeoJSON` point
+#[derive(Debug, Serialize)]
+pub struct PointGeometry {
+    /// Always `Point`
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Longitude then latitude, in WGS 84 degrees
+    pub coordinates: [f64; 2],
+}
+
+/// Properties of a call feature
+#[derive(Debug, Serialize)]
+pub struct CallFeatureProperties {
+    /// When the call occurred
+    pub call_timestamp: DateTime<Utc>,
+    /// System the call was heard on
+    pub system_id: SystemId,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Talkgroup the call was heard on
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Transcription status
+    pub transcription_status: Option<String>,
+    /// Transcribed text, once available
+    pub transcription_text: Option<String>,
+}
+
+impl From<CallLocation> for CallFeature {
+    fn from(call: CallLocation) -> Self {
+        Self {
+            kind: "Feature",
+            id: call.id,
+            geometry: PointGeometry {
+                kind: "Point",
+                coordinates: [call.longitude, call.latitude],
+            },
+            properties: CallFeatureProperties {
+                call_timestamp: call.call_timestamp,
+                system_id: call.system_id,
+                system_label: call.system_label,
+                talkgroup_id: call.talkgroup_id,
+                talkgroup_label: call.talkgroup_label,
+                transcription_status: call.transcription_status,
+                transcription_text: call.transcription_text,
+            },
+        }
+    }
+}
+
+/// List located calls as `GeoJSON`, newest first
+///
+/// # Errors
+///
+/// Returns error if the limit is out of range or the query fails
+pub async fn geo_calls(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<GeoCallsQuery>,
+) -> Result<Json<CallFeatureCollection>, ApiError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            format!("limit must be 1 to {MAX_LIMIT}"),
+        ));
+    }
+    let filter = GeoFilter {
+        system_id: query.system_id,
+        allowed_systems: scope.systems().map(<[SystemId]>::to_vec),
+        talkgroup_id: query.talkgroup_id,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
+
+    match GeoQueries::list(&state.read_pool, &filter, limit).await {
+        Ok(calls) => Ok(Json(CallFeatureCollection {
+            kind: "FeatureCollection",
+            features: calls.into_iter().map(CallFeature::from).collect(),
+        })),
+        Err(e) => {
+            error!("Failed to list call locations: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list call locations: {e}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_feature_is_geojson() {
+        let call = CallLocation {
+            id: Uuid::new_v4(),
+            call_timestamp: Utc::now(),
+            system_id: SystemId::new("metro").unwrap(),
+            system_label: Some("Metro".to_string()),
+            talkgroup_id: Some(TalkgroupId::new(100).unwrap()),
+            talkgroup_label: Some("Dispatch".to_string()),
+            transcription_status: Some("completed".to_string()),
+            transcription_text: Some("Engine 4 responding".to_string()),
+            latitude: 35.7796,
+            longitude: -78.6382,
+        };
+        let collection = CallFeatureCollection {
+            kind: "FeatureCollection",
+            features: vec![CallFeature::from(call)],
+        };
+
+        let json = serde_json::to_value(&collection).unwrap();
+        assert_eq!(json["type"], "FeatureCollection");
+        let feature = &json["features"][0];
+        assert_eq!(feature["type"], "Feature");
+        assert_eq!(feature["geometry"]["type"], "Point");
+        assert_eq!(
+            feature["geometry"]["coordinates"],
+            serde_json::json!([-78.6382, 35.7796])
+        );
+        assert_eq!(feature["properties"]["system_id"], "metro");
+        assert_eq!(feature["properties"]["talkgroup_id"], 100);
+    }
+}
@@ -1,13 +1,20 @@
 //! Health check endpoints for monitoring and diagnostics
+//!
+//! `/health/live` only shows the process answers. `/health/ready` checks the
+//! database, transcription backend, job queue, and storage volume one by one
+//! and answers 503 when any of them is down, so orchestrators stop routing
+//! traffic to an instance that cannot take uploads.
 
 use crate::state::AppState;
 use axum::{extract::State, http::StatusCode, response::Json};
+use sdrtrunk_storage::{JobQueue, ProbeQueries, TranscriptionProbe};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tracing::{error, info};
+use std::{path::Path, sync::Arc};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
 
 /// Health check response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     /// Service status
     pub status: String,
@@ -24,7 +31,7 @@ pub struct HealthResponse {
 }
 
 /// Transcription queue health
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TranscriptionQueueHealth {
     /// Jobs waiting to be claimed
     pub pending: i64,
@@ -37,7 +44,7 @@ pub struct TranscriptionQueueHealth {
 }
 
 /// Database health status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DatabaseHealth {
     /// Database connection status
     pub connected: bool,
@@ -48,7 +55,7 @@ pub struct DatabaseHealth {
 }
 
 /// Connection pool statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PoolStats {
     /// Number of connections currently in use
     pub connections_in_use: u32,
@@ -59,12 +66,133 @@ pub struct PoolStats {
 }
 
 /// Readiness check response (simpler than health)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ReadinessResponse {
     /// Service readiness status
     pub ready: bool,
     /// Timestamp of the check
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Transcription backend health from worker probes (absent if no worker has probed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcription_backend: Option<TranscriptionBackendHealth>,
+}
+
+/// Transcription backend health derived from synthetic worker probes
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranscriptionBackendHealth {
+    /// At least one worker has a recent, successful probe
+    pub healthy: bool,
+    /// Workers that have ever reported a probe
+    pub workers_reporting: usize,
+    /// Workers whose latest probe is recent and successful
+    pub healthy_workers: usize,
+    /// Time of the most recent probe from any worker
+    pub last_probe_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Latency of the most recent probe in milliseconds
+    pub last_latency_ms: Option<i64>,
+    /// Most recent probe error, if any worker is failing
+    pub last_error: Option<String>,
+}
+
+/// Liveness check response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LivenessResponse {
+    /// Always true when the process answers
+    pub alive: bool,
+    /// Service version
+    pub version: String,
+    /// Timestamp of the check
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Process uptime in seconds
+    pub uptime_seconds: u64,
+}
+
+/// State of one component checked for readiness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    /// Working normally
+    Ok,
+    /// Impaired, but the service can still take traffic
+    Degraded,
+    /// Unusable, so the service is not ready
+    Down,
+}
+
+/// Component-level readiness response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ComponentReadinessResponse {
+    /// No component is down
+    pub ready: bool,
+    /// Timestamp of the check
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Database connectivity and pool usage
+    pub database: DatabaseReadiness,
+    /// Transcription backend reachability from worker probes
+    pub transcription: TranscriptionReadiness,
+    /// Transcription queue depth against its capacity
+    pub queue: QueueReadiness,
+    /// Free space on the storage volume
+    pub disk: DiskReadiness,
+}
+
+/// Database readiness
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DatabaseReadiness {
+    /// Component state (down when unreachable, degraded when the pool is exhausted)
+    pub status: ComponentStatus,
+    /// Why the component is degraded or down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Round trip of a trivial query in milliseconds
+    pub response_time_ms: Option<u64>,
+    /// Connection pool statistics
+    pub pool: PoolStats,
+}
+
+/// Transcription backend readiness
+///
+/// Never down: uploads are still queued while workers recover.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranscriptionReadiness {
+    /// Component state
+    pub status: ComponentStatus,
+    /// Why the component is degraded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Worker probe summary (absent if no worker has probed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<TranscriptionBackendHealth>,
+}
+
+/// Transcription queue readiness
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QueueReadiness {
+    /// Component state (down once the queue is full)
+    pub status: ComponentStatus,
+    /// Why the component is degraded or down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Unfinished jobs (pending plus in-flight)
+    pub depth: Option<i64>,
+    /// Configured `transcription.queue_size`
+    pub capacity: Option<usize>,
+}
+
+/// Storage volume readiness
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiskReadiness {
+    /// Component state (down when inaccessible or below `min_free_bytes`)
+    pub status: ComponentStatus,
+    /// Why the component is down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Storage base directory
+    pub path: String,
+    /// Bytes available to the service
+    pub free_bytes: Option<u64>,
+    /// Configured `storage.min_free_bytes`
+    pub min_free_bytes: u64,
 }
 
 /// Basic health check endpoint for monitoring systems
@@ -105,6 +233,17 @@ pub struct ReadinessResponse {
 /// # Errors
 ///
 /// Returns `SERVICE_UNAVAILABLE` if the database health check fails.
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "Health",
+    summary = "Health check",
+    description = "Service status, database connectivity, pool statistics, and transcription queue counts.",
+    responses(
+        (status = 200, description = "Service is healthy", body = HealthResponse),
+        (status = 503, description = "Database unavailable"),
+    ),
+)]
 pub async fn health_check(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<HealthResponse>, StatusCode> {
@@ -123,12 +262,12 @@ pub async fn health_check(
     let response_time = start_time.elapsed().as_millis() as u64;
 
     // Check transcription queue
-    let queue_health = match sdrtrunk_storage::jobs::JobQueue::stats(&state.pool).await {
-        Ok(stats) => Some(TranscriptionQueueHealth {
-            pending: stats.pending,
-            processing: stats.processing,
-            completed: stats.completed,
-            failed: stats.failed,
+    let queue_health = match JobQueue::stats(&state.pool).await {
+        Ok(queue_stats) => Some(TranscriptionQueueHealth {
+            pending: queue_stats.pending,
+            processing: queue_stats.processing,
+            completed: queue_stats.completed,
+            failed: queue_stats.failed,
         }),
         Err(e) => {
             error!("Queue stats check failed: {e}");
@@ -151,20 +290,36 @@ pub async fn health_check(
 
 /// Readiness check endpoint for Kubernetes-style health checks
 ///
-/// Returns 200 OK if the service is ready to accept traffic.
+/// Returns 200 OK if the service is ready to accept traffic. Transcription
+/// backend health from worker probes is reported alongside but does not fail
+/// readiness, since uploads are still queued while workers recover.
 ///
 /// # Errors
 ///
 /// Returns `SERVICE_UNAVAILABLE` if the database is not accessible.
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "Health",
+    summary = "Readiness check",
+    description = "Whether the service can take traffic, with transcription backend health from worker probes.",
+    responses(
+        (status = 200, description = "Service is ready", body = ReadinessResponse),
+        (status = 503, description = "Database unavailable"),
+    ),
+)]
 pub async fn readiness_check(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ReadinessResponse>, StatusCode> {
     // Simple database ping to verify readiness
     match sqlx::query("SELECT 1").fetch_one(&state.pool).await {
         Ok(_) => {
+            let transcription_backend = check_transcription_backend(&state).await;
+
             let response = ReadinessResponse {
                 ready: true,
                 timestamp: chrono::Utc::now(),
+                transcription_backend,
             };
             Ok(Json(response))
         }
@@ -175,6 +330,301 @@ pub async fn readiness_check(
     }
 }
 
+/// Liveness check endpoint
+///
+/// Answers as long as the process can serve requests; dependencies are not
+/// checked, so orchestrators only restart a hung process.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "Health",
+    summary = "Liveness check",
+    description = "Whether the process is up. Does not check dependencies.",
+    responses(
+        (status = 200, description = "Process is alive", body = LivenessResponse),
+    ),
+)]
+pub async fn liveness_check() -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        alive: true,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: chrono::Utc::now(),
+        uptime_seconds: get_uptime_seconds(),
+    })
+}
+
+/// Component-level readiness check endpoint
+///
+/// Checks the database, transcription backend, transcription queue, and
+/// storage volume. The response body is the same either way; the status is
+/// 503 when any component is down.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "Health",
+    summary = "Component readiness",
+    description = "Database, transcription backend, queue saturation, and free disk space. 503 when any component is down.",
+    responses(
+        (status = 200, description = "Service is ready", body = ComponentReadinessResponse),
+        (status = 503, description = "A component is down", body = ComponentReadinessResponse),
+    ),
+)]
+pub async fn component_readiness_check(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ComponentReadinessResponse>) {
+    let start_time = std::time::Instant::now();
+    let ping = sqlx::query("SELECT 1")
+        .fetch_one(&state.pool)
+        .await
+        .map(|_| u64::try_from(start_time.elapsed().as_millis()).unwrap_or(u64::MAX))
+        .map_err(|e| e.to_string());
+    let database = database_readiness(ping, pool_stats(&state.pool));
+    let database_up = database.status != ComponentStatus::Down;
+
+    // Skip the remaining queries when they would only wait out the pool timeout
+    let transcription_config = state.config.transcription.as_ref();
+    let transcription_enabled = transcription_config.is_some_and(|t| t.enabled);
+    let backend = if transcription_enabled && database_up {
+        check_transcription_backend(&state).await
+    } else {
+        None
+    };
+    let transcription = transcription_readiness(transcription_enabled, backend);
+
+    let depth = if database_up {
+        match JobQueue::stats(&state.pool).await {
+            Ok(queue_stats) => Some(queue_stats.pending + queue_stats.processing),
+            Err(e) => {
+                warn!("Failed to read transcription queue depth: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let queue = queue_readiness(depth, transcription_config.map(|t| t.queue_size));
+
+    let storage = &state.config.storage;
+    let disk = disk_readiness(
+        &storage.base_dir,
+        free_bytes(&storage.base_dir),
+        storage.min_free_bytes,
+    );
+
+    let ready = [
+        database.status,
+        transcription.status,
+        queue.status,
+        disk.status,
+    ]
+    .iter()
+    .all(|status| *status != ComponentStatus::Down);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        warn!("Readiness check failed: a component is down");
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(ComponentReadinessResponse {
+            ready,
+            timestamp: chrono::Utc::now(),
+            database,
+            transcription,
+            queue,
+            disk,
+        }),
+    )
+}
+
+/// Rate the database from a ping (round trip in ms, or the error) and pool usage
+fn database_readiness(ping: Result<u64, String>, pool: PoolStats) -> DatabaseReadiness {
+    let (status, message, response_time_ms) = match ping {
+        Err(e) => (ComponentStatus::Down, Some(e), None),
+        Ok(ms) if pool.idle_connections == 0 && pool.connections_in_use >= pool.max_connections => {
+            (
+                ComponentStatus::Degraded,
+                Some("Connection pool exhausted".to_string()),
+                Some(ms),
+            )
+        }
+        Ok(ms) => (ComponentStatus::Ok, None, Some(ms)),
+    };
+    DatabaseReadiness {
+        status,
+        message,
+        response_time_ms,
+        pool,
+    }
+}
+
+/// Rate the transcription backend from worker probes
+fn transcription_readiness(
+    enabled: bool,
+    backend: Option<TranscriptionBackendHealth>,
+) -> TranscriptionReadiness {
+    let (status, message) = match &backend {
+        _ if !enabled => (
+            ComponentStatus::Ok,
+            Some("Transcription disabled".to_string()),
+        ),
+        None => (
+            ComponentStatus::Degraded,
+            Some("No worker has probed the transcription backend".to_string()),
+        ),
+        Some(backend) if backend.healthy => (ComponentStatus::Ok, None),
+        Some(backend) => (
+            ComponentStatus::Degraded,
+            Some(
+                backend
+                    .last_error
+                    .clone()
+                    .unwrap_or_else(|| "No recent successful probe".to_string()),
+            ),
+        ),
+    };
+    TranscriptionReadiness {
+        status,
+        message,
+        backend,
+    }
+}
+
+/// Rate the transcription queue; a full queue means uploads pile up unprocessed
+fn queue_readiness(depth: Option<i64>, capacity: Option<usize>) -> QueueReadiness {
+    let (status, message) = match (depth, capacity) {
+        (None, _) => (
+            ComponentStatus::Degraded,
+            Some("Queue depth unavailable".to_string()),
+        ),
+        (Some(depth), Some(capacity))
+            if usize::try_from(depth).is_ok_and(|depth| depth >= capacity) =>
+        {
+            (
+                ComponentStatus::Down,
+                Some(format!("Transcription queue full ({depth}/{capacity})")),
+            )
+        }
+        (Some(_), _) => (ComponentStatus::Ok, None),
+    };
+    QueueReadiness {
+        status,
+        message,
+        depth,
+        capacity,
+    }
+}
+
+/// Rate the storage volume from its free space (`None` when not reported)
+fn disk_readiness(
+    path: &Path,
+    free_bytes: std::io::Result<Option<u64>>,
+    min_free_bytes: u64,
+) -> DiskReadiness {
+    let (status, message, free_bytes) = match free_bytes {
+        Err(e) => (
+            ComponentStatus::Down,
+            Some(format!("Storage path not accessible: {e}")),
+            None,
+        ),
+        Ok(Some(free)) if free < min_free_bytes => (
+            ComponentStatus::Down,
+            Some(format!(
+                "Only {free} bytes free, below the {min_free_bytes} byte minimum"
+            )),
+            Some(free),
+        ),
+        Ok(free) => (ComponentStatus::Ok, None, free),
+    };
+    DiskReadiness {
+        status,
+        message,
+        path: path.display().to_string(),
+        free_bytes,
+        min_free_bytes,
+    }
+}
+
+/// Bytes available to unprivileged users on the volume holding `path`
+///
+/// # Errors
+///
+/// Returns error if `path` cannot be examined
+#[cfg(unix)]
+fn free_bytes(path: &Path) -> std::io::Result<Option<u64>> {
+    let stats = rustix::fs::statvfs(path).map_err(std::io::Error::from)?;
+    Ok(Some(stats.f_bavail.saturating_mul(stats.f_frsize)))
+}
+
+/// Free space is not reported on this platform; only check the path exists
+///
+/// # Errors
+///
+/// Returns error if `path` cannot be examined
+#[cfg(not(unix))]
+fn free_bytes(path: &Path) -> std::io::Result<Option<u64>> {
+    std::fs::metadata(path).map(|_| None)
+}
+
+/// Load worker probe results and summarize transcription backend health
+async fn check_transcription_backend(state: &AppState) -> Option<TranscriptionBackendHealth> {
+    let probes = match ProbeQueries::list(&state.pool).await {
+        Ok(probes) => probes,
+        Err(e) => {
+            warn!("Failed to load transcription probes: {e}");
+            return None;
+        }
+    };
+
+    let backend = evaluate_probes(&probes, chrono::Utc::now(), probe_max_age_seconds(state))?;
+    if !backend.healthy {
+        warn!(
+            "Transcription backend unhealthy: {}/{} workers passing probes",
+            backend.healthy_workers, backend.workers_reporting
+        );
+    }
+    Some(backend)
+}
+
+/// Oldest probe (in seconds) still considered current: three probe intervals
+fn probe_max_age_seconds(state: &AppState) -> i64 {
+    let interval = state
+        .config
+        .transcription
+        .as_ref()
+        .map_or(300, |t| t.probe_interval_seconds);
+    i64::try_from(interval.saturating_mul(3)).unwrap_or(i64::MAX)
+}
+
+/// Summarize worker probe results (`None` when no worker has reported yet)
+fn evaluate_probes(
+    probes: &[TranscriptionProbe],
+    now: chrono::DateTime<chrono::Utc>,
+    max_age_seconds: i64,
+) -> Option<TranscriptionBackendHealth> {
+    let latest = probes.iter().max_by_key(|p| p.probed_at)?;
+    let healthy_workers = probes
+        .iter()
+        .filter(|p| p.success && p.is_fresh(now, max_age_seconds))
+        .count();
+    let last_error = probes
+        .iter()
+        .filter(|p| !p.success)
+        .max_by_key(|p| p.probed_at)
+        .and_then(|p| p.error.clone());
+
+    Some(TranscriptionBackendHealth {
+        healthy: healthy_workers > 0,
+        workers_reporting: probes.len(),
+        healthy_workers,
+        last_probe_at: Some(latest.probed_at),
+        last_latency_ms: Some(latest.latency_ms),
+        last_error,
+    })
+}
+
 /// Check database health and gather metrics
 ///
 /// # Errors
@@ -191,20 +641,23 @@ async fn check_database_health(state: &Arc<AppState>) -> Result<DatabaseHealth,
 
     let response_time_ms = start_time.elapsed().as_millis() as u64;
 
-    // Get pool statistics
-    let pool_stats = PoolStats {
-        connections_in_use: state.pool.num_idle() as u32, // Note: SQLx doesn't expose in-use count directly
-        max_connections: state.pool.options().get_max_connections(),
-        idle_connections: state.pool.num_idle() as u32,
-    };
-
     Ok(DatabaseHealth {
         connected: true,
-        pool_stats,
+        pool_stats: pool_stats(&state.pool),
         response_time_ms,
     })
 }
 
+/// Current connection pool usage
+fn pool_stats(pool: &sqlx::PgPool) -> PoolStats {
+    let idle_connections = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX);
+    PoolStats {
+        connections_in_use: pool.size().saturating_sub(idle_connections),
+        max_connections: pool.options().get_max_connections(),
+        idle_connections,
+    }
+}
+
 /// Get system uptime in seconds (simplified - returns process uptime)
 fn get_uptime_seconds() -> u64 {
     static START_TIME: std::sync::LazyLock<std::time::Instant> =
@@ -287,7 +740,7 @@ fn get_memory_usage() -> serde_json::Value {
 }
 
 /// Get disk usage for storage directory
-fn get_disk_usage(path: &std::path::Path) -> serde_json::Value {
+fn get_disk_usage(path: &Path) -> serde_json::Value {
     match std::fs::metadata(path) {
         Ok(_) => serde_json::json!({
             "storage_path": path.display().to_string(),
@@ -343,6 +796,7 @@ fn get_disk_usage(path: &std::path::Path) -> serde_json::Value {
 )]
 mod tests {
     use super::*;
+    use chrono::Utc;
     use tempfile::TempDir;
 
     // Mock functions removed - they were causing unused warnings
@@ -379,6 +833,7 @@ mod tests {
         let readiness_response = ReadinessResponse {
             ready: true,
             timestamp: chrono::Utc::now(),
+            transcription_backend: None,
         };
 
         let json = serde_json::to_string(&readiness_response).expect("Failed to serialize");
@@ -506,6 +961,7 @@ mod tests {
         let readiness = ReadinessResponse {
             ready: true,
             timestamp,
+            transcription_backend: None,
         };
 
         assert!(readiness.ready);
@@ -520,6 +976,7 @@ mod tests {
         let readiness = ReadinessResponse {
             ready: false,
             timestamp: chrono::Utc::now(),
+            transcription_backend: None,
         };
 
         assert!(!readiness.ready);
@@ -678,6 +1135,7 @@ mod tests {
         let readiness = ReadinessResponse {
             ready: true,
             timestamp: Utc::now(),
+            transcription_backend: None,
         };
 
         let json =
@@ -792,6 +1250,7 @@ mod tests {
                     },
                     response_time_ms: response_time,
                 },
+                transcription_queue: None,
                 uptime_seconds: uptime,
             };
 
@@ -801,4 +1260,157 @@ mod tests {
             assert_eq!(health.uptime_seconds, uptime);
         }
     }
+
+    fn probe(worker_id: &str, age_seconds: i64, success: bool) -> TranscriptionProbe {
+        TranscriptionProbe {
+            worker_id: worker_id.to_string(),
+            probed_at: Utc::now() - chrono::Duration::seconds(age_seconds),
+            success,
+            latency_ms: 1_200,
+            transcript: success.then(String::new),
+            error: (!success).then(|| "ffmpeg conversion failed".to_string()),
+            consecutive_failures: i32::from(!success),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_probes_none_reported() {
+        assert!(evaluate_probes(&[], Utc::now(), 900).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_probes_healthy_and_failing() {
+        let probes = vec![probe("w1", 30, true), probe("w2", 10, false)];
+        let health = evaluate_probes(&probes, Utc::now(), 900).unwrap();
+
+        assert!(health.healthy);
+        assert_eq!(health.workers_reporting, 2);
+        assert_eq!(health.healthy_workers, 1);
+        assert_eq!(
+            health.last_error.as_deref(),
+            Some("ffmpeg conversion failed")
+        );
+    }
+
+    #[test]
+    fn test_evaluate_probes_stale() {
+        // A hung worker stops probing; its last success goes stale
+        let probes = vec![probe("w1", 5_000, true)];
+        let health = evaluate_probes(&probes, Utc::now(), 900).unwrap();
+
+        assert!(!health.healthy);
+        assert_eq!(health.healthy_workers, 0);
+        assert!(health.last_error.is_none());
+    }
+
+    #[test]
+    fn test_database_readiness() {
+        let pool = |in_use, idle| PoolStats {
+            connections_in_use: in_use,
+            max_connections: 10,
+            idle_connections: idle,
+        };
+
+        let up = database_readiness(Ok(3), pool(2, 3));
+        assert_eq!(up.status, ComponentStatus::Ok);
+        assert_eq!(up.response_time_ms, Some(3));
+
+        let exhausted = database_readiness(Ok(3), pool(10, 0));
+        assert_eq!(exhausted.status, ComponentStatus::Degraded);
+
+        let down = database_readiness(Err("connection refused".to_string()), pool(0, 0));
+        assert_eq!(down.status, ComponentStatus::Down);
+        assert_eq!(down.message.as_deref(), Some("connection refused"));
+        assert!(down.response_time_ms.is_none());
+    }
+
+    #[test]
+    fn test_transcription_readiness_never_down() {
+        let backend = |healthy| TranscriptionBackendHealth {
+            healthy,
+            workers_reporting: 1,
+            healthy_workers: usize::from(healthy),
+            last_probe_at: Some(Utc::now()),
+            last_latency_ms: Some(120),
+            last_error: (!healthy).then(|| "connection refused".to_string()),
+        };
+
+        assert_eq!(
+            transcription_readiness(false, None).status,
+            ComponentStatus::Ok
+        );
+        assert_eq!(
+            transcription_readiness(true, Some(backend(true))).status,
+            ComponentStatus::Ok
+        );
+        assert_eq!(
+            transcription_readiness(true, None).status,
+            ComponentStatus::Degraded
+        );
+        let failing = transcription_readiness(true, Some(backend(false)));
+        assert_eq!(failing.status, ComponentStatus::Degraded);
+        assert_eq!(failing.message.as_deref(), Some("connection refused"));
+    }
+
+    #[test]
+    fn test_queue_readiness() {
+        assert_eq!(
+            queue_readiness(Some(5), Some(100)).status,
+            ComponentStatus::Ok
+        );
+        assert_eq!(
+            queue_readiness(Some(5_000), None).status,
+            ComponentStatus::Ok
+        );
+        assert_eq!(
+            queue_readiness(None, Some(100)).status,
+            ComponentStatus::Degraded
+        );
+
+        let full = queue_readiness(Some(100), Some(100));
+        assert_eq!(full.status, ComponentStatus::Down);
+        assert_eq!(
+            full.message.as_deref(),
+            Some("Transcription queue full (100/100)")
+        );
+    }
+
+    #[test]
+    fn test_disk_readiness() {
+        let path = Path::new("/data");
+        assert_eq!(
+            disk_readiness(path, Ok(Some(2_000)), 1_000).status,
+            ComponentStatus::Ok
+        );
+        assert_eq!(
+            disk_readiness(path, Ok(None), 1_000).status,
+            ComponentStatus::Ok
+        );
+
+        let low = disk_readiness(path, Ok(Some(500)), 1_000);
+        assert_eq!(low.status, ComponentStatus::Down);
+        assert_eq!(low.free_bytes, Some(500));
+
+        let missing = disk_readiness(
+            path,
+            Err(std::io::Error::from(std::io::ErrorKind::NotFound)),
+            1_000,
+        );
+        assert_eq!(missing.status, ComponentStatus::Down);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_free_bytes_of_temp_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(free_bytes(temp_dir.path()).unwrap().is_some());
+        assert!(free_bytes(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_liveness_check() {
+        let Json(liveness) = liveness_check().await;
+        assert!(liveness.alive);
+        assert_eq!(liveness.version, env!("CARGO_PKG_VERSION"));
+    }
 }
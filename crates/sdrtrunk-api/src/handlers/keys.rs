@@ -0,0 +1,311 @@
+//! Self-service API key usage handlers
+//!
+//! Lets feed operators inspect the upload activity of their own key so they
+//! can diagnose rejections and throttling without contacting the admin. The
+//! caller must present the key being inspected.
+
+use crate::{error::ApiError, handlers::admin::hash_api_key, state::AppState};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{
+    ApiKeyUsage, StorageError, get_api_key_usage, models::ApiKeyDb, queries::ApiKeyQueries,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, warn};
+use validator::Validate;
+
+/// Maximum number of client IPs reported
+const RECENT_IP_LIMIT: i64 = 10;
+
+/// Query parameters for API key usage
+#[derive(Debug, Deserialize, Validate)]
+pub struct KeyUsageQuery {
+    /// Days of history to include (default 7)
+    #[validate(range(min = 1, max = 90))]
+    pub days: Option<i32>,
+}
+
+/// Usage statistics for an API key
+#[derive(Debug, Serialize)]
+pub struct KeyUsageResponse {
+    /// Key ID
+    pub key_id: String,
+    /// Whether the key is active
+    pub active: bool,
+    /// Key expiration timestamp
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Lifetime authenticated requests
+    pub total_requests: i64,
+    /// Last time the key was used
+    pub last_used: Option<DateTime<Utc>>,
+    /// Days of history covered by the upload figures
+    pub window_days: i32,
+    /// Successful uploads in the window
+    pub uploads: i64,
+    /// Bytes uploaded in the window
+    pub upload_bytes: i64,
+    /// Rejected uploads in the window
+    pub rejections: i64,
+    /// Percentage of attempts rejected in the window
+    pub rejection_rate: f64,
+    /// Most recent upload attempt in the window
+    pub last_upload_at: Option<DateTime<Utc>>,
+    /// Rejection reasons, most frequent first
+    pub rejection_reasons: Vec<RejectionReason>,
+    /// Client IPs that used the key, most recent first
+    pub recent_ips: Vec<ClientIpActivity>,
+}
+
+/// Count of rejected uploads for one reason
+#[derive(Debug, Serialize)]
+pub struct RejectionReason {
+    /// Error message recorded for the rejection
+    pub reason: String,
+    /// Number of rejections
+    pub count: i64,
+}
+
+/// Last activity from one client IP
+#[derive(Debug, Serialize)]
+pub struct ClientIpActivity {
+    /// Client IP address
+    pub ip: String,
+    /// Last upload attempt from this IP
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Extract the presented API key from `X-API-Key` or `Authorization: Bearer`
+pub(crate) fn presented_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+}
+
+/// Resolve the presented key and check it matches the requested key ID
+///
+/// # Errors
+///
+/// Returns 401 if no valid key is presented, 403 if it belongs to another key ID.
+async fn authorize_key(
+    state: &AppState,
+    headers: &HeaderMap,
+    key_id: &str,
+) -> Result<ApiKeyDb, ApiError> {
+    let Some(presented) = presented_api_key(headers) else {
+        return Err(ApiError::unauthorized("UNAUTHORIZED", "API key required"));
+    };
+
+    let api_key = match ApiKeyQueries::find_by_key_hash(&state.pool, &hash_api_key(presented)).await
+    {
+        Ok(key) => key,
+        Err(StorageError::NotFound { .. }) => {
+            return Err(ApiError::unauthorized("UNAUTHORIZED", "Invalid API key"));
+        }
+        Err(StorageError::Query(ref msg)) if msg.contains("no rows returned") => {
+            return Err(ApiError::unauthorized("UNAUTHORIZED", "Invalid API key"));
+        }
+        Err(e) => {
+            error!("Failed to look up API key: {}", e);
+            return Err(ApiError::database("Failed to look up API key"));
+        }
+    };
+
+    if api_key.id != key_id {
+        warn!(
+            "API key {} attempted to read usage for {}",
+            api_key.id, key_id
+        );
+        return Err(ApiError::forbidden(
+            "FORBIDDEN",
+            "API key may only read its own usage",
+        ));
+    }
+
+    Ok(api_key)
+}
+
+/// Get usage statistics for an API key
+///
+/// # Errors
+///
+/// Returns an error if the caller does not present the key, the query
+/// parameters are invalid, or the database query fails.
+pub async fn get_key_usage(
+    State(state): State<Arc<AppState>>,
+    Path(key_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<KeyUsageQuery>,
+) -> Result<Json<KeyUsageResponse>, ApiError> {
+    if let Err(validation_errors) = query.validate() {
+        warn!("Invalid query parameters: {:?}", validation_errors);
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
+    }
+
+    let api_key = authorize_key(&state, &headers, &key_id).await?;
+    let window_days = query.days.unwrap_or(7);
+
+    let usage = get_api_key_usage(&state.pool, &key_id, window_days, RECENT_IP_LIMIT)
+        .await
+        .map_err(|e| {
+            error!("Failed to retrieve usage for API key {}: {}", key_id, e);
+            ApiError::database("Failed to retrieve API key usage")
+        })?;
+
+    Ok(Json(build_usage_response(&api_key, window_days, usage)))
+}
+
+/// Combine key metadata with windowed upload activity
+#[allow(clippy::cast_precision_loss)]
+fn build_usage_response(
+    api_key: &ApiKeyDb,
+    window_days: i32,
+    usage: ApiKeyUsage,
+) -> KeyUsageResponse {
+    let attempts = usage.uploads + usage.rejections;
+    let rejection_rate = if attempts > 0 {
+        (usage.rejections as f64 / attempts as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    KeyUsageResponse {
+        key_id: api_key.id.clone(),
+        active: api_key.active,
+        expires_at: api_key.expires_at,
+        total_requests: i64::from(api_key.total_requests.unwrap_or(0)),
+        last_used: api_key.last_used,
+        window_days,
+        uploads: usage.uploads,
+        upload_bytes: usage.upload_bytes,
+        rejections: usage.rejections,
+        rejection_rate,
+        last_upload_at: usage.last_upload_at,
+        rejection_reasons: usage
+            .rejection_reasons
+            .into_iter()
+            .map(|r| RejectionReason {
+                reason: r.reason,
+                count: r.count,
+            })
+            .collect(),
+        recent_ips: usage
+            .recent_ips
+            .into_iter()
+            .map(|a| ClientIpActivity {
+                ip: a.ip,
+                last_seen: a.last_seen,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use sdrtrunk_storage::{ApiKeyIpActivity, ApiKeyRejectionCount};
+
+    fn api_key() -> ApiKeyDb {
+        ApiKeyDb {
+            id: "feed-1".to_string(),
+            key_hash: hash_api_key("secret"),
+            description: None,
+            created_at: Utc::now(),
+            expires_at: None,
+            allowed_ips: None,
+            allowed_systems: None,
+            active: true,
+            last_used: Some(Utc::now()),
+            total_requests: Some(42),
+        }
+    }
+
+    #[test]
+    fn test_presented_api_key_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(presented_api_key(&headers), None);
+
+        headers.insert("x-api-key", HeaderValue::from_static("abc"));
+        assert_eq!(presented_api_key(&headers), Some("abc"));
+    }
+
+    #[test]
+    fn test_presented_api_key_bearer() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer xyz"));
+        assert_eq!(presented_api_key(&headers), Some("xyz"));
+
+        headers.insert("authorization", HeaderValue::from_static("Basic xyz"));
+        assert_eq!(presented_api_key(&headers), None);
+
+        headers.insert("x-api-key", HeaderValue::from_static("  "));
+        assert_eq!(presented_api_key(&headers), None);
+    }
+
+    #[test]
+    fn test_build_usage_response() {
+        let usage = ApiKeyUsage {
+            uploads: 30,
+            upload_bytes: 3_000_000,
+            rejections: 10,
+            last_upload_at: Some(Utc::now()),
+            rejection_reasons: vec![ApiKeyRejectionCount {
+                reason: "File too large".to_string(),
+                count: 10,
+            }],
+            recent_ips: vec![ApiKeyIpActivity {
+                ip: "10.0.0.1".to_string(),
+                last_seen: Utc::now(),
+            }],
+        };
+
+        let response = build_usage_response(&api_key(), 7, usage);
+        assert_eq!(response.key_id, "feed-1");
+        assert_eq!(response.total_requests, 42);
+        assert_eq!(response.window_days, 7);
+        assert_eq!(response.rejection_rate, 25.0);
+        assert_eq!(response.rejection_reasons[0].reason, "File too large");
+        assert_eq!(response.recent_ips[0].ip, "10.0.0.1");
+    }
+
+    #[test]
+    fn test_build_usage_response_no_activity() {
+        let response = build_usage_response(&api_key(), 7, ApiKeyUsage::default());
+        assert_eq!(response.uploads, 0);
+        assert_eq!(response.rejection_rate, 0.0);
+        assert!(response.recent_ips.is_empty());
+    }
+
+    #[test]
+    fn test_key_usage_query_validation() {
+        assert!(KeyUsageQuery { days: Some(7) }.validate().is_ok());
+        assert!(KeyUsageQuery { days: None }.validate().is_ok());
+        assert!(KeyUsageQuery { days: Some(0) }.validate().is_err());
+        assert!(KeyUsageQuery { days: Some(91) }.validate().is_err());
+    }
+}
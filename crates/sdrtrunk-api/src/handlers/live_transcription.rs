@@ -0,0 +1,413 @@
+//! WebSocket handler for live transcription of streamed audio
+//!
+//! After connecting, the client sends binary frames of 16-bit little-endian
+//! mono PCM at the `sample_rate` given in the query string. The server
+//! answers with JSON text frames: `ready` once, then `partial` transcripts
+//! of the segment being recorded and a `final` transcript for each complete
+//! segment. The client sends `{"type": "flush"}` to finish the current
+//! segment early (e.g. when the squelch closes), or `{"type": "end"}` to
+//! finish it and close the session.
+
+use axum::{
+    extract::{
+        Query, State, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+use crate::{
+    error::ApiError,
+    live_transcription::{
+        Chunk, ChunkKind, DEFAULT_SAMPLE_RATE, LiveTranscriber, MAX_SAMPLE_RATE, MIN_SAMPLE_RATE,
+        Segmenter,
+    },
+    state::AppState,
+};
+
+/// Longest language code accepted
+const MAX_LANGUAGE_LEN: usize = 8;
+
+/// Query parameters of a live transcription session
+#[derive(Debug, Default, Deserialize)]
+pub struct LiveTranscriptionParams {
+    /// Sample rate of the streamed PCM (default 16000)
+    pub sample_rate: Option<u32>,
+    /// Language of the audio, overriding `live_transcription.language`
+    pub language: Option<String>,
+}
+
+/// Messages sent to a live transcription client
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveTranscriptEvent {
+    /// The session is ready for audio
+    Ready {
+        /// Sample rate the audio is read at
+        sample_rate: u32,
+        /// Seconds of audio per final transcript
+        segment_seconds: u64,
+        /// Seconds of audio between partial transcripts (0 = none)
+        partial_interval_seconds: u64,
+    },
+    /// Interim transcript of the segment still being recorded
+    Partial {
+        /// Segment number, counting from 0
+        segment: u32,
+        /// Segment start, in seconds from the start of the stream
+        start_seconds: f64,
+        /// End of the audio transcribed so far
+        end_seconds: f64,
+        /// Transcript so far
+        text: String,
+    },
+    /// Transcript of a complete segment, replacing its partials
+    Final {
+        /// Segment number, counting from 0
+        segment: u32,
+        /// Segment start, in seconds from the start of the stream
+        start_seconds: f64,
+        /// Segment end, in seconds from the start of the stream
+        end_seconds: f64,
+        /// Transcript of the segment
+        text: String,
+    },
+    /// A segment could not be transcribed, or a message was not understood
+    Error {
+        /// Segment that failed, if any
+        segment: Option<u32>,
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// Control messages a client may send
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Finish the current segment
+    Flush,
+    /// Finish the current segment and close the session
+    End,
+}
+
+/// Live transcription WebSocket handler
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Live transcription is not enabled
+/// * `BAD_REQUEST` - Unsupported sample rate or language
+/// * `TOO_MANY_REQUESTS` - `live_transcription.max_sessions` sessions are running
+pub async fn live_transcription_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<LiveTranscriptionParams>,
+) -> Result<Response, ApiError> {
+    let Some(transcriber) = state.live_transcriber.clone() else {
+        return Err(ApiError::not_found(
+            "LIVE_TRANSCRIPTION_DISABLED",
+            "Live transcription is not enabled",
+        ));
+    };
+    let sample_rate = params.sample_rate.unwrap_or(DEFAULT_SAMPLE_RATE);
+    if !(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE).contains(&sample_rate) {
+        return Err(ApiError::bad_request(
+            "INVALID_SAMPLE_RATE",
+            format!("sample_rate must be between {MIN_SAMPLE_RATE} and {MAX_SAMPLE_RATE}"),
+        ));
+    }
+    let language = params.language.filter(|language| !language.is_empty());
+    if let Some(language) = &language
+        && (language.len() > MAX_LANGUAGE_LEN
+            || !language
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '-'))
+    {
+        return Err(ApiError::bad_request(
+            "INVALID_LANGUAGE",
+            format!("Invalid language code: {language}"),
+        ));
+    }
+    let Some(session) = transcriber.start_session() else {
+        return Err(ApiError::too_many_requests(
+            "LIVE_TRANSCRIPTION_BUSY",
+            format!(
+                "All {} live transcription sessions are in use",
+                transcriber.config().max_sessions
+            ),
+        ));
+    };
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        run_session(socket, &transcriber, sample_rate, language.as_deref()).await;
+        drop(session);
+    }))
+}
+
+/// What a client frame asks of the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    /// Audio to add to the current segment
+    Audio,
+    /// A control message
+    Control(ClientMessage),
+    /// Nothing to do
+    Ignored,
+    /// The client closed the session or disconnected
+    Closed,
+}
+
+/// A running live transcription session
+#[derive(Debug)]
+struct Session<'a> {
+    socket: WebSocket,
+    transcriber: &'a LiveTranscriber,
+    segmenter: Segmenter,
+    sample_rate: u32,
+    language: Option<&'a str>,
+    idle_timeout: Duration,
+    /// Last final transcript, sent as context with the next segment
+    previous: String,
+}
+
+/// Transcribe a client's audio until it ends the session or disconnects
+async fn run_session(
+    socket: WebSocket,
+    transcriber: &LiveTranscriber,
+    sample_rate: u32,
+    language: Option<&str>,
+) {
+    let config = transcriber.config();
+    let mut session = Session {
+        socket,
+        transcriber,
+        segmenter: Segmenter::new(
+            sample_rate,
+            config.segment_seconds,
+            config.partial_interval_seconds,
+        ),
+        sample_rate,
+        language,
+        idle_timeout: Duration::from_secs(config.idle_timeout_seconds.max(1)),
+        previous: String::new(),
+    };
+
+    let ready = LiveTranscriptEvent::Ready {
+        sample_rate,
+        segment_seconds: config.segment_seconds.max(1),
+        partial_interval_seconds: config.partial_interval_seconds,
+    };
+    if !send_event(&mut session.socket, &ready).await {
+        return;
+    }
+    info!("Live transcription session started at {sample_rate} Hz");
+
+    while let Some(message) = session.next_message().await {
+        let control = match session.handle_frame(message).await {
+            Frame::Audio => None,
+            Frame::Control(control) => Some(control),
+            Frame::Ignored => continue,
+            Frame::Closed => break,
+        };
+        if !session.transcribe_due(control.is_some()).await {
+            return;
+        }
+        if control == Some(ClientMessage::End) {
+            let _ = session.socket.send(Message::Close(None)).await;
+            break;
+        }
+    }
+    info!("Live transcription session ended");
+}
+
+impl Session<'_> {
+    /// The next frame from the client, or `None` once it disconnects or
+    /// sends nothing for `idle_timeout`
+    async fn next_message(&mut self) -> Option<Message> {
+        match tokio::time::timeout(self.idle_timeout, self.socket.recv()).await {
+            Ok(Some(Ok(message))) => Some(message),
+            Ok(None | Some(Err(_))) => None,
+            Err(_) => {
+                let idle = LiveTranscriptEvent::Error {
+                    segment: None,
+                    message: format!("No audio for {} seconds", self.idle_timeout.as_secs()),
+                };
+                let _ = send_event(&mut self.socket, &idle).await;
+                let _ = self.socket.send(Message::Close(None)).await;
+                None
+            }
+        }
+    }
+
+    /// Add audio from a client frame, or read the control message it holds
+    ///
+    /// Text that is not a control message is answered with an error event.
+    async fn handle_frame(&mut self, message: Message) -> Frame {
+        match message {
+            Message::Binary(bytes) => {
+                self.segmenter.push(&bytes);
+                Frame::Audio
+            }
+            Message::Text(text) => {
+                if let Ok(control) = serde_json::from_str::<ClientMessage>(&text) {
+                    return Frame::Control(control);
+                }
+                let unknown = LiveTranscriptEvent::Error {
+                    segment: None,
+                    message: r#"Expected {"type": "flush"} or {"type": "end"}"#.to_string(),
+                };
+                if send_event(&mut self.socket, &unknown).await {
+                    Frame::Ignored
+                } else {
+                    Frame::Closed
+                }
+            }
+            Message::Close(_) => Frame::Closed,
+            // Pings are answered by axum
+            Message::Ping(_) | Message::Pong(_) => Frame::Ignored,
+        }
+    }
+
+    /// Transcribe and send the audio that is due, all of it with `flush`
+    ///
+    /// Returns whether the client is still connected.
+    async fn transcribe_due(&mut self, flush: bool) -> bool {
+        while let Some(chunk) = self.segmenter.next_chunk(flush) {
+            let event = transcribe_chunk(
+                self.transcriber,
+                chunk,
+                self.sample_rate,
+                self.language,
+                &mut self.previous,
+            )
+            .await;
+            if !send_event(&mut self.socket, &event).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Transcribe one chunk into the event sent for it
+async fn transcribe_chunk(
+    transcriber: &LiveTranscriber,
+    chunk: Chunk,
+    sample_rate: u32,
+    language: Option<&str>,
+    previous: &mut String,
+) -> LiveTranscriptEvent {
+    let start_seconds = seconds(chunk.start_samples, sample_rate);
+    let end_seconds = seconds(
+        chunk.start_samples + chunk.samples.len() as u64,
+        sample_rate,
+    );
+    let text = match transcriber
+        .transcribe(&chunk.samples, sample_rate, language, Some(previous))
+        .await
+    {
+        Ok(text) => text,
+        Err(e) => {
+            warn!(
+                "Live transcription of segment {} failed: {e:#}",
+                chunk.segment
+            );
+            return LiveTranscriptEvent::Error {
+                segment: Some(chunk.segment),
+                message: format!("Transcription failed: {e}"),
+            };
+        }
+    };
+
+    match chunk.kind {
+        ChunkKind::Partial => LiveTranscriptEvent::Partial {
+            segment: chunk.segment,
+            start_seconds,
+            end_seconds,
+            text,
+        },
+        ChunkKind::Final => {
+            if !text.is_empty() {
+                previous.clone_from(&text);
+            }
+            LiveTranscriptEvent::Final {
+                segment: chunk.segment,
+                start_seconds,
+                end_seconds,
+                text,
+            }
+        }
+    }
+}
+
+/// Stream position of `samples` in seconds
+#[allow(clippy::cast_precision_loss)]
+fn seconds(samples: u64, sample_rate: u32) -> f64 {
+    samples as f64 / f64::from(sample_rate)
+}
+
+/// Send an event, returning whether the client is still connected
+async fn send_event(socket: &mut WebSocket, event: &LiveTranscriptEvent) -> bool {
+    match serde_json::to_string(event) {
+        Ok(json) => socket.send(Message::Text(json)).await.is_ok(),
+        Err(e) => {
+            warn!("Failed to serialize live transcription event: {e}");
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = LiveTranscriptEvent::Final {
+            segment: 2,
+            start_seconds: 20.0,
+            end_seconds: 24.5,
+            text: "Engine 5 on scene".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({"type": "final", "segment": 2, "start_seconds": 20.0,
+                   "end_seconds": 24.5, "text": "Engine 5 on scene"})
+        );
+        let error = LiveTranscriptEvent::Error {
+            segment: None,
+            message: "No audio for 30 seconds".to_string(),
+        };
+        assert_eq!(serde_json::to_value(&error).unwrap()["type"], "error");
+    }
+
+    #[test]
+    fn test_client_messages() {
+        assert_eq!(
+            serde_json::from_str::<ClientMessage>(r#"{"type": "flush"}"#).unwrap(),
+            ClientMessage::Flush
+        );
+        assert_eq!(
+            serde_json::from_str::<ClientMessage>(r#"{"type": "end"}"#).unwrap(),
+            ClientMessage::End
+        );
+        assert!(serde_json::from_str::<ClientMessage>(r#"{"type": "pause"}"#).is_err());
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert!((seconds(24_000, 16_000) - 1.5).abs() < f64::EPSILON);
+    }
+}
@@ -4,7 +4,7 @@ use axum::{extract::State, http::StatusCode, response::IntoResponse};
 use std::sync::Arc;
 use tracing::{error, warn};
 
-use crate::state::AppState;
+use crate::{state::AppState, worker_metrics::WorkerPoolSnapshot};
 
 /// Serve Prometheus metrics
 ///
@@ -24,7 +24,16 @@ pub async fn serve_metrics(
     };
 
     // Format as Prometheus exposition format
-    let output = format_prometheus_metrics(&metrics);
+    let mut output = format_prometheus_metrics(&metrics);
+
+    // Per-worker synthetic transcription probe results
+    match sdrtrunk_storage::ProbeQueries::list(&state.pool).await {
+        Ok(probes) => output.push_str(&format_probe_metrics(&probes, chrono::Utc::now())),
+        Err(e) => warn!("Failed to get transcription probe metrics: {}", e),
+    }
+
+    // Worker pool activity reported since the API started
+    output.push_str(&format_worker_pool_metrics(&state.worker_pool.snapshot()));
 
     Ok((
         [(http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
@@ -41,9 +50,15 @@ struct Metrics {
     transcriptions_pending: i64,
     transcriptions_processing: i64,
     transcriptions_completed: i64,
+    transcriptions_needs_review: i64,
     transcriptions_failed: i64,
+    transcriptions_skipped: i64,
+    transcriptions_deferred: i64,
     upload_success_count: i64,
     upload_error_count: i64,
+    storage_bytes_total: i64,
+    storage_bytes_added_24h: i64,
+    storage_capacity_bytes: u64,
 }
 
 /// Gather metrics from database
@@ -56,14 +71,32 @@ async fn gather_metrics(state: &AppState) -> Result<Metrics, String> {
     let pool = &state.pool;
 
     // Execute all metrics queries in parallel
-    let (total_calls, recent_calls, systems, pending, processing, completed, failed) = tokio::join!(
-        sdrtrunk_storage::count_radio_calls(pool),
-        sdrtrunk_storage::count_recent_calls(pool, 24),
-        sdrtrunk_storage::count_systems(pool),
+    let (
+        total_calls,
+        recent_calls,
+        systems,
+        pending,
+        processing,
+        completed,
+        needs_review,
+        failed,
+        skipped,
+        deferred,
+        storage_bytes,
+        storage_growth,
+    ) = tokio::join!(
+        sdrtrunk_storage::count_radio_calls(pool, None),
+        sdrtrunk_storage::count_recent_calls(pool, 24, None),
+        sdrtrunk_storage::count_systems(pool, None),
         count_calls_by_status(pool, "pending"),
         count_calls_by_status(pool, "processing"),
         count_calls_by_status(pool, "completed"),
+        count_calls_by_status(pool, "needs_review"),
         count_calls_by_status(pool, "failed"),
+        count_calls_by_status(pool, "skipped"),
+        count_calls_by_status(pool, "deferred"),
+        sdrtrunk_storage::sum_audio_bytes(pool, None),
+        sdrtrunk_storage::get_daily_storage_growth(pool, 1, None),
     );
 
     // Log warnings for failed queries but continue with available data
@@ -85,7 +118,23 @@ async fn gather_metrics(state: &AppState) -> Result<Metrics, String> {
     let transcriptions_pending = pending.unwrap_or(0);
     let transcriptions_processing = processing.unwrap_or(0);
     let transcriptions_completed = completed.unwrap_or(0);
+    let transcriptions_needs_review = needs_review.unwrap_or(0);
     let transcriptions_failed = failed.unwrap_or(0);
+    let transcriptions_skipped = skipped.unwrap_or(0);
+    let transcriptions_deferred = deferred.unwrap_or(0);
+
+    let storage_bytes_total = storage_bytes.unwrap_or_else(|e| {
+        warn!("Failed to get storage_bytes_total metric: {}", e);
+        0
+    });
+
+    let storage_bytes_added_24h = storage_growth.map_or_else(
+        |e| {
+            warn!("Failed to get storage_bytes_added_24h metric: {}", e);
+            0
+        },
+        |growth| growth.iter().map(|g| g.bytes_added).sum(),
+    );
 
     // TODO: Add upload log metrics from upload_log table when implemented
     let upload_success_count = 0;
@@ -98,9 +147,15 @@ async fn gather_metrics(state: &AppState) -> Result<Metrics, String> {
         transcriptions_pending,
         transcriptions_processing,
         transcriptions_completed,
+        transcriptions_needs_review,
         transcriptions_failed,
+        transcriptions_skipped,
+        transcriptions_deferred,
         upload_success_count,
         upload_error_count,
+        storage_bytes_total,
+        storage_bytes_added_24h,
+        storage_capacity_bytes: state.config.storage.capacity_bytes.unwrap_or(0),
     })
 }
 
@@ -143,13 +198,28 @@ sdrtrunk_systems_total {}
 sdrtrunk_transcriptions_total{{status="pending"}} {}
 sdrtrunk_transcriptions_total{{status="processing"}} {}
 sdrtrunk_transcriptions_total{{status="completed"}} {}
+sdrtrunk_transcriptions_total{{status="needs_review"}} {}
 sdrtrunk_transcriptions_total{{status="failed"}} {}
+sdrtrunk_transcriptions_total{{status="skipped"}} {}
+sdrtrunk_transcriptions_total{{status="deferred"}} {}
 
 # HELP sdrtrunk_uploads_total Total uploads by result
 # TYPE sdrtrunk_uploads_total counter
 sdrtrunk_uploads_total{{result="success"}} {}
 sdrtrunk_uploads_total{{result="error"}} {}
 
+# HELP sdrtrunk_storage_bytes Audio bytes currently stored
+# TYPE sdrtrunk_storage_bytes gauge
+sdrtrunk_storage_bytes {}
+
+# HELP sdrtrunk_storage_bytes_added_24h Audio bytes added in the last 24 hours
+# TYPE sdrtrunk_storage_bytes_added_24h gauge
+sdrtrunk_storage_bytes_added_24h {}
+
+# HELP sdrtrunk_storage_capacity_bytes Configured storage volume capacity (0 if unset)
+# TYPE sdrtrunk_storage_capacity_bytes gauge
+sdrtrunk_storage_capacity_bytes {}
+
 # HELP sdrtrunk_info Application information
 # TYPE sdrtrunk_info gauge
 sdrtrunk_info{{version="0.1.0"}} 1
@@ -160,12 +230,118 @@ sdrtrunk_info{{version="0.1.0"}} 1
         metrics.transcriptions_pending,
         metrics.transcriptions_processing,
         metrics.transcriptions_completed,
+        metrics.transcriptions_needs_review,
         metrics.transcriptions_failed,
+        metrics.transcriptions_skipped,
+        metrics.transcriptions_deferred,
         metrics.upload_success_count,
         metrics.upload_error_count,
+        metrics.storage_bytes_total,
+        metrics.storage_bytes_added_24h,
+        metrics.storage_capacity_bytes,
     )
 }
 
+/// Format per-worker probe results in Prometheus exposition format
+fn format_probe_metrics(
+    probes: &[sdrtrunk_storage::TranscriptionProbe],
+    now: chrono::DateTime<chrono::Utc>,
+) -> String {
+    use std::fmt::Write;
+
+    if probes.is_empty() {
+        return String::new();
+    }
+
+    let mut success = String::from(
+        "\n# HELP sdrtrunk_transcription_probe_success Whether the worker's last synthetic probe succeeded\n\
+         # TYPE sdrtrunk_transcription_probe_success gauge\n",
+    );
+    let mut latency = String::from(
+        "\n# HELP sdrtrunk_transcription_probe_latency_ms Latency of the worker's last synthetic probe\n\
+         # TYPE sdrtrunk_transcription_probe_latency_ms gauge\n",
+    );
+    let mut age = String::from(
+        "\n# HELP sdrtrunk_transcription_probe_age_seconds Seconds since the worker's last synthetic probe\n\
+         # TYPE sdrtrunk_transcription_probe_age_seconds gauge\n",
+    );
+
+    for probe in probes {
+        let worker = probe.worker_id.replace('\\', "\\\\").replace('"', "\\\"");
+        let _ = writeln!(
+            success,
+            "sdrtrunk_transcription_probe_success{{worker_id=\"{worker}\"}} {}",
+            u8::from(probe.success)
+        );
+        let _ = writeln!(
+            latency,
+            "sdrtrunk_transcription_probe_latency_ms{{worker_id=\"{worker}\"}} {}",
+            probe.latency_ms
+        );
+        let _ = writeln!(
+            age,
+            "sdrtrunk_transcription_probe_age_seconds{{worker_id=\"{worker}\"}} {}",
+            (now - probe.probed_at).num_seconds().max(0)
+        );
+    }
+
+    success + &latency + &age
+}
+
+/// Format transcription worker pool counters in Prometheus exposition format
+fn format_worker_pool_metrics(pool: &WorkerPoolSnapshot) -> String {
+    use std::fmt::Write;
+
+    #[allow(clippy::cast_precision_loss)]
+    let duration_seconds = pool.processing_ms_total as f64 / 1000.0;
+    let mut output = format!(
+        r"
+# HELP sdrtrunk_worker_jobs_processed_total Transcription jobs completed by workers since API start
+# TYPE sdrtrunk_worker_jobs_processed_total counter
+sdrtrunk_worker_jobs_processed_total {}
+
+# HELP sdrtrunk_worker_job_duration_seconds Processing time of completed transcription jobs
+# TYPE sdrtrunk_worker_job_duration_seconds summary
+sdrtrunk_worker_job_duration_seconds_sum {duration_seconds}
+sdrtrunk_worker_job_duration_seconds_count {}
+
+# HELP sdrtrunk_worker_jobs_in_flight Transcription jobs being processed now
+# TYPE sdrtrunk_worker_jobs_in_flight gauge
+sdrtrunk_worker_jobs_in_flight {}
+
+# HELP sdrtrunk_worker_job_retries_total Failed transcription attempts that were re-queued
+# TYPE sdrtrunk_worker_job_retries_total counter
+sdrtrunk_worker_job_retries_total {}
+
+# HELP sdrtrunk_worker_job_failures_total Failed transcription attempts by error type
+# TYPE sdrtrunk_worker_job_failures_total counter
+",
+        pool.jobs_processed, pool.jobs_processed, pool.concurrency, pool.retries,
+    );
+    for (error_type, count) in &pool.failures {
+        let _ = writeln!(
+            output,
+            "sdrtrunk_worker_job_failures_total{{error_type=\"{error_type}\"}} {count}"
+        );
+    }
+
+    if !pool.workers.is_empty() {
+        output.push_str(
+            "\n# HELP sdrtrunk_worker_busy_jobs Transcription jobs being processed now by each worker\n\
+             # TYPE sdrtrunk_worker_busy_jobs gauge\n",
+        );
+        for (worker, jobs) in &pool.workers {
+            let worker = worker.replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = writeln!(
+                output,
+                "sdrtrunk_worker_busy_jobs{{worker_id=\"{worker}\"}} {jobs}"
+            );
+        }
+    }
+
+    output
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -217,9 +393,15 @@ mod tests {
             transcriptions_pending: 10,
             transcriptions_processing: 3,
             transcriptions_completed: 900,
+            transcriptions_needs_review: 12,
             transcriptions_failed: 87,
+            transcriptions_skipped: 40,
+            transcriptions_deferred: 6,
             upload_success_count: 950,
             upload_error_count: 50,
+            storage_bytes_total: 5_000_000,
+            storage_bytes_added_24h: 250_000,
+            storage_capacity_bytes: 10_000_000,
         };
 
         let output = format_prometheus_metrics(&metrics);
@@ -229,6 +411,12 @@ mod tests {
         assert!(output.contains("sdrtrunk_systems_total 5"));
         assert!(output.contains(r#"sdrtrunk_transcriptions_total{status="pending"} 10"#));
         assert!(output.contains(r#"sdrtrunk_transcriptions_total{status="completed"} 900"#));
+        assert!(output.contains(r#"sdrtrunk_transcriptions_total{status="needs_review"} 12"#));
+        assert!(output.contains(r#"sdrtrunk_transcriptions_total{status="skipped"} 40"#));
+        assert!(output.contains(r#"sdrtrunk_transcriptions_total{status="deferred"} 6"#));
+        assert!(output.contains("sdrtrunk_storage_bytes 5000000"));
+        assert!(output.contains("sdrtrunk_storage_bytes_added_24h 250000"));
+        assert!(output.contains("sdrtrunk_storage_capacity_bytes 10000000"));
         assert!(output.contains("# HELP"));
         assert!(output.contains("# TYPE"));
     }
@@ -259,4 +447,54 @@ mod tests {
         assert_eq!(metrics.total_systems, 0);
         assert_eq!(metrics.transcriptions_pending, 0);
     }
+
+    #[test]
+    fn test_format_probe_metrics() {
+        let now = chrono::Utc::now();
+        let probes = vec![sdrtrunk_storage::TranscriptionProbe {
+            worker_id: "worker-1".to_string(),
+            probed_at: now - chrono::Duration::seconds(42),
+            success: true,
+            latency_ms: 950,
+            transcript: Some(String::new()),
+            error: None,
+            consecutive_failures: 0,
+        }];
+
+        let output = format_probe_metrics(&probes, now);
+        assert!(output.contains(r#"sdrtrunk_transcription_probe_success{worker_id="worker-1"} 1"#));
+        assert!(
+            output.contains(r#"sdrtrunk_transcription_probe_latency_ms{worker_id="worker-1"} 950"#)
+        );
+        assert!(
+            output.contains(r#"sdrtrunk_transcription_probe_age_seconds{worker_id="worker-1"} 42"#)
+        );
+        assert!(output.contains("# TYPE sdrtrunk_transcription_probe_success gauge"));
+
+        assert!(format_probe_metrics(&[], now).is_empty());
+    }
+
+    #[test]
+    fn test_format_worker_pool_metrics() {
+        let pool = WorkerPoolSnapshot {
+            since: chrono::Utc::now(),
+            jobs_processed: 4,
+            processing_ms_total: 10_500,
+            average_duration_ms: Some(2625.0),
+            failures: [("timeout".to_string(), 2), ("audio".to_string(), 1)].into(),
+            retries: 2,
+            concurrency: 3,
+            peak_concurrency: 5,
+            workers: [("gpu-0".to_string(), 2), ("gpu-1".to_string(), 1)].into(),
+        };
+
+        let output = format_worker_pool_metrics(&pool);
+        assert!(output.contains("sdrtrunk_worker_jobs_processed_total 4"));
+        assert!(output.contains("sdrtrunk_worker_job_duration_seconds_sum 10.5"));
+        assert!(output.contains("sdrtrunk_worker_job_duration_seconds_count 4"));
+        assert!(output.contains("sdrtrunk_worker_jobs_in_flight 3"));
+        assert!(output.contains(r#"sdrtrunk_worker_job_failures_total{error_type="timeout"} 2"#));
+        assert!(output.contains(r#"sdrtrunk_worker_busy_jobs{worker_id="gpu-0"} 2"#));
+        assert!(output.contains("# TYPE sdrtrunk_worker_job_failures_total counter"));
+    }
 }
@@ -1,11 +1,26 @@
 //! Request handlers
 
 pub mod admin;
+pub mod alerts;
 pub mod audio_utils;
+pub mod auth;
 pub mod calls;
+pub mod compare;
+pub mod conversations;
+pub mod duplicates;
+pub mod feedback;
+pub mod geo;
 pub mod health;
+pub mod keys;
+pub mod live_transcription;
 pub mod metrics;
+pub mod radios;
+pub mod resumable;
+pub mod searches;
 pub mod stats;
+pub mod tags;
+pub mod talkgroups;
 pub mod transcription;
 pub mod upload;
+pub mod users;
 pub mod websocket;
@@ -0,0 +1,300 @@
+//! Radio (unit) ID handlers
+//!
+//! Lists the radio IDs heard on a system with when they were first and last
+//! heard, shows one radio's talkgroups and call history, and lets analysts
+//! label radios (e.g. "Engine 5 portable").
+
+use crate::{error::ApiError, handlers::calls::CallSummary, state::AppState, tenant::TenantScope};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use sdrtrunk_storage::{CallCursor, Radio, RadioQueries, RadioTalkgroup};
+use sdrtrunk_types::{RadioId, SystemId};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Default number of radios or calls per page
+const DEFAULT_LIMIT: i64 = 50;
+/// Largest page of radios or calls
+const MAX_LIMIT: i64 = 1000;
+/// Longest radio label, matching the `radios.label` column
+const MAX_LABEL_CHARS: usize = 255;
+
+/// Query parameters for listing radios
+#[derive(Debug, Default, Deserialize)]
+pub struct RadioListQuery {
+    /// Radios to return (default 50, max 1000)
+    pub limit: Option<i64>,
+}
+
+/// Query parameters for a radio's call history
+#[derive(Debug, Default, Deserialize)]
+pub struct RadioCallsQuery {
+    /// Calls per page (default 50, max 1000)
+    pub limit: Option<i64>,
+    /// Resume after this call: the previous page's `next_cursor`
+    pub after: Option<String>,
+}
+
+/// Radios heard on a system
+#[derive(Debug, Serialize)]
+pub struct RadioListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Radios, most recently heard first
+    pub radios: Vec<Radio>,
+}
+
+/// A radio with its talkgroups and a page of its calls
+#[derive(Debug, Serialize)]
+pub struct RadioResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The radio's activity and label
+    pub radio: Radio,
+    /// Talkgroups the radio has called on, most calls first
+    pub talkgroups: Vec<RadioTalkgroup>,
+    /// The radio's calls across all talkgroups, newest first
+    pub calls: Vec<CallSummary>,
+    /// Whether more calls follow
+    pub has_next: bool,
+    /// Pass as `after` to get the next page of calls
+    pub next_cursor: Option<String>,
+}
+
+/// Request to label a radio
+#[derive(Debug, Deserialize)]
+pub struct LabelRadioRequest {
+    /// New label; `null` or blank clears it
+    pub label: Option<String>,
+}
+
+/// A radio after labelling
+#[derive(Debug, Serialize)]
+pub struct LabelRadioResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The labelled radio
+    pub radio: Radio,
+}
+
+/// List the radios heard on a system
+///
+/// # Errors
+///
+/// Returns error if the API key may not access the system, the limit is out
+/// of range, or the database query fails
+pub async fn list_radios(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(system_id): Path<SystemId>,
+    Query(query): Query<RadioListQuery>,
+) -> Result<Json<RadioListResponse>, ApiError> {
+    check_scope(&scope, &system_id)?;
+    let limit = page_limit(query.limit)?;
+    match RadioQueries::list(&state.read_pool, &system_id, limit).await {
+        Ok(radios) => Ok(Json(RadioListResponse {
+            success: true,
+            radios,
+        })),
+        Err(e) => {
+            error!("Failed to list radios for {system_id}: {e}");
+            Err(ApiError::database(format!("Failed to list radios: {e}")))
+        }
+    }
+}
+
+/// Get a radio with the talkgroups it has used and a page of its calls
+///
+/// # Errors
+///
+/// Returns error if the API key may not access the system, the parameters
+/// are invalid, the radio has never been heard, or a database query fails
+pub async fn get_radio(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path((system_id, radio_id)): Path<(SystemId, i32)>,
+    Query(query): Query<RadioCallsQuery>,
+) -> Result<Json<RadioResponse>, ApiError> {
+    check_scope(&scope, &system_id)?;
+    let radio_id = parse_radio_id(radio_id)?;
+    let limit = page_limit(query.limit)?;
+    let after = match query
+        .after
+        .as_deref()
+        .map(str::parse::<CallCursor>)
+        .transpose()
+    {
+        Ok(after) => after,
+        Err(e) => {
+            warn!("Invalid call cursor: {e}");
+            return Err(ApiError::bad_request(
+                "INVALID_PARAMETERS",
+                "Invalid cursor; pass a next_cursor from a previous page",
+            ));
+        }
+    };
+
+    // Fetch one extra call to see whether another page follows
+    let pool = &state.read_pool;
+    let result = async {
+        let Some(radio) = RadioQueries::get(pool, &system_id, radio_id).await? else {
+            return Ok(None);
+        };
+        let talkgroups = RadioQueries::talkgroups(pool, &system_id, radio_id).await?;
+        let calls = RadioQueries::calls(pool, &system_id, radio_id, limit + 1, after).await?;
+        Ok::<_, sdrtrunk_storage::StorageError>(Some((radio, talkgroups, calls)))
+    }
+    .await;
+    match result {
+        Ok(Some((radio, talkgroups, mut calls))) => {
+            let page = usize::try_from(limit).unwrap_or_default();
+            let has_next = calls.len() > page;
+            calls.truncate(page);
+            let next_cursor = calls
+                .last()
+                .filter(|_| has_next)
+                .map(|call| CallCursor::after(call).to_string());
+            Ok(Json(RadioResponse {
+                success: true,
+                radio,
+                talkgroups,
+                calls: calls.into_iter().map(CallSummary::from).collect(),
+                has_next,
+                next_cursor,
+            }))
+        }
+        Ok(None) => Err(not_found(&system_id, radio_id)),
+        Err(e) => {
+            error!("Failed to get radio {radio_id} on {system_id}: {e}");
+            Err(ApiError::database(format!("Failed to get radio: {e}")))
+        }
+    }
+}
+
+/// Label a radio, or clear its label
+///
+/// # Errors
+///
+/// Returns error if the API key may not access the system, the label is too
+/// long, the radio has never been heard, or the database query fails
+pub async fn label_radio(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path((system_id, radio_id)): Path<(SystemId, i32)>,
+    Json(request): Json<LabelRadioRequest>,
+) -> Result<Json<LabelRadioResponse>, ApiError> {
+    check_scope(&scope, &system_id)?;
+    let radio_id = parse_radio_id(radio_id)?;
+    let label = request
+        .label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty());
+    if label.is_some_and(|label| label.chars().count() > MAX_LABEL_CHARS) {
+        return Err(ApiError::bad_request(
+            "INVALID_LABEL",
+            format!("Label must be at most {MAX_LABEL_CHARS} characters"),
+        ));
+    }
+
+    match RadioQueries::set_label(&state.pool, &system_id, radio_id, label).await {
+        Ok(Some(radio)) => {
+            info!(
+                "Radio {radio_id} on {system_id} labelled {:?}",
+                radio.label.as_deref().unwrap_or_default()
+            );
+            Ok(Json(LabelRadioResponse {
+                success: true,
+                radio,
+            }))
+        }
+        Ok(None) => Err(not_found(&system_id, radio_id)),
+        Err(e) => {
+            error!("Failed to label radio {radio_id} on {system_id}: {e}");
+            Err(ApiError::database(format!("Failed to label radio: {e}")))
+        }
+    }
+}
+
+/// Reject systems outside the API key's scope
+///
+/// # Errors
+///
+/// Returns `SYSTEM_NOT_ALLOWED` if the API key may not access the system
+fn check_scope(scope: &TenantScope, system_id: &SystemId) -> Result<(), ApiError> {
+    if scope.allows(system_id) {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "SYSTEM_NOT_ALLOWED",
+            format!("API key may not access system {system_id}"),
+        ))
+    }
+}
+
+/// Validate a radio ID from the path
+///
+/// # Errors
+///
+/// Returns `INVALID_PARAMETERS` if the ID is not positive
+fn parse_radio_id(radio_id: i32) -> Result<RadioId, ApiError> {
+    RadioId::new(radio_id)
+        .map_err(|e| ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid radio: {e}")))
+}
+
+/// Validate a page size, defaulting when absent
+///
+/// # Errors
+///
+/// Returns `INVALID_PARAMETERS` if the limit is out of range
+fn page_limit(limit: Option<i64>) -> Result<i64, ApiError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if (1..=MAX_LIMIT).contains(&limit) {
+        Ok(limit)
+    } else {
+        Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            format!("limit must be 1 to {MAX_LIMIT}"),
+        ))
+    }
+}
+
+/// Error for a radio that has never been heard on a system
+fn not_found(system_id: &SystemId, radio_id: RadioId) -> ApiError {
+    ApiError::not_found(
+        "RADIO_NOT_FOUND",
+        format!("Radio {radio_id} has not been heard on system {system_id}"),
+    )
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(page_limit(None).unwrap(), DEFAULT_LIMIT);
+        assert_eq!(page_limit(Some(10)).unwrap(), 10);
+        assert_eq!(page_limit(Some(MAX_LIMIT)).unwrap(), MAX_LIMIT);
+        assert!(page_limit(Some(0)).is_err());
+        assert!(page_limit(Some(MAX_LIMIT + 1)).is_err());
+    }
+
+    #[test]
+    fn test_parse_radio_id() {
+        assert_eq!(parse_radio_id(1234).unwrap().as_i32(), 1234);
+        assert!(parse_radio_id(0).is_err());
+        assert!(parse_radio_id(-5).is_err());
+    }
+}
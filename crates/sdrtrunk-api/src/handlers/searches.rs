@@ -0,0 +1,485 @@
+//! Saved search handlers
+//!
+//! Signed-in users and API keys save named call list filters (system,
+//! talkgroup, transcript keyword, transcription language, date range) and load them again from the
+//! web calls page. Each caller only sees its own searches.
+
+use crate::{
+    error::{ApiError, ErrorResponse},
+    state::AppState,
+};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{
+    NewSavedSearch, SavedSearch, SearchQueries, StorageError, User, models::ApiKeyDb,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Longest accepted search name, in characters
+const MAX_NAME_CHARS: usize = 100;
+
+/// Longest accepted language code, in characters
+const MAX_LANGUAGE_CHARS: usize = 10;
+
+/// A named set of call list filters
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SavedSearchRequest {
+    /// Name, up to 100 characters and unique among the caller's searches
+    pub name: String,
+    /// System ID filter
+    pub system_id: Option<String>,
+    /// Talkgroup ID filter
+    pub talkgroup_id: Option<i32>,
+    /// Transcript keyword filter, matched case-insensitively
+    pub keyword: Option<String>,
+    /// Transcription language filter, e.g. `en`
+    pub language: Option<String>,
+    /// Earliest call time
+    pub from_date: Option<DateTime<Utc>>,
+    /// Latest call time
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl SavedSearchRequest {
+    /// Validate the request into the search to store
+    ///
+    /// Blank filters are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid field
+    pub fn into_search(self) -> Result<NewSavedSearch, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("name is required".to_string());
+        }
+        if name.chars().count() > MAX_NAME_CHARS {
+            return Err(format!("name must be at most {MAX_NAME_CHARS} characters"));
+        }
+        if let (Some(from), Some(to)) = (self.from_date, self.to_date)
+            && from > to
+        {
+            return Err("from_date must not be after to_date".to_string());
+        }
+        let non_blank = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let language = non_blank(self.language);
+        if language
+            .as_ref()
+            .is_some_and(|l| l.chars().count() > MAX_LANGUAGE_CHARS)
+        {
+            return Err(format!(
+                "language must be at most {MAX_LANGUAGE_CHARS} characters"
+            ));
+        }
+
+        Ok(NewSavedSearch {
+            name,
+            system_id: non_blank(self.system_id),
+            talkgroup_id: self.talkgroup_id,
+            keyword: non_blank(self.keyword),
+            language,
+            from_date: self.from_date,
+            to_date: self.to_date,
+        })
+    }
+}
+
+/// A saved search
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SavedSearchInfo {
+    /// Search ID
+    pub id: Uuid,
+    /// Name
+    pub name: String,
+    /// System ID filter
+    pub system_id: Option<String>,
+    /// Talkgroup ID filter
+    pub talkgroup_id: Option<i32>,
+    /// Transcript keyword filter
+    pub keyword: Option<String>,
+    /// Transcription language filter
+    pub language: Option<String>,
+    /// Earliest call time
+    pub from_date: Option<DateTime<Utc>>,
+    /// Latest call time
+    pub to_date: Option<DateTime<Utc>>,
+    /// When the search was saved
+    pub created_at: DateTime<Utc>,
+    /// When the search was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<SavedSearch> for SavedSearchInfo {
+    fn from(search: SavedSearch) -> Self {
+        Self {
+            id: search.id,
+            name: search.name,
+            system_id: search.system_id,
+            talkgroup_id: search.talkgroup_id,
+            keyword: search.keyword,
+            language: search.language,
+            from_date: search.from_date,
+            to_date: search.to_date,
+            created_at: search.created_at,
+            updated_at: search.updated_at,
+        }
+    }
+}
+
+/// A single saved search
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SavedSearchResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The search
+    pub search: SavedSearchInfo,
+}
+
+/// The caller's saved searches
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SavedSearchesResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Searches in name order
+    pub searches: Vec<SavedSearchInfo>,
+}
+
+/// Owner of the caller's saved searches: the signed-in user, else the API key
+///
+/// # Errors
+///
+/// Returns `UNAUTHORIZED` if there is neither
+fn search_owner(
+    user: Option<Extension<User>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+) -> Result<String, ApiError> {
+    match (user, api_key) {
+        (Some(Extension(user)), _) => Ok(format!("user:{}", user.id)),
+        (None, Some(Extension(key))) => Ok(format!("api_key:{}", key.id)),
+        (None, None) => Err(ApiError::unauthorized(
+            "AUTHENTICATION_REQUIRED",
+            "Sign in or use an API key to save searches",
+        )),
+    }
+}
+
+/// Map a storage error from saving a search to the API error
+fn save_error(name: &str, e: &StorageError) -> ApiError {
+    if matches!(e, StorageError::ConstraintViolation { .. }) {
+        return ApiError::conflict(
+            "SEARCH_NAME_TAKEN",
+            format!("A saved search named '{name}' already exists"),
+        );
+    }
+    error!("Failed to save search '{name}': {e}");
+    ApiError::database("Failed to save search")
+}
+
+/// Not found error for a search the caller does not have
+fn search_not_found(id: Uuid) -> ApiError {
+    ApiError::not_found("SEARCH_NOT_FOUND", format!("Saved search {id} not found"))
+}
+
+/// List the caller's saved searches
+///
+/// # Errors
+///
+/// * `UNAUTHORIZED` - No signed-in user or API key
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+#[utoipa::path(
+    get,
+    path = "/api/searches",
+    tag = "Searches",
+    summary = "List saved searches",
+    description = "The caller's saved call searches, in name order.",
+    responses(
+        (status = 200, description = "Saved searches", body = SavedSearchesResponse),
+        (status = 401, description = "Not signed in", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn list_searches(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<User>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+) -> Result<Json<SavedSearchesResponse>, ApiError> {
+    let owner = search_owner(user, api_key)?;
+    match SearchQueries::list_for_owner(&state.pool, &owner).await {
+        Ok(searches) => Ok(Json(SavedSearchesResponse {
+            success: true,
+            searches: searches.into_iter().map(SavedSearchInfo::from).collect(),
+        })),
+        Err(e) => {
+            error!("Failed to list saved searches of {owner}: {e}");
+            Err(ApiError::database("Failed to list saved searches"))
+        }
+    }
+}
+
+/// Save a named set of call list filters
+///
+/// # Errors
+///
+/// * `BAD_REQUEST` - Invalid name or date range
+/// * `UNAUTHORIZED` - No signed-in user or API key
+/// * `CONFLICT` - The caller already has a search with the name
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// POST /api/searches
+/// {"name": "County fires", "system_id": "county", "keyword": "structure fire"}
+/// ```
+#[utoipa::path(
+    post,
+    path = "/api/searches",
+    tag = "Searches",
+    summary = "Save a search",
+    description = "Save a named set of call list filters for the caller. Names are unique per caller.",
+    request_body = SavedSearchRequest,
+    responses(
+        (status = 201, description = "Search saved", body = SavedSearchResponse),
+        (status = 400, description = "Invalid search", body = ErrorResponse),
+        (status = 401, description = "Not signed in", body = ErrorResponse),
+        (status = 409, description = "Name already used", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn create_search(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<User>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    Json(request): Json<SavedSearchRequest>,
+) -> Result<(StatusCode, Json<SavedSearchResponse>), ApiError> {
+    let owner = search_owner(user, api_key)?;
+    let search = request
+        .into_search()
+        .map_err(|e| ApiError::bad_request("INVALID_SEARCH", e))?;
+
+    match SearchQueries::create(&state.pool, &owner, &search).await {
+        Ok(saved) => {
+            info!("Saved search '{}' for {owner}", saved.name);
+            Ok((
+                StatusCode::CREATED,
+                Json(SavedSearchResponse {
+                    success: true,
+                    search: saved.into(),
+                }),
+            ))
+        }
+        Err(e) => Err(save_error(&search.name, &e)),
+    }
+}
+
+/// Get one of the caller's saved searches
+///
+/// # Errors
+///
+/// * `UNAUTHORIZED` - No signed-in user or API key
+/// * `NOT_FOUND` - The caller has no search with the ID
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+#[utoipa::path(
+    get,
+    path = "/api/searches/{id}",
+    tag = "Searches",
+    summary = "Get a saved search",
+    params(("id" = Uuid, Path, description = "Saved search UUID")),
+    responses(
+        (status = 200, description = "The saved search", body = SavedSearchResponse),
+        (status = 401, description = "Not signed in", body = ErrorResponse),
+        (status = 404, description = "Search not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn get_search(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<User>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SavedSearchResponse>, ApiError> {
+    let owner = search_owner(user, api_key)?;
+    match SearchQueries::get(&state.pool, id, &owner).await {
+        Ok(Some(search)) => Ok(Json(SavedSearchResponse {
+            success: true,
+            search: search.into(),
+        })),
+        Ok(None) => Err(search_not_found(id)),
+        Err(e) => {
+            error!("Failed to get saved search {id}: {e}");
+            Err(ApiError::database("Failed to get saved search"))
+        }
+    }
+}
+
+/// Replace the name and filters of one of the caller's saved searches
+///
+/// # Errors
+///
+/// * `BAD_REQUEST` - Invalid name or date range
+/// * `UNAUTHORIZED` - No signed-in user or API key
+/// * `NOT_FOUND` - The caller has no search with the ID
+/// * `CONFLICT` - The caller already has another search with the name
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+#[utoipa::path(
+    put,
+    path = "/api/searches/{id}",
+    tag = "Searches",
+    summary = "Update a saved search",
+    description = "Replace the name and all filters of a saved search.",
+    params(("id" = Uuid, Path, description = "Saved search UUID")),
+    request_body = SavedSearchRequest,
+    responses(
+        (status = 200, description = "Search updated", body = SavedSearchResponse),
+        (status = 400, description = "Invalid search", body = ErrorResponse),
+        (status = 401, description = "Not signed in", body = ErrorResponse),
+        (status = 404, description = "Search not found", body = ErrorResponse),
+        (status = 409, description = "Name already used", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn update_search(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<User>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SavedSearchRequest>,
+) -> Result<Json<SavedSearchResponse>, ApiError> {
+    let owner = search_owner(user, api_key)?;
+    let search = request
+        .into_search()
+        .map_err(|e| ApiError::bad_request("INVALID_SEARCH", e))?;
+
+    match SearchQueries::update(&state.pool, id, &owner, &search).await {
+        Ok(Some(saved)) => {
+            info!("Updated saved search {id} for {owner}");
+            Ok(Json(SavedSearchResponse {
+                success: true,
+                search: saved.into(),
+            }))
+        }
+        Ok(None) => Err(search_not_found(id)),
+        Err(e) => Err(save_error(&search.name, &e)),
+    }
+}
+
+/// Delete one of the caller's saved searches
+///
+/// # Errors
+///
+/// * `UNAUTHORIZED` - No signed-in user or API key
+/// * `NOT_FOUND` - The caller has no search with the ID
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+#[utoipa::path(
+    delete,
+    path = "/api/searches/{id}",
+    tag = "Searches",
+    summary = "Delete a saved search",
+    params(("id" = Uuid, Path, description = "Saved search UUID")),
+    responses(
+        (status = 204, description = "Search deleted"),
+        (status = 401, description = "Not signed in", body = ErrorResponse),
+        (status = 404, description = "Search not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn delete_search(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<User>>,
+    api_key: Option<Extension<ApiKeyDb>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let owner = search_owner(user, api_key)?;
+    match SearchQueries::delete(&state.pool, id, &owner).await {
+        Ok(true) => {
+            info!("Deleted saved search {id} for {owner}");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(search_not_found(id)),
+        Err(e) => {
+            error!("Failed to delete saved search {id}: {e}");
+            Err(ApiError::database("Failed to delete saved search"))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> SavedSearchRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_into_search() {
+        let search = request(serde_json::json!({
+            "name": " County fires ",
+            "system_id": "county",
+            "talkgroup_id": 1234,
+            "keyword": "  ",
+            "language": "es",
+            "from_date": "2025-01-01T00:00:00Z",
+            "to_date": "2025-01-02T00:00:00Z",
+        }))
+        .into_search()
+        .unwrap();
+        assert_eq!(search.name, "County fires");
+        assert_eq!(search.system_id.as_deref(), Some("county"));
+        assert_eq!(search.talkgroup_id, Some(1234));
+        assert!(search.keyword.is_none());
+        assert_eq!(search.language.as_deref(), Some("es"));
+        assert!(search.from_date.is_some());
+    }
+
+    #[test]
+    fn test_into_search_validation() {
+        for json in [
+            serde_json::json!({"name": ""}),
+            serde_json::json!({"name": "   "}),
+            serde_json::json!({"name": "x".repeat(MAX_NAME_CHARS + 1)}),
+            serde_json::json!({"name": "long", "language": "x".repeat(MAX_LANGUAGE_CHARS + 1)}),
+            serde_json::json!({
+                "name": "backwards",
+                "from_date": "2025-01-02T00:00:00Z",
+                "to_date": "2025-01-01T00:00:00Z",
+            }),
+        ] {
+            assert!(
+                request(json.clone()).into_search().is_err(),
+                "{json} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_owner() {
+        let err = search_owner(None, None).unwrap_err();
+        assert!(matches!(err, ApiError::Unauthorized { .. }));
+    }
+}
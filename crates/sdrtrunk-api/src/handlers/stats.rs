@@ -1,11 +1,16 @@
 //! System statistics endpoint for monitoring and analytics
 
-use crate::state::AppState;
+use crate::{
+    error::ApiError, quotas::SystemStorage, state::AppState, stats_cache::StatsKey,
+    tenant::TenantScope, worker_metrics::WorkerPoolSnapshot,
+};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Json},
 };
+use sdrtrunk_storage::{FrequencyQueries, FrequencyUsage, QuotaQueries};
+use sdrtrunk_types::{SystemId, TalkgroupId};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -29,10 +34,10 @@ pub struct StatsQuery {
 }
 
 /// System statistics response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SystemStatsResponse {
     /// System identifier
-    pub system_id: String,
+    pub system_id: SystemId,
     /// Optional system display label
     pub system_label: Option<String>,
 
@@ -42,6 +47,9 @@ pub struct SystemStatsResponse {
     /// Time information
     pub time_info: TimeInfo,
 
+    /// Recording storage used and the system's quota
+    pub storage: SystemStorage,
+
     /// Top talkgroups (if requested)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_talkgroups: Option<Vec<TalkgroupStats>>,
@@ -62,7 +70,7 @@ pub struct SystemStatsResponse {
 }
 
 /// Call count information
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CallCounts {
     /// Total calls ever recorded
     pub total_calls: i32,
@@ -84,7 +92,7 @@ pub struct CallCounts {
 }
 
 /// Time-related information
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TimeInfo {
     /// When the system was first seen
     pub first_seen: Option<chrono::DateTime<chrono::Utc>>,
@@ -100,7 +108,7 @@ pub struct TimeInfo {
 }
 
 /// Activity status enum
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ActivityStatus {
     /// Active (calls in last hour)
@@ -114,10 +122,10 @@ pub enum ActivityStatus {
 }
 
 /// Talkgroup statistics
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TalkgroupStats {
     /// Talkgroup ID
-    pub talkgroup_id: i32,
+    pub talkgroup_id: TalkgroupId,
 
     /// Talkgroup label
     pub talkgroup_label: Option<String>,
@@ -136,7 +144,7 @@ pub struct TalkgroupStats {
 }
 
 /// Upload source statistics
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct UploadSourceStats {
     /// Source IP address
     pub source_ip: String,
@@ -155,7 +163,7 @@ pub struct UploadSourceStats {
 }
 
 /// Hourly call statistics
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HourlyStats {
     /// Hour (0-23)
     pub hour: i32,
@@ -168,7 +176,7 @@ pub struct HourlyStats {
 }
 
 /// Global statistics response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GlobalStatsResponse {
     /// Total number of systems
     pub total_systems: i32,
@@ -182,21 +190,24 @@ pub struct GlobalStatsResponse {
     /// Most active systems
     pub top_systems: Vec<SystemSummary>,
 
-    /// Recent activity timeline
+    /// Hourly call counts for the last 24 hours, oldest first
     pub recent_activity: Vec<ActivityPeriod>,
 
     /// Storage statistics
     pub storage_stats: StorageStats,
 
+    /// Storage used by each system with a quota
+    pub storage_quotas: Vec<SystemStorage>,
+
     /// Generated timestamp
     pub generated_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// System summary for global stats
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SystemSummary {
     /// System ID
-    pub system_id: String,
+    pub system_id: SystemId,
 
     /// System label
     pub system_label: Option<String>,
@@ -209,7 +220,7 @@ pub struct SystemSummary {
 }
 
 /// Activity period for timeline
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ActivityPeriod {
     /// Start of period
     pub period_start: chrono::DateTime<chrono::Utc>,
@@ -222,7 +233,7 @@ pub struct ActivityPeriod {
 }
 
 /// Storage statistics
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StorageStats {
     /// Total files stored
     pub total_files: i64,
@@ -237,45 +248,374 @@ pub struct StorageStats {
     pub storage_path: String,
 }
 
-/// Error response
+/// Query parameters for storage growth statistics
+#[derive(Debug, Deserialize, Validate)]
+pub struct StorageGrowthQuery {
+    /// Number of days of history used to compute the growth rate
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<i32>,
+}
+
+/// Storage growth and time-to-full projection
+#[derive(Debug, Serialize)]
+pub struct StorageGrowthResponse {
+    /// Days of history the rates are based on
+    pub window_days: i32,
+
+    /// Total audio bytes currently stored
+    pub total_bytes_stored: i64,
+
+    /// Bytes added within the window
+    pub bytes_added_in_window: i64,
+
+    /// Average bytes added per day over the window
+    pub avg_bytes_per_day: f64,
+
+    /// Bytes added per day across all systems
+    pub daily_growth: Vec<DailyGrowthPoint>,
+
+    /// Growth broken down by system, largest first
+    pub systems: Vec<SystemStorageGrowth>,
+
+    /// Time-to-full projection (present when a volume capacity is configured)
+    pub projection: Option<StorageProjection>,
+
+    /// Generated timestamp
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Bytes added on a single day
+#[derive(Debug, Serialize)]
+pub struct DailyGrowthPoint {
+    /// Calendar day (UTC)
+    pub date: chrono::NaiveDate,
+
+    /// Bytes added that day
+    pub bytes_added: i64,
+
+    /// Calls stored that day
+    pub call_count: i64,
+}
+
+/// Storage growth for one system
+#[derive(Debug, Serialize)]
+pub struct SystemStorageGrowth {
+    /// System ID
+    pub system_id: SystemId,
+
+    /// Bytes added within the window
+    pub bytes_added: i64,
+
+    /// Average bytes added per day over the window
+    pub avg_bytes_per_day: f64,
+}
+
+/// Projection of when the storage volume will fill up
+#[derive(Debug, Serialize)]
+pub struct StorageProjection {
+    /// Configured volume capacity in bytes
+    pub capacity_bytes: u64,
+
+    /// Bytes remaining before the volume is full
+    pub remaining_bytes: u64,
+
+    /// Percentage of capacity used
+    pub used_percent: f64,
+
+    /// Days until full at the current rate (`None` when there is no growth)
+    pub days_until_full: Option<f64>,
+
+    /// Estimated time the volume fills up
+    pub estimated_full_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Days-until-full threshold below which headroom is considered low
+    pub low_headroom_threshold_days: u32,
+
+    /// Whether the projection is under the low headroom threshold
+    pub low_headroom: bool,
+}
+
+/// Query parameters for language statistics
+#[derive(Debug, Deserialize, Validate)]
+pub struct LanguageStatsQuery {
+    /// Restrict to a single system
+    pub system_id: Option<SystemId>,
+
+    /// Restrict to a single talkgroup
+    pub talkgroup_id: Option<TalkgroupId>,
+
+    /// Number of days of history to include
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<i32>,
+
+    /// Include per-talkgroup breakdown
+    pub include_talkgroups: Option<bool>,
+}
+
+/// Detected language statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageStatsResponse {
+    /// Days of history included
+    pub window_days: i32,
+
+    /// Completed transcriptions with a detected language
+    pub total_calls: i64,
+
+    /// Language distribution across the selection, most common first
+    pub languages: Vec<LanguageCount>,
+
+    /// Language distribution per system
+    pub systems: Vec<SystemLanguageStats>,
+
+    /// Language distribution per talkgroup (when requested)
+    pub talkgroups: Option<Vec<TalkgroupLanguageStats>>,
+
+    /// Calls per language per day
+    pub timeline: Vec<LanguageTimelinePoint>,
+
+    /// Generated timestamp
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Call count for a single language
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageCount {
+    /// Language code
+    pub language: String,
+
+    /// Number of calls
+    pub call_count: i64,
+
+    /// Percentage of calls in the group
+    pub percentage: f64,
+}
+
+/// Language distribution for a system
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemLanguageStats {
+    /// System ID
+    pub system_id: SystemId,
+
+    /// Language distribution
+    pub languages: Vec<LanguageCount>,
+}
+
+/// Language distribution for a talkgroup
+#[derive(Debug, Clone, Serialize)]
+pub struct TalkgroupLanguageStats {
+    /// System ID
+    pub system_id: SystemId,
+
+    /// Talkgroup ID
+    pub talkgroup_id: Option<TalkgroupId>,
+
+    /// Language distribution
+    pub languages: Vec<LanguageCount>,
+}
+
+/// Calls in one language on one day
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageTimelinePoint {
+    /// Calendar day (UTC)
+    pub date: chrono::NaiveDate,
+
+    /// Language code
+    pub language: String,
+
+    /// Number of calls
+    pub call_count: i64,
+}
+
+/// Query parameters for talkgroup activity statistics
+#[derive(Debug, Deserialize, Validate)]
+pub struct TalkgroupActivityQuery {
+    /// Restrict to a single system
+    pub system_id: Option<SystemId>,
+
+    /// Number of hours of history to include
+    #[validate(range(min = 1, max = 8760))]
+    pub hours: Option<i32>,
+
+    /// Number of talkgroups to return, busiest first
+    #[validate(range(min = 1, max = 500))]
+    pub limit: Option<i64>,
+}
+
+/// Talkgroup activity statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct TalkgroupActivityResponse {
+    /// Hours of history included
+    pub window_hours: i32,
+
+    /// Busiest talkgroups, most calls first
+    pub talkgroups: Vec<TalkgroupActivity>,
+
+    /// Generated timestamp
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Call activity of one talkgroup
+#[derive(Debug, Clone, Serialize)]
+pub struct TalkgroupActivity {
+    /// System ID
+    pub system_id: SystemId,
+
+    /// Talkgroup ID
+    pub talkgroup_id: TalkgroupId,
+
+    /// Talkgroup label
+    pub talkgroup_label: Option<String>,
+
+    /// Number of calls
+    pub call_count: i64,
+
+    /// Total airtime in seconds
+    pub airtime_seconds: f64,
+
+    /// Average call duration in seconds
+    pub avg_duration_seconds: f64,
+
+    /// First call in the window
+    pub first_call: chrono::DateTime<chrono::Utc>,
+
+    /// Last call in the window
+    pub last_call: chrono::DateTime<chrono::Utc>,
+
+    /// Hours of the day (UTC) with the most calls, busiest first
+    pub busiest_hours: Vec<BusyHour>,
+}
+
+/// Calls in one hour of the day
+#[derive(Debug, Clone, Serialize)]
+pub struct BusyHour {
+    /// Hour (0-23, UTC)
+    pub hour: i32,
+
+    /// Number of calls
+    pub call_count: i64,
+}
+
+/// Query parameters for frequency usage statistics
+#[derive(Debug, Deserialize, Validate)]
+pub struct FrequencyUsageQuery {
+    /// Restrict to a single system
+    pub system_id: Option<SystemId>,
+
+    /// Number of days of history to include
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<i32>,
+
+    /// Number of frequencies to return, most used first
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<usize>,
+}
+
+/// Channel usage report
 #[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    /// Error message
-    pub error: String,
-    /// Error code
-    pub code: String,
+pub struct FrequencyUsageResponse {
+    /// Days of history included
+    pub window_days: i32,
+
+    /// Frequencies heard in the window (before `limit`)
+    pub total_frequencies: usize,
+
+    /// Most used frequencies, most calls first
+    pub frequencies: Vec<FrequencyUsageStats>,
+
+    /// Generated timestamp
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Usage of one frequency
+#[derive(Debug, Serialize)]
+pub struct FrequencyUsageStats {
+    /// Frequency in Hz
+    pub frequency_hz: i64,
+
+    /// Frequency in MHz
+    pub frequency_mhz: f64,
+
+    /// Calls heard on the frequency
+    pub call_count: i64,
+
+    /// Total airtime in seconds
+    pub airtime_seconds: f64,
+
+    /// Share of the window the frequency was busy
+    pub utilization_percent: f64,
+
+    /// Decoding errors reported on the frequency
+    pub error_count: i64,
+
+    /// Signal spikes reported on the frequency
+    pub spike_count: i64,
+
+    /// Systems heard on the frequency
+    pub systems: Vec<SystemId>,
+
+    /// Most recent call on the frequency
+    pub last_seen: chrono::DateTime<chrono::Utc>,
 }
 
 /// Get system statistics
 ///
 /// # Errors
 ///
-/// Returns an error if the database queries fail or query parameters are invalid.
-#[allow(clippy::cognitive_complexity, clippy::cast_possible_truncation)]
+/// Returns an error if the database queries fail, query parameters are invalid,
+/// or the API key may not access the system.
 pub async fn get_system_stats(
     State(state): State<Arc<AppState>>,
-    Path(system_id): Path<String>,
+    scope: TenantScope,
+    Path(system_id): Path<SystemId>,
     Query(query): Query<StatsQuery>,
-) -> Result<Json<SystemStatsResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<SystemStatsResponse>, ApiError> {
+    if !scope.allows(&system_id) {
+        return Err(ApiError::forbidden(
+            "FORBIDDEN",
+            format!("API key may not access system {system_id}"),
+        ));
+    }
+
     // Validate query parameters
     if let Err(validation_errors) = query.validate() {
         warn!("Invalid query parameters: {:?}", validation_errors);
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Invalid query parameters".to_string(),
-                code: "INVALID_PARAMETERS".to_string(),
-            }),
-        ));
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
     }
 
+    let key = StatsKey::new(
+        "system",
+        Some(std::slice::from_ref(&system_id)),
+        format!("{query:?}"),
+    );
+    let response = state
+        .stats_cache
+        .get_or_load(key, load_system_stats(&state, &system_id, &query))
+        .await?;
+    Ok(Json(response))
+}
+
+/// Compute the statistics of one system
+#[allow(
+    clippy::cognitive_complexity,
+    clippy::cast_possible_truncation,
+    clippy::too_many_lines
+)]
+async fn load_system_stats(
+    state: &AppState,
+    system_id: &SystemId,
+    query: &StatsQuery,
+) -> Result<SystemStatsResponse, ApiError> {
     info!("Retrieving statistics for system: {}", system_id);
 
     // Execute all queries in parallel for better performance
-    let (system_stats_result, calls_24h_result, calls_7d_result) = tokio::join!(
-        sdrtrunk_storage::get_system_stats(&state.pool, &system_id),
-        sdrtrunk_storage::count_system_calls_since(&state.pool, &system_id, 24),
-        sdrtrunk_storage::count_system_calls_since(&state.pool, &system_id, 168) // 7 days
+    let (system_stats_result, calls_24h_result, calls_7d_result, usage_result) = tokio::join!(
+        sdrtrunk_storage::get_system_stats(&state.read_pool, system_id),
+        sdrtrunk_storage::count_system_calls_since(&state.read_pool, system_id, 24),
+        sdrtrunk_storage::count_system_calls_since(&state.read_pool, system_id, 168), // 7 days
+        QuotaQueries::usage(&state.read_pool, system_id)
     );
 
     // Handle system stats result
@@ -283,13 +623,7 @@ pub async fn get_system_stats(
         Ok(system_stats) => system_stats,
         Err(e) => {
             error!("Failed to retrieve system stats: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve statistics".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                }),
-            ));
+            return Err(ApiError::database("Failed to retrieve statistics"));
         }
     };
 
@@ -297,6 +631,10 @@ pub async fn get_system_stats(
     let calls_last_24h: i32 = calls_24h_result.unwrap_or(0).try_into().unwrap_or(0);
     let calls_last_7d: i32 = calls_7d_result.unwrap_or(0).try_into().unwrap_or(0);
     let avg_calls_per_day = f64::from(calls_last_7d) / 7.0;
+    let used_bytes = usage_result.unwrap_or_else(|e| {
+        warn!("Failed to measure storage of {system_id}: {e}");
+        0
+    });
 
     // Determine activity status
     let activity_status =
@@ -309,7 +647,7 @@ pub async fn get_system_stats(
 
     // Build response
     let mut response = SystemStatsResponse {
-        system_id: system_stats.system_id.as_str().to_string(),
+        system_id: system_stats.system_id.clone(),
         system_label: system_stats.system_label.clone(),
         call_counts: CallCounts {
             total_calls: system_stats.total_calls.unwrap_or(0),
@@ -325,6 +663,11 @@ pub async fn get_system_stats(
             days_active,
             activity_status,
         },
+        storage: SystemStorage::new(
+            system_id.clone(),
+            used_bytes,
+            state.config.uploads.policy_for(system_id.as_str()),
+        ),
         top_talkgroups: None,
         upload_sources: None,
         hourly_distribution: None,
@@ -334,41 +677,59 @@ pub async fn get_system_stats(
 
     // Add optional detailed stats
     if query.include_talkgroups.unwrap_or(false) {
-        response.top_talkgroups = Some(get_talkgroup_stats(&state.pool, &system_id));
+        response.top_talkgroups = Some(get_talkgroup_stats(&state.read_pool, system_id));
     }
 
     if query.include_sources.unwrap_or(false) {
-        response.upload_sources = Some(get_upload_source_stats(&state.pool, &system_id));
+        response.upload_sources = Some(get_upload_source_stats(&state.read_pool, system_id));
     }
 
     if query.include_hourly.unwrap_or(false) {
-        response.hourly_distribution = Some(get_hourly_stats(&state.pool, &system_id));
+        response.hourly_distribution = Some(get_hourly_stats(&state.read_pool, system_id));
     }
 
     info!(
         "Successfully retrieved statistics for system: {}",
         system_id
     );
-    Ok(Json(response))
+    Ok(response)
 }
 
-/// Get global statistics across all systems
+/// Get global statistics across all systems the API key may access
+///
+/// Served from the statistics cache while fresh.
 ///
 /// # Errors
 ///
 /// Returns an error if the database queries fail.
-#[allow(clippy::cognitive_complexity, clippy::cast_possible_truncation)]
 pub async fn get_global_stats(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<GlobalStatsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    scope: TenantScope,
+) -> Result<Json<GlobalStatsResponse>, ApiError> {
+    let systems = scope.systems();
+    let key = StatsKey::new("global", systems, String::new());
+    let response = state
+        .stats_cache
+        .get_or_load(key, load_global_stats(&state, systems))
+        .await?;
+    Ok(Json(response))
+}
+
+/// Compute global statistics across `systems`, or all systems for `None`
+#[allow(clippy::cognitive_complexity, clippy::cast_possible_truncation)]
+async fn load_global_stats(
+    state: &AppState,
+    systems: Option<&[SystemId]>,
+) -> Result<GlobalStatsResponse, ApiError> {
     info!("Retrieving global statistics");
 
     // Execute all independent queries in parallel for better performance
-    let (systems_result, calls_result, recent_result, top_systems_result) = tokio::join!(
-        sdrtrunk_storage::count_systems(&state.pool),
-        sdrtrunk_storage::count_radio_calls(&state.pool),
-        sdrtrunk_storage::count_recent_calls(&state.pool, 24),
-        sdrtrunk_storage::get_top_systems(&state.pool, 10)
+    let (systems_result, calls_result, recent_result, top_systems_result, activity_result) = tokio::join!(
+        sdrtrunk_storage::count_systems(&state.read_pool, systems),
+        sdrtrunk_storage::count_radio_calls(&state.read_pool, systems),
+        sdrtrunk_storage::count_recent_calls(&state.read_pool, 24, systems),
+        sdrtrunk_storage::get_top_systems(&state.read_pool, 10, systems),
+        sdrtrunk_storage::get_hourly_activity(&state.read_pool, 24, systems)
     );
 
     // Handle results
@@ -376,13 +737,7 @@ pub async fn get_global_stats(
         Ok(count) => count,
         Err(e) => {
             error!("Failed to count systems: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve global statistics".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                }),
-            ));
+            return Err(ApiError::database("Failed to retrieve global statistics"));
         }
     };
 
@@ -390,13 +745,7 @@ pub async fn get_global_stats(
         Ok(count) => count,
         Err(e) => {
             error!("Failed to count calls: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve global statistics".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                }),
-            ));
+            return Err(ApiError::database("Failed to retrieve global statistics"));
         }
     };
 
@@ -424,21 +773,49 @@ pub async fn get_global_stats(
         }
     };
 
+    let recent_activity = activity_result.map_or_else(
+        |e| {
+            warn!("Failed to get hourly activity: {}", e);
+            Vec::new()
+        },
+        activity_timeline,
+    );
+
     // Get storage stats
     let storage_stats = calculate_storage_stats(&state.config.storage.base_dir);
+    let storage_quotas =
+        crate::quotas::quota_usage(&state.read_pool, &state.config.uploads.systems, systems)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to measure storage quota usage: {}", e);
+                Vec::new()
+            });
 
     let response = GlobalStatsResponse {
         total_systems: total_systems.try_into().unwrap_or(0),
         total_calls,
         calls_last_24h,
         top_systems,
-        recent_activity: Vec::new(), // TODO: Implement activity timeline
+        recent_activity,
         storage_stats,
+        storage_quotas,
         generated_at: chrono::Utc::now(),
     };
 
     info!("Successfully retrieved global statistics");
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// Convert hourly call counts into the global stats timeline
+fn activity_timeline(hours: Vec<sdrtrunk_storage::HourlyActivity>) -> Vec<ActivityPeriod> {
+    hours
+        .into_iter()
+        .map(|hour| ActivityPeriod {
+            period_start: hour.period_start,
+            call_count: hour.call_count.try_into().unwrap_or(0),
+            active_systems: hour.active_systems.try_into().unwrap_or(0),
+        })
+        .collect()
 }
 
 /// Determine activity status based on call counts
@@ -453,21 +830,24 @@ const fn determine_activity_status(calls_this_hour: i32, calls_last_24h: i32) ->
 }
 
 /// Get talkgroup statistics for a system
-const fn get_talkgroup_stats(_pool: &sqlx::PgPool, _system_id: &str) -> Vec<TalkgroupStats> {
+const fn get_talkgroup_stats(_pool: &sqlx::PgPool, _system_id: &SystemId) -> Vec<TalkgroupStats> {
     // This would query the database for talkgroup stats
     // Placeholder implementation
     Vec::new()
 }
 
 /// Get upload source statistics for a system
-const fn get_upload_source_stats(_pool: &sqlx::PgPool, _system_id: &str) -> Vec<UploadSourceStats> {
+const fn get_upload_source_stats(
+    _pool: &sqlx::PgPool,
+    _system_id: &SystemId,
+) -> Vec<UploadSourceStats> {
     // This would query the database for upload source stats
     // Placeholder implementation
     Vec::new()
 }
 
 /// Get hourly call distribution for a system
-fn get_hourly_stats(_pool: &sqlx::PgPool, _system_id: &str) -> Vec<HourlyStats> {
+fn get_hourly_stats(_pool: &sqlx::PgPool, _system_id: &SystemId) -> Vec<HourlyStats> {
     // This would query the database for hourly distribution
     // Placeholder implementation
     let mut hourly_stats = Vec::new();
@@ -493,6 +873,480 @@ fn calculate_storage_stats(storage_path: &std::path::Path) -> StorageStats {
     }
 }
 
+/// Get storage growth statistics and a time-to-full projection
+///
+/// Stored bytes and daily growth only cover the API key's systems; the
+/// projection always uses the capacity of the whole storage volume.
+///
+/// # Errors
+///
+/// Returns an error if the database queries fail or query parameters are invalid.
+#[allow(clippy::cast_precision_loss)]
+pub async fn get_storage_growth(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<StorageGrowthQuery>,
+) -> Result<Json<StorageGrowthResponse>, ApiError> {
+    if let Err(validation_errors) = query.validate() {
+        warn!("Invalid query parameters: {:?}", validation_errors);
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
+    }
+
+    let window_days = query.days.unwrap_or(30);
+
+    let (total_result, growth_result) = tokio::join!(
+        sdrtrunk_storage::sum_audio_bytes(&state.read_pool, scope.systems()),
+        sdrtrunk_storage::get_daily_storage_growth(&state.read_pool, window_days, scope.systems())
+    );
+
+    let (total_bytes_stored, growth) = match (total_result, growth_result) {
+        (Ok(total), Ok(growth)) => (total, growth),
+        (Err(e), _) | (_, Err(e)) => {
+            error!("Failed to retrieve storage growth: {}", e);
+            return Err(ApiError::database("Failed to retrieve storage growth"));
+        }
+    };
+
+    let daily_growth = aggregate_daily_growth(&growth);
+    let systems = aggregate_system_growth(&growth, window_days);
+    let bytes_added_in_window: i64 = daily_growth.iter().map(|d| d.bytes_added).sum();
+    let avg_bytes_per_day = bytes_added_in_window as f64 / f64::from(window_days);
+
+    let projection = state.config.storage.capacity_bytes.map(|capacity_bytes| {
+        project_storage(
+            capacity_bytes,
+            total_bytes_stored,
+            avg_bytes_per_day,
+            state.config.storage.low_headroom_days,
+        )
+    });
+
+    if let Some(days) = projection
+        .as_ref()
+        .filter(|p| p.low_headroom)
+        .and_then(|p| p.days_until_full)
+    {
+        warn!(
+            "Storage headroom low: volume projected to fill in {:.1} days",
+            days
+        );
+    }
+
+    Ok(Json(StorageGrowthResponse {
+        window_days,
+        total_bytes_stored,
+        bytes_added_in_window,
+        avg_bytes_per_day,
+        daily_growth,
+        systems,
+        projection,
+        generated_at: chrono::Utc::now(),
+    }))
+}
+
+/// Collapse per-system daily rows into one total per day
+fn aggregate_daily_growth(
+    growth: &[sdrtrunk_storage::DailyStorageGrowth],
+) -> Vec<DailyGrowthPoint> {
+    let mut days = std::collections::BTreeMap::new();
+    for row in growth {
+        let point = days.entry(row.day).or_insert(DailyGrowthPoint {
+            date: row.day,
+            bytes_added: 0,
+            call_count: 0,
+        });
+        point.bytes_added += row.bytes_added;
+        point.call_count += row.call_count;
+    }
+
+    days.into_values().collect()
+}
+
+/// Sum growth per system over the window, largest first
+#[allow(clippy::cast_precision_loss)]
+fn aggregate_system_growth(
+    growth: &[sdrtrunk_storage::DailyStorageGrowth],
+    window_days: i32,
+) -> Vec<SystemStorageGrowth> {
+    let mut totals: std::collections::HashMap<&SystemId, i64> = std::collections::HashMap::new();
+    for row in growth {
+        *totals.entry(&row.system_id).or_default() += row.bytes_added;
+    }
+
+    let mut systems: Vec<SystemStorageGrowth> = totals
+        .into_iter()
+        .map(|(system_id, bytes_added)| SystemStorageGrowth {
+            system_id: system_id.clone(),
+            bytes_added,
+            avg_bytes_per_day: bytes_added as f64 / f64::from(window_days.max(1)),
+        })
+        .collect();
+    systems.sort_by(|a, b| {
+        b.bytes_added
+            .cmp(&a.bytes_added)
+            .then_with(|| a.system_id.cmp(&b.system_id))
+    });
+    systems
+}
+
+/// Project when the volume fills up at the given daily growth rate
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn project_storage(
+    capacity_bytes: u64,
+    used_bytes: i64,
+    avg_bytes_per_day: f64,
+    low_headroom_days: u32,
+) -> StorageProjection {
+    let used = u64::try_from(used_bytes).unwrap_or(0);
+    let remaining_bytes = capacity_bytes.saturating_sub(used);
+    let used_percent = if capacity_bytes == 0 {
+        100.0
+    } else {
+        (used as f64 / capacity_bytes as f64 * 100.0).min(100.0)
+    };
+
+    let days_until_full = if remaining_bytes == 0 {
+        Some(0.0)
+    } else if avg_bytes_per_day > 0.0 {
+        Some(remaining_bytes as f64 / avg_bytes_per_day)
+    } else {
+        None
+    };
+
+    let estimated_full_at = days_until_full.and_then(|days| {
+        let seconds = (days * 86_400.0).min(f64::from(i32::MAX)) as i64;
+        chrono::Utc::now().checked_add_signed(chrono::Duration::seconds(seconds))
+    });
+
+    let low_headroom = days_until_full.is_some_and(|days| days < f64::from(low_headroom_days));
+
+    StorageProjection {
+        capacity_bytes,
+        remaining_bytes,
+        used_percent,
+        days_until_full,
+        estimated_full_at,
+        low_headroom_threshold_days: low_headroom_days,
+        low_headroom,
+    }
+}
+
+/// Get detected transcription language statistics per system and talkgroup
+///
+/// # Errors
+///
+/// Returns an error if the database query fails or query parameters are invalid.
+pub async fn get_language_stats(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<LanguageStatsQuery>,
+) -> Result<Json<LanguageStatsResponse>, ApiError> {
+    if let Err(validation_errors) = query.validate() {
+        warn!("Invalid query parameters: {:?}", validation_errors);
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
+    }
+
+    let systems = query
+        .system_id
+        .as_ref()
+        .map(std::slice::from_ref)
+        .or_else(|| scope.systems());
+    let key = StatsKey::new("languages", systems, format!("{query:?}"));
+    let response = state
+        .stats_cache
+        .get_or_load(key, async {
+            let window_days = query.days.unwrap_or(30);
+            let filter = sdrtrunk_storage::LanguageStatsFilter {
+                system_id: query.system_id.as_ref(),
+                allowed_systems: scope.systems(),
+                talkgroup_id: query.talkgroup_id,
+                days: window_days,
+            };
+            match sdrtrunk_storage::get_language_stats(&state.read_pool, &filter).await {
+                Ok(rows) => Ok(build_language_stats(
+                    window_days,
+                    &rows,
+                    query.include_talkgroups.unwrap_or(false),
+                )),
+                Err(e) => {
+                    error!("Failed to retrieve language stats: {}", e);
+                    Err(ApiError::database("Failed to retrieve language statistics"))
+                }
+            }
+        })
+        .await?;
+    Ok(Json(response))
+}
+
+/// Aggregate language rows into overall, per-system, per-talkgroup and daily views
+fn build_language_stats(
+    window_days: i32,
+    rows: &[sdrtrunk_storage::LanguageStatsRow],
+    include_talkgroups: bool,
+) -> LanguageStatsResponse {
+    let total_calls = rows.iter().map(|r| r.call_count).sum();
+    let languages = language_distribution(rows.iter());
+
+    let mut system_ids: Vec<&SystemId> = rows.iter().map(|r| &r.system_id).collect();
+    system_ids.sort_unstable();
+    system_ids.dedup();
+    let systems = system_ids
+        .into_iter()
+        .map(|system_id| SystemLanguageStats {
+            system_id: system_id.clone(),
+            languages: language_distribution(rows.iter().filter(|r| &r.system_id == system_id)),
+        })
+        .collect();
+
+    let talkgroups = include_talkgroups.then(|| {
+        let mut keys: Vec<_> = rows
+            .iter()
+            .map(|r| (&r.system_id, r.talkgroup_id))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys.into_iter()
+            .map(|(system_id, talkgroup_id)| TalkgroupLanguageStats {
+                system_id: system_id.clone(),
+                talkgroup_id,
+                languages: language_distribution(
+                    rows.iter()
+                        .filter(|r| &r.system_id == system_id && r.talkgroup_id == talkgroup_id),
+                ),
+            })
+            .collect()
+    });
+
+    let mut timeline_counts = std::collections::BTreeMap::new();
+    for row in rows {
+        *timeline_counts
+            .entry((row.day, row.language.as_str()))
+            .or_insert(0_i64) += row.call_count;
+    }
+    let timeline = timeline_counts
+        .into_iter()
+        .map(|((date, language), call_count)| LanguageTimelinePoint {
+            date,
+            language: language.to_string(),
+            call_count,
+        })
+        .collect();
+
+    LanguageStatsResponse {
+        window_days,
+        total_calls,
+        languages,
+        systems,
+        talkgroups,
+        timeline,
+        generated_at: chrono::Utc::now(),
+    }
+}
+
+/// Count calls per language, most common first
+#[allow(clippy::cast_precision_loss)]
+fn language_distribution<'a>(
+    rows: impl Iterator<Item = &'a sdrtrunk_storage::LanguageStatsRow>,
+) -> Vec<LanguageCount> {
+    let mut counts: std::collections::HashMap<&str, i64> = std::collections::HashMap::new();
+    for row in rows {
+        *counts.entry(row.language.as_str()).or_default() += row.call_count;
+    }
+
+    let total: i64 = counts.values().sum();
+    let mut languages: Vec<LanguageCount> = counts
+        .into_iter()
+        .map(|(language, call_count)| LanguageCount {
+            language: language.to_string(),
+            call_count,
+            percentage: if total > 0 {
+                call_count as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    languages.sort_by(|a, b| {
+        b.call_count
+            .cmp(&a.call_count)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    languages
+}
+
+/// Get call counts, airtime, and busiest hours per talkgroup
+///
+/// # Errors
+///
+/// Returns an error if the database query fails or query parameters are invalid.
+pub async fn get_talkgroup_activity(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<TalkgroupActivityQuery>,
+) -> Result<Json<TalkgroupActivityResponse>, ApiError> {
+    if let Err(validation_errors) = query.validate() {
+        warn!("Invalid query parameters: {:?}", validation_errors);
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
+    }
+
+    let systems = query
+        .system_id
+        .as_ref()
+        .map(std::slice::from_ref)
+        .or_else(|| scope.systems());
+    let key = StatsKey::new("talkgroups", systems, format!("{query:?}"));
+    let response = state
+        .stats_cache
+        .get_or_load(key, async {
+            let window_hours = query.hours.unwrap_or(24);
+            let filter = sdrtrunk_storage::TalkgroupActivityFilter {
+                system_id: query.system_id.as_ref(),
+                allowed_systems: scope.systems(),
+                hours: window_hours,
+                limit: query.limit.unwrap_or(50),
+            };
+            match sdrtrunk_storage::get_talkgroup_activity(&state.read_pool, &filter).await {
+                Ok(rows) => Ok(TalkgroupActivityResponse {
+                    window_hours,
+                    talkgroups: rows.into_iter().map(TalkgroupActivity::from).collect(),
+                    generated_at: chrono::Utc::now(),
+                }),
+                Err(e) => {
+                    error!("Failed to retrieve talkgroup activity: {}", e);
+                    Err(ApiError::database("Failed to retrieve talkgroup activity"))
+                }
+            }
+        })
+        .await?;
+    Ok(Json(response))
+}
+
+impl From<sdrtrunk_storage::TalkgroupActivityRow> for TalkgroupActivity {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(row: sdrtrunk_storage::TalkgroupActivityRow) -> Self {
+        Self {
+            system_id: row.system_id,
+            talkgroup_id: row.talkgroup_id,
+            talkgroup_label: row.talkgroup_label,
+            call_count: row.call_count,
+            airtime_seconds: row.airtime_seconds,
+            avg_duration_seconds: if row.call_count > 0 {
+                row.airtime_seconds / row.call_count as f64
+            } else {
+                0.0
+            },
+            first_call: row.first_call,
+            last_call: row.last_call,
+            busiest_hours: row
+                .busiest_hours
+                .into_iter()
+                .map(|h| BusyHour {
+                    hour: h.hour,
+                    call_count: h.call_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Get per-frequency channel usage from the calls' frequency lists
+///
+/// # Errors
+///
+/// Returns an error if the database query fails, query parameters are invalid,
+/// or the API key may not access the requested system.
+pub async fn get_frequency_usage(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Query(query): Query<FrequencyUsageQuery>,
+) -> Result<Json<FrequencyUsageResponse>, ApiError> {
+    if let Err(validation_errors) = query.validate() {
+        warn!("Invalid query parameters: {:?}", validation_errors);
+        return Err(
+            ApiError::bad_request("INVALID_PARAMETERS", "Invalid query parameters")
+                .with_details(serde_json::json!(validation_errors)),
+        );
+    }
+    if let Some(system_id) = &query.system_id
+        && !scope.allows(system_id)
+    {
+        return Err(ApiError::forbidden(
+            "FORBIDDEN",
+            format!("API key may not access system {system_id}"),
+        ));
+    }
+
+    let window_days = query.days.unwrap_or(7);
+    let since = chrono::Utc::now() - chrono::Duration::days(i64::from(window_days));
+    let systems = query.system_id.as_ref().map(std::slice::from_ref);
+    let usage =
+        match FrequencyQueries::usage(&state.read_pool, systems.or_else(|| scope.systems()), since)
+            .await
+        {
+            Ok(usage) => usage,
+            Err(e) => {
+                error!("Failed to retrieve frequency usage: {}", e);
+                return Err(ApiError::database("Failed to retrieve frequency usage"));
+            }
+        };
+
+    Ok(Json(build_frequency_usage(
+        window_days,
+        usage,
+        query.limit.unwrap_or(100),
+    )))
+}
+
+/// Report the `limit` most used frequencies with their share of the window
+#[allow(clippy::cast_precision_loss)]
+fn build_frequency_usage(
+    window_days: i32,
+    usage: Vec<FrequencyUsage>,
+    limit: usize,
+) -> FrequencyUsageResponse {
+    let window_seconds = f64::from(window_days) * 86_400.0;
+    let total_frequencies = usage.len();
+    let frequencies = usage
+        .into_iter()
+        .take(limit)
+        .map(|frequency| FrequencyUsageStats {
+            frequency_hz: frequency.frequency_hz,
+            frequency_mhz: frequency.frequency_hz as f64 / 1_000_000.0,
+            call_count: frequency.call_count,
+            airtime_seconds: frequency.airtime_seconds,
+            utilization_percent: if window_seconds > 0.0 {
+                frequency.airtime_seconds / window_seconds * 100.0
+            } else {
+                0.0
+            },
+            error_count: frequency.error_count,
+            spike_count: frequency.spike_count,
+            systems: frequency.systems,
+            last_seen: frequency.last_seen,
+        })
+        .collect();
+
+    FrequencyUsageResponse {
+        window_days,
+        total_frequencies,
+        frequencies,
+        generated_at: chrono::Utc::now(),
+    }
+}
+
 /// Get transcription job queue statistics
 ///
 /// Returns aggregate counts of pending, processing, completed, and failed jobs.
@@ -503,14 +1357,31 @@ fn calculate_storage_stats(storage_path: &std::path::Path) -> StorageStats {
 pub async fn queue_stats(State(app_state): State<Arc<AppState>>) -> impl IntoResponse {
     match sdrtrunk_storage::jobs::JobQueue::stats(&app_state.pool).await {
         Ok(stats) => (StatusCode::OK, Json(serde_json::json!(stats))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": e.to_string()})),
-        )
-            .into_response(),
+        Err(e) => ApiError::database(e.to_string()).into_response(),
     }
 }
 
+/// Transcription worker pool activity since the API started
+#[derive(Debug, Serialize)]
+pub struct TranscriptionStatsResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Jobs processed, failures by error type, and current concurrency
+    pub worker_pool: WorkerPoolSnapshot,
+}
+
+/// Get transcription worker pool metrics
+///
+/// Counts cover progress reported by workers since the API started.
+pub async fn get_transcription_stats(
+    State(state): State<Arc<AppState>>,
+) -> Json<TranscriptionStatsResponse> {
+    Json(TranscriptionStatsResponse {
+        success: true,
+        worker_pool: state.worker_pool.snapshot(),
+    })
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -552,6 +1423,7 @@ pub async fn queue_stats(State(app_state): State<Arc<AppState>>) -> impl IntoRes
 )]
 mod tests {
     use super::*;
+    use crate::error::ErrorResponse;
     use chrono::Utc;
     use serde_json;
     use validator::Validate;
@@ -657,7 +1529,7 @@ mod tests {
         let timestamp = Utc::now();
 
         let response = SystemStatsResponse {
-            system_id: "police".to_string(),
+            system_id: SystemId::new("police").unwrap(),
             system_label: Some("Police Department".to_string()),
             call_counts: CallCounts {
                 total_calls: 1000,
@@ -673,6 +1545,7 @@ mod tests {
                 days_active: Some(365),
                 activity_status: ActivityStatus::Active,
             },
+            storage: SystemStorage::new(SystemId::new("metro").unwrap(), 0, None),
             top_talkgroups: None,
             upload_sources: None,
             hourly_distribution: None,
@@ -685,6 +1558,7 @@ mod tests {
         assert!(json.contains("Police Department"));
         assert!(json.contains("1000"));
         assert!(json.contains("\"active\""));
+        assert!(json.contains("\"storage\":{\"system_id\":\"metro\",\"used_bytes\":0"));
     }
 
     #[test]
@@ -730,7 +1604,7 @@ mod tests {
     #[test]
     fn test_talkgroup_stats_serialization() {
         let tg_stats = TalkgroupStats {
-            talkgroup_id: 12345,
+            talkgroup_id: TalkgroupId::new(12345).unwrap(),
             talkgroup_label: Some("Emergency Dispatch".to_string()),
             talkgroup_group: Some("Emergency Services".to_string()),
             call_count: 150,
@@ -785,13 +1659,13 @@ mod tests {
             calls_last_24h: 500,
             top_systems: vec![
                 SystemSummary {
-                    system_id: "police".to_string(),
+                    system_id: SystemId::new("police").unwrap(),
                     system_label: Some("Police".to_string()),
                     call_count: 3000,
                     last_activity: Some(Utc::now()),
                 },
                 SystemSummary {
-                    system_id: "fire".to_string(),
+                    system_id: SystemId::new("fire").unwrap(),
                     system_label: Some("Fire Department".to_string()),
                     call_count: 2000,
                     last_activity: Some(Utc::now()),
@@ -804,6 +1678,7 @@ mod tests {
                 avg_file_size: 50_000,
                 storage_path: "/storage".to_string(),
             },
+            storage_quotas: Vec::new(),
             generated_at: Utc::now(),
         };
 
@@ -834,8 +1709,10 @@ mod tests {
     #[test]
     fn test_error_response_serialization() {
         let error = ErrorResponse {
+            success: false,
             error: "System not found".to_string(),
             code: "SYSTEM_NOT_FOUND".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&error).expect("Failed to serialize");
@@ -883,13 +1760,13 @@ mod tests {
     #[test]
     fn test_system_summary_creation() {
         let summary = SystemSummary {
-            system_id: "ems".to_string(),
+            system_id: SystemId::new("ems").unwrap(),
             system_label: Some("Emergency Medical Services".to_string()),
             call_count: 750,
             last_activity: Some(Utc::now()),
         };
 
-        assert_eq!(summary.system_id, "ems");
+        assert_eq!(summary.system_id.as_str(), "ems");
         assert_eq!(
             summary.system_label,
             Some("Emergency Medical Services".to_string())
@@ -902,7 +1779,7 @@ mod tests {
     fn test_percentage_calculations() {
         // Test that percentages are handled correctly
         let tg_stats = TalkgroupStats {
-            talkgroup_id: 999,
+            talkgroup_id: TalkgroupId::new(999).unwrap(),
             talkgroup_label: None,
             talkgroup_group: None,
             call_count: 25,
@@ -1032,7 +1909,7 @@ mod tests {
     fn test_talkgroup_stats_comprehensive() {
         // Test with minimal data
         let minimal_tg = TalkgroupStats {
-            talkgroup_id: 1,
+            talkgroup_id: TalkgroupId::new(1).unwrap(),
             talkgroup_label: None,
             talkgroup_group: None,
             call_count: 0,
@@ -1047,7 +1924,7 @@ mod tests {
 
         // Test with maximum values
         let max_tg = TalkgroupStats {
-            talkgroup_id: i32::MAX,
+            talkgroup_id: TalkgroupId::new(i32::MAX).unwrap(),
             talkgroup_label: Some("Maximum Talkgroup".repeat(10)),
             talkgroup_group: Some("Emergency Services Maximum Group".to_string()),
             call_count: i32::MAX,
@@ -1123,8 +2000,10 @@ mod tests {
     fn test_error_response_comprehensive() {
         // Test with empty error
         let empty_error = ErrorResponse {
+            success: false,
             error: String::new(),
             code: String::new(),
+            details: None,
         };
 
         let json = serde_json::to_string(&empty_error).expect("Failed to serialize");
@@ -1133,8 +2012,10 @@ mod tests {
 
         // Test with long error messages
         let long_error = ErrorResponse {
+            success: false,
             error: "This is a very long error message that might occur in production systems when complex validation or processing fails".repeat(5),
             code: "COMPLEX_VALIDATION_ERROR_WITH_LONG_CODE".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&long_error).expect("Failed to serialize");
@@ -1143,8 +2024,10 @@ mod tests {
 
         // Test with special characters
         let special_error = ErrorResponse {
+            success: false,
             error: "Error with special chars: !@#$%^&*(){}[]|\\:;\"'<>?,.~`".to_string(),
             code: "SPECIAL_CHARS_ERROR".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&special_error).expect("Failed to serialize");
@@ -1155,7 +2038,7 @@ mod tests {
     fn test_system_summary_comprehensive() {
         // Test with minimal data
         let minimal_summary = SystemSummary {
-            system_id: "min".to_string(),
+            system_id: SystemId::new("min").unwrap(),
             system_label: None,
             call_count: 0,
             last_activity: None,
@@ -1168,7 +2051,7 @@ mod tests {
 
         // Test with very long system ID
         let long_id_summary = SystemSummary {
-            system_id: "very_long_system_identifier_that_might_be_used_in_production".to_string(),
+            system_id: SystemId::new("very_long_system_identifier_used_in_production_01").unwrap(),
             system_label: Some("Very Long System Label That Describes The System".to_string()),
             call_count: i32::MAX,
             last_activity: Some(Utc::now()),
@@ -1339,14 +2222,14 @@ mod tests {
 
         // Test that we can create these structures
         let sample_tg = TalkgroupStats {
-            talkgroup_id: 999,
+            talkgroup_id: TalkgroupId::new(999).unwrap(),
             talkgroup_label: Some("Test".to_string()),
             talkgroup_group: None,
             call_count: 1,
             percentage: 0.1,
             last_activity: None,
         };
-        assert_eq!(sample_tg.talkgroup_id, 999);
+        assert_eq!(sample_tg.talkgroup_id.as_i32(), 999);
 
         let sample_source = UploadSourceStats {
             source_ip: "127.0.0.1".to_string(),
@@ -1425,7 +2308,7 @@ mod tests {
 
         // Test response with all optional fields present
         let full_response = SystemStatsResponse {
-            system_id: "full_system".to_string(),
+            system_id: SystemId::new("full_system").unwrap(),
             system_label: Some("Full System Label".to_string()),
             call_counts: CallCounts {
                 total_calls: 1000,
@@ -1441,8 +2324,9 @@ mod tests {
                 days_active: Some(100),
                 activity_status: ActivityStatus::Active,
             },
+            storage: SystemStorage::new(SystemId::new("metro").unwrap(), 0, None),
             top_talkgroups: Some(vec![TalkgroupStats {
-                talkgroup_id: 123,
+                talkgroup_id: TalkgroupId::new(123).unwrap(),
                 talkgroup_label: Some("Test TG".to_string()),
                 talkgroup_group: None,
                 call_count: 50,
@@ -1474,7 +2358,7 @@ mod tests {
 
         // Test response with no optional fields (should not include them in JSON)
         let minimal_response = SystemStatsResponse {
-            system_id: "minimal".to_string(),
+            system_id: SystemId::new("minimal").unwrap(),
             system_label: None,
             call_counts: CallCounts {
                 total_calls: 0,
@@ -1490,6 +2374,7 @@ mod tests {
                 days_active: None,
                 activity_status: ActivityStatus::Unknown,
             },
+            storage: SystemStorage::new(SystemId::new("metro").unwrap(), 0, None),
             top_talkgroups: None,
             upload_sources: None,
             hourly_distribution: None,
@@ -1522,6 +2407,7 @@ mod tests {
                 avg_file_size: 0,
                 storage_path: "/empty".to_string(),
             },
+            storage_quotas: Vec::new(),
             generated_at: now,
         };
 
@@ -1537,7 +2423,7 @@ mod tests {
             calls_last_24h: i64::MAX,
             top_systems: (0..10)
                 .map(|i| SystemSummary {
-                    system_id: format!("system_{}", i),
+                    system_id: SystemId::new(format!("system_{}", i)).unwrap(),
                     system_label: Some(format!("System Label {}", i)),
                     call_count: i * 1000,
                     last_activity: Some(now - chrono::Duration::minutes(i64::from(i * 5))),
@@ -1556,6 +2442,7 @@ mod tests {
                 avg_file_size: 1000000,
                 storage_path: "/maximum/storage/path".to_string(),
             },
+            storage_quotas: Vec::new(),
             generated_at: now,
         };
 
@@ -1597,7 +2484,7 @@ mod tests {
 
         // Very precise percentages
         let precise_tg = TalkgroupStats {
-            talkgroup_id: 12345,
+            talkgroup_id: TalkgroupId::new(12345).unwrap(),
             talkgroup_label: Some("Precise TG".to_string()),
             talkgroup_group: Some("Precision Group".to_string()),
             call_count: 1,
@@ -1623,7 +2510,7 @@ mod tests {
         assert!(!debug_str.is_empty());
 
         let response = SystemStatsResponse {
-            system_id: "debug_test".to_string(),
+            system_id: SystemId::new("debug_test").unwrap(),
             system_label: Some("Debug Test System".to_string()),
             call_counts: CallCounts {
                 total_calls: 100,
@@ -1639,6 +2526,7 @@ mod tests {
                 days_active: Some(1),
                 activity_status: ActivityStatus::Active,
             },
+            storage: SystemStorage::new(SystemId::new("metro").unwrap(), 0, None),
             top_talkgroups: None,
             upload_sources: None,
             hourly_distribution: None,
@@ -1669,7 +2557,7 @@ mod tests {
         assert!(!format!("{:?}", time_info).is_empty());
 
         let talkgroup = TalkgroupStats {
-            talkgroup_id: 123,
+            talkgroup_id: TalkgroupId::new(123).unwrap(),
             talkgroup_label: Some("Debug TG".to_string()),
             talkgroup_group: None,
             call_count: 10,
@@ -1678,4 +2566,240 @@ mod tests {
         };
         assert!(!format!("{:?}", talkgroup).is_empty());
     }
+
+    fn growth_row(
+        system_id: &str,
+        day: u32,
+        bytes_added: i64,
+    ) -> sdrtrunk_storage::DailyStorageGrowth {
+        sdrtrunk_storage::DailyStorageGrowth {
+            system_id: SystemId::new(system_id).unwrap(),
+            day: chrono::NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            bytes_added,
+            call_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_storage_growth_query_validation() {
+        assert!(StorageGrowthQuery { days: None }.validate().is_ok());
+        assert!(StorageGrowthQuery { days: Some(30) }.validate().is_ok());
+        assert!(StorageGrowthQuery { days: Some(0) }.validate().is_err());
+        assert!(StorageGrowthQuery { days: Some(366) }.validate().is_err());
+    }
+
+    #[test]
+    fn test_aggregate_daily_growth() {
+        let rows = vec![
+            growth_row("a", 1, 100),
+            growth_row("b", 1, 50),
+            growth_row("a", 2, 25),
+        ];
+
+        let daily = aggregate_daily_growth(&rows);
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].date.to_string(), "2024-01-01");
+        assert_eq!(daily[0].bytes_added, 150);
+        assert_eq!(daily[0].call_count, 2);
+        assert_eq!(daily[1].bytes_added, 25);
+    }
+
+    #[test]
+    fn test_aggregate_system_growth() {
+        let rows = vec![
+            growth_row("a", 1, 100),
+            growth_row("b", 1, 500),
+            growth_row("a", 2, 300),
+        ];
+
+        let systems = aggregate_system_growth(&rows, 2);
+        assert_eq!(systems.len(), 2);
+        assert_eq!(systems[0].system_id.as_str(), "b");
+        assert_eq!(systems[0].bytes_added, 500);
+        assert_eq!(systems[1].system_id.as_str(), "a");
+        assert_eq!(systems[1].avg_bytes_per_day, 200.0);
+    }
+
+    #[test]
+    fn test_project_storage() {
+        let projection = project_storage(1_000, 400, 60.0, 14);
+        assert_eq!(projection.remaining_bytes, 600);
+        assert_eq!(projection.used_percent, 40.0);
+        assert_eq!(projection.days_until_full, Some(10.0));
+        assert!(projection.estimated_full_at.is_some());
+        assert!(projection.low_headroom);
+
+        let projection = project_storage(1_000, 400, 10.0, 14);
+        assert_eq!(projection.days_until_full, Some(60.0));
+        assert!(!projection.low_headroom);
+    }
+
+    #[test]
+    fn test_project_storage_no_growth_or_full() {
+        let idle = project_storage(1_000, 100, 0.0, 14);
+        assert!(idle.days_until_full.is_none());
+        assert!(idle.estimated_full_at.is_none());
+        assert!(!idle.low_headroom);
+
+        let full = project_storage(1_000, 2_000, 10.0, 14);
+        assert_eq!(full.remaining_bytes, 0);
+        assert_eq!(full.used_percent, 100.0);
+        assert_eq!(full.days_until_full, Some(0.0));
+        assert!(full.low_headroom);
+    }
+
+    fn language_row(
+        system_id: &str,
+        talkgroup_id: i32,
+        language: &str,
+        count: i64,
+    ) -> sdrtrunk_storage::LanguageStatsRow {
+        sdrtrunk_storage::LanguageStatsRow {
+            system_id: SystemId::new(system_id).unwrap(),
+            talkgroup_id: Some(TalkgroupId::new(talkgroup_id).unwrap()),
+            language: language.to_string(),
+            day: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            call_count: count,
+        }
+    }
+
+    #[test]
+    fn test_language_stats_query_validation() {
+        let query = LanguageStatsQuery {
+            system_id: Some(SystemId::new("sys").unwrap()),
+            talkgroup_id: None,
+            days: Some(7),
+            include_talkgroups: None,
+        };
+        assert!(query.validate().is_ok());
+
+        let query = LanguageStatsQuery {
+            system_id: None,
+            talkgroup_id: None,
+            days: Some(400),
+            include_talkgroups: None,
+        };
+        assert!(query.validate().is_err());
+
+        // Empty system IDs and non-positive talkgroups are rejected when parsed
+        for uri in [
+            "/api/stats/languages?system_id=",
+            "/api/stats/languages?talkgroup_id=0",
+            "/api/stats/languages?talkgroup_id=fire",
+        ] {
+            let uri: axum::http::Uri = uri.parse().unwrap();
+            assert!(Query::<LanguageStatsQuery>::try_from_uri(&uri).is_err());
+        }
+    }
+
+    #[test]
+    fn test_language_distribution() {
+        let rows = vec![
+            language_row("a", 1, "en", 6),
+            language_row("a", 2, "es", 2),
+            language_row("b", 1, "en", 2),
+        ];
+
+        let languages = language_distribution(rows.iter());
+        assert_eq!(languages.len(), 2);
+        assert_eq!(languages[0].language, "en");
+        assert_eq!(languages[0].call_count, 8);
+        assert_eq!(languages[0].percentage, 80.0);
+        assert_eq!(languages[1].language, "es");
+        assert_eq!(languages[1].percentage, 20.0);
+
+        assert!(language_distribution(std::iter::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_talkgroup_activity_query_validation() {
+        let uri: axum::http::Uri = "/api/stats/talkgroups?system_id=metro&hours=168&limit=10"
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<TalkgroupActivityQuery>::try_from_uri(&uri).unwrap();
+        assert!(query.validate().is_ok());
+
+        for uri in [
+            "/api/stats/talkgroups?hours=0",
+            "/api/stats/talkgroups?hours=9000",
+            "/api/stats/talkgroups?limit=0",
+            "/api/stats/talkgroups?limit=501",
+        ] {
+            let uri: axum::http::Uri = uri.parse().unwrap();
+            let Query(query) = Query::<TalkgroupActivityQuery>::try_from_uri(&uri).unwrap();
+            assert!(query.validate().is_err(), "{uri} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_talkgroup_activity_from_row() {
+        let now = chrono::Utc::now();
+        let activity = TalkgroupActivity::from(sdrtrunk_storage::TalkgroupActivityRow {
+            system_id: SystemId::new("metro").unwrap(),
+            talkgroup_id: TalkgroupId::new(101).unwrap(),
+            talkgroup_label: Some("Dispatch".to_string()),
+            call_count: 4,
+            airtime_seconds: 50.0,
+            first_call: now,
+            last_call: now,
+            busiest_hours: vec![sdrtrunk_storage::TalkgroupHourCount {
+                hour: 17,
+                call_count: 3,
+            }],
+        });
+        assert_eq!(activity.avg_duration_seconds, 12.5);
+        assert_eq!(activity.busiest_hours[0].hour, 17);
+        assert_eq!(activity.busiest_hours[0].call_count, 3);
+    }
+
+    #[test]
+    fn test_build_frequency_usage() {
+        let usage = |frequency_hz, call_count, airtime_seconds| FrequencyUsage {
+            frequency_hz,
+            call_count,
+            airtime_seconds,
+            error_count: 0,
+            spike_count: 0,
+            systems: vec![SystemId::new("metro").unwrap()],
+            last_seen: chrono::Utc::now(),
+        };
+        let report = build_frequency_usage(
+            1,
+            vec![usage(851_012_500, 10, 8_640.0), usage(851_037_500, 2, 10.0)],
+            1,
+        );
+
+        assert_eq!(report.total_frequencies, 2);
+        assert_eq!(report.frequencies.len(), 1);
+        assert_eq!(report.frequencies[0].frequency_mhz, 851.0125);
+        assert_eq!(report.frequencies[0].utilization_percent, 10.0);
+
+        let uri: axum::http::Uri = "/api/stats/frequencies?days=400".parse().unwrap();
+        let Query(query) = Query::<FrequencyUsageQuery>::try_from_uri(&uri).unwrap();
+        assert!(query.validate().is_err());
+    }
+
+    #[test]
+    fn test_build_language_stats() {
+        let rows = vec![
+            language_row("a", 1, "en", 3),
+            language_row("a", 2, "es", 1),
+            language_row("b", 1, "es", 4),
+        ];
+
+        let stats = build_language_stats(30, &rows, false);
+        assert_eq!(stats.total_calls, 8);
+        assert_eq!(stats.languages[0].language, "es");
+        assert_eq!(stats.systems.len(), 2);
+        assert_eq!(stats.systems[0].system_id.as_str(), "a");
+        assert_eq!(stats.systems[0].languages[0].language, "en");
+        assert!(stats.talkgroups.is_none());
+        assert_eq!(stats.timeline.len(), 2);
+
+        let stats = build_language_stats(30, &rows, true);
+        let talkgroups = stats.talkgroups.unwrap();
+        assert_eq!(talkgroups.len(), 3);
+        assert_eq!(talkgroups[1].talkgroup_id.map(TalkgroupId::as_i32), Some(2));
+        assert_eq!(talkgroups[1].languages[0].language, "es");
+    }
 }
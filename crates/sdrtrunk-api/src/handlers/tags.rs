@@ -0,0 +1,340 @@
+//! Call tag and note handlers
+//!
+//! Analysts tag calls (e.g. `fire`, `mutual-aid`) with an optional free-text
+//! note per tag; `GET /api/calls?tag=` lists the calls carrying a tag.
+
+use crate::{
+    error::{ApiError, ErrorResponse},
+    handlers::feedback::scoped_call,
+    state::AppState,
+    tenant::TenantScope,
+};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use sdrtrunk_storage::{CallTag, NewCallTag, TagQueries, models::ApiKeyDb};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Longest accepted tag, in characters
+const MAX_TAG_CHARS: usize = 32;
+/// Longest accepted note, in characters
+const MAX_NOTE_CHARS: usize = 2_000;
+
+/// A tag to attach to a call
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagCallRequest {
+    /// Tag: ASCII letters, digits, `-` and `_`, up to 32 characters; stored
+    /// lowercase
+    pub tag: String,
+    /// Free-text note on the tag, up to 2000 characters; replaces any note
+    /// the tag already has
+    pub note: Option<String>,
+}
+
+impl TagCallRequest {
+    /// Validate the request into the tag to store on `call_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid field
+    pub fn into_tag(self, call_id: Uuid, api_key_id: Option<String>) -> Result<NewCallTag, String> {
+        let tag = normalize_tag(&self.tag)?;
+        let note = self
+            .note
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+        if note
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS)
+        {
+            return Err(format!("note must be at most {MAX_NOTE_CHARS} characters"));
+        }
+
+        Ok(NewCallTag {
+            call_id,
+            tag,
+            note,
+            api_key_id,
+        })
+    }
+}
+
+/// A tag as stored: trimmed and lowercased
+///
+/// # Errors
+///
+/// Returns a message if the tag is empty, too long, or has characters other
+/// than ASCII letters, digits, `-` and `_`
+pub fn normalize_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err("tag is required".to_string());
+    }
+    if tag.chars().count() > MAX_TAG_CHARS {
+        return Err(format!("tag must be at most {MAX_TAG_CHARS} characters"));
+    }
+    if !tag
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("tag may only contain letters, digits, '-' and '_'".to_string());
+    }
+    Ok(tag)
+}
+
+/// A tag on a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallTagInfo {
+    /// Tag, lowercase
+    pub tag: String,
+    /// Free-text note on the tag
+    pub note: Option<String>,
+    /// API key the tag was last set with
+    pub api_key_id: Option<String>,
+    /// When the tag was first attached
+    pub created_at: DateTime<Utc>,
+    /// When the tag was last set
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CallTag> for CallTagInfo {
+    fn from(tag: CallTag) -> Self {
+        Self {
+            tag: tag.tag,
+            note: tag.note,
+            api_key_id: tag.api_key_id,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
+
+/// A tag attached to a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagCallResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Call ID
+    pub call_id: Uuid,
+    /// The stored tag
+    pub tag: CallTagInfo,
+}
+
+/// All tags on a call
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CallTagsResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Call ID
+    pub call_id: Uuid,
+    /// Tags in alphabetical order
+    pub tags: Vec<CallTagInfo>,
+}
+
+/// List the tags on a call with their notes
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist or is outside the API key's systems
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+#[utoipa::path(
+    get,
+    path = "/api/calls/{id}/tags",
+    tag = "Calls",
+    summary = "List call tags",
+    description = "Tags on the call with their notes, in alphabetical order.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    responses(
+        (status = 200, description = "The call's tags", body = CallTagsResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security((), ("ApiKeyAuth" = [])),
+)]
+pub async fn list_call_tags(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(call_id): Path<Uuid>,
+) -> Result<Json<CallTagsResponse>, ApiError> {
+    let call = scoped_call(&state, &scope, call_id).await?;
+    match TagQueries::list_for_call(&state.pool, call.id).await {
+        Ok(tags) => Ok(Json(CallTagsResponse {
+            success: true,
+            call_id,
+            tags: tags.into_iter().map(CallTagInfo::from).collect(),
+        })),
+        Err(e) => {
+            error!("Failed to list tags of call {call_id}: {e}");
+            Err(ApiError::database("Failed to list tags"))
+        }
+    }
+}
+
+/// Attach a tag, with an optional note, to a call
+///
+/// Tagging a call again with the same tag replaces its note.
+///
+/// # Errors
+///
+/// * `BAD_REQUEST` - Invalid tag or note
+/// * `NOT_FOUND` - Call does not exist or is outside the API key's systems
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+///
+/// # Example
+///
+/// ```text
+/// POST /api/calls/550e8400-e29b-41d4-a716-446655440000/tags
+/// {"tag": "fire", "note": "Second alarm on Main St"}
+/// ```
+#[utoipa::path(
+    post,
+    path = "/api/calls/{id}/tags",
+    tag = "Calls",
+    summary = "Tag a call",
+    description = "Attach a tag with an optional free-text note to the call (analyst or admin). Tags are stored lowercase; tagging a call again with the same tag replaces its note.",
+    params(("id" = Uuid, Path, description = "Call UUID")),
+    request_body = TagCallRequest,
+    responses(
+        (status = 201, description = "Tag stored", body = TagCallResponse),
+        (status = 400, description = "Invalid tag or note", body = ErrorResponse),
+        (status = 404, description = "Call not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn tag_call(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    api_key: Option<Extension<ApiKeyDb>>,
+    Path(call_id): Path<Uuid>,
+    Json(request): Json<TagCallRequest>,
+) -> Result<(StatusCode, Json<TagCallResponse>), ApiError> {
+    let call = scoped_call(&state, &scope, call_id).await?;
+    let tag = request
+        .into_tag(call.id, api_key.map(|Extension(key)| key.id))
+        .map_err(|e| ApiError::bad_request("INVALID_TAG", e))?;
+
+    match TagQueries::upsert(&state.pool, &tag).await {
+        Ok(tag) => {
+            info!("Call {call_id} tagged '{}'", tag.tag);
+            Ok((
+                StatusCode::CREATED,
+                Json(TagCallResponse {
+                    success: true,
+                    call_id,
+                    tag: tag.into(),
+                }),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to tag call {call_id}: {e}");
+            Err(ApiError::database("Failed to store tag"))
+        }
+    }
+}
+
+/// Remove a tag and its note from a call
+///
+/// # Errors
+///
+/// * `NOT_FOUND` - Call does not exist, is outside the API key's systems, or
+///   does not carry the tag
+/// * `INTERNAL_SERVER_ERROR` - Database query failures
+#[utoipa::path(
+    delete,
+    path = "/api/calls/{id}/tags/{tag}",
+    tag = "Calls",
+    summary = "Remove a call tag",
+    description = "Remove a tag and its note from the call (analyst or admin).",
+    params(
+        ("id" = Uuid, Path, description = "Call UUID"),
+        ("tag" = String, Path, description = "Tag to remove"),
+    ),
+    responses(
+        (status = 204, description = "Tag removed"),
+        (status = 404, description = "Call not found or not tagged", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    ),
+    security(("ApiKeyAuth" = [])),
+)]
+pub async fn untag_call(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path((call_id, tag)): Path<(Uuid, String)>,
+) -> Result<StatusCode, ApiError> {
+    let call = scoped_call(&state, &scope, call_id).await?;
+    let tag = tag.trim().to_lowercase();
+
+    match TagQueries::delete(&state.pool, call.id, &tag).await {
+        Ok(true) => {
+            info!("Tag '{tag}' removed from call {call_id}");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(ApiError::not_found(
+            "TAG_NOT_FOUND",
+            format!("Call {call_id} is not tagged '{tag}'"),
+        )),
+        Err(e) => {
+            error!("Failed to remove tag from call {call_id}: {e}");
+            Err(ApiError::database("Failed to remove tag"))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> TagCallRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_into_tag() {
+        let call_id = Uuid::new_v4();
+        let tag = request(serde_json::json!({"tag": " Mutual-Aid ", "note": " Second alarm "}))
+            .into_tag(call_id, Some("key-1".to_string()))
+            .unwrap();
+        assert_eq!(tag.call_id, call_id);
+        assert_eq!(tag.tag, "mutual-aid");
+        assert_eq!(tag.note.as_deref(), Some("Second alarm"));
+        assert_eq!(tag.api_key_id.as_deref(), Some("key-1"));
+
+        let tag = request(serde_json::json!({"tag": "fire", "note": "  "}))
+            .into_tag(call_id, None)
+            .unwrap();
+        assert!(tag.note.is_none());
+    }
+
+    #[test]
+    fn test_into_tag_validation() {
+        for json in [
+            serde_json::json!({"tag": ""}),
+            serde_json::json!({"tag": "   "}),
+            serde_json::json!({"tag": "two words"}),
+            serde_json::json!({"tag": "<b>"}),
+            serde_json::json!({"tag": "x".repeat(MAX_TAG_CHARS + 1)}),
+            serde_json::json!({"tag": "fire", "note": "x".repeat(MAX_NOTE_CHARS + 1)}),
+        ] {
+            assert!(
+                request(json.clone()).into_tag(Uuid::nil(), None).is_err(),
+                "{json} should be rejected"
+            );
+        }
+    }
+}
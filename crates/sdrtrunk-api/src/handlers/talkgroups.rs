@@ -0,0 +1,183 @@
+//! Talkgroup alias handlers
+//!
+//! Imports friendly talkgroup names from an `SDRTrunk` playlist or a
+//! `RadioReference` CSV export so calls show names instead of raw IDs, and
+//! lists the names known for a system.
+
+use crate::{error::ApiError, state::AppState, tenant::TenantScope};
+use axum::{
+    Json,
+    extract::{Multipart, Path, State},
+};
+use sdrtrunk_protocol::talkgroups::{self, ImportFormat};
+use sdrtrunk_storage::{Talkgroup, TalkgroupQueries};
+use sdrtrunk_types::SystemId;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Result of a talkgroup import
+#[derive(Debug, Serialize)]
+pub struct TalkgroupImportResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// System the talkgroups were imported for
+    pub system_id: SystemId,
+    /// Detected or requested file format
+    pub format: ImportFormat,
+    /// Talkgroups written
+    pub imported: u64,
+    /// Entries in the file that were ignored
+    pub skipped: usize,
+    /// Existing calls whose missing names were filled in
+    pub calls_updated: u64,
+}
+
+/// Talkgroups known for a system
+#[derive(Debug, Serialize)]
+pub struct TalkgroupListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Talkgroups ordered by ID
+    pub talkgroups: Vec<Talkgroup>,
+}
+
+/// Fields of an import request
+#[derive(Debug, Default)]
+struct ImportForm {
+    system_id: Option<String>,
+    format: Option<String>,
+    alias_list: Option<String>,
+    filename: Option<String>,
+    content: Option<String>,
+}
+
+/// Import talkgroup names for a system
+///
+/// Multipart fields: `system` (required), `file` (playlist XML or
+/// `RadioReference` CSV), optional `format` (`playlist` or
+/// `radioreference_csv`, detected from the file when omitted), and optional
+/// `alias_list` to import a single playlist alias list.
+///
+/// # Errors
+///
+/// Returns error if the form or file is invalid or a database query fails
+pub async fn import_talkgroups(
+    State(state): State<Arc<AppState>>,
+    multipart: Multipart,
+) -> Result<Json<TalkgroupImportResponse>, ApiError> {
+    let form = read_form(multipart).await?;
+
+    let system_id = SystemId::new(form.system_id.unwrap_or_default())
+        .map_err(|e| ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid system: {e}")))?;
+    let content = form
+        .content
+        .ok_or_else(|| ApiError::bad_request("INVALID_PARAMETERS", "Missing file field"))?;
+    let format = match form.format.as_deref() {
+        None | Some("") => ImportFormat::detect(form.filename.as_deref(), &content),
+        Some("playlist") => ImportFormat::Playlist,
+        Some("radioreference_csv" | "csv") => ImportFormat::RadioReferenceCsv,
+        Some(other) => {
+            return Err(ApiError::bad_request(
+                "INVALID_PARAMETERS",
+                format!("Unknown format '{other}'"),
+            ));
+        }
+    };
+
+    let parsed = talkgroups::parse(format, &content, form.alias_list.as_deref())
+        .map_err(|e| ApiError::bad_request("INVALID_PARAMETERS", e.to_string()))?;
+    if parsed.aliases.is_empty() {
+        return Err(ApiError::bad_request(
+            "INVALID_PARAMETERS",
+            "File contains no talkgroups",
+        ));
+    }
+
+    let database_error = |e: sdrtrunk_storage::StorageError| {
+        error!("Talkgroup import for {system_id} failed: {e}");
+        ApiError::database(format!("Talkgroup import failed: {e}"))
+    };
+    let imported =
+        TalkgroupQueries::upsert_many(&state.pool, &system_id, &parsed.aliases, format.as_str())
+            .await
+            .map_err(database_error)?;
+    let calls_updated = TalkgroupQueries::apply_to_calls(&state.pool, &system_id)
+        .await
+        .map_err(database_error)?;
+
+    info!(
+        "Imported {imported} talkgroups for {system_id} from {} ({} skipped, {calls_updated} calls updated)",
+        format.as_str(),
+        parsed.skipped
+    );
+    Ok(Json(TalkgroupImportResponse {
+        success: true,
+        system_id,
+        format,
+        imported,
+        skipped: parsed.skipped,
+        calls_updated,
+    }))
+}
+
+/// List the talkgroup names known for a system
+///
+/// # Errors
+///
+/// Returns error if the API key may not access the system or the database
+/// query fails
+pub async fn list_talkgroups(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Path(system_id): Path<SystemId>,
+) -> Result<Json<TalkgroupListResponse>, ApiError> {
+    if !scope.allows(&system_id) {
+        return Err(ApiError::forbidden(
+            "SYSTEM_NOT_ALLOWED",
+            format!("API key may not access system {system_id}"),
+        ));
+    }
+    match TalkgroupQueries::list(&state.pool, &system_id).await {
+        Ok(talkgroups) => Ok(Json(TalkgroupListResponse {
+            success: true,
+            talkgroups,
+        })),
+        Err(e) => {
+            error!("Failed to list talkgroups for {system_id}: {e}");
+            Err(ApiError::database(format!(
+                "Failed to list talkgroups: {e}"
+            )))
+        }
+    }
+}
+
+/// Collect the import form fields
+///
+/// # Errors
+///
+/// Returns error if the multipart body or a field is malformed
+async fn read_form(mut multipart: Multipart) -> Result<ImportForm, ApiError> {
+    let mut form = ImportForm::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid multipart body: {e}"))
+    })? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            form.filename = field.file_name().map(String::from);
+        }
+        let text = field.text().await.map_err(|e| {
+            ApiError::bad_request("INVALID_PARAMETERS", format!("Invalid {name} field: {e}"))
+        })?;
+        match name.as_str() {
+            "system" => form.system_id = Some(text.trim().to_string()),
+            "format" => form.format = Some(text.trim().to_string()),
+            "alias_list" => {
+                form.alias_list = Some(text.trim().to_string()).filter(|l| !l.is_empty());
+            }
+            "file" => form.content = Some(text),
+            _ => {}
+        }
+    }
+    Ok(form)
+}
@@ -1,15 +1,32 @@
-//! Transcription webhook callback handler
+//! Transcription webhook callback and batch re-transcription handlers
 
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::config::EncryptedField;
+use sdrtrunk_types::{SystemId, TalkgroupId, TranscriptionStatus};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::state::AppState;
+use crate::{encryption::ColumnEncryption, error::ApiError, state::AppState, tenant::TenantScope};
 use sdrtrunk_storage::queries::{RadioCallQueries, TranscriptionUpdate};
+use sdrtrunk_storage::{JobQueue, RetryFilter, SegmentQueries, TranscriptionSegment};
 use std::sync::Arc;
 
+/// Call statuses that can be selected for re-transcription
+const RETRYABLE_STATUSES: [&str; 6] = [
+    "failed",
+    "completed",
+    "needs_review",
+    "pending",
+    "skipped",
+    "deferred",
+];
+
+/// Priority for re-queued jobs; below zero so new uploads are claimed first
+const RETRY_PRIORITY: i32 = -1;
+
 /// Webhook callback payload from `WhisperX` service
 #[derive(Debug, Deserialize)]
 pub struct TranscriptionCallback {
@@ -58,31 +75,48 @@ pub struct CallbackResponse {
 )]
 pub async fn transcription_callback(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<TranscriptionCallback>,
+    Json(mut payload): Json<TranscriptionCallback>,
 ) -> impl IntoResponse {
     info!(
         "Received transcription callback for call {} with status: {}",
         payload.call_id, payload.status
     );
 
-    // Determine database status
-    let db_status = match payload.status.as_str() {
-        "completed" => "completed",
-        "failed" => "failed",
-        _ => {
-            warn!("Unknown transcription status: {}", payload.status);
-            "failed"
+    let db_status = callback_status(&state, &payload);
+    let raw_text = match anonymize(&state, &mut payload) {
+        Ok(raw_text) => raw_text,
+        Err(e) => {
+            error!(
+                "Failed to anonymize transcription for call {}: {}",
+                payload.call_id, e
+            );
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(CallbackResponse {
+                    status: "error".to_string(),
+                    message: format!("Failed to anonymize transcription: {e}"),
+                }),
+            );
         }
     };
-
-    // Prepare speaker segments JSON if present
-    let speaker_segments_json = payload.speaker_segments.as_ref().and_then(|segments| {
-        if segments.is_empty() {
-            None
-        } else {
-            Some(serde_json::Value::Array(segments.clone()))
+    let text_encrypted = match seal_text(&state, &payload) {
+        Ok(sealed) => sealed,
+        Err(e) => {
+            error!(
+                "Failed to encrypt transcription for call {}: {}",
+                payload.call_id, e
+            );
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(CallbackResponse {
+                    status: "error".to_string(),
+                    message: format!("Failed to encrypt transcription: {e}"),
+                }),
+            );
         }
-    });
+    };
+
+    let speaker_segments_json = speaker_segments(&payload, text_encrypted.is_some());
 
     // Update database with transcription result
     let update_result = RadioCallQueries::update_transcription_status(
@@ -90,11 +124,14 @@ pub async fn transcription_callback(
         TranscriptionUpdate {
             id: payload.call_id,
             status: db_status,
-            text: payload.text.as_deref(),
+            text: payload.text.as_deref().filter(|_| text_encrypted.is_none()),
             confidence: payload.confidence,
             error: payload.error.as_deref(),
             speaker_segments: speaker_segments_json.as_ref(),
             speaker_count: payload.speaker_count.map(|c| c as i32),
+            language: payload.language.as_deref(),
+            raw_text: raw_text.as_deref(),
+            text_encrypted: text_encrypted.as_deref(),
         },
     )
     .await;
@@ -105,9 +142,14 @@ pub async fn transcription_callback(
                 "Successfully updated transcription for call {} in database",
                 payload.call_id
             );
+            store_segments(&state, &payload).await;
+            state
+                .stats_cache
+                .invalidate_call(&state.pool, payload.call_id)
+                .await;
 
-            // Log transcription summary
-            if let Some(text) = &payload.text {
+            // Log transcription summary, unless it is encrypted at rest
+            if let Some(text) = payload.text.as_ref().filter(|_| text_encrypted.is_none()) {
                 let preview = if text.len() > 100 {
                     format!("{}...", &text[..100])
                 } else {
@@ -148,6 +190,118 @@ pub async fn transcription_callback(
     }
 }
 
+/// Database status for a callback
+///
+/// Completed transcriptions scoring below `transcription.min_confidence` are
+/// flagged `needs_review`.
+fn callback_status(state: &AppState, payload: &TranscriptionCallback) -> &'static str {
+    let min_confidence = state
+        .config
+        .transcription
+        .as_ref()
+        .and_then(|transcription| transcription.min_confidence);
+    match payload.status.as_str() {
+        "completed" => TranscriptionStatus::finished(payload.confidence, min_confidence).as_str(),
+        "failed" => "failed",
+        _ => {
+            warn!("Unknown transcription status: {}", payload.status);
+            "failed"
+        }
+    }
+}
+
+/// Scrub personal details from a callback when anonymization is on
+///
+/// Replaces the text and the text of every segment, returning the encrypted
+/// original text when one is kept.
+fn anonymize(
+    state: &AppState,
+    payload: &mut TranscriptionCallback,
+) -> sdrtrunk_storage::Result<Option<Vec<u8>>> {
+    let Some(redactor) = state.redactor.as_deref() else {
+        return Ok(None);
+    };
+    let segments = payload
+        .segments
+        .iter_mut()
+        .chain(payload.speaker_segments.iter_mut())
+        .flatten();
+    for segment in segments {
+        if let Some(serde_json::Value::String(text)) = segment.get_mut("text") {
+            *text = redactor.scrub(text).into_owned();
+        }
+    }
+    let Some(text) = payload.text.as_mut() else {
+        return Ok(None);
+    };
+    let redacted = redactor.redact(text)?;
+    *text = redacted.text;
+    Ok(redacted.raw)
+}
+
+/// Seal the text of a callback when transcripts are encrypted at rest
+fn seal_text(
+    state: &AppState,
+    payload: &TranscriptionCallback,
+) -> sdrtrunk_storage::Result<Option<Vec<u8>>> {
+    let Some(encryption) = state
+        .encryption
+        .as_deref()
+        .filter(|encryption| encryption.encrypts(EncryptedField::TranscriptionText))
+    else {
+        return Ok(None);
+    };
+    payload
+        .text
+        .as_deref()
+        .map(|text| encryption.seal(text))
+        .transpose()
+}
+
+/// Speaker segments of a callback as stored with the call, if any
+///
+/// When the transcript is sealed, only the timing and speaker of each
+/// segment are kept; its text stays with the sealed transcript segments.
+fn speaker_segments(payload: &TranscriptionCallback, sealed: bool) -> Option<serde_json::Value> {
+    let segments = payload
+        .speaker_segments
+        .as_ref()
+        .filter(|segments| !segments.is_empty())?;
+    let mut segments = segments.clone();
+    if sealed {
+        for segment in &mut segments {
+            if let Some(fields) = segment.as_object_mut() {
+                let _ = fields.remove("text");
+            }
+        }
+    }
+    Some(serde_json::Value::Array(segments))
+}
+
+/// Store the timed segments of a completed transcription for subtitle export
+///
+/// Segment text is sealed when transcripts are encrypted at rest. Failures
+/// are logged; the transcript itself has already been stored.
+async fn store_segments(state: &AppState, payload: &TranscriptionCallback) {
+    let Some(segments) = payload.segments.as_deref() else {
+        return;
+    };
+    if payload.status != "completed" {
+        return;
+    }
+    let segments = TranscriptionSegment::parse_all(segments);
+    let keys = state
+        .encryption
+        .as_deref()
+        .and_then(ColumnEncryption::transcript_keys);
+    if let Err(e) = SegmentQueries::replace(&state.pool, payload.call_id, &segments, keys).await {
+        warn!(
+            "Failed to store transcript segments for call {}: {e}",
+            payload.call_id
+        );
+    }
+}
+
 /// Health check endpoint for transcription service
 #[allow(clippy::unused_async)]
 pub async fn transcription_health() -> impl IntoResponse {
@@ -157,3 +311,227 @@ pub async fn transcription_health() -> impl IntoResponse {
         "version": "1.0.0"
     }))
 }
+
+/// Filters for re-queueing calls for transcription
+#[derive(Debug, Deserialize)]
+pub struct RetryRequest {
+    /// Only calls from this system
+    pub system_id: Option<SystemId>,
+    /// Only calls on this talkgroup
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only calls with this transcription status; `all` matches any status
+    #[serde(default = "default_retry_status")]
+    pub status: String,
+    /// Only calls at or after this time
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only calls before this time
+    pub to_date: Option<DateTime<Utc>>,
+    /// Re-queue at most this many calls, newest first
+    pub limit: Option<i64>,
+    /// Count matching calls without queueing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_retry_status() -> String {
+    "failed".to_string()
+}
+
+impl RetryRequest {
+    /// Validate the request and convert it to a storage filter
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid field
+    pub fn to_filter(&self) -> Result<RetryFilter, String> {
+        let status = match self.status.as_str() {
+            "all" => None,
+            status if RETRYABLE_STATUSES.contains(&status) => Some(status.to_string()),
+            other => {
+                return Err(format!(
+                    "Invalid status '{other}'; expected all, {}",
+                    RETRYABLE_STATUSES.join(", ")
+                ));
+            }
+        };
+        if let (Some(from), Some(to)) = (self.from_date, self.to_date)
+            && from >= to
+        {
+            return Err("from_date must be before to_date".to_string());
+        }
+        if self.limit.is_some_and(|limit| limit < 1) {
+            return Err("limit must be at least 1".to_string());
+        }
+
+        Ok(RetryFilter {
+            system_id: self.system_id.clone(),
+            talkgroup_id: self.talkgroup_id,
+            status,
+            from_date: self.from_date,
+            to_date: self.to_date,
+            limit: self.limit,
+        })
+    }
+}
+
+/// Result of a batch re-transcription request
+#[derive(Debug, Serialize)]
+pub struct RetryResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Whether this was a dry run
+    pub dry_run: bool,
+    /// Calls queued, or that would be queued on a dry run
+    pub count: u64,
+}
+
+/// Re-queue matching calls for transcription
+///
+/// Calls that already have a pending or processing job are skipped. With
+/// `dry_run` set, only counts the calls that would be queued. API keys limited
+/// to specific systems must name one of them in `system_id`.
+///
+/// # Errors
+///
+/// Returns error if the filters are invalid, the API key may not access the
+/// system, or the database query fails
+pub async fn retry_transcriptions(
+    State(state): State<Arc<AppState>>,
+    scope: TenantScope,
+    Json(request): Json<RetryRequest>,
+) -> Result<Json<RetryResponse>, ApiError> {
+    let filter = request
+        .to_filter()
+        .map_err(|error| ApiError::bad_request("INVALID_PARAMETERS", error))?;
+    if !scope.covers(filter.system_id.as_ref()) {
+        return Err(ApiError::forbidden(
+            "SYSTEM_NOT_ALLOWED",
+            "API key may only retry calls from its allowed systems; set system_id",
+        ));
+    }
+
+    let result = if request.dry_run {
+        JobQueue::count_retryable(&state.pool, &filter)
+            .await
+            .map(|count| u64::try_from(count).unwrap_or(0))
+    } else {
+        let timeout_seconds = state
+            .config
+            .transcription
+            .as_ref()
+            .map_or(300, |t| i32::try_from(t.timeout_seconds).unwrap_or(300));
+        JobQueue::retry_calls(
+            &state.pool,
+            &filter,
+            RETRY_PRIORITY,
+            &json!({"diarize": true}),
+            timeout_seconds,
+        )
+        .await
+    };
+
+    match result {
+        Ok(count) => {
+            if !request.dry_run {
+                info!("Re-queued {count} calls for transcription ({filter:?})");
+            }
+            Ok(Json(RetryResponse {
+                success: true,
+                dry_run: request.dry_run,
+                count,
+            }))
+        }
+        Err(e) => {
+            error!("Batch re-transcription failed: {e}");
+            Err(ApiError::database(format!(
+                "Batch re-transcription failed: {e}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn request(body: serde_json::Value) -> RetryRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn test_retry_request_defaults_to_failed() {
+        let req = request(json!({}));
+        assert!(!req.dry_run);
+
+        let filter = req.to_filter().unwrap();
+        assert_eq!(filter.status.as_deref(), Some("failed"));
+        assert!(filter.system_id.is_none());
+        assert!(filter.limit.is_none());
+    }
+
+    #[test]
+    fn test_retry_request_filters() {
+        let filter = request(json!({
+            "system_id": "metro",
+            "talkgroup_id": 1001,
+            "status": "all",
+            "from_date": "2024-01-01T00:00:00Z",
+            "to_date": "2024-02-01T00:00:00Z",
+            "limit": 50,
+            "dry_run": true
+        }))
+        .to_filter()
+        .unwrap();
+
+        assert_eq!(filter.system_id.unwrap().as_str(), "metro");
+        assert_eq!(filter.talkgroup_id.unwrap().as_i32(), 1001);
+        assert!(filter.status.is_none());
+        assert_eq!(filter.limit, Some(50));
+    }
+
+    #[test]
+    fn test_retry_request_rejects_invalid() {
+        for body in [
+            json!({"status": "processing"}),
+            json!({"limit": 0}),
+            json!({
+                "from_date": "2024-02-01T00:00:00Z",
+                "to_date": "2024-01-01T00:00:00Z"
+            }),
+        ] {
+            assert!(request(body).to_filter().is_err());
+        }
+    }
+
+    #[test]
+    fn test_speaker_segments_drop_text_when_sealed() {
+        let payload: TranscriptionCallback = serde_json::from_value(json!({
+            "request_id": Uuid::new_v4(),
+            "call_id": Uuid::new_v4(),
+            "status": "completed",
+            "processing_time_ms": 1200,
+            "speaker_segments": [
+                {"start": 0.0, "end": 1.5, "speaker": "SPEAKER_00", "text": "Engine 5 responding"}
+            ],
+            "completed_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        let plain = speaker_segments(&payload, false).unwrap();
+        assert_eq!(plain[0]["text"], "Engine 5 responding");
+
+        let sealed = speaker_segments(&payload, true).unwrap();
+        assert_eq!(
+            sealed,
+            json!([{"start": 0.0, "end": 1.5, "speaker": "SPEAKER_00"}])
+        );
+    }
+}
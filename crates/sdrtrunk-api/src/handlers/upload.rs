@@ -1,24 +1,130 @@
 //! File upload handler for Rdio-compatible call uploads
 
-use super::audio_utils;
-use crate::state::AppState;
+use super::{
+    admin::hash_api_key,
+    audio_utils,
+    websocket::{WebSocketEvent, broadcast_event},
+};
+use crate::{
+    error::{ApiError, ErrorResponse},
+    features, fingerprint,
+    middleware::{
+        auth::{Credential, lookup_key, record_ingest_use, record_key_usage},
+        rate_limit,
+    },
+    progress::publish_progress,
+    quotas,
+    resumable::{ResumableError, UploadInfo},
+    state::AppState,
+    tenant::TenantScope,
+    waveform, webhooks,
+};
 use axum::{
     body::Body,
     extract::{ConnectInfo, FromRequest, Multipart, State},
-    http::{HeaderMap, Request, StatusCode},
+    http::{HeaderMap, HeaderValue, Request, StatusCode, header},
     response::{IntoResponse, Json, Response},
 };
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sdrtrunk_storage::models::RadioCallDb;
-use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
+use sdrtrunk_protocol::config::{
+    BackPressureConfig, BackPressureMode, DuplicatePolicy, EncryptedField, GeoConfig, WebhookEvent,
+};
+use sdrtrunk_storage::{
+    CallEventKind, CallEventQueries, ConversationQueries, EncryptionQueries, IdempotencyClaim,
+    IdempotencyQueries, IngestKey, JobQueue, ProgressStage, QueueBacklog, RadioQueries,
+    TalkgroupQueries, UploadLogParams,
+    models::{ApiKeyDb, RadioCallDb},
+    queries::{AttachedAudio, RadioCallQueries},
+    recording_key,
+};
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId, TranscriptionStatus};
 use serde_json;
-use std::{net::SocketAddr, sync::Arc};
+use sha2::{Digest, Sha256};
+use std::{
+    net::{IpAddr, SocketAddr},
+    ops::ControlFlow,
+    sync::Arc,
+};
 use tracing::{error, info, warn};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Header reporting unfinished transcription jobs (pending plus in-flight)
+pub const QUEUE_DEPTH_HEADER: &str = "x-transcription-queue-depth";
+
+/// Header reporting the estimated seconds until the transcription backlog drains
+pub const BACKLOG_SECONDS_HEADER: &str = "x-transcription-backlog-seconds";
+
+/// Header naming the existing call when an upload duplicates it
+pub const DUPLICATE_OF_HEADER: &str = "x-duplicate-of";
+
+/// Header carrying a client-chosen key that makes retried uploads safe
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header marking a response replayed for a retried `Idempotency-Key`
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest accepted `Idempotency-Key`
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Multipart form fields accepted by the upload endpoints
+///
+/// Only used to document the request; the handler reads the form field by
+/// field so it can accept partial trunk-recorder metadata.
+#[derive(Debug, ToSchema)]
+#[schema(rename_all = "camelCase")]
+pub struct CallUploadRequest {
+    /// Audio file (MP3, WAV, FLAC)
+    #[schema(value_type = String, format = Binary)]
+    pub audio: Vec<u8>,
+    /// ID of a finished resumable upload (`/api/uploads`), sent instead of `audio`
+    pub upload_id: Option<Uuid>,
+    /// `true` to register the call now and send its audio in a later upload
+    pub audio_pending: Option<bool>,
+    /// Call registered with `audioPending` whose audio this upload carries
+    pub call_id: Option<Uuid>,
+    /// Upload API key
+    pub key: Option<String>,
+    /// System ID
+    pub system: Option<String>,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Call start as Unix seconds or RFC 3339
+    pub date_time: Option<String>,
+    /// Talkgroup ID
+    pub talkgroup: Option<i32>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Talkgroup group
+    pub talkgroup_group: Option<String>,
+    /// Talkgroup tag
+    pub talkgroup_tag: Option<String>,
+    /// Frequency in Hz
+    pub frequency: Option<i64>,
+    /// Source radio ID
+    pub source: Option<i32>,
+    /// Audio file name, if the audio part has none
+    pub audio_name: Option<String>,
+    /// Audio MIME type
+    pub audio_type: Option<String>,
+    /// JSON array of frequency changes
+    pub frequencies: Option<String>,
+    /// JSON array of source units
+    pub sources: Option<String>,
+    /// JSON array of patched talkgroups
+    pub patches: Option<String>,
+    /// Receiving site latitude in degrees; used with `longitude`
+    pub latitude: Option<f64>,
+    /// Receiving site longitude in degrees; used with `latitude`
+    pub longitude: Option<f64>,
+    /// trunk-recorder call JSON; fills fields not sent explicitly
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub meta: Option<Vec<u8>>,
+}
+
 /// Response for successful upload
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct UploadResponse {
     /// Whether the upload was successful
     pub success: bool,
@@ -28,21 +134,38 @@ pub struct UploadResponse {
     pub message: String,
 }
 
-/// Response for upload error
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct ErrorResponse {
-    /// Whether the upload was successful (always false)
-    pub success: bool,
-    /// Error message describing what went wrong
-    pub error: String,
-}
-
 /// Handle multipart form data upload from Rdio Scanner compatible systems.
 ///
 /// This endpoint accepts radio call uploads in multipart/form-data format, compatible
 /// with `SDRTrunk` and Rdio Scanner systems. It handles file validation, storage, database
 /// insertion, and system statistics updates.
 ///
+/// Besides the Rdio Scanner fields (`key`, `system`, `dateTime`, `talkgroup`,
+/// `audio`, ...), a trunk-recorder `meta` call JSON part is accepted, so
+/// trunk-recorder's Rdio Scanner uploader and its native call upload can both
+/// post here. Explicit form fields take precedence over `meta` values.
+///
+/// Large recordings can be sent ahead of time as a resumable upload
+/// (`/api/uploads`); its ID in the `uploadId` field then takes the place of
+/// `audio`, and the upload is discarded once the call is stored.
+///
+/// Clients whose audio lags behind the call metadata can register the call
+/// first: an upload with `audioPending=true` and no audio stores the call as
+/// `awaiting_audio`, so it is listed and announced right away. A later upload
+/// carrying the recording and the returned ID as `callId` attaches the audio
+/// and queues the call for transcription.
+///
+/// Uploads sent with an `Idempotency-Key` header can be retried safely: a
+/// retry with the same key for the same system within a day gets the
+/// original response (marked `Idempotent-Replayed: true`) instead of storing
+/// the call twice, and a retry arriving while the first attempt is still
+/// being stored is answered with `409 UPLOAD_IN_PROGRESS`.
+///
+/// While the transcription queue is nearly full (see
+/// `[transcription.backpressure]`), recordings are either refused with
+/// `QUEUE_FULL` and a `Retry-After` header, or stored as `deferred` and
+/// queued by the workers once the queue drains.
+///
 /// # Arguments
 ///
 /// * `state` - Application state with database pool and configuration
@@ -57,8 +180,12 @@ pub struct ErrorResponse {
 ///
 /// # Errors
 ///
-/// * `BAD_REQUEST` - Invalid multipart data, missing required fields, file validation failures
+/// * `BAD_REQUEST` - Invalid multipart data, missing required fields, file validation failures,
+///   a breach of the system's `[[uploads.systems]]` policy, or an ingest key issued for
+///   another system
 /// * `UNAUTHORIZED` - Invalid API key (when authentication enabled)
+/// * `TOO_MANY_REQUESTS` or `SERVICE_UNAVAILABLE` - The transcription queue is
+///   nearly full and back-pressure refuses uploads
 /// * `INTERNAL_SERVER_ERROR` - Database failures, file system errors
 ///
 /// # Example Request
@@ -81,6 +208,41 @@ pub struct ErrorResponse {
 ///
 /// 12345
 /// ```
+#[utoipa::path(
+    post,
+    path = "/api/call-upload",
+    tag = "Uploads",
+    summary = "Upload radio call recording",
+    description = "Upload audio from SDRTrunk or trunk-recorder for transcription. Rdio Scanner compatible; also served at `/api/rdio-scanner/upload` and `/api/trunk-recorder-call-upload`.",
+    request_body(content = CallUploadRequest, content_type = "multipart/form-data"),
+    responses(
+        (
+            status = 200,
+            description = "Upload successful",
+            body = UploadResponse,
+            headers(
+                ("X-Transcription-Queue-Depth" = i64, description = "Unfinished transcription jobs (pending plus in-flight); sent when transcription is enabled"),
+                ("X-Transcription-Backlog-Seconds" = i64, description = "Estimated seconds until the transcription backlog drains; omitted when there is no recent throughput"),
+                ("Idempotent-Replayed" = bool, description = "Sent as `true` when a retried `Idempotency-Key` returned the original call"),
+            ),
+        ),
+        (status = 400, description = "Invalid request or API key", body = ErrorResponse),
+        (status = 404, description = "The `callId` names no call of the system", body = ErrorResponse),
+        (status = 409, description = "An upload with the same `Idempotency-Key` is still being stored, or the `callId` call already has different audio", body = ErrorResponse),
+        (
+            status = 429,
+            description = "The transcription queue is nearly full (`reject_status = 429`)",
+            body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Seconds to wait before retrying")),
+        ),
+        (
+            status = 503,
+            description = "The transcription queue is nearly full (`reject_status = 503`)",
+            body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Seconds to wait before retrying")),
+        ),
+    ),
+)]
 #[allow(
     clippy::too_many_lines,
     clippy::cognitive_complexity,
@@ -95,7 +257,9 @@ pub async fn handle_call_upload(
     headers: HeaderMap,
     request: Request<Body>,
 ) -> impl IntoResponse {
-    let client_ip = addr.ip();
+    let received_at = Utc::now();
+    let client_ip = rate_limit::client_ip(&request, &state.config.api.trusted_proxies)
+        .unwrap_or_else(|| addr.ip());
     let user_agent = headers
         .get("user-agent")
         .and_then(|v| v.to_str().ok())
@@ -108,7 +272,7 @@ pub async fn handle_call_upload(
         .unwrap_or("");
 
     if !content_type.starts_with("multipart/form-data") {
-        let (status, json_error) = upload_error(
+        return upload_error(
             &state,
             client_ip,
             user_agent,
@@ -116,13 +280,29 @@ pub async fn handle_call_upload(
             None,
             "Request must have Content-Type: multipart/form-data",
         )
-        .await;
-        return (status, json_error).into_response();
+        .await
+        .into_response();
     }
 
+    let idempotency_key = match idempotency_key(&headers) {
+        Ok(key) => key,
+        Err(message) => {
+            return upload_error(&state, client_ip, user_agent, None, None, message)
+                .await
+                .into_response();
+        }
+    };
+
+    // Key already validated (and counted) by the auth middleware, if any
+    let header_key_id = request
+        .extensions()
+        .get::<ApiKeyDb>()
+        .map(|api_key| api_key.id.clone());
+    let header_ingest_key = request.extensions().get::<IngestKey>().cloned();
+
     // Try to extract multipart data from the request
     let Ok(mut multipart) = Multipart::from_request(request, &state).await else {
-        let (status, json_error) = upload_error(
+        return upload_error(
             &state,
             client_ip,
             user_agent,
@@ -130,8 +310,8 @@ pub async fn handle_call_upload(
             None,
             "Failed to parse multipart data - invalid format",
         )
-        .await;
-        return (status, json_error).into_response();
+        .await
+        .into_response();
     };
 
     // Parse multipart form data with proper error handling
@@ -139,6 +319,7 @@ pub async fn handle_call_upload(
     // Use Bytes instead of Vec<u8> to avoid copying the buffer (saves memory for large uploads)
     let mut audio_data: Option<axum::body::Bytes> = None;
     let mut audio_filename: Option<String> = None;
+    let mut upload_id: Option<String> = None;
 
     loop {
         match multipart.next_field().await {
@@ -149,11 +330,14 @@ pub async fn handle_call_upload(
                 match name.as_str() {
                     "audio" => {
                         audio_filename = field.file_name().map(String::from);
+                        if metadata.audio_type.is_none() {
+                            metadata.audio_type = field.content_type().map(String::from);
+                        }
                         match field.bytes().await {
                             Ok(data) => audio_data = Some(data),
                             Err(e) => {
                                 error!("Failed to read audio data: {}", e);
-                                let (status, json_error) = upload_error(
+                                return upload_error(
                                     &state,
                                     client_ip,
                                     user_agent,
@@ -161,8 +345,42 @@ pub async fn handle_call_upload(
                                     None,
                                     "Failed to read audio data",
                                 )
-                                .await;
-                                return (status, json_error).into_response();
+                                .await
+                                .into_response();
+                            }
+                        }
+                    }
+                    "uploadId" => {
+                        if let Ok(text) = field.text().await {
+                            upload_id = Some(text);
+                        }
+                    }
+                    "audioPending" => {
+                        if let Ok(text) = field.text().await {
+                            metadata.audio_pending = matches!(text.trim(), "1" | "true");
+                        }
+                    }
+                    "callId" => {
+                        if let Ok(text) = field.text().await {
+                            metadata.call_id = Some(text);
+                        }
+                    }
+                    "audioName" => {
+                        if let Ok(text) = field.text().await {
+                            metadata.audio_name = Some(text);
+                        }
+                    }
+                    "audioType" => {
+                        if let Ok(text) = field.text().await {
+                            metadata.audio_type = Some(text);
+                        }
+                    }
+                    "meta" => {
+                        // trunk-recorder call JSON, sent as a file part
+                        if let Ok(text) = field.text().await {
+                            match serde_json::from_str::<serde_json::Value>(&text) {
+                                Ok(meta) => metadata.apply_trunk_recorder_meta(&meta),
+                                Err(e) => warn!("Ignoring unparseable call metadata: {}", e),
                             }
                         }
                     }
@@ -191,11 +409,8 @@ pub async fn handle_call_upload(
                         }
                     }
                     "dateTime" | "datetime" => {
-                        if let Ok(text) = field.text().await
-                            && let Ok(ts) = text.parse::<i64>()
-                        {
-                            metadata.datetime =
-                                Some(DateTime::from_timestamp(ts, 0).unwrap_or_else(Utc::now));
+                        if let Ok(text) = field.text().await {
+                            metadata.datetime = parse_rdio_datetime(&text);
                         }
                     }
                     "talkgroup" => {
@@ -244,11 +459,25 @@ pub async fn handle_call_upload(
                             metadata.sources = serde_json::from_str(&text).ok();
                         }
                     }
-                    "freqList" => {
+                    "freqList" | "frequencies" => {
                         if let Ok(text) = field.text().await {
                             metadata.frequencies = serde_json::from_str(&text).ok();
                         }
                     }
+                    "latitude" | "lat" => {
+                        if let Ok(text) = field.text().await
+                            && let Ok(lat) = text.trim().parse::<f64>()
+                        {
+                            metadata.latitude = Some(lat);
+                        }
+                    }
+                    "longitude" | "lon" => {
+                        if let Ok(text) = field.text().await
+                            && let Ok(lon) = text.trim().parse::<f64>()
+                        {
+                            metadata.longitude = Some(lon);
+                        }
+                    }
                     "talkerAlias" => {
                         if let Ok(text) = field.text().await {
                             metadata.talker_alias = Some(text);
@@ -275,7 +504,7 @@ pub async fn handle_call_upload(
             Err(e) => {
                 // Handle multipart parsing errors gracefully
                 error!("Error parsing multipart data: {}", e);
-                let (status, json_error) = upload_error(
+                return upload_error(
                     &state,
                     client_ip,
                     user_agent,
@@ -283,8 +512,8 @@ pub async fn handle_call_upload(
                     None,
                     &format!("Invalid multipart data: {e}"),
                 )
-                .await;
-                return (status, json_error).into_response();
+                .await
+                .into_response();
             }
         }
     }
@@ -326,19 +555,52 @@ pub async fn handle_call_upload(
             .into_response();
     }
 
+    // A finished resumable upload stands in for the audio part
+    let mut resumable_id = None;
+    if audio_data.is_none()
+        && let Some(upload_id) = upload_id
+    {
+        match load_resumable(&state, &upload_id).await {
+            Ok((id, info, data)) => {
+                audio_filename = audio_filename.or_else(|| info.filename().map(String::from));
+                if metadata.audio_type.is_none() {
+                    metadata.audio_type = info.filetype().map(String::from);
+                }
+                audio_data = Some(data);
+                resumable_id = Some(id);
+            }
+            Err(message) => {
+                return upload_error(
+                    &state,
+                    client_ip,
+                    user_agent,
+                    metadata.api_key,
+                    metadata.system_id.as_deref(),
+                    &message,
+                )
+                .await
+                .into_response();
+            }
+        }
+    }
+
+    // Calls can be registered before their recording, which is then sent
+    // with the call's `callId`
+    let registering = audio_data.is_none() && metadata.audio_pending && metadata.call_id.is_none();
+
     // For non-test requests, validate required fields
-    let Some(audio) = audio_data else {
-        let (status, json_error) = upload_error(
+    if audio_data.is_none() && !registering {
+        return upload_error(
             &state,
             client_ip,
             user_agent,
             metadata.api_key,
-            metadata.system_id,
+            metadata.system_id.as_deref(),
             "No audio file provided",
         )
-        .await;
-        return (status, json_error).into_response();
-    };
+        .await
+        .into_response();
+    }
 
     tracing::debug!(
         "Parsed metadata: system_id={:?}, talkgroup_id={:?}, frequency={:?}",
@@ -347,8 +609,8 @@ pub async fn handle_call_upload(
         metadata.frequency
     );
 
-    let Some(system_id) = metadata.system_id else {
-        let (status, json_error) = upload_error(
+    let Some(raw_system_id) = metadata.system_id.clone() else {
+        return upload_error(
             &state,
             client_ip,
             user_agent,
@@ -356,183 +618,1114 @@ pub async fn handle_call_upload(
             None,
             "System ID is required",
         )
-        .await;
-        return (status, json_error).into_response();
+        .await
+        .into_response();
     };
 
-    let Some(filename) = audio_filename else {
-        let (status, json_error) = upload_error(
+    let system_id = match SystemId::new(raw_system_id.as_str()) {
+        Ok(system_id) => system_id,
+        Err(e) => {
+            return upload_error(
+                &state,
+                client_ip,
+                user_agent,
+                metadata.api_key,
+                Some(&raw_system_id),
+                &format!("Invalid system ID: {e}"),
+            )
+            .await
+            .into_response();
+        }
+    };
+
+    let filename = audio_filename.or_else(|| metadata.audio_name.clone());
+    if filename.is_none() && !registering {
+        return upload_error(
             &state,
             client_ip,
             user_agent,
             metadata.api_key,
-            Some(system_id),
+            Some(system_id.as_str()),
             "Audio filename is required",
         )
-        .await;
-        return (status, json_error).into_response();
-    };
+        .await
+        .into_response();
+    }
 
     // Validate API key if configured
     let mut api_key_id = None;
+    let mut ingest_key = header_ingest_key;
     if state.config.security.require_api_key {
         if let Some(key) = &metadata.api_key {
-            // Simple hash for API key using SHA256
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
-            let key_hash = format!("{:x}", hasher.finish());
-
-            match sdrtrunk_storage::validate_api_key(&state.pool, &key_hash).await {
-                Ok(Some(api_key)) => {
+            // Keys are stored as SHA-256 hashes when created through the admin API
+            let key_hash = hash_api_key(key);
+
+            match lookup_key(&state.pool, &key_hash).await {
+                Ok(Some(Credential::ApiKey(api_key)))
+                    if !TenantScope::from_api_key(&api_key).allows(&system_id) =>
+                {
+                    warn!(
+                        "API key {} may not upload to system {}",
+                        api_key.id, system_id
+                    );
+                    return upload_error(
+                        &state,
+                        client_ip,
+                        user_agent,
+                        Some(api_key.id),
+                        Some(system_id.as_str()),
+                        "API key is not authorized for this system",
+                    )
+                    .await
+                    .into_response();
+                }
+                Ok(Some(Credential::ApiKey(api_key))) => {
                     let api_key_uuid = api_key.id;
                     api_key_id = Some(api_key_uuid.clone());
                     info!("Valid API key used: {}", api_key_uuid);
+                    if header_key_id.as_ref() != Some(&api_key_uuid) {
+                        record_key_usage(&state, api_key_uuid);
+                    }
                 }
-                Ok(None) => {
-                    let (status, json_error) = upload_error(
+                Ok(Some(Credential::Ingest(form_ingest_key))) => {
+                    ingest_key = Some(form_ingest_key);
+                }
+                // Login sessions are for people, not recorders
+                Ok(None | Some(Credential::Session(_))) => {
+                    return upload_error(
                         &state,
                         client_ip,
                         user_agent,
                         Some(key.clone()),
-                        Some(system_id),
+                        Some(system_id.as_str()),
                         "Invalid API key",
                     )
-                    .await;
-                    return (status, json_error).into_response();
+                    .await
+                    .into_response();
                 }
                 Err(e) => {
                     error!("Failed to validate API key: {}", e);
-                    let (status, json_error) = upload_error(
+                    return upload_error(
                         &state,
                         client_ip,
                         user_agent,
                         Some(key.clone()),
-                        Some(system_id),
+                        Some(system_id.as_str()),
                         "Failed to validate API key",
                     )
-                    .await;
-                    return (status, json_error).into_response();
+                    .await
+                    .into_response();
                 }
             }
-        } else {
-            let (status, json_error) = upload_error(
+        } else if ingest_key.is_none() {
+            return upload_error(
                 &state,
                 client_ip,
                 user_agent,
                 None,
-                Some(system_id),
+                Some(system_id.as_str()),
                 "API key is required",
             )
-            .await;
-            return (status, json_error).into_response();
+            .await
+            .into_response();
         }
     }
 
-    // Validate file size
-    if audio.len() as u64 > state.config.security.max_upload_size {
-        let (status, json_error) = upload_error(
+    // Ingest keys only ever upload to the system they were issued for
+    if let Some(ingest_key) = ingest_key {
+        if ingest_key.system_id != system_id {
+            warn!(
+                "Ingest key {} may not upload to system {}",
+                ingest_key.id, system_id
+            );
+            return upload_error(
+                &state,
+                client_ip,
+                user_agent,
+                Some(ingest_key.id.to_string()),
+                Some(system_id.as_str()),
+                "Ingest key is not authorized for this system",
+            )
+            .await
+            .into_response();
+        }
+        info!("Valid ingest key used: {}", ingest_key.id);
+        let _ = api_key_id.get_or_insert_with(|| ingest_key.id.to_string());
+        record_ingest_use(&state, ingest_key.id);
+    }
+
+    // Attribute upload logs to the validated key ID so per-key usage can be reported
+    let log_key = api_key_id.clone().or_else(|| metadata.api_key.take());
+
+    // A retry of an upload that already created its call
+    if let Some(key) = &idempotency_key {
+        match IdempotencyQueries::find(&state.pool, &system_id, key).await {
+            Ok(Some(call_id)) => {
+                info!("REPLAY: {} | call {} | {}", system_id, call_id, client_ip);
+                discard_resumable(&state, resumable_id).await;
+                return replayed_upload(&headers, call_id);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Idempotency key lookup failed for {system_id}: {e}"),
+        }
+    }
+
+    let (Some(audio), Some(filename)) = (audio_data, filename) else {
+        let source = UploadSource {
+            client_ip,
+            user_agent,
+            api_key_id,
+            log_key,
+        };
+        return register_call(
             &state,
+            &headers,
+            metadata,
+            &system_id,
+            source,
+            idempotency_key.as_deref(),
+            received_at,
+        )
+        .await;
+    };
+
+    // The recording of a call registered earlier
+    if metadata.call_id.is_some() {
+        let source = UploadSource {
             client_ip,
             user_agent,
-            metadata.api_key,
-            Some(system_id),
-            &format!(
-                "File size exceeds maximum of {} bytes",
-                state.config.security.max_upload_size
-            ),
+            api_key_id,
+            log_key,
+        };
+        return attach_audio(
+            &state,
+            &headers,
+            &metadata,
+            &system_id,
+            source,
+            &audio,
+            &filename,
+            resumable_id,
         )
         .await;
-        return (status, json_error).into_response();
+    }
+
+    let (file_extension, duration) = match check_audio(
+        &state,
+        &system_id,
+        &filename,
+        &audio,
+        metadata.duration,
+        metadata.talkgroup_id,
+    ) {
+        Ok(checked) => checked,
+        Err(message) => {
+            return upload_error(
+                &state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                &message,
+            )
+            .await
+            .into_response();
+        }
+    };
+
+    // Recognise the same recording sent by several upload sources
+    let audio_sha256 = format!("{:x}", Sha256::digest(&audio));
+    let duplicate_policy = state.config.storage.duplicate_uploads;
+    if duplicate_policy != DuplicatePolicy::Allow {
+        match RadioCallQueries::find_by_audio_hash(&state.pool, &system_id, &audio_sha256).await {
+            Ok(Some(existing_id)) if duplicate_policy == DuplicatePolicy::Reject => {
+                let message = format!("Duplicate of call {existing_id}");
+                let _ = upload_error(
+                    &state,
+                    client_ip,
+                    user_agent,
+                    log_key,
+                    Some(system_id.as_str()),
+                    &message,
+                )
+                .await;
+                return ApiError::conflict("DUPLICATE_CALL", message).into_response();
+            }
+            Ok(Some(existing_id)) => {
+                info!(
+                    "DUPLICATE: {} | call {} | {}",
+                    system_id, existing_id, client_ip
+                );
+                log_upload(
+                    &state,
+                    UploadLogParams {
+                        client_ip,
+                        user_agent,
+                        api_key_id: log_key,
+                        system_id: Some(system_id.to_string()),
+                        success: true,
+                        error_message: None,
+                        filename: Some(filename),
+                        file_size: Some(audio.len() as i64),
+                    },
+                );
+                discard_resumable(&state, resumable_id).await;
+                let mut response = upload_success(&headers, existing_id, "Call already uploaded");
+                if let Ok(value) = HeaderValue::from_str(&existing_id.to_string()) {
+                    let _ = response.headers_mut().insert(DUPLICATE_OF_HEADER, value);
+                }
+                return response;
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Duplicate lookup failed for {system_id}: {e}"),
+        }
+    }
+
+    if let Err(message) = quotas::check_upload(&state, &system_id, audio.len() as u64).await {
+        return upload_error(
+            &state,
+            client_ip,
+            user_agent,
+            log_key,
+            Some(system_id.as_str()),
+            &message,
+        )
+        .await
+        .into_response();
+    }
+
+    // Hold recorders back while the transcription queue is nearly full
+    let pressure = queue_pressure(&state).await;
+    if pressure == BackPressureMode::Reject {
+        return refuse_upload(&state, client_ip, user_agent, log_key, &system_id).await;
+    }
+
+    // Claim the idempotency key so concurrent retries do not store the call twice
+    let idempotency_key = match claim_idempotency_key(
+        &state,
+        &headers,
+        &system_id,
+        idempotency_key.as_deref(),
+        client_ip,
+        resumable_id,
+    )
+    .await
+    {
+        ControlFlow::Continue(key) => key,
+        ControlFlow::Break(response) => return response,
+    };
+
+    // Save audio with a meaningful name derived from call metadata
+    let call_timestamp = *metadata.datetime.get_or_insert_with(Utc::now);
+    let unique_filename = recording_filename(
+        &system_id,
+        metadata.talkgroup_id,
+        call_timestamp,
+        &file_extension,
+    );
+    let key = recording_key(&system_id, call_timestamp.date_naive(), &unique_filename);
+
+    let audio_location = match state.audio_storage.put(&key, audio.clone()).await {
+        Ok(location) => location,
+        Err(e) => {
+            error!("Failed to save audio file: {}", e);
+            release_idempotency_key(&state, &system_id, idempotency_key).await;
+            return upload_error(
+                &state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                "Failed to save audio file",
+            )
+            .await
+            .into_response();
+        }
+    };
+
+    let stored_at = Utc::now();
+
+    fill_talkgroup_names(&state, &system_id, &mut metadata).await;
+    let transcription_status = initial_status(&state, &system_id, metadata.talkgroup_id, pressure);
+
+    let mut radio_call = call_record(
+        &state,
+        metadata,
+        &system_id,
+        client_ip,
+        api_key_id,
+        transcription_status,
+        duration,
+    );
+    radio_call.audio_filename = Some(unique_filename.clone());
+    radio_call.audio_file_path = Some(audio_location.clone());
+    radio_call.audio_size_bytes = Some(audio.len() as i64);
+    radio_call.audio_sha256 = Some(audio_sha256);
+
+    // Save to database
+    let call_id = match insert_call(&state, &mut radio_call).await {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Failed to save radio call to database: {}", e);
+            // Try to clean up the recording
+            if let Err(e) = state.audio_storage.delete(&audio_location).await {
+                warn!("Failed to remove orphaned recording {audio_location}: {e}");
+            }
+            release_idempotency_key(&state, &system_id, idempotency_key).await;
+            return upload_error(
+                &state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                "Failed to save call to database",
+            )
+            .await
+            .into_response();
+        }
+    };
+
+    if let Some(key) = idempotency_key
+        && let Err(e) = IdempotencyQueries::complete(&state.pool, &system_id, key, call_id).await
+    {
+        warn!("Failed to record idempotency key for call {call_id}: {e}");
+    }
+
+    record_event(
+        &state,
+        call_id,
+        CallEventKind::Received,
+        received_at,
+        Some(&format!("{filename} from {client_ip}")),
+    )
+    .await;
+    record_event(
+        &state,
+        call_id,
+        CallEventKind::Stored,
+        stored_at,
+        Some(&audio_location),
+    )
+    .await;
+
+    process_audio(
+        &state,
+        call_id,
+        &radio_call,
+        &audio_location,
+        &audio,
+        transcription_status,
+    )
+    .await;
+    announce_call(&state, call_id, &radio_call);
+    webhooks::spawn_event(
+        &state.pool,
+        &state.config.webhooks,
+        WebhookEvent::CallUploaded,
+        call_id,
+        None,
+    );
+    track_call(&state, call_id, &radio_call);
+    quotas::spawn_enforcement(&state, &system_id);
+
+    // Log successful upload
+    log_upload(
+        &state,
+        UploadLogParams {
+            client_ip,
+            user_agent,
+            api_key_id: log_key,
+            system_id: Some(system_id.to_string()),
+            success: true,
+            error_message: None,
+            filename: Some(unique_filename.clone()),
+            file_size: Some(audio.len() as i64),
+        },
+    );
+
+    // Create formatted log with useful details
+    let talkgroup_info = radio_call.talkgroup_id.map_or_else(
+        || "Unknown TG".to_string(),
+        |tg_id| {
+            radio_call.talkgroup_label.as_ref().map_or_else(
+                || format!("TG {tg_id}"),
+                |label| format!("TG {tg_id} ({label})"),
+            )
+        },
+    );
+
+    let freq_mhz = radio_call.frequency.map_or_else(
+        || "Unknown Freq".to_string(),
+        |f| format!("{:.4} MHz", f.as_hz() as f64 / 1_000_000.0),
+    );
+
+    let duration_str = duration.map_or_else(|| "N/A".to_string(), |d| format!("{d:.2}s"));
+
+    #[allow(clippy::cast_precision_loss)]
+    let file_size_kb = audio.len() as f64 / 1024.0;
+
+    info!(
+        "UPLOAD: {} | {} | {} | {} | {:.1}KB | {} | {}",
+        system_id,
+        talkgroup_info,
+        freq_mhz,
+        duration_str,
+        file_size_kb,
+        call_id.to_string().split('-').next().unwrap_or(""),
+        client_ip
+    );
+
+    discard_resumable(&state, resumable_id).await;
+    let mut response = upload_success(&headers, call_id, "Call uploaded successfully");
+    add_queue_headers(&state, &mut response).await;
+    response
+}
+
+/// Who sent an upload, for the call record and the upload log
+struct UploadSource {
+    client_ip: IpAddr,
+    user_agent: Option<String>,
+    /// Validated API or ingest key the call is attributed to
+    api_key_id: Option<String>,
+    /// Key the upload log attributes the upload to
+    log_key: Option<String>,
+}
+
+/// Register a call whose recording is sent later (`audioPending`)
+///
+/// The call is stored as `awaiting_audio` so listings and the live scanner
+/// show it right away; its recording is attached by [`attach_audio`]. Size
+/// and duration limits wait for the recording, but the talkgroup rule of the
+/// system's upload policy is enforced here.
+async fn register_call(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    mut metadata: CallMetadata,
+    system_id: &SystemId,
+    source: UploadSource,
+    idempotency_key: Option<&str>,
+    received_at: DateTime<Utc>,
+) -> Response {
+    let UploadSource {
+        client_ip,
+        user_agent,
+        api_key_id,
+        log_key,
+    } = source;
+
+    if let Some(policy) = state.config.uploads.policy_for(system_id.as_str())
+        && let Err(rejection) = policy.check_talkgroup(metadata.talkgroup_id)
+    {
+        return upload_error(
+            state,
+            client_ip,
+            user_agent,
+            log_key,
+            Some(system_id.as_str()),
+            &format!("Rejected by upload policy: {rejection}"),
+        )
+        .await
+        .into_response();
+    }
+
+    let idempotency_key =
+        match claim_idempotency_key(state, headers, system_id, idempotency_key, client_ip, None)
+            .await
+        {
+            ControlFlow::Continue(key) => key,
+            ControlFlow::Break(response) => return response,
+        };
+
+    fill_talkgroup_names(state, system_id, &mut metadata).await;
+    let duration = metadata.duration;
+    let mut radio_call = call_record(
+        state,
+        metadata,
+        system_id,
+        client_ip,
+        api_key_id,
+        TranscriptionStatus::AwaitingAudio,
+        duration,
+    );
+
+    let call_id = match insert_call(state, &mut radio_call).await {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Failed to save radio call to database: {}", e);
+            release_idempotency_key(state, system_id, idempotency_key).await;
+            return upload_error(
+                state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                "Failed to save call to database",
+            )
+            .await
+            .into_response();
+        }
+    };
+
+    if let Some(key) = idempotency_key
+        && let Err(e) = IdempotencyQueries::complete(&state.pool, system_id, key, call_id).await
+    {
+        warn!("Failed to record idempotency key for call {call_id}: {e}");
+    }
+
+    record_event(
+        state,
+        call_id,
+        CallEventKind::Received,
+        received_at,
+        Some(&format!("call metadata from {client_ip}; audio pending")),
+    )
+    .await;
+    announce_call(state, call_id, &radio_call);
+    webhooks::spawn_event(
+        &state.pool,
+        &state.config.webhooks,
+        WebhookEvent::CallUploaded,
+        call_id,
+        None,
+    );
+    track_call(state, call_id, &radio_call);
+
+    log_upload(
+        state,
+        UploadLogParams {
+            client_ip,
+            user_agent,
+            api_key_id: log_key,
+            system_id: Some(system_id.to_string()),
+            success: true,
+            error_message: None,
+            filename: None,
+            file_size: None,
+        },
+    );
+    info!(
+        "REGISTERED: {} | TG {} | {} | {}",
+        system_id,
+        radio_call
+            .talkgroup_id
+            .map_or_else(|| "unknown".to_string(), |tg| tg.to_string()),
+        call_id,
+        client_ip
+    );
+
+    upload_success(
+        headers,
+        call_id,
+        "Call registered; send its audio with this callId",
+    )
+}
+
+/// Attach the recording of a call registered with `audioPending`
+///
+/// The recording is checked against the same limits as a regular upload,
+/// with the registered call's talkgroup. Sending the same recording again
+/// gets the original response, while a different one is refused with
+/// `409 AUDIO_ALREADY_ATTACHED`.
+#[allow(
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_wrap
+)]
+async fn attach_audio(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    metadata: &CallMetadata,
+    system_id: &SystemId,
+    source: UploadSource,
+    audio: &axum::body::Bytes,
+    filename: &str,
+    resumable_id: Option<Uuid>,
+) -> Response {
+    let UploadSource {
+        client_ip,
+        user_agent,
+        log_key,
+        ..
+    } = source;
+    let raw_call_id = metadata.call_id.as_deref().unwrap_or_default();
+
+    let Ok(call_id) = Uuid::parse_str(raw_call_id.trim()) else {
+        return upload_error(
+            state,
+            client_ip,
+            user_agent,
+            log_key,
+            Some(system_id.as_str()),
+            &format!("Invalid call ID: {raw_call_id}"),
+        )
+        .await
+        .into_response();
+    };
+
+    let call = match sdrtrunk_storage::get_radio_call(&state.pool, call_id).await {
+        Ok(Some(call)) if call.system_id == *system_id => call,
+        Ok(_) => {
+            let message = format!("Call {call_id} not found in system {system_id}");
+            let _ = upload_error(
+                state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                &message,
+            )
+            .await;
+            return ApiError::not_found("CALL_NOT_FOUND", message).into_response();
+        }
+        Err(e) => {
+            error!("Failed to load call {call_id}: {}", e);
+            return upload_error(
+                state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                "Failed to load call",
+            )
+            .await
+            .into_response();
+        }
+    };
+
+    let audio_sha256 = format!("{:x}", Sha256::digest(audio));
+    if call.transcription_status.as_deref() != Some(TranscriptionStatus::AwaitingAudio.as_str()) {
+        // A retry of an attachment that already went through
+        if call.audio_sha256.as_deref() == Some(audio_sha256.as_str()) {
+            info!("REPLAY: {} | call {} | {}", system_id, call_id, client_ip);
+            discard_resumable(state, resumable_id).await;
+            return replayed_upload(headers, call_id);
+        }
+        return ApiError::conflict(
+            "AUDIO_ALREADY_ATTACHED",
+            format!("Call {call_id} already has its audio"),
+        )
+        .into_response();
+    }
+
+    let pressure = queue_pressure(state).await;
+    if pressure == BackPressureMode::Reject {
+        return refuse_upload(state, client_ip, user_agent, log_key, system_id).await;
+    }
+
+    let talkgroup_id = call.talkgroup_id.map(TalkgroupId::as_i32);
+    let (file_extension, duration) = match check_audio(
+        state,
+        system_id,
+        filename,
+        audio,
+        metadata.duration,
+        talkgroup_id,
+    ) {
+        Ok(checked) => checked,
+        Err(message) => {
+            return upload_error(
+                state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                &message,
+            )
+            .await
+            .into_response();
+        }
+    };
+    if let Err(message) = quotas::check_upload(state, system_id, audio.len() as u64).await {
+        return upload_error(
+            state,
+            client_ip,
+            user_agent,
+            log_key,
+            Some(system_id.as_str()),
+            &message,
+        )
+        .await
+        .into_response();
+    }
+
+    let unique_filename = recording_filename(
+        system_id,
+        talkgroup_id,
+        call.call_timestamp,
+        &file_extension,
+    );
+    let key = recording_key(
+        system_id,
+        call.call_timestamp.date_naive(),
+        &unique_filename,
+    );
+    let audio_location = match state.audio_storage.put(&key, audio.clone()).await {
+        Ok(location) => location,
+        Err(e) => {
+            error!("Failed to save audio file: {}", e);
+            return upload_error(
+                state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                "Failed to save audio file",
+            )
+            .await
+            .into_response();
+        }
+    };
+    let stored_at = Utc::now();
+
+    let transcription_status = initial_status(state, system_id, talkgroup_id, pressure);
+    let attached = AttachedAudio {
+        filename: &unique_filename,
+        file_path: &audio_location,
+        size_bytes: audio.len() as i64,
+        content_type: metadata.audio_type.as_deref(),
+        sha256: &audio_sha256,
+        duration_seconds: duration.and_then(|d| Decimal::try_from(d).ok()),
+        transcription_status: transcription_status.as_str(),
+    };
+    match RadioCallQueries::attach_audio(&state.pool, call_id, &attached).await {
+        Ok(true) => {}
+        Ok(false) => {
+            // Another upload attached its audio first
+            if let Err(e) = state.audio_storage.delete(&audio_location).await {
+                warn!("Failed to remove orphaned recording {audio_location}: {e}");
+            }
+            return ApiError::conflict(
+                "AUDIO_ALREADY_ATTACHED",
+                format!("Call {call_id} already has its audio"),
+            )
+            .into_response();
+        }
+        Err(e) => {
+            error!("Failed to attach audio to call {call_id}: {}", e);
+            if let Err(e) = state.audio_storage.delete(&audio_location).await {
+                warn!("Failed to remove orphaned recording {audio_location}: {e}");
+            }
+            return upload_error(
+                state,
+                client_ip,
+                user_agent,
+                log_key,
+                Some(system_id.as_str()),
+                "Failed to save call to database",
+            )
+            .await
+            .into_response();
+        }
+    }
+
+    record_event(
+        state,
+        call_id,
+        CallEventKind::Stored,
+        stored_at,
+        Some(&audio_location),
+    )
+    .await;
+    quotas::spawn_enforcement(state, system_id);
+    process_audio(
+        state,
+        call_id,
+        &call,
+        &audio_location,
+        audio,
+        transcription_status,
+    )
+    .await;
+    if state.features.is_enabled(features::LIVE_LISTEN) {
+        broadcast_event(
+            &state.events,
+            WebSocketEvent::TranscriptionUpdate {
+                call_id,
+                status: transcription_status.to_string(),
+                confidence: None,
+            },
+        );
+    }
+
+    log_upload(
+        state,
+        UploadLogParams {
+            client_ip,
+            user_agent,
+            api_key_id: log_key,
+            system_id: Some(system_id.to_string()),
+            success: true,
+            error_message: None,
+            filename: Some(unique_filename),
+            file_size: Some(audio.len() as i64),
+        },
+    );
+    info!(
+        "ATTACHED: {} | call {} | {:.1}KB | {}",
+        system_id,
+        call_id,
+        audio.len() as f64 / 1024.0,
+        client_ip
+    );
+
+    discard_resumable(state, resumable_id).await;
+    let mut response = upload_success(headers, call_id, "Audio attached to call");
+    add_queue_headers(state, &mut response).await;
+    response
+}
+
+/// Check a recording against the size, extension, and upload policy limits
+///
+/// Returns the recording's lowercased extension and its duration, measured
+/// from the audio when the uploader sent none.
+///
+/// # Errors
+///
+/// Returns the message to report if the recording breaks a limit
+fn check_audio(
+    state: &AppState,
+    system_id: &SystemId,
+    filename: &str,
+    audio: &[u8],
+    duration: Option<f64>,
+    talkgroup_id: Option<i32>,
+) -> Result<(String, Option<f64>), String> {
+    // Validate file size
+    if audio.len() as u64 > state.config.security.max_upload_size {
+        return Err(format!(
+            "File size exceeds maximum of {} bytes",
+            state.config.security.max_upload_size
+        ));
     }
 
     // Validate file extension
-    let file_extension = std::path::Path::new(&filename)
+    let file_extension = std::path::Path::new(filename)
         .extension()
         .and_then(|ext| ext.to_str())
         .unwrap_or("")
         .to_lowercase();
-
     if !state
         .config
         .storage
         .allowed_extensions
         .contains(&file_extension)
     {
-        let (status, json_error) = upload_error(
-            &state,
-            client_ip,
-            user_agent,
-            metadata.api_key,
-            Some(system_id),
-            &format!("File extension '{file_extension}' is not allowed"),
-        )
-        .await;
-        return (status, json_error).into_response();
+        return Err(format!("File extension '{file_extension}' is not allowed"));
     }
 
-    // Determine storage path
-    let date = metadata.datetime.unwrap_or_else(Utc::now).date_naive();
-    let storage_path = state.get_storage_path(&system_id, date);
+    // Calculate duration if not provided
+    let duration =
+        duration.or_else(|| audio_utils::calculate_audio_duration(audio, Some(filename)));
 
-    // Create directory structure (async to avoid blocking Tokio worker)
-    if let Err(e) = tokio::fs::create_dir_all(&storage_path).await {
-        error!("Failed to create storage directory: {}", e);
-        let (status, json_error) = upload_error(
-            &state,
-            client_ip,
-            user_agent,
-            metadata.api_key,
-            Some(system_id),
-            "Failed to create storage directory",
-        )
-        .await;
-        return (status, json_error).into_response();
+    // Enforce the system's upload policy, if it has one
+    if let Some(policy) = state.config.uploads.policy_for(system_id.as_str())
+        && let Err(rejection) = policy.check(audio.len() as u64, duration, talkgroup_id)
+    {
+        return Err(format!("Rejected by upload policy: {rejection}"));
+    }
+
+    Ok((file_extension, duration))
+}
+
+/// Claim the upload's idempotency key so concurrent retries do not store the
+/// call twice
+///
+/// Continues with the claimed key, or `None` when the upload sent no key or
+/// it could not be claimed. Breaks with the response to send instead when
+/// the key already belongs to a stored call or to an upload still in
+/// progress.
+async fn claim_idempotency_key<'a>(
+    state: &AppState,
+    headers: &HeaderMap,
+    system_id: &SystemId,
+    key: Option<&'a str>,
+    client_ip: IpAddr,
+    resumable_id: Option<Uuid>,
+) -> ControlFlow<Response, Option<&'a str>> {
+    let Some(key) = key else {
+        return ControlFlow::Continue(None);
+    };
+    match IdempotencyQueries::claim(&state.pool, system_id, key).await {
+        Ok(IdempotencyClaim::Claimed) => ControlFlow::Continue(Some(key)),
+        Ok(IdempotencyClaim::Completed(call_id)) => {
+            info!("REPLAY: {} | call {} | {}", system_id, call_id, client_ip);
+            discard_resumable(state, resumable_id).await;
+            ControlFlow::Break(replayed_upload(headers, call_id))
+        }
+        Ok(IdempotencyClaim::InProgress) => {
+            warn!("Upload for {system_id} retried while still in progress ({client_ip})");
+            ControlFlow::Break(
+                ApiError::conflict(
+                    "UPLOAD_IN_PROGRESS",
+                    "An upload with this Idempotency-Key is still being stored; retry later",
+                )
+                .into_response(),
+            )
+        }
+        Err(e) => {
+            warn!("Failed to claim idempotency key for {system_id}: {e}");
+            ControlFlow::Continue(None)
+        }
+    }
+}
+
+/// Stored name of a call's recording, derived from the call's metadata
+fn recording_filename(
+    system_id: &SystemId,
+    talkgroup_id: Option<i32>,
+    call_timestamp: DateTime<Utc>,
+    extension: &str,
+) -> String {
+    let tg_str = talkgroup_id.map_or_else(|| "unknown".to_string(), |t| t.to_string());
+    let ts = call_timestamp.format("%Y%m%d_%H%M%S");
+    format!("{system_id}_TG{tg_str}_{ts}.{extension}")
+}
+
+/// Fill talkgroup names the uploader left out from imported aliases
+async fn fill_talkgroup_names(state: &AppState, system_id: &SystemId, metadata: &mut CallMetadata) {
+    if let Some(talkgroup_id) = metadata
+        .talkgroup_id
+        .and_then(|id| TalkgroupId::new(id).ok())
+        .filter(|_| {
+            metadata.talkgroup_label.is_none()
+                || metadata.talkgroup_group.is_none()
+                || metadata.talkgroup_tag.is_none()
+        })
+    {
+        match TalkgroupQueries::find(&state.pool, system_id, talkgroup_id).await {
+            Ok(Some(talkgroup)) => {
+                metadata.talkgroup_label = metadata.talkgroup_label.take().or(talkgroup.label);
+                metadata.talkgroup_group = metadata.talkgroup_group.take().or(talkgroup.group);
+                metadata.talkgroup_tag = metadata.talkgroup_tag.take().or(talkgroup.tag);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Talkgroup lookup failed for {system_id}/{talkgroup_id}: {e}"),
+        }
+    }
+}
+/// Transcription status a call starts in once its recording is stored
+///
+/// Calls on opted-out talkgroups are stored but never queued; calls stored
+/// while back-pressure defers uploads are queued once the queue drains.
+fn initial_status(
+    state: &AppState,
+    system_id: &SystemId,
+    talkgroup_id: Option<i32>,
+    pressure: BackPressureMode,
+) -> TranscriptionStatus {
+    if state
+        .config
+        .transcription
+        .as_ref()
+        .is_some_and(|t| t.skips(system_id.as_str(), talkgroup_id))
+    {
+        TranscriptionStatus::Skipped
+    } else if pressure == BackPressureMode::Defer {
+        TranscriptionStatus::Deferred
+    } else {
+        TranscriptionStatus::Pending
+    }
+}
+
+/// How a nearly full transcription queue treats new recordings
+///
+/// [`BackPressureMode::Off`] while the queue has room, when back-pressure is
+/// off, or when the queue depth cannot be read.
+async fn queue_pressure(state: &AppState) -> BackPressureMode {
+    let Some(transcription) = state.config.transcription.as_ref().filter(|t| t.enabled) else {
+        return BackPressureMode::Off;
+    };
+    let backpressure = &transcription.backpressure;
+    if backpressure.mode == BackPressureMode::Off {
+        return BackPressureMode::Off;
+    }
+    match JobQueue::depth(&state.pool).await {
+        Ok(depth) if backpressure.room(transcription.queue_size, depth) == 0 => {
+            warn!(
+                "Transcription queue nearly full ({depth}/{}), {:?} uploads",
+                transcription.queue_size, backpressure.mode
+            );
+            backpressure.mode
+        }
+        Ok(_) => BackPressureMode::Off,
+        Err(e) => {
+            warn!("Failed to read transcription queue depth: {e}");
+            BackPressureMode::Off
+        }
     }
+}
 
-    // Save audio file with a meaningful name derived from call metadata
-    let tg_str = metadata
-        .talkgroup_id
-        .map_or("unknown".to_string(), |t| t.to_string());
-    let ts = metadata
-        .datetime
-        .unwrap_or_else(Utc::now)
-        .format("%Y%m%d_%H%M%S");
-    let unique_filename = format!("{system_id}_TG{tg_str}_{ts}.{file_extension}");
-    let file_path = storage_path.join(&unique_filename);
-
-    // Write file asynchronously (critical: avoids blocking Tokio worker for large files up to 100MB)
-    if let Err(e) = tokio::fs::write(&file_path, &audio).await {
-        error!("Failed to save audio file: {}", e);
-        let (status, json_error) = upload_error(
-            &state,
-            client_ip,
-            user_agent,
-            metadata.api_key,
-            Some(system_id),
-            "Failed to save audio file",
-        )
-        .await;
-        return (status, json_error).into_response();
-    }
+/// Refuse a recording while the transcription queue is nearly full
+///
+/// A resumable upload the recording came from is kept, so the retry can
+/// send the same `uploadId`.
+async fn refuse_upload(
+    state: &Arc<AppState>,
+    client_ip: IpAddr,
+    user_agent: Option<String>,
+    log_key: Option<String>,
+    system_id: &SystemId,
+) -> Response {
+    let message = "Transcription queue is full, retry later";
+    let _ = upload_error(
+        state,
+        client_ip,
+        user_agent,
+        log_key,
+        Some(system_id.as_str()),
+        message,
+    )
+    .await;
+    let backpressure = state
+        .config
+        .transcription
+        .as_ref()
+        .map(|t| t.backpressure.clone())
+        .unwrap_or_default();
+    queue_full(&backpressure, message)
+}
 
-    // Calculate duration if not provided
-    let duration = metadata
-        .duration
-        .or_else(|| audio_utils::calculate_audio_duration(&audio, Some(&filename)));
+/// `QUEUE_FULL` response telling the uploader when to retry
+fn queue_full(backpressure: &BackPressureConfig, message: &str) -> Response {
+    let error = if backpressure.reject_status == StatusCode::TOO_MANY_REQUESTS.as_u16() {
+        ApiError::too_many_requests("QUEUE_FULL", message)
+    } else {
+        ApiError::service_unavailable("QUEUE_FULL", message)
+    };
+    let mut response = error.into_response();
+    let _ = response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(backpressure.retry_after_seconds),
+    );
+    response
+}
+
+/// Build the call record for an upload, without its recording
+///
+/// The caller fills in the audio fields once the recording is stored.
+#[allow(clippy::too_many_arguments)]
+fn call_record(
+    state: &AppState,
+    metadata: CallMetadata,
+    system_id: &SystemId,
+    client_ip: IpAddr,
+    api_key_id: Option<String>,
+    transcription_status: TranscriptionStatus,
+    duration: Option<f64>,
+) -> RadioCallDb {
+    let location = call_location(
+        metadata.latitude.zip(metadata.longitude),
+        &state.config.geo,
+        system_id.as_str(),
+    );
 
-    // Create RadioCallDb record
-    let radio_call = RadioCallDb {
+    RadioCallDb {
         id: Uuid::new_v4(),
         created_at: Utc::now(),
         call_timestamp: metadata.datetime.unwrap_or_else(Utc::now),
-        system_id: SystemId::new(&system_id).unwrap_or_else(|_| {
-            // SAFETY: "unknown" is a valid static system ID
-            #[allow(clippy::expect_used)]
-            SystemId::new("unknown").expect("static system ID 'unknown' is always valid")
-        }),
-        system_label: metadata.system_label.clone(),
+        system_id: system_id.clone(),
+        system_label: metadata.system_label,
         frequency: metadata.frequency.and_then(|f| Frequency::new(f).ok()),
         talkgroup_id: metadata
             .talkgroup_id
@@ -544,61 +1737,62 @@ pub async fn handle_call_upload(
             .source_radio_id
             .and_then(|id| RadioId::new(id).ok()),
         talker_alias: metadata.talker_alias,
-        audio_filename: Some(unique_filename.clone()),
-        audio_file_path: Some(file_path.to_string_lossy().to_string()),
-        audio_size_bytes: Some(audio.len() as i64),
-        audio_content_type: None,
+        audio_filename: None,
+        audio_file_path: None,
+        audio_size_bytes: None,
+        audio_content_type: metadata.audio_type,
+        audio_sha256: None,
         duration_seconds: duration.and_then(|d| Decimal::try_from(d).ok()),
         upload_ip: Some(sqlx::types::ipnetwork::IpNetwork::from(client_ip)),
         upload_timestamp: Utc::now(),
         upload_api_key_id: api_key_id,
+        latitude: location.map(|(lat, _)| lat),
+        longitude: location.map(|(_, lon)| lon),
         patches: metadata.patches.map(|v| v.to_string()),
         frequencies: metadata.frequencies.map(|v| v.to_string()),
         sources: metadata.sources.map(|v| v.to_string()),
-        transcription_status: Some("pending".to_string()),
+        transcription_status: Some(transcription_status.to_string()),
         transcription_text: None,
         transcription_confidence: None,
         transcription_language: None,
         speaker_count: None,
         speaker_segments: None,
-    };
-
-    // Save to database
-    let call_id = match sdrtrunk_storage::insert_radio_call(&state.pool, &radio_call).await {
-        Ok(id) => id,
-        Err(e) => {
-            error!("Failed to save radio call to database: {}", e);
-            // Try to clean up the file (async to avoid blocking)
-            let _ = tokio::fs::remove_file(&file_path).await;
-            let (status, json_error) = upload_error(
-                &state,
-                client_ip,
-                user_agent,
-                metadata.api_key,
-                Some(system_id),
-                "Failed to save call to database",
-            )
-            .await;
-            return (status, json_error).into_response();
-        }
-    };
+    }
+}
 
-    // Trigger transcription if enabled
+/// Queue a stored recording for transcription, waveform, and fingerprinting
+async fn process_audio(
+    state: &AppState,
+    call_id: Uuid,
+    call: &RadioCallDb,
+    audio_location: &str,
+    audio: &axum::body::Bytes,
+    transcription_status: TranscriptionStatus,
+) {
+    // Trigger transcription if enabled, the talkgroup has not opted out, and
+    // back-pressure has not deferred the call
     if let Some(ref transcription_config) = state.config.transcription
         && transcription_config.enabled
+        && transcription_status == TranscriptionStatus::Pending
     {
         let params = sdrtrunk_storage::jobs::EnqueueParams {
             call_id,
-            audio_path: Some(file_path.to_string_lossy().to_string()),
+            audio_path: Some(audio_location.to_string()),
             audio_data: Some(audio.to_vec()),
-            priority: 0,
-            options: serde_json::json!({"language": "en", "diarize": true}),
+            // Calls on priority talkgroups jump the queue
+            priority: transcription_config.priority_for(
+                call.system_id.as_str(),
+                call.talkgroup_id.map(TalkgroupId::as_i32),
+            ),
+            // Model and language are chosen per system by the worker
+            options: serde_json::json!({"diarize": true}),
             timeout_seconds: i32::try_from(transcription_config.timeout_seconds).unwrap_or(300),
         };
 
-        match sdrtrunk_storage::jobs::JobQueue::enqueue(&state.pool, &params).await {
+        match JobQueue::enqueue(&state.pool, &params).await {
             Ok(job_id) => {
                 info!("Transcription job {job_id} enqueued for call {call_id}");
+                publish_progress(&state.pool, call_id, Some(job_id), ProgressStage::Queued).await;
             }
             Err(e) => {
                 error!("Failed to enqueue transcription for call {call_id}: {e}");
@@ -606,10 +1800,76 @@ pub async fn handle_call_upload(
         }
     }
 
-    // Update system statistics (non-critical, spawn as background task to avoid blocking response)
+    waveform::spawn_generate(&state.pool, call_id, audio.clone());
+    if state.config.fingerprints.enabled {
+        fingerprint::spawn_fingerprint(
+            &state.pool,
+            &state.config.fingerprints,
+            call_id,
+            call.call_timestamp,
+            audio.clone(),
+        );
+    }
+}
+
+/// Feed a new call to the web interface's live scanner
+fn announce_call(state: &AppState, call_id: Uuid, call: &RadioCallDb) {
+    if state.features.is_enabled(features::LIVE_LISTEN) {
+        broadcast_event(
+            &state.events,
+            WebSocketEvent::NewCall {
+                call_id,
+                system_id: call.system_id.to_string(),
+                talkgroup_id: call.talkgroup_id.map(TalkgroupId::as_i32),
+                talkgroup_label: call.talkgroup_label.clone(),
+                transcription_status: call.transcription_status.clone(),
+                timestamp: call.call_timestamp,
+            },
+        );
+    }
+}
+
+/// Update conversations, radio activity, and system statistics for a new
+/// call (non-critical, spawned so the response is not delayed)
+fn track_call(state: &AppState, call_id: Uuid, call: &RadioCallDb) {
+    // Group the call into its talkgroup's conversation
+    if state.config.conversations.enabled {
+        let pool_clone = state.pool.clone();
+        let gap_seconds = state.config.conversations.gap_seconds;
+        let mut call = call.clone();
+        call.id = call_id;
+        drop(tokio::spawn(async move {
+            if let Err(e) = ConversationQueries::assign(&pool_clone, &call, gap_seconds).await {
+                warn!("Failed to group call {} into a conversation: {e}", call.id);
+            }
+        }));
+    }
+
+    // Track the transmitting radio's activity
+    if let Some(radio_id) = call.source_radio_id {
+        let pool_clone = state.pool.clone();
+        let system_id_clone = call.system_id.clone();
+        let alias = call.talker_alias.clone();
+        let call_timestamp = call.call_timestamp;
+        drop(tokio::spawn(async move {
+            if let Err(e) = RadioQueries::record_call(
+                &pool_clone,
+                &system_id_clone,
+                radio_id,
+                alias.as_deref(),
+                call_timestamp,
+            )
+            .await
+            {
+                warn!("Failed to record activity of radio {radio_id}: {e}");
+            }
+        }));
+    }
+
+    // Update system statistics
     let pool_clone = state.pool.clone();
-    let system_id_clone = system_id.clone();
-    let system_label_clone = metadata.system_label.clone();
+    let system_id_clone = call.system_id.clone();
+    let system_label_clone = call.system_label.clone();
     drop(tokio::spawn(async move {
         if let Err(e) =
             sdrtrunk_storage::update_system_stats(&pool_clone, &system_id_clone, system_label_clone)
@@ -618,57 +1878,89 @@ pub async fn handle_call_upload(
             warn!("Failed to update system stats: {}", e);
         }
     }));
+}
 
-    // Log successful upload (non-critical, spawn as background task to avoid blocking response)
-    let pool_clone = state.pool.clone();
-    let log_params = sdrtrunk_storage::UploadLogParams {
-        client_ip,
-        user_agent,
-        api_key_id: metadata.api_key,
-        system_id: Some(system_id.clone()),
-        success: true,
-        error_message: None,
-        filename: Some(unique_filename.clone()),
-        file_size: Some(audio.len() as i64),
-    };
-    drop(tokio::spawn(async move {
-        if let Err(e) = sdrtrunk_storage::insert_upload_log(&pool_clone, log_params).await {
-            warn!("Failed to log upload: {}", e);
+/// Let clients pace themselves against the transcription backlog
+async fn add_queue_headers(state: &AppState, response: &mut Response) {
+    if state
+        .config
+        .transcription
+        .as_ref()
+        .is_some_and(|t| t.enabled)
+    {
+        match JobQueue::backlog(&state.pool).await {
+            Ok(backlog) => apply_queue_headers(response.headers_mut(), &backlog),
+            Err(e) => warn!("Failed to read transcription backlog: {}", e),
         }
-    }));
-
-    // Create formatted log with useful details
-    let talkgroup_info = radio_call.talkgroup_id.map_or_else(
-        || "Unknown TG".to_string(),
-        |tg_id| {
-            radio_call.talkgroup_label.as_ref().map_or_else(
-                || format!("TG {tg_id}"),
-                |label| format!("TG {tg_id} ({label})"),
-            )
-        },
-    );
+    }
+}
 
-    let freq_mhz = radio_call.frequency.map_or_else(
-        || "Unknown Freq".to_string(),
-        |f| format!("{:.4} MHz", f.as_hz() as f64 / 1_000_000.0),
-    );
+/// The recording of the finished resumable upload `upload_id`
+///
+/// # Errors
+///
+/// Returns the message to report if the ID is malformed or the upload is
+/// unknown, unfinished, or unreadable
+async fn load_resumable(
+    state: &AppState,
+    upload_id: &str,
+) -> Result<(Uuid, UploadInfo, axum::body::Bytes), String> {
+    let id =
+        Uuid::parse_str(upload_id.trim()).map_err(|_| format!("Invalid upload ID: {upload_id}"))?;
+    match state.resumable_uploads().read_complete(id).await {
+        Ok((info, data)) => Ok((id, info, data)),
+        Err(e @ (ResumableError::NotFound | ResumableError::Incomplete { .. })) => {
+            Err(format!("Resumable upload {id}: {e}"))
+        }
+        Err(e) => {
+            error!("Failed to read resumable upload {id}: {e}");
+            Err("Failed to read resumable upload".to_string())
+        }
+    }
+}
 
-    let duration_str = duration.map_or_else(|| "N/A".to_string(), |d| format!("{d:.2}s"));
+/// Discard the resumable upload a call was made from, now that it is stored
+async fn discard_resumable(state: &AppState, resumable_id: Option<Uuid>) {
+    if let Some(id) = resumable_id
+        && let Err(e) = state.resumable_uploads().remove(id).await
+    {
+        warn!("Failed to discard resumable upload {id}: {e}");
+    }
+}
 
-    #[allow(clippy::cast_precision_loss)]
-    let file_size_kb = audio.len() as f64 / 1024.0;
+/// Record a processing event of an uploaded call (non-critical)
+async fn record_event(
+    state: &AppState,
+    call_id: Uuid,
+    kind: CallEventKind,
+    occurred_at: DateTime<Utc>,
+    detail: Option<&str>,
+) {
+    if let Err(e) = CallEventQueries::record(&state.pool, call_id, kind, occurred_at, detail).await
+    {
+        warn!(
+            "Failed to record {} event for call {call_id}: {e}",
+            kind.as_str()
+        );
+    }
+}
 
-    info!(
-        "UPLOAD: {} | {} | {} | {} | {:.1}KB | {} | {}",
-        system_id,
-        talkgroup_info,
-        freq_mhz,
-        duration_str,
-        file_size_kb,
-        call_id.to_string().split('-').next().unwrap_or(""),
-        client_ip
-    );
+/// Add transcription queue pressure headers to an upload response
+///
+/// The backlog estimate is omitted when there is no recent throughput to
+/// base it on.
+fn apply_queue_headers(headers: &mut HeaderMap, backlog: &QueueBacklog) {
+    let _ = headers.insert(QUEUE_DEPTH_HEADER, HeaderValue::from(backlog.depth()));
+    if let Some(seconds) = backlog.estimated_seconds() {
+        let _ = headers.insert(BACKLOG_SECONDS_HEADER, HeaderValue::from(seconds));
+    }
+}
 
+/// Respond to an accepted upload
+///
+/// Clients asking for JSON get an [`UploadResponse`] carrying `message`;
+/// everyone else gets the plain text reply `SDRTrunk` expects.
+fn upload_success(headers: &HeaderMap, call_id: Uuid, message: &str) -> Response {
     // Check if client wants JSON response (like Python implementation)
     let accept_header = headers
         .get("accept")
@@ -676,13 +1968,12 @@ pub async fn handle_call_upload(
         .unwrap_or("");
 
     if accept_header.contains("application/json") {
-        // Return JSON response
         (
             StatusCode::OK,
             Json(UploadResponse {
                 success: true,
                 id: call_id,
-                message: "Call uploaded successfully".to_string(),
+                message: message.to_string(),
             }),
         )
             .into_response()
@@ -693,58 +1984,157 @@ pub async fn handle_call_upload(
             .header("content-type", "text/plain")
             .body(Body::from("Call imported successfully."))
             .unwrap_or_else(|_| Response::new(Body::from("Call imported successfully.")))
-            .into_response()
     }
 }
 
+/// Respond to a retried upload with the original call's success response
+fn replayed_upload(headers: &HeaderMap, call_id: Uuid) -> Response {
+    let mut response = upload_success(headers, call_id, "Call uploaded successfully");
+    let _ = response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// The upload's `Idempotency-Key`, if it sent one
+///
+/// # Errors
+///
+/// Returns the message to report if the key is empty, too long, or not
+/// printable ASCII
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, &'static str> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    match value.to_str().map(str::trim) {
+        Ok(key)
+            if !key.is_empty()
+                && key.len() <= MAX_IDEMPOTENCY_KEY_LEN
+                && key.bytes().all(|b| b.is_ascii_graphic() || b == b' ') =>
+        {
+            Ok(Some(key.to_string()))
+        }
+        _ => Err("Idempotency-Key must be 1 to 255 printable ASCII characters"),
+    }
+}
+
+/// Save a new call, sealing its talker alias when aliases are encrypted, and
+/// drop the cached statistics it makes stale
+///
+/// A sealed alias is taken out of `radio_call`, so radio activity, events,
+/// and webhooks never see it in the clear either.
+async fn insert_call(
+    state: &AppState,
+    radio_call: &mut RadioCallDb,
+) -> sdrtrunk_storage::Result<Uuid> {
+    let sealed_alias = match state
+        .encryption
+        .as_deref()
+        .filter(|encryption| encryption.encrypts(EncryptedField::TalkerAlias))
+    {
+        Some(encryption) => radio_call
+            .talker_alias
+            .take()
+            .map(|alias| encryption.seal(&alias))
+            .transpose()?,
+        None => None,
+    };
+    let call_id = sdrtrunk_storage::insert_radio_call(&state.pool, radio_call).await?;
+    state.stats_cache.invalidate_system(&radio_call.system_id);
+    if let Some(sealed) = sealed_alias
+        && let Err(e) = EncryptionQueries::seal_talker_alias(&state.pool, call_id, &sealed).await
+    {
+        warn!("Failed to store the talker alias of call {call_id}: {e}");
+    }
+    Ok(call_id)
+}
+
+/// Give up a claimed idempotency key after a failed upload so it can be retried
+async fn release_idempotency_key(state: &AppState, system_id: &SystemId, key: Option<&str>) {
+    if let Some(key) = key
+        && let Err(e) = IdempotencyQueries::release(&state.pool, system_id, key).await
+    {
+        warn!("Failed to release idempotency key for {system_id}: {e}");
+    }
+}
+
+/// Record an upload attempt in the background so the response is not delayed
+fn log_upload(state: &AppState, log_params: UploadLogParams) {
+    let pool = state.pool.clone();
+    drop(tokio::spawn(async move {
+        if let Err(e) = sdrtrunk_storage::insert_upload_log(&pool, log_params).await {
+            warn!("Failed to log upload: {}", e);
+        }
+    }));
+}
+
 /// Helper function to handle upload errors with proper logging
 #[allow(clippy::too_many_arguments, clippy::unused_async)]
 async fn upload_error(
     state: &Arc<AppState>,
-    client_ip: std::net::IpAddr,
+    client_ip: IpAddr,
     user_agent: Option<String>,
     api_key: Option<String>,
-    system_id: Option<String>,
+    system_id: Option<&str>,
     error_message: &str,
-) -> (StatusCode, Json<ErrorResponse>) {
+) -> ApiError {
     error!(
         "❌ UPLOAD FAILED: {} | System: {} | IP: {}",
         error_message,
-        system_id.as_deref().unwrap_or("Unknown"),
+        system_id.unwrap_or("Unknown"),
         client_ip
     );
 
-    // Log failed upload (spawn as background task to avoid delaying error response)
-    let pool_clone = state.pool.clone();
-    let log_params = sdrtrunk_storage::UploadLogParams {
-        client_ip,
-        user_agent,
-        api_key_id: api_key,
-        system_id,
-        success: false,
-        error_message: Some(error_message.to_string()),
-        filename: None,
-        file_size: None,
-    };
-    drop(tokio::spawn(async move {
-        if let Err(e) = sdrtrunk_storage::insert_upload_log(&pool_clone, log_params).await {
-            warn!("Failed to log upload error: {}", e);
-        }
-    }));
-
-    (
-        StatusCode::BAD_REQUEST,
-        Json(ErrorResponse {
+    // Log failed upload
+    log_upload(
+        state,
+        UploadLogParams {
+            client_ip,
+            user_agent,
+            api_key_id: api_key,
+            system_id: system_id.map(str::to_string),
             success: false,
-            error: error_message.to_string(),
-        }),
-    )
+            error_message: Some(error_message.to_string()),
+            filename: None,
+            file_size: None,
+        },
+    );
+
+    ApiError::bad_request("INVALID_UPLOAD", error_message)
+}
+
+/// Parse a Rdio Scanner `dateTime` field
+///
+/// `SDRTrunk` and trunk-recorder send Unix seconds; some Rdio Scanner clients
+/// send RFC 3339 timestamps.
+fn parse_rdio_datetime(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(ts) = text.parse::<i64>() {
+        return DateTime::from_timestamp(ts, 0);
+    }
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Where a call was heard: the uploaded site coordinates when valid,
+/// otherwise the system's configured location
+fn call_location(site: Option<(f64, f64)>, geo: &GeoConfig, system_id: &str) -> Option<(f64, f64)> {
+    let valid =
+        |&(lat, lon): &(f64, f64)| (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon);
+    site.filter(valid).or_else(|| {
+        geo.location_for(system_id)
+            .map(|l| (l.latitude, l.longitude))
+            .filter(valid)
+    })
 }
 
 /// Metadata extracted from multipart form
 #[derive(Default)]
 struct CallMetadata {
     api_key: Option<String>,
+    audio_name: Option<String>,
+    audio_type: Option<String>,
     system_id: Option<String>,
     system_label: Option<String>,
     datetime: Option<DateTime<Utc>>,
@@ -760,6 +2150,75 @@ struct CallMetadata {
     patches: Option<serde_json::Value>,
     sources: Option<serde_json::Value>,
     frequencies: Option<serde_json::Value>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    /// The recording follows in a later upload naming the call's ID
+    audio_pending: bool,
+    /// Registered call whose recording this upload carries
+    call_id: Option<String>,
+}
+
+impl CallMetadata {
+    /// Fill unset fields from a trunk-recorder call JSON (`meta` part)
+    fn apply_trunk_recorder_meta(&mut self, meta: &serde_json::Value) {
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(serde_json::Value::as_str)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        let int = |key: &str| meta.get(key).and_then(serde_json::Value::as_i64);
+        let first_source = meta
+            .get("srcList")
+            .and_then(serde_json::Value::as_array)
+            .and_then(|sources| sources.first());
+
+        self.system_id = self.system_id.take().or_else(|| text("short_name"));
+        self.datetime = self
+            .datetime
+            .or_else(|| int("start_time").and_then(|ts| DateTime::from_timestamp(ts, 0)));
+        self.talkgroup_id = self
+            .talkgroup_id
+            .or_else(|| int("talkgroup").and_then(|tg| i32::try_from(tg).ok()));
+        self.talkgroup_label = self
+            .talkgroup_label
+            .take()
+            .or_else(|| text("talkgroup_tag"));
+        self.talkgroup_group = self
+            .talkgroup_group
+            .take()
+            .or_else(|| text("talkgroup_group"));
+        self.talkgroup_tag = self
+            .talkgroup_tag
+            .take()
+            .or_else(|| text("talkgroup_group_tag"));
+        self.frequency = self.frequency.or_else(|| int("freq"));
+        self.duration = self
+            .duration
+            .or_else(|| meta.get("call_length").and_then(serde_json::Value::as_f64));
+        self.source_radio_id = self.source_radio_id.or_else(|| {
+            first_source
+                .and_then(|src| src.get("src"))
+                .and_then(serde_json::Value::as_i64)
+                .and_then(|id| i32::try_from(id).ok())
+        });
+        self.talker_alias = self.talker_alias.take().or_else(|| {
+            first_source
+                .and_then(|src| src.get("tag"))
+                .and_then(serde_json::Value::as_str)
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+        });
+        self.sources = self.sources.take().or_else(|| meta.get("srcList").cloned());
+        self.frequencies = self
+            .frequencies
+            .take()
+            .or_else(|| meta.get("freqList").cloned());
+        self.patches = self
+            .patches
+            .take()
+            .or_else(|| meta.get("patched_talkgroups").cloned());
+    }
 }
 
 #[cfg(test)]
@@ -809,6 +2268,107 @@ mod tests {
     use serde_json;
     use uuid::Uuid;
 
+    #[test]
+    fn test_apply_queue_headers() {
+        let mut headers = HeaderMap::new();
+        let backlog = QueueBacklog {
+            pending: 8,
+            processing: 2,
+            active_workers: 2,
+            avg_processing_ms: Some(3000.0),
+        };
+        apply_queue_headers(&mut headers, &backlog);
+
+        assert_eq!(headers.get(QUEUE_DEPTH_HEADER).unwrap(), "10");
+        assert_eq!(headers.get(BACKLOG_SECONDS_HEADER).unwrap(), "15");
+    }
+
+    #[test]
+    fn test_apply_queue_headers_without_throughput() {
+        let mut headers = HeaderMap::new();
+        let backlog = QueueBacklog {
+            pending: 3,
+            ..QueueBacklog::default()
+        };
+        apply_queue_headers(&mut headers, &backlog);
+
+        assert_eq!(headers.get(QUEUE_DEPTH_HEADER).unwrap(), "3");
+        assert!(headers.get(BACKLOG_SECONDS_HEADER).is_none());
+    }
+
+    #[test]
+    fn test_queue_full() {
+        let backpressure = BackPressureConfig {
+            reject_status: 429,
+            retry_after_seconds: 45,
+            ..BackPressureConfig::default()
+        };
+        let response = queue_full(&backpressure, "full");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "45");
+
+        let response = queue_full(&BackPressureConfig::default(), "full");
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+    }
+
+    #[tokio::test]
+    async fn test_upload_success_formats() {
+        let call_id = Uuid::new_v4();
+
+        let response = upload_success(&HeaderMap::new(), call_id, "Call already uploaded");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"Call imported successfully.");
+
+        let mut headers = HeaderMap::new();
+        let _ = headers.insert("accept", HeaderValue::from_static("application/json"));
+        let response = upload_success(&headers, call_id, "Call already uploaded");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: UploadResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.id, call_id);
+        assert_eq!(json.message, "Call already uploaded");
+    }
+
+    #[test]
+    fn test_idempotency_key() {
+        assert_eq!(idempotency_key(&HeaderMap::new()), Ok(None));
+
+        let mut headers = HeaderMap::new();
+        let _ = headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_static(" call-20240101-1 "),
+        );
+        assert_eq!(
+            idempotency_key(&headers),
+            Ok(Some("call-20240101-1".to_string()))
+        );
+
+        let _ = headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("  "));
+        assert!(idempotency_key(&headers).is_err());
+
+        let long = "k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1);
+        let _ = headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_str(&long).unwrap(),
+        );
+        assert!(idempotency_key(&headers).is_err());
+    }
+
+    #[test]
+    fn test_replayed_upload() {
+        let call_id = Uuid::new_v4();
+        let response = replayed_upload(&HeaderMap::new(), call_id);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+            "true"
+        );
+    }
+
     #[test]
     fn test_upload_response_serialization() {
         let call_id = Uuid::new_v4();
@@ -843,6 +2403,8 @@ mod tests {
         let error = ErrorResponse {
             success: false,
             error: "Invalid file format".to_string(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&error).expect("Failed to serialize");
@@ -870,6 +2432,8 @@ mod tests {
         assert!(metadata.patches.is_none());
         assert!(metadata.sources.is_none());
         assert!(metadata.frequencies.is_none());
+        assert!(!metadata.audio_pending);
+        assert!(metadata.call_id.is_none());
     }
 
     #[test]
@@ -973,6 +2537,8 @@ mod tests {
             let error = ErrorResponse {
                 success: false,
                 error: message.to_string(),
+                code: "INVALID_UPLOAD".to_string(),
+                details: None,
             };
 
             let json = serde_json::to_string(&error).expect("Failed to serialize");
@@ -987,6 +2553,8 @@ mod tests {
         let error = ErrorResponse {
             success: false,
             error: "Error with \"quotes\" and \n newlines".to_string(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&error).expect("Failed to serialize");
@@ -1155,6 +2723,8 @@ mod tests {
         let original_error = ErrorResponse {
             success: false,
             error: "Test error message".to_string(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         // Serialize to JSON
@@ -1360,6 +2930,8 @@ mod tests {
         let error = ErrorResponse {
             success: false,
             error: long_message.clone(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&error).expect("Failed to serialize");
@@ -1443,11 +3015,15 @@ mod tests {
         let error1 = ErrorResponse {
             success: false,
             error: "Error 1".to_string(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         let error2 = ErrorResponse {
             success: false,
             error: "Error 2".to_string(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         // Both should have success = false
@@ -1523,6 +3099,8 @@ mod tests {
             let error = ErrorResponse {
                 success: false,
                 error: message.to_string(),
+                code: "INVALID_UPLOAD".to_string(),
+                details: None,
             };
 
             let json = serde_json::to_string(&error).expect("Should serialize");
@@ -1591,6 +3169,8 @@ mod tests {
         let empty_error = ErrorResponse {
             success: false,
             error: String::new(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         assert!(!empty_error.success);
@@ -1604,6 +3184,8 @@ mod tests {
         let long_error = ErrorResponse {
             success: false,
             error: long_error_message.clone(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         assert_eq!(long_error.error, long_error_message);
@@ -1677,6 +3259,8 @@ mod tests {
         let original_error = ErrorResponse {
             success: false,
             error: "Error with \"quotes\" and \nnewlines and \ttabs".to_string(),
+            code: "INVALID_UPLOAD".to_string(),
+            details: None,
         };
 
         let json = serde_json::to_string(&original_error).unwrap();
@@ -1906,19 +3490,19 @@ mod tests {
             let client_ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
             let user_agent = Some("TestAgent/1.0".to_string());
 
-            let (status, error_json) = upload_error(
+            let error = upload_error(
                 &state,
                 client_ip,
                 user_agent,
                 Some("test-key".to_string()),
-                Some("test-system".to_string()),
+                Some("test-system"),
                 "Test error message",
             )
             .await;
 
-            assert_eq!(status, StatusCode::BAD_REQUEST);
-            assert!(!error_json.success);
-            assert_eq!(error_json.error, "Test error message");
+            assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(error.code(), "INVALID_UPLOAD");
+            assert_eq!(error.message(), "Test error message");
         }
     }
 
@@ -2133,6 +3717,8 @@ mod tests {
             let error_response = ErrorResponse {
                 success: false,
                 error: format!("{}: {}", error_type, error_msg),
+                code: error_type.to_string(),
+                details: None,
             };
 
             let json = serde_json::to_string(&error_response).expect("Serialization failed");
@@ -2245,6 +3831,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_recording_filename() {
+        let system_id = SystemId::new("metro").unwrap();
+        let timestamp = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        assert_eq!(
+            recording_filename(&system_id, Some(100), timestamp, "mp3"),
+            "metro_TG100_20231114_221320.mp3"
+        );
+        assert_eq!(
+            recording_filename(&system_id, None, timestamp, "wav"),
+            "metro_TGunknown_20231114_221320.wav"
+        );
+    }
+
     #[test]
     fn test_api_key_hashing_logic() {
         // Test API key hashing logic used in the upload handler
@@ -2387,7 +3988,7 @@ mod tests {
         assert_eq!(audio_size, 1024 * 50);
         assert!(file_path.contains("test_system"));
         assert!(unique_filename.contains("test_file.mp3"));
-        assert!(client_ip.parse::<std::net::IpAddr>().is_ok());
+        assert!(client_ip.parse::<IpAddr>().is_ok());
     }
 
     #[test]
@@ -2661,4 +4262,107 @@ mod tests {
             assert_eq!(wants_json, expects_json, "Accept: {}", accept_header);
         }
     }
+
+    #[test]
+    fn test_parse_rdio_datetime() {
+        assert_eq!(
+            parse_rdio_datetime("1704067200").unwrap().timestamp(),
+            1704067200
+        );
+        assert_eq!(
+            parse_rdio_datetime(" 2024-01-01T00:00:00Z ")
+                .unwrap()
+                .timestamp(),
+            1704067200
+        );
+        assert_eq!(
+            parse_rdio_datetime("2024-01-01T02:00:00+02:00")
+                .unwrap()
+                .timestamp(),
+            1704067200
+        );
+        assert!(parse_rdio_datetime("yesterday").is_none());
+        assert!(parse_rdio_datetime("").is_none());
+    }
+
+    #[test]
+    fn test_apply_trunk_recorder_meta() {
+        let meta = serde_json::json!({
+            "freq": 851012500,
+            "start_time": 1704067200,
+            "stop_time": 1704067205,
+            "call_length": 5,
+            "talkgroup": 52198,
+            "talkgroup_tag": "Fire Dispatch",
+            "talkgroup_group": "Fire",
+            "talkgroup_group_tag": "Fire-Tac",
+            "short_name": "metro",
+            "freqList": [{"freq": 851012500, "time": 1704067200, "pos": 0.0, "len": 5.0}],
+            "srcList": [{"src": 1234567, "time": 1704067200, "pos": 0.0, "tag": "ENGINE 4"}],
+            "patched_talkgroups": [52198, 52199]
+        });
+
+        let mut metadata = CallMetadata::default();
+        metadata.apply_trunk_recorder_meta(&meta);
+
+        assert_eq!(metadata.system_id.as_deref(), Some("metro"));
+        assert_eq!(metadata.datetime.unwrap().timestamp(), 1704067200);
+        assert_eq!(metadata.talkgroup_id, Some(52198));
+        assert_eq!(metadata.talkgroup_label.as_deref(), Some("Fire Dispatch"));
+        assert_eq!(metadata.talkgroup_group.as_deref(), Some("Fire"));
+        assert_eq!(metadata.talkgroup_tag.as_deref(), Some("Fire-Tac"));
+        assert_eq!(metadata.frequency, Some(851012500));
+        assert_eq!(metadata.duration, Some(5.0));
+        assert_eq!(metadata.source_radio_id, Some(1234567));
+        assert_eq!(metadata.talker_alias.as_deref(), Some("ENGINE 4"));
+        assert_eq!(metadata.sources.unwrap()[0]["src"], 1234567);
+        assert_eq!(metadata.frequencies.unwrap()[0]["freq"], 851012500);
+        assert_eq!(metadata.patches.unwrap()[1], 52199);
+    }
+
+    #[test]
+    fn test_apply_trunk_recorder_meta_keeps_form_fields() {
+        let meta = serde_json::json!({
+            "short_name": "metro",
+            "talkgroup": 52198,
+            "freq": 851012500
+        });
+
+        let mut metadata = CallMetadata {
+            system_id: Some("1".to_string()),
+            talkgroup_id: Some(100),
+            ..CallMetadata::default()
+        };
+        metadata.apply_trunk_recorder_meta(&meta);
+
+        assert_eq!(metadata.system_id.as_deref(), Some("1"));
+        assert_eq!(metadata.talkgroup_id, Some(100));
+        assert_eq!(metadata.frequency, Some(851012500));
+    }
+
+    #[test]
+    fn test_call_location() {
+        let geo = GeoConfig {
+            systems: vec![sdrtrunk_protocol::config::SystemLocation {
+                system_id: "metro".to_string(),
+                latitude: 35.7796,
+                longitude: -78.6382,
+            }],
+        };
+        assert_eq!(
+            call_location(Some((36.0, -79.0)), &geo, "metro"),
+            Some((36.0, -79.0))
+        );
+
+        // Missing or out-of-range site coordinates fall back to the system's
+        assert_eq!(
+            call_location(Some((91.0, -79.0)), &geo, "metro"),
+            Some((35.7796, -78.6382))
+        );
+        assert_eq!(
+            call_location(None, &geo, "metro"),
+            Some((35.7796, -78.6382))
+        );
+        assert_eq!(call_location(None, &geo, "county"), None);
+    }
 }
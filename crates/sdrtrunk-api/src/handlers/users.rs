@@ -0,0 +1,253 @@
+//! User account administration handlers
+
+use crate::{
+    error::ApiError,
+    handlers::{admin::DeleteApiKeyResponse, auth::hash_password},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use sdrtrunk_storage::{NewUser, User, UserQueries, UserUpdate};
+use sdrtrunk_types::UserRole;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Longest allowed username
+const MAX_USERNAME_LEN: usize = 100;
+/// Shortest allowed password
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// Request to create a user
+#[derive(Debug, Deserialize)]
+pub struct CreateUserRequest {
+    /// Login name
+    pub username: String,
+    /// Password
+    pub password: String,
+    /// What the user may do
+    pub role: UserRole,
+}
+
+/// Request to change a user; omitted fields are left as they are
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateUserRequest {
+    /// New role
+    pub role: Option<UserRole>,
+    /// Enable or disable the account
+    pub active: Option<bool>,
+    /// New password
+    pub password: Option<String>,
+}
+
+/// Response containing one user
+#[derive(Debug, Serialize)]
+pub struct UserResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The user
+    pub user: User,
+}
+
+/// Response listing users
+#[derive(Debug, Serialize)]
+pub struct UserListResponse {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// Users, oldest first
+    pub users: Vec<User>,
+}
+
+/// Check a new username and password
+///
+/// # Errors
+///
+/// Returns error describing the first problem found
+fn validate_credentials(username: Option<&str>, password: Option<&str>) -> Result<(), String> {
+    if username.is_some_and(|u| u.trim().is_empty() || u.len() > MAX_USERNAME_LEN) {
+        return Err(format!(
+            "Username must be 1 to {MAX_USERNAME_LEN} characters"
+        ));
+    }
+    if password.is_some_and(|p| p.chars().count() < MIN_PASSWORD_LEN) {
+        return Err(format!(
+            "Password must be at least {MIN_PASSWORD_LEN} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Hash `password` for storage
+///
+/// # Errors
+///
+/// Returns error if hashing fails
+fn password_hash(password: &str) -> Result<String, ApiError> {
+    hash_password(password).map_err(|e| {
+        error!("Failed to hash password: {e}");
+        ApiError::internal(
+            "PASSWORD_HASH_FAILED",
+            format!("Failed to hash password: {e}"),
+        )
+    })
+}
+
+/// List users
+///
+/// # Errors
+///
+/// Returns error if database operation fails
+pub async fn list_users(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<UserListResponse>, ApiError> {
+    match UserQueries::list(&state.pool).await {
+        Ok(users) => Ok(Json(UserListResponse {
+            success: true,
+            users,
+        })),
+        Err(e) => {
+            error!("Failed to list users: {e}");
+            Err(ApiError::database(format!("Failed to list users: {e}")))
+        }
+    }
+}
+
+/// Create a user
+///
+/// # Errors
+///
+/// Returns error if the username or password is invalid, the username is
+/// taken, or the database operation fails
+pub async fn create_user(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateUserRequest>,
+) -> Result<Json<UserResponse>, ApiError> {
+    validate_credentials(Some(&request.username), Some(&request.password))
+        .map_err(|error| ApiError::bad_request("INVALID_PARAMETERS", error))?;
+    let password_hash = password_hash(&request.password)?;
+
+    match UserQueries::create(
+        &state.pool,
+        &NewUser {
+            username: &request.username,
+            password_hash: &password_hash,
+            role: request.role,
+        },
+    )
+    .await
+    {
+        Ok(user) => {
+            info!("Created user {} with role {}", user.username, user.role);
+            Ok(Json(UserResponse {
+                success: true,
+                user,
+            }))
+        }
+        Err(e) => {
+            error!("Failed to create user {}: {e}", request.username);
+            Err(ApiError::database(format!("Failed to create user: {e}")))
+        }
+    }
+}
+
+/// Change a user's role, status, or password
+///
+/// Disabling a user or changing their password signs them out everywhere.
+///
+/// # Errors
+///
+/// Returns error if the password is invalid, the user is unknown, or the
+/// database operation fails
+pub async fn update_user(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<UpdateUserRequest>,
+) -> Result<Json<UserResponse>, ApiError> {
+    validate_credentials(None, request.password.as_deref())
+        .map_err(|error| ApiError::bad_request("INVALID_PARAMETERS", error))?;
+    let password_hash = request.password.as_deref().map(password_hash).transpose()?;
+
+    let update = UserUpdate {
+        role: request.role,
+        active: request.active,
+        password_hash: password_hash.as_deref(),
+    };
+    match UserQueries::update(&state.pool, user_id, &update).await {
+        Ok(Some(user)) => {
+            info!("Updated user {}", user.username);
+            Ok(Json(UserResponse {
+                success: true,
+                user,
+            }))
+        }
+        Ok(None) => Err(ApiError::not_found(
+            "USER_NOT_FOUND",
+            format!("No user {user_id}"),
+        )),
+        Err(e) => {
+            error!("Failed to update user {user_id}: {e}");
+            Err(ApiError::database(format!("Failed to update user: {e}")))
+        }
+    }
+}
+
+/// Delete a user and end their sessions
+///
+/// # Errors
+///
+/// Returns error if the user is unknown or the database operation fails
+pub async fn delete_user(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<DeleteApiKeyResponse>, ApiError> {
+    match UserQueries::delete(&state.pool, user_id).await {
+        Ok(true) => {
+            info!("Deleted user {user_id}");
+            Ok(Json(DeleteApiKeyResponse {
+                success: true,
+                message: format!("User {user_id} has been deleted"),
+            }))
+        }
+        Ok(false) => Err(ApiError::not_found(
+            "USER_NOT_FOUND",
+            format!("No user {user_id}"),
+        )),
+        Err(e) => {
+            error!("Failed to delete user {user_id}: {e}");
+            Err(ApiError::database(format!("Failed to delete user: {e}")))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_credentials() {
+        assert!(validate_credentials(Some("dispatch"), Some("long enough")).is_ok());
+        assert!(validate_credentials(Some("  "), Some("long enough")).is_err());
+        assert!(validate_credentials(Some(&"a".repeat(101)), None).is_err());
+        assert!(validate_credentials(None, Some("short")).is_err());
+        assert!(validate_credentials(None, None).is_ok());
+    }
+
+    #[test]
+    fn test_create_user_request_roles() {
+        let request: CreateUserRequest = serde_json::from_str(
+            r#"{"username": "dispatch", "password": "long enough", "role": "read_only"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.role, UserRole::ReadOnly);
+        assert!(
+            serde_json::from_str::<CreateUserRequest>(
+                r#"{"username": "dispatch", "password": "long enough", "role": "root"}"#,
+            )
+            .is_err()
+        );
+    }
+}
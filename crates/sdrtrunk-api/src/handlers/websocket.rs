@@ -8,26 +8,40 @@ use axum::{
     response::Response,
 };
 use futures_util::{SinkExt, StreamExt};
+use sdrtrunk_storage::{PgPool, TranscriptionProgress};
+use sdrtrunk_types::SystemId;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::broadcast;
-use tracing::{info, warn};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
-use crate::state::AppState;
+use crate::{state::AppState, tenant::TenantScope};
+
+/// Calls whose visibility a scoped connection remembers before starting over
+const CALL_SCOPE_CACHE_CAPACITY: usize = 1024;
 
 /// WebSocket event types that can be broadcast to clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum WebSocketEvent {
     /// New call received
+    ///
+    /// Sent for each stored upload while the `live_listen` feature is on.
     #[serde(rename = "new_call")]
     NewCall {
         /// Call ID
-        call_id: uuid::Uuid,
+        call_id: Uuid,
         /// System ID
         system_id: String,
         /// Talkgroup ID
         talkgroup_id: Option<i32>,
+        /// Talkgroup display name
+        #[serde(default)]
+        talkgroup_label: Option<String>,
+        /// Transcription status the call was stored with
+        #[serde(default)]
+        transcription_status: Option<String>,
         /// Timestamp
         timestamp: chrono::DateTime<chrono::Utc>,
     },
@@ -35,7 +49,7 @@ pub enum WebSocketEvent {
     #[serde(rename = "transcription_update")]
     TranscriptionUpdate {
         /// Call ID
-        call_id: uuid::Uuid,
+        call_id: Uuid,
         /// New status
         status: String,
         /// Confidence score (if completed)
@@ -61,22 +75,87 @@ pub enum WebSocketEvent {
         /// Calls in last hour
         calls_last_hour: i32,
     },
+    /// Transcription lifecycle event published by a worker
+    #[serde(rename = "transcription_progress")]
+    TranscriptionProgress(TranscriptionProgress),
 }
 
 /// WebSocket handler
+///
+/// Connections opened with an API key limited to specific systems only receive
+/// events for calls from those systems.
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    scope: TenantScope,
 ) -> Response {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, scope))
+}
+
+/// Decides which events a connection may receive
+struct EventScope {
+    scope: TenantScope,
+    /// Whether each recently seen call is visible
+    calls: HashMap<Uuid, bool>,
+}
+
+impl EventScope {
+    fn new(scope: TenantScope) -> Self {
+        Self {
+            scope,
+            calls: HashMap::new(),
+        }
+    }
+
+    /// Whether `event` may be sent to the connection
+    async fn allows(&mut self, pool: &PgPool, event: &WebSocketEvent) -> bool {
+        if !self.scope.is_restricted() {
+            return true;
+        }
+        match event {
+            WebSocketEvent::NewCall { system_id, .. }
+            | WebSocketEvent::SystemStatus { system_id, .. } => self.allows_system(system_id),
+            WebSocketEvent::StatsUpdate { system_id, .. } => system_id
+                .as_deref()
+                .is_some_and(|system_id| self.allows_system(system_id)),
+            WebSocketEvent::TranscriptionUpdate { call_id, .. } => {
+                self.allows_call(pool, *call_id).await
+            }
+            WebSocketEvent::TranscriptionProgress(progress) => {
+                self.allows_call(pool, progress.call_id).await
+            }
+        }
+    }
+
+    fn allows_system(&self, system_id: &str) -> bool {
+        SystemId::new(system_id).is_ok_and(|system_id| self.scope.allows(&system_id))
+    }
+
+    async fn allows_call(&mut self, pool: &PgPool, call_id: Uuid) -> bool {
+        if let Some(&visible) = self.calls.get(&call_id) {
+            return visible;
+        }
+        let visible = match sdrtrunk_storage::get_radio_call(pool, call_id).await {
+            Ok(call) => call.is_some_and(|call| self.scope.allows(&call.system_id)),
+            Err(e) => {
+                warn!("Failed to look up call {call_id} for WebSocket event: {e}");
+                return false;
+            }
+        };
+        if self.calls.len() >= CALL_SCOPE_CACHE_CAPACITY {
+            self.calls.clear();
+        }
+        let _ = self.calls.insert(call_id, visible);
+        visible
+    }
 }
 
 /// Handle WebSocket connection
-async fn handle_socket(socket: WebSocket, _state: Arc<AppState>) {
+async fn handle_socket(socket: WebSocket, state: Arc<AppState>, scope: TenantScope) {
     let (mut sender, mut receiver) = socket.split();
 
-    // Create a broadcast channel for this connection
-    let (_tx, mut rx) = broadcast::channel::<WebSocketEvent>(100);
+    // Subscribe to events shared by all connections
+    let mut rx = state.events.subscribe();
 
     info!("WebSocket client connected");
 
@@ -92,8 +171,21 @@ async fn handle_socket(socket: WebSocket, _state: Arc<AppState>) {
     }
 
     // Spawn task to handle incoming messages from client
+    let pool = state.pool.clone();
+    let mut event_scope = EventScope::new(scope);
     let mut send_task = tokio::spawn(async move {
-        while let Ok(event) = rx.recv().await {
+        loop {
+            let event = match rx.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!("WebSocket client lagged, skipped {} events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            if !event_scope.allows(&pool, &event).await {
+                continue;
+            }
             if let Ok(json) = serde_json::to_string(&event)
                 && sender.send(Message::Text(json)).await.is_err()
             {
@@ -139,13 +231,11 @@ async fn handle_socket(socket: WebSocket, _state: Arc<AppState>) {
 
 /// Broadcast an event to all connected WebSocket clients
 ///
-/// This would be called from upload handler, transcription callback, etc.
-#[allow(clippy::unused_async)]
-pub async fn broadcast_event(_event: WebSocketEvent) {
-    // TODO: Implement global broadcast channel
-    // For now, this is a placeholder that would need to be wired
-    // to a global Arc<broadcast::Sender> stored in AppState
-    warn!("WebSocket broadcasting not yet fully wired - event not sent");
+/// Events are dropped when no client is connected.
+pub fn broadcast_event(events: &broadcast::Sender<WebSocketEvent>, event: WebSocketEvent) {
+    if events.send(event).is_err() {
+        debug!("No WebSocket clients connected, event dropped");
+    }
 }
 
 #[cfg(test)]
@@ -196,12 +286,15 @@ mod tests {
             call_id: uuid::Uuid::new_v4(),
             system_id: "test_system".to_string(),
             talkgroup_id: Some(12345),
+            talkgroup_label: Some("Fire Dispatch".to_string()),
+            transcription_status: Some("pending".to_string()),
             timestamp: chrono::Utc::now(),
         };
 
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("new_call"));
         assert!(json.contains("test_system"));
+        assert!(json.contains("Fire Dispatch"));
     }
 
     #[test]
@@ -255,13 +348,64 @@ mod tests {
         if let WebSocketEvent::NewCall {
             system_id,
             talkgroup_id,
+            talkgroup_label,
             ..
         } = event
         {
             assert_eq!(system_id, "test");
             assert_eq!(talkgroup_id, Some(123));
+            assert_eq!(talkgroup_label, None);
         } else {
             panic!("Wrong event type");
         }
     }
+
+    #[test]
+    fn test_transcription_progress_event() {
+        use sdrtrunk_storage::ProgressStage;
+
+        let call_id = uuid::Uuid::new_v4();
+        let event = WebSocketEvent::TranscriptionProgress(TranscriptionProgress::new(
+            call_id,
+            None,
+            ProgressStage::Segment {
+                index: 2,
+                start_ms: 3000,
+                end_ms: 4500,
+                text: "copy that".to_string(),
+            },
+        ));
+
+        let json: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "transcription_progress");
+        assert_eq!(json["stage"], "segment");
+        assert_eq!(json["call_id"], call_id.to_string());
+        assert_eq!(json["text"], "copy that");
+
+        let decoded: WebSocketEvent = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            decoded,
+            WebSocketEvent::TranscriptionProgress(TranscriptionProgress { call_id: id, .. }) if id == call_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_event_reaches_subscribers() {
+        let (events, _) = broadcast::channel(8);
+        let event = WebSocketEvent::StatsUpdate {
+            system_id: None,
+            total_calls: 1,
+            calls_last_hour: 1,
+        };
+
+        // No subscribers: dropped without error
+        broadcast_event(&events, event.clone());
+
+        let mut rx = events.subscribe();
+        broadcast_event(&events, event);
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            WebSocketEvent::StatsUpdate { total_calls: 1, .. }
+        ));
+    }
 }
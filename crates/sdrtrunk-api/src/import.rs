@@ -0,0 +1,512 @@
+//! Bulk import of historical `SDRTrunk` recordings
+//!
+//! Started with `sdrtrunk-api-server --import-recordings <dir> --import-system
+//! <system_id> [--import-rate <calls per second>]`. Walks the directory tree
+//! for recordings named the way `SDRTrunk` names them
+//! (`20240101_123456Metro_Site_1__TO_52198_FROM_1234567.mp3`), stores and
+//! inserts each one as a call, queues it for transcription behind live
+//! uploads, and exits without starting the server. Recordings already
+//! imported for the system are recognised by their SHA-256 and skipped, so an
+//! interrupted import resumes where it stopped when re-run.
+
+use crate::{handlers::audio_utils, state::AppState};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use sdrtrunk_protocol::Config;
+use sdrtrunk_storage::{
+    CallEventKind, CallEventQueries, JobQueue, PgPool, RadioQueries, jobs::EnqueueParams,
+    legacy::refresh_system_stats, models::RadioCallDb, queries::RadioCallQueries, recording_key,
+};
+use sdrtrunk_types::{RadioId, SystemId, TalkgroupId, TranscriptionStatus};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::{Interval, MissedTickBehavior};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Command-line flag selecting the directory of recordings to import
+pub const IMPORT_FLAG: &str = "--import-recordings";
+
+/// Command-line flag giving the system the recordings belong to
+pub const SYSTEM_FLAG: &str = "--import-system";
+
+/// Command-line flag limiting how many calls are imported per second
+pub const RATE_FLAG: &str = "--import-rate";
+
+/// Calls imported per second unless `--import-rate` says otherwise
+pub const DEFAULT_RATE: u32 = 10;
+
+/// API key ID recorded on imported calls
+pub const IMPORT_KEY_ID: &str = "recording-import";
+
+/// Job priority of imported calls, below live uploads (0)
+const IMPORT_PRIORITY: i32 = -10;
+
+/// Calls between progress log lines
+const PROGRESS_INTERVAL: usize = 500;
+
+/// Recording import options parsed from the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingImportArgs {
+    /// Directory searched for recordings, including subdirectories
+    pub dir: PathBuf,
+    /// System the imported calls are filed under
+    pub system_id: SystemId,
+    /// Calls imported per second (0 for no limit)
+    pub rate: u32,
+}
+
+/// Counts from a recording import run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordingImportSummary {
+    /// Calls inserted
+    pub imported: usize,
+    /// Recordings skipped because they were already imported
+    pub duplicates: usize,
+    /// Files skipped because their name could not be parsed
+    pub invalid: usize,
+    /// Imported calls queued for transcription
+    pub queued: usize,
+}
+
+/// Call details encoded in an `SDRTrunk` recording file name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingName {
+    /// When the call started, read in the server's local time zone
+    pub timestamp: DateTime<Utc>,
+    /// System, site, and channel text between the timestamp and `TO`
+    pub label: Option<String>,
+    /// Talkgroup (`TO_`)
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Transmitting radio (`FROM_`)
+    pub source_radio_id: Option<RadioId>,
+}
+
+impl RecordingName {
+    /// Parse a file name such as
+    /// `20240101_123456Metro_Site_1__TO_52198_FROM_1234567.mp3`
+    ///
+    /// Only the leading `YYYYMMDD_HHMMSS` timestamp is required. Returns
+    /// `None` when it is missing or invalid.
+    #[must_use]
+    pub fn parse(file_name: &str) -> Option<Self> {
+        let stem = Path::new(file_name).file_stem()?.to_str()?;
+        let timestamp = NaiveDateTime::parse_from_str(stem.get(..15)?, "%Y%m%d_%H%M%S").ok()?;
+        let timestamp = Local
+            .from_local_datetime(&timestamp)
+            .earliest()?
+            .with_timezone(&Utc);
+        let rest = stem.get(15..)?;
+
+        let (label, addressing) = rest.find("_TO_").map_or((rest, ""), |at| {
+            (rest.get(..at).unwrap_or(rest), rest.get(at..).unwrap_or(""))
+        });
+        let label = label.trim_matches('_').replace('_', " ");
+
+        Some(Self {
+            timestamp,
+            label: (!label.is_empty()).then_some(label),
+            talkgroup_id: leading_number(addressing, "_TO_")
+                .and_then(|id| TalkgroupId::new(id).ok()),
+            source_radio_id: leading_number(addressing, "_FROM_")
+                .and_then(|id| RadioId::new(id).ok()),
+        })
+    }
+}
+
+/// The number right after `marker` in `text`
+fn leading_number(text: &str, marker: &str) -> Option<i32> {
+    let start = text.find(marker)? + marker.len();
+    let digits: String = text
+        .get(start..)?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Parse recording import options from the command line
+///
+/// Returns `None` when no import was requested.
+///
+/// # Errors
+///
+/// Returns an error if a flag is given without a value, the system ID or rate
+/// is invalid, or a directory is given without a system.
+pub fn import_requested<I, S>(args: I) -> Result<Option<RecordingImportArgs>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut dir = None;
+    let mut system = None;
+    let mut rate = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let target = match arg.as_ref() {
+            IMPORT_FLAG => &mut dir,
+            SYSTEM_FLAG => &mut system,
+            RATE_FLAG => &mut rate,
+            _ => continue,
+        };
+        let value = args
+            .next()
+            .ok_or_else(|| anyhow!("{} requires a value", arg.as_ref()))?;
+        *target = Some(value.as_ref().to_string());
+    }
+
+    let Some(dir) = dir else {
+        if system.is_some() || rate.is_some() {
+            return Err(anyhow!("{SYSTEM_FLAG} and {RATE_FLAG} need {IMPORT_FLAG}"));
+        }
+        return Ok(None);
+    };
+    let system = system.ok_or_else(|| anyhow!("{IMPORT_FLAG} requires {SYSTEM_FLAG}"))?;
+    let system_id =
+        SystemId::new(system.trim()).map_err(|e| anyhow!("invalid {SYSTEM_FLAG}: {e}"))?;
+    let rate = rate.map_or(Ok(DEFAULT_RATE), |rate| {
+        rate.trim()
+            .parse()
+            .map_err(|e| anyhow!("invalid {RATE_FLAG} {rate}: {e}"))
+    })?;
+
+    Ok(Some(RecordingImportArgs {
+        dir: PathBuf::from(dir),
+        system_id,
+        rate,
+    }))
+}
+
+/// Import every recording under `args.dir`
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or a database write or
+/// storage upload fails. Files whose name cannot be parsed are logged and
+/// skipped.
+pub async fn run_import(
+    config: Config,
+    pool: PgPool,
+    args: &RecordingImportArgs,
+) -> Result<RecordingImportSummary> {
+    let dir = args.dir.clone();
+    let extensions = config.storage.allowed_extensions.clone();
+    let recordings =
+        tokio::task::spawn_blocking(move || find_recordings(&dir, &extensions)).await??;
+    info!(
+        "Importing {} recordings from {} into system {}",
+        recordings.len(),
+        args.dir.display(),
+        args.system_id
+    );
+
+    let state = AppState::new(config, pool)?;
+    let mut pace = pacing(args.rate);
+    let mut summary = RecordingImportSummary::default();
+
+    for (index, path) in recordings.iter().enumerate() {
+        if let Some(pace) = pace.as_mut() {
+            let _ = pace.tick().await;
+        }
+        import_recording(&state, &args.system_id, path, &mut summary).await?;
+        if (index + 1) % PROGRESS_INTERVAL == 0 {
+            log_progress(index + 1, recordings.len(), summary);
+        }
+    }
+
+    if summary.imported > 0 {
+        refresh_system_stats(&state.pool, &args.system_id).await?;
+    }
+    info!(
+        "Recording import complete: {} imported, {} duplicate, {} invalid, {} queued for transcription",
+        summary.imported, summary.duplicates, summary.invalid, summary.queued
+    );
+    Ok(summary)
+}
+
+/// Log how far an import has got
+fn log_progress(done: usize, total: usize, summary: RecordingImportSummary) {
+    info!(
+        "Recording import progress: {done}/{total} files, {} imported, {} duplicate, {} invalid",
+        summary.imported, summary.duplicates, summary.invalid
+    );
+}
+
+/// Timer spacing imports `rate` per second, `None` for no limit
+fn pacing(rate: u32) -> Option<Interval> {
+    (rate > 0).then(|| {
+        let mut interval = tokio::time::interval(Duration::from_secs(1) / rate);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    })
+}
+
+/// Import one recording file
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, stored, or inserted.
+async fn import_recording(
+    state: &AppState,
+    system_id: &SystemId,
+    path: &Path,
+    summary: &mut RecordingImportSummary,
+) -> Result<()> {
+    let Some((file_name, name)) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|file_name| Some((file_name, RecordingName::parse(file_name)?)))
+    else {
+        warn!(
+            "Skipping {}: not an SDRTrunk recording name",
+            path.display()
+        );
+        summary.invalid += 1;
+        return Ok(());
+    };
+
+    let audio = tokio::fs::read(path).await?;
+    let audio_sha256 = format!("{:x}", Sha256::digest(&audio));
+    if RadioCallQueries::find_by_audio_hash(&state.pool, system_id, &audio_sha256)
+        .await?
+        .is_some()
+    {
+        summary.duplicates += 1;
+        return Ok(());
+    }
+
+    let key = recording_key(system_id, name.timestamp.date_naive(), file_name);
+    let location = state.audio_storage.put(&key, audio.clone().into()).await?;
+
+    // Recordings on opted-out talkgroups are imported but never queued
+    let skipped = state.config.transcription.as_ref().is_some_and(|t| {
+        t.skips(
+            system_id.as_str(),
+            name.talkgroup_id.map(TalkgroupId::as_i32),
+        )
+    });
+    let mut call = RadioCallDb {
+        audio_sha256: Some(audio_sha256),
+        ..imported_call(system_id, &name, file_name, &audio, location.clone())
+    };
+    if skipped {
+        call.transcription_status = Some(TranscriptionStatus::Skipped.to_string());
+    }
+    let call_id = RadioCallQueries::insert(&state.pool, &call).await?;
+    if let Some(radio_id) = call.source_radio_id {
+        RadioQueries::record_call(
+            &state.pool,
+            &call.system_id,
+            radio_id,
+            call.talker_alias.as_deref(),
+            call.call_timestamp,
+        )
+        .await?;
+    }
+    CallEventQueries::record(
+        &state.pool,
+        call_id,
+        CallEventKind::Received,
+        Utc::now(),
+        Some(&format!("imported from {}", path.display())),
+    )
+    .await?;
+    summary.imported += 1;
+
+    if let Some(transcription) = state
+        .config
+        .transcription
+        .as_ref()
+        .filter(|t| t.enabled && !skipped)
+    {
+        let params = EnqueueParams {
+            call_id,
+            audio_path: Some(location),
+            audio_data: Some(audio),
+            priority: IMPORT_PRIORITY,
+            // Model and language are chosen per system by the worker
+            options: serde_json::json!({"diarize": true}),
+            timeout_seconds: i32::try_from(transcription.timeout_seconds).unwrap_or(300),
+        };
+        let _ = JobQueue::enqueue(&state.pool, &params).await?;
+        summary.queued += 1;
+    }
+    Ok(())
+}
+
+/// Call record for an imported recording
+fn imported_call(
+    system_id: &SystemId,
+    name: &RecordingName,
+    file_name: &str,
+    audio: &[u8],
+    location: String,
+) -> RadioCallDb {
+    let duration = audio_utils::calculate_audio_duration(audio, Some(file_name));
+    RadioCallDb {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        call_timestamp: name.timestamp,
+        system_id: system_id.clone(),
+        system_label: name.label.clone(),
+        frequency: None,
+        talkgroup_id: name.talkgroup_id,
+        talkgroup_label: None,
+        talkgroup_group: None,
+        talkgroup_tag: None,
+        source_radio_id: name.source_radio_id,
+        talker_alias: None,
+        audio_filename: Some(file_name.to_string()),
+        audio_file_path: Some(location),
+        audio_size_bytes: i64::try_from(audio.len()).ok(),
+        audio_content_type: None,
+        audio_sha256: None,
+        duration_seconds: duration.and_then(|d| Decimal::try_from(d).ok()),
+        transcription_text: None,
+        transcription_confidence: None,
+        transcription_language: None,
+        transcription_status: Some("pending".to_string()),
+        speaker_segments: None,
+        speaker_count: None,
+        patches: None,
+        frequencies: None,
+        sources: None,
+        upload_ip: None,
+        upload_timestamp: Utc::now(),
+        upload_api_key_id: Some(IMPORT_KEY_ID.to_string()),
+        latitude: None,
+        longitude: None,
+    }
+}
+
+/// Files under `dir` with one of `extensions`, in path order
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be read.
+fn find_recordings(dir: &Path, extensions: &[String]) -> std::io::Result<Vec<PathBuf>> {
+    let mut recordings = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext.to_lowercase()))
+            {
+                recordings.push(path);
+            }
+        }
+    }
+    recordings.sort();
+    Ok(recordings)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn local(text: &str) -> DateTime<Utc> {
+        let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap();
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_recording_name() {
+        let name =
+            RecordingName::parse("20240101_123456Metro_Site_1__TO_52198_FROM_1234567.mp3").unwrap();
+        assert_eq!(name.timestamp, local("2024-01-01 12:34:56"));
+        assert_eq!(name.label.as_deref(), Some("Metro Site 1"));
+        assert_eq!(name.talkgroup_id, Some(TalkgroupId::new(52198).unwrap()));
+        assert_eq!(name.source_radio_id, Some(RadioId::new(1_234_567).unwrap()));
+
+        let name = RecordingName::parse("20240101_123456_TO_52198.mp3").unwrap();
+        assert_eq!(name.label, None);
+        assert_eq!(name.talkgroup_id, Some(TalkgroupId::new(52198).unwrap()));
+        assert_eq!(name.source_radio_id, None);
+
+        let name = RecordingName::parse("20240101_123456County.wav").unwrap();
+        assert_eq!(name.label.as_deref(), Some("County"));
+        assert_eq!(name.talkgroup_id, None);
+
+        assert_eq!(RecordingName::parse("call.mp3"), None);
+        assert_eq!(RecordingName::parse("20241341_123456_TO_1.mp3"), None);
+    }
+
+    #[test]
+    fn test_import_requested() {
+        assert_eq!(import_requested(["server"]).unwrap(), None);
+        assert_eq!(
+            import_requested(["server", IMPORT_FLAG, "/old", SYSTEM_FLAG, "metro"]).unwrap(),
+            Some(RecordingImportArgs {
+                dir: PathBuf::from("/old"),
+                system_id: SystemId::new("metro").unwrap(),
+                rate: DEFAULT_RATE,
+            })
+        );
+        assert_eq!(
+            import_requested([
+                "server",
+                RATE_FLAG,
+                "0",
+                IMPORT_FLAG,
+                "/old",
+                SYSTEM_FLAG,
+                "metro"
+            ])
+            .unwrap()
+            .unwrap()
+            .rate,
+            0
+        );
+
+        assert!(import_requested(["server", IMPORT_FLAG, "/old"]).is_err());
+        assert!(import_requested(["server", SYSTEM_FLAG, "metro"]).is_err());
+        assert!(import_requested(["server", IMPORT_FLAG, "/old", SYSTEM_FLAG]).is_err());
+        assert!(
+            import_requested([
+                "server",
+                IMPORT_FLAG,
+                "/old",
+                SYSTEM_FLAG,
+                "m",
+                RATE_FLAG,
+                "x"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_find_recordings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("2024/01")).unwrap();
+        std::fs::write(dir.path().join("2024/01/b.MP3"), b"").unwrap();
+        std::fs::write(dir.path().join("a.mp3"), b"").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"").unwrap();
+
+        let found = find_recordings(dir.path(), &["mp3".to_string()]).unwrap();
+        assert_eq!(
+            found,
+            [dir.path().join("2024/01/b.MP3"), dir.path().join("a.mp3")]
+        );
+        assert!(find_recordings(&dir.path().join("missing"), &[]).is_err());
+    }
+}
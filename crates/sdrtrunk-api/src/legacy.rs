@@ -0,0 +1,336 @@
+//! Import from the legacy Python `sdrtrunk-transcriber`
+//!
+//! Started with `sdrtrunk-api-server --import-legacy <recordings.db>
+//! [--legacy-audio-dir <dir>]`. Reads every call from the old `SQLite` database,
+//! copies its recording into the current storage layout, inserts it, and
+//! exits without starting the server. Calls already present are skipped, so
+//! an interrupted import can simply be re-run.
+
+use crate::state::AppState;
+use anyhow::{Result, anyhow};
+use chrono::NaiveDate;
+use sdrtrunk_protocol::Config;
+use sdrtrunk_storage::{
+    AudioStorage, PgPool,
+    legacy::{LegacyCallRow, LegacyDatabase, legacy_call_exists, refresh_system_stats},
+    queries::RadioCallQueries,
+    recording_key,
+};
+use sdrtrunk_types::SystemId;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Command-line flag selecting the legacy database to import
+pub const IMPORT_FLAG: &str = "--import-legacy";
+
+/// Command-line flag giving the directory legacy recordings are relative to
+pub const AUDIO_DIR_FLAG: &str = "--legacy-audio-dir";
+
+/// Rows read from the legacy database per batch
+const BATCH_SIZE: i64 = 500;
+
+/// Legacy import options parsed from the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyImportArgs {
+    /// Path to the legacy `SQLite` database
+    pub database: PathBuf,
+    /// Directory relative recording paths are resolved against
+    ///
+    /// Defaults to the directory containing the database.
+    pub audio_dir: Option<PathBuf>,
+}
+
+/// Counts from a legacy import run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LegacyImportSummary {
+    /// Calls inserted
+    pub imported: usize,
+    /// Calls skipped because they were already present
+    pub duplicates: usize,
+    /// Rows skipped because they could not be converted
+    pub invalid: usize,
+    /// Recordings copied into storage
+    pub audio_copied: usize,
+    /// Imported calls whose recording could not be found
+    pub audio_missing: usize,
+}
+
+/// Parse legacy import options from the command line
+///
+/// Returns `None` when no import was requested.
+///
+/// # Errors
+///
+/// Returns an error if a flag is given without a value.
+pub fn import_requested<I, S>(args: I) -> Result<Option<LegacyImportArgs>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut database = None;
+    let mut audio_dir = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let target = match arg.as_ref() {
+            IMPORT_FLAG => &mut database,
+            AUDIO_DIR_FLAG => &mut audio_dir,
+            _ => continue,
+        };
+        let value = args
+            .next()
+            .ok_or_else(|| anyhow!("{} requires a path", arg.as_ref()))?;
+        *target = Some(PathBuf::from(value.as_ref()));
+    }
+
+    match (database, audio_dir) {
+        (Some(database), audio_dir) => Ok(Some(LegacyImportArgs {
+            database,
+            audio_dir,
+        })),
+        (None, Some(_)) => Err(anyhow!("{AUDIO_DIR_FLAG} requires {IMPORT_FLAG}")),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Import all calls from a legacy database
+///
+/// # Errors
+///
+/// Returns an error if the legacy database cannot be read or a database write
+/// fails. Individual rows that cannot be converted are logged and skipped.
+#[allow(clippy::cognitive_complexity)]
+pub async fn run_import(
+    config: Config,
+    pool: PgPool,
+    args: &LegacyImportArgs,
+) -> Result<LegacyImportSummary> {
+    let legacy = LegacyDatabase::open(&args.database).await?;
+    let total = legacy.count().await?;
+    info!(
+        "Importing {} legacy calls from table '{}' in {}",
+        total,
+        legacy.table(),
+        args.database.display()
+    );
+
+    let audio_dir = args.audio_dir.clone().unwrap_or_else(|| {
+        args.database
+            .parent()
+            .map_or_else(PathBuf::new, Path::to_path_buf)
+    });
+    let state = AppState::new(config, pool)?;
+    let mut summary = LegacyImportSummary::default();
+    let mut systems = BTreeSet::new();
+    let mut after_rowid = 0;
+
+    loop {
+        let rows = legacy.read_batch(after_rowid, BATCH_SIZE).await?;
+        let Some(last) = rows.last() else {
+            break;
+        };
+        after_rowid = last.legacy_rowid;
+
+        for row in &rows {
+            if let Some(system_id) = import_row(&state, row, &audio_dir, &mut summary).await? {
+                let _ = systems.insert(system_id);
+            }
+        }
+        info!(
+            "Legacy import progress: {} imported, {} duplicate, {} invalid",
+            summary.imported, summary.duplicates, summary.invalid
+        );
+    }
+    legacy.close().await;
+
+    for system_id in &systems {
+        refresh_system_stats(&state.pool, system_id).await?;
+    }
+
+    info!(
+        "Legacy import complete: {} imported, {} duplicate, {} invalid, {} recordings copied, {} recordings missing",
+        summary.imported,
+        summary.duplicates,
+        summary.invalid,
+        summary.audio_copied,
+        summary.audio_missing
+    );
+    Ok(summary)
+}
+
+/// Import one legacy row, returning its system ID if it was inserted
+///
+/// # Errors
+///
+/// Returns an error if a database query or file copy fails.
+async fn import_row(
+    state: &AppState,
+    row: &LegacyCallRow,
+    audio_dir: &Path,
+    summary: &mut LegacyImportSummary,
+) -> Result<Option<SystemId>> {
+    let mut call = match row.to_radio_call(None, None) {
+        Ok(call) => call,
+        Err(e) => {
+            warn!("Skipping {e}");
+            summary.invalid += 1;
+            return Ok(None);
+        }
+    };
+    if legacy_call_exists(&state.pool, &call).await? {
+        summary.duplicates += 1;
+        return Ok(None);
+    }
+
+    match locate_recording(row, audio_dir) {
+        Some(source) => {
+            let (location, size) = copy_recording(
+                state.audio_storage.as_ref(),
+                &source,
+                &call.system_id,
+                call.call_timestamp.date_naive(),
+            )
+            .await?;
+            call.audio_file_path = Some(location);
+            call.audio_size_bytes = i64::try_from(size).ok();
+            summary.audio_copied += 1;
+        }
+        None => summary.audio_missing += 1,
+    }
+
+    let _ = RadioCallQueries::insert(&state.pool, &call).await?;
+    summary.imported += 1;
+    Ok(Some(call.system_id))
+}
+
+/// Find a legacy recording on disk
+///
+/// Tries the recorded path as given, then relative to `audio_dir`, then the
+/// bare file name under `audio_dir` and its talkgroup subdirectory.
+fn locate_recording(row: &LegacyCallRow, audio_dir: &Path) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(path) = row.audio_path.as_deref().map(str::trim) {
+        let path = Path::new(path);
+        candidates.push(path.to_path_buf());
+        if path.is_relative() {
+            candidates.push(audio_dir.join(path));
+        }
+    }
+    if let Some(name) = row.file_name() {
+        candidates.push(audio_dir.join(&name));
+        if let Some(talkgroup) = row.talkgroup_id.as_deref().map(str::trim) {
+            candidates.push(audio_dir.join(talkgroup).join(&name));
+        }
+    }
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// Copy a recording into recording storage, keeping its file name
+///
+/// The recording is stored under the call's system and date, so re-runs
+/// overwrite rather than duplicate it. Returns the stored location and size.
+///
+/// # Errors
+///
+/// Returns an error if the recording cannot be read or stored.
+async fn copy_recording(
+    storage: &dyn AudioStorage,
+    source: &Path,
+    system_id: &SystemId,
+    date: NaiveDate,
+) -> Result<(String, u64)> {
+    let name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("recording path has no file name: {}", source.display()))?;
+    let recording = tokio::fs::read(source).await?;
+    let size = recording.len() as u64;
+    let location = storage
+        .put(&recording_key(system_id, date, name), recording.into())
+        .await?;
+    Ok((location, size))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use sdrtrunk_storage::LocalAudioStorage;
+
+    #[test]
+    fn test_import_requested() {
+        assert_eq!(import_requested(["server"]).unwrap(), None);
+        assert_eq!(
+            import_requested(["server", IMPORT_FLAG, "/old/recordings.db"]).unwrap(),
+            Some(LegacyImportArgs {
+                database: PathBuf::from("/old/recordings.db"),
+                audio_dir: None,
+            })
+        );
+        assert_eq!(
+            import_requested([
+                "server",
+                AUDIO_DIR_FLAG,
+                "/old/audio",
+                IMPORT_FLAG,
+                "/old/recordings.db"
+            ])
+            .unwrap()
+            .unwrap()
+            .audio_dir,
+            Some(PathBuf::from("/old/audio"))
+        );
+    }
+
+    #[test]
+    fn test_import_requested_errors() {
+        assert!(import_requested(["server", IMPORT_FLAG]).is_err());
+        assert!(import_requested(["server", AUDIO_DIR_FLAG, "/old/audio"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_locate_and_copy_recording() {
+        let legacy = tempfile::tempdir().unwrap();
+        let storage = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(legacy.path().join("52198")).unwrap();
+        std::fs::write(legacy.path().join("52198/call.mp3"), b"ID3audio").unwrap();
+
+        let row = LegacyCallRow {
+            talkgroup_id: Some("52198".to_string()),
+            audio_filename: Some("call.mp3".to_string()),
+            ..LegacyCallRow::default()
+        };
+        let source = locate_recording(&row, legacy.path()).unwrap();
+        assert_eq!(source, legacy.path().join("52198/call.mp3"));
+
+        let recordings = LocalAudioStorage::new(storage.path(), "uploads");
+        let system_id = SystemId::new("metro").unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let (location, size) = copy_recording(&recordings, &source, &system_id, date)
+            .await
+            .unwrap();
+        let dest = storage.path().join("uploads/metro/2024/01/01/call.mp3");
+        assert_eq!(location, dest.to_string_lossy());
+        assert_eq!(size, 8);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"ID3audio");
+
+        // Re-running stores the recording in the same place
+        let (again, _) = copy_recording(&recordings, &source, &system_id, date)
+            .await
+            .unwrap();
+        assert_eq!(again, location);
+
+        let missing = LegacyCallRow {
+            audio_path: Some("gone.mp3".to_string()),
+            ..LegacyCallRow::default()
+        };
+        assert_eq!(locate_recording(&missing, legacy.path()), None);
+    }
+}
@@ -1,14 +1,46 @@
 //! `SDRTrunk` API server library
 
 #![forbid(unsafe_code)]
-
+// The hand-written OpenAPI spec is a single `json!` invocation
+#![recursion_limit = "256"]
+
+pub mod alerts;
+pub mod demo;
+pub mod encryption;
+pub mod error;
+pub mod extractors;
+pub mod features;
+pub mod fingerprint;
 pub mod handlers;
+pub mod import;
+pub mod legacy;
+pub mod live_transcription;
+pub mod mail;
+pub mod maintenance;
+pub mod middleware;
+pub mod migrate;
+pub mod notifications;
 pub mod openapi;
+pub mod progress;
+pub mod quotas;
+pub mod reload;
+pub mod reports;
+pub mod resumable;
+pub mod retention;
 pub mod routes;
+pub mod scheduler;
+pub mod search_index;
+pub mod server;
 pub mod state;
-// pub mod middleware; // Disabled for minimal build
-// pub mod extractors; // Disabled for minimal build
-
+pub mod stats_cache;
+pub mod subtitles;
+pub mod summarizer;
+pub mod tenant;
+pub mod waveform;
+pub mod webhooks;
+pub mod worker_metrics;
+
+pub use error::ApiError;
 pub use state::AppState;
 
 use anyhow::Result;
@@ -22,19 +54,62 @@ use std::sync::Arc;
 /// # Errors
 ///
 /// Returns an error if the application state validation fails.
-#[allow(clippy::unused_async)]
 pub async fn build_router(config: Config, pool: PgPool) -> Result<Router> {
-    let app_state = AppState::new(config, pool)?;
+    let (app, _state) = build_app(config, pool.clone(), pool).await?;
+    Ok(app)
+}
+
+/// Build the API router along with the state it serves
+///
+/// Heavy read-only queries go to `read_pool`, which may be the same pool as
+/// `pool` when no read replica is configured.
+///
+/// # Errors
+///
+/// Returns an error if the application state validation fails.
+#[allow(clippy::unused_async)]
+pub async fn build_app(
+    config: Config,
+    pool: PgPool,
+    read_pool: PgPool,
+) -> Result<(Router, Arc<AppState>)> {
+    let app_state = AppState::new(config, pool)?.with_read_pool(read_pool);
 
     let state = Arc::new(app_state);
 
     // Validate the application state
     state.validate()?;
 
-    // Build the complete router with all routes
-    let app = routes::build_router().with_state(state);
-
-    Ok(app)
+    // Relay worker progress notifications to WebSocket clients
+    drop(progress::spawn_progress_relay(
+        state.pool.clone(),
+        state.events.clone(),
+    ));
+
+    drop(middleware::rate_limit::spawn_cleanup_task(
+        state.rate_limiter.clone(),
+    ));
+
+    // Build the complete router with all routes; API keys are validated
+    // before rate limiting so each key gets its own bucket (failed keys are
+    // charged to the client IP's bucket during validation), and CORS runs
+    // first so preflights are answered without a key
+    let app = routes::build_router()
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::rate_limit::rate_limit,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::auth::authenticate,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::cors::cors,
+        ))
+        .with_state(state.clone());
+
+    Ok((routes::versioned(app), state))
 }
 
 /// Build a minimal router for testing (without authentication)
@@ -0,0 +1,431 @@
+//! Live transcription of streamed audio
+//!
+//! With `live_transcription.enabled` set, clients stream raw 16-bit
+//! little-endian mono PCM over the `/api/transcribe/live` WebSocket (for
+//! example from a scanner's audio output) and receive transcripts as they
+//! go. A [`Segmenter`] cuts the stream into segments of `segment_seconds`;
+//! each segment is sent to `{url}/audio/transcriptions` as a WAV file once
+//! complete, and re-sent every `partial_interval_seconds` while it grows so
+//! the client sees interim text. Any endpoint speaking the `OpenAI`
+//! transcriptions API works: `OpenAI`, or a local faster-whisper server.
+//! Nothing is stored; live audio never enters the call pipeline.
+
+use anyhow::{Context, Result, anyhow};
+use reqwest::multipart::{Form, Part};
+use sdrtrunk_protocol::config::LiveTranscriptionConfig;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Lowest sample rate accepted from clients
+pub const MIN_SAMPLE_RATE: u32 = 8000;
+/// Highest sample rate accepted from clients
+pub const MAX_SAMPLE_RATE: u32 = 48000;
+/// Sample rate assumed when the client does not give one
+pub const DEFAULT_SAMPLE_RATE: u32 = 16000;
+/// Characters of earlier transcript sent as context with each segment
+const PROMPT_CHARS: usize = 200;
+
+/// Transcriptions API response, reduced to the text
+#[derive(Debug, Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+/// Sends live audio segments to the transcriptions API
+#[derive(Debug, Clone)]
+pub struct LiveTranscriber {
+    http: reqwest::Client,
+    config: LiveTranscriptionConfig,
+    sessions: Arc<Semaphore>,
+}
+
+impl LiveTranscriber {
+    /// Create a transcriber from the live transcription settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(config: &LiveTranscriptionConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds.max(1)))
+            .build()
+            .context("Failed to build live transcription client")?;
+        Ok(Self {
+            http,
+            config: config.clone(),
+            sessions: Arc::new(Semaphore::new(config.max_sessions)),
+        })
+    }
+
+    /// Live transcription settings
+    #[must_use]
+    pub const fn config(&self) -> &LiveTranscriptionConfig {
+        &self.config
+    }
+
+    /// Reserve a session slot, or `None` when `max_sessions` are running
+    ///
+    /// The slot is freed when the permit is dropped.
+    #[must_use]
+    pub fn start_session(&self) -> Option<OwnedSemaphorePermit> {
+        Arc::clone(&self.sessions).try_acquire_owned().ok()
+    }
+
+    /// Transcribe PCM `samples` recorded at `sample_rate`
+    ///
+    /// `prompt` is earlier transcript text from the same stream, which helps
+    /// the model carry words and spelling across segment boundaries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API answers with an
+    /// error.
+    pub async fn transcribe(
+        &self,
+        samples: &[i16],
+        sample_rate: u32,
+        language: Option<&str>,
+        prompt: Option<&str>,
+    ) -> Result<String> {
+        let url = format!(
+            "{}/audio/transcriptions",
+            self.config.url.trim_end_matches('/')
+        );
+        let file = Part::bytes(wav_bytes(samples, sample_rate))
+            .file_name("live.wav")
+            .mime_str("audio/wav")?;
+        let mut form = Form::new()
+            .part("file", file)
+            .text("model", self.config.model.clone())
+            .text("response_format", "json");
+        if let Some(language) = language.or(self.config.language.as_deref()) {
+            form = form.text("language", language.to_string());
+        }
+        if let Some(prompt) = prompt.filter(|prompt| !prompt.is_empty()) {
+            form = form.text("prompt", prompt_tail(prompt).to_string());
+        }
+        let mut request = self.http.post(&url).multipart(form);
+        if let Some(api_key) = self.config.api_key.as_deref() {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP {status}: {detail}"));
+        }
+        let transcription: TranscriptionResponse = response.json().await?;
+        Ok(transcription.text.trim().to_string())
+    }
+}
+
+/// The last [`PROMPT_CHARS`] characters of `text`
+fn prompt_tail(text: &str) -> &str {
+    let skip = text.chars().count().saturating_sub(PROMPT_CHARS);
+    text.char_indices()
+        .nth(skip)
+        .and_then(|(start, _)| text.get(start..))
+        .unwrap_or(text)
+}
+
+/// Wrap 16-bit mono PCM in a WAV file
+#[must_use]
+pub fn wav_bytes(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_len = u32::try_from(samples.len() * 2).unwrap_or(u32::MAX - 36);
+    let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// Whether a chunk's text may still change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkKind {
+    /// Interim text of the segment still being recorded
+    Partial,
+    /// Text of a complete segment
+    Final,
+}
+
+/// Audio due for transcription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Partial or final
+    pub kind: ChunkKind,
+    /// Segment number, counting from 0
+    pub segment: u32,
+    /// Offset of the segment's first sample from the start of the stream
+    pub start_samples: u64,
+    /// Samples to transcribe
+    pub samples: Vec<i16>,
+}
+
+/// Splits a PCM stream into segments and decides what to transcribe next
+#[derive(Debug)]
+pub struct Segmenter {
+    segment_samples: usize,
+    partial_samples: usize,
+    /// Samples of the current segment
+    samples: Vec<i16>,
+    /// First byte of a sample split across two frames
+    odd_byte: Option<u8>,
+    /// Samples of the current segment covered by the last partial
+    partial_len: usize,
+    segment: u32,
+    start_samples: u64,
+}
+
+impl Segmenter {
+    /// Create a segmenter for a stream recorded at `sample_rate`
+    #[must_use]
+    pub fn new(sample_rate: u32, segment_seconds: u64, partial_interval_seconds: u64) -> Self {
+        let samples_per = |seconds: u64| {
+            usize::try_from(u64::from(sample_rate).saturating_mul(seconds)).unwrap_or(usize::MAX)
+        };
+        Self {
+            segment_samples: samples_per(segment_seconds.max(1)),
+            partial_samples: samples_per(partial_interval_seconds),
+            samples: Vec::new(),
+            odd_byte: None,
+            partial_len: 0,
+            segment: 0,
+            start_samples: 0,
+        }
+    }
+
+    /// Append little-endian PCM bytes
+    pub fn push(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes.iter().copied();
+        if let Some(low) = self.odd_byte.take() {
+            if let Some(high) = bytes.next() {
+                self.samples.push(i16::from_le_bytes([low, high]));
+            } else {
+                self.odd_byte = Some(low);
+                return;
+            }
+        }
+        loop {
+            match (bytes.next(), bytes.next()) {
+                (Some(low), Some(high)) => self.samples.push(i16::from_le_bytes([low, high])),
+                (Some(low), None) => {
+                    self.odd_byte = Some(low);
+                    break;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// The next audio to transcribe, if any is due
+    ///
+    /// A full segment is always due. Otherwise the growing segment is due as
+    /// a partial once `partial_interval_seconds` of audio arrived since the
+    /// last one. With `flush`, whatever audio is left becomes a final chunk.
+    pub fn next_chunk(&mut self, flush: bool) -> Option<Chunk> {
+        if self.samples.len() >= self.segment_samples || (flush && !self.samples.is_empty()) {
+            let len = self.samples.len().min(self.segment_samples);
+            let rest = self.samples.split_off(len);
+            let samples = std::mem::replace(&mut self.samples, rest);
+            let chunk = Chunk {
+                kind: ChunkKind::Final,
+                segment: self.segment,
+                start_samples: self.start_samples,
+                samples,
+            };
+            self.segment += 1;
+            self.start_samples += len as u64;
+            self.partial_len = 0;
+            return Some(chunk);
+        }
+        if self.partial_samples > 0 && self.samples.len() >= self.partial_len + self.partial_samples
+        {
+            self.partial_len = self.samples.len();
+            return Some(Chunk {
+                kind: ChunkKind::Partial,
+                segment: self.segment,
+                start_samples: self.start_samples,
+                samples: self.samples.clone(),
+            });
+        }
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::extract::Multipart;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+
+    fn pcm(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_wav_bytes() {
+        let wav = wav_bytes(&[1, -1], 8000);
+        assert_eq!(wav.len(), 48);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 40);
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 8000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 4);
+        assert_eq!(&wav[44..], &[1, 0, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_prompt_tail() {
+        assert_eq!(prompt_tail("short"), "short");
+        let long = format!("{}é{}", "a".repeat(300), "b".repeat(199));
+        let tail = prompt_tail(&long);
+        assert_eq!(tail.chars().count(), PROMPT_CHARS);
+        assert!(tail.starts_with('é'));
+    }
+
+    #[test]
+    fn test_segmenter_splits_samples_across_frames() {
+        let mut segmenter = Segmenter::new(4, 1, 0);
+        let bytes = pcm(&[1, 2, -3, 4, 5]);
+        segmenter.push(&bytes[..3]);
+        segmenter.push(&bytes[3..3]);
+        segmenter.push(&bytes[3..]);
+        let chunk = segmenter.next_chunk(false).unwrap();
+        assert_eq!(chunk.samples, vec![1, 2, -3, 4]);
+        assert_eq!(segmenter.next_chunk(false), None);
+        assert_eq!(segmenter.next_chunk(true).unwrap().samples, vec![5]);
+    }
+
+    #[test]
+    fn test_segmenter_partials_and_finals() {
+        // 2 samples per second, 3 second segments, partial every second
+        let mut segmenter = Segmenter::new(2, 3, 1);
+        assert_eq!(segmenter.next_chunk(false), None);
+
+        segmenter.push(&pcm(&[1]));
+        assert_eq!(segmenter.next_chunk(false), None);
+        segmenter.push(&pcm(&[2, 3]));
+        let partial = segmenter.next_chunk(false).unwrap();
+        assert_eq!(partial.kind, ChunkKind::Partial);
+        assert_eq!((partial.segment, partial.samples), (0, vec![1, 2, 3]));
+        assert_eq!(segmenter.next_chunk(false), None);
+
+        // A full segment and the start of the next arrive at once
+        segmenter.push(&pcm(&[4, 5, 6, 7, 8]));
+        let last = segmenter.next_chunk(false).unwrap();
+        assert_eq!(last.kind, ChunkKind::Final);
+        assert_eq!(
+            (last.segment, last.start_samples, last.samples),
+            (0, 0, vec![1, 2, 3, 4, 5, 6])
+        );
+        let partial = segmenter.next_chunk(false).unwrap();
+        assert_eq!(
+            (partial.kind, partial.segment, partial.start_samples),
+            (ChunkKind::Partial, 1, 6)
+        );
+        assert_eq!(partial.samples, vec![7, 8]);
+
+        let flushed = segmenter.next_chunk(true).unwrap();
+        assert_eq!(
+            (flushed.kind, flushed.segment, flushed.samples),
+            (ChunkKind::Final, 1, vec![7, 8])
+        );
+        assert_eq!(segmenter.next_chunk(true), None);
+    }
+
+    #[test]
+    fn test_session_limit() {
+        let transcriber = LiveTranscriber::new(&LiveTranscriptionConfig {
+            max_sessions: 1,
+            ..LiveTranscriptionConfig::default()
+        })
+        .unwrap();
+        let session = transcriber.start_session().unwrap();
+        assert!(transcriber.start_session().is_none());
+        drop(session);
+        assert!(transcriber.start_session().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_transcribe() {
+        let app = Router::new().route(
+            "/v1/audio/transcriptions",
+            post(|headers: HeaderMap, mut form: Multipart| async move {
+                assert_eq!(headers["authorization"], "Bearer sk-test");
+                let mut fields = HashMap::new();
+                while let Some(field) = form.next_field().await.unwrap() {
+                    let name = field.name().unwrap().to_string();
+                    fields.insert(name, field.bytes().await.unwrap());
+                }
+                assert_eq!(&fields["model"][..], b"whisper-1");
+                assert_eq!(&fields["language"][..], b"en");
+                assert_eq!(&fields["prompt"][..], b"engine five");
+                assert_eq!(&fields["file"][..4], b"RIFF");
+                axum::Json(json!({"text": " on scene "}))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let transcriber = LiveTranscriber::new(&LiveTranscriptionConfig {
+            enabled: true,
+            url: format!("{base}/v1/"),
+            api_key: Some("sk-test".to_string()),
+            language: Some("en".to_string()),
+            ..LiveTranscriptionConfig::default()
+        })
+        .unwrap();
+        let text = transcriber
+            .transcribe(&[0; 160], 16000, None, Some("engine five"))
+            .await
+            .unwrap();
+        assert_eq!(text, "on scene");
+
+        let unreachable = LiveTranscriber::new(&LiveTranscriptionConfig {
+            url: format!("{base}/missing"),
+            ..LiveTranscriptionConfig::default()
+        })
+        .unwrap();
+        let error = unreachable
+            .transcribe(&[0; 160], 16000, None, None)
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(&StatusCode::NOT_FOUND.as_u16().to_string())
+        );
+    }
+}
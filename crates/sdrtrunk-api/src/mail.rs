@@ -0,0 +1,250 @@
+//! Outgoing email
+//!
+//! A minimal SMTP client for alert notifications and digest reports. Mail is
+//! handed to the configured relay unauthenticated and unencrypted, one
+//! connection per message.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Utc;
+use sdrtrunk_protocol::config::SmtpConfig;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// Whether `address` is a plain `local@domain` address safe to put in SMTP
+/// commands and headers
+#[must_use]
+pub fn is_valid_email(address: &str) -> bool {
+    let Some((local, domain)) = address.split_once('@') else {
+        return false;
+    };
+    address.len() <= 254
+        && !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@')
+        && !address
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | ',' | '"'))
+}
+
+/// Format of an email body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// Plain text
+    Text,
+    /// HTML
+    Html,
+}
+
+impl BodyFormat {
+    /// MIME type of the body
+    const fn content_type(self) -> &'static str {
+        match self {
+            Self::Text => "text/plain",
+            Self::Html => "text/html",
+        }
+    }
+}
+
+/// Send an email through an SMTP relay
+///
+/// # Errors
+///
+/// Returns an error if an address is invalid, the relay cannot be reached, or
+/// it rejects the message.
+pub async fn send_email(
+    smtp: &SmtpConfig,
+    to: &str,
+    subject: &str,
+    body: &str,
+    format: BodyFormat,
+) -> Result<()> {
+    if !is_valid_email(to) || !is_valid_email(&smtp.from) {
+        bail!("invalid email address");
+    }
+
+    let stream = TcpStream::connect((smtp.host.as_str(), smtp.port))
+        .await
+        .with_context(|| format!("failed to connect to {}:{}", smtp.host, smtp.port))?;
+    let mut conn = BufReader::new(stream);
+    smtp_reply(&mut conn, 220).await?;
+    smtp_command(&mut conn, "EHLO sdrtrunk-transcriber", 250).await?;
+    smtp_command(&mut conn, &format!("MAIL FROM:<{}>", smtp.from), 250).await?;
+    smtp_command(&mut conn, &format!("RCPT TO:<{to}>"), 250).await?;
+    smtp_command(&mut conn, "DATA", 354).await?;
+    conn.get_mut()
+        .write_all(email_message(&smtp.from, to, subject, body, format).as_bytes())
+        .await?;
+    smtp_reply(&mut conn, 250).await?;
+    // The message is accepted; a failed QUIT does not matter
+    let _ = smtp_command(&mut conn, "QUIT", 221).await;
+    Ok(())
+}
+
+/// Send one SMTP command and check the reply
+///
+/// # Errors
+///
+/// Returns an error if the connection fails or the reply is unexpected.
+async fn smtp_command(conn: &mut BufReader<TcpStream>, command: &str, expected: u16) -> Result<()> {
+    conn.get_mut()
+        .write_all(format!("{command}\r\n").as_bytes())
+        .await?;
+    smtp_reply(conn, expected)
+        .await
+        .with_context(|| command.split(':').next().unwrap_or(command).to_string())
+}
+
+/// Read a (possibly multi-line) SMTP reply and check its code class
+///
+/// # Errors
+///
+/// Returns an error if the connection fails or the reply is unexpected.
+async fn smtp_reply(conn: &mut BufReader<TcpStream>, expected: u16) -> Result<()> {
+    loop {
+        let mut line = String::new();
+        if conn.read_line(&mut line).await? == 0 {
+            bail!("SMTP connection closed");
+        }
+        let code: u16 = line
+            .get(..3)
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| anyhow!("malformed SMTP reply: {}", line.trim_end()))?;
+        if line.as_bytes().get(3) == Some(&b'-') {
+            continue;
+        }
+        if code / 100 != expected / 100 {
+            bail!("SMTP server replied {}", line.trim_end());
+        }
+        return Ok(());
+    }
+}
+
+/// Format a message for the SMTP `DATA` command, including the final `.`
+fn email_message(from: &str, to: &str, subject: &str, body: &str, format: BodyFormat) -> String {
+    // Header values must be single-line ASCII
+    let subject: String = subject
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect();
+    let mut message = format!(
+        "From: <{from}>\r\nTo: <{to}>\r\nSubject: {subject}\r\nDate: {}\r\n\
+         MIME-Version: 1.0\r\nContent-Type: {}; charset=utf-8\r\n\
+         Content-Transfer-Encoding: 8bit\r\n\r\n",
+        Utc::now().to_rfc2822(),
+        format.content_type()
+    );
+    for line in body.lines() {
+        // Dot-stuffing, so a line holding "." does not end the message
+        if line.starts_with('.') {
+            message.push('.');
+        }
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    message.push_str(".\r\n");
+    message
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email("dispatch@example.com"));
+        assert!(!is_valid_email("dispatch"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("a@localhost"));
+        assert!(!is_valid_email("a@b.com>\r\nRCPT TO:<c@d.com"));
+        assert!(!is_valid_email("a b@example.com"));
+    }
+
+    #[test]
+    fn test_email_message() {
+        let message = email_message(
+            "alerts@example.com",
+            "ops@example.com",
+            "Fire\r\nBcc: x@y.com",
+            "line one\n.\n..two",
+            BodyFormat::Text,
+        );
+
+        assert!(message.contains("Subject: Fire??Bcc: x@y.com\r\n"));
+        assert!(message.contains("Content-Type: text/plain; charset=utf-8\r\n"));
+        assert!(message.ends_with("\r\n\r\nline one\r\n..\r\n...two\r\n.\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_send_email() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut conn = BufReader::new(stream);
+            let mut received = Vec::new();
+            conn.get_mut().write_all(b"220 ready\r\n").await.unwrap();
+            for reply in [
+                "250-relay\r\n250 8BITMIME\r\n",
+                "250 ok\r\n",
+                "250 ok\r\n",
+                "354 go\r\n",
+            ] {
+                let mut line = String::new();
+                conn.read_line(&mut line).await.unwrap();
+                received.push(line.trim_end().to_string());
+                conn.get_mut().write_all(reply.as_bytes()).await.unwrap();
+            }
+            loop {
+                let mut line = String::new();
+                conn.read_line(&mut line).await.unwrap();
+                if line == ".\r\n" {
+                    break;
+                }
+            }
+            conn.get_mut().write_all(b"250 queued\r\n").await.unwrap();
+            received
+        });
+
+        let smtp = SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            from: "alerts@example.com".to_string(),
+        };
+        send_email(
+            &smtp,
+            "ops@example.com",
+            "Subject",
+            "Body",
+            BodyFormat::Text,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            server.await.unwrap(),
+            [
+                "EHLO sdrtrunk-transcriber",
+                "MAIL FROM:<alerts@example.com>",
+                "RCPT TO:<ops@example.com>",
+                "DATA"
+            ]
+        );
+    }
+}
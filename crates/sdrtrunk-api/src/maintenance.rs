@@ -0,0 +1,241 @@
+//! Background database maintenance
+//!
+//! When `maintenance.auto_analyze` is enabled, periodically re-analyzes tables
+//! whose planner statistics have drifted after bulk imports or purges.
+//!
+//! Independently, every `maintenance.stats_rollup_interval_seconds` (or on
+//! `schedules.stats_rollup`) the per-system counters in `system_stats` are
+//! recomputed from `radio_calls`, correcting drift in the counts kept up to
+//! date on each upload.
+//!
+//! Every `maintenance.partition_interval_seconds` (or on
+//! `schedules.partitions`) the monthly `radio_calls` partitions for the
+//! current month and the next `partition_premake_months` are created, and
+//! empty partitions for months past call retention are dropped.
+
+use crate::scheduler::{self, Schedule};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use sdrtrunk_protocol::{
+    config::{MaintenanceConfig, RetentionConfig},
+    schedule::CronSchedule,
+};
+use sdrtrunk_storage::{MaintenanceQueries, PartitionQueries, PgPool, queries::SystemStatsQueries};
+use tokio::{sync::watch, task::JoinHandle};
+use tracing::info;
+
+/// Spawn the maintenance task if automatic analyze is enabled
+///
+/// Runs on `schedule` when given, else every `check_interval_seconds`.
+#[must_use]
+pub fn spawn_maintenance_task(
+    pool: PgPool,
+    config: MaintenanceConfig,
+    schedule: Option<&CronSchedule>,
+) -> Option<JoinHandle<()>> {
+    if !config.auto_analyze {
+        return None;
+    }
+
+    let schedule = Schedule::from_config(schedule, config.check_interval_seconds);
+    let job_pool = pool.clone();
+    Some(scheduler::spawn_job(
+        pool,
+        "auto_analyze",
+        schedule,
+        move || {
+            let pool = job_pool.clone();
+            let config = config.clone();
+            async move { run_auto_analyze(&pool, &config).await }
+        },
+    ))
+}
+
+/// Analyze tables past the configured thresholds
+///
+/// # Errors
+///
+/// Returns error message if table statistics cannot be read or ANALYZE fails
+async fn run_auto_analyze(pool: &PgPool, config: &MaintenanceConfig) -> Result<String, String> {
+    match MaintenanceQueries::analyze_stale(pool, config.analyze_min_rows, config.analyze_ratio)
+        .await
+    {
+        Ok(analyzed) if analyzed.is_empty() => Ok("No tables needed analyzing".to_string()),
+        Ok(analyzed) => {
+            let message = format!("Analyzed tables: {}", analyzed.join(", "));
+            info!("{message}");
+            Ok(message)
+        }
+        Err(e) => Err(format!("Database maintenance failed: {e}")),
+    }
+}
+
+/// Spawn the system statistics rollup task
+///
+/// Runs on `schedule` when given, else every `stats_rollup_interval_seconds`
+/// unless that is 0. Interval rollups start immediately, so counters are
+/// corrected on startup.
+#[must_use]
+pub fn spawn_stats_rollup_task(
+    pool: PgPool,
+    config: &MaintenanceConfig,
+    schedule: Option<&CronSchedule>,
+) -> Option<JoinHandle<()>> {
+    if schedule.is_none() && config.stats_rollup_interval_seconds == 0 {
+        return None;
+    }
+
+    let schedule = Schedule::from_config(schedule, config.stats_rollup_interval_seconds);
+    let job_pool = pool.clone();
+    Some(scheduler::spawn_job(
+        pool,
+        "stats_rollup",
+        schedule,
+        move || {
+            let pool = job_pool.clone();
+            async move {
+                SystemStatsQueries::rollup(&pool, None)
+                    .await
+                    .map(|systems| format!("Rolled up statistics for {systems} systems"))
+                    .map_err(|e| format!("System stats rollup failed: {e}"))
+            }
+        },
+    ))
+}
+
+/// Spawn the call partition maintenance task
+///
+/// Runs on `schedule` when given, else every `partition_interval_seconds`
+/// unless that is 0. Interval runs start immediately, so the current month's
+/// partition exists from startup. Empty months are only dropped while
+/// retention (as currently configured in `retention`) purges calls.
+#[must_use]
+pub fn spawn_partition_task(
+    pool: PgPool,
+    config: &MaintenanceConfig,
+    retention: watch::Receiver<RetentionConfig>,
+    schedule: Option<&CronSchedule>,
+) -> Option<JoinHandle<()>> {
+    if schedule.is_none() && config.partition_interval_seconds == 0 {
+        return None;
+    }
+
+    let premake_months = config.partition_premake_months;
+    let schedule = Schedule::from_config(schedule, config.partition_interval_seconds);
+    let job_pool = pool.clone();
+    Some(scheduler::spawn_job(
+        pool,
+        "partitions",
+        schedule,
+        move || {
+            let pool = job_pool.clone();
+            let retention = retention.borrow().clone();
+            async move {
+                run_partition_maintenance(&pool, premake_months, &retention)
+                    .await
+                    .map_err(|e| format!("Partition maintenance failed: {e}"))
+            }
+        },
+    ))
+}
+
+/// Create upcoming monthly partitions and drop empty expired ones
+///
+/// # Errors
+///
+/// Returns error if a partition cannot be created or dropped
+async fn run_partition_maintenance(
+    pool: &PgPool,
+    premake_months: u32,
+    retention: &RetentionConfig,
+) -> sdrtrunk_storage::Result<String> {
+    let now = Utc::now();
+    let mut created = Vec::new();
+    for month in upcoming_months(now, premake_months) {
+        if PartitionQueries::create_month(pool, month).await? {
+            created.push(month.format("%Y-%m").to_string());
+        }
+    }
+
+    let dropped = match expired_before(now, retention) {
+        Some(before) => PartitionQueries::drop_empty_before(pool, before).await?,
+        None => Vec::new(),
+    };
+
+    if created.is_empty() && dropped.is_empty() {
+        return Ok("Partitions up to date".to_string());
+    }
+    let message = format!(
+        "Created partitions for [{}]; dropped [{}]",
+        created.join(", "),
+        dropped.join(", ")
+    );
+    info!("{message}");
+    Ok(message)
+}
+
+/// First days of the month of `now` and the `premake_months` after it
+fn upcoming_months(now: DateTime<Utc>, premake_months: u32) -> Vec<NaiveDate> {
+    let current = now
+        .date_naive()
+        .with_day(1)
+        .unwrap_or_else(|| now.date_naive());
+    (0..=premake_months)
+        .filter_map(|offset| current.checked_add_months(Months::new(offset)))
+        .collect()
+}
+
+/// Months ending by this are past call retention, or `None` while retention
+/// keeps calls forever
+fn expired_before(now: DateTime<Utc>, retention: &RetentionConfig) -> Option<DateTime<Utc>> {
+    (retention.enabled && retention.call_retention_days > 0)
+        .then(|| now - chrono::Duration::days(i64::from(retention.call_retention_days)))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_upcoming_months() {
+        let now = Utc.with_ymd_and_hms(2025, 11, 30, 23, 0, 0).unwrap();
+        let months: Vec<String> = upcoming_months(now, 3)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            months,
+            ["2025-11-01", "2025-12-01", "2026-01-01", "2026-02-01"]
+        );
+        assert_eq!(upcoming_months(now, 0).len(), 1);
+    }
+
+    #[test]
+    fn test_expired_before() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        let mut retention = RetentionConfig {
+            enabled: true,
+            call_retention_days: 30,
+            ..RetentionConfig::default()
+        };
+        assert_eq!(
+            expired_before(now, &retention),
+            Some(Utc.with_ymd_and_hms(2025, 5, 2, 0, 0, 0).unwrap())
+        );
+
+        retention.call_retention_days = 0;
+        assert_eq!(expired_before(now, &retention), None);
+
+        retention.call_retention_days = 30;
+        retention.enabled = false;
+        assert_eq!(expired_before(now, &retention), None);
+    }
+}
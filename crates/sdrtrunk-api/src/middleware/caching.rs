@@ -0,0 +1,281 @@
+//! Conditional requests for polled JSON and recordings
+//!
+//! Dashboards poll listings and statistics that rarely change between
+//! polls. [`etag`] tags every successful JSON `GET` response with a weak
+//! `ETag` derived from its body, and answers a matching `If-None-Match` with
+//! an empty `304 Not Modified`. The `ETag` is weak because compression runs
+//! outside this layer, so the bytes on the wire differ per encoding.
+//!
+//! Recordings never change once stored, so the audio endpoint validates
+//! requests itself with [`not_modified`] before fetching anything, using
+//! the recording's SHA-256 and upload time.
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// Largest JSON body buffered to compute an `ETag`
+const MAX_TAGGED_BODY: usize = 32 * 1024 * 1024;
+
+/// `Cache-Control` for tagged JSON: cache, but revalidate on every use
+///
+/// Private because responses are scoped to the caller's key.
+const JSON_CACHE_CONTROL: &str = "private, no-cache";
+
+/// Format of an HTTP date (`Last-Modified`, `If-Modified-Since`)
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Tag JSON `GET` responses with an `ETag` and answer revalidations with 304
+pub async fn etag(request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|t| t.starts_with("application/json"));
+    if response.status() != StatusCode::OK
+        || !is_json
+        || response.headers().contains_key(header::ETAG)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_TAGGED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to buffer response for ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let tag = format!("W/\"{}\"", body_digest(&bytes));
+    let Ok(tag) = HeaderValue::from_str(&tag) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let _ = parts
+        .headers
+        .entry(header::CACHE_CONTROL)
+        .or_insert(HeaderValue::from_static(JSON_CACHE_CONTROL));
+
+    if if_none_match.is_some_and(|value| etag_matches(&value, &tag)) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        let headers = not_modified.headers_mut();
+        let _ = headers.insert(header::ETAG, tag);
+        if let Some(cache_control) = parts.headers.get(header::CACHE_CONTROL) {
+            let _ = headers.insert(header::CACHE_CONTROL, cache_control.clone());
+        }
+        return not_modified;
+    }
+    let _ = parts.headers.insert(header::ETAG, tag);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// First 128 bits of the body's SHA-256, in hex
+fn body_digest(bytes: &[u8]) -> String {
+    let mut hex = format!("{:x}", Sha256::digest(bytes));
+    hex.truncate(32);
+    hex
+}
+
+/// Whether an `If-None-Match` value names `tag`
+///
+/// Comparison is weak, as RFC 9110 requires for `If-None-Match`: `W/"x"`
+/// matches `"x"`.
+fn etag_matches(if_none_match: &HeaderValue, tag: &HeaderValue) -> bool {
+    let Ok(value) = if_none_match.to_str() else {
+        return false;
+    };
+    let Ok(tag) = tag.to_str() else {
+        return false;
+    };
+    let opaque = |t: &str| t.trim().trim_start_matches("W/").to_string();
+    let tag = opaque(tag);
+    value
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == tag)
+}
+
+/// Validators of a resource that never changes once stored
+#[derive(Debug, Clone)]
+pub struct Validators {
+    /// Strong `ETag`, quoted
+    pub etag: Option<HeaderValue>,
+    /// When the resource was stored
+    pub last_modified: DateTime<Utc>,
+}
+
+impl Validators {
+    /// Validators for content identified by `digest` (e.g. a SHA-256),
+    /// stored at `last_modified`
+    #[must_use]
+    pub fn new(digest: Option<&str>, last_modified: DateTime<Utc>) -> Self {
+        Self {
+            etag: digest.and_then(|d| HeaderValue::from_str(&format!("\"{d}\"")).ok()),
+            last_modified,
+        }
+    }
+
+    /// Add the validators and `cache_control` to a successful response,
+    /// keeping any validator it already has
+    pub fn apply(&self, response: &mut Response, cache_control: &'static str) {
+        if !response.status().is_success() {
+            return;
+        }
+        let headers = response.headers_mut();
+        if let Some(etag) = &self.etag {
+            let _ = headers.entry(header::ETAG).or_insert(etag.clone());
+        }
+        if let Ok(date) = HeaderValue::from_str(&http_date(self.last_modified)) {
+            let _ = headers.entry(header::LAST_MODIFIED).or_insert(date);
+        }
+        let _ = headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(cache_control),
+        );
+    }
+}
+
+/// A `304 Not Modified` if the request's conditional headers show the
+/// client already has this version
+///
+/// `If-None-Match` takes precedence over `If-Modified-Since`, as RFC 9110
+/// requires.
+#[must_use]
+pub fn not_modified(headers: &HeaderMap, validators: &Validators) -> Option<Response> {
+    let fresh = match (headers.get(header::IF_NONE_MATCH), &validators.etag) {
+        (Some(if_none_match), Some(etag)) => etag_matches(if_none_match, etag),
+        (Some(_), None) => false,
+        (None, _) => headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+            .is_some_and(|since| validators.last_modified.timestamp() <= since.timestamp()),
+    };
+    if !fresh {
+        return None;
+    }
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    if let Some(etag) = &validators.etag {
+        let _ = response.headers_mut().insert(header::ETAG, etag.clone());
+    }
+    Some(response)
+}
+
+/// Format a time as an HTTP date
+fn http_date(time: DateTime<Utc>) -> String {
+    time.format(HTTP_DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, middleware::from_fn, routing::get};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/json",
+                get(|| async { Json(serde_json::json!({"calls": []})) }),
+            )
+            .route("/text", get(|| async { "plain" }))
+            .layer(from_fn(etag))
+    }
+
+    async fn get_with(path: &str, if_none_match: Option<&str>) -> Response {
+        let mut request = Request::get(path);
+        if let Some(value) = if_none_match {
+            request = request.header(header::IF_NONE_MATCH, value);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_json_revalidation() {
+        let response = get_with("/json", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            JSON_CACHE_CONTROL
+        );
+        let tag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(tag.starts_with("W/\""));
+
+        let response = get_with("/json", Some(&tag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], tag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        let response = get_with("/json", Some("\"stale\"")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_non_json_untouched() {
+        let response = get_with("/text", None).await;
+        assert!(!response.headers().contains_key(header::ETAG));
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        let tag = HeaderValue::from_static("\"abc\"");
+        for value in ["\"abc\"", "W/\"abc\"", "\"x\", \"abc\"", "*"] {
+            assert!(
+                etag_matches(&HeaderValue::from_static(value), &tag),
+                "{value}"
+            );
+        }
+        assert!(!etag_matches(&HeaderValue::from_static("\"abcd\""), &tag));
+    }
+
+    #[test]
+    fn test_not_modified() {
+        let stored = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let validators = Validators::new(Some("abc"), stored);
+        let headers = |name, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(name, HeaderValue::from_str(value).unwrap());
+            headers
+        };
+
+        assert!(not_modified(&HeaderMap::new(), &validators).is_none());
+        let response = not_modified(&headers(header::IF_NONE_MATCH, "\"abc\""), &validators);
+        assert_eq!(response.unwrap().status(), StatusCode::NOT_MODIFIED);
+        assert!(not_modified(&headers(header::IF_NONE_MATCH, "\"x\""), &validators).is_none());
+
+        let since = headers(header::IF_MODIFIED_SINCE, &http_date(stored));
+        assert!(not_modified(&since, &validators).is_some());
+        let earlier = headers(
+            header::IF_MODIFIED_SINCE,
+            &http_date(stored - chrono::Duration::seconds(1)),
+        );
+        assert!(not_modified(&earlier, &validators).is_none());
+    }
+}
@@ -0,0 +1,333 @@
+//! API version negotiation
+//!
+//! Every API route is served under `/api/v{N}/...`, and the unversioned
+//! `/api/...` paths that `SDRTrunk` and other uploaders already use stay as
+//! aliases of [`DEFAULT_VERSION`]. A request picks its version from the path
+//! prefix, else an `API-Version` header, else an
+//! `application/vnd.sdrtrunk.v{N}+json` `Accept` type; asking for a version
+//! that is not in [`VERSIONS`] gets 400.
+//!
+//! Versioned paths are rewritten to the unversioned route table before
+//! routing, so path-based policies (authentication, CORS, rate limits) see one
+//! path per endpoint, and handlers that change between versions read the
+//! negotiated [`ApiVersion`] from the request extensions. Responses name the
+//! version in `API-Version`; deprecated versions add `Deprecation`, their
+//! `Sunset` date, and a `Link` to the latest version.
+
+use crate::ApiError;
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderName, HeaderValue, Uri, header, uri::PathAndQuery},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Header a client may send to pick a version, and that names the version
+/// used on every response
+pub const VERSION_HEADER: HeaderName = HeaderName::from_static("api-version");
+
+/// Version used when a request does not ask for one
+pub const DEFAULT_VERSION: u16 = 1;
+
+/// API versions this server answers, oldest first
+pub const VERSIONS: &[VersionInfo] = &[VersionInfo {
+    version: 1,
+    deprecated: false,
+    sunset: None,
+}];
+
+/// Prefix of the versioned vendor media type in `Accept`
+const MEDIA_TYPE_PREFIX: &str = "application/vnd.sdrtrunk.v";
+
+/// A supported API version and its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// Version number, as in `/api/v1`
+    pub version: u16,
+    /// Whether clients should move to a later version
+    pub deprecated: bool,
+    /// HTTP date after which the version may be removed
+    pub sunset: Option<&'static str>,
+}
+
+/// The API version negotiated for a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiVersion(pub u16);
+
+impl Default for ApiVersion {
+    fn default() -> Self {
+        Self(DEFAULT_VERSION)
+    }
+}
+
+/// Negotiate the API version and route versioned paths to their endpoints
+pub async fn negotiate(mut request: Request, next: Next) -> Response {
+    let (path_version, unversioned) = match split_version(request.uri().path()) {
+        Some((version, rest)) => (Some(version), Some(rest)),
+        None => (None, None),
+    };
+    let requested = match path_version {
+        Some(version) => Some(version),
+        None => match requested_version(request.headers()) {
+            Ok(version) => version,
+            Err(error) => return error.into_response(),
+        },
+    };
+    let version = requested.unwrap_or(DEFAULT_VERSION);
+    let Some(info) = VERSIONS.iter().find(|info| info.version == version) else {
+        return unsupported(&version.to_string()).into_response();
+    };
+
+    if let Some(path) = unversioned {
+        match rewrite_path(request.uri(), &path) {
+            Some(uri) => *request.uri_mut() = uri,
+            None => {
+                return ApiError::bad_request("INVALID_PATH", "Request path is not valid")
+                    .into_response();
+            }
+        }
+    }
+    let _ = request.extensions_mut().insert(ApiVersion(version));
+
+    let mut response = next.run(request).await;
+    apply_version_headers(response.headers_mut(), *info, latest_version());
+    response
+}
+
+/// Split `/api/v{N}/rest` into `N` and the unversioned `/api/rest`
+fn split_version(path: &str) -> Option<(u16, String)> {
+    let rest = path.strip_prefix("/api/v")?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (number, tail) = rest.split_at(digits);
+    if number.is_empty() || !(tail.is_empty() || tail.starts_with('/')) {
+        return None;
+    }
+    let version = number.parse().ok()?;
+    Some((version, format!("/api{tail}")))
+}
+
+/// Version asked for by the `API-Version` header or a vendor `Accept` type
+///
+/// # Errors
+///
+/// Returns an error if the requested version is not a number.
+fn requested_version(headers: &HeaderMap) -> Result<Option<u16>, ApiError> {
+    if let Some(value) = headers.get(&VERSION_HEADER) {
+        let value = value.to_str().unwrap_or_default().trim();
+        let number = value.strip_prefix(['v', 'V']).unwrap_or(value);
+        return number.parse().map(Some).map_err(|_| unsupported(value));
+    }
+    let accepted = headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|media_type| {
+            let media_type = media_type.split(';').next()?.trim();
+            media_type
+                .strip_prefix(MEDIA_TYPE_PREFIX)?
+                .strip_suffix("+json")
+                .map(str::to_owned)
+        });
+    accepted.map_or(Ok(None), |number| {
+        number.parse().map(Some).map_err(|_| unsupported(&number))
+    })
+}
+
+/// Replace the path of `uri`, keeping its query
+fn rewrite_path(uri: &Uri, path: &str) -> Option<Uri> {
+    let path_and_query = uri
+        .query()
+        .map_or_else(|| path.to_owned(), |query| format!("{path}?{query}"));
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+/// Newest supported version
+fn latest_version() -> u16 {
+    VERSIONS
+        .iter()
+        .map(|info| info.version)
+        .max()
+        .unwrap_or(DEFAULT_VERSION)
+}
+
+/// Name the version on a response and flag it when deprecated
+fn apply_version_headers(headers: &mut HeaderMap, info: VersionInfo, latest: u16) {
+    let _ = headers.insert(VERSION_HEADER, HeaderValue::from(info.version));
+    if !info.deprecated {
+        return;
+    }
+    let _ = headers.insert("deprecation", HeaderValue::from_static("true"));
+    if let Some(sunset) = info.sunset {
+        let _ = headers.insert("sunset", HeaderValue::from_static(sunset));
+    }
+    if let Ok(link) = HeaderValue::from_str(&format!("</api/v{latest}>; rel=\"successor-version\""))
+    {
+        let _ = headers.insert(header::LINK, link);
+    }
+}
+
+/// Error for a version this server does not answer
+fn unsupported(requested: &str) -> ApiError {
+    let supported = VERSIONS
+        .iter()
+        .map(|info| format!("v{}", info.version))
+        .collect::<Vec<_>>()
+        .join(", ");
+    ApiError::bad_request(
+        "UNSUPPORTED_API_VERSION",
+        format!("API version {requested} is not supported (supported: {supported})"),
+    )
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, routing::get};
+    use http::StatusCode;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let inner = Router::new().route("/api", get(|| async { "info" })).route(
+            "/api/calls",
+            get(|request: Request| async move {
+                let version = request.extensions().get::<ApiVersion>().copied();
+                format!(
+                    "{}|{}",
+                    request.uri(),
+                    version.map_or(0, |ApiVersion(version)| version)
+                )
+            }),
+        );
+        Router::new().fallback_service(
+            tower::ServiceBuilder::new()
+                .layer(axum::middleware::from_fn(negotiate))
+                .service(inner),
+        )
+    }
+
+    async fn send(request: http::Request<Body>) -> (StatusCode, HeaderMap, String) {
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn get_request(uri: &str) -> http::Request<Body> {
+        http::Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_split_version() {
+        assert_eq!(
+            split_version("/api/v1/calls"),
+            Some((1, "/api/calls".to_string()))
+        );
+        assert_eq!(split_version("/api/v2"), Some((2, "/api".to_string())));
+        assert_eq!(split_version("/api/calls"), None);
+        assert_eq!(split_version("/api/videos"), None);
+        assert_eq!(split_version("/api/v1x/calls"), None);
+        assert_eq!(split_version("/health"), None);
+    }
+
+    #[tokio::test]
+    async fn test_versioned_path_reaches_unversioned_route() {
+        let (status, headers, body) = send(get_request("/api/v1/calls?limit=5")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "/api/calls?limit=5|1");
+        assert_eq!(headers[&VERSION_HEADER], "1");
+        assert!(headers.get("deprecation").is_none());
+
+        let (status, _, body) = send(get_request("/api/v1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "info");
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_path_uses_default_version() {
+        let (status, headers, body) = send(get_request("/api/calls")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, format!("/api/calls|{DEFAULT_VERSION}"));
+        assert_eq!(
+            headers[&VERSION_HEADER],
+            DEFAULT_VERSION.to_string().as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_from_headers() {
+        let request = http::Request::get("/api/calls")
+            .header("API-Version", "v1")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _, body) = send(request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "/api/calls|1");
+
+        let request = http::Request::get("/api/calls")
+            .header(
+                header::ACCEPT,
+                "text/html, application/vnd.sdrtrunk.v1+json",
+            )
+            .body(Body::empty())
+            .unwrap();
+        let (status, _, body) = send(request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "/api/calls|1");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_version_rejected() {
+        let (status, _, body) = send(get_request("/api/v99/calls")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("UNSUPPORTED_API_VERSION"));
+
+        let request = http::Request::get("/api/calls")
+            .header("API-Version", "latest")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _, body) = send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("UNSUPPORTED_API_VERSION"));
+
+        let request = http::Request::get("/api/calls")
+            .header(header::ACCEPT, "application/vnd.sdrtrunk.v7+json")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _, _) = send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_deprecated_version_headers() {
+        let mut headers = HeaderMap::new();
+        let info = VersionInfo {
+            version: 1,
+            deprecated: true,
+            sunset: Some("Wed, 01 Jul 2026 00:00:00 GMT"),
+        };
+        apply_version_headers(&mut headers, info, 2);
+        assert_eq!(headers[&VERSION_HEADER], "1");
+        assert_eq!(headers["deprecation"], "true");
+        assert_eq!(headers["sunset"], "Wed, 01 Jul 2026 00:00:00 GMT");
+        assert_eq!(
+            headers[header::LINK],
+            "</api/v2>; rel=\"successor-version\""
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Database migration command
+//!
+//! `sdrtrunk-api-server migrate [--dry-run] [--to <version>] [--baseline]`
+//! applies pending schema migrations (or, with `--baseline`, records them as
+//! applied without running them, for databases created before migrations
+//! were recorded) and exits without starting the server. `--to` stops at a
+//! migration version, and `--dry-run` lists what would happen without
+//! touching the database.
+
+use anyhow::{Result, anyhow};
+use clap::{Args, Parser, Subcommand};
+use sdrtrunk_protocol::Config;
+use sdrtrunk_storage::{Database, MigrationQueries, migrations};
+use std::ffi::OsString;
+use tracing::{info, warn};
+
+/// Command-line subcommand selecting the migration command
+pub const MIGRATE_COMMAND: &str = "migrate";
+
+/// Migration options parsed from the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+pub struct MigrateArgs {
+    /// List the pending migrations without applying them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Stop after this migration version (e.g. 20240101000001)
+    #[arg(long, value_name = "VERSION")]
+    pub to: Option<String>,
+
+    /// Record pending migrations as applied without running them, for
+    /// databases whose schema is already up to date
+    #[arg(long)]
+    pub baseline: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sdrtrunk-api-server",
+    about = "SDRTrunk transcriber API server"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Apply, baseline, or list schema migrations without starting the server
+    Migrate(MigrateArgs),
+}
+
+/// Counts from one migration run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Migrations already recorded before the run
+    pub already_applied: usize,
+    /// Migrations applied, baselined, or (in a dry run) pending
+    pub migrations: Vec<String>,
+}
+
+/// Parse migration options from the command line
+///
+/// Returns `None` unless the first argument is `migrate`.
+///
+/// # Errors
+///
+/// Returns clap's error, including help and version output, if the options
+/// are invalid or help was asked for.
+pub fn migrate_requested<I, S>(args: I) -> Result<Option<MigrateArgs>, clap::Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    if args.get(1).is_none_or(|command| command != MIGRATE_COMMAND) {
+        return Ok(None);
+    }
+    let Cli {
+        command: Command::Migrate(migrate),
+    } = Cli::try_parse_from(args)?;
+    Ok(Some(migrate))
+}
+
+/// Apply, baseline, or list the pending migrations
+///
+/// # Errors
+///
+/// Returns an error if the database is unreachable, `--to` names an unknown
+/// version, or a migration fails. Migrations applied before the failure stay
+/// recorded.
+pub async fn run_migrate(config: &Config, args: &MigrateArgs) -> Result<MigrationReport> {
+    let database = Database::new(config)
+        .await
+        .map_err(|e| anyhow!("Database connection failed: {e}"))?;
+    let pool = database.pool();
+
+    let applied = MigrationQueries::applied(pool).await?;
+    let known = migrations::MIGRATIONS;
+    for unknown in applied
+        .iter()
+        .filter(|a| !known.iter().any(|m| m.version() == a.version))
+    {
+        warn!(
+            "Database has migration {} ({}), which this build does not know",
+            unknown.version, unknown.name
+        );
+    }
+    let pending = migrations::pending(&applied, args.to.as_deref())?;
+    let mut report = MigrationReport {
+        already_applied: applied.len(),
+        migrations: Vec::with_capacity(pending.len()),
+    };
+
+    let action = if args.baseline { "baseline" } else { "apply" };
+    for migration in pending {
+        if args.dry_run {
+            info!("Would {action} {}", migration.name);
+        } else if args.baseline {
+            MigrationQueries::baseline(pool, migration).await?;
+            info!("Baselined {}", migration.name);
+        } else {
+            MigrationQueries::apply(pool, migration).await?;
+            info!("Applied {}", migration.name);
+        }
+        report.migrations.push(migration.name.to_string());
+    }
+
+    info!(
+        "Migrations: {} already applied, {} {}{}",
+        report.already_applied,
+        report.migrations.len(),
+        match (args.dry_run, args.baseline) {
+            (true, _) => "pending",
+            (false, true) => "baselined",
+            (false, false) => "applied",
+        },
+        args.to
+            .as_deref()
+            .map_or_else(String::new, |to| format!(" up to {to}"))
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_requested() {
+        assert_eq!(migrate_requested(["sdrtrunk-api-server"]).unwrap(), None);
+        assert_eq!(
+            migrate_requested(["sdrtrunk-api-server", "--demo"]).unwrap(),
+            None
+        );
+
+        assert_eq!(
+            migrate_requested(["sdrtrunk-api-server", "migrate"]).unwrap(),
+            Some(MigrateArgs::default())
+        );
+        assert_eq!(
+            migrate_requested([
+                "sdrtrunk-api-server",
+                "migrate",
+                "--dry-run",
+                "--to",
+                "20240301000001",
+                "--baseline"
+            ])
+            .unwrap(),
+            Some(MigrateArgs {
+                dry_run: true,
+                to: Some("20240301000001".to_string()),
+                baseline: true,
+            })
+        );
+
+        assert!(migrate_requested(["sdrtrunk-api-server", "migrate", "--to"]).is_err());
+        assert!(migrate_requested(["sdrtrunk-api-server", "migrate", "--force"]).is_err());
+    }
+}
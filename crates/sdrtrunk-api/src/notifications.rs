@@ -0,0 +1,659 @@
+//! Chat notifications
+//!
+//! Posts keyword alerts, digest summaries, and transcription failures to the
+//! channels in `[notifications]`: Discord webhooks, Slack incoming webhooks,
+//! and Telegram bots. Each service has a [`ChannelSender`] that turns message
+//! text into its API request. Messages are rendered from the channel's
+//! template for the event, or the built-in one, by replacing `{{field}}`
+//! placeholders. Posts are attempted once; failures are logged.
+
+use crate::alerts::AlertNotification;
+use crate::progress::ProgressEvents;
+use crate::reports::DigestPeriod;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::config::{
+    NotificationChannel, NotificationChannelKind, NotificationEvent, NotificationsConfig,
+};
+use sdrtrunk_storage::models::RadioCallDb;
+use sdrtrunk_storage::{PgPool, ProgressStage, SystemDigest};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Telegram Bot API base URL
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+/// Longest Discord message
+const DISCORD_MAX_CHARS: usize = 2000;
+/// Longest Telegram message
+const TELEGRAM_MAX_CHARS: usize = 4096;
+/// Longest Slack message
+const SLACK_MAX_CHARS: usize = 40_000;
+
+/// Built-in alert message
+const ALERT_TEMPLATE: &str = "Alert \"{{rule}}\" matched \"{{matched}}\" on {{talkgroup}} \
+                              ({{system}}) at {{time}}\n{{transcript}}";
+/// Built-in digest message
+const DIGEST_TEMPLATE: &str = "{{period}} digest for {{system}} ({{start}} to {{end}} UTC): \
+                               {{calls}} calls, {{transcribed}} transcribed, {{failed}} failed \
+                               ({{failure_rate}}), {{alert_hits}} alert hits\n\
+                               Busiest talkgroups: {{talkgroups}}";
+/// Built-in transcription failure message
+const FAILURE_TEMPLATE: &str = "Transcription failed for call {{call_id}} on {{talkgroup}} \
+                                ({{system}}) at {{time}}: {{error}}";
+
+/// A chat service messages are posted to
+pub trait ChannelSender: Send + Sync + std::fmt::Debug {
+    /// Build the request posting `text`
+    fn request(&self, http: &reqwest::Client, text: &str) -> reqwest::RequestBuilder;
+}
+
+/// Posts to a Discord channel webhook
+#[derive(Debug, Clone)]
+struct DiscordSender {
+    webhook_url: String,
+}
+
+impl ChannelSender for DiscordSender {
+    fn request(&self, http: &reqwest::Client, text: &str) -> reqwest::RequestBuilder {
+        http.post(&self.webhook_url)
+            .json(&json!({ "content": truncate(text, DISCORD_MAX_CHARS) }))
+    }
+}
+
+/// Posts to a Slack incoming webhook
+#[derive(Debug, Clone)]
+struct SlackSender {
+    webhook_url: String,
+}
+
+impl ChannelSender for SlackSender {
+    fn request(&self, http: &reqwest::Client, text: &str) -> reqwest::RequestBuilder {
+        http.post(&self.webhook_url)
+            .json(&json!({ "text": truncate(text, SLACK_MAX_CHARS) }))
+    }
+}
+
+/// Sends to a Telegram chat through a bot
+#[derive(Clone)]
+struct TelegramSender {
+    api_url: String,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl std::fmt::Debug for TelegramSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramSender")
+            .field("api_url", &self.api_url)
+            .field("chat_id", &self.chat_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChannelSender for TelegramSender {
+    fn request(&self, http: &reqwest::Client, text: &str) -> reqwest::RequestBuilder {
+        http.post(format!(
+            "{}/bot{}/sendMessage",
+            self.api_url, self.bot_token
+        ))
+        .json(&json!({
+            "chat_id": self.chat_id,
+            "text": truncate(text, TELEGRAM_MAX_CHARS),
+            "disable_web_page_preview": true,
+        }))
+    }
+}
+
+/// Sender for a configured channel
+///
+/// # Errors
+///
+/// Returns an error if a setting the channel's service needs is missing.
+fn sender_for(channel: &NotificationChannel) -> Result<Box<dyn ChannelSender>> {
+    let webhook_url = || {
+        channel
+            .webhook_url
+            .clone()
+            .ok_or_else(|| anyhow!("webhook_url is required"))
+    };
+    Ok(match channel.kind {
+        NotificationChannelKind::Discord => Box::new(DiscordSender {
+            webhook_url: webhook_url()?,
+        }),
+        NotificationChannelKind::Slack => Box::new(SlackSender {
+            webhook_url: webhook_url()?,
+        }),
+        NotificationChannelKind::Telegram => Box::new(TelegramSender {
+            api_url: TELEGRAM_API_URL.to_string(),
+            bot_token: channel
+                .bot_token
+                .clone()
+                .ok_or_else(|| anyhow!("bot_token is required"))?,
+            chat_id: channel
+                .chat_id
+                .clone()
+                .ok_or_else(|| anyhow!("chat_id is required"))?,
+        }),
+    })
+}
+
+/// The first `max` characters of `text`
+fn truncate(text: &str, max: usize) -> &str {
+    text.char_indices()
+        .nth(max)
+        .and_then(|(end, _)| text.get(..end))
+        .unwrap_or(text)
+}
+
+/// An event to post, with the fields its template may use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    event: NotificationEvent,
+    system_id: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Notification {
+    /// A keyword alert
+    #[must_use]
+    pub fn alert(alert: &AlertNotification) -> Self {
+        Self {
+            event: NotificationEvent::Alert,
+            system_id: alert.system_id.to_string(),
+            fields: vec![
+                ("rule", alert.rule_name.clone()),
+                ("matched", alert.matched_text.clone()),
+                (
+                    "system",
+                    system_name(alert.system_label.as_deref(), alert.system_id.as_str()),
+                ),
+                (
+                    "talkgroup",
+                    talkgroup_name(
+                        alert.talkgroup_label.as_deref(),
+                        alert.talkgroup_id.map(|id| id.to_string()),
+                    ),
+                ),
+                ("time", format_time(alert.call_timestamp)),
+                ("call_id", alert.call_id.to_string()),
+                ("transcript", alert.transcript.clone()),
+            ],
+        }
+    }
+
+    /// A system's digest for the period from `start` to `end`
+    #[must_use]
+    pub fn digest(
+        digest: &SystemDigest,
+        period: DigestPeriod,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        let talkgroups = if digest.top_talkgroups.is_empty() {
+            "none".to_string()
+        } else {
+            digest
+                .top_talkgroups
+                .iter()
+                .map(|tg| {
+                    let name = talkgroup_name(
+                        tg.talkgroup_label.as_deref(),
+                        Some(tg.talkgroup_id.to_string()),
+                    );
+                    format!("{name} ({})", tg.calls)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        Self {
+            event: NotificationEvent::Digest,
+            system_id: digest.system_id.to_string(),
+            fields: vec![
+                ("period", period.title().to_string()),
+                (
+                    "system",
+                    system_name(digest.system_label.as_deref(), digest.system_id.as_str()),
+                ),
+                ("start", start.format("%Y-%m-%d %H:%M").to_string()),
+                ("end", end.format("%Y-%m-%d %H:%M").to_string()),
+                ("calls", digest.calls.to_string()),
+                ("transcribed", digest.transcribed.to_string()),
+                ("failed", digest.failed.to_string()),
+                (
+                    "failure_rate",
+                    format!("{:.1}%", digest.failure_rate() * 100.0),
+                ),
+                ("alert_hits", digest.alert_hits.to_string()),
+                ("talkgroups", talkgroups),
+            ],
+        }
+    }
+
+    /// A transcription that failed with no retries left
+    #[must_use]
+    pub fn failure(call: &RadioCallDb, error: &str) -> Self {
+        Self {
+            event: NotificationEvent::Failure,
+            system_id: call.system_id.to_string(),
+            fields: vec![
+                (
+                    "system",
+                    system_name(call.system_label.as_deref(), call.system_id.as_str()),
+                ),
+                (
+                    "talkgroup",
+                    talkgroup_name(
+                        call.talkgroup_label.as_deref(),
+                        call.talkgroup_id.map(|id| id.to_string()),
+                    ),
+                ),
+                ("time", format_time(call.call_timestamp)),
+                ("call_id", call.id.to_string()),
+                ("error", error.to_string()),
+            ],
+        }
+    }
+
+    /// Event of the notification
+    #[must_use]
+    pub const fn event(&self) -> NotificationEvent {
+        self.event
+    }
+
+    /// Fill `template`'s `{{field}}` placeholders
+    ///
+    /// Unknown placeholders are left as they are.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        self.fields
+            .iter()
+            .fold(template.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{{{name}}}}}"), value)
+            })
+    }
+
+    /// Message for `channel`: its template for the event, else the built-in
+    fn message_for(&self, channel: &NotificationChannel) -> String {
+        let template = channel
+            .templates
+            .get(&self.event)
+            .map_or_else(|| default_template(self.event), String::as_str);
+        self.render(template)
+    }
+}
+
+/// Built-in template of `event`
+const fn default_template(event: NotificationEvent) -> &'static str {
+    match event {
+        NotificationEvent::Alert => ALERT_TEMPLATE,
+        NotificationEvent::Digest => DIGEST_TEMPLATE,
+        NotificationEvent::Failure => FAILURE_TEMPLATE,
+    }
+}
+
+/// System label, else its ID
+fn system_name(label: Option<&str>, id: &str) -> String {
+    label.unwrap_or(id).to_string()
+}
+
+/// Talkgroup label, else `TG <id>`
+fn talkgroup_name(label: Option<&str>, id: Option<String>) -> String {
+    match (label, id) {
+        (Some(label), _) => label.to_string(),
+        (None, Some(id)) => format!("TG {id}"),
+        (None, None) => "unknown talkgroup".to_string(),
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+/// Outcome of posting a notification
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PostOutcome {
+    /// Channels that accepted the message
+    pub sent: usize,
+    /// Channels that could not be posted to
+    pub failed: usize,
+}
+
+/// A configured channel with its sender
+#[derive(Debug)]
+struct Channel {
+    config: NotificationChannel,
+    sender: Box<dyn ChannelSender>,
+}
+
+/// Posts notifications to the configured chat channels
+#[derive(Debug)]
+pub struct Notifications {
+    http: reqwest::Client,
+    channels: Vec<Channel>,
+}
+
+impl Notifications {
+    /// Create the channels from the notification settings
+    ///
+    /// Channels missing a setting their service needs are skipped with a
+    /// warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(config: &NotificationsConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds.max(1)))
+            .build()
+            .context("Failed to build notification client")?;
+        let channels = config
+            .channels
+            .iter()
+            .filter_map(|channel| match sender_for(channel) {
+                Ok(sender) => Some(Channel {
+                    config: channel.clone(),
+                    sender,
+                }),
+                Err(e) => {
+                    warn!("Skipping notification channel '{}': {e}", channel.name);
+                    None
+                }
+            })
+            .collect();
+        Ok(Self { http, channels })
+    }
+
+    /// Shared notifications for the configured channels, or `None` when
+    /// there are none
+    #[must_use]
+    pub fn from_config(config: &NotificationsConfig) -> Option<Arc<Self>> {
+        if config.channels.is_empty() {
+            return None;
+        }
+        match Self::new(config) {
+            Ok(notifications) if notifications.channels.is_empty() => None,
+            Ok(notifications) => {
+                info!(
+                    "Posting notifications to {} chat channels",
+                    notifications.channels.len()
+                );
+                Some(Arc::new(notifications))
+            }
+            Err(e) => {
+                warn!("Chat notifications disabled: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Whether any channel subscribes to `event`, for any system
+    #[must_use]
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.channels.iter().any(|channel| {
+            channel.config.events.is_empty() || channel.config.events.contains(&event)
+        })
+    }
+
+    /// Post `notification` to every channel subscribed to it
+    pub async fn notify(&self, notification: &Notification) -> PostOutcome {
+        let channels = self.channels.iter().filter(|channel| {
+            channel
+                .config
+                .wants(notification.event, &notification.system_id)
+        });
+        let results = futures_util::future::join_all(
+            channels
+                .map(|channel| async move { (channel, self.post(channel, notification).await) }),
+        )
+        .await;
+
+        let mut outcome = PostOutcome::default();
+        for (channel, result) in results {
+            match result {
+                Ok(()) => outcome.sent += 1,
+                Err(e) => {
+                    warn!(
+                        "Failed to post {} notification to '{}': {e:#}",
+                        notification.event.as_str(),
+                        channel.config.name
+                    );
+                    outcome.failed += 1;
+                }
+            }
+        }
+        outcome
+    }
+
+    /// Send one channel its message
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or gets a non-2xx response.
+    async fn post(&self, channel: &Channel, notification: &Notification) -> Result<()> {
+        let text = notification.message_for(&channel.config);
+        let _ = channel
+            .sender
+            .request(&self.http, &text)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Spawn the task posting transcription failures if a channel wants them
+#[must_use]
+pub fn spawn_failure_task(
+    pool: PgPool,
+    mut progress: ProgressEvents,
+    notifications: Option<&Arc<Notifications>>,
+) -> Option<JoinHandle<()>> {
+    let notifications = Arc::clone(notifications?);
+    if !notifications.wants(NotificationEvent::Failure) {
+        return None;
+    }
+
+    Some(tokio::spawn(async move {
+        info!("Posting transcription failures to chat channels");
+        while let Some(event) = progress.recv().await {
+            // Failures that will be retried are not reported
+            if let ProgressStage::Failed {
+                error,
+                will_retry: false,
+            } = event.stage
+            {
+                drop(tokio::spawn(post_failure_logged(
+                    pool.clone(),
+                    Arc::clone(&notifications),
+                    event.call_id,
+                    error,
+                )));
+            }
+        }
+    }))
+}
+
+async fn post_failure_logged(
+    pool: PgPool,
+    notifications: Arc<Notifications>,
+    call_id: Uuid,
+    error: String,
+) {
+    match sdrtrunk_storage::get_radio_call(&pool, call_id).await {
+        Ok(Some(call)) => {
+            let _ = notifications
+                .notify(&Notification::failure(&call, &error))
+                .await;
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Failed to load call {call_id} for a failure notification: {e}"),
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use sdrtrunk_types::{SystemId, TalkgroupId};
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+
+    type Posts = Arc<Mutex<Vec<(String, serde_json::Value)>>>;
+
+    fn alert() -> AlertNotification {
+        AlertNotification {
+            alert_id: Uuid::new_v4(),
+            rule_id: None,
+            rule_name: "Fire".to_string(),
+            call_id: Uuid::nil(),
+            call_timestamp: "2025-01-02T03:04:05Z".parse().unwrap(),
+            system_id: SystemId::new("metro").unwrap(),
+            system_label: Some("Metro".to_string()),
+            talkgroup_id: Some(TalkgroupId::new(52198).unwrap()),
+            talkgroup_label: None,
+            matched_text: "structure fire".to_string(),
+            transcript: "Engine 4, structure fire on Main".to_string(),
+        }
+    }
+
+    fn channel(kind: NotificationChannelKind) -> NotificationChannel {
+        NotificationChannel {
+            name: "ops".to_string(),
+            kind,
+            webhook_url: None,
+            bot_token: None,
+            chat_id: None,
+            events: Vec::new(),
+            systems: Vec::new(),
+            templates: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_alert() {
+        let notification = Notification::alert(&alert());
+        assert_eq!(notification.event(), NotificationEvent::Alert);
+        assert_eq!(
+            notification.message_for(&channel(NotificationChannelKind::Slack)),
+            "Alert \"Fire\" matched \"structure fire\" on TG 52198 (Metro) at \
+             2025-01-02 03:04:05 UTC\nEngine 4, structure fire on Main"
+        );
+        assert_eq!(
+            notification.render("{{rule}} / {{unknown}} / {{call_id}}"),
+            format!("Fire / {{{{unknown}}}} / {}", Uuid::nil())
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("héllo", 2), "hé");
+    }
+
+    #[test]
+    fn test_sender_needs_settings() {
+        assert!(sender_for(&channel(NotificationChannelKind::Discord)).is_err());
+        let telegram = NotificationChannel {
+            bot_token: Some("123:abc".to_string()),
+            ..channel(NotificationChannelKind::Telegram)
+        };
+        assert!(sender_for(&telegram).is_err());
+        let telegram = NotificationChannel {
+            chat_id: Some("-100".to_string()),
+            ..telegram
+        };
+        assert!(sender_for(&telegram).is_ok());
+        assert!(!format!("{:?}", sender_for(&telegram).unwrap()).contains("123:abc"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_posts_to_subscribed_channels() {
+        let posts = Posts::default();
+        let app = Router::new()
+            .route(
+                "/*path",
+                post(
+                    |State(posts): State<Posts>,
+                     Path(path): Path<String>,
+                     axum::Json(body): axum::Json<serde_json::Value>| async move {
+                        if path == "broken" {
+                            return StatusCode::INTERNAL_SERVER_ERROR;
+                        }
+                        posts.lock().await.push((path, body));
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state(Arc::clone(&posts));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut notifications = Notifications::new(&NotificationsConfig {
+            channels: vec![
+                NotificationChannel {
+                    webhook_url: Some(format!("{base}/discord")),
+                    templates: HashMap::from([(
+                        NotificationEvent::Alert,
+                        "{{rule}}: {{matched}}".to_string(),
+                    )]),
+                    ..channel(NotificationChannelKind::Discord)
+                },
+                NotificationChannel {
+                    webhook_url: Some(format!("{base}/slack")),
+                    events: vec![NotificationEvent::Failure],
+                    ..channel(NotificationChannelKind::Slack)
+                },
+                NotificationChannel {
+                    webhook_url: Some(format!("{base}/broken")),
+                    systems: vec!["metro".to_string()],
+                    ..channel(NotificationChannelKind::Slack)
+                },
+            ],
+            ..NotificationsConfig::default()
+        })
+        .unwrap();
+        notifications.channels.push(Channel {
+            config: channel(NotificationChannelKind::Telegram),
+            sender: Box::new(TelegramSender {
+                api_url: base.clone(),
+                bot_token: "123:abc".to_string(),
+                chat_id: "-100".to_string(),
+            }),
+        });
+        assert!(notifications.wants(NotificationEvent::Digest));
+
+        let outcome = notifications.notify(&Notification::alert(&alert())).await;
+        assert_eq!(outcome, PostOutcome { sent: 2, failed: 1 });
+
+        let mut posts = posts.lock().await.clone();
+        posts.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].0, "bot123:abc/sendMessage");
+        assert_eq!(posts[0].1["chat_id"], "-100");
+        assert!(
+            posts[0].1["text"]
+                .as_str()
+                .unwrap()
+                .starts_with("Alert \"Fire\"")
+        );
+        assert_eq!(posts[1].0, "discord");
+        assert_eq!(posts[1].1["content"], "Fire: structure fire");
+    }
+}
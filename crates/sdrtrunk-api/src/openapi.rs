@@ -1,186 +1,475 @@
-//! `OpenAPI` 3.0 specification generator
+//! `OpenAPI` specification generator
+//!
+//! [`ApiDoc`] is derived by utoipa from the `#[utoipa::path]` annotations on
+//! the handlers and the `ToSchema` types they return. Endpoints that are not
+//! annotated yet are still described by hand in [`manual_spec`] and merged in
+//! by [`generate_openapi_spec`]; generated entries always take precedence.
 
-use serde_json::json;
+use crate::{
+    error,
+    handlers::{calls, compare, duplicates, health, searches, tags, upload},
+};
+use serde_json::{Value, json};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
 
-/// Generate comprehensive `OpenAPI` 3.0 specification
+/// Description of the `ApiKeyAuth` security scheme
+const API_KEY_DESCRIPTION: &str = "Also accepted as `Authorization: Bearer <key>`. Keys with `allowed_systems` only see calls, stats, talkgroups, and alerts from those systems, and may only upload to them. Required when `security.require_api_key` is set.";
+
+/// Generated `OpenAPI` document for the annotated endpoints
+#[derive(Debug, OpenApi)]
+#[openapi(
+    info(
+        title = "SDRTrunk Transcriber API",
+        version = "0.1.0",
+        description = "REST API for SDRTrunk P25 radio call transcription and management with speaker diarization",
+        contact(
+            name = "API Support",
+            url = "https://github.com/swiftraccoon/rs-sdrtrunk-transcriber"
+        ),
+        license(name = "GPL-3.0", url = "https://www.gnu.org/licenses/gpl-3.0.html")
+    ),
+    servers((url = "http://localhost:9000", description = "Development server")),
+    paths(
+        health::health_check,
+        health::readiness_check,
+        health::liveness_check,
+        health::component_readiness_check,
+        upload::handle_call_upload,
+        calls::list_calls,
+        calls::get_call,
+        calls::get_call_audio,
+        calls::get_call_events,
+        calls::review_call,
+        calls::get_call_speakers,
+        calls::get_call_transcript,
+        calls::get_call_waveform,
+        calls::get_call_summary,
+        compare::compare_calls,
+        duplicates::get_call_duplicates,
+        tags::list_call_tags,
+        tags::tag_call,
+        tags::untag_call,
+        searches::list_searches,
+        searches::create_search,
+        searches::get_search,
+        searches::update_search,
+        searches::delete_search,
+    ),
+    components(schemas(
+        health::HealthResponse,
+        health::ReadinessResponse,
+        health::LivenessResponse,
+        health::ComponentReadinessResponse,
+        upload::CallUploadRequest,
+        upload::UploadResponse,
+        calls::AudioFormat,
+        calls::ListCallsResponse,
+        calls::PaginationInfo,
+        calls::CallSummary,
+        calls::CallDetail,
+        calls::CallEventsResponse,
+        calls::ReviewCallResponse,
+        calls::CallEventInfo,
+        calls::CallSpeakersResponse,
+        calls::TranscriptFormat,
+        calls::CallTranscriptResponse,
+        calls::TranscriptSegmentInfo,
+        calls::CallWaveformResponse,
+        calls::TranscriptSummaryResponse,
+        calls::SummaryEntityInfo,
+        compare::ComparedCall,
+        compare::CallComparisonResponse,
+        duplicates::DuplicateCallInfo,
+        duplicates::CallDuplicatesResponse,
+        tags::TagCallRequest,
+        tags::CallTagInfo,
+        tags::TagCallResponse,
+        tags::CallTagsResponse,
+        searches::SavedSearchRequest,
+        searches::SavedSearchInfo,
+        searches::SavedSearchResponse,
+        searches::SavedSearchesResponse,
+        error::ErrorResponse,
+    )),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "Uploads", description = "Audio upload endpoints"),
+        (name = "Calls", description = "Call management and retrieval"),
+        (name = "Searches", description = "Saved call list filters"),
+        (name = "Statistics", description = "System and global statistics"),
+        (name = "WebSocket", description = "Real-time updates"),
+        (name = "Health", description = "Health and readiness checks"),
+        (name = "Monitoring", description = "Metrics and monitoring"),
+        (name = "API Keys", description = "Self-service API key usage"),
+        (name = "Talkgroups", description = "Talkgroup names"),
+        (name = "Transcription", description = "Transcription job management"),
+        (name = "Alerts", description = "Keyword alert rules and history"),
+        (name = "Conversations", description = "Consecutive talkgroup calls grouped together"),
+        (name = "Auth", description = "User sign-in sessions"),
+        (name = "Admin", description = "Administrative functions")
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `ApiKeyAuth` security scheme
+#[derive(Debug)]
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "ApiKeyAuth",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "X-API-Key",
+                API_KEY_DESCRIPTION,
+            ))),
+        );
+    }
+}
+
+/// Generate the `OpenAPI` specification served at `/api/docs/openapi.json`
 #[must_use]
+pub fn generate_openapi_spec() -> Value {
+    let mut spec = serde_json::to_value(ApiDoc::openapi()).unwrap_or_else(|_| json!({}));
+    let manual = manual_spec();
+
+    for section in ["/paths", "/components/schemas"] {
+        if let (Some(target), Some(source)) = (
+            spec.pointer_mut(section).and_then(Value::as_object_mut),
+            manual.pointer(section).and_then(Value::as_object),
+        ) {
+            for (key, value) in source {
+                let _ = target.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    spec
+}
+
+/// Hand-written descriptions of endpoints without `#[utoipa::path]` annotations
 #[allow(clippy::too_many_lines)]
-pub fn generate_openapi_spec() -> serde_json::Value {
+fn manual_spec() -> Value {
     json!({
-        "openapi": "3.0.3",
-        "info": {
-            "title": "SDRTrunk Transcriber API",
-            "version": "0.1.0",
-            "description": "REST API for SDRTrunk P25 radio call transcription and management with speaker diarization",
-            "contact": {
-                "name": "API Support",
-                "url": "https://github.com/swiftraccoon/rs-sdrtrunk-transcriber"
-            },
-            "license": {
-                "name": "GPL-3.0",
-                "url": "https://www.gnu.org/licenses/gpl-3.0.html"
-            }
-        },
-        "servers": [
-            {
-                "url": "http://localhost:9000",
-                "description": "Development server"
-            }
-        ],
         "paths": {
-            "/api/call-upload": {
+            "/api/uploads": {
+                "options": {
+                    "summary": "Describe resumable upload support",
+                    "description": "tus 1.0.0 discovery: Tus-Version, Tus-Extension (creation, termination), and Tus-Max-Size headers",
+                    "tags": ["Uploads"],
+                    "responses": {
+                        "204": {
+                            "description": "Protocol support headers"
+                        }
+                    }
+                },
                 "post": {
-                    "summary": "Upload radio call recording",
-                    "description": "Upload audio files from SDRTrunk for processing and transcription. Rdio Scanner compatible.",
+                    "summary": "Start a resumable upload",
+                    "description": "tus creation: send Tus-Resumable: 1.0.0, Upload-Length, and optionally Upload-Metadata (base64 filename and filetype). Send the chunks with PATCH, then pass the upload's ID as the uploadId field of a call upload in place of audio. Unfinished uploads expire after uploads.resumable_expiry_hours.",
+                    "tags": ["Uploads"],
+                    "responses": {
+                        "201": {
+                            "description": "Upload created; its URL is in Location"
+                        },
+                        "400": {
+                            "description": "Missing Upload-Length or malformed Upload-Metadata"
+                        },
+                        "412": {
+                            "description": "Unsupported Tus-Resumable version"
+                        },
+                        "413": {
+                            "description": "Upload-Length exceeds the maximum upload size"
+                        }
+                    }
+                }
+            },
+            "/api/uploads/{id}": {
+                "head": {
+                    "summary": "Get resumable upload progress",
+                    "description": "Upload-Offset (bytes received) and Upload-Length of the upload",
+                    "tags": ["Uploads"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "description": "Upload ID",
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Upload progress headers"
+                        },
+                        "404": {
+                            "description": "Unknown or expired upload"
+                        }
+                    }
+                },
+                "patch": {
+                    "summary": "Append to a resumable upload",
+                    "description": "Send the next chunk as application/offset+octet-stream with Upload-Offset set to the bytes received so far. Bytes received before a dropped connection are kept.",
                     "tags": ["Uploads"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "description": "Upload ID",
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
                     "requestBody": {
                         "required": true,
                         "content": {
-                            "multipart/form-data": {
-                                "schema": {
-                                    "$ref": "#/components/schemas/CallUploadRequest"
-                                }
+                            "application/offset+octet-stream": {
+                                "schema": { "type": "string", "format": "binary" }
                             }
                         }
                     },
+                    "responses": {
+                        "204": {
+                            "description": "Chunk stored; the new offset is in Upload-Offset"
+                        },
+                        "404": {
+                            "description": "Unknown or expired upload"
+                        },
+                        "409": {
+                            "description": "Upload-Offset does not match the bytes received"
+                        },
+                        "413": {
+                            "description": "Data runs past Upload-Length"
+                        },
+                        "415": {
+                            "description": "Wrong Content-Type"
+                        }
+                    }
+                },
+                "delete": {
+                    "summary": "Discard a resumable upload",
+                    "description": "tus termination",
+                    "tags": ["Uploads"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "description": "Upload ID",
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
+                    "responses": {
+                        "204": {
+                            "description": "Upload discarded"
+                        },
+                        "404": {
+                            "description": "Unknown or expired upload"
+                        }
+                    }
+                }
+            },
+            "/api/systems/{system_id}/stats": {
+                "get": {
+                    "summary": "Get system statistics",
+                    "description": "Retrieve comprehensive statistics for a specific system",
+                    "tags": ["Statistics"],
+                    "parameters": [
+                        {
+                            "name": "system_id",
+                            "in": "path",
+                            "required": true,
+                            "description": "System identifier",
+                            "schema": { "type": "string" }
+                        }
+                    ],
                     "responses": {
                         "200": {
-                            "description": "Upload successful",
-                            "content": {
-                                "application/json": {
-                                    "schema": {
-                                        "$ref": "#/components/schemas/UploadResponse"
-                                    }
-                                }
-                            }
+                            "description": "System statistics"
+                        }
+                    }
+                }
+            },
+            "/api/systems/{system_id}/talkgroups": {
+                "get": {
+                    "summary": "List talkgroup names",
+                    "description": "List the imported talkgroup labels, groups, and tags for a system, ordered by talkgroup ID",
+                    "tags": ["Talkgroups"],
+                    "parameters": [
+                        {
+                            "name": "system_id",
+                            "in": "path",
+                            "required": true,
+                            "description": "System identifier",
+                            "schema": { "type": "string" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Talkgroups for the system"
+                        }
+                    }
+                }
+            },
+            "/api/stats/global": {
+                "get": {
+                    "summary": "Get global statistics",
+                    "description": "Retrieve aggregated statistics across all systems, including hourly call counts for the last 24 hours",
+                    "tags": ["Statistics"],
+                    "responses": {
+                        "200": {
+                            "description": "Global statistics"
+                        }
+                    }
+                }
+            },
+            "/api/stats/storage": {
+                "get": {
+                    "summary": "Get storage growth",
+                    "description": "Daily bytes added per system and a time-to-full projection for the storage volume",
+                    "tags": ["Statistics"],
+                    "parameters": [
+                        {
+                            "name": "days",
+                            "in": "query",
+                            "required": false,
+                            "description": "Days of history used to compute the growth rate (1-365, default 30)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 365 }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Storage growth and projection"
                         },
                         "400": {
-                            "description": "Invalid request",
-                            "content": {
-                                "application/json": {
-                                    "schema": {
-                                        "$ref": "#/components/schemas/ErrorResponse"
-                                    }
-                                }
-                            }
+                            "description": "Invalid query parameters"
                         }
                     }
                 }
             },
-            "/api/calls": {
+            "/api/stats/languages": {
                 "get": {
-                    "summary": "List radio calls",
-                    "description": "Retrieve a paginated list of radio calls with optional filtering",
-                    "tags": ["Calls"],
+                    "summary": "Get language statistics",
+                    "description": "Detected transcription languages per system and talkgroup over time",
+                    "tags": ["Statistics"],
                     "parameters": [
                         {
                             "name": "system_id",
                             "in": "query",
-                            "description": "Filter by system ID",
+                            "required": false,
+                            "description": "Restrict to a single system",
                             "schema": { "type": "string" }
                         },
                         {
                             "name": "talkgroup_id",
                             "in": "query",
-                            "description": "Filter by talkgroup ID",
+                            "required": false,
+                            "description": "Restrict to a single talkgroup",
                             "schema": { "type": "integer" }
                         },
                         {
-                            "name": "status",
-                            "in": "query",
-                            "description": "Filter by transcription status",
-                            "schema": {
-                                "type": "string",
-                                "enum": ["pending", "processing", "completed", "failed"]
-                            }
-                        },
-                        {
-                            "name": "page",
+                            "name": "days",
                             "in": "query",
-                            "description": "Page number (1-indexed)",
-                            "schema": { "type": "integer", "default": 1, "minimum": 1 }
+                            "required": false,
+                            "description": "Days of history to include (1-365, default 30)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 365 }
                         },
                         {
-                            "name": "per_page",
+                            "name": "include_talkgroups",
                             "in": "query",
-                            "description": "Results per page",
-                            "schema": { "type": "integer", "default": 50, "minimum": 1, "maximum": 200 }
+                            "required": false,
+                            "description": "Include per-talkgroup breakdown",
+                            "schema": { "type": "boolean" }
                         }
                     ],
                     "responses": {
                         "200": {
-                            "description": "List of calls",
-                            "content": {
-                                "application/json": {
-                                    "schema": {
-                                        "$ref": "#/components/schemas/CallListResponse"
-                                    }
-                                }
-                            }
+                            "description": "Language statistics"
+                        },
+                        "400": {
+                            "description": "Invalid query parameters"
                         }
                     }
                 }
             },
-            "/api/calls/{id}": {
+            "/api/stats/talkgroups": {
                 "get": {
-                    "summary": "Get call details",
-                    "description": "Retrieve detailed information for a specific call",
-                    "tags": ["Calls"],
+                    "summary": "Get talkgroup activity",
+                    "description": "Call counts, total airtime, and busiest hours (UTC) of the busiest talkgroups",
+                    "tags": ["Statistics"],
                     "parameters": [
                         {
-                            "name": "id",
-                            "in": "path",
-                            "required": true,
-                            "description": "Call UUID",
-                            "schema": { "type": "string", "format": "uuid" }
+                            "name": "system_id",
+                            "in": "query",
+                            "required": false,
+                            "description": "Restrict to a single system",
+                            "schema": { "type": "string" }
+                        },
+                        {
+                            "name": "hours",
+                            "in": "query",
+                            "required": false,
+                            "description": "Hours of history to include (1-8760, default 24)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 8760 }
+                        },
+                        {
+                            "name": "limit",
+                            "in": "query",
+                            "required": false,
+                            "description": "Talkgroups to return, busiest first (1-500, default 50)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 500 }
                         }
                     ],
                     "responses": {
                         "200": {
-                            "description": "Call details",
-                            "content": {
-                                "application/json": {
-                                    "schema": {
-                                        "$ref": "#/components/schemas/CallDetail"
-                                    }
-                                }
-                            }
+                            "description": "Talkgroup activity"
                         },
-                        "404": {
-                            "description": "Call not found"
+                        "400": {
+                            "description": "Invalid query parameters"
                         }
                     }
                 }
             },
-            "/api/systems/{system_id}/stats": {
+            "/api/stats/frequencies": {
                 "get": {
-                    "summary": "Get system statistics",
-                    "description": "Retrieve comprehensive statistics for a specific system",
+                    "summary": "Get frequency usage",
+                    "description": "Per-frequency call counts, airtime, utilization, and systems seen, from each call's frequency list",
                     "tags": ["Statistics"],
                     "parameters": [
                         {
                             "name": "system_id",
-                            "in": "path",
-                            "required": true,
-                            "description": "System identifier",
+                            "in": "query",
+                            "required": false,
+                            "description": "Restrict to a single system",
                             "schema": { "type": "string" }
+                        },
+                        {
+                            "name": "days",
+                            "in": "query",
+                            "required": false,
+                            "description": "Days of history to include (1-365, default 7)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 365 }
+                        },
+                        {
+                            "name": "limit",
+                            "in": "query",
+                            "required": false,
+                            "description": "Frequencies to return, most used first (1-1000, default 100)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 1000 }
                         }
                     ],
                     "responses": {
                         "200": {
-                            "description": "System statistics"
-                        }
-                    }
-                }
-            },
-            "/api/stats/global": {
-                "get": {
-                    "summary": "Get global statistics",
-                    "description": "Retrieve aggregated statistics across all systems",
-                    "tags": ["Statistics"],
-                    "responses": {
-                        "200": {
-                            "description": "Global statistics"
+                            "description": "Frequency usage"
+                        },
+                        "400": {
+                            "description": "Invalid query parameters"
+                        },
+                        "403": {
+                            "description": "API key may not access the system"
                         }
                     }
                 }
@@ -197,14 +486,37 @@ pub fn generate_openapi_spec() -> serde_json::Value {
                     }
                 }
             },
-            "/health": {
+            "/api/transcribe/live": {
                 "get": {
-                    "summary": "Health check",
-                    "description": "Basic health status",
-                    "tags": ["Health"],
+                    "summary": "Live transcription WebSocket",
+                    "description": "Stream 16-bit little-endian mono PCM as binary frames and receive JSON transcripts: ready, partial (interim text of the segment being recorded), final (one per complete segment), and error. Send {\"type\": \"flush\"} to finish the current segment or {\"type\": \"end\"} to finish it and close. Requires live_transcription.enabled.",
+                    "tags": ["WebSocket"],
+                    "parameters": [
+                        {
+                            "name": "sample_rate",
+                            "in": "query",
+                            "description": "Sample rate of the streamed audio, 8000 to 48000 (default 16000)",
+                            "schema": { "type": "integer" }
+                        },
+                        {
+                            "name": "language",
+                            "in": "query",
+                            "description": "Language of the audio (ISO-639-1), overriding live_transcription.language",
+                            "schema": { "type": "string" }
+                        }
+                    ],
                     "responses": {
-                        "200": {
-                            "description": "Service is healthy"
+                        "101": {
+                            "description": "Switching protocols to WebSocket"
+                        },
+                        "400": {
+                            "description": "Unsupported sample rate or language"
+                        },
+                        "404": {
+                            "description": "Live transcription is not enabled"
+                        },
+                        "429": {
+                            "description": "All live transcription sessions are in use"
                         }
                     }
                 }
@@ -228,6 +540,44 @@ pub fn generate_openapi_spec() -> serde_json::Value {
                     }
                 }
             },
+            "/api/keys/{id}/usage": {
+                "get": {
+                    "summary": "Get API key usage",
+                    "description": "Requests, uploads, bytes, rejections, and recent client IPs for the calling API key. The key in X-API-Key must match the requested ID.",
+                    "tags": ["API Keys"],
+                    "security": [{ "ApiKeyAuth": [] }],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "description": "API key ID",
+                            "schema": { "type": "string" }
+                        },
+                        {
+                            "name": "days",
+                            "in": "query",
+                            "required": false,
+                            "description": "Days of history to include (1-90, default 7)",
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 90 }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "API key usage statistics"
+                        },
+                        "400": {
+                            "description": "Invalid query parameters"
+                        },
+                        "401": {
+                            "description": "Missing or invalid API key"
+                        },
+                        "403": {
+                            "description": "API key does not match the requested ID"
+                        }
+                    }
+                }
+            },
             "/admin/api-keys": {
                 "get": {
                     "summary": "List API keys",
@@ -259,129 +609,675 @@ pub fn generate_openapi_spec() -> serde_json::Value {
                         }
                     }
                 }
-            }
-        },
-        "components": {
-            "schemas": {
-                "CallUploadRequest": {
-                    "type": "object",
-                    "properties": {
-                        "audio": {
-                            "type": "string",
-                            "format": "binary",
-                            "description": "Audio file (MP3, WAV, FLAC)"
-                        },
-                        "key": {
-                            "type": "string",
-                            "description": "Metadata JSON"
+            },
+            "/admin/ingest-keys": {
+                "get": {
+                    "summary": "List ingest keys",
+                    "description": "List upload-only ingest keys, revoked ones included; filter with `system_id`",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "List of ingest keys"
                         }
-                    },
-                    "required": ["audio"]
+                    }
                 },
-                "UploadResponse": {
-                    "type": "object",
-                    "properties": {
-                        "success": {
-                            "type": "boolean"
+                "post": {
+                    "summary": "Create ingest key",
+                    "description": "Generate a key that may only upload calls for `system_id`. The key is only returned once.",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Ingest key created"
                         },
-                        "call_id": {
-                            "type": "string",
-                            "format": "uuid"
+                        "400": {
+                            "description": "Invalid expiration date"
+                        }
+                    }
+                }
+            },
+            "/admin/ingest-keys/{key_id}": {
+                "delete": {
+                    "summary": "Revoke ingest key",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Ingest key revoked"
+                        },
+                        "400": {
+                            "description": "No active ingest key with this ID"
+                        }
+                    }
+                }
+            },
+            "/admin/users": {
+                "get": {
+                    "summary": "List users",
+                    "description": "User accounts with their roles (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "List of users"
                         }
                     }
                 },
-                "ErrorResponse": {
-                    "type": "object",
-                    "properties": {
-                        "error": {
-                            "type": "string"
+                "post": {
+                    "summary": "Create user",
+                    "description": "Create an account with `username`, `password` (at least 8 characters), and `role` (`read_only`, `analyst`, or `admin`) (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "User created"
                         },
-                        "code": {
-                            "type": "string"
+                        "400": {
+                            "description": "Invalid username or password, or username taken"
+                        }
+                    }
+                }
+            },
+            "/admin/users/{user_id}": {
+                "put": {
+                    "summary": "Update user",
+                    "description": "Change `role`, `active`, or `password`. Disabling a user or changing their password ends their sessions (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "User updated"
+                        },
+                        "400": {
+                            "description": "Unknown user or invalid password"
                         }
                     }
                 },
-                "CallListResponse": {
-                    "type": "object",
-                    "properties": {
-                        "calls": {
-                            "type": "array",
-                            "items": {
-                                "$ref": "#/components/schemas/CallSummary"
+                "delete": {
+                    "summary": "Delete user",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "User deleted"
+                        },
+                        "400": {
+                            "description": "Unknown user"
+                        }
+                    }
+                }
+            },
+            "/api/auth/login": {
+                "post": {
+                    "summary": "Sign in",
+                    "description": "Exchange `username` and `password` for a session token, presented as `Authorization: Bearer <token>` until it expires after `security.session_ttl_hours`",
+                    "tags": ["Auth"],
+                    "responses": {
+                        "200": {
+                            "description": "Session token, expiry, and user"
+                        },
+                        "401": {
+                            "description": "Invalid username or password"
+                        }
+                    }
+                }
+            },
+            "/api/auth/logout": {
+                "post": {
+                    "summary": "Sign out",
+                    "description": "End the session whose token is presented",
+                    "tags": ["Auth"],
+                    "responses": {
+                        "200": {
+                            "description": "Session ended"
+                        },
+                        "400": {
+                            "description": "No active session for the presented token"
+                        }
+                    }
+                }
+            },
+            "/api/auth/me": {
+                "get": {
+                    "summary": "Current session",
+                    "description": "The signed-in user, if any, the role the request acts with, and the systems it may see (null for every system)",
+                    "tags": ["Auth"],
+                    "responses": {
+                        "200": {
+                            "description": "User, role, and allowed systems"
+                        }
+                    }
+                }
+            },
+            "/api/admin/features": {
+                "get": {
+                    "summary": "List feature flags",
+                    "description": "Configured value, runtime override, and effective state of each experimental feature flag (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Feature flag state"
+                        }
+                    }
+                }
+            },
+            "/api/admin/features/{name}": {
+                "put": {
+                    "summary": "Override feature flag",
+                    "description": "Enable or disable a feature flag until the next restart (admin only)",
+                    "tags": ["Admin"],
+                    "parameters": [
+                        {
+                            "name": "name",
+                            "in": "path",
+                            "required": true,
+                            "description": "Feature flag name",
+                            "schema": { "type": "string", "enum": ["graphql", "live_listen", "summarization"] }
+                        }
+                    ],
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "required": ["enabled"],
+                                    "properties": {
+                                        "enabled": { "type": "boolean" }
+                                    }
+                                }
                             }
+                        }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Updated feature flag state"
                         },
-                        "pagination": {
-                            "$ref": "#/components/schemas/Pagination"
+                        "400": {
+                            "description": "Unknown feature flag"
                         }
                     }
                 },
-                "CallSummary": {
-                    "type": "object",
-                    "properties": {
-                        "id": {
-                            "type": "string",
-                            "format": "uuid"
+                "delete": {
+                    "summary": "Clear feature flag override",
+                    "description": "Revert a feature flag to its configured value (admin only)",
+                    "tags": ["Admin"],
+                    "parameters": [
+                        {
+                            "name": "name",
+                            "in": "path",
+                            "required": true,
+                            "description": "Feature flag name",
+                            "schema": { "type": "string" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Feature flag state after clearing the override"
                         },
-                        "system_id": {
-                            "type": "string"
+                        "400": {
+                            "description": "Unknown feature flag"
+                        }
+                    }
+                }
+            },
+            "/api/admin/maintenance/tables": {
+                "get": {
+                    "summary": "Table bloat statistics",
+                    "description": "Live and dead rows, size, rows modified since the last analyze, and vacuum/analyze timestamps for each table (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Table maintenance statistics"
                         },
-                        "talkgroup_id": {
-                            "type": "integer"
+                        "400": {
+                            "description": "Statistics could not be read"
+                        }
+                    }
+                }
+            },
+            "/api/admin/maintenance/partitions": {
+                "get": {
+                    "summary": "Call partitions",
+                    "description": "Monthly partitions of radio_calls with their estimated rows and size, plus the default partition holding calls outside every month (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Call partitions"
                         },
-                        "timestamp": {
-                            "type": "string",
-                            "format": "date-time"
+                        "400": {
+                            "description": "Partitions could not be read"
+                        }
+                    }
+                }
+            },
+            "/api/admin/maintenance/analyze": {
+                "post": {
+                    "summary": "Run ANALYZE",
+                    "description": "Refresh planner statistics after a bulk import or purge. Analyzes the named table, or every table past the configured thresholds (admin only)",
+                    "tags": ["Admin"],
+                    "requestBody": {
+                        "required": false,
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {
+                                        "table": { "type": "string" }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Tables that were analyzed"
                         },
-                        "duration": {
-                            "type": "number"
+                        "400": {
+                            "description": "Unknown table or ANALYZE failed"
+                        }
+                    }
+                }
+            },
+            "/api/admin/retention/run": {
+                "post": {
+                    "summary": "Run retention purge",
+                    "description": "Delete calls, their recordings and transcription jobs, and upload logs older than the configured retention periods, and report what was removed (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Retention report"
                         },
-                        "transcription_status": {
-                            "type": "string",
-                            "enum": ["pending", "processing", "completed", "failed"]
+                        "400": {
+                            "description": "Purge failed"
                         }
                     }
-                },
-                "CallDetail": {
-                    "type": "object",
-                    "properties": {
-                        "id": {
-                            "type": "string",
-                            "format": "uuid"
+                }
+            },
+            "/api/admin/purge": {
+                "delete": {
+                    "summary": "Purge matching calls",
+                    "description": "Erase every call matching the given system, talkgroup, and/or radio, with its transcript, recording, upload log entries, and webhook deliveries, in one audited transaction; e.g. for erasure requests (admin only)",
+                    "tags": ["Admin"],
+                    "parameters": [
+                        {
+                            "name": "system_id",
+                            "in": "query",
+                            "required": false,
+                            "description": "Erase calls from this system",
+                            "schema": { "type": "string" }
                         },
-                        "system_id": {
-                            "type": "string"
+                        {
+                            "name": "talkgroup_id",
+                            "in": "query",
+                            "required": false,
+                            "description": "Erase calls on this talkgroup",
+                            "schema": { "type": "integer" }
                         },
-                        "talkgroup_id": {
-                            "type": "integer"
+                        {
+                            "name": "radio_id",
+                            "in": "query",
+                            "required": false,
+                            "description": "Erase calls transmitted by this radio",
+                            "schema": { "type": "integer" }
                         },
-                        "transcription_text": {
-                            "type": "string"
+                        {
+                            "name": "reason",
+                            "in": "query",
+                            "required": false,
+                            "description": "Why the data is erased, kept in the audit log",
+                            "schema": { "type": "string" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Audit record of what was erased"
                         },
-                        "speaker_segments": {
-                            "type": "array",
-                            "items": {
-                                "type": "object"
+                        "400": {
+                            "description": "No valid filter given, or the purge failed and nothing was erased"
+                        }
+                    }
+                }
+            },
+            "/api/admin/jobs": {
+                "get": {
+                    "summary": "List scheduled jobs",
+                    "description": "Periodic jobs (retention, stats_rollup, auto_analyze, partitions) with their schedule, next run, and the status and message of their last run (admin only)",
+                    "tags": ["Admin"],
+                    "responses": {
+                        "200": {
+                            "description": "Scheduled jobs"
+                        },
+                        "400": {
+                            "description": "Database error"
+                        }
+                    }
+                }
+            },
+            "/api/transcriptions/retry": {
+                "post": {
+                    "summary": "Re-queue calls for transcription",
+                    "description": "Queue a new transcription job for every call matching the filters, newest first. Calls with a pending or processing job are skipped. With dry_run set, only returns how many calls would be queued.",
+                    "tags": ["Transcription"],
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {
+                                        "system_id": { "type": "string" },
+                                        "talkgroup_id": { "type": "integer" },
+                                        "status": {
+                                            "type": "string",
+                                            "enum": ["failed", "completed", "pending", "all"],
+                                            "default": "failed"
+                                        },
+                                        "from_date": { "type": "string", "format": "date-time" },
+                                        "to_date": { "type": "string", "format": "date-time" },
+                                        "limit": { "type": "integer", "minimum": 1 },
+                                        "dry_run": { "type": "boolean", "default": false }
+                                    }
+                                }
                             }
                         }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Number of calls queued, or that would be queued on a dry run"
+                        },
+                        "400": {
+                            "description": "Invalid filters or query failure"
+                        }
+                    }
+                }
+            },
+            "/api/alerts": {
+                "get": {
+                    "summary": "List alerts",
+                    "description": "Alerts raised when a completed transcription matched an alert rule, newest first, with the outcome of their webhook and email notifications",
+                    "tags": ["Alerts"],
+                    "parameters": [
+                        { "name": "rule_id", "in": "query", "schema": { "type": "string", "format": "uuid" } },
+                        { "name": "system_id", "in": "query", "schema": { "type": "string" } },
+                        { "name": "talkgroup_id", "in": "query", "schema": { "type": "integer" } },
+                        { "name": "from_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "to_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": 1000, "default": 50 } },
+                        { "name": "offset", "in": "query", "schema": { "type": "integer", "minimum": 0, "default": 0 } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "A page of alerts and the total matching the filters"
+                        },
+                        "400": {
+                            "description": "Invalid paging parameters or query failure"
+                        }
+                    }
+                }
+            },
+            "/api/alerts/rules": {
+                "get": {
+                    "summary": "List alert rules",
+                    "tags": ["Alerts"],
+                    "responses": {
+                        "200": {
+                            "description": "All alert rules"
+                        }
                     }
                 },
-                "Pagination": {
-                    "type": "object",
-                    "properties": {
-                        "page": {
-                            "type": "integer"
+                "post": {
+                    "summary": "Create an alert rule",
+                    "description": "Keywords match case-insensitively on word boundaries; regexes match as written. Rules without a system or talkgroup apply to all calls.",
+                    "tags": ["Alerts"],
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "required": ["name", "pattern"],
+                                    "properties": {
+                                        "name": { "type": "string" },
+                                        "pattern": { "type": "string" },
+                                        "pattern_type": {
+                                            "type": "string",
+                                            "enum": ["keyword", "regex"],
+                                            "default": "keyword"
+                                        },
+                                        "system_id": { "type": "string" },
+                                        "talkgroup_id": { "type": "integer" },
+                                        "webhook_url": { "type": "string", "format": "uri" },
+                                        "email": { "type": "string", "format": "email" },
+                                        "enabled": { "type": "boolean", "default": true }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "The created rule"
+                        },
+                        "400": {
+                            "description": "Invalid rule"
+                        }
+                    }
+                }
+            },
+            "/api/alerts/rules/{id}": {
+                "delete": {
+                    "summary": "Delete an alert rule",
+                    "description": "Alerts the rule already raised stay in the history",
+                    "tags": ["Alerts"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Rule deleted"
+                        },
+                        "400": {
+                            "description": "Rule not found or query failure"
+                        }
+                    }
+                }
+            },
+            "/api/calls/geo": {
+                "get": {
+                    "summary": "Map calls",
+                    "description": "Located calls as a GeoJSON FeatureCollection of points, newest first. Calls without coordinates are left out.",
+                    "tags": ["Calls"],
+                    "parameters": [
+                        { "name": "system_id", "in": "query", "schema": { "type": "string" } },
+                        { "name": "talkgroup_id", "in": "query", "schema": { "type": "integer" } },
+                        { "name": "from_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "to_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": 5000, "default": 500 } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "GeoJSON FeatureCollection with one Point feature per call"
+                        },
+                        "400": {
+                            "description": "Invalid limit or query failure"
+                        }
+                    }
+                }
+            },
+            "/api/conversations": {
+                "get": {
+                    "summary": "List conversations",
+                    "description": "Runs of calls on one talkgroup, each starting within the configured gap of the previous call ending, most recently active first",
+                    "tags": ["Conversations"],
+                    "parameters": [
+                        { "name": "system_id", "in": "query", "schema": { "type": "string" } },
+                        { "name": "talkgroup_id", "in": "query", "schema": { "type": "integer" } },
+                        { "name": "from_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "to_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": 1000, "default": 50 } },
+                        { "name": "offset", "in": "query", "schema": { "type": "integer", "minimum": 0, "default": 0 } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "A page of conversations and the total matching the filters"
                         },
-                        "per_page": {
-                            "type": "integer"
+                        "400": {
+                            "description": "Invalid paging parameters or query failure"
+                        }
+                    }
+                }
+            },
+            "/api/conversations/{id}": {
+                "get": {
+                    "summary": "Get a conversation",
+                    "description": "The conversation and its calls, oldest first",
+                    "tags": ["Conversations"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "The conversation and its calls"
                         },
-                        "total": {
-                            "type": "integer"
+                        "400": {
+                            "description": "Conversation not found or query failure"
+                        }
+                    }
+                }
+            },
+            "/api/calls/{id}/transcription/feedback": {
+                "get": {
+                    "summary": "List transcription feedback",
+                    "description": "Corrections and ratings submitted for the call, oldest first",
+                    "tags": ["Transcription"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "The call's feedback"
                         },
-                        "total_pages": {
-                            "type": "integer"
+                        "404": {
+                            "description": "Call not found"
                         }
                     }
                 },
+                "post": {
+                    "summary": "Submit transcription feedback",
+                    "description": "Rate the call's transcription and/or submit a corrected transcript. The current transcript is stored with the feedback.",
+                    "tags": ["Transcription"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" }
+                        }
+                    ],
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {
+                                        "corrected_text": { "type": "string", "maxLength": 10000 },
+                                        "rating": { "type": "integer", "minimum": 1, "maximum": 5 },
+                                        "comment": { "type": "string", "maxLength": 2000 }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "responses": {
+                        "201": {
+                            "description": "The stored feedback"
+                        },
+                        "400": {
+                            "description": "Neither corrected_text nor rating given, or a field is invalid"
+                        },
+                        "404": {
+                            "description": "Call not found"
+                        }
+                    }
+                }
+            },
+            "/api/admin/transcription/feedback/export": {
+                "get": {
+                    "summary": "Export transcription feedback",
+                    "description": "Feedback as JSON Lines, oldest first, with each call's recording location, language, and best known transcript (`text`) for building fine-tuning datasets (admin only)",
+                    "tags": ["Transcription"],
+                    "parameters": [
+                        { "name": "system_id", "in": "query", "schema": { "type": "string" } },
+                        { "name": "min_rating", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": 5 } },
+                        { "name": "corrected_only", "in": "query", "schema": { "type": "boolean", "default": false } },
+                        { "name": "from_date", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+                        { "name": "to_date", "in": "query", "schema": { "type": "string", "format": "date-time" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "One JSON object per line",
+                            "content": {
+                                "application/x-ndjson": {
+                                    "schema": { "type": "string" }
+                                }
+                            }
+                        },
+                        "400": {
+                            "description": "Invalid min_rating"
+                        }
+                    }
+                }
+            },
+            "/api/admin/talkgroups/import": {
+                "post": {
+                    "summary": "Import talkgroup names",
+                    "description": "Import talkgroup labels, groups, and tags for a system from an SDRTrunk playlist XML or RadioReference CSV, then fill missing names on existing calls (admin only)",
+                    "tags": ["Talkgroups"],
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "multipart/form-data": {
+                                "schema": {
+                                    "type": "object",
+                                    "required": ["system", "file"],
+                                    "properties": {
+                                        "system": { "type": "string", "description": "System ID the talkgroups belong to" },
+                                        "file": { "type": "string", "format": "binary", "description": "Playlist XML or RadioReference CSV" },
+                                        "format": {
+                                            "type": "string",
+                                            "enum": ["playlist", "radioreference_csv"],
+                                            "description": "File format; detected from the file when omitted"
+                                        },
+                                        "alias_list": { "type": "string", "description": "Only import this playlist alias list" }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Import summary"
+                        },
+                        "400": {
+                            "description": "Invalid file or query failure"
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
                 "CreateApiKeyRequest": {
                     "type": "object",
                     "properties": {
@@ -394,45 +1290,8 @@ pub fn generate_openapi_spec() -> serde_json::Value {
                         }
                     }
                 }
-            },
-            "securitySchemes": {
-                "ApiKeyAuth": {
-                    "type": "apiKey",
-                    "in": "header",
-                    "name": "X-API-Key"
-                }
-            }
-        },
-        "tags": [
-            {
-                "name": "Uploads",
-                "description": "Audio upload endpoints"
-            },
-            {
-                "name": "Calls",
-                "description": "Call management and retrieval"
-            },
-            {
-                "name": "Statistics",
-                "description": "System and global statistics"
-            },
-            {
-                "name": "WebSocket",
-                "description": "Real-time updates"
-            },
-            {
-                "name": "Health",
-                "description": "Health and readiness checks"
-            },
-            {
-                "name": "Monitoring",
-                "description": "Metrics and monitoring"
-            },
-            {
-                "name": "Admin",
-                "description": "Administrative functions"
             }
-        ]
+        }
     })
 }
 
@@ -482,7 +1341,7 @@ mod tests {
     fn test_openapi_spec_structure() {
         let spec = generate_openapi_spec();
 
-        assert_eq!(spec["openapi"], "3.0.3");
+        assert_eq!(spec["openapi"], "3.1.0");
         assert_eq!(spec["info"]["title"], "SDRTrunk Transcriber API");
         assert_eq!(spec["info"]["version"], "0.1.0");
 
@@ -501,7 +1360,9 @@ mod tests {
         // Check schemas exist
         assert!(spec["components"]["schemas"]["CallUploadRequest"].is_object());
         assert!(spec["components"]["schemas"]["ErrorResponse"].is_object());
-        assert!(spec["components"]["schemas"]["CallListResponse"].is_object());
+        assert!(spec["components"]["schemas"]["ListCallsResponse"].is_object());
+        // Hand-written schemas are merged in
+        assert!(spec["components"]["schemas"]["CreateApiKeyRequest"].is_object());
 
         // Check security schemes
         assert!(spec["components"]["securitySchemes"]["ApiKeyAuth"].is_object());
@@ -0,0 +1,158 @@
+//! Transcription progress relay
+//!
+//! Workers publish per-call lifecycle events with `PostgreSQL` `NOTIFY`. The
+//! relay listens for them and rebroadcasts each one to `/api/ws` clients as a
+//! `transcription_progress` event. Background tasks reacting to progress
+//! (alerts, webhooks, worker metrics, and the like) read the same broadcast
+//! through [`ProgressEvents`] rather than opening listeners of their own.
+
+use crate::handlers::websocket::{WebSocketEvent, broadcast_event};
+use sdrtrunk_storage::{
+    PgPool, ProgressListener, ProgressQueries, ProgressStage, TranscriptionProgress,
+};
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Delay before reconnecting a failed listener
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Spawn the task relaying progress notifications to WebSocket clients
+#[must_use]
+pub fn spawn_progress_relay(
+    pool: PgPool,
+    events: broadcast::Sender<WebSocketEvent>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match ProgressListener::connect(&pool).await {
+                Ok(mut listener) => {
+                    info!("Listening for transcription progress");
+                    loop {
+                        match listener.recv().await {
+                            Ok(progress) => broadcast_event(
+                                &events,
+                                WebSocketEvent::TranscriptionProgress(progress),
+                            ),
+                            Err(e) => {
+                                warn!("Transcription progress listener failed: {e}");
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to listen for transcription progress: {e}"),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    })
+}
+
+/// Worker progress events rebroadcast by [`spawn_progress_relay`]
+#[derive(Debug)]
+pub struct ProgressEvents {
+    receiver: broadcast::Receiver<WebSocketEvent>,
+    /// What the events are for, to tell subscribers apart in logs
+    purpose: &'static str,
+}
+
+impl ProgressEvents {
+    /// Receive the progress events broadcast on `events` from now on
+    #[must_use]
+    pub fn subscribe(events: &broadcast::Sender<WebSocketEvent>, purpose: &'static str) -> Self {
+        Self {
+            receiver: events.subscribe(),
+            purpose,
+        }
+    }
+
+    /// Wait for the next progress event, skipping other events
+    ///
+    /// Events missed by falling behind the broadcast are logged and skipped.
+    /// Returns `None` once the broadcast is closed.
+    pub async fn recv(&mut self) -> Option<TranscriptionProgress> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(WebSocketEvent::TranscriptionProgress(progress)) => return Some(progress),
+                Ok(_) => {}
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("{} fell behind and missed {missed} events", self.purpose);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Publish a progress event, logging rather than failing on error
+///
+/// Progress is advisory; a failed notification must not fail the upload or
+/// job that triggered it.
+pub async fn publish_progress(
+    pool: &PgPool,
+    call_id: Uuid,
+    job_id: Option<Uuid>,
+    stage: ProgressStage,
+) {
+    let event = TranscriptionProgress::new(call_id, job_id, stage);
+    if let Err(e) = ProgressQueries::publish(pool, &event).await {
+        warn!("Failed to publish transcription progress for call {call_id}: {e}");
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_progress_events_skip_other_events() {
+        let (events, _) = broadcast::channel(16);
+        let mut progress = ProgressEvents::subscribe(&events, "Test");
+        let call_id = Uuid::new_v4();
+
+        events
+            .send(WebSocketEvent::TranscriptionUpdate {
+                call_id,
+                status: "completed".to_string(),
+                confidence: None,
+            })
+            .unwrap();
+        events
+            .send(WebSocketEvent::TranscriptionProgress(
+                TranscriptionProgress::new(call_id, None, ProgressStage::Queued),
+            ))
+            .unwrap();
+        drop(events);
+
+        let received = progress.recv().await.unwrap();
+        assert_eq!(received.call_id, call_id);
+        assert!(progress.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_progress_events_survive_lag() {
+        let (events, _) = broadcast::channel(1);
+        let mut progress = ProgressEvents::subscribe(&events, "Test");
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        for call_id in [first, second] {
+            events
+                .send(WebSocketEvent::TranscriptionProgress(
+                    TranscriptionProgress::new(call_id, None, ProgressStage::Queued),
+                ))
+                .unwrap();
+        }
+
+        // The first event was overwritten; the subscriber carries on
+        assert_eq!(progress.recv().await.unwrap().call_id, second);
+    }
+}
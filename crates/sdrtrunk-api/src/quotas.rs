@@ -0,0 +1,253 @@
+//! Per-system storage quotas
+//!
+//! Systems with `storage_quota_bytes` in their upload policy either have
+//! uploads refused once the quota is reached, or keep accepting them while
+//! the oldest recordings are removed after each upload until the system is
+//! back under its quota. Purged calls keep their metadata and transcripts.
+
+use crate::{
+    retention::{RecordingOutcome, remove_recording},
+    state::AppState,
+};
+use sdrtrunk_protocol::config::{QuotaAction, SystemUploadPolicy};
+use sdrtrunk_storage::{AudioStorage, PgPool, QuotaQueries};
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Recordings detached per purge batch before usage is checked again
+const PURGE_BATCH_SIZE: i64 = 10;
+
+/// Storage used by one system and its quota
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemStorage {
+    /// System ID
+    pub system_id: SystemId,
+
+    /// Bytes of recordings kept for the system
+    pub used_bytes: u64,
+
+    /// Configured quota in bytes (`None` when the system has no quota)
+    pub quota_bytes: Option<u64>,
+
+    /// Percentage of the quota used
+    pub used_percent: Option<f64>,
+
+    /// What happens once the quota is reached
+    pub quota_action: Option<QuotaAction>,
+
+    /// Whether usage is over the quota
+    pub exceeded: bool,
+}
+
+impl SystemStorage {
+    /// Usage of `system_id` measured against its upload policy
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(system_id: SystemId, used_bytes: u64, policy: Option<&SystemUploadPolicy>) -> Self {
+        let quota_bytes = policy.and_then(|p| p.storage_quota_bytes);
+        Self {
+            system_id,
+            used_bytes,
+            quota_bytes,
+            used_percent: quota_bytes.map(|quota| used_bytes as f64 * 100.0 / quota.max(1) as f64),
+            quota_action: quota_bytes.and(policy.map(|p| p.quota_action)),
+            exceeded: quota_bytes.is_some_and(|quota| used_bytes > quota),
+        }
+    }
+}
+
+/// Counts from bringing one system under its quota
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotaPurgeReport {
+    /// Recordings detached from their calls
+    pub recordings_detached: usize,
+    /// Bytes freed by removed recordings
+    pub bytes_freed: u64,
+    /// Recordings that could not be removed from storage
+    pub files_failed: usize,
+    /// Bytes still used once the purge finished
+    pub used_bytes: u64,
+}
+
+/// Storage of every system with a quota among `systems` (all for `None`)
+///
+/// # Errors
+///
+/// Returns an error if a usage query fails.
+pub async fn quota_usage(
+    pool: &PgPool,
+    policies: &[SystemUploadPolicy],
+    systems: Option<&[SystemId]>,
+) -> sdrtrunk_storage::Result<Vec<SystemStorage>> {
+    let mut usage = Vec::new();
+    for policy in policies.iter().filter(|p| p.storage_quota_bytes.is_some()) {
+        let Ok(system_id) = SystemId::new(policy.system_id.clone()) else {
+            continue;
+        };
+        if systems.is_some_and(|allowed| !allowed.contains(&system_id)) {
+            continue;
+        }
+        let used = QuotaQueries::usage(pool, &system_id).await?;
+        usage.push(SystemStorage::new(system_id, used, Some(policy)));
+    }
+    Ok(usage)
+}
+
+/// Check an upload of `file_size` bytes against the system's quota
+///
+/// Quota checks are skipped, with a warning, when usage cannot be measured.
+///
+/// # Errors
+///
+/// Returns the message to report if the upload would exceed a quota that
+/// refuses uploads.
+pub async fn check_upload(
+    state: &AppState,
+    system_id: &SystemId,
+    file_size: u64,
+) -> Result<(), String> {
+    let Some(policy) = state.config.uploads.policy_for(system_id.as_str()) else {
+        return Ok(());
+    };
+    if policy.storage_quota_bytes.is_none() || policy.quota_action != QuotaAction::Reject {
+        return Ok(());
+    }
+    match QuotaQueries::usage(&state.pool, system_id).await {
+        Ok(used) => policy
+            .check_quota(used, file_size)
+            .map_err(|rejection| format!("Rejected by upload policy: {rejection}")),
+        Err(e) => {
+            warn!("Storage quota check failed for {system_id}: {e}");
+            Ok(())
+        }
+    }
+}
+
+/// Purge the system's oldest recordings in the background if it has gone
+/// over a quota that purges
+pub fn spawn_enforcement(state: &Arc<AppState>, system_id: &SystemId) {
+    let Some(quota) = state
+        .config
+        .uploads
+        .policy_for(system_id.as_str())
+        .filter(|policy| policy.quota_action == QuotaAction::PurgeOldest)
+        .and_then(|policy| policy.storage_quota_bytes)
+    else {
+        return;
+    };
+    let state = Arc::clone(state);
+    let system_id = system_id.clone();
+    drop(tokio::spawn(async move {
+        match enforce_quota(&state.pool, state.audio_storage.as_ref(), &system_id, quota).await {
+            Ok(report) if report.recordings_detached > 0 => info!(
+                "Storage quota: purged {} recordings ({} bytes, {} failed) from {system_id}, {} of {quota} bytes used",
+                report.recordings_detached,
+                report.bytes_freed,
+                report.files_failed,
+                report.used_bytes
+            ),
+            Ok(_) => {}
+            Err(e) => warn!("Failed to enforce storage quota for {system_id}: {e}"),
+        }
+    }));
+}
+
+/// Remove `system_id`'s oldest recordings until it uses at most `quota`
+/// bytes
+///
+/// Stops early, leaving the system over quota, when only archived calls or
+/// calls awaiting transcription still have recordings.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails. Recordings that cannot be
+/// removed are counted in the report rather than failing the purge.
+pub async fn enforce_quota(
+    pool: &PgPool,
+    storage: &dyn AudioStorage,
+    system_id: &SystemId,
+    quota: u64,
+) -> sdrtrunk_storage::Result<QuotaPurgeReport> {
+    let mut report = QuotaPurgeReport::default();
+    loop {
+        report.used_bytes = QuotaQueries::usage(pool, system_id).await?;
+        if report.used_bytes <= quota {
+            break;
+        }
+        let detached = QuotaQueries::detach_oldest(pool, system_id, PURGE_BATCH_SIZE).await?;
+        if detached.is_empty() {
+            warn!(
+                "{system_id} uses {} bytes, over its {quota} byte quota, but has no recordings left to purge",
+                report.used_bytes
+            );
+            break;
+        }
+        report.recordings_detached += detached.len();
+        for recording in detached {
+            match remove_recording(&recording.audio_file_path, storage).await {
+                RecordingOutcome::Deleted(bytes) => report.bytes_freed += bytes,
+                RecordingOutcome::Failed => report.files_failed += 1,
+                RecordingOutcome::Missing | RecordingOutcome::Skipped => {}
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn policy(quota: Option<u64>, action: QuotaAction) -> SystemUploadPolicy {
+        SystemUploadPolicy {
+            system_id: "metro".to_string(),
+            max_duration_seconds: None,
+            min_file_size: None,
+            allowed_talkgroups: Vec::new(),
+            storage_quota_bytes: quota,
+            quota_action: action,
+        }
+    }
+
+    #[test]
+    fn test_system_storage() {
+        let metro = SystemId::new("metro").unwrap();
+
+        let purging = policy(Some(1000), QuotaAction::PurgeOldest);
+        let storage = SystemStorage::new(metro.clone(), 1250, Some(&purging));
+        assert_eq!(storage.quota_bytes, Some(1000));
+        assert_eq!(storage.used_percent, Some(125.0));
+        assert_eq!(storage.quota_action, Some(QuotaAction::PurgeOldest));
+        assert!(storage.exceeded);
+
+        let full = SystemStorage::new(
+            metro.clone(),
+            1000,
+            Some(&policy(Some(1000), QuotaAction::Reject)),
+        );
+        assert!(!full.exceeded);
+
+        // Without a quota only usage is reported
+        let unlimited = SystemStorage::new(
+            metro.clone(),
+            1250,
+            Some(&policy(None, QuotaAction::Reject)),
+        );
+        assert_eq!(unlimited.quota_bytes, None);
+        assert_eq!(unlimited.used_percent, None);
+        assert_eq!(unlimited.quota_action, None);
+        assert!(!unlimited.exceeded);
+        assert_eq!(SystemStorage::new(metro, 0, None).quota_action, None);
+    }
+}
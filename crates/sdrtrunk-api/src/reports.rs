@@ -0,0 +1,498 @@
+//! Emailed digest reports
+//!
+//! On the daily and weekly schedules from `[reports]`, summarises each system
+//! with calls in the period before the run (call volume, busiest talkgroups,
+//! alert hits, transcription failure rate) and mails it as HTML, rendered from
+//! `templates/digest.html`, to every recipient of that system. A short summary
+//! is also posted to the chat channels subscribed to digests. Sending is
+//! attempted once per run; failures are logged and reported in the run's
+//! outcome in `scheduled_jobs`.
+
+use crate::mail::{self, BodyFormat};
+use crate::notifications::{Notification, Notifications};
+use crate::scheduler::{self, Schedule};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::config::{NotificationEvent, ReportsConfig, SmtpConfig};
+use sdrtrunk_protocol::schedule::CronSchedule;
+use sdrtrunk_storage::{PgPool, ReportQueries, SystemDigest};
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// HTML layout of a digest email
+const DIGEST_TEMPLATE: &str = include_str!("../templates/digest.html");
+
+/// Longest wait for the relay to accept one digest
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How much history a digest covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestPeriod {
+    /// The 24 hours before the run
+    Daily,
+    /// The 7 days before the run
+    Weekly,
+}
+
+impl DigestPeriod {
+    /// Name the digest job is recorded under in `scheduled_jobs`
+    #[must_use]
+    pub const fn job_name(self) -> &'static str {
+        match self {
+            Self::Daily => "daily_digest",
+            Self::Weekly => "weekly_digest",
+        }
+    }
+
+    /// Length of the period
+    #[must_use]
+    pub const fn length(self) -> chrono::Duration {
+        match self {
+            Self::Daily => chrono::Duration::days(1),
+            Self::Weekly => chrono::Duration::weeks(1),
+        }
+    }
+
+    /// Capitalised name for subjects and headings
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+        }
+    }
+}
+
+/// Outcome of a digest run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DigestRun {
+    /// Systems with calls in the period
+    pub systems: usize,
+    /// Emails accepted by the relay
+    pub sent: usize,
+    /// Emails that could not be sent
+    pub failed: usize,
+    /// Chat messages posted
+    pub posted: usize,
+    /// Chat messages that could not be posted
+    pub post_failed: usize,
+}
+
+/// Start the daily and weekly digest jobs that have a schedule
+///
+/// Digests go through `reports.smtp`, or `alerts_smtp` when that is unset,
+/// and to the chat channels in `notifications` subscribed to digests. Nothing
+/// is started without either recipients and a relay or such a channel.
+#[must_use]
+pub fn spawn_report_tasks(
+    pool: &PgPool,
+    config: &ReportsConfig,
+    alerts_smtp: Option<&SmtpConfig>,
+    notifications: Option<&Arc<Notifications>>,
+) -> Vec<JoinHandle<()>> {
+    let schedules = [
+        (DigestPeriod::Daily, config.daily.as_ref()),
+        (DigestPeriod::Weekly, config.weekly.as_ref()),
+    ];
+    if schedules.iter().all(|(_, cron)| cron.is_none()) {
+        return Vec::new();
+    }
+    let Some(targets) = DigestTargets::new(config, alerts_smtp, notifications) else {
+        warn!("Digest reports are scheduled but have no recipients or chat channels; not sending");
+        return Vec::new();
+    };
+
+    info!(
+        "Digest reports enabled for {} recipients{}",
+        targets.smtp.as_ref().map_or(0, |_| config.recipients.len()),
+        if targets.notifications.is_some() {
+            " and chat channels"
+        } else {
+            ""
+        }
+    );
+    let targets = Arc::new(targets);
+    schedules
+        .into_iter()
+        .filter_map(|(period, cron)| Some((period, cron?)))
+        .map(|(period, cron)| spawn_digest_job(pool, period, cron, Arc::clone(&targets)))
+        .collect()
+}
+
+/// Where digests are sent
+#[derive(Debug)]
+pub struct DigestTargets {
+    /// Digest settings, including the email recipients
+    pub config: ReportsConfig,
+    /// Relay for the email recipients, unless digests are not mailed
+    pub smtp: Option<SmtpConfig>,
+    /// Chat channels subscribed to digests
+    pub notifications: Option<Arc<Notifications>>,
+}
+
+impl DigestTargets {
+    /// Targets for `config`, or `None` when digests have nowhere to go
+    ///
+    /// Digests are mailed through `reports.smtp`, or `alerts_smtp` when that
+    /// is unset, and posted to the channels of `notifications` subscribed to
+    /// digests.
+    fn new(
+        config: &ReportsConfig,
+        alerts_smtp: Option<&SmtpConfig>,
+        notifications: Option<&Arc<Notifications>>,
+    ) -> Option<Self> {
+        let smtp = if config.recipients.is_empty() {
+            None
+        } else {
+            let smtp = config.smtp.as_ref().or(alerts_smtp);
+            if smtp.is_none() {
+                warn!(
+                    "Digest reports have recipients but no SMTP relay is configured; not mailing"
+                );
+            }
+            smtp
+        };
+        let notifications = notifications
+            .filter(|notifications| notifications.wants(NotificationEvent::Digest))
+            .cloned();
+        if smtp.is_none() && notifications.is_none() {
+            return None;
+        }
+        Some(Self {
+            config: config.clone(),
+            smtp: smtp.cloned(),
+            notifications,
+        })
+    }
+
+    /// Post a digest to the chat channels, counting the messages in `run`
+    async fn post(&self, notification: &Notification, run: &mut DigestRun) {
+        let Some(notifications) = self.notifications.as_deref() else {
+            return;
+        };
+        let outcome = notifications.notify(notification).await;
+        run.posted += outcome.sent;
+        run.post_failed += outcome.failed;
+    }
+
+    /// Mail a digest to the recipients of its system, counting the emails in
+    /// `run`
+    async fn mail(&self, digest: &SystemDigest, subject: &str, body: &str, run: &mut DigestRun) {
+        let Some(smtp) = self.smtp.as_ref() else {
+            return;
+        };
+        for to in self.config.recipients_for(digest.system_id.as_str()) {
+            let outcome = tokio::time::timeout(
+                SEND_TIMEOUT,
+                mail::send_email(smtp, to, subject, body, BodyFormat::Html),
+            )
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("timed out")));
+            match outcome {
+                Ok(()) => run.sent += 1,
+                Err(e) => {
+                    warn!("Failed to send \"{subject}\" to {to}: {e:#}");
+                    run.failed += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Run the digest for `period` on `cron`
+fn spawn_digest_job(
+    pool: &PgPool,
+    period: DigestPeriod,
+    cron: &CronSchedule,
+    targets: Arc<DigestTargets>,
+) -> JoinHandle<()> {
+    let job_pool = pool.clone();
+    scheduler::spawn_job(
+        pool.clone(),
+        period.job_name(),
+        Schedule::Cron(cron.clone()),
+        move || {
+            let pool = job_pool.clone();
+            let targets = Arc::clone(&targets);
+            async move {
+                let run = send_digests(&pool, &targets, period, Utc::now())
+                    .await
+                    .map_err(|e| format!("{e:#}"))?;
+                let mut summary = format!(
+                    "{} systems, {} emails sent, {} failed",
+                    run.systems, run.sent, run.failed
+                );
+                if targets.notifications.is_some() {
+                    let _ = write!(
+                        summary,
+                        ", {} chat messages posted, {} failed",
+                        run.posted, run.post_failed
+                    );
+                }
+                if run.failed > 0 || run.post_failed > 0 {
+                    Err(summary)
+                } else {
+                    Ok(summary)
+                }
+            }
+        },
+    )
+}
+
+/// Mail and post the digests for the period ending at `end` to `targets`
+///
+/// # Errors
+///
+/// Returns an error if the digest figures cannot be loaded. Failed sends are
+/// logged and counted instead.
+pub async fn send_digests(
+    pool: &PgPool,
+    targets: &DigestTargets,
+    period: DigestPeriod,
+    end: DateTime<Utc>,
+) -> Result<DigestRun> {
+    let start = end - period.length();
+    let top_talkgroups = i64::from(targets.config.top_talkgroups);
+    let digests = ReportQueries::system_digests(pool, start, end, top_talkgroups).await?;
+
+    let mut run = DigestRun {
+        systems: digests.len(),
+        ..DigestRun::default()
+    };
+    for digest in &digests {
+        targets
+            .post(&Notification::digest(digest, period, start, end), &mut run)
+            .await;
+        if targets.smtp.is_some() {
+            let subject = digest_subject(digest, period, end);
+            let body = render_digest(digest, period, start, end);
+            targets.mail(digest, &subject, &body, &mut run).await;
+        }
+    }
+    Ok(run)
+}
+
+/// Subject line of a digest
+fn digest_subject(digest: &SystemDigest, period: DigestPeriod, end: DateTime<Utc>) -> String {
+    format!(
+        "{} digest: {} ({})",
+        period.title(),
+        system_name(digest),
+        end.format("%Y-%m-%d")
+    )
+}
+
+/// Render a digest as an HTML email body
+#[must_use]
+pub fn render_digest(
+    digest: &SystemDigest,
+    period: DigestPeriod,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> String {
+    let talkgroups = if digest.top_talkgroups.is_empty() {
+        "                    <tr><td colspan=\"2\">No talkgroup activity</td></tr>".to_string()
+    } else {
+        digest
+            .top_talkgroups
+            .iter()
+            .map(|tg| {
+                let name = tg.talkgroup_label.as_deref().map_or_else(
+                    || tg.talkgroup_id.to_string(),
+                    |label| format!("{label} ({})", tg.talkgroup_id),
+                );
+                format!(
+                    "                    <tr><td>{}</td><td align=\"right\">{}</td></tr>",
+                    escape_html(&name),
+                    tg.calls
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    DIGEST_TEMPLATE
+        .replace(
+            "{{title}}",
+            &escape_html(&format!(
+                "{} digest: {}",
+                period.title(),
+                system_name(digest)
+            )),
+        )
+        .replace(
+            "{{period}}",
+            &format!(
+                "{} to {} UTC",
+                start.format("%Y-%m-%d %H:%M"),
+                end.format("%Y-%m-%d %H:%M")
+            ),
+        )
+        .replace("{{calls}}", &digest.calls.to_string())
+        .replace("{{transcribed}}", &digest.transcribed.to_string())
+        .replace("{{failed}}", &digest.failed.to_string())
+        .replace(
+            "{{failure_rate}}",
+            &format!("{:.1}%", digest.failure_rate() * 100.0),
+        )
+        .replace("{{alert_hits}}", &digest.alert_hits.to_string())
+        .replace("{{talkgroups}}", &talkgroups)
+}
+
+/// System label, or its ID when it has none
+fn system_name(digest: &SystemDigest) -> String {
+    digest
+        .system_label
+        .clone()
+        .unwrap_or_else(|| digest.system_id.to_string())
+}
+
+/// Escape text for use in HTML
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use sdrtrunk_protocol::config::{
+        NotificationChannel, NotificationChannelKind, NotificationsConfig, ReportRecipient,
+    };
+    use sdrtrunk_storage::TalkgroupCount;
+    use sdrtrunk_types::{SystemId, TalkgroupId};
+    use std::collections::HashMap;
+
+    fn digest() -> SystemDigest {
+        let system_id = SystemId::new("metro").unwrap();
+        SystemDigest {
+            system_id: system_id.clone(),
+            system_label: Some("Metro <North>".to_string()),
+            calls: 120,
+            transcribed: 90,
+            failed: 10,
+            alert_hits: 3,
+            top_talkgroups: vec![
+                TalkgroupCount {
+                    system_id: system_id.clone(),
+                    talkgroup_id: TalkgroupId::new(52198).unwrap(),
+                    talkgroup_label: Some("Fire Dispatch".to_string()),
+                    calls: 40,
+                },
+                TalkgroupCount {
+                    system_id,
+                    talkgroup_id: TalkgroupId::new(100).unwrap(),
+                    talkgroup_label: None,
+                    calls: 12,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_digest() {
+        let start = "2025-01-01T07:00:00Z".parse().unwrap();
+        let end = "2025-01-02T07:00:00Z".parse().unwrap();
+        let html = render_digest(&digest(), DigestPeriod::Daily, start, end);
+
+        assert!(!html.contains("{{"));
+        assert!(html.contains("<title>Daily digest: Metro &lt;North&gt;</title>"));
+        assert!(html.contains("2025-01-01 07:00 to 2025-01-02 07:00 UTC"));
+        assert!(html.contains("<strong>120</strong>"));
+        assert!(html.contains("10 (10.0%)"));
+        assert!(html.contains("<td>Fire Dispatch (52198)</td><td align=\"right\">40</td>"));
+        assert!(html.contains("<td>100</td><td align=\"right\">12</td>"));
+
+        let quiet = SystemDigest {
+            top_talkgroups: Vec::new(),
+            ..digest()
+        };
+        assert!(render_digest(&quiet, DigestPeriod::Weekly, start, end).contains("No talkgroup"));
+    }
+
+    #[test]
+    fn test_digest_subject() {
+        let end = "2025-01-06T07:00:00Z".parse().unwrap();
+        assert_eq!(
+            digest_subject(&digest(), DigestPeriod::Weekly, end),
+            "Weekly digest: Metro <North> (2025-01-06)"
+        );
+        let unlabeled = SystemDigest {
+            system_label: None,
+            ..digest()
+        };
+        assert_eq!(
+            digest_subject(&unlabeled, DigestPeriod::Daily, end),
+            "Daily digest: metro (2025-01-06)"
+        );
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawn_report_tasks_needs_recipients_and_relay() {
+        let pool = PgPool::connect_lazy("postgresql://localhost/unused").unwrap();
+        let smtp = SmtpConfig {
+            host: "localhost".to_string(),
+            port: 25,
+            from: "reports@example.com".to_string(),
+        };
+        let scheduled = ReportsConfig {
+            daily: Some("0 7 * * *".parse().unwrap()),
+            ..ReportsConfig::default()
+        };
+        assert!(spawn_report_tasks(&pool, &ReportsConfig::default(), Some(&smtp), None).is_empty());
+        assert!(spawn_report_tasks(&pool, &scheduled, Some(&smtp), None).is_empty());
+
+        let with_recipient = ReportsConfig {
+            recipients: vec![ReportRecipient {
+                email: "ops@example.com".to_string(),
+                systems: Vec::new(),
+            }],
+            ..scheduled
+        };
+        assert!(spawn_report_tasks(&pool, &with_recipient, None, None).is_empty());
+
+        let alerts_only = Notifications::from_config(&NotificationsConfig {
+            channels: vec![NotificationChannel {
+                name: "ops".to_string(),
+                kind: NotificationChannelKind::Slack,
+                webhook_url: Some("http://127.0.0.1:9/hook".to_string()),
+                bot_token: None,
+                chat_id: None,
+                events: vec![NotificationEvent::Alert],
+                systems: Vec::new(),
+                templates: HashMap::new(),
+            }],
+            ..NotificationsConfig::default()
+        });
+        assert!(spawn_report_tasks(&pool, &scheduled, None, alerts_only.as_ref()).is_empty());
+    }
+}
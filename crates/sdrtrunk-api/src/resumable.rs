@@ -0,0 +1,464 @@
+//! Resumable uploads
+//!
+//! Recorders on flaky links can send a large recording in pieces with the
+//! [tus 1.0.0](https://tus.io/protocols/resumable-upload) protocol (core,
+//! `creation`, and `termination`) instead of restarting a whole multipart
+//! upload after every dropped connection. Each upload is kept under
+//! `{upload_dir}/.resumable` as `<id>.part`, the bytes received so far, and
+//! `<id>.json`, its declared length and metadata.
+//!
+//! A finished upload becomes a call by passing its ID as the `uploadId` field
+//! of a regular call upload in place of the `audio` file. Uploads left
+//! unfinished for `uploads.resumable_expiry_hours` are discarded.
+
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, io, path::PathBuf, pin::pin, time::Duration};
+use tokio::{fs, io::AsyncWriteExt};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Protocol version spoken, sent as `Tus-Resumable` and `Tus-Version`
+pub const TUS_VERSION: &str = "1.0.0";
+
+/// Protocol extensions supported, sent as `Tus-Extension`
+pub const TUS_EXTENSIONS: &str = "creation,termination";
+
+/// Content type of `PATCH` request bodies
+pub const OFFSET_CONTENT_TYPE: &str = "application/offset+octet-stream";
+
+/// Name of the directory under the upload directory holding the uploads
+const RESUMABLE_DIR: &str = ".resumable";
+
+/// A resumable upload's declared size and metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadInfo {
+    /// Total size of the upload, in bytes
+    pub length: u64,
+    /// Decoded `Upload-Metadata` pairs
+    pub metadata: BTreeMap<String, String>,
+    /// When the upload was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl UploadInfo {
+    /// File name of the recording, from the `filename` (or `name`) metadata
+    #[must_use]
+    pub fn filename(&self) -> Option<&str> {
+        self.metadata
+            .get("filename")
+            .or_else(|| self.metadata.get("name"))
+            .map(String::as_str)
+    }
+
+    /// MIME type of the recording, from the `filetype` (or `type`) metadata
+    #[must_use]
+    pub fn filetype(&self) -> Option<&str> {
+        self.metadata
+            .get("filetype")
+            .or_else(|| self.metadata.get("type"))
+            .map(String::as_str)
+    }
+}
+
+/// Why a resumable upload operation failed
+#[derive(Debug)]
+pub enum ResumableError {
+    /// No upload with that ID (never created, finished with, or expired)
+    NotFound,
+    /// The client's offset does not match the bytes received so far
+    OffsetMismatch {
+        /// Bytes received so far
+        expected: u64,
+    },
+    /// The data runs past the upload's declared length
+    TooLarge {
+        /// Declared length of the upload
+        length: u64,
+    },
+    /// Not all of the upload has been received yet
+    Incomplete {
+        /// Bytes received so far
+        offset: u64,
+        /// Declared length of the upload
+        length: u64,
+    },
+    /// Reading or writing the upload failed
+    Io(io::Error),
+}
+
+impl fmt::Display for ResumableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("upload not found"),
+            Self::OffsetMismatch { expected } => {
+                write!(f, "upload offset does not match, expected {expected}")
+            }
+            Self::TooLarge { length } => {
+                write!(f, "data exceeds the upload length of {length} bytes")
+            }
+            Self::Incomplete { offset, length } => {
+                write!(f, "upload incomplete: {offset} of {length} bytes received")
+            }
+            Self::Io(error) => write!(f, "upload storage error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ResumableError {}
+
+impl From<io::Error> for ResumableError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::NotFound {
+            Self::NotFound
+        } else {
+            Self::Io(e)
+        }
+    }
+}
+
+/// Result type for resumable upload operations
+pub type Result<T> = std::result::Result<T, ResumableError>;
+
+/// Resumable uploads kept on the local filesystem
+#[derive(Debug, Clone)]
+pub struct ResumableUploads {
+    /// Directory holding the uploads
+    dir: PathBuf,
+}
+
+impl ResumableUploads {
+    /// Uploads kept in the `.resumable` directory under `upload_dir`
+    #[must_use]
+    pub fn new(upload_dir: &std::path::Path) -> Self {
+        Self {
+            dir: upload_dir.join(RESUMABLE_DIR),
+        }
+    }
+
+    /// Start an upload of `length` bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the upload files cannot be written
+    pub async fn create(&self, length: u64, metadata: BTreeMap<String, String>) -> Result<Uuid> {
+        fs::create_dir_all(&self.dir).await?;
+        let id = Uuid::new_v4();
+        let info = UploadInfo {
+            length,
+            metadata,
+            created_at: Utc::now(),
+        };
+        let json = serde_json::to_vec(&info).map_err(io::Error::other)?;
+        drop(fs::File::create(self.data_path(id)).await?);
+        fs::write(self.info_path(id), json).await?;
+        Ok(id)
+    }
+
+    /// An upload's declared size and metadata, and the bytes received so far
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResumableError::NotFound`] for unknown uploads, or an error
+    /// if the upload files cannot be read
+    pub async fn status(&self, id: Uuid) -> Result<(UploadInfo, u64)> {
+        let json = fs::read(self.info_path(id)).await?;
+        let info: UploadInfo = serde_json::from_slice(&json).map_err(io::Error::other)?;
+        let offset = fs::metadata(self.data_path(id)).await?.len();
+        Ok((info, offset))
+    }
+
+    /// Append `data` to an upload whose client believes `offset` bytes were
+    /// received, returning the new offset
+    ///
+    /// Bytes received before the client disconnects are kept so it can
+    /// resume from there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResumableError::OffsetMismatch`] if `offset` is stale,
+    /// [`ResumableError::TooLarge`] if the data runs past the declared length
+    /// (the part that fits is kept), or an error if the upload is unknown or
+    /// cannot be written
+    pub async fn append<S, E>(&self, id: Uuid, offset: u64, data: S) -> Result<u64>
+    where
+        S: Stream<Item = std::result::Result<Bytes, E>>,
+        E: fmt::Display,
+    {
+        let (info, current) = self.status(id).await?;
+        if offset != current {
+            return Err(ResumableError::OffsetMismatch { expected: current });
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(self.data_path(id))
+            .await?;
+        let mut written = current;
+        let mut data = pin!(data);
+        let mut outcome = Ok(());
+        while let Some(chunk) = data.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    debug!("Resumable upload {id} interrupted at {written} bytes: {e}");
+                    break;
+                }
+            };
+            let room = usize::try_from(info.length.saturating_sub(written)).unwrap_or(usize::MAX);
+            if chunk.len() > room {
+                file.write_all(&chunk.slice(..room)).await?;
+                written = info.length;
+                outcome = Err(ResumableError::TooLarge {
+                    length: info.length,
+                });
+                break;
+            }
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+
+        outcome.map(|()| written)
+    }
+
+    /// The whole recording of a finished upload
+    ///
+    /// The upload is kept until [`remove`](Self::remove)d, so a failed call
+    /// upload can be retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResumableError::Incomplete`] if bytes are still missing, or
+    /// an error if the upload is unknown or cannot be read
+    pub async fn read_complete(&self, id: Uuid) -> Result<(UploadInfo, Bytes)> {
+        let (info, offset) = self.status(id).await?;
+        if offset != info.length {
+            return Err(ResumableError::Incomplete {
+                offset,
+                length: info.length,
+            });
+        }
+        let data = fs::read(self.data_path(id)).await?;
+        Ok((info, Bytes::from(data)))
+    }
+
+    /// Discard an upload
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResumableError::NotFound`] for unknown uploads, or an error
+    /// if the upload files cannot be removed
+    pub async fn remove(&self, id: Uuid) -> Result<()> {
+        fs::remove_file(self.info_path(id)).await?;
+        match fs::remove_file(self.data_path(id)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Discard uploads created more than `max_age` ago, returning how many
+    /// were removed
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the upload directory cannot be listed
+    pub async fn purge_expired(&self, max_age: Duration) -> Result<usize> {
+        let mut entries = match fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let cutoff = chrono::Duration::from_std(max_age)
+            .ok()
+            .and_then(|max_age| Utc::now().checked_sub_signed(max_age));
+
+        let mut removed = 0;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(id) = path
+                .extension()
+                .is_some_and(|ext| ext == "json")
+                .then(|| path.file_stem().and_then(|stem| stem.to_str()))
+                .flatten()
+                .and_then(|stem| Uuid::parse_str(stem).ok())
+            else {
+                continue;
+            };
+            let expired = match self.status(id).await {
+                Ok((info, _)) => cutoff.is_some_and(|cutoff| info.created_at < cutoff),
+                // Unreadable leftovers are as good as expired
+                Err(ResumableError::NotFound | ResumableError::Io(_)) => true,
+                Err(_) => false,
+            };
+            if expired {
+                match self.remove(id).await {
+                    Ok(()) => removed += 1,
+                    Err(e) => warn!("Failed to discard expired resumable upload {id}: {e}"),
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn info_path(&self, id: Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    fn data_path(&self, id: Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.part"))
+    }
+}
+
+/// Decode an `Upload-Metadata` header
+///
+/// Pairs are comma-separated; each is a key and an optional base64 value
+/// separated by a space. Returns `None` if a pair is malformed.
+#[must_use]
+pub fn parse_metadata(header: &str) -> Option<BTreeMap<String, String>> {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+
+    let mut metadata = BTreeMap::new();
+    for pair in header.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let mut parts = pair.split(' ');
+        let key = parts.next().filter(|key| !key.is_empty())?;
+        let value = match parts.next() {
+            Some(encoded) => String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?,
+            None => String::new(),
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        let _ = metadata.insert(key.to_string(), value);
+    }
+    Some(metadata)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn chunks(
+        parts: &'static [&'static [u8]],
+    ) -> impl Stream<Item = std::result::Result<Bytes, String>> {
+        stream::iter(parts.iter().map(|part| Ok(Bytes::from_static(part))))
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let metadata =
+            parse_metadata("filename Y2FsbC5tcDM=, filetype YXVkaW8vbXBlZw==,is_final").unwrap();
+        assert_eq!(metadata["filename"], "call.mp3");
+        assert_eq!(metadata["filetype"], "audio/mpeg");
+        assert_eq!(metadata["is_final"], "");
+        assert!(parse_metadata("").unwrap().is_empty());
+
+        assert!(parse_metadata("filename not-base64!").is_none());
+        assert!(parse_metadata("filename Y2FsbC5tcDM= extra").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resume_upload() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = ResumableUploads::new(dir.path());
+        let metadata = BTreeMap::from([("filename".to_string(), "call.mp3".to_string())]);
+        let id = uploads.create(10, metadata).await.unwrap();
+
+        let (info, offset) = uploads.status(id).await.unwrap();
+        assert_eq!((info.length, offset), (10, 0));
+        assert_eq!(info.filename(), Some("call.mp3"));
+
+        // A dropped connection keeps what arrived before it
+        let interrupted = chunks(&[b"abcd"]).chain(stream::iter([Err("reset".to_string())]));
+        assert_eq!(uploads.append(id, 0, interrupted).await.unwrap(), 4);
+        assert!(matches!(
+            uploads.read_complete(id).await,
+            Err(ResumableError::Incomplete {
+                offset: 4,
+                length: 10
+            })
+        ));
+
+        // Resuming from a stale offset is refused
+        assert!(matches!(
+            uploads.append(id, 0, chunks(&[b"abcd"])).await,
+            Err(ResumableError::OffsetMismatch { expected: 4 })
+        ));
+
+        assert_eq!(
+            uploads
+                .append(id, 4, chunks(&[b"ef", b"ghij"]))
+                .await
+                .unwrap(),
+            10
+        );
+        let (_, data) = uploads.read_complete(id).await.unwrap();
+        assert_eq!(&data[..], b"abcdefghij");
+
+        uploads.remove(id).await.unwrap();
+        assert!(matches!(
+            uploads.status(id).await,
+            Err(ResumableError::NotFound)
+        ));
+        assert!(matches!(
+            uploads.remove(id).await,
+            Err(ResumableError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_append_past_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = ResumableUploads::new(dir.path());
+        let id = uploads.create(3, BTreeMap::new()).await.unwrap();
+
+        assert!(matches!(
+            uploads.append(id, 0, chunks(&[b"ab", b"cd"])).await,
+            Err(ResumableError::TooLarge { length: 3 })
+        ));
+        let (_, data) = uploads.read_complete(id).await.unwrap();
+        assert_eq!(&data[..], b"abc");
+
+        assert!(matches!(
+            uploads.append(Uuid::new_v4(), 0, chunks(&[b"a"])).await,
+            Err(ResumableError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads = ResumableUploads::new(dir.path());
+        assert_eq!(
+            uploads
+                .purge_expired(Duration::from_secs(60))
+                .await
+                .unwrap(),
+            0
+        );
+
+        let id = uploads.create(5, BTreeMap::new()).await.unwrap();
+        assert_eq!(
+            uploads
+                .purge_expired(Duration::from_secs(60))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(uploads.purge_expired(Duration::ZERO).await.unwrap(), 1);
+        assert!(matches!(
+            uploads.status(id).await,
+            Err(ResumableError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,317 @@
+//! Retention purges
+//!
+//! When `retention.enabled` is set, periodically (or on
+//! `schedules.retention`) deletes calls, their recordings, and upload logs
+//! older than the configured retention periods and logs a report of what was
+//! removed. Admins can also trigger a purge on demand through
+//! `POST /api/admin/retention/run`.
+
+use crate::scheduler::{self, Schedule};
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::{config::RetentionConfig, schedule::CronSchedule};
+use sdrtrunk_storage::{AudioStorage, PgPool, RetentionQueries, legacy::refresh_system_stats};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::{sync::watch, task::JoinHandle};
+use tracing::{debug, info, warn};
+
+/// Counts from one retention run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RetentionReport {
+    /// Calls created before this were purged (`None` when calls are kept forever)
+    pub calls_cutoff: Option<DateTime<Utc>>,
+    /// Upload logs recorded before this were purged (`None` when kept forever)
+    pub upload_logs_cutoff: Option<DateTime<Utc>>,
+    /// Calls deleted, along with their transcription jobs
+    pub calls_deleted: usize,
+    /// Recordings removed from storage
+    pub audio_files_deleted: usize,
+    /// Bytes freed by removed recordings
+    pub audio_bytes_freed: u64,
+    /// Recordings that were already gone
+    pub audio_files_missing: usize,
+    /// Recordings left in place because they are outside recording storage
+    pub audio_files_skipped: usize,
+    /// Recordings that could not be removed
+    pub audio_files_failed: usize,
+    /// Upload log entries deleted
+    pub upload_logs_deleted: u64,
+    /// Wall-clock duration of the run in milliseconds
+    pub duration_ms: u64,
+}
+
+impl RetentionReport {
+    /// Whether the run deleted anything
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.calls_deleted == 0 && self.upload_logs_deleted == 0
+    }
+}
+
+/// What happened to one recording during a purge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RecordingOutcome {
+    /// Removed, freeing this many bytes
+    Deleted(u64),
+    /// Already gone
+    Missing,
+    /// Outside recording storage, left in place
+    Skipped,
+    /// Removal failed
+    Failed,
+}
+
+/// Spawn the retention task if retention is enabled
+///
+/// Runs on `schedule` when given, else every `check_interval_seconds`.
+/// Recordings are removed through `storage`. Each run uses the settings
+/// current in `settings`, so reloaded retention periods apply from the next
+/// run and disabling retention on a reload skips runs; the schedule itself
+/// is fixed at startup.
+#[must_use]
+pub fn spawn_retention_task(
+    pool: PgPool,
+    settings: watch::Receiver<RetentionConfig>,
+    storage: Arc<dyn AudioStorage>,
+    schedule: Option<&CronSchedule>,
+) -> Option<JoinHandle<()>> {
+    let config = settings.borrow().clone();
+    if !config.enabled {
+        return None;
+    }
+
+    info!(
+        "Retention enabled: keeping calls {} days, upload logs {} days",
+        config.call_retention_days, config.upload_log_retention_days
+    );
+    let schedule = Schedule::from_config(schedule, config.check_interval_seconds);
+    let job_pool = pool.clone();
+    Some(scheduler::spawn_job(
+        pool,
+        "retention",
+        schedule,
+        move || {
+            let pool = job_pool.clone();
+            let config = settings.borrow().clone();
+            let storage = Arc::clone(&storage);
+            async move {
+                if !config.enabled {
+                    return Ok("Retention disabled".to_string());
+                }
+                match run_retention(&pool, &config, storage.as_ref()).await {
+                    Ok(report) if report.is_empty() => {
+                        debug!("Retention run found nothing to purge");
+                        Ok("Nothing to purge".to_string())
+                    }
+                    Ok(report) => {
+                        log_report(&report);
+                        Ok(format!(
+                            "Purged {} calls, {} upload logs, {} recordings",
+                            report.calls_deleted,
+                            report.upload_logs_deleted,
+                            report.audio_files_deleted
+                        ))
+                    }
+                    Err(e) => Err(format!("Retention run failed: {e}")),
+                }
+            }
+        },
+    ))
+}
+
+/// Purge calls, recordings, and upload logs past their retention periods
+///
+/// Runs regardless of `config.enabled`, which only controls the schedule.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails. Recordings that cannot be
+/// removed are counted in the report rather than failing the run.
+pub async fn run_retention(
+    pool: &PgPool,
+    config: &RetentionConfig,
+    storage: &dyn AudioStorage,
+) -> sdrtrunk_storage::Result<RetentionReport> {
+    let started = Instant::now();
+    let mut report = RetentionReport {
+        calls_cutoff: cutoff(config.call_retention_days),
+        upload_logs_cutoff: cutoff(config.upload_log_retention_days),
+        ..RetentionReport::default()
+    };
+
+    if let Some(older_than) = report.calls_cutoff {
+        let mut systems = BTreeSet::new();
+        loop {
+            let purged =
+                RetentionQueries::purge_calls_batch(pool, older_than, config.batch_size).await?;
+            if purged.is_empty() {
+                break;
+            }
+            report.calls_deleted += purged.len();
+
+            for call in purged {
+                if config.delete_audio_files
+                    && let Some(path) = call.audio_file_path.as_deref()
+                {
+                    match remove_recording(path, storage).await {
+                        RecordingOutcome::Deleted(bytes) => {
+                            report.audio_files_deleted += 1;
+                            report.audio_bytes_freed += bytes;
+                        }
+                        RecordingOutcome::Missing => report.audio_files_missing += 1,
+                        RecordingOutcome::Skipped => report.audio_files_skipped += 1,
+                        RecordingOutcome::Failed => report.audio_files_failed += 1,
+                    }
+                }
+                let _ = systems.insert(call.system_id);
+            }
+        }
+
+        for system_id in &systems {
+            refresh_system_stats(pool, system_id).await?;
+        }
+    }
+
+    if let Some(older_than) = report.upload_logs_cutoff {
+        report.upload_logs_deleted = RetentionQueries::purge_upload_logs(pool, older_than).await?;
+    }
+
+    report.duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    Ok(report)
+}
+
+/// Log a summary of a retention run
+pub fn log_report(report: &RetentionReport) {
+    info!(
+        "Retention purge: {} calls, {} upload logs deleted; {} recordings removed ({} bytes), {} missing, {} skipped, {} failed in {}ms",
+        report.calls_deleted,
+        report.upload_logs_deleted,
+        report.audio_files_deleted,
+        report.audio_bytes_freed,
+        report.audio_files_missing,
+        report.audio_files_skipped,
+        report.audio_files_failed,
+        report.duration_ms
+    );
+}
+
+/// Cutoff for a retention period, or `None` when data is kept forever
+fn cutoff(days: u32) -> Option<DateTime<Utc>> {
+    (days > 0).then(|| Utc::now() - chrono::Duration::days(i64::from(days)))
+}
+
+/// Remove a recording from recording storage
+///
+/// Locations outside the storage are never touched, so a call record pointing
+/// at an arbitrary file cannot be used to delete it. Empty date directories
+/// left behind by local recordings are pruned by the storage.
+pub(crate) async fn remove_recording(
+    location: &str,
+    storage: &dyn AudioStorage,
+) -> RecordingOutcome {
+    if !storage.contains(location) {
+        return RecordingOutcome::Skipped;
+    }
+    match storage.delete(location).await {
+        Ok(Some(bytes)) => RecordingOutcome::Deleted(bytes),
+        Ok(None) => RecordingOutcome::Missing,
+        Err(e) => {
+            warn!("Failed to remove recording {location}: {e}");
+            RecordingOutcome::Failed
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use sdrtrunk_storage::LocalAudioStorage;
+
+    #[test]
+    fn test_cutoff() {
+        assert_eq!(cutoff(0), None);
+
+        let thirty_days = cutoff(30).unwrap();
+        let expected = Utc::now() - chrono::Duration::days(30);
+        assert!((expected - thirty_days).num_seconds().abs() < 5);
+    }
+
+    #[tokio::test]
+    async fn test_remove_recording() {
+        let storage = tempfile::tempdir().unwrap();
+        let root = storage.path().join("uploads");
+        let day_dir = root.join("metro/2024/01/01");
+        std::fs::create_dir_all(&day_dir).unwrap();
+        std::fs::write(day_dir.join("a.mp3"), b"ID3audio").unwrap();
+        std::fs::write(day_dir.join("b.mp3"), b"ID3").unwrap();
+        let recordings = LocalAudioStorage::new(&root, "");
+        let location = |name: &str| day_dir.join(name).to_string_lossy().to_string();
+
+        // A sibling keeps the directory alive
+        assert_eq!(
+            remove_recording(&location("a.mp3"), &recordings).await,
+            RecordingOutcome::Deleted(8)
+        );
+        assert!(day_dir.is_dir());
+
+        // The last recording takes the empty date directories with it
+        assert_eq!(
+            remove_recording(&location("b.mp3"), &recordings).await,
+            RecordingOutcome::Deleted(3)
+        );
+        assert!(!root.join("metro").exists());
+        assert!(root.is_dir());
+
+        assert_eq!(
+            remove_recording(&location("a.mp3"), &recordings).await,
+            RecordingOutcome::Missing
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_recording_outside_storage() {
+        let storage = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let file = outside.path().join("keep.mp3");
+        std::fs::write(&file, b"ID3").unwrap();
+        let recordings = LocalAudioStorage::new(storage.path(), "uploads");
+
+        assert_eq!(
+            remove_recording(&file.to_string_lossy(), &recordings).await,
+            RecordingOutcome::Skipped
+        );
+        assert!(file.exists());
+
+        let escaping = storage.path().join("..").join("keep.mp3");
+        assert_eq!(
+            remove_recording(&escaping.to_string_lossy(), &recordings).await,
+            RecordingOutcome::Skipped
+        );
+        assert_eq!(
+            remove_recording("s3://radio/metro/keep.mp3", &recordings).await,
+            RecordingOutcome::Skipped
+        );
+    }
+
+    #[test]
+    fn test_report_is_empty() {
+        assert!(RetentionReport::default().is_empty());
+        assert!(
+            !RetentionReport {
+                upload_logs_deleted: 3,
+                ..RetentionReport::default()
+            }
+            .is_empty()
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Scheduled background jobs
+//!
+//! Periodic jobs (retention purges, statistics rollups, analyze) run either
+//! every N seconds or on a cron expression from the `[schedules]` config
+//! section. Each job records its next run and the outcome of its last run in
+//! `scheduled_jobs`, listed by `GET /api/admin/jobs`.
+
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::schedule::CronSchedule;
+use sdrtrunk_storage::{PgPool, ScheduleQueries};
+use std::{fmt, future::Future, time::Duration};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// When a job runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// Immediately on startup, then this long after each run starts
+    Every(Duration),
+    /// At the minutes matching a cron expression
+    Cron(CronSchedule),
+}
+
+impl Schedule {
+    /// The cron schedule if one is configured, else every `interval_seconds`
+    /// (at least one second)
+    #[must_use]
+    pub fn from_config(cron: Option<&CronSchedule>, interval_seconds: u64) -> Self {
+        cron.map_or_else(
+            || Self::Every(Duration::from_secs(interval_seconds.max(1))),
+            |cron| Self::Cron(cron.clone()),
+        )
+    }
+
+    /// When the first run is due, given the scheduler starts at `now`
+    #[must_use]
+    pub fn first_run(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(_) => Some(now),
+            Self::Cron(cron) => cron.next_after(now),
+        }
+    }
+
+    /// When the run after one started at `started` is due
+    #[must_use]
+    pub fn next_run(&self, started: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(period) => chrono::Duration::from_std(*period)
+                .ok()
+                .and_then(|period| started.checked_add_signed(period)),
+            Self::Cron(cron) => cron.next_after(started),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Every(period) => write!(f, "every {}s", period.as_secs()),
+            Self::Cron(cron) => write!(f, "cron {cron}"),
+        }
+    }
+}
+
+/// Run `job` on `schedule` in a background task
+///
+/// The job returns a summary of what it did, or an error message; both are
+/// recorded under `name` in `scheduled_jobs`. Failing to record a run is
+/// logged but does not stop the job.
+pub fn spawn_job<F, Fut>(
+    pool: PgPool,
+    name: &'static str,
+    schedule: Schedule,
+    mut job: F,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<String, String>> + Send,
+{
+    info!("Scheduled job {name}: {schedule}");
+    tokio::spawn(async move {
+        let description = schedule.to_string();
+        let mut next = schedule.first_run(Utc::now());
+        loop {
+            record(
+                ScheduleQueries::schedule(&pool, name, &description, next).await,
+                name,
+            );
+            let Some(due) = next else {
+                warn!("Scheduled job {name} has no upcoming run; stopping");
+                return;
+            };
+            tokio::time::sleep((due - Utc::now()).to_std().unwrap_or_default()).await;
+
+            let started = Utc::now();
+            record(ScheduleQueries::start(&pool, name).await, name);
+            let (succeeded, message) = match job().await {
+                Ok(summary) => {
+                    debug!("Scheduled job {name} finished: {summary}");
+                    (true, summary)
+                }
+                Err(e) => {
+                    warn!("Scheduled job {name} failed: {e}");
+                    (false, e)
+                }
+            };
+            record(
+                ScheduleQueries::finish(&pool, name, succeeded, &message).await,
+                name,
+            );
+            next = schedule.next_run(started);
+        }
+    })
+}
+
+/// Log a failure to record a job's state
+fn record(result: sdrtrunk_storage::Result<()>, name: &str) {
+    if let Err(e) = result {
+        warn!("Failed to record state of scheduled job {name}: {e}");
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_interval_schedule() {
+        let schedule = Schedule::from_config(None, 0);
+        assert_eq!(schedule, Schedule::Every(Duration::from_secs(1)));
+
+        let schedule = Schedule::from_config(None, 300);
+        let now = at("2025-01-01T12:00:00Z");
+        assert_eq!(schedule.first_run(now), Some(now));
+        assert_eq!(schedule.next_run(now), Some(at("2025-01-01T12:05:00Z")));
+        assert_eq!(schedule.to_string(), "every 300s");
+    }
+
+    #[test]
+    fn test_cron_schedule_overrides_interval() {
+        let cron: CronSchedule = "0 3 * * *".parse().unwrap();
+        let schedule = Schedule::from_config(Some(&cron), 300);
+        let now = at("2025-01-01T12:00:00Z");
+        assert_eq!(schedule.first_run(now), Some(at("2025-01-02T03:00:00Z")));
+        assert_eq!(
+            schedule.next_run(at("2025-01-02T03:00:00Z")),
+            Some(at("2025-01-03T03:00:00Z"))
+        );
+        assert_eq!(schedule.to_string(), "cron 0 3 * * *");
+    }
+}
@@ -0,0 +1,579 @@
+//! Export of completed transcriptions to Elasticsearch/OpenSearch
+//!
+//! With `search_index.enabled` set, a task listens for completed
+//! transcriptions in the worker progress notifications and sends the calls to
+//! the cluster's `_bulk` API, one document per call ID, in batches of up to
+//! `batch_size`. Batches that fail are retried with the next flush.
+//! Transcriptions that complete while the API server is down, or before the
+//! index was enabled, are sent by a backfill: `sdrtrunk-api-server
+//! --search-backfill` indexes every completed call and exits without starting
+//! the server. Re-indexing a call replaces its document, so a backfill can be
+//! re-run at any time.
+
+use crate::progress::ProgressEvents;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use reqwest::header::CONTENT_TYPE;
+use rust_decimal::Decimal;
+use sdrtrunk_protocol::config::SearchIndexConfig;
+use sdrtrunk_storage::models::RadioCallDb;
+use sdrtrunk_storage::{
+    CallCursor, PgPool, ProgressStage, RadioCallFilter, list_radio_calls_filtered,
+    queries::RadioCallQueries,
+};
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Command-line flag requesting a backfill of the search index
+pub const BACKFILL_FLAG: &str = "--search-backfill";
+
+/// Batches of call IDs kept while the cluster is unreachable
+const MAX_PENDING_BATCHES: usize = 10;
+
+/// Document indexed for a call
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchDocument {
+    /// Call ID
+    pub call_id: Uuid,
+    /// When the call was recorded
+    pub call_timestamp: DateTime<Utc>,
+    /// System of the call
+    pub system_id: SystemId,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Talkgroup of the call
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Talkgroup category
+    pub talkgroup_group: Option<String>,
+    /// Talkgroup tag
+    pub talkgroup_tag: Option<String>,
+    /// Radio that transmitted
+    pub source_radio_id: Option<i32>,
+    /// Talker alias of the transmitting radio
+    pub talker_alias: Option<String>,
+    /// Frequency in Hz
+    pub frequency: Option<i64>,
+    /// Call length in seconds
+    pub duration_seconds: Option<Decimal>,
+    /// Transcript
+    pub transcription_text: Option<String>,
+    /// Transcription confidence
+    pub transcription_confidence: Option<Decimal>,
+    /// Detected or configured language
+    pub transcription_language: Option<String>,
+    /// Number of speakers, when diarized
+    pub speaker_count: Option<i32>,
+    /// Where the call was heard, as an Elasticsearch `geo_point`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoPoint>,
+}
+
+/// Latitude and longitude in WGS 84 degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeoPoint {
+    /// Latitude
+    pub lat: f64,
+    /// Longitude
+    pub lon: f64,
+}
+
+impl From<&RadioCallDb> for SearchDocument {
+    fn from(call: &RadioCallDb) -> Self {
+        Self {
+            call_id: call.id,
+            call_timestamp: call.call_timestamp,
+            system_id: call.system_id.clone(),
+            system_label: call.system_label.clone(),
+            talkgroup_id: call.talkgroup_id,
+            talkgroup_label: call.talkgroup_label.clone(),
+            talkgroup_group: call.talkgroup_group.clone(),
+            talkgroup_tag: call.talkgroup_tag.clone(),
+            source_radio_id: call.source_radio_id.map(RadioId::as_i32),
+            talker_alias: call.talker_alias.clone(),
+            frequency: call.frequency.map(Frequency::as_hz),
+            duration_seconds: call.duration_seconds,
+            transcription_text: call.transcription_text.clone(),
+            transcription_confidence: call.transcription_confidence,
+            transcription_language: call.transcription_language.clone(),
+            speaker_count: call.speaker_count,
+            location: call
+                .latitude
+                .zip(call.longitude)
+                .map(|(lat, lon)| GeoPoint { lat, lon }),
+        }
+    }
+}
+
+/// Counts from indexing calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexSummary {
+    /// Calls the cluster accepted
+    pub indexed: usize,
+    /// Calls the cluster rejected
+    pub failed: usize,
+}
+
+/// Check whether a backfill was requested on the command line
+#[must_use]
+pub fn backfill_requested<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref() == BACKFILL_FLAG)
+}
+
+/// Newline-delimited `_bulk` body indexing `calls` into `index`
+///
+/// # Errors
+///
+/// Returns an error if a document cannot be serialized.
+pub fn bulk_body(index: &str, calls: &[RadioCallDb]) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    for call in calls {
+        let action = serde_json::json!({ "index": { "_index": index, "_id": call.id } });
+        serde_json::to_writer(&mut body, &action)?;
+        body.push(b'\n');
+        serde_json::to_writer(&mut body, &SearchDocument::from(call))?;
+        body.push(b'\n');
+    }
+    Ok(body)
+}
+
+/// Sends calls to the cluster's `_bulk` API
+#[derive(Debug, Clone)]
+pub struct SearchIndexer {
+    http: reqwest::Client,
+    config: SearchIndexConfig,
+}
+
+impl SearchIndexer {
+    /// Create an indexer from the search index settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(config: &SearchIndexConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds.max(1)))
+            .build()
+            .context("Failed to build search index client")?;
+        Ok(Self {
+            http,
+            config: config.clone(),
+        })
+    }
+
+    /// Index `calls` in one bulk request
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the cluster rejects it as a
+    /// whole. Calls rejected individually are counted as failed.
+    pub async fn index(&self, calls: &[RadioCallDb]) -> Result<IndexSummary> {
+        if calls.is_empty() {
+            return Ok(IndexSummary::default());
+        }
+        let url = format!("{}/_bulk", self.config.url.trim_end_matches('/'));
+        let mut request = self
+            .http
+            .post(&url)
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(bulk_body(&self.config.index, calls)?);
+        if let Some(username) = self.config.username.as_deref() {
+            request = request.basic_auth(username, self.config.password.as_deref());
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP {status}: {detail}"));
+        }
+        let body: serde_json::Value = response.json().await?;
+        let failed = bulk_failures(&body);
+        Ok(IndexSummary {
+            indexed: calls.len().saturating_sub(failed),
+            failed,
+        })
+    }
+
+    /// Index the completed calls among `ids`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calls cannot be loaded or indexed.
+    pub async fn index_ids(&self, pool: &PgPool, ids: &[Uuid]) -> Result<IndexSummary> {
+        let calls = RadioCallQueries::find_completed(pool, ids).await?;
+        self.index(&calls).await
+    }
+}
+
+/// Number of items a `_bulk` response reports as failed, logging the first
+/// reason
+fn bulk_failures(response: &serde_json::Value) -> usize {
+    if response["errors"] != serde_json::Value::Bool(true) {
+        return 0;
+    }
+    let errors: Vec<&serde_json::Value> = response["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item["index"].get("error"))
+        .collect();
+    if let Some(error) = errors.first() {
+        warn!(
+            "Search index rejected {} call(s), e.g.: {error}",
+            errors.len()
+        );
+    }
+    errors.len()
+}
+
+/// Index every completed call, newest first
+///
+/// # Errors
+///
+/// Returns an error if a database query or bulk request fails.
+pub async fn run_backfill(config: &SearchIndexConfig, pool: &PgPool) -> Result<IndexSummary> {
+    let indexer = SearchIndexer::new(config)?;
+    let batch_size = i64::try_from(config.batch_size.max(1)).unwrap_or(i64::MAX);
+    info!(
+        "Backfilling search index '{}' at {}",
+        config.index, config.url
+    );
+
+    let mut summary = IndexSummary::default();
+    let mut after = None;
+    loop {
+        let filter = RadioCallFilter {
+            system_id: None,
+            allowed_systems: None,
+            talkgroup_id: None,
+            transcription_status: Some("completed"),
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
+            from_date: None,
+            to_date: None,
+            limit: batch_size,
+            after,
+        };
+        let calls = list_radio_calls_filtered(pool, filter).await?;
+        let Some(last) = calls.last() else {
+            break;
+        };
+        after = Some(CallCursor::after(last));
+
+        let batch = indexer.index(&calls).await?;
+        summary.indexed += batch.indexed;
+        summary.failed += batch.failed;
+        info!("Indexed {} calls so far", summary.indexed);
+    }
+
+    info!(
+        "Search index backfill finished: {} indexed, {} failed",
+        summary.indexed, summary.failed
+    );
+    Ok(summary)
+}
+
+/// Spawn the search index task if enabled
+///
+/// One task collects completed call IDs from worker progress events; the
+/// returned one sends them in batches.
+#[must_use]
+pub fn spawn_search_index_task(
+    pool: PgPool,
+    progress: ProgressEvents,
+    config: &SearchIndexConfig,
+) -> Option<JoinHandle<()>> {
+    if !config.enabled {
+        return None;
+    }
+    let indexer = match SearchIndexer::new(config) {
+        Ok(indexer) => indexer,
+        Err(e) => {
+            warn!("Search index disabled: {e:#}");
+            return None;
+        }
+    };
+    info!(
+        "Indexing completed transcriptions into '{}' at {}",
+        config.index, config.url
+    );
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    drop(tokio::spawn(collect_completed_calls(progress, tx)));
+    Some(tokio::spawn(send_batches(
+        indexer,
+        pool,
+        rx,
+        config.batch_size.max(1),
+        Duration::from_secs(config.flush_interval_seconds.max(1)),
+    )))
+}
+
+/// Forward the IDs of calls whose transcription completed
+async fn collect_completed_calls(mut progress: ProgressEvents, tx: mpsc::UnboundedSender<Uuid>) {
+    while let Some(event) = progress.recv().await {
+        if matches!(event.stage, ProgressStage::Completed { .. }) && tx.send(event.call_id).is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Index collected call IDs once `batch_size` are waiting or `flush_interval`
+/// has passed
+async fn send_batches(
+    indexer: SearchIndexer,
+    pool: PgPool,
+    mut rx: mpsc::UnboundedReceiver<Uuid>,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut pending: Vec<Uuid> = Vec::new();
+    let mut ticker = tokio::time::interval(flush_interval);
+    loop {
+        let closed = tokio::select! {
+            received = rx.recv() => match received {
+                Some(call_id) => {
+                    pending.push(call_id);
+                    if pending.len() < batch_size {
+                        continue;
+                    }
+                    false
+                }
+                None => true,
+            },
+            _ = ticker.tick() => false,
+        };
+
+        let mut sent = 0;
+        for batch in pending.chunks(batch_size) {
+            match indexer.index_ids(&pool, batch).await {
+                Ok(_) => sent += batch.len(),
+                Err(e) => {
+                    warn!("Failed to index {} call(s): {e:#}", batch.len());
+                    break;
+                }
+            }
+        }
+        drop(pending.drain(..sent));
+        trim_pending(&mut pending, batch_size);
+        if closed {
+            return;
+        }
+    }
+}
+
+/// Drop the oldest call IDs beyond `MAX_PENDING_BATCHES` batches
+///
+/// Unsent IDs are retried with the next flush; while the cluster stays
+/// unreachable, the oldest are left for a backfill.
+fn trim_pending(pending: &mut Vec<Uuid>, batch_size: usize) {
+    let excess = pending
+        .len()
+        .saturating_sub(batch_size.saturating_mul(MAX_PENDING_BATCHES));
+    if excess > 0 {
+        warn!(
+            "Search index unreachable; dropped {excess} call(s), run {BACKFILL_FLAG} to add them"
+        );
+        drop(pending.drain(..excess));
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    fn call(text: &str) -> RadioCallDb {
+        let now = Utc::now();
+        RadioCallDb {
+            id: Uuid::new_v4(),
+            created_at: now,
+            call_timestamp: now,
+            system_id: SystemId::new("metro").unwrap(),
+            system_label: Some("Metro".to_string()),
+            frequency: None,
+            talkgroup_id: Some(TalkgroupId::new(100).unwrap()),
+            talkgroup_label: None,
+            talkgroup_group: None,
+            talkgroup_tag: None,
+            source_radio_id: None,
+            talker_alias: None,
+            audio_filename: None,
+            audio_file_path: None,
+            audio_size_bytes: None,
+            audio_content_type: None,
+            audio_sha256: None,
+            duration_seconds: None,
+            transcription_text: Some(text.to_string()),
+            transcription_confidence: None,
+            transcription_language: Some("en".to_string()),
+            transcription_status: Some("completed".to_string()),
+            speaker_segments: None,
+            speaker_count: None,
+            patches: None,
+            frequencies: None,
+            sources: None,
+            upload_ip: None,
+            upload_timestamp: now,
+            upload_api_key_id: None,
+            latitude: Some(35.7796),
+            longitude: Some(-78.6382),
+        }
+    }
+
+    #[test]
+    fn test_backfill_requested() {
+        assert!(backfill_requested([
+            "sdrtrunk-api-server",
+            "--search-backfill"
+        ]));
+        assert!(!backfill_requested(["sdrtrunk-api-server", "--demo"]));
+    }
+
+    #[test]
+    fn test_bulk_body() {
+        let calls = [call("engine two on scene"), call("copy")];
+        let body = String::from_utf8(bulk_body("calls", &calls).unwrap()).unwrap();
+        let lines: Vec<serde_json::Value> = body
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(body.ends_with('\n'));
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            json!({"index": {"_index": "calls", "_id": calls[0].id}})
+        );
+        assert_eq!(lines[1]["transcription_text"], "engine two on scene");
+        assert_eq!(lines[1]["system_id"], "metro");
+        assert_eq!(lines[1]["talkgroup_id"], 100);
+        assert_eq!(
+            lines[1]["location"],
+            json!({"lat": 35.7796, "lon": -78.6382})
+        );
+        assert_eq!(lines[2]["index"]["_id"], calls[1].id.to_string());
+    }
+
+    #[test]
+    fn test_document_without_location() {
+        let mut call = call("copy");
+        call.longitude = None;
+        let document = serde_json::to_value(SearchDocument::from(&call)).unwrap();
+        assert!(document.get("location").is_none());
+    }
+
+    #[test]
+    fn test_bulk_failures() {
+        assert_eq!(bulk_failures(&json!({"errors": false, "items": []})), 0);
+        let response = json!({"errors": true, "items": [
+            {"index": {"_id": "a", "status": 201}},
+            {"index": {"_id": "b", "status": 400, "error": {"type": "mapper_parsing_exception"}}},
+        ]});
+        assert_eq!(bulk_failures(&response), 1);
+    }
+
+    #[test]
+    fn test_trim_pending() {
+        let mut pending: Vec<Uuid> = (0..25).map(|_| Uuid::new_v4()).collect();
+        let newest = pending[24];
+        trim_pending(&mut pending, 2);
+        assert_eq!(pending.len(), 2 * MAX_PENDING_BATCHES);
+        assert_eq!(pending.last(), Some(&newest));
+
+        trim_pending(&mut pending, 100);
+        assert_eq!(pending.len(), 2 * MAX_PENDING_BATCHES);
+    }
+
+    #[tokio::test]
+    async fn test_index() {
+        let app = Router::new().route(
+            "/_bulk",
+            post(|headers: HeaderMap, body: String| async move {
+                assert_eq!(headers[CONTENT_TYPE], "application/x-ndjson");
+                assert!(headers.contains_key("authorization"));
+                let items: Vec<serde_json::Value> = body
+                    .lines()
+                    .step_by(2)
+                    .enumerate()
+                    .map(|(i, _)| {
+                        if i == 0 {
+                            json!({"index": {"status": 201}})
+                        } else {
+                            json!({"index": {"status": 400, "error": {"type": "rejected"}}})
+                        }
+                    })
+                    .collect();
+                axum::Json(json!({"errors": items.len() > 1, "items": items}))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let indexer = SearchIndexer::new(&SearchIndexConfig {
+            enabled: true,
+            url: format!("{base}/"),
+            username: Some("elastic".to_string()),
+            password: Some("changeme".to_string()),
+            ..SearchIndexConfig::default()
+        })
+        .unwrap();
+        assert_eq!(
+            indexer.index(&[call("copy")]).await.unwrap(),
+            IndexSummary {
+                indexed: 1,
+                failed: 0
+            }
+        );
+        assert_eq!(
+            indexer
+                .index(&[call("copy"), call("ten four")])
+                .await
+                .unwrap(),
+            IndexSummary {
+                indexed: 1,
+                failed: 1
+            }
+        );
+        assert_eq!(indexer.index(&[]).await.unwrap(), IndexSummary::default());
+
+        let unreachable = SearchIndexer::new(&SearchIndexConfig {
+            url: format!("{base}/missing"),
+            ..SearchIndexConfig::default()
+        })
+        .unwrap();
+        let error = unreachable.index(&[call("copy")]).await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(&StatusCode::NOT_FOUND.as_u16().to_string())
+        );
+    }
+}
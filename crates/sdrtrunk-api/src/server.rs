@@ -0,0 +1,532 @@
+//! API server startup
+//!
+//! [`run`] is the whole life of the `sdrtrunk-api-server` binary: logging,
+//! configuration, the one-off import, backfill, and key rotation modes,
+//! database setup, background tasks, and serving until a shutdown signal. A
+//! caller can hand it the web interface's router to serve under [`UI_PATH`]
+//! on the same port, so small deployments run one process instead of two.
+
+use crate::{
+    AppState, alerts, build_app, demo, encryption, import, legacy, maintenance, migrate,
+    notifications,
+    progress::ProgressEvents,
+    reload::{self, LiveSettings, LogFilterHandle},
+    reports, retention, search_index, stats_cache, summarizer, webhooks, worker_metrics,
+};
+use anyhow::{Result, anyhow};
+use axum::Router;
+use sdrtrunk_protocol::Config;
+use sdrtrunk_storage::{Database, PgPool};
+use std::{net::SocketAddr, path::PathBuf};
+use tokio::net::TcpListener;
+use tower::ServiceBuilder;
+use tower_http::trace::TraceLayer;
+use tracing::{error, info};
+
+/// Config file watched for changes, alongside the other `config.*` formats
+/// read at startup
+const CONFIG_FILE: &str = "config.toml";
+
+/// Path the web interface is served under when embedded in the API server
+pub const UI_PATH: &str = "/ui";
+
+/// Builds the web interface's router from the loaded configuration
+pub type UiBuilder = fn(Config) -> Router;
+
+/// Load environment configuration
+///
+/// Returns the handle for replacing the log filter on config reloads.
+///
+/// # Errors
+///
+/// Returns error if logging initialization fails
+pub fn load_environment() -> Result<LogFilterHandle> {
+    use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
+    let (filter, handle) = tracing_subscriber::reload::Layer::new(
+        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+    );
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            fmt::layer()
+                .with_target(false)
+                .with_thread_ids(false)
+                .with_thread_names(false)
+                .with_file(false)
+                .with_line_number(false)
+                .with_level(true)
+                .compact(),
+        )
+        .init();
+    Ok(handle)
+}
+
+/// Load configuration from environment variables and config files.
+///
+/// # Errors
+///
+/// Returns an error if configuration cannot be loaded or parsed.
+fn load_config() -> Result<Config> {
+    let cfg = config::Config::builder()
+        .add_source(config::File::with_name("config").required(false))
+        .add_source(config::Environment::with_prefix("SDRTRUNK").separator("_"))
+        .build()
+        .map_err(|e| anyhow!("Configuration load error: {e}"))?;
+    cfg.try_deserialize()
+        .map_err(|e| anyhow!("Configuration deserialization error: {e}"))
+}
+
+/// Load and validate configuration
+#[must_use]
+pub fn load_and_validate_config() -> Config {
+    load_config().unwrap_or_else(|err| {
+        info!("Failed to load config ({}), using defaults", err);
+        Config::default()
+    })
+}
+
+/// Print startup banner
+#[allow(clippy::cognitive_complexity)]
+pub fn print_startup_banner(config: &Config) {
+    info!("╔══════════════════════════════════════════════════════════╗");
+    info!(
+        "║       SDRTrunk Transcriber API Server v{}             ║",
+        env!("CARGO_PKG_VERSION")
+    );
+    info!("╚══════════════════════════════════════════════════════════╝");
+    info!(
+        "Starting server on {}:{}",
+        config.server.host, config.server.port
+    );
+}
+
+/// Initialize database with migrations and health check
+///
+/// # Errors
+///
+/// Returns error if database connection, migration, or health check fails
+#[allow(clippy::cognitive_complexity)]
+pub async fn initialize_database(config: &Config) -> Result<Database> {
+    // Initialize database connection
+    info!("Connecting to database...");
+    let database = Database::new(config).await.map_err(|e| {
+        error!("Failed to connect to database: {}", e);
+        anyhow!("Database connection failed: {e}")
+    })?;
+
+    info!("Database connection established");
+    if config.database.read_url.is_some() {
+        info!("Sending heavy read queries to the read replica");
+    }
+
+    // Initialize schema (creates tables if they don't exist)
+    info!("Initializing database schema...");
+    database.init_schema().await.map_err(|e| {
+        error!("Schema initialization failed: {}", e);
+        anyhow!("Schema init failed: {e}")
+    })?;
+
+    info!("Database schema ready");
+
+    // Perform database health check
+    database.health_check().await.map_err(|e| {
+        error!("Database health check failed: {}", e);
+        anyhow!("Database health check failed: {e}")
+    })?;
+
+    info!("Database health check passed");
+    Ok(database)
+}
+
+/// Create server address from configuration
+///
+/// # Errors
+///
+/// Returns error if address format is invalid
+pub fn create_server_address(config: &Config) -> Result<SocketAddr> {
+    format!("{}:{}", config.server.host, config.server.port)
+        .parse()
+        .map_err(|e| anyhow!("Invalid server address: {e}"))
+}
+
+/// Print server ready banner
+#[allow(clippy::cognitive_complexity)]
+pub fn print_ready_banner(addr: SocketAddr) {
+    info!("╔══════════════════════════════════════════════════════════╗");
+    info!("║                     SERVER READY                         ║");
+    info!("╟──────────────────────────────────────────────────────────╢");
+    info!("║ 🌐 API:     http://{:12}", addr);
+    info!("║ Health:  http://{:12}/health", addr);
+    info!("║ Docs:    http://{:12}/api/docs", addr);
+    info!("╚══════════════════════════════════════════════════════════╝\n");
+}
+
+/// Run the API server until a shutdown signal arrives
+///
+/// With `ui`, the router it builds is served under [`UI_PATH`] alongside the
+/// API, outside the API's middleware.
+///
+/// # Errors
+///
+/// Returns an error if logging, the database, a migration, import, backfill,
+/// or key rotation run, or the listener fails.
+#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+pub async fn run(ui: Option<UiBuilder>) -> Result<()> {
+    let log_filter = load_environment()?;
+    let mut config = load_and_validate_config();
+    // RUST_LOG takes precedence over the configured level at startup
+    if std::env::var_os("RUST_LOG").is_none()
+        && let Err(e) = reload::set_log_level(&log_filter, &config.logging.level)
+    {
+        error!("Ignoring logging.level: {e}");
+    }
+    let migrate_args = migrate::migrate_requested(std::env::args()).unwrap_or_else(|e| e.exit());
+    if let Some(args) = migrate_args {
+        let _report = migrate::run_migrate(&config, &args)
+            .await
+            .map_err(|e| anyhow!("Migration failed: {e:#}"))?;
+        return Ok(());
+    }
+    let legacy_import = legacy::import_requested(std::env::args())?;
+    let recording_import = import::import_requested(std::env::args())?;
+    let demo_mode = demo::demo_requested(std::env::args());
+    let search_backfill = search_index::backfill_requested(std::env::args());
+    let rotate_key = encryption::rotate_requested(std::env::args());
+    if demo_mode {
+        info!("Demo mode enabled: seeding synthetic data and using the mock transcriber");
+        demo::apply_demo_config(&mut config);
+    }
+    print_startup_banner(&config);
+    let database = initialize_database(&config).await?;
+
+    if let Some(args) = legacy_import {
+        let _summary = legacy::run_import(config, database.pool().clone(), &args)
+            .await
+            .map_err(|e| anyhow!("Legacy import failed: {e}"))?;
+        return Ok(());
+    }
+
+    if let Some(args) = recording_import {
+        let _summary = import::run_import(config, database.pool().clone(), &args)
+            .await
+            .map_err(|e| anyhow!("Recording import failed: {e:#}"))?;
+        return Ok(());
+    }
+
+    if search_backfill {
+        let _summary = search_index::run_backfill(&config.search_index, database.pool())
+            .await
+            .map_err(|e| anyhow!("Search index backfill failed: {e:#}"))?;
+        return Ok(());
+    }
+
+    if rotate_key {
+        let _report = encryption::run_rotation(&config.encryption, database.pool())
+            .await
+            .map_err(|e| anyhow!("Encryption key rotation failed: {e:#}"))?;
+        return Ok(());
+    }
+
+    if demo_mode {
+        let _summary = demo::seed(database.pool())
+            .await
+            .map_err(|e| anyhow!("Demo seeding failed: {e}"))?;
+        drop(demo::spawn_mock_transcriber(database.pool().clone()));
+    }
+
+    // Build the application router
+    info!("Building application routes...");
+    let (app, state) = build_app(
+        config.clone(),
+        database.pool().clone(),
+        database.read_pool().clone(),
+    )
+    .await?;
+    let app = match ui {
+        Some(build_ui) => {
+            info!("Serving the web interface under {UI_PATH}");
+            app.nest(UI_PATH, build_ui(config.clone()))
+        }
+        None => app,
+    };
+    let app = app.layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()));
+
+    spawn_background_tasks(&config, database.pool(), &state)?;
+
+    drop(reload::spawn_config_watcher(
+        PathBuf::from(CONFIG_FILE),
+        move || {
+            let mut config = load_config()?;
+            if demo_mode {
+                demo::apply_demo_config(&mut config);
+            }
+            Ok(config)
+        },
+        LiveSettings::new(config.clone(), &state, Some(log_filter)),
+    ));
+
+    let addr = create_server_address(&config)?;
+
+    // Create TCP listener
+    let listener = TcpListener::bind(&addr)
+        .await
+        .map_err(|e| anyhow!("Failed to bind to {addr}: {e}"))?;
+
+    print_ready_banner(addr);
+    if ui.is_some() {
+        info!("Web interface: http://{addr}{UI_PATH}");
+    }
+
+    // Start the server with graceful shutdown
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await
+    .map_err(|e| anyhow!("Server error: {e}"))?;
+
+    info!("Server shutdown complete");
+    Ok(())
+}
+
+/// Start the periodic and background jobs
+///
+/// # Errors
+///
+/// Returns error if the configured recording storage is invalid
+fn spawn_background_tasks(config: &Config, pool: &PgPool, state: &AppState) -> Result<()> {
+    drop(maintenance::spawn_maintenance_task(
+        pool.clone(),
+        config.maintenance.clone(),
+        config.schedules.auto_analyze.as_ref(),
+    ));
+    drop(maintenance::spawn_stats_rollup_task(
+        pool.clone(),
+        &config.maintenance,
+        config.schedules.stats_rollup.as_ref(),
+    ));
+    drop(maintenance::spawn_partition_task(
+        pool.clone(),
+        &config.maintenance,
+        state.retention.subscribe(),
+        config.schedules.partitions.as_ref(),
+    ));
+    drop(retention::spawn_retention_task(
+        pool.clone(),
+        state.retention.subscribe(),
+        sdrtrunk_storage::audio::from_config(&config.storage)?,
+        config.schedules.retention.as_ref(),
+    ));
+    let notifications = notifications::Notifications::from_config(&config.notifications);
+    drop(alerts::spawn_alert_task(
+        pool.clone(),
+        ProgressEvents::subscribe(&state.events, "Keyword alerts"),
+        &config.alerts,
+        notifications.as_ref(),
+    ));
+    drop(reports::spawn_report_tasks(
+        pool,
+        &config.reports,
+        config.alerts.smtp.as_ref(),
+        notifications.as_ref(),
+    ));
+    drop(notifications::spawn_failure_task(
+        pool.clone(),
+        ProgressEvents::subscribe(&state.events, "Failure notifications"),
+        notifications.as_ref(),
+    ));
+    drop(webhooks::spawn_webhook_task(
+        pool.clone(),
+        ProgressEvents::subscribe(&state.events, "Webhooks"),
+        &config.webhooks,
+    ));
+    drop(search_index::spawn_search_index_task(
+        pool.clone(),
+        ProgressEvents::subscribe(&state.events, "Search index"),
+        &config.search_index,
+    ));
+    drop(summarizer::spawn_summarizer_task(
+        pool.clone(),
+        ProgressEvents::subscribe(&state.events, "Summarizer"),
+        &config.summarizer,
+    ));
+    drop(worker_metrics::spawn_worker_metrics_task(
+        ProgressEvents::subscribe(&state.events, "Worker metrics"),
+        state.worker_pool.clone(),
+    ));
+    drop(stats_cache::spawn_invalidation_task(
+        pool.clone(),
+        ProgressEvents::subscribe(&state.events, "Statistics cache"),
+        state.stats_cache.clone(),
+    ));
+    Ok(())
+}
+
+/// Handle graceful shutdown signals
+///
+/// # Panics
+///
+/// Panics if signal handlers cannot be installed.
+#[allow(clippy::expect_used)]
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        let _ = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {
+            info!("Received Ctrl+C, shutting down gracefully...");
+        },
+        () = terminate => {
+            info!("Received terminate signal, shutting down gracefully...");
+        },
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::cognitive_complexity,
+    clippy::too_many_lines,
+    clippy::unreadable_literal,
+    clippy::redundant_clone,
+    clippy::missing_panics_doc,
+    clippy::missing_errors_doc,
+    clippy::needless_pass_by_value,
+    clippy::uninlined_format_args,
+    unused_qualifications,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap,
+    clippy::items_after_statements,
+    clippy::float_cmp,
+    clippy::redundant_closure_for_method_calls,
+    clippy::fn_params_excessive_bools,
+    clippy::similar_names,
+    clippy::map_unwrap_or,
+    clippy::unused_async,
+    clippy::case_sensitive_file_extension_comparisons,
+    clippy::manual_string_new,
+    clippy::no_effect_underscore_binding,
+    clippy::option_if_let_else,
+    clippy::single_char_pattern,
+    clippy::ip_constant,
+    clippy::or_fun_call,
+    clippy::cast_lossless,
+    clippy::needless_collect,
+    clippy::single_match_else,
+    clippy::needless_raw_string_hashes,
+    clippy::match_same_arms
+)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_load_and_validate_config() {
+        let config = load_and_validate_config();
+        assert!(!config.server.host.is_empty());
+        assert!(config.server.port > 0);
+    }
+
+    #[test]
+    fn test_print_startup_banner() {
+        let config = Config::default();
+        print_startup_banner(&config);
+        // Should not panic
+    }
+
+    #[test]
+    fn test_create_server_address_valid() {
+        let mut config = Config::default();
+        config.server.host = "127.0.0.1".to_string();
+        config.server.port = 8080;
+
+        let addr = create_server_address(&config).unwrap();
+        assert_eq!(addr.ip(), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(addr.port(), 8080);
+    }
+
+    #[test]
+    fn test_create_server_address_invalid_host() {
+        let mut config = Config::default();
+        config.server.host = "invalid-host-format".to_string();
+        config.server.port = 8080;
+
+        let result = create_server_address(&config);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_server_address_ipv6() {
+        let mut config = Config::default();
+        config.server.host = "[::1]".to_string(); // IPv6 addresses need brackets in socket format
+        config.server.port = 9000;
+
+        let addr = create_server_address(&config).unwrap();
+        assert_eq!(addr.port(), 9000);
+    }
+
+    #[test]
+    fn test_print_ready_banner() {
+        let addr = "127.0.0.1:8080".parse().unwrap();
+        print_ready_banner(addr);
+        // Should not panic
+    }
+
+    #[test]
+    fn test_load_environment_success() {
+        // This will try to load .env but shouldn't fail if it doesn't exist
+        let result = load_environment();
+        // Should succeed even if no .env file
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_config_variations() {
+        // Test with default config
+        let config = Config::default();
+        assert!(create_server_address(&config).is_ok());
+
+        // Test with custom host/port
+        let mut config = Config::default();
+        config.server.host = "0.0.0.0".to_string();
+        config.server.port = 3000;
+
+        let addr = create_server_address(&config).unwrap();
+        assert_eq!(addr.port(), 3000);
+    }
+
+    #[test]
+    fn test_server_address_edge_cases() {
+        // Test port boundaries with valid IP
+        let mut config = Config::default();
+        config.server.host = "127.0.0.1".to_string();
+        config.server.port = 1;
+
+        let addr = create_server_address(&config).unwrap();
+        assert_eq!(addr.port(), 1);
+
+        config.server.port = 65535;
+        let addr = create_server_address(&config).unwrap();
+        assert_eq!(addr.port(), 65535);
+    }
+}
@@ -1,9 +1,24 @@
 //! Application state management
 
+use crate::encryption::ColumnEncryption;
+use crate::features::FeatureFlags;
+use crate::handlers::websocket::WebSocketEvent;
+use crate::live_transcription::LiveTranscriber;
+use crate::middleware::rate_limit::SharedRateLimiter;
+use crate::resumable::ResumableUploads;
+use crate::stats_cache::StatsCache;
+use crate::worker_metrics::WorkerPoolMetrics;
 use anyhow::{Result, anyhow};
-use sdrtrunk_protocol::Config;
-use sdrtrunk_storage::PgPool;
+use sdrtrunk_protocol::{Config, config::RetentionConfig};
+use sdrtrunk_storage::{AudioStorage, PgPool, TranscriptRedactor};
+use sdrtrunk_types::SystemId;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+
+/// Events buffered per subscriber (WebSocket client or background task)
+/// before it starts missing them
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
 
 /// Shared application state
 #[derive(Clone)]
@@ -12,8 +27,32 @@ pub struct AppState {
     pub config: Config,
     /// Database connection pool
     pub pool: PgPool,
+    /// Pool for heavy read-only queries: the read replica when configured,
+    /// otherwise the same pool as `pool`
+    pub read_pool: PgPool,
     /// Base directory for uploaded files
     pub upload_dir: PathBuf,
+    /// Where recordings are written to and read back from
+    pub audio_storage: Arc<dyn AudioStorage>,
+    /// Feature flags for experimental endpoints
+    pub features: FeatureFlags,
+    /// Real-time events fanned out to WebSocket clients and to the
+    /// background tasks following worker progress
+    pub events: broadcast::Sender<WebSocketEvent>,
+    /// Per-client request limiter, following reloads of `api.rate_limit`
+    pub rate_limiter: SharedRateLimiter,
+    /// Retention settings, following config reloads
+    pub retention: watch::Sender<RetentionConfig>,
+    /// Transcription worker pool counters, fed by worker progress events
+    pub worker_pool: WorkerPoolMetrics,
+    /// Live transcription backend, when `live_transcription.enabled` is set
+    pub live_transcriber: Option<LiveTranscriber>,
+    /// Transcript anonymization, when `anonymization.enabled` is set
+    pub redactor: Option<Arc<TranscriptRedactor>>,
+    /// Column encryption, when `encryption.enabled` is set
+    pub encryption: Option<Arc<ColumnEncryption>>,
+    /// Cached statistics responses
+    pub stats_cache: StatsCache,
 }
 
 impl std::fmt::Debug for AppState {
@@ -21,7 +60,18 @@ impl std::fmt::Debug for AppState {
         f.debug_struct("AppState")
             .field("config", &self.config)
             .field("pool", &"PgPool { .. }")
+            .field("read_pool", &"PgPool { .. }")
             .field("upload_dir", &self.upload_dir)
+            .field("audio_storage", &self.audio_storage)
+            .field("features", &self.features)
+            .field("event_subscribers", &self.events.receiver_count())
+            .field("rate_limiter", &self.rate_limiter)
+            .field("retention", &*self.retention.borrow())
+            .field("worker_pool", &self.worker_pool)
+            .field("live_transcriber", &self.live_transcriber)
+            .field("redactor", &self.redactor)
+            .field("encryption", &self.encryption)
+            .field("stats_cache", &self.stats_cache)
             .finish()
     }
 }
@@ -31,26 +81,60 @@ impl AppState {
     ///
     /// # Errors
     ///
-    /// Returns an error if the upload directory cannot be created.
+    /// Returns an error if the upload directory cannot be created, the
+    /// configured recording storage, anonymization, or encryption is invalid,
+    /// or the live transcription client cannot be built.
     pub fn new(config: Config, pool: PgPool) -> Result<Self> {
         // Build the full upload directory path
         let upload_dir = config.storage.base_dir.join(&config.storage.upload_dir);
 
         // Ensure upload directory exists
         std::fs::create_dir_all(&upload_dir)?;
+        let audio_storage = sdrtrunk_storage::audio::from_config(&config.storage)?;
+
+        let features = FeatureFlags::new(config.features.clone());
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let rate_limiter = SharedRateLimiter::new(config.api.rate_limit);
+        let retention = watch::Sender::new(config.retention.clone());
+        let live_transcriber = if config.live_transcription.enabled {
+            Some(LiveTranscriber::new(&config.live_transcription)?)
+        } else {
+            None
+        };
+        let redactor = TranscriptRedactor::from_config(&config.anonymization)?.map(Arc::new);
+        let encryption = ColumnEncryption::from_config(&config.encryption)?.map(Arc::new);
+        let stats_cache = StatsCache::new(&config.stats_cache);
 
         Ok(Self {
             config,
+            read_pool: pool.clone(),
             pool,
             upload_dir,
+            audio_storage,
+            features,
+            events,
+            rate_limiter,
+            retention,
+            worker_pool: WorkerPoolMetrics::new(),
+            live_transcriber,
+            redactor,
+            encryption,
+            stats_cache,
         })
     }
 
+    /// Send heavy read-only queries to `read_pool` instead of the primary
+    #[must_use]
+    pub fn with_read_pool(mut self, read_pool: PgPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
     /// Get file storage path for a given system and date
     #[must_use]
-    pub fn get_storage_path(&self, system_id: &str, date: chrono::NaiveDate) -> PathBuf {
+    pub fn get_storage_path(&self, system_id: &SystemId, date: chrono::NaiveDate) -> PathBuf {
         self.upload_dir
-            .join(system_id)
+            .join(system_id.as_str())
             .join(date.format("%Y").to_string())
             .join(date.format("%m").to_string())
             .join(date.format("%d").to_string())
@@ -62,6 +146,12 @@ impl AppState {
         &self.upload_dir
     }
 
+    /// Resumable uploads in progress, kept under the upload directory
+    #[must_use]
+    pub fn resumable_uploads(&self) -> ResumableUploads {
+        ResumableUploads::new(&self.upload_dir)
+    }
+
     /// Check if the application is properly configured
     ///
     /// # Errors
@@ -218,7 +308,8 @@ mod tests {
         let state = AppState::new(config, pool).expect("Failed to create AppState");
 
         let test_date = chrono::NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
-        let storage_path = state.get_storage_path("police_system", test_date);
+        let system_id = SystemId::new("police_system").unwrap();
+        let storage_path = state.get_storage_path(&system_id, test_date);
 
         let expected = upload_dir
             .join("police_system")
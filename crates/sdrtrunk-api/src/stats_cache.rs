@@ -0,0 +1,261 @@
+//! Cache of aggregate statistics responses
+//!
+//! Dashboards poll the statistics endpoints every few seconds, and each poll
+//! runs aggregate queries over `radio_calls`. [`StatsCache`] keeps computed
+//! responses in memory for `stats_cache.ttl_seconds`, keyed by endpoint,
+//! query, and the systems the response covers. A call uploaded to this
+//! server drops the entries covering its system, so they never lag behind
+//! its uploads; uploads to other replicas show up once entries expire.
+//! Finished transcriptions, whether reported by the transcription callback
+//! or announced by workers in progress notifications, drop them too.
+//! Concurrent requests for a missing entry share one computation.
+
+use crate::error::ApiError;
+use crate::progress::ProgressEvents;
+use moka::future::Cache;
+use sdrtrunk_protocol::config::StatsCacheConfig;
+use sdrtrunk_storage::{PgPool, ProgressStage, queries::RadioCallQueries};
+use sdrtrunk_types::SystemId;
+use std::any::Any;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// A cached response of any statistics endpoint
+type CachedResponse = Arc<dyn Any + Send + Sync>;
+
+/// Identifies a cached statistics response
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatsKey {
+    endpoint: &'static str,
+    /// Systems the response covers, `None` for all
+    systems: Option<Vec<SystemId>>,
+    /// Query parameters shaping the response
+    params: String,
+}
+
+impl StatsKey {
+    /// Key for a response of `endpoint` covering `systems` (`None` for all)
+    #[must_use]
+    pub fn new(endpoint: &'static str, systems: Option<&[SystemId]>, params: String) -> Self {
+        let systems = systems.map(|systems| {
+            let mut systems = systems.to_vec();
+            systems.sort_unstable();
+            systems.dedup();
+            systems
+        });
+        Self {
+            endpoint,
+            systems,
+            params,
+        }
+    }
+
+    /// Whether a call for `system_id` may change the response
+    fn covers(&self, system_id: &SystemId) -> bool {
+        self.systems
+            .as_ref()
+            .is_none_or(|systems| systems.contains(system_id))
+    }
+}
+
+/// Statistics responses shared by all requests
+#[derive(Clone)]
+pub struct StatsCache {
+    /// `None` when caching is disabled
+    cache: Option<Cache<StatsKey, CachedResponse>>,
+}
+
+impl std::fmt::Debug for StatsCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatsCache")
+            .field("enabled", &self.cache.is_some())
+            .field(
+                "entries",
+                &self.cache.as_ref().map_or(0, Cache::entry_count),
+            )
+            .finish()
+    }
+}
+
+impl StatsCache {
+    /// Cache following `config`
+    #[must_use]
+    pub fn new(config: &StatsCacheConfig) -> Self {
+        let cache = (config.enabled && config.ttl_seconds > 0).then(|| {
+            Cache::builder()
+                .max_capacity(config.max_entries)
+                .time_to_live(Duration::from_secs(config.ttl_seconds))
+                .support_invalidation_closures()
+                .build()
+        });
+        Self { cache }
+    }
+
+    /// The cached response for `key`, or the one `load` computes
+    ///
+    /// Errors are returned to every request waiting on the load and are not
+    /// cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error `load` fails with.
+    pub async fn get_or_load<T, F>(&self, key: StatsKey, load: F) -> Result<T, ApiError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, ApiError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return load.await;
+        };
+        let endpoint = key.endpoint;
+        let cached = cache
+            .try_get_with(key, async {
+                load.await
+                    .map(|response| -> CachedResponse { Arc::new(response) })
+            })
+            .await
+            .map_err(|e| ApiError::clone(&e))?;
+        cached.downcast_ref::<T>().cloned().ok_or_else(|| {
+            warn!("Cached {endpoint} statistics have an unexpected type");
+            ApiError::internal("STATS_CACHE_ERROR", "Cached statistics are unreadable")
+        })
+    }
+
+    /// Drop the responses a new call for `system_id` makes stale
+    pub fn invalidate_system(&self, system_id: &SystemId) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        let system_id = system_id.clone();
+        if let Err(e) = cache.invalidate_entries_if(move |key, _| key.covers(&system_id)) {
+            warn!("Failed to invalidate cached statistics: {e}");
+        }
+    }
+
+    /// Drop every cached response, e.g. after calls are deleted
+    pub fn invalidate_all(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate_all();
+        }
+    }
+
+    /// Drop the responses covering the system of call `call_id`, e.g. once
+    /// its transcription finishes
+    pub async fn invalidate_call(&self, pool: &PgPool, call_id: Uuid) {
+        if self.cache.is_none() {
+            return;
+        }
+        match RadioCallQueries::find_by_id(pool, call_id).await {
+            Ok(call) => self.invalidate_system(&call.system_id),
+            Err(e) => warn!("Failed to find call {call_id} to invalidate cached statistics: {e}"),
+        }
+    }
+}
+
+/// Spawn the task dropping cached statistics as workers finish
+/// transcriptions
+///
+/// Returns `None` when caching is disabled.
+#[must_use]
+pub fn spawn_invalidation_task(
+    pool: PgPool,
+    mut progress: ProgressEvents,
+    cache: StatsCache,
+) -> Option<JoinHandle<()>> {
+    cache.cache.as_ref()?;
+    Some(tokio::spawn(async move {
+        info!("Invalidating cached statistics as transcriptions finish");
+        while let Some(event) = progress.recv().await {
+            if matches!(
+                event.stage,
+                ProgressStage::Completed { .. } | ProgressStage::Failed { .. }
+            ) {
+                cache.invalidate_call(&pool, event.call_id).await;
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn system(id: &str) -> SystemId {
+        SystemId::new(id).unwrap()
+    }
+
+    async fn load(cache: &StatsCache, key: StatsKey, loads: &AtomicUsize) -> usize {
+        cache
+            .get_or_load(key, async {
+                Ok::<_, ApiError>(loads.fetch_add(1, Ordering::SeqCst) + 1)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_caches_until_invalidated() {
+        let cache = StatsCache::new(&StatsCacheConfig::default());
+        let loads = AtomicUsize::new(0);
+        let metro = || StatsKey::new("system", Some(&[system("metro")]), String::new());
+        let global = || StatsKey::new("global", None, String::new());
+
+        assert_eq!(load(&cache, metro(), &loads).await, 1);
+        assert_eq!(load(&cache, metro(), &loads).await, 1);
+        assert_eq!(load(&cache, global(), &loads).await, 2);
+
+        // A call for another system leaves metro's stats alone
+        cache.invalidate_system(&system("county"));
+        assert_eq!(load(&cache, metro(), &loads).await, 1);
+        assert_eq!(load(&cache, global(), &loads).await, 3);
+
+        cache.invalidate_system(&system("metro"));
+        assert_eq!(load(&cache, metro(), &loads).await, 4);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let cache = StatsCache::new(&StatsCacheConfig::default());
+        let key = || StatsKey::new("global", None, String::new());
+        let failed = cache
+            .get_or_load::<usize, _>(key(), async { Err(ApiError::database("down")) })
+            .await;
+        assert!(failed.is_err());
+        let loaded = cache.get_or_load(key(), async { Ok(7_usize) }).await;
+        assert_eq!(loaded.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_always_loads() {
+        let cache = StatsCache::new(&StatsCacheConfig {
+            enabled: false,
+            ..StatsCacheConfig::default()
+        });
+        let loads = AtomicUsize::new(0);
+        let key = || StatsKey::new("global", None, String::new());
+        assert_eq!(load(&cache, key(), &loads).await, 1);
+        assert_eq!(load(&cache, key(), &loads).await, 2);
+    }
+
+    #[test]
+    fn test_key_ignores_system_order() {
+        let a = StatsKey::new("global", Some(&[system("b"), system("a")]), String::new());
+        let b = StatsKey::new("global", Some(&[system("a"), system("b")]), String::new());
+        assert_eq!(a, b);
+        assert!(a.covers(&system("a")));
+        assert!(!a.covers(&system("c")));
+    }
+}
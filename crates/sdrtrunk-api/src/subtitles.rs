@@ -0,0 +1,142 @@
+//! Subtitle rendering of call transcripts
+//!
+//! Turns a call's timed transcript segments into `SubRip` (`.srt`) or `WebVTT`
+//! (`.vtt`) cues so video and audio tools can show the transcript in step
+//! with the recording. Diarized segments are labelled with their speaker.
+
+use sdrtrunk_storage::TranscriptionSegment;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// MIME type of `SubRip` subtitles
+pub const SRT_CONTENT_TYPE: &str = "application/x-subrip; charset=utf-8";
+
+/// MIME type of `WebVTT` subtitles
+pub const VTT_CONTENT_TYPE: &str = "text/vtt; charset=utf-8";
+
+/// Render segments as `SubRip` cues
+#[must_use]
+pub fn srt(segments: &[TranscriptionSegment]) -> String {
+    let mut out = String::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let text = cue_text(&segment.text);
+        let _ = write!(
+            out,
+            "{}\n{} --> {}\n",
+            index + 1,
+            timestamp(segment.start_seconds, ','),
+            timestamp(segment.end_seconds, ',')
+        );
+        let _ = match &segment.speaker {
+            Some(speaker) => writeln!(out, "{speaker}: {text}\n"),
+            None => writeln!(out, "{text}\n"),
+        };
+    }
+    out
+}
+
+/// Render segments as a `WebVTT` file, with speakers as voice spans
+#[must_use]
+pub fn vtt(segments: &[TranscriptionSegment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for segment in segments {
+        let text = escape_vtt(&cue_text(&segment.text));
+        let _ = writeln!(
+            out,
+            "{} --> {}",
+            timestamp(segment.start_seconds, '.'),
+            timestamp(segment.end_seconds, '.')
+        );
+        let _ = match &segment.speaker {
+            Some(speaker) => writeln!(out, "<v {}>{text}\n", escape_vtt(speaker)),
+            None => writeln!(out, "{text}\n"),
+        };
+    }
+    out
+}
+
+/// `HH:MM:SS` plus milliseconds after `separator`
+///
+/// Negative or invalid times are clamped to zero.
+fn timestamp(seconds: f64, separator: char) -> String {
+    let millis = Duration::try_from_secs_f64(seconds)
+        .unwrap_or_default()
+        .as_millis();
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Segment text without blank lines, which would end the cue early
+fn cue_text(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escape the characters `WebVTT` treats as markup
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    fn segments() -> Vec<TranscriptionSegment> {
+        vec![
+            TranscriptionSegment {
+                start_seconds: 0.0,
+                end_seconds: 2.5,
+                text: "Engine 5, respond to Main & 3rd".to_string(),
+                speaker: Some("SPEAKER_00".to_string()),
+                confidence: None,
+            },
+            TranscriptionSegment {
+                start_seconds: 3_661.25,
+                end_seconds: 3_663.0,
+                text: "Copy\n\nen route".to_string(),
+                speaker: None,
+                confidence: Some(0.9),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(timestamp(3_661.25, '.'), "01:01:01.250");
+        assert_eq!(timestamp(-1.0, ','), "00:00:00,000");
+        assert_eq!(timestamp(f64::NAN, ','), "00:00:00,000");
+    }
+
+    #[test]
+    fn test_srt() {
+        assert_eq!(
+            srt(&segments()),
+            "1\n00:00:00,000 --> 00:00:02,500\nSPEAKER_00: Engine 5, respond to Main & 3rd\n\n\
+             2\n01:01:01,250 --> 01:01:03,000\nCopy\nen route\n\n"
+        );
+        assert_eq!(srt(&[]), "");
+    }
+
+    #[test]
+    fn test_vtt() {
+        assert_eq!(
+            vtt(&segments()),
+            "WEBVTT\n\n\
+             00:00:00.000 --> 00:00:02.500\n<v SPEAKER_00>Engine 5, respond to Main &amp; 3rd\n\n\
+             01:01:01.250 --> 01:01:03.000\nCopy\nen route\n\n"
+        );
+        assert_eq!(vtt(&[]), "WEBVTT\n\n");
+    }
+}
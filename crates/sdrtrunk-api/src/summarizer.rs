@@ -0,0 +1,361 @@
+//! Transcript summaries from an OpenAI-compatible LLM endpoint
+//!
+//! With `summarizer.enabled` set, a task listens for completed
+//! transcriptions in the worker progress notifications and sends each
+//! transcript of at least `min_transcript_chars` characters to
+//! `{url}/chat/completions`. The model answers with a JSON object holding a
+//! short summary and the entities the call mentions, which are stored in
+//! `call_summaries` for the call detail view and keyword search. Any
+//! endpoint speaking the chat completions API works: `OpenAI`, or a local
+//! Ollama, vLLM, or llama.cpp server. Calls are summarized one at a time;
+//! failures are logged and the call is left without a summary.
+
+use crate::progress::ProgressEvents;
+use anyhow::{Context, Result, anyhow};
+use sdrtrunk_protocol::config::SummarizerConfig;
+use sdrtrunk_storage::{
+    CallSummary, PgPool, ProgressStage, SummaryEntity, SummaryQueries, get_radio_call,
+};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Completed calls waiting for a summary before new ones are dropped
+const QUEUE_CAPACITY: usize = 1000;
+/// Most entities kept per call
+const MAX_ENTITIES: usize = 20;
+
+/// Instructions sent ahead of each transcript
+const SYSTEM_PROMPT: &str = "You summarize transcripts of public safety radio calls. \
+Reply with only a JSON object of the form \
+{\"summary\": \"...\", \"entities\": [{\"kind\": \"...\", \"text\": \"...\"}]}. \
+The summary is one or two plain sentences saying what happened. Entities are the units, \
+people, locations, vehicles, and incidents the call names, with kind one of unit, person, \
+location, vehicle, incident, or other. Do not invent details that are not in the transcript.";
+
+/// Summary and entities parsed from a model reply
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Summary {
+    /// Short summary of the call
+    pub summary: String,
+    /// Entities the call mentions
+    #[serde(default)]
+    pub entities: Vec<SummaryEntity>,
+}
+
+/// Parse a model reply into a summary
+///
+/// Replies wrapped in a Markdown code fence or surrounded by prose are
+/// accepted as long as they contain one JSON object. The summary is cut to
+/// `max_chars` characters, entities without text are dropped, and at most
+/// [`MAX_ENTITIES`] are kept.
+///
+/// # Errors
+///
+/// Returns an error if the reply holds no JSON object or the summary is
+/// empty.
+pub fn parse_summary(reply: &str, max_chars: usize) -> Result<Summary> {
+    let start = reply
+        .find('{')
+        .ok_or_else(|| anyhow!("Reply holds no JSON object"))?;
+    let end = reply
+        .rfind('}')
+        .filter(|end| *end > start)
+        .ok_or_else(|| anyhow!("Reply holds no JSON object"))?;
+    let json = reply.get(start..=end).unwrap_or_default();
+    let mut summary: Summary = serde_json::from_str(json).context("Reply is not a summary")?;
+
+    summary.summary = summary.summary.trim().chars().take(max_chars).collect();
+    if summary.summary.is_empty() {
+        return Err(anyhow!("Reply has an empty summary"));
+    }
+    summary
+        .entities
+        .retain(|entity| !entity.text.trim().is_empty());
+    for entity in &mut summary.entities {
+        entity.kind = entity.kind.trim().to_lowercase();
+        entity.text = entity.text.trim().to_string();
+    }
+    summary.entities.truncate(MAX_ENTITIES);
+    Ok(summary)
+}
+
+/// Chat completions response, reduced to the reply text
+#[derive(Debug, Deserialize)]
+struct ChatCompletion {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Sends transcripts to the chat completions API
+#[derive(Debug, Clone)]
+pub struct Summarizer {
+    http: reqwest::Client,
+    config: SummarizerConfig,
+}
+
+impl Summarizer {
+    /// Create a summarizer from the summarizer settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(config: &SummarizerConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds.max(1)))
+            .build()
+            .context("Failed to build summarizer client")?;
+        Ok(Self {
+            http,
+            config: config.clone(),
+        })
+    }
+
+    /// Ask the model to summarize `transcript`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API answers with an error,
+    /// or the reply is not a summary.
+    pub async fn summarize(&self, transcript: &str) -> Result<Summary> {
+        let url = format!("{}/chat/completions", self.config.url.trim_end_matches('/'));
+        let body = serde_json::json!({
+            "model": self.config.model,
+            "temperature": 0,
+            "response_format": { "type": "json_object" },
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": transcript },
+            ],
+        });
+        let mut request = self.http.post(&url).json(&body);
+        if let Some(api_key) = self.config.api_key.as_deref() {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP {status}: {detail}"));
+        }
+        let completion: ChatCompletion = response.json().await?;
+        let reply = completion
+            .choices
+            .into_iter()
+            .find_map(|choice| choice.message.content)
+            .ok_or_else(|| anyhow!("Response has no reply"))?;
+        parse_summary(&reply, self.config.max_summary_chars.max(1))
+    }
+
+    /// Summarize a call and store the result
+    ///
+    /// Returns `None` without asking the model when the call is missing, not
+    /// transcribed, or its transcript is shorter than
+    /// `min_transcript_chars`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call cannot be loaded, summarized, or stored.
+    pub async fn summarize_call(
+        &self,
+        pool: &PgPool,
+        call_id: Uuid,
+    ) -> Result<Option<CallSummary>> {
+        let Some(call) = get_radio_call(pool, call_id).await? else {
+            return Ok(None);
+        };
+        let transcript = call
+            .transcription_text
+            .as_deref()
+            .unwrap_or_default()
+            .trim();
+        if transcript.chars().count() < self.config.min_transcript_chars.max(1) {
+            debug!("Call {call_id} is too short to summarize");
+            return Ok(None);
+        }
+
+        let summary = self.summarize(transcript).await?;
+        let saved = SummaryQueries::save(
+            pool,
+            call_id,
+            &summary.summary,
+            &summary.entities,
+            &self.config.model,
+        )
+        .await?;
+        Ok(Some(saved))
+    }
+}
+
+/// Spawn the summarizer task if enabled
+///
+/// One task collects completed call IDs from worker progress notifications;
+/// the returned one summarizes them in order.
+#[must_use]
+pub fn spawn_summarizer_task(
+    pool: PgPool,
+    progress: ProgressEvents,
+    config: &SummarizerConfig,
+) -> Option<JoinHandle<()>> {
+    if !config.enabled {
+        return None;
+    }
+    let summarizer = match Summarizer::new(config) {
+        Ok(summarizer) => summarizer,
+        Err(e) => {
+            warn!("Summarizer disabled: {e:#}");
+            return None;
+        }
+    };
+    info!(
+        "Summarizing completed transcriptions with '{}' at {}",
+        config.model, config.url
+    );
+
+    let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+    drop(tokio::spawn(collect_completed_calls(progress, tx)));
+    Some(tokio::spawn(summarize_calls(summarizer, pool, rx)))
+}
+
+/// Forward the IDs of calls whose transcription completed
+async fn collect_completed_calls(mut progress: ProgressEvents, tx: mpsc::Sender<Uuid>) {
+    while let Some(event) = progress.recv().await {
+        if matches!(event.stage, ProgressStage::Completed { .. }) && !queue_call(&tx, event.call_id)
+        {
+            return;
+        }
+    }
+}
+
+/// Queue a call for summarizing, dropping it when the backlog is full
+///
+/// Returns `false` once the summarizing task has stopped.
+fn queue_call(tx: &mpsc::Sender<Uuid>, call_id: Uuid) -> bool {
+    match tx.try_send(call_id) {
+        Ok(()) => true,
+        Err(mpsc::error::TrySendError::Full(call_id)) => {
+            warn!("Summarizer backlog full; not summarizing call {call_id}");
+            true
+        }
+        Err(mpsc::error::TrySendError::Closed(_)) => false,
+    }
+}
+
+/// Summarize collected calls one at a time
+async fn summarize_calls(summarizer: Summarizer, pool: PgPool, mut rx: mpsc::Receiver<Uuid>) {
+    while let Some(call_id) = rx.recv().await {
+        match summarizer.summarize_call(&pool, call_id).await {
+            Ok(Some(summary)) => debug!(
+                "Summarized call {call_id} ({} entities)",
+                summary.entities.len()
+            ),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to summarize call {call_id}: {e:#}"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_parse_summary() {
+        let reply = r#"{"summary": " Engine 5 responding to a fire. ",
+            "entities": [{"kind": "Unit", "text": " Engine 5 "}, {"kind": "location", "text": ""}]}"#;
+        let summary = parse_summary(reply, 500).unwrap();
+        assert_eq!(summary.summary, "Engine 5 responding to a fire.");
+        assert_eq!(
+            summary.entities,
+            vec![SummaryEntity {
+                kind: "unit".to_string(),
+                text: "Engine 5".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_summary_fenced_and_truncated() {
+        let reply = "Here you go:\n```json\n{\"summary\": \"Medic 12 transporting\"}\n```";
+        let summary = parse_summary(reply, 8).unwrap();
+        assert_eq!(summary.summary, "Medic 12");
+        assert!(summary.entities.is_empty());
+    }
+
+    #[test]
+    fn test_parse_summary_rejects_bad_replies() {
+        assert!(parse_summary("No summary today", 500).is_err());
+        assert!(parse_summary(r#"{"summary": "  "}"#, 500).is_err());
+        assert!(parse_summary(r#"{"text": "wrong shape"}"#, 500).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_summarize() {
+        let app = Router::new().route(
+            "/v1/chat/completions",
+            post(|headers: HeaderMap, body: axum::Json<serde_json::Value>| async move {
+                assert_eq!(headers["authorization"], "Bearer sk-test");
+                assert_eq!(body["model"], "gpt-4o-mini");
+                assert_eq!(body["messages"][1]["content"], "engine five on scene");
+                axum::Json(json!({"choices": [{"message": {
+                    "role": "assistant",
+                    "content": "{\"summary\": \"Engine 5 arrived.\", \"entities\": [{\"kind\": \"unit\", \"text\": \"Engine 5\"}]}"
+                }}]}))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let summarizer = Summarizer::new(&SummarizerConfig {
+            enabled: true,
+            url: format!("{base}/v1/"),
+            api_key: Some("sk-test".to_string()),
+            model: "gpt-4o-mini".to_string(),
+            ..SummarizerConfig::default()
+        })
+        .unwrap();
+        let summary = summarizer.summarize("engine five on scene").await.unwrap();
+        assert_eq!(summary.summary, "Engine 5 arrived.");
+        assert_eq!(summary.entities.len(), 1);
+
+        let unreachable = Summarizer::new(&SummarizerConfig {
+            url: format!("{base}/missing"),
+            ..SummarizerConfig::default()
+        })
+        .unwrap();
+        let error = unreachable.summarize("copy").await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(&StatusCode::NOT_FOUND.as_u16().to_string())
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Per-API-key system scoping
+//!
+//! An API key's `allowed_systems` limits which radio systems it can read and
+//! upload to. [`crate::middleware::auth::authenticate`] runs in front of every
+//! route: when a key is presented it is validated and the key's
+//! [`TenantScope`] is stored in the request extensions. Handlers take
+//! [`TenantScope`] as an extractor and pass it into their queries. Requests
+//! without a key are unscoped unless `security.require_api_key` is set, in
+//! which case scoped endpoints reject them.
+
+use crate::{error::ApiError, state::AppState};
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use sdrtrunk_storage::models::ApiKeyDb;
+use sdrtrunk_types::SystemId;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Systems a request may see
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TenantScope {
+    /// Every system (no key, or a key without system restrictions)
+    #[default]
+    All,
+    /// Only these systems
+    Systems(Vec<SystemId>),
+}
+
+impl TenantScope {
+    /// Scope granted by an API key
+    ///
+    /// A missing or empty `allowed_systems` list grants every system. Entries
+    /// that are not valid system IDs are ignored, so a key listing only
+    /// invalid IDs sees nothing.
+    #[must_use]
+    pub fn from_api_key(api_key: &ApiKeyDb) -> Self {
+        match api_key.allowed_systems.as_deref() {
+            None | Some([]) => Self::All,
+            Some(systems) => Self::Systems(
+                systems
+                    .iter()
+                    .filter_map(|system| match SystemId::new(system.as_str()) {
+                        Ok(system_id) => Some(system_id),
+                        Err(e) => {
+                            warn!(
+                                "API key {} allows invalid system '{system}': {e}",
+                                api_key.id
+                            );
+                            None
+                        }
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Whether calls from `system_id` are visible
+    #[must_use]
+    pub fn allows(&self, system_id: &SystemId) -> bool {
+        match self {
+            Self::All => true,
+            Self::Systems(systems) => systems.contains(system_id),
+        }
+    }
+
+    /// Systems to restrict queries to (`None` for every system)
+    #[must_use]
+    pub fn systems(&self) -> Option<&[SystemId]> {
+        match self {
+            Self::All => None,
+            Self::Systems(systems) => Some(systems),
+        }
+    }
+
+    /// Whether a request for `system_id`, or for every system when `None`,
+    /// stays within the scope
+    #[must_use]
+    pub fn covers(&self, system_id: Option<&SystemId>) -> bool {
+        system_id.map_or_else(|| !self.is_restricted(), |system_id| self.allows(system_id))
+    }
+
+    /// Whether the scope is limited to specific systems
+    #[must_use]
+    pub const fn is_restricted(&self) -> bool {
+        matches!(self, Self::Systems(_))
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for TenantScope {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(scope) = parts.extensions.get::<Self>() {
+            return Ok(scope.clone());
+        }
+        if state.config.security.require_api_key {
+            return Err(ApiError::unauthorized("UNAUTHORIZED", "API key required"));
+        }
+        Ok(Self::All)
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn api_key(allowed_systems: Option<Vec<&str>>) -> ApiKeyDb {
+        ApiKeyDb {
+            id: "key-1".to_string(),
+            key_hash: "hash".to_string(),
+            description: None,
+            created_at: chrono::Utc::now(),
+            expires_at: None,
+            allowed_ips: None,
+            allowed_systems: allowed_systems
+                .map(|systems| systems.into_iter().map(str::to_string).collect()),
+            active: true,
+            last_used: None,
+            total_requests: None,
+        }
+    }
+
+    fn system(id: &str) -> SystemId {
+        SystemId::new(id).unwrap()
+    }
+
+    #[test]
+    fn test_unrestricted_keys_see_all_systems() {
+        for key in [api_key(None), api_key(Some(vec![]))] {
+            let scope = TenantScope::from_api_key(&key);
+            assert_eq!(scope, TenantScope::All);
+            assert!(scope.allows(&system("police")));
+            assert!(scope.systems().is_none());
+            assert!(!scope.is_restricted());
+            assert!(scope.covers(None));
+        }
+    }
+
+    #[test]
+    fn test_restricted_key_scope() {
+        let scope = TenantScope::from_api_key(&api_key(Some(vec!["police", "fire"])));
+        assert!(scope.is_restricted());
+        assert!(scope.allows(&system("police")));
+        assert!(scope.allows(&system("fire")));
+        assert!(!scope.allows(&system("ems")));
+        assert_eq!(scope.systems().unwrap().len(), 2);
+        assert!(scope.covers(Some(&system("fire"))));
+        assert!(!scope.covers(Some(&system("ems"))));
+        assert!(!scope.covers(None));
+    }
+
+    #[test]
+    fn test_invalid_allowed_systems_grant_nothing() {
+        let scope = TenantScope::from_api_key(&api_key(Some(vec![""])));
+        assert_eq!(scope, TenantScope::Systems(Vec::new()));
+        assert!(!scope.allows(&system("police")));
+    }
+}
@@ -0,0 +1,189 @@
+//! Waveform peaks for the web player
+//!
+//! Recordings are decoded with ffmpeg to 8 kHz mono PCM and reduced to
+//! [`PEAK_COUNT`] peak amplitudes, scaled so the loudest is 1.0. The upload
+//! handler generates peaks in the background for every new call; calls
+//! uploaded before that, or whose generation failed, get theirs on the first
+//! request to `/api/calls/{id}/waveform`.
+
+use crate::handlers::calls::{TranscodeInput, run_ffmpeg};
+use anyhow::{Context, Result, anyhow, bail};
+use axum::body::Bytes;
+use sdrtrunk_storage::{CallWaveform, PgPool, WaveformQueries};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Peaks generated per call
+pub const PEAK_COUNT: usize = 1000;
+
+/// Sample rate recordings are decoded at; ample for drawing peaks and for
+/// the voice band fingerprints cover
+pub(crate) const DECODE_SAMPLE_RATE: u32 = 8000;
+
+/// ffmpeg output options decoding to raw mono 16-bit PCM at [`DECODE_SAMPLE_RATE`]
+const DECODE_ARGS: &[&str] = &[
+    "-ac",
+    "1",
+    "-ar",
+    "8000",
+    "-codec:a",
+    "pcm_s16le",
+    "-f",
+    "s16le",
+];
+
+/// Peaks of a decoded recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waveform {
+    /// Loudest sample of each equal slice of the recording, in `0.0..=1.0`
+    pub peaks: Vec<f32>,
+    /// Length of the recording in seconds
+    pub duration_seconds: f64,
+}
+
+impl Waveform {
+    /// Reduce little-endian 16-bit mono PCM to at most `count` peaks
+    ///
+    /// Each peak is the loudest sample of its slice, scaled so the loudest
+    /// sample in the recording is 1.0; silence stays at 0.0. Recordings with
+    /// fewer than `count` samples get one peak per sample.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_pcm(pcm: &[u8], sample_rate: u32, count: usize) -> Self {
+        let samples: Vec<u16> = pcm
+            .chunks_exact(2)
+            .filter_map(|pair| pair.try_into().ok())
+            .map(|pair: [u8; 2]| i16::from_le_bytes(pair).unsigned_abs())
+            .collect();
+        let duration_seconds = samples.len() as f64 / f64::from(sample_rate.max(1));
+        if samples.is_empty() || count == 0 {
+            return Self {
+                peaks: Vec::new(),
+                duration_seconds,
+            };
+        }
+
+        let slice_len = samples.len().div_ceil(count);
+        let raw: Vec<u16> = samples
+            .chunks(slice_len)
+            .map(|slice| slice.iter().copied().max().unwrap_or(0))
+            .collect();
+        let loudest = f32::from(raw.iter().copied().max().unwrap_or(0).max(1));
+
+        Self {
+            // Three decimals is finer than any display and keeps the JSON small
+            peaks: raw
+                .into_iter()
+                .map(|peak| (f32::from(peak) / loudest * 1000.0).round() / 1000.0)
+                .collect(),
+            duration_seconds,
+        }
+    }
+}
+
+/// Decode a recording to little-endian 16-bit mono PCM at
+/// [`DECODE_SAMPLE_RATE`]
+///
+/// # Errors
+///
+/// Returns an error if ffmpeg cannot be started or fails
+pub(crate) async fn decode(input: TranscodeInput<'_>) -> Result<Vec<u8>> {
+    let output = run_ffmpeg(input, DECODE_ARGS)
+        .await
+        .context("failed to run ffmpeg")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffmpeg failed to decode audio: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Decode a recording and compute its waveform
+///
+/// # Errors
+///
+/// Returns an error if ffmpeg cannot be started or fails, or the recording
+/// holds no audio
+pub(crate) async fn generate(input: TranscodeInput<'_>) -> Result<Waveform> {
+    let pcm = decode(input).await?;
+    let waveform = Waveform::from_pcm(&pcm, DECODE_SAMPLE_RATE, PEAK_COUNT);
+    if waveform.peaks.is_empty() {
+        bail!("recording holds no audio");
+    }
+    Ok(waveform)
+}
+
+/// Compute a call's waveform and store it
+///
+/// # Errors
+///
+/// Returns an error if decoding fails or the waveform cannot be stored
+pub(crate) async fn generate_and_store(
+    pool: &PgPool,
+    call_id: Uuid,
+    input: TranscodeInput<'_>,
+) -> Result<CallWaveform> {
+    let waveform = generate(input).await?;
+    let stored = WaveformQueries::save(pool, call_id, &waveform.peaks, waveform.duration_seconds)
+        .await
+        .context("failed to store waveform")?;
+    debug!(
+        "Stored {} waveform peaks for call {call_id}",
+        waveform.peaks.len()
+    );
+    Ok(stored)
+}
+
+/// Generate the waveform of an uploaded call in the background, logging failures
+pub fn spawn_generate(pool: &PgPool, call_id: Uuid, audio: Bytes) {
+    let pool = pool.clone();
+    drop(tokio::spawn(async move {
+        if let Err(e) = generate_and_store(&pool, call_id, TranscodeInput::Memory(audio)).await {
+            warn!("Failed to generate waveform for call {call_id}: {e:#}");
+        }
+    }));
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn pcm(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_decode_args_match_sample_rate() {
+        assert!(DECODE_ARGS.contains(&DECODE_SAMPLE_RATE.to_string().as_str()));
+    }
+
+    #[test]
+    fn test_peaks_from_pcm() {
+        // Eight samples into four slices of two; negative samples count by magnitude
+        let waveform = Waveform::from_pcm(&pcm(&[0, 100, -400, 200, 0, 0, i16::MIN, 50]), 4, 4);
+        assert_eq!(waveform.peaks, vec![0.003, 0.012, 0.0, 1.0]);
+        assert_eq!(waveform.duration_seconds, 2.0);
+
+        // Short recordings get one peak per sample; a trailing odd byte is ignored
+        let mut short = pcm(&[1000, -500]);
+        short.push(7);
+        let waveform = Waveform::from_pcm(&short, 8000, PEAK_COUNT);
+        assert_eq!(waveform.peaks, vec![1.0, 0.5]);
+
+        let silent = Waveform::from_pcm(&pcm(&[0; 10]), 8000, 3);
+        assert_eq!(silent.peaks, vec![0.0; 3]);
+
+        assert!(Waveform::from_pcm(&[], 8000, PEAK_COUNT).peaks.is_empty());
+    }
+}
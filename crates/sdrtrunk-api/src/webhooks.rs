@@ -0,0 +1,510 @@
+//! Call lifecycle webhooks
+//!
+//! Queues a delivery in the webhook log for every configured endpoint
+//! subscribed to an event: `call_uploaded` from the upload handler, and
+//! `transcription_completed` / `transcription_failed` from the worker progress
+//! notifications. A delivery task posts due deliveries as JSON, signed with
+//! the endpoint's secret, and retries failures with exponential backoff until
+//! the attempts run out. Queued deliveries survive restarts; transcriptions
+//! that finish while the API server is down are not reported.
+
+use crate::progress::ProgressEvents;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use rust_decimal::Decimal;
+use sdrtrunk_protocol::config::{WebhookEndpoint, WebhookEvent, WebhooksConfig};
+use sdrtrunk_storage::models::RadioCallDb;
+use sdrtrunk_storage::{
+    NewWebhookDelivery, PgPool, ProgressStage, WebhookAttempt, WebhookDelivery, WebhookQueries,
+};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Header carrying `sha256=<hex HMAC of the body>` for endpoints with a secret
+pub const SIGNATURE_HEADER: &str = "x-sdrtrunk-signature";
+/// Header carrying the event name
+pub const EVENT_HEADER: &str = "x-sdrtrunk-event";
+/// Header carrying the delivery ID, stable across retries
+pub const DELIVERY_HEADER: &str = "x-sdrtrunk-delivery";
+
+/// Delay between checks for due deliveries
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Deliveries claimed (and sent concurrently) at a time
+const BATCH_SIZE: i64 = 50;
+/// Extra time past the request timeout before a claimed delivery is retried
+/// by another claim
+const LEASE_MARGIN_SECONDS: i64 = 60;
+
+/// Body posted to webhook endpoints
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    /// Event name
+    pub event: WebhookEvent,
+    /// When the event was queued
+    pub timestamp: DateTime<Utc>,
+    /// The call the event is about
+    pub call: CallSummary,
+    /// Why the transcription failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl WebhookPayload {
+    /// Build the payload for `event` on `call`
+    #[must_use]
+    pub fn new(event: WebhookEvent, call: &RadioCallDb, error: Option<String>) -> Self {
+        Self {
+            event,
+            timestamp: Utc::now(),
+            call: CallSummary::from(call),
+            error,
+        }
+    }
+}
+
+/// Call fields included in webhook payloads
+#[derive(Debug, Clone, Serialize)]
+pub struct CallSummary {
+    /// Call ID
+    pub call_id: Uuid,
+    /// When the call was recorded
+    pub call_timestamp: DateTime<Utc>,
+    /// System of the call
+    pub system_id: SystemId,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Talkgroup of the call
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Call length in seconds
+    pub duration_seconds: Option<Decimal>,
+    /// Transcription status
+    pub transcription_status: Option<String>,
+    /// Transcript, once completed
+    pub transcription_text: Option<String>,
+    /// Detected or configured language
+    pub transcription_language: Option<String>,
+    /// Number of speakers, when diarized
+    pub speaker_count: Option<i32>,
+}
+
+impl From<&RadioCallDb> for CallSummary {
+    fn from(call: &RadioCallDb) -> Self {
+        Self {
+            call_id: call.id,
+            call_timestamp: call.call_timestamp,
+            system_id: call.system_id.clone(),
+            system_label: call.system_label.clone(),
+            talkgroup_id: call.talkgroup_id,
+            talkgroup_label: call.talkgroup_label.clone(),
+            duration_seconds: call.duration_seconds,
+            transcription_status: call.transcription_status.clone(),
+            transcription_text: call.transcription_text.clone(),
+            transcription_language: call.transcription_language.clone(),
+            speaker_count: call.speaker_count,
+        }
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 signature of `body` under `secret`
+///
+/// # Errors
+///
+/// Returns an error if the HMAC cannot be keyed.
+pub fn sign(secret: &str, body: &[u8]) -> Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| anyhow!("invalid webhook secret: {e}"))?;
+    mac.update(body);
+    Ok(format!("sha256={:x}", mac.finalize().into_bytes()))
+}
+
+/// Queue `event` for every endpoint subscribed to it
+///
+/// Returns the number of deliveries queued.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn queue_event(
+    pool: &PgPool,
+    config: &WebhooksConfig,
+    event: WebhookEvent,
+    call_id: Uuid,
+    error: Option<String>,
+) -> Result<usize> {
+    let Some(call) = sdrtrunk_storage::get_radio_call(pool, call_id).await? else {
+        return Ok(0);
+    };
+    let endpoints: Vec<&WebhookEndpoint> = config
+        .endpoints_for(event, call.system_id.as_str())
+        .collect();
+    if endpoints.is_empty() {
+        return Ok(0);
+    }
+
+    let payload = serde_json::to_value(WebhookPayload::new(event, &call, error))?;
+    for endpoint in &endpoints {
+        let _ = WebhookQueries::enqueue(
+            pool,
+            &NewWebhookDelivery {
+                event: event.as_str(),
+                endpoint_url: &endpoint.url,
+                call_id: Some(call_id),
+                payload: &payload,
+            },
+        )
+        .await?;
+    }
+    Ok(endpoints.len())
+}
+
+/// Queue `event` in the background, logging failures
+pub fn spawn_event(
+    pool: &PgPool,
+    config: &WebhooksConfig,
+    event: WebhookEvent,
+    call_id: Uuid,
+    error: Option<String>,
+) {
+    if config.endpoints.is_empty() {
+        return;
+    }
+    let pool = pool.clone();
+    let config = config.clone();
+    drop(tokio::spawn(async move {
+        if let Err(e) = queue_event(&pool, &config, event, call_id, error).await {
+            warn!(
+                "Failed to queue {} webhooks for call {call_id}: {e:#}",
+                event.as_str()
+            );
+        }
+    }));
+}
+
+/// A transcription event and its failure reason
+type TranscriptionEvent = (WebhookEvent, Option<String>);
+
+/// Webhook event for a progress stage
+///
+/// Failures that will be retried are not reported.
+fn lifecycle_event(stage: ProgressStage) -> Option<TranscriptionEvent> {
+    match stage {
+        ProgressStage::Completed { .. } => Some((WebhookEvent::TranscriptionCompleted, None)),
+        ProgressStage::Failed {
+            error,
+            will_retry: false,
+        } => Some((WebhookEvent::TranscriptionFailed, Some(error))),
+        _ => None,
+    }
+}
+
+/// Posts queued deliveries to their endpoints
+#[derive(Debug, Clone)]
+pub struct WebhookSender {
+    http: reqwest::Client,
+    config: WebhooksConfig,
+}
+
+impl WebhookSender {
+    /// Create a sender from the webhook settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(config: &WebhooksConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds.max(1)))
+            .build()
+            .context("Failed to build webhook client")?;
+        Ok(Self {
+            http,
+            config: config.clone(),
+        })
+    }
+
+    /// Make one attempt at a delivery and return the outcome to record
+    pub async fn attempt(&self, delivery: &WebhookDelivery) -> WebhookAttempt {
+        let Some(endpoint) = self.config.endpoint(&delivery.endpoint_url) else {
+            return WebhookAttempt {
+                response_status: None,
+                error: Some("endpoint is no longer configured".to_string()),
+                retry_at: None,
+            };
+        };
+
+        let (response_status, error) = match self.post(endpoint, delivery).await {
+            Ok(status) if (200..300).contains(&status) => {
+                return WebhookAttempt {
+                    response_status: Some(i32::from(status)),
+                    error: None,
+                    retry_at: None,
+                };
+            }
+            Ok(status) => (Some(i32::from(status)), format!("HTTP {status}")),
+            Err(e) => (None, format!("{e:#}")),
+        };
+        let attempts = u32::try_from(delivery.attempts)
+            .unwrap_or(0)
+            .saturating_add(1);
+        let retry_at = self
+            .config
+            .retry_delay(attempts)
+            .and_then(|seconds| TimeDelta::try_seconds(i64::try_from(seconds).ok()?))
+            .and_then(|delay| Utc::now().checked_add_signed(delay));
+        WebhookAttempt {
+            response_status,
+            error: Some(error),
+            retry_at,
+        }
+    }
+
+    /// POST the delivery's payload, returning the response status
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload cannot be signed or the request fails.
+    async fn post(&self, endpoint: &WebhookEndpoint, delivery: &WebhookDelivery) -> Result<u16> {
+        let body = serde_json::to_vec(&delivery.payload)?;
+        let mut request = self
+            .http
+            .post(&endpoint.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.id.to_string());
+        if let Some(secret) = endpoint.secret.as_deref() {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &body)?);
+        }
+        let response = request.body(body).send().await?;
+        Ok(response.status().as_u16())
+    }
+
+    /// Send every due delivery, a batch at a time
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn deliver_due(&self, pool: &PgPool) -> Result<usize> {
+        let lease = i64::try_from(self.config.timeout_seconds)
+            .unwrap_or(i64::MAX)
+            .saturating_add(LEASE_MARGIN_SECONDS);
+        let mut sent = 0;
+        loop {
+            let batch = WebhookQueries::claim_due(pool, BATCH_SIZE, lease).await?;
+            let attempts =
+                futures_util::future::join_all(batch.iter().map(|d| self.attempt(d))).await;
+            for (delivery, attempt) in batch.iter().zip(&attempts) {
+                if let Some(error) = &attempt.error {
+                    warn!(
+                        "Webhook {} to {} failed (attempt {}): {error}",
+                        delivery.event,
+                        delivery.endpoint_url,
+                        delivery.attempts + 1
+                    );
+                }
+                WebhookQueries::record_attempt(pool, delivery.id, attempt).await?;
+            }
+            sent += batch.len();
+            if i64::try_from(batch.len()).unwrap_or(i64::MAX) < BATCH_SIZE {
+                return Ok(sent);
+            }
+        }
+    }
+}
+
+/// Spawn the webhook tasks if any endpoint is configured
+///
+/// One task queues transcription events from worker progress events; the
+/// returned one sends due deliveries.
+#[must_use]
+pub fn spawn_webhook_task(
+    pool: PgPool,
+    progress: ProgressEvents,
+    config: &WebhooksConfig,
+) -> Option<JoinHandle<()>> {
+    if config.endpoints.is_empty() {
+        return None;
+    }
+    let sender = match WebhookSender::new(config) {
+        Ok(sender) => sender,
+        Err(e) => {
+            warn!("Webhooks disabled: {e:#}");
+            return None;
+        }
+    };
+    info!(
+        "Sending call events to {} webhook endpoint(s)",
+        config.endpoints.len()
+    );
+
+    drop(tokio::spawn(queue_transcription_events(
+        pool.clone(),
+        progress,
+        Arc::new(config.clone()),
+    )));
+    Some(tokio::spawn(async move {
+        loop {
+            if let Err(e) = sender.deliver_due(&pool).await {
+                warn!("Webhook delivery failed: {e:#}");
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }))
+}
+
+/// Queue webhooks for completed and failed transcriptions
+async fn queue_transcription_events(
+    pool: PgPool,
+    mut progress: ProgressEvents,
+    config: Arc<WebhooksConfig>,
+) {
+    while let Some(progress) = progress.recv().await {
+        let Some((event, error)) = lifecycle_event(progress.stage) else {
+            continue;
+        };
+        let pool = pool.clone();
+        let config = Arc::clone(&config);
+        let call_id = progress.call_id;
+        drop(tokio::spawn(async move {
+            if let Err(e) = queue_event(&pool, &config, event, call_id, error).await {
+                warn!(
+                    "Failed to queue {} webhooks for call {call_id}: {e:#}",
+                    event.as_str()
+                );
+            }
+        }));
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog").unwrap(),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_event() {
+        let completed = ProgressStage::Completed {
+            text: None,
+            processing_time_ms: 10,
+        };
+        assert_eq!(
+            lifecycle_event(completed),
+            Some((WebhookEvent::TranscriptionCompleted, None))
+        );
+        let failed = |will_retry| ProgressStage::Failed {
+            error: "decode error".to_string(),
+            will_retry,
+        };
+        assert_eq!(
+            lifecycle_event(failed(false)),
+            Some((
+                WebhookEvent::TranscriptionFailed,
+                Some("decode error".to_string())
+            ))
+        );
+        assert_eq!(lifecycle_event(failed(true)), None);
+        assert_eq!(lifecycle_event(ProgressStage::Queued), None);
+    }
+
+    fn delivery(url: String, attempts: i32) -> WebhookDelivery {
+        WebhookDelivery {
+            id: Uuid::new_v4(),
+            event: "call_uploaded".to_string(),
+            endpoint_url: url,
+            call_id: Some(Uuid::new_v4()),
+            payload: json!({"event": "call_uploaded"}),
+            status: "pending".to_string(),
+            attempts,
+            next_attempt_at: Utc::now(),
+            response_status: None,
+            last_error: None,
+            created_at: Utc::now(),
+            delivered_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_attempt() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let app = Router::new()
+            .route(
+                "/ok",
+                post(move |headers: HeaderMap, body: String| async move {
+                    tx.send((headers, body)).unwrap();
+                    StatusCode::NO_CONTENT
+                }),
+            )
+            .route("/down", post(|| async { StatusCode::SERVICE_UNAVAILABLE }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = WebhooksConfig {
+            endpoints: ["ok", "down"]
+                .map(|path| WebhookEndpoint {
+                    url: format!("{base}/{path}"),
+                    secret: Some("s3cret".to_string()),
+                    events: Vec::new(),
+                    systems: Vec::new(),
+                })
+                .to_vec(),
+            max_attempts: 2,
+            ..WebhooksConfig::default()
+        };
+        let sender = WebhookSender::new(&config).unwrap();
+
+        let ok = delivery(format!("{base}/ok"), 0);
+        let attempt = sender.attempt(&ok).await;
+        assert_eq!(attempt.response_status, Some(204));
+        assert_eq!(attempt.error, None);
+        let (headers, body) = rx.recv().await.unwrap();
+        assert_eq!(headers[EVENT_HEADER], "call_uploaded");
+        assert_eq!(headers[DELIVERY_HEADER], ok.id.to_string().as_str());
+        assert_eq!(
+            headers[SIGNATURE_HEADER],
+            sign("s3cret", body.as_bytes()).unwrap().as_str()
+        );
+
+        let attempt = sender.attempt(&delivery(format!("{base}/down"), 0)).await;
+        assert_eq!(attempt.response_status, Some(503));
+        assert!(attempt.retry_at.unwrap() > Utc::now());
+        let attempt = sender.attempt(&delivery(format!("{base}/down"), 1)).await;
+        assert_eq!(attempt.error.as_deref(), Some("HTTP 503"));
+        assert_eq!(attempt.retry_at, None);
+
+        let attempt = sender
+            .attempt(&delivery("https://removed.example/hook".to_string(), 0))
+            .await;
+        assert_eq!(attempt.response_status, None);
+        assert_eq!(attempt.retry_at, None);
+    }
+}
@@ -0,0 +1,298 @@
+//! Transcription worker pool metrics
+//!
+//! Workers run as separate processes, so the API follows the pool through
+//! the progress notifications they publish: jobs processed and how long they
+//! took, failures by error type, and jobs in flight on each worker. The
+//! counters live in [`AppState`](crate::state::AppState), start at zero when
+//! the API starts, and are served by `/metrics` and `/api/stats/transcription`.
+
+use crate::progress::ProgressEvents;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use sdrtrunk_storage::{ProgressStage, TranscriptionProgress};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::task::JoinHandle;
+use tracing::info;
+use uuid::Uuid;
+
+/// Jobs in flight longer than this are assumed to have lost their completion
+/// event and are no longer counted
+const STALE_AFTER_MINUTES: i64 = 60;
+
+/// An error type and the message fragments that identify it
+type ErrorType = (&'static str, &'static [&'static str]);
+
+/// Error types failures are counted under (fragments matched
+/// case-insensitively, first match wins)
+const ERROR_TYPES: &[ErrorType] = &[
+    ("timeout", &["timeout", "timed out"]),
+    (
+        "audio",
+        &["audio", "not found", "inaccessible", "ffmpeg", "decode"],
+    ),
+    ("model", &["model"]),
+    (
+        "service",
+        &["unavailable", "communicate", "http", "connection", "python"],
+    ),
+    ("database", &["database"]),
+];
+
+/// Error type for failures matching none of [`ERROR_TYPES`]
+const OTHER_ERROR: &str = "other";
+
+/// Live counters of the transcription worker pool
+#[derive(Debug, Clone, Default)]
+pub struct WorkerPoolMetrics {
+    inner: Arc<Mutex<Counters>>,
+}
+
+/// Counters behind [`WorkerPoolMetrics`]
+#[derive(Debug, Default)]
+struct Counters {
+    since: Option<DateTime<Utc>>,
+    jobs_processed: u64,
+    processing_ms_total: u64,
+    failures: BTreeMap<&'static str, u64>,
+    retries: u64,
+    in_flight: HashMap<Uuid, InFlight>,
+    peak_concurrency: usize,
+}
+
+/// A job a worker has claimed and not yet finished
+#[derive(Debug)]
+struct InFlight {
+    worker_id: String,
+    claimed_at: DateTime<Utc>,
+}
+
+/// Worker pool metrics at one moment
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct WorkerPoolSnapshot {
+    /// When counting started (API startup)
+    pub since: DateTime<Utc>,
+    /// Jobs completed
+    pub jobs_processed: u64,
+    /// Total processing time of completed jobs, in milliseconds
+    pub processing_ms_total: u64,
+    /// Mean processing time of completed jobs, in milliseconds
+    pub average_duration_ms: Option<f64>,
+    /// Failed attempts by error type
+    pub failures: BTreeMap<String, u64>,
+    /// Failed attempts that were re-queued
+    pub retries: u64,
+    /// Jobs being transcribed now
+    pub concurrency: usize,
+    /// Most jobs seen in flight at once
+    pub peak_concurrency: usize,
+    /// Jobs being transcribed now, by worker
+    pub workers: BTreeMap<String, usize>,
+}
+
+impl WorkerPoolMetrics {
+    /// Start counting from now
+    #[must_use]
+    pub fn new() -> Self {
+        let metrics = Self::default();
+        metrics.lock().since = Some(Utc::now());
+        metrics
+    }
+
+    /// Count a progress event
+    pub fn record(&self, progress: &TranscriptionProgress) {
+        let key = progress.job_id.unwrap_or(progress.call_id);
+        let mut counters = self.lock();
+        match &progress.stage {
+            ProgressStage::Processing { worker_id } => {
+                let _ = counters.in_flight.insert(
+                    key,
+                    InFlight {
+                        worker_id: worker_id.clone(),
+                        claimed_at: progress.timestamp,
+                    },
+                );
+                counters.peak_concurrency = counters.peak_concurrency.max(counters.in_flight.len());
+            }
+            ProgressStage::Completed {
+                processing_time_ms, ..
+            } => {
+                let _ = counters.in_flight.remove(&key);
+                counters.jobs_processed += 1;
+                counters.processing_ms_total = counters
+                    .processing_ms_total
+                    .saturating_add(u64::try_from(*processing_time_ms).unwrap_or_default());
+            }
+            ProgressStage::Failed { error, will_retry } => {
+                let _ = counters.in_flight.remove(&key);
+                *counters.failures.entry(error_type(error)).or_default() += 1;
+                if *will_retry {
+                    counters.retries += 1;
+                }
+            }
+            ProgressStage::Queued | ProgressStage::Segment { .. } => {}
+        }
+    }
+
+    /// Current values, forgetting jobs in flight for too long
+    #[must_use]
+    pub fn snapshot(&self) -> WorkerPoolSnapshot {
+        let mut counters = self.lock();
+        let cutoff = Utc::now() - ChronoDuration::minutes(STALE_AFTER_MINUTES);
+        counters.in_flight.retain(|_, job| job.claimed_at >= cutoff);
+
+        let mut workers = BTreeMap::new();
+        for job in counters.in_flight.values() {
+            *workers.entry(job.worker_id.clone()).or_default() += 1;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let average_duration_ms = (counters.jobs_processed > 0)
+            .then(|| counters.processing_ms_total as f64 / counters.jobs_processed as f64);
+
+        WorkerPoolSnapshot {
+            since: counters.since.unwrap_or_else(Utc::now),
+            jobs_processed: counters.jobs_processed,
+            processing_ms_total: counters.processing_ms_total,
+            average_duration_ms,
+            failures: counters
+                .failures
+                .iter()
+                .map(|(kind, count)| ((*kind).to_string(), *count))
+                .collect(),
+            retries: counters.retries,
+            concurrency: counters.in_flight.len(),
+            peak_concurrency: counters.peak_concurrency,
+            workers,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Counters> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Error type a failure message is counted under
+#[must_use]
+pub fn error_type(message: &str) -> &'static str {
+    let message = message.to_lowercase();
+    ERROR_TYPES
+        .iter()
+        .find(|(_, fragments)| fragments.iter().any(|f| message.contains(f)))
+        .map_or(OTHER_ERROR, |(kind, _)| kind)
+}
+
+/// Spawn the task feeding progress events into `metrics`
+#[must_use]
+pub fn spawn_worker_metrics_task(
+    mut progress: ProgressEvents,
+    metrics: WorkerPoolMetrics,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("Tracking transcription worker pool metrics");
+        while let Some(event) = progress.recv().await {
+            metrics.record(&event);
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn event(job_id: Uuid, stage: ProgressStage) -> TranscriptionProgress {
+        TranscriptionProgress::new(Uuid::new_v4(), Some(job_id), stage)
+    }
+
+    fn processing(worker: &str) -> ProgressStage {
+        ProgressStage::Processing {
+            worker_id: worker.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_error_type() {
+        assert_eq!(
+            error_type("Transcription processing timeout after 300 seconds"),
+            "timeout"
+        );
+        assert_eq!(error_type("File not found or inaccessible: a.mp3"), "audio");
+        assert_eq!(
+            error_type("Failed to load transcription model: large-v3"),
+            "model"
+        );
+        assert_eq!(
+            error_type("Transcription service unavailable: whisperx"),
+            "service"
+        );
+        assert_eq!(error_type("Database error: closed"), "database");
+        assert_eq!(error_type("CUDA out of memory"), OTHER_ERROR);
+    }
+
+    #[test]
+    fn test_record_lifecycle() {
+        let metrics = WorkerPoolMetrics::new();
+        let (first, second, third) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        metrics.record(&event(first, processing("gpu-0")));
+        metrics.record(&event(second, processing("gpu-0")));
+        metrics.record(&event(third, processing("gpu-1")));
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.concurrency, 3);
+        assert_eq!(snapshot.workers["gpu-0"], 2);
+        assert_eq!(snapshot.average_duration_ms, None);
+
+        metrics.record(&event(
+            first,
+            ProgressStage::Completed {
+                text: None,
+                processing_time_ms: 1000,
+            },
+        ));
+        metrics.record(&event(
+            second,
+            ProgressStage::Completed {
+                text: None,
+                processing_time_ms: 3000,
+            },
+        ));
+        metrics.record(&event(
+            third,
+            ProgressStage::Failed {
+                error: "Transcription processing timeout after 300 seconds".to_string(),
+                will_retry: true,
+            },
+        ));
+        metrics.record(&event(third, ProgressStage::Queued));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.jobs_processed, 2);
+        assert_eq!(snapshot.processing_ms_total, 4000);
+        assert_eq!(snapshot.average_duration_ms, Some(2000.0));
+        assert_eq!(snapshot.failures["timeout"], 1);
+        assert_eq!(snapshot.retries, 1);
+        assert_eq!(snapshot.concurrency, 0);
+        assert_eq!(snapshot.peak_concurrency, 3);
+        assert!(snapshot.workers.is_empty());
+    }
+
+    #[test]
+    fn test_stale_jobs_forgotten() {
+        let metrics = WorkerPoolMetrics::new();
+        let mut stale = event(Uuid::new_v4(), processing("gpu-0"));
+        stale.timestamp = Utc::now() - ChronoDuration::minutes(STALE_AFTER_MINUTES + 1);
+        metrics.record(&stale);
+        metrics.record(&event(Uuid::new_v4(), processing("gpu-1")));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.concurrency, 1);
+        assert_eq!(snapshot.workers.keys().collect::<Vec<_>>(), ["gpu-1"]);
+    }
+}
@@ -0,0 +1,448 @@
+//! The API client
+
+use crate::error::{ClientError, Result};
+use crate::retry::RetryPolicy;
+use crate::types::{CallDetail, CallPage, CallUpload, ListCalls, UploadReceipt};
+use reqwest::header::{ACCEPT, HeaderMap, RETRY_AFTER};
+use reqwest::multipart::{Form, Part};
+use reqwest::{RequestBuilder, Response, Url};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Header naming the existing call when an upload duplicates it
+const DUPLICATE_OF_HEADER: &str = "x-duplicate-of";
+
+/// Header carrying the number of unfinished transcription jobs
+const QUEUE_DEPTH_HEADER: &str = "x-transcription-queue-depth";
+
+/// Header carrying the estimated seconds until the backlog drains
+const BACKLOG_SECONDS_HEADER: &str = "x-transcription-backlog-seconds";
+
+/// Default per-request timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// JSON body of an accepted upload
+#[derive(Debug, serde::Deserialize)]
+struct UploadBody {
+    id: Uuid,
+    message: String,
+}
+
+/// Typed async client for the transcriber API
+///
+/// Requests that fail with a retryable error are sent again according to
+/// the client's [`RetryPolicy`]. Uploads are retried too: under the server's
+/// default `storage.duplicate_uploads = "link"`, a retry whose first attempt
+/// was stored is answered with the existing call (see
+/// [`UploadReceipt::duplicate_of`]).
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: Url,
+    api_key: Option<String>,
+    retry: RetryPolicy,
+}
+
+impl Client {
+    /// A client for the server at `base_url` (e.g. `http://localhost:8080`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `base_url` is not an absolute HTTP(S) URL or the
+    /// HTTP client cannot be built.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let invalid = |reason: &str| ClientError::InvalidBaseUrl {
+            url: base_url.to_string(),
+            reason: reason.to_string(),
+        };
+        let mut url = Url::parse(base_url).map_err(|e| invalid(&e.to_string()))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(invalid("scheme must be http or https"));
+        }
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+        let http = reqwest::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            http,
+            base_url: url,
+            api_key: None,
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Authenticate with an API key
+    ///
+    /// It is sent as `X-API-Key`, and as the `key` form field on uploads.
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Retry failed requests according to `retry`
+    #[must_use]
+    pub const fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Send requests through `http`, e.g. one with a custom timeout or proxy
+    #[must_use]
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Base URL of the server
+    #[must_use]
+    pub const fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Upload a recording for transcription
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server rejects the upload (e.g. a bad key or
+    /// a `DUPLICATE_CALL` conflict) or cannot be reached within the retry
+    /// policy.
+    pub async fn upload_call(&self, upload: &CallUpload) -> Result<UploadReceipt> {
+        let url = self.url("api/call-upload")?;
+        let response = self
+            .send(|| {
+                self.http
+                    .post(url.clone())
+                    .header(ACCEPT, "application/json")
+                    .multipart(self.upload_form(upload))
+            })
+            .await?;
+        let headers = response.headers().clone();
+        let body: UploadBody = decode(response).await?;
+        Ok(UploadReceipt {
+            call_id: body.id,
+            message: body.message,
+            duplicate_of: header_value(&headers, DUPLICATE_OF_HEADER),
+            queue_depth: header_value(&headers, QUEUE_DEPTH_HEADER),
+            backlog_seconds: header_value(&headers, BACKLOG_SECONDS_HEADER),
+        })
+    }
+
+    /// List calls matching `filter`, one page at a time
+    ///
+    /// Searching is done with the filters; follow [`CallPage::next_cursor`]
+    /// through [`ListCalls::after`] for further pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server rejects the filters or cannot be
+    /// reached within the retry policy.
+    pub async fn list_calls(&self, filter: &ListCalls) -> Result<CallPage> {
+        let url = self.url("api/calls")?;
+        let response = self
+            .send(|| self.authorized(self.http.get(url.clone()).query(filter)))
+            .await?;
+        decode(response).await
+    }
+
+    /// Get one call with its transcript
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with code `CALL_NOT_FOUND` if the call does not
+    /// exist or is outside the key's systems, or if the server cannot be
+    /// reached within the retry policy.
+    pub async fn get_call(&self, id: Uuid) -> Result<CallDetail> {
+        let url = self.url(&format!("api/calls/{id}"))?;
+        let response = self
+            .send(|| self.authorized(self.http.get(url.clone())))
+            .await?;
+        decode(response).await
+    }
+
+    /// `path` resolved against the base URL
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` does not form a valid URL.
+    fn url(&self, path: &str) -> Result<Url> {
+        self.base_url
+            .join(path)
+            .map_err(|e| ClientError::InvalidBaseUrl {
+                url: self.base_url.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.api_key {
+            Some(key) => request.header("X-API-Key", key),
+            None => request,
+        }
+    }
+
+    fn upload_form(&self, upload: &CallUpload) -> Form {
+        let part = || Part::stream(upload.audio.clone()).file_name(upload.audio_name.clone());
+        // An unparseable MIME type is left for the server to guess
+        let audio = upload
+            .audio_type
+            .as_deref()
+            .and_then(|mime| part().mime_str(mime).ok())
+            .unwrap_or_else(part);
+        let mut form = Form::new().part("audio", audio);
+        if let Some(key) = &self.api_key {
+            form = form.text("key", key.clone());
+        }
+        for (name, value) in upload.text_fields() {
+            form = form.text(name, value);
+        }
+        form
+    }
+
+    /// Send the request built by `build`, retrying per the policy
+    ///
+    /// # Errors
+    ///
+    /// Returns the last failure once it is not retryable or the attempts
+    /// are used up.
+    async fn send(&self, build: impl Fn() -> RequestBuilder) -> Result<Response> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let error = match build().send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => api_error(response).await,
+                Err(e) => ClientError::from(e),
+            };
+            let retry_after = match &error {
+                ClientError::Api { retry_after, .. } => *retry_after,
+                _ => None,
+            };
+            let delay = if error.is_retryable() {
+                self.retry.delay(attempts, retry_after)
+            } else {
+                None
+            };
+            let Some(delay) = delay else {
+                return Err(error);
+            };
+            warn!("Attempt {attempts} failed ({error}); retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Parse a header as `T`, ignoring it when absent or malformed
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Turn an error response into a [`ClientError::Api`]
+async fn api_error(response: Response) -> ClientError {
+    #[derive(serde::Deserialize)]
+    struct ErrorBody {
+        error: String,
+        code: Option<String>,
+    }
+
+    let status = response.status();
+    let retry_after = header_value(response.headers(), RETRY_AFTER.as_str());
+    let text = response.text().await.unwrap_or_default();
+    let (message, code) = match serde_json::from_str::<ErrorBody>(&text) {
+        Ok(body) => (body.error, body.code),
+        Err(_) if text.trim().is_empty() => (
+            status
+                .canonical_reason()
+                .unwrap_or("Unknown error")
+                .to_string(),
+            None,
+        ),
+        Err(_) => (text, None),
+    };
+    ClientError::Api {
+        status: status.as_u16(),
+        code,
+        message,
+        retry_after,
+    }
+}
+
+/// Deserialize a successful response body
+///
+/// # Errors
+///
+/// Returns an error if the body cannot be read or is not a `T`.
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T> {
+    let bytes = response.bytes().await?;
+    serde_json::from_slice(&bytes).map_err(|e| ClientError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use axum::{
+        Json, Router,
+        extract::{Multipart, Query},
+        http::{HeaderMap as AxumHeaders, StatusCode},
+        routing::{get, post},
+    };
+    use sdrtrunk_types::{SystemId, TalkgroupId};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::net::TcpListener;
+
+    async fn serve(app: Router) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base
+    }
+
+    fn fast_retry(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    #[test]
+    fn test_new_validates_base_url() {
+        assert!(Client::new("not a url").is_err());
+        assert!(Client::new("ftp://example.com").is_err());
+        let client = Client::new("http://example.com/transcriber").unwrap();
+        assert_eq!(
+            client.url("api/calls").unwrap().as_str(),
+            "http://example.com/transcriber/api/calls"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_call() {
+        let app = Router::new().route(
+            "/api/call-upload",
+            post(|headers: AxumHeaders, mut form: Multipart| async move {
+                assert_eq!(headers["accept"], "application/json");
+                let mut fields = HashMap::new();
+                while let Some(field) = form.next_field().await.unwrap() {
+                    let name = field.name().unwrap().to_string();
+                    if name == "audio" {
+                        assert_eq!(field.file_name(), Some("call.mp3"));
+                    }
+                    fields.insert(name, field.bytes().await.unwrap());
+                }
+                assert_eq!(&fields["audio"][..], b"ID3");
+                assert_eq!(&fields["key"][..], b"k1");
+                assert_eq!(&fields["system"][..], b"metro");
+                assert_eq!(&fields["talkgroup"][..], b"52197");
+                (
+                    [
+                        (DUPLICATE_OF_HEADER, Uuid::nil().to_string()),
+                        (QUEUE_DEPTH_HEADER, "7".to_string()),
+                    ],
+                    Json(serde_json::json!({
+                        "success": true,
+                        "id": Uuid::nil(),
+                        "message": "Call already uploaded",
+                    })),
+                )
+            }),
+        );
+        let client = Client::new(&serve(app).await).unwrap().with_api_key("k1");
+
+        let upload = CallUpload {
+            talkgroup: Some(TalkgroupId::new(52197).unwrap()),
+            ..CallUpload::new(SystemId::new("metro").unwrap(), &b"ID3"[..], "call.mp3")
+        };
+        let receipt = client.upload_call(&upload).await.unwrap();
+        assert_eq!(receipt.call_id, Uuid::nil());
+        assert_eq!(receipt.duplicate_of, Some(Uuid::nil()));
+        assert_eq!(receipt.queue_depth, Some(7));
+        assert_eq!(receipt.backlog_seconds, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_calls_retries_server_errors() {
+        let hits = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&hits);
+        let app = Router::new().route(
+            "/api/calls",
+            get(
+                move |headers: AxumHeaders, Query(query): Query<HashMap<String, String>>| {
+                    let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        assert_eq!(headers["x-api-key"], "k1");
+                        assert_eq!(query["system_id"], "metro");
+                        if attempt == 0 {
+                            return Err(StatusCode::SERVICE_UNAVAILABLE);
+                        }
+                        Ok(Json(serde_json::json!({
+                            "calls": [],
+                            "total": 0,
+                            "count": 0,
+                            "pagination": { "has_next": false, "next_cursor": null },
+                        })))
+                    }
+                },
+            ),
+        );
+        let client = Client::new(&serve(app).await)
+            .unwrap()
+            .with_api_key("k1")
+            .with_retry(fast_retry(3));
+
+        let filter = ListCalls {
+            system_id: Some(SystemId::new("metro").unwrap()),
+            ..ListCalls::default()
+        };
+        let page = client.list_calls(&filter).await.unwrap();
+        assert!(page.calls.is_empty());
+        assert_eq!(page.next_cursor(), None);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_call_reports_api_errors() {
+        let hits = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&hits);
+        let app = Router::new().route(
+            "/api/calls/:id",
+            get(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(serde_json::json!({
+                            "success": false,
+                            "error": "Call not found",
+                            "code": "CALL_NOT_FOUND",
+                        })),
+                    )
+                }
+            }),
+        );
+        let client = Client::new(&serve(app).await)
+            .unwrap()
+            .with_retry(fast_retry(3));
+
+        let error = client.get_call(Uuid::nil()).await.unwrap_err();
+        assert_eq!(error.status(), Some(404));
+        assert_eq!(error.code(), Some("CALL_NOT_FOUND"));
+        assert_eq!(error.to_string(), "API error 404: Call not found");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
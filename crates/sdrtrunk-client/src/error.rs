@@ -0,0 +1,115 @@
+//! Errors returned by the client
+
+use thiserror::Error;
+
+/// Result type alias for client operations
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Errors that can occur while calling the API
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// The base URL could not be parsed
+    #[error("Invalid base URL {url}: {reason}")]
+    InvalidBaseUrl {
+        /// URL as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+
+    /// The request never got a response (connection refused, timeout, ...)
+    #[error("Request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    /// The server answered with an error status
+    #[error("API error {status}: {message}")]
+    Api {
+        /// HTTP status code
+        status: u16,
+        /// Stable error code from the response body (e.g. `CALL_NOT_FOUND`)
+        code: Option<String>,
+        /// Error message from the response body
+        message: String,
+        /// Seconds the server asked us to wait (`Retry-After`)
+        retry_after: Option<u64>,
+    },
+
+    /// The response body was not what the endpoint documents
+    #[error("Unexpected response: {0}")]
+    Decode(String),
+}
+
+impl ClientError {
+    /// Whether sending the same request again may succeed
+    ///
+    /// Transport failures, rate limiting (429), and server errors (5xx) are
+    /// retryable; other API errors will fail the same way again.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(e) => !e.is_builder() && !e.is_decode(),
+            Self::Api { status, .. } => *status == 429 || *status >= 500,
+            Self::InvalidBaseUrl { .. } | Self::Decode(_) => false,
+        }
+    }
+
+    /// HTTP status of an API error
+    #[must_use]
+    pub const fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Stable error code of an API error
+    #[must_use]
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Api { code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn api(status: u16) -> ClientError {
+        ClientError::Api {
+            status,
+            code: Some("X".to_string()),
+            message: "x".to_string(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(api(429).is_retryable());
+        assert!(api(500).is_retryable());
+        assert!(api(503).is_retryable());
+        assert!(!api(400).is_retryable());
+        assert!(!api(404).is_retryable());
+        assert!(!api(409).is_retryable());
+        assert!(!ClientError::Decode("x".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let error = api(404);
+        assert_eq!(error.status(), Some(404));
+        assert_eq!(error.code(), Some("X"));
+        assert_eq!(error.to_string(), "API error 404: x");
+        assert_eq!(ClientError::Decode("x".to_string()).status(), None);
+    }
+}
@@ -0,0 +1,52 @@
+//! Typed async client for the `SDRTrunk` transcriber API.
+//!
+//! Integrators use [`Client`] to upload recordings, page through calls, and
+//! fetch transcripts instead of building requests by hand. Failed requests
+//! are retried with exponential backoff according to a [`RetryPolicy`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use sdrtrunk_client::{CallUpload, Client, ListCalls};
+//! use sdrtrunk_types::SystemId;
+//!
+//! # async fn run() -> sdrtrunk_client::Result<()> {
+//! let client = Client::new("http://localhost:8080")?.with_api_key("my-key");
+//!
+//! let system = SystemId::new("metro").unwrap();
+//! let audio = std::fs::read("call.mp3").unwrap();
+//! let receipt = client
+//!     .upload_call(&CallUpload::new(system.clone(), audio, "call.mp3"))
+//!     .await?;
+//! println!("stored as {}", receipt.call_id);
+//!
+//! let mut filter = ListCalls {
+//!     system_id: Some(system),
+//!     include_transcription: Some(true),
+//!     ..ListCalls::default()
+//! };
+//! loop {
+//!     let page = client.list_calls(&filter).await?;
+//!     for call in &page.calls {
+//!         println!("{} {:?}", call.id, call.transcription_text);
+//!     }
+//!     match page.next_cursor() {
+//!         Some(cursor) => filter.after = Some(cursor.to_string()),
+//!         None => break,
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod error;
+mod retry;
+mod types;
+
+pub use client::Client;
+pub use error::{ClientError, Result};
+pub use retry::RetryPolicy;
+pub use types::{
+    CallDetail, CallPage, CallSummary, CallUpload, ListCalls, Pagination, SortOrder, UploadReceipt,
+};
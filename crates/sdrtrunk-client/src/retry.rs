@@ -0,0 +1,93 @@
+//! Retry with exponential backoff
+
+use std::time::Duration;
+
+/// How often and how patiently to retry a failed request
+///
+/// Only retryable failures (see [`ClientError::is_retryable`]) are retried.
+/// The wait doubles after each attempt, starting at `base_delay` and capped
+/// at `max_delay`; a rate-limited response's `Retry-After` is honoured up to
+/// the same cap.
+///
+/// [`ClientError::is_retryable`]: crate::ClientError::is_retryable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first (1 disables retries)
+    pub max_attempts: u32,
+    /// Wait after the first failed attempt
+    pub base_delay: Duration,
+    /// Longest wait between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send each request once
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before retrying after `attempts` failed attempts, or `None` once
+    /// the attempts are used up
+    ///
+    /// `retry_after` is the server's requested wait in seconds, if any.
+    #[must_use]
+    pub fn delay(&self, attempts: u32, retry_after: Option<u64>) -> Option<Duration> {
+        if attempts == 0 || attempts >= self.max_attempts {
+            return None;
+        }
+        let factor = 1_u32.checked_shl(attempts - 1).unwrap_or(u32::MAX);
+        let backoff = self.base_delay.saturating_mul(factor);
+        let requested = retry_after.map_or(Duration::ZERO, Duration::from_secs);
+        Some(backoff.max(requested).min(self.max_delay))
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 6,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+        assert_eq!(policy.delay(0, None), None);
+        assert_eq!(policy.delay(1, None), Some(Duration::from_secs(1)));
+        assert_eq!(policy.delay(2, None), Some(Duration::from_secs(2)));
+        assert_eq!(policy.delay(3, None), Some(Duration::from_secs(4)));
+        assert_eq!(policy.delay(4, None), Some(Duration::from_secs(5)));
+        assert_eq!(policy.delay(6, None), None);
+    }
+
+    #[test]
+    fn test_delay_honours_retry_after() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(1, Some(3)), Some(Duration::from_secs(3)));
+        assert_eq!(policy.delay(1, Some(3600)), Some(policy.max_delay));
+        assert_eq!(RetryPolicy::none().delay(1, Some(3)), None);
+    }
+}
@@ -0,0 +1,369 @@
+//! Request and response types for the client's endpoints
+//!
+//! Responses keep only the fields integrators commonly need; fields the
+//! server adds later are ignored rather than breaking deserialization.
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId, TranscriptionStatus};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A recording to send to `POST /api/call-upload`
+///
+/// Build one with [`CallUpload::new`] and fill in whatever metadata is known:
+///
+/// ```
+/// use sdrtrunk_client::CallUpload;
+/// use sdrtrunk_types::{SystemId, TalkgroupId};
+///
+/// let upload = CallUpload {
+///     talkgroup: Some(TalkgroupId::new(52197).unwrap()),
+///     ..CallUpload::new(SystemId::new("metro").unwrap(), vec![0_u8; 16], "call.mp3")
+/// };
+/// assert_eq!(upload.audio_name, "call.mp3");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CallUpload {
+    /// System the call was heard on
+    pub system: SystemId,
+    /// Recording bytes (MP3, WAV, FLAC)
+    pub audio: Bytes,
+    /// File name of the recording
+    pub audio_name: String,
+    /// MIME type of the recording; guessed by the server when unset
+    pub audio_type: Option<String>,
+    /// When the call started; the server uses the upload time when unset
+    pub date_time: Option<DateTime<Utc>>,
+    /// Talkgroup of the call
+    pub talkgroup: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Frequency of the call
+    pub frequency: Option<Frequency>,
+    /// Radio that transmitted
+    pub source: Option<RadioId>,
+    /// Alias of the transmitting radio
+    pub talker_alias: Option<String>,
+    /// Receiving site latitude in degrees; sent only with `longitude`
+    pub latitude: Option<f64>,
+    /// Receiving site longitude in degrees; sent only with `latitude`
+    pub longitude: Option<f64>,
+}
+
+impl CallUpload {
+    /// An upload of `audio` for `system` with no other metadata
+    #[must_use]
+    pub fn new(system: SystemId, audio: impl Into<Bytes>, audio_name: impl Into<String>) -> Self {
+        Self {
+            system,
+            audio: audio.into(),
+            audio_name: audio_name.into(),
+            audio_type: None,
+            date_time: None,
+            talkgroup: None,
+            talkgroup_label: None,
+            frequency: None,
+            source: None,
+            talker_alias: None,
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    /// Text form fields in the names the upload endpoint reads
+    pub(crate) fn text_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![("system", self.system.to_string())];
+        let mut push = |name, value: Option<String>| {
+            if let Some(value) = value {
+                fields.push((name, value));
+            }
+        };
+        push(
+            "dateTime",
+            self.date_time.map(|t| t.timestamp().to_string()),
+        );
+        push("talkgroup", self.talkgroup.map(|t| t.as_i32().to_string()));
+        push("talkgroupLabel", self.talkgroup_label.clone());
+        push("frequency", self.frequency.map(|f| f.as_hz().to_string()));
+        push("source", self.source.map(|r| r.as_i32().to_string()));
+        push("talkerAlias", self.talker_alias.clone());
+        if let (Some(latitude), Some(longitude)) = (self.latitude, self.longitude) {
+            push("latitude", Some(latitude.to_string()));
+            push("longitude", Some(longitude.to_string()));
+        }
+        fields
+    }
+}
+
+/// The server's answer to an accepted upload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadReceipt {
+    /// ID of the stored call
+    pub call_id: Uuid,
+    /// Message from the server
+    pub message: String,
+    /// Set when the recording was already stored; `call_id` is then the
+    /// existing call
+    pub duplicate_of: Option<Uuid>,
+    /// Unfinished transcription jobs, when transcription is enabled
+    pub queue_depth: Option<i64>,
+    /// Estimated seconds until the transcription backlog drains
+    pub backlog_seconds: Option<i64>,
+}
+
+/// Sort order of call listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Newest first
+    #[default]
+    Desc,
+    /// Oldest first
+    Asc,
+}
+
+/// Filters for `GET /api/calls`; unset fields do not filter
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListCalls {
+    /// Calls per page (1-1000, server default 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Resume after this cursor: the previous page's `next_cursor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Only calls on this system
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_id: Option<SystemId>,
+    /// Only calls on this talkgroup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only calls in this transcription state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_status: Option<TranscriptionStatus>,
+    /// Only calls carrying this tag (case-insensitive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Search expression such as `system:butler tg:1234..1299 text~"pursuit"`;
+    /// plain words match transcript or summary text (case-insensitive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// Only calls transcribed in this language, e.g. `en`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// List archived calls instead of live ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+    /// Only calls at or after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only calls at or before this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_date: Option<DateTime<Utc>>,
+    /// Order of the listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortOrder>,
+    /// Include each call's transcript text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_transcription: Option<bool>,
+}
+
+/// One page of `GET /api/calls`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallPage {
+    /// Calls on this page
+    pub calls: Vec<CallSummary>,
+    /// Calls matching the filters across all pages
+    pub total: i64,
+    /// Where the next page starts
+    pub pagination: Pagination,
+}
+
+impl CallPage {
+    /// Cursor for the next page, or `None` on the last page
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<&str> {
+        if self.pagination.has_next {
+            self.pagination.next_cursor.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Pagination of a call listing
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pagination {
+    /// Whether more calls follow
+    pub has_next: bool,
+    /// Pass as [`ListCalls::after`] to get the next page
+    pub next_cursor: Option<String>,
+}
+
+/// A call in a listing
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallSummary {
+    /// Call ID
+    pub id: Uuid,
+    /// When the call started
+    pub call_timestamp: DateTime<Utc>,
+    /// System the call was heard on
+    pub system_id: SystemId,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Talkgroup of the call
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Radio that transmitted
+    pub source_radio_id: Option<RadioId>,
+    /// Length of the recording in seconds
+    pub duration_seconds: Option<Decimal>,
+    /// Transcription state
+    pub transcription_status: Option<TranscriptionStatus>,
+    /// Transcription confidence (0.0-1.0)
+    pub transcription_confidence: Option<Decimal>,
+    /// Detected language
+    #[serde(default)]
+    pub transcription_language: Option<String>,
+    /// Transcript text, when requested with `include_transcription`
+    #[serde(default)]
+    pub transcription_text: Option<String>,
+    /// Frequency of the call
+    pub frequency: Option<Frequency>,
+    /// Tags analysts attached to the call
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A call from `GET /api/calls/{id}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallDetail {
+    /// Call ID
+    pub id: Uuid,
+    /// When the call started
+    pub call_timestamp: DateTime<Utc>,
+    /// When the call was uploaded
+    pub upload_timestamp: DateTime<Utc>,
+    /// System the call was heard on
+    pub system_id: SystemId,
+    /// System display name
+    pub system_label: Option<String>,
+    /// Talkgroup of the call
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name
+    pub talkgroup_label: Option<String>,
+    /// Radio that transmitted
+    pub source_radio_id: Option<RadioId>,
+    /// Alias of the transmitting radio
+    pub talker_alias: Option<String>,
+    /// Length of the recording in seconds
+    pub duration_seconds: Option<Decimal>,
+    /// Transcription state
+    pub transcription_status: Option<TranscriptionStatus>,
+    /// Transcript text
+    pub transcription_text: Option<String>,
+    /// Transcription confidence (0.0-1.0)
+    pub transcription_confidence: Option<Decimal>,
+    /// Detected language
+    pub transcription_language: Option<String>,
+    /// Frequency of the call
+    pub frequency: Option<Frequency>,
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_text_fields() {
+        let upload = CallUpload {
+            date_time: DateTime::from_timestamp(1_640_995_200, 0),
+            talkgroup: Some(TalkgroupId::new(52197).unwrap()),
+            frequency: Some(Frequency::new(851_012_500).unwrap()),
+            latitude: Some(35.5),
+            ..CallUpload::new(SystemId::new("metro").unwrap(), Bytes::new(), "call.mp3")
+        };
+        assert_eq!(
+            upload.text_fields(),
+            [
+                ("system", "metro".to_string()),
+                ("dateTime", "1640995200".to_string()),
+                ("talkgroup", "52197".to_string()),
+                ("frequency", "851012500".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_calls_query() {
+        let query = ListCalls {
+            limit: Some(10),
+            transcription_status: Some(TranscriptionStatus::NeedsReview),
+            tag: Some("fire".to_string()),
+            sort: Some(SortOrder::Asc),
+            ..ListCalls::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({
+                "limit": 10,
+                "transcription_status": "needs_review",
+                "tag": "fire",
+                "sort": "asc",
+            })
+        );
+    }
+
+    #[test]
+    fn test_call_page_cursor() {
+        let page: CallPage = serde_json::from_value(serde_json::json!({
+            "calls": [{
+                "id": Uuid::nil(),
+                "call_timestamp": "2025-01-01T00:00:00Z",
+                "system_id": "metro",
+                "system_label": null,
+                "talkgroup_id": 52197,
+                "talkgroup_label": null,
+                "source_radio_id": null,
+                "duration_seconds": "4.5",
+                "transcription_status": "completed",
+                "transcription_confidence": null,
+                "frequency": null,
+                "audio_filename": "call.mp3",
+                "tags": ["fire"],
+            }],
+            "total": 3,
+            "count": 1,
+            "pagination": { "has_next": true, "next_cursor": "c1" },
+        }))
+        .unwrap();
+        assert_eq!(page.next_cursor(), Some("c1"));
+        let call = &page.calls[0];
+        assert_eq!(call.system_id.as_str(), "metro");
+        assert_eq!(
+            call.transcription_status,
+            Some(TranscriptionStatus::Completed)
+        );
+        assert_eq!(call.transcription_text, None);
+        assert_eq!(call.tags, ["fire"]);
+
+        let last = CallPage {
+            pagination: Pagination {
+                has_next: false,
+                next_cursor: Some("c2".to_string()),
+            },
+            ..page
+        };
+        assert_eq!(last.next_cursor(), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Keyword alert matching.
+//!
+//! An alert rule is either a **keyword** or a **regex**:
+//!
+//! - Keywords are words or phrases matched case-insensitively on word
+//!   boundaries, so `fire` matches "Structure FIRE on Main" but not
+//!   "firefighter". Any run of whitespace in a phrase matches any run of
+//!   whitespace in the transcript.
+//! - Regexes use the [`regex`] crate syntax and are matched as written; add
+//!   `(?i)` for a case-insensitive pattern.
+
+use crate::error::{ProtocolError, Result};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Longest pattern accepted, in bytes.
+pub const MAX_PATTERN_LEN: usize = 1000;
+
+/// Compiled size limit for a rule, keeping user regexes cheap to run.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// How a rule's pattern is interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternKind {
+    /// Case-insensitive word or phrase.
+    #[default]
+    Keyword,
+    /// Regular expression.
+    Regex,
+}
+
+impl PatternKind {
+    /// Name stored in the `alert_rules.pattern_type` column.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Regex => "regex",
+        }
+    }
+}
+
+impl FromStr for PatternKind {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "keyword" => Ok(Self::Keyword),
+            "regex" => Ok(Self::Regex),
+            other => Err(ProtocolError::FieldParse {
+                field: "pattern_type".to_string(),
+                detail: format!("unknown pattern type '{other}'"),
+            }),
+        }
+    }
+}
+
+/// A compiled alert pattern.
+#[derive(Debug, Clone)]
+pub struct AlertMatcher {
+    regex: Regex,
+}
+
+impl AlertMatcher {
+    /// Compile a rule's pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::InvalidFormat`] if the pattern is empty, too
+    /// long, or not a valid regex.
+    pub fn new(kind: PatternKind, pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(invalid("pattern is empty".to_string()));
+        }
+        if pattern.len() > MAX_PATTERN_LEN {
+            return Err(invalid(format!(
+                "pattern is longer than {MAX_PATTERN_LEN} bytes"
+            )));
+        }
+
+        let source = match kind {
+            PatternKind::Keyword => keyword_regex(pattern),
+            PatternKind::Regex => pattern.to_string(),
+        };
+        let regex = RegexBuilder::new(&source)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|e| invalid(format!("invalid pattern: {e}")))?;
+        Ok(Self { regex })
+    }
+
+    /// First match in `text`, if any.
+    #[must_use]
+    pub fn find<'t>(&self, text: &'t str) -> Option<&'t str> {
+        self.regex.find(text).map(|m| m.as_str())
+    }
+}
+
+/// Build a case-insensitive, word-bounded regex for a keyword or phrase.
+///
+/// Word boundaries are only required next to word characters, so keywords
+/// such as `10-33` or `#1` still match.
+pub(crate) fn keyword_regex(keyword: &str) -> String {
+    let words: Vec<String> = keyword.split_whitespace().map(regex::escape).collect();
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if is_word(keyword.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if is_word(keyword.chars().next_back()) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!(r"(?i){start}{}{end}", words.join(r"\s+"))
+}
+
+const fn invalid(reason: String) -> ProtocolError {
+    ProtocolError::InvalidFormat { reason }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn keyword(pattern: &str) -> AlertMatcher {
+        AlertMatcher::new(PatternKind::Keyword, pattern).unwrap()
+    }
+
+    #[test]
+    fn test_keyword_matches_whole_words() {
+        let matcher = keyword("fire");
+        assert_eq!(matcher.find("Structure FIRE on Main"), Some("FIRE"));
+        assert_eq!(matcher.find("Engine 5, firefighter down"), None);
+    }
+
+    #[test]
+    fn test_keyword_phrase_and_symbols() {
+        let matcher = keyword("shots  fired");
+        assert_eq!(
+            matcher.find("report of shots\nfired near the park"),
+            Some("shots\nfired")
+        );
+        assert_eq!(keyword("10-33").find("unit 4, 10-33"), Some("10-33"));
+        assert_eq!(keyword("#1").find("engine #1 responding"), Some("#1"));
+        // Regex metacharacters in keywords are literal
+        assert_eq!(keyword("a.b").find("axb"), None);
+    }
+
+    #[test]
+    fn test_regex_pattern() {
+        let matcher = AlertMatcher::new(PatternKind::Regex, r"code \d+").unwrap();
+        assert_eq!(matcher.find("respond code 3"), Some("code 3"));
+        assert_eq!(matcher.find("respond CODE 3"), None);
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(AlertMatcher::new(PatternKind::Keyword, "  ").is_err());
+        assert!(AlertMatcher::new(PatternKind::Regex, "(unclosed").is_err());
+        assert!(AlertMatcher::new(PatternKind::Keyword, &"x".repeat(MAX_PATTERN_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_pattern_kind_round_trip() {
+        for kind in [PatternKind::Keyword, PatternKind::Regex] {
+            assert_eq!(kind.as_str().parse::<PatternKind>().unwrap(), kind);
+        }
+        assert!("glob".parse::<PatternKind>().is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Transcript anonymization.
+//!
+//! An [`Anonymizer`] replaces the personal details selected in
+//! [`AnonymizationConfig`] with placeholders such as `[NAME]` or `[PHONE]`.
+//! Detection is pattern based: names are found from the configured list and
+//! after titles ("Officer Smith", "Mrs. Jones"), which catches the names
+//! dispatchers and units say on the air without a language model.
+
+use crate::alerts::{MAX_PATTERN_LEN, keyword_regex};
+use crate::config::{AnonymizationConfig, PiiKind};
+use crate::error::{ProtocolError, Result};
+use regex::{Regex, RegexBuilder};
+use std::borrow::Cow;
+
+/// Compiled size limit for a pattern, keeping user regexes cheap to run.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Capitalized name after a title, keeping the title.
+const TITLED_NAME: &str = r"\b((?i:officer|deputy|trooper|sergeant|sgt|lieutenant|lt|captain|capt|detective|det|chief|mister|mr|mrs|ms|miss|doctor|dr)\.?\s+)[A-Z][a-zA-Z'-]+";
+
+/// Ten-digit numbers with optional country code, and seven-digit numbers
+/// with a separator.
+const PHONE_NUMBER: &str =
+    r"(?:\+?1[-.\s]?)?(?:\(\d{3}\)\s?|\b\d{3}[-.\s]?)\d{3}[-.\s]?\d{4}\b|\b\d{3}[-.]\d{4}\b";
+
+/// House number, street name of up to three words, and street suffix.
+const STREET_ADDRESS: &str = r"(?i)\b\d{1,6}\s+(?:[a-z0-9']+\s+){1,3}(?:street|st|avenue|ave|road|rd|boulevard|blvd|drive|dr|lane|ln|court|ct|way|place|pl|circle|cir|highway|hwy|parkway|pkwy|terrace|ter|trail|trl)\b\.?";
+
+const EMAIL_ADDRESS: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b";
+
+/// A pattern and what replaces its matches.
+#[derive(Debug, Clone)]
+struct Rule {
+    regex: Regex,
+    replacement: String,
+}
+
+/// Compiled anonymization rules.
+#[derive(Debug, Clone)]
+pub struct Anonymizer {
+    rules: Vec<Rule>,
+}
+
+impl Anonymizer {
+    /// Compile the rules selected in `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::InvalidFormat`] if a name or custom pattern
+    /// is too long or not a valid regex.
+    pub fn new(config: &AnonymizationConfig) -> Result<Self> {
+        let mut rules = Vec::new();
+        // Custom patterns first, so built-in placeholders never feed them
+        for pattern in &config.patterns {
+            if pattern.regex.len() > MAX_PATTERN_LEN {
+                return Err(invalid(format!(
+                    "scrub pattern is longer than {MAX_PATTERN_LEN} bytes"
+                )));
+            }
+            rules.push(rule(&pattern.regex, &pattern.replacement)?);
+        }
+
+        let names = PiiKind::Names.placeholder();
+        if config.scrub.contains(&PiiKind::Names) {
+            let listed: Vec<String> = config
+                .names
+                .iter()
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .map(|name| format!("(?:{})", keyword_regex(name)))
+                .collect();
+            if !listed.is_empty() {
+                rules.push(rule(&listed.join("|"), names)?);
+            }
+            rules.push(rule(TITLED_NAME, &format!("${{1}}{names}"))?);
+        }
+        // Addresses before phone numbers, whose digits they may contain
+        for (kind, source) in [
+            (PiiKind::Emails, EMAIL_ADDRESS),
+            (PiiKind::Addresses, STREET_ADDRESS),
+            (PiiKind::PhoneNumbers, PHONE_NUMBER),
+        ] {
+            if config.scrub.contains(&kind) {
+                rules.push(rule(source, kind.placeholder())?);
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// `text` with every detail the rules match replaced.
+    #[must_use]
+    pub fn scrub<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        self.rules.iter().fold(Cow::Borrowed(text), |text, rule| {
+            let scrubbed = match rule.regex.replace_all(&text, rule.replacement.as_str()) {
+                Cow::Borrowed(_) => None,
+                Cow::Owned(scrubbed) => Some(scrubbed),
+            };
+            scrubbed.map_or(text, Cow::Owned)
+        })
+    }
+}
+
+fn rule(source: &str, replacement: &str) -> Result<Rule> {
+    let regex = RegexBuilder::new(source)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| invalid(format!("invalid scrub pattern: {e}")))?;
+    Ok(Rule {
+        regex,
+        replacement: replacement.to_string(),
+    })
+}
+
+const fn invalid(reason: String) -> ProtocolError {
+    ProtocolError::InvalidFormat { reason }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::config::ScrubPattern;
+
+    fn anonymizer(scrub: &[PiiKind]) -> Anonymizer {
+        Anonymizer::new(&AnonymizationConfig {
+            enabled: true,
+            scrub: scrub.to_vec(),
+            names: vec!["Jane Doe".to_string()],
+            ..AnonymizationConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scrubs_names() {
+        let scrub = anonymizer(&[PiiKind::Names]);
+        assert_eq!(
+            scrub.scrub("Officer Smith requests a callback for JANE  doe"),
+            "Officer [NAME] requests a callback for [NAME]"
+        );
+        assert_eq!(scrub.scrub("Mrs. Jones reports"), "Mrs. [NAME] reports");
+        // Titles followed by ordinary words are left alone
+        assert_eq!(scrub.scrub("officer down"), "officer down");
+    }
+
+    #[test]
+    fn test_scrubs_phone_numbers() {
+        let scrub = anonymizer(&[PiiKind::PhoneNumbers]);
+        assert_eq!(
+            scrub.scrub("call back at (919) 555-0134 or 919.555.0199"),
+            "call back at [PHONE] or [PHONE]"
+        );
+        assert_eq!(scrub.scrub("RP at 555-0100"), "RP at [PHONE]");
+        // Unit numbers and times are not phone numbers
+        assert_eq!(scrub.scrub("unit 4512 at 1430"), "unit 4512 at 1430");
+    }
+
+    #[test]
+    fn test_scrubs_addresses_and_emails() {
+        let scrub = anonymizer(&[PiiKind::Addresses, PiiKind::Emails]);
+        assert_eq!(
+            scrub.scrub("respond to 1200 N Main Street for a fall"),
+            "respond to [ADDRESS] for a fall"
+        );
+        assert_eq!(scrub.scrub("email jdoe@example.com"), "email [EMAIL]");
+    }
+
+    #[test]
+    fn test_only_selected_kinds_and_custom_patterns() {
+        let scrub = Anonymizer::new(&AnonymizationConfig {
+            enabled: true,
+            scrub: vec![PiiKind::PhoneNumbers],
+            patterns: vec![ScrubPattern {
+                regex: r"(?i)plate \w+".to_string(),
+                replacement: "plate [PLATE]".to_string(),
+            }],
+            ..AnonymizationConfig::default()
+        })
+        .unwrap();
+        assert_eq!(
+            scrub.scrub("Officer Smith, plate ABC123, 919-555-0134"),
+            "Officer Smith, plate [PLATE], [PHONE]"
+        );
+    }
+
+    #[test]
+    fn test_unchanged_text_is_borrowed() {
+        let scrub = anonymizer(&[PiiKind::Names, PiiKind::PhoneNumbers]);
+        assert!(matches!(scrub.scrub("engine 5 on scene"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_rejects_invalid_patterns() {
+        let config = AnonymizationConfig {
+            patterns: vec![ScrubPattern {
+                regex: "(unclosed".to_string(),
+                replacement: String::new(),
+            }],
+            ..AnonymizationConfig::default()
+        };
+        assert!(Anonymizer::new(&config).is_err());
+    }
+}
@@ -0,0 +1,601 @@
+//! Filter expressions for call searches.
+//!
+//! A [`CallQuery`] is a search such as
+//! `system:butler AND tg:1234..1299 AND text~"pursuit"`. Terms are
+//! `field:value` (is) or `field~value` (contains), with values quoted when
+//! they contain spaces. Terms can be combined with `AND`, `OR`, `NOT` (or a
+//! leading `-`), and parentheses; `AND` binds tighter than `OR`, and terms
+//! written next to each other must all match. A bare word or quoted phrase
+//! searches transcripts, so plain keyword searches keep working.
+//!
+//! | Field | Matches |
+//! |-------|---------|
+//! | `system` | System ID |
+//! | `tg`, `talkgroup` | Talkgroup ID or range (`1234..1299`, `1234..`, `..1299`) |
+//! | `radio` | Source radio ID or range |
+//! | `freq`, `frequency` | Frequency in Hz, or range |
+//! | `duration` | Whole seconds, or range |
+//! | `status` | Transcription status |
+//! | `tag` | Call tag |
+//! | `lang`, `language` | Transcription language (`en` also matches `en-US`) |
+//! | `label` | Talkgroup label |
+//! | `text` | Transcript or summary (always contains) |
+//!
+//! Text comparisons ignore case. Parsing only builds the expression; the
+//! storage layer turns it into SQL with every value bound as a parameter.
+
+use crate::error::ProtocolError;
+use std::{
+    fmt,
+    iter::Peekable,
+    str::{Chars, FromStr},
+    vec::IntoIter,
+};
+
+/// Longest expression accepted, in characters
+pub const MAX_QUERY_LENGTH: usize = 1024;
+
+/// Most terms an expression may contain
+pub const MAX_TERMS: usize = 32;
+
+/// Deepest nesting of parentheses and negations accepted
+pub const MAX_DEPTH: usize = 16;
+
+/// A parsed search expression.
+///
+/// # Examples
+///
+/// ```
+/// use sdrtrunk_protocol::call_query::{CallPredicate, CallQuery, NumberRange};
+///
+/// let query: CallQuery = "tg:1234..1299 pursuit".parse().unwrap();
+/// assert_eq!(
+///     query,
+///     CallQuery::And(
+///         Box::new(CallQuery::Term(CallPredicate::Talkgroup(NumberRange {
+///             min: Some(1234),
+///             max: Some(1299),
+///         }))),
+///         Box::new(CallQuery::Term(CallPredicate::Text("pursuit".to_string()))),
+///     )
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallQuery {
+    /// Both sides match
+    And(Box<Self>, Box<Self>),
+    /// Either side matches
+    Or(Box<Self>, Box<Self>),
+    /// The inner expression does not match
+    Not(Box<Self>),
+    /// A single field comparison
+    Term(CallPredicate),
+}
+
+/// One field comparison in a [`CallQuery`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallPredicate {
+    /// System ID
+    System(TextMatch),
+    /// Talkgroup ID
+    Talkgroup(NumberRange),
+    /// Source radio ID
+    Radio(NumberRange),
+    /// Frequency in Hz
+    Frequency(NumberRange),
+    /// Call duration in seconds
+    Duration(NumberRange),
+    /// Transcription status (lowercase)
+    Status(String),
+    /// Call tag (lowercase)
+    Tag(String),
+    /// Transcription language
+    Language(String),
+    /// Talkgroup label
+    Label(TextMatch),
+    /// Text the transcript or summary contains
+    Text(String),
+}
+
+/// How a text field is compared, ignoring case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextMatch {
+    /// The field equals the text
+    Is(String),
+    /// The field contains the text
+    Contains(String),
+}
+
+/// Inclusive numeric range; a missing bound is open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberRange {
+    /// Smallest value matched
+    pub min: Option<i64>,
+    /// Largest value matched
+    pub max: Option<i64>,
+}
+
+impl CallQuery {
+    /// Number of terms in the expression
+    #[must_use]
+    pub fn terms(&self) -> usize {
+        match self {
+            Self::And(left, right) | Self::Or(left, right) => left.terms() + right.terms(),
+            Self::Not(inner) => inner.terms(),
+            Self::Term(_) => 1,
+        }
+    }
+}
+
+impl FromStr for CallQuery {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().count() > MAX_QUERY_LENGTH {
+            return Err(invalid(format!(
+                "longer than {MAX_QUERY_LENGTH} characters"
+            )));
+        }
+        let tokens = tokenize(s)?;
+        if tokens.is_empty() {
+            return Err(invalid("empty search".to_string()));
+        }
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+            depth: 0,
+            terms: 0,
+        };
+        let query = parser.parse_or()?;
+        match parser.tokens.next() {
+            None => Ok(query),
+            Some(Token::Close) => Err(invalid("unmatched `)`".to_string())),
+            Some(token) => Err(invalid(format!("unexpected {token}"))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tokens
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    /// Bare word or quoted phrase
+    Text(String),
+    /// `field:value` or `field~value`
+    Field {
+        name: String,
+        contains: bool,
+        value: String,
+    },
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("`(`"),
+            Self::Close => f.write_str("`)`"),
+            Self::And => f.write_str("`AND`"),
+            Self::Or => f.write_str("`OR`"),
+            Self::Not => f.write_str("`NOT`"),
+            Self::Text(text) => write!(f, "`{text}`"),
+            Self::Field {
+                name,
+                contains,
+                value,
+            } => write!(f, "`{name}{}{value}`", if *contains { '~' } else { ':' }),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ProtocolError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                let _ = chars.next();
+            }
+            '(' => {
+                let _ = chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                let _ = chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' => tokens.push(Token::Text(quoted(&mut chars)?)),
+            '-' => {
+                let _ = chars.next();
+                if chars.peek().is_none_or(|c| c.is_whitespace() || *c == ')') {
+                    tokens.push(Token::Text("-".to_string()));
+                } else {
+                    tokens.push(Token::Not);
+                }
+            }
+            _ => tokens.push(word(&mut chars)?),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read a bare word, keyword, or field term
+fn word(chars: &mut Peekable<Chars<'_>>) -> Result<Token, ProtocolError> {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || matches!(c, '(' | ')' | '"') {
+            break;
+        }
+        if matches!(c, ':' | '~') && !word.is_empty() && word.chars().all(char::is_alphabetic) {
+            let _ = chars.next();
+            let value = if chars.peek() == Some(&'"') {
+                quoted(chars)?
+            } else {
+                bare(chars)
+            };
+            return Ok(Token::Field {
+                name: word.to_lowercase(),
+                contains: c == '~',
+                value,
+            });
+        }
+        word.push(c);
+        let _ = chars.next();
+    }
+    Ok(match word.as_str() {
+        "AND" => Token::And,
+        "OR" => Token::Or,
+        "NOT" => Token::Not,
+        _ => Token::Text(word),
+    })
+}
+
+/// Read an unquoted value up to whitespace or a parenthesis
+fn bare(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut value = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || matches!(c, '(' | ')') {
+            break;
+        }
+        value.push(c);
+        let _ = chars.next();
+    }
+    value
+}
+
+/// Read a double-quoted value; `\"` and `\\` escape quotes and backslashes
+fn quoted(chars: &mut Peekable<Chars<'_>>) -> Result<String, ProtocolError> {
+    let _ = chars.next();
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(value),
+            Some('\\') => match chars.next() {
+                Some(escaped) => value.push(escaped),
+                None => break,
+            },
+            Some(c) => value.push(c),
+            None => break,
+        }
+    }
+    Err(invalid("unterminated quote".to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct Parser {
+    tokens: Peekable<IntoIter<Token>>,
+    depth: usize,
+    terms: usize,
+}
+
+impl Parser {
+    /// `and (OR and)*`
+    fn parse_or(&mut self) -> Result<CallQuery, ProtocolError> {
+        let mut query = self.parse_and()?;
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            let right = self.parse_and()?;
+            query = CallQuery::Or(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    /// `unary ([AND] unary)*`
+    fn parse_and(&mut self) -> Result<CallQuery, ProtocolError> {
+        let mut query = self.parse_unary()?;
+        loop {
+            let explicit = self.tokens.next_if_eq(&Token::And).is_some();
+            if !explicit && matches!(self.tokens.peek(), None | Some(Token::Or | Token::Close)) {
+                return Ok(query);
+            }
+            let right = self.parse_unary()?;
+            query = CallQuery::And(Box::new(query), Box::new(right));
+        }
+    }
+
+    /// `NOT unary | ( or ) | term`
+    fn parse_unary(&mut self) -> Result<CallQuery, ProtocolError> {
+        match self.tokens.next() {
+            Some(Token::Not) => {
+                self.enter()?;
+                let inner = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(CallQuery::Not(Box::new(inner)))
+            }
+            Some(Token::Open) => {
+                self.enter()?;
+                let inner = self.parse_or()?;
+                if self.tokens.next() != Some(Token::Close) {
+                    return Err(invalid("missing `)`".to_string()));
+                }
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Some(Token::Text(text)) if text.is_empty() => Err(invalid("empty phrase".to_string())),
+            Some(Token::Text(text)) => self.term(CallPredicate::Text(text)),
+            Some(Token::Field {
+                name,
+                contains,
+                value,
+            }) => self.term(predicate(&name, contains, value)?),
+            Some(token) => Err(invalid(format!("expected a search term, found {token}"))),
+            None => Err(invalid("expected a search term at the end".to_string())),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), ProtocolError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(invalid(format!("nested more than {MAX_DEPTH} levels deep")));
+        }
+        Ok(())
+    }
+
+    fn term(&mut self, predicate: CallPredicate) -> Result<CallQuery, ProtocolError> {
+        self.terms += 1;
+        if self.terms > MAX_TERMS {
+            return Err(invalid(format!("more than {MAX_TERMS} terms")));
+        }
+        Ok(CallQuery::Term(predicate))
+    }
+}
+
+/// Build the predicate for `name:value` (or `name~value` when `contains`)
+fn predicate(name: &str, contains: bool, value: String) -> Result<CallPredicate, ProtocolError> {
+    if value.is_empty() {
+        return Err(invalid(format!("missing value for `{name}`")));
+    }
+    let text_match = |value: String| {
+        if contains {
+            TextMatch::Contains(value)
+        } else {
+            TextMatch::Is(value)
+        }
+    };
+    match name {
+        "system" => return Ok(CallPredicate::System(text_match(value))),
+        "label" => return Ok(CallPredicate::Label(text_match(value))),
+        "text" => return Ok(CallPredicate::Text(value)),
+        _ => {}
+    }
+    if contains {
+        return Err(invalid(format!(
+            "`{name}` cannot be searched with `~`; use `{name}:`"
+        )));
+    }
+    Ok(match name {
+        "tg" | "talkgroup" => CallPredicate::Talkgroup(range(name, &value)?),
+        "radio" => CallPredicate::Radio(range(name, &value)?),
+        "freq" | "frequency" => CallPredicate::Frequency(range(name, &value)?),
+        "duration" => CallPredicate::Duration(range(name, &value)?),
+        "status" => CallPredicate::Status(value.to_lowercase()),
+        "tag" => CallPredicate::Tag(value.to_lowercase()),
+        "lang" | "language" => CallPredicate::Language(value),
+        _ => return Err(invalid(format!("unknown field `{name}`"))),
+    })
+}
+
+/// Parse `n`, `min..max`, `min..`, or `..max`
+fn range(name: &str, value: &str) -> Result<NumberRange, ProtocolError> {
+    let number = |bound: &str| -> Result<Option<i64>, ProtocolError> {
+        if bound.is_empty() {
+            return Ok(None);
+        }
+        bound
+            .parse()
+            .map(Some)
+            .map_err(|_| invalid(format!("`{name}` expects a number or range, got `{value}`")))
+    };
+    let range = match value.split_once("..") {
+        Some((min, max)) => NumberRange {
+            min: number(min)?,
+            max: number(max)?,
+        },
+        None => {
+            let exact = number(value)?;
+            NumberRange {
+                min: exact,
+                max: exact,
+            }
+        }
+    };
+    match (range.min, range.max) {
+        (None, None) => Err(invalid(format!("`{name}` range needs a bound"))),
+        (Some(min), Some(max)) if min > max => {
+            Err(invalid(format!("`{name}` range {min}..{max} is empty")))
+        }
+        _ => Ok(range),
+    }
+}
+
+fn invalid(detail: String) -> ProtocolError {
+    ProtocolError::FieldParse {
+        field: "q".to_string(),
+        detail,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> CallQuery {
+        s.parse().unwrap()
+    }
+
+    fn error(s: &str) -> String {
+        s.parse::<CallQuery>().unwrap_err().to_string()
+    }
+
+    fn term(predicate: CallPredicate) -> Box<CallQuery> {
+        Box::new(CallQuery::Term(predicate))
+    }
+
+    fn text(s: &str) -> CallPredicate {
+        CallPredicate::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(
+            parse(r#"system:butler AND tg:1234..1299 AND text~"pursuit""#),
+            CallQuery::And(
+                Box::new(CallQuery::And(
+                    term(CallPredicate::System(TextMatch::Is("butler".to_string()))),
+                    term(CallPredicate::Talkgroup(NumberRange {
+                        min: Some(1234),
+                        max: Some(1299),
+                    })),
+                )),
+                term(text("pursuit")),
+            )
+        );
+        assert_eq!(
+            parse("radio:..500"),
+            CallQuery::Term(CallPredicate::Radio(NumberRange {
+                min: None,
+                max: Some(500),
+            }))
+        );
+        assert_eq!(
+            parse("talkgroup:42"),
+            CallQuery::Term(CallPredicate::Talkgroup(NumberRange {
+                min: Some(42),
+                max: Some(42),
+            }))
+        );
+        assert_eq!(
+            parse("STATUS:Completed"),
+            CallQuery::Term(CallPredicate::Status("completed".to_string()))
+        );
+        assert_eq!(
+            parse(r#"label~"fire dispatch""#),
+            CallQuery::Term(CallPredicate::Label(TextMatch::Contains(
+                "fire dispatch".to_string()
+            )))
+        );
+        assert_eq!(
+            parse("lang:en"),
+            CallQuery::Term(CallPredicate::Language("en".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_plain_keywords() {
+        assert_eq!(parse("pursuit"), CallQuery::Term(text("pursuit")));
+        assert_eq!(
+            parse(r#""shots fired""#),
+            CallQuery::Term(text("shots fired"))
+        );
+        // Adjacent words must all match
+        assert_eq!(
+            parse("shots fired"),
+            CallQuery::And(term(text("shots")), term(text("fired")))
+        );
+        // Only capitalized keywords are operators, and only letters name fields
+        assert_eq!(
+            parse("rock or roll"),
+            CallQuery::And(
+                Box::new(CallQuery::And(term(text("rock")), term(text("or")))),
+                term(text("roll")),
+            )
+        );
+        assert_eq!(parse("10:45"), CallQuery::Term(text("10:45")));
+        assert_eq!(parse("10-4"), CallQuery::Term(text("10-4")));
+    }
+
+    #[test]
+    fn test_operators() {
+        // AND binds tighter than OR
+        assert_eq!(
+            parse("a OR b c"),
+            CallQuery::Or(
+                term(text("a")),
+                Box::new(CallQuery::And(term(text("b")), term(text("c")))),
+            )
+        );
+        assert_eq!(
+            parse("(a OR b) c"),
+            CallQuery::And(
+                Box::new(CallQuery::Or(term(text("a")), term(text("b")))),
+                term(text("c")),
+            )
+        );
+        assert_eq!(
+            parse("NOT tag:noise -fire"),
+            CallQuery::And(
+                Box::new(CallQuery::Not(term(CallPredicate::Tag(
+                    "noise".to_string()
+                )))),
+                Box::new(CallQuery::Not(term(text("fire")))),
+            )
+        );
+        assert_eq!(
+            parse(r#""say \"again\"""#),
+            CallQuery::Term(text(r#"say "again""#))
+        );
+        assert_eq!(parse("a OR b c").terms(), 3);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(error("").contains("empty search"));
+        assert!(error("   ").contains("empty search"));
+        assert!(error("unit:5").contains("unknown field `unit`"));
+        assert!(error("tg:abc").contains("expects a number"));
+        assert!(error("tg:1299..1234").contains("is empty"));
+        assert!(error("tg:..").contains("needs a bound"));
+        assert!(error("tg~12").contains("cannot be searched with `~`"));
+        assert!(error("system:").contains("missing value"));
+        assert!(error(r#"a """#).contains("empty phrase"));
+        assert!(error(r#"text~"pursuit"#).contains("unterminated quote"));
+        assert!(error("(a OR b").contains("missing `)`"));
+        assert!(error("a OR b)").contains("unmatched `)`"));
+        assert!(error("a AND").contains("at the end"));
+        assert!(error("OR a").contains("found `OR`"));
+        assert!(error("a AND OR b").contains("found `OR`"));
+        assert!(error("()").contains("found `)`"));
+    }
+
+    #[test]
+    fn test_limits() {
+        let terms = ["a"; MAX_TERMS].join(" ");
+        assert_eq!(parse(&terms).terms(), MAX_TERMS);
+        assert!(error(&format!("{terms} b")).contains("more than"));
+
+        let nested = format!("{}a{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert_eq!(parse(&nested), CallQuery::Term(text("a")));
+        assert!(error(&format!("({nested})")).contains("nested"));
+        assert!(error(&"NOT ".repeat(MAX_DEPTH + 1)).contains("nested"));
+
+        assert!(error(&"a".repeat(MAX_QUERY_LENGTH + 1)).contains("longer than"));
+    }
+}
@@ -8,6 +8,17 @@
 //! - **Configuration types**: [`Config`], `ServerConfig`, `DatabaseConfig`,
 //!   `StorageConfig`, `TranscriptionConfig`, etc.
 //! - **Protocol errors**: [`ProtocolError`] for serialization and format issues
+//! - **Alert matching**: [`alerts`] compiles keyword and regex alert rules and
+//!   finds them in transcripts
+//! - **Call searches**: [`call_query`] parses filter expressions such as
+//!   `system:butler AND tg:1234..1299 AND text~"pursuit"`
+//! - **Anonymization**: [`anonymize`] scrubs names, phone numbers, and
+//!   addresses from transcripts
+//! - **Post-processing**: [`postprocess`] restores punctuation, expands
+//!   ten-codes, and substitutes unit aliases in transcripts
+//! - **Schedules**: [`schedule`] parses cron expressions for periodic jobs
+//! - **Talkgroup imports**: [`talkgroups`] parses `SDRTrunk` playlists and
+//!   `RadioReference` CSV exports into talkgroup aliases
 //! - **Type re-exports**: [`types`] module re-exports the validated types layer
 //!
 //! # Design
@@ -15,8 +26,14 @@
 //! No async, no I/O, no database — pure data structures and validation.
 //! Configuration loading (file/env) happens in the binary crates, not here.
 
+pub mod alerts;
+pub mod anonymize;
+pub mod call_query;
 pub mod config;
 pub mod error;
+pub mod postprocess;
+pub mod schedule;
+pub mod talkgroups;
 
 pub use config::Config;
 pub use error::ProtocolError;
@@ -0,0 +1,411 @@
+//! Transcript post-processing.
+//!
+//! A [`PostProcessorChain`] runs a transcript through [`TranscriptPostProcessor`]s
+//! in turn. The built-in ones, selected in [`PostProcessingConfig`], restore
+//! sentence punctuation, expand ten-codes, and substitute unit aliases; other
+//! processors can be appended with [`PostProcessorChain::push`].
+
+use crate::alerts::{MAX_PATTERN_LEN, keyword_regex};
+use crate::config::{PostProcessingConfig, PostProcessorKind};
+use crate::error::{ProtocolError, Result};
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Ten-codes as Whisper writes them, "10-4" or "10 4".
+const TEN_CODE: &str = r"\b10[-\s](\d{1,3})\b";
+
+/// Punctuation written without a space before it.
+const CLOSING_PUNCTUATION: [char; 6] = [',', '.', ';', ':', '?', '!'];
+
+/// Characters ending a sentence.
+const SENTENCE_END: [char; 4] = ['.', '?', '!', '…'];
+
+/// APCO ten-codes in common use, by number.
+const APCO_TEN_CODES: &[(&str, &str)] = &[
+    ("1", "poor reception"),
+    ("2", "good reception"),
+    ("3", "stop transmitting"),
+    ("4", "acknowledged"),
+    ("5", "relay"),
+    ("6", "busy"),
+    ("7", "out of service"),
+    ("8", "in service"),
+    ("9", "repeat"),
+    ("10", "fight in progress"),
+    ("19", "return to station"),
+    ("20", "location"),
+    ("22", "disregard"),
+    ("23", "arrived at scene"),
+    ("33", "emergency"),
+    ("76", "en route"),
+    ("97", "arrived at scene"),
+];
+
+/// A step applied to transcript text before it is stored.
+pub trait TranscriptPostProcessor: Send + Sync + std::fmt::Debug {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// `text` after processing, borrowed when nothing changed
+    fn process<'t>(&self, text: &'t str) -> Cow<'t, str>;
+}
+
+/// Post-processors run in order.
+#[derive(Debug, Default)]
+pub struct PostProcessorChain {
+    processors: Vec<Box<dyn TranscriptPostProcessor>>,
+}
+
+impl PostProcessorChain {
+    /// The built-in post-processors listed in `config.chain`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::InvalidFormat`] if a ten-code is not written
+    /// as `10-` and up to three digits, or a unit designation is empty or
+    /// too long.
+    pub fn from_config(config: &PostProcessingConfig) -> Result<Self> {
+        let mut chain = Self::default();
+        for kind in &config.chain {
+            match kind {
+                PostProcessorKind::Punctuation => chain.push(Box::new(Punctuation)),
+                PostProcessorKind::TenCodes => {
+                    chain.push(Box::new(TenCodes::new(&config.ten_codes)?));
+                }
+                PostProcessorKind::UnitAliases => {
+                    chain.push(Box::new(UnitAliases::new(&config.unit_aliases)?));
+                }
+            }
+        }
+        Ok(chain)
+    }
+
+    /// Append `processor` to the end of the chain.
+    pub fn push(&mut self, processor: Box<dyn TranscriptPostProcessor>) {
+        self.processors.push(processor);
+    }
+
+    /// Whether the chain leaves text unchanged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    /// Names of the processors, in order.
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        self.processors.iter().map(|p| p.name()).collect()
+    }
+
+    /// `text` after every processor in turn.
+    #[must_use]
+    pub fn process<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        self.processors
+            .iter()
+            .fold(Cow::Borrowed(text), |text, processor| {
+                match processor.process(&text) {
+                    Cow::Borrowed(_) => text,
+                    Cow::Owned(processed) => Cow::Owned(processed),
+                }
+            })
+    }
+}
+
+/// Tidies spacing, capitalizes sentences, and ends the text with a full stop.
+#[derive(Debug, Clone, Copy)]
+pub struct Punctuation;
+
+impl TranscriptPostProcessor for Punctuation {
+    fn name(&self) -> &'static str {
+        "punctuation"
+    }
+
+    fn process<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut punctuated = String::with_capacity(text.len() + 1);
+        let mut sentence_start = true;
+        for word in text.split_whitespace() {
+            if !punctuated.is_empty() && !word.starts_with(CLOSING_PUNCTUATION) {
+                punctuated.push(' ');
+            }
+            for c in word.chars() {
+                if sentence_start && c.is_alphabetic() {
+                    punctuated.extend(c.to_uppercase());
+                    sentence_start = false;
+                } else {
+                    punctuated.push(c);
+                    if SENTENCE_END.contains(&c) {
+                        sentence_start = true;
+                    } else if c.is_alphanumeric() {
+                        sentence_start = false;
+                    }
+                }
+            }
+        }
+        if punctuated
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, ')' | '"' | '\''))
+        {
+            punctuated.push('.');
+        }
+
+        if punctuated == text {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(punctuated)
+        }
+    }
+}
+
+/// Follows ten-codes with their meaning, e.g. "10-4 (acknowledged)".
+#[derive(Debug, Clone)]
+pub struct TenCodes {
+    pattern: Regex,
+    meanings: HashMap<String, String>,
+}
+
+impl TenCodes {
+    /// The APCO ten-codes, with `codes` added or replacing them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::InvalidFormat`] if a code is not written as
+    /// `10-` and up to three digits.
+    pub fn new(codes: &HashMap<String, String>) -> Result<Self> {
+        let mut meanings: HashMap<String, String> = APCO_TEN_CODES
+            .iter()
+            .map(|&(number, meaning)| (number.to_string(), meaning.to_string()))
+            .collect();
+        for (code, meaning) in codes {
+            let number = code
+                .trim()
+                .strip_prefix("10-")
+                .filter(|n| (1..=3).contains(&n.len()) && n.bytes().all(|b| b.is_ascii_digit()))
+                .ok_or_else(|| invalid(format!("'{code}' is not a ten-code such as 10-4")))?;
+            let _previous = meanings.insert(number.to_string(), meaning.trim().to_string());
+        }
+        let pattern =
+            Regex::new(TEN_CODE).map_err(|e| invalid(format!("invalid ten-code pattern: {e}")))?;
+        Ok(Self { pattern, meanings })
+    }
+}
+
+impl TranscriptPostProcessor for TenCodes {
+    fn name(&self) -> &'static str {
+        "ten_codes"
+    }
+
+    fn process<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        self.pattern.replace_all(text, |captures: &Captures<'_>| {
+            let matched = captures.get(0).map_or("", |m| m.as_str());
+            captures
+                .get(1)
+                .and_then(|number| {
+                    let number = number.as_str();
+                    self.meanings
+                        .get(number)
+                        .map(|meaning| format!("10-{number} ({meaning})"))
+                })
+                .unwrap_or_else(|| matched.to_string())
+        })
+    }
+}
+
+/// Replaces unit designations with their configured alias.
+///
+/// All designations are matched in one pass, so an alias is never itself
+/// substituted.
+#[derive(Debug, Clone)]
+pub struct UnitAliases {
+    /// Every designation, longest first; `None` when there are none
+    pattern: Option<Regex>,
+    /// Aliases by normalized designation
+    aliases: HashMap<String, String>,
+}
+
+impl UnitAliases {
+    /// Aliases for the designations in `aliases`, preferring the longest
+    /// match so "Engine 1 Alpha" wins over "Engine 1".
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::InvalidFormat`] if a designation is empty or
+    /// longer than [`MAX_PATTERN_LEN`] bytes.
+    pub fn new(aliases: &HashMap<String, String>) -> Result<Self> {
+        let mut designations = Vec::with_capacity(aliases.len());
+        for designation in aliases.keys() {
+            let designation = designation.trim();
+            if designation.is_empty() || designation.len() > MAX_PATTERN_LEN {
+                return Err(invalid(format!(
+                    "unit designation must be 1 to {MAX_PATTERN_LEN} bytes"
+                )));
+            }
+            designations.push(designation);
+        }
+        designations.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let pattern = if designations.is_empty() {
+            None
+        } else {
+            let alternatives: Vec<String> = designations
+                .iter()
+                .map(|designation| format!("(?:{})", keyword_regex(designation)))
+                .collect();
+            Some(
+                Regex::new(&alternatives.join("|"))
+                    .map_err(|e| invalid(format!("invalid unit designation: {e}")))?,
+            )
+        };
+        let aliases = aliases
+            .iter()
+            .map(|(designation, alias)| (normalize(designation), alias.clone()))
+            .collect();
+        Ok(Self { pattern, aliases })
+    }
+}
+
+impl TranscriptPostProcessor for UnitAliases {
+    fn name(&self) -> &'static str {
+        "unit_aliases"
+    }
+
+    fn process<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let Some(pattern) = &self.pattern else {
+            return Cow::Borrowed(text);
+        };
+        pattern.replace_all(text, |captures: &Captures<'_>| {
+            let matched = captures.get(0).map_or("", |m| m.as_str());
+            self.aliases
+                .get(&normalize(matched))
+                .map_or_else(|| matched.to_string(), Clone::clone)
+        })
+    }
+}
+
+/// A designation lowercased with single spaces, as matched case-insensitively.
+fn normalize(designation: &str) -> String {
+    designation
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+const fn invalid(reason: String) -> ProtocolError {
+    ProtocolError::InvalidFormat { reason }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn aliases(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_punctuation() {
+        assert_eq!(
+            Punctuation.process("  engine 5 on scene .  requesting   ems? copy"),
+            "Engine 5 on scene. Requesting ems? Copy."
+        );
+        assert_eq!(Punctuation.process("¿dónde está"), "¿Dónde está.");
+        assert!(matches!(
+            Punctuation.process("Engine 5 on scene."),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(Punctuation.process(""), "");
+    }
+
+    #[test]
+    fn test_ten_codes() {
+        let codes =
+            TenCodes::new(&aliases(&[("10-50", "traffic accident"), ("10-4", "ok")])).unwrap();
+        assert_eq!(
+            codes.process("10-4, en route to a 10 50, 10-99 on file"),
+            "10-4 (ok), en route to a 10-50 (traffic accident), 10-99 on file"
+        );
+        // Numbers that only start with 10 are left alone
+        assert!(matches!(
+            codes.process("unit 1050 at 104 Main"),
+            Cow::Borrowed(_)
+        ));
+
+        assert!(TenCodes::new(&aliases(&[("code 3", "lights and siren")])).is_err());
+        assert!(TenCodes::new(&aliases(&[("10-1000", "too long")])).is_err());
+    }
+
+    #[test]
+    fn test_unit_aliases() {
+        let units = UnitAliases::new(&aliases(&[
+            ("Engine 1", "E1 (Station 1)"),
+            ("engine 1 alpha", "E1A (Station 1)"),
+            ("Medic 12", "M12 ($5 unit)"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            units.process("ENGINE 1 and Engine 1  Alpha respond, medic 12 and engine 10 stage"),
+            "E1 (Station 1) and E1A (Station 1) respond, M12 ($5 unit) and engine 10 stage"
+        );
+        assert!(UnitAliases::new(&aliases(&[(" ", "blank")])).is_err());
+    }
+
+    #[test]
+    fn test_chain_runs_in_order() {
+        let config = PostProcessingConfig {
+            chain: vec![
+                PostProcessorKind::UnitAliases,
+                PostProcessorKind::TenCodes,
+                PostProcessorKind::Punctuation,
+            ],
+            ten_codes: HashMap::new(),
+            unit_aliases: aliases(&[("medic 12", "Medic 12 (Station 3)")]),
+        };
+        let chain = PostProcessorChain::from_config(&config).unwrap();
+        assert_eq!(chain.names(), ["unit_aliases", "ten_codes", "punctuation"]);
+        assert_eq!(
+            chain.process("medic 12 10 97"),
+            "Medic 12 (Station 3) 10-97 (arrived at scene)."
+        );
+
+        let empty = PostProcessorChain::from_config(&PostProcessingConfig::default()).unwrap();
+        assert!(empty.is_empty());
+        assert!(matches!(empty.process("10-4"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_custom_processor() {
+        #[derive(Debug)]
+        struct Shout;
+
+        impl TranscriptPostProcessor for Shout {
+            fn name(&self) -> &'static str {
+                "shout"
+            }
+
+            fn process<'t>(&self, text: &'t str) -> Cow<'t, str> {
+                Cow::Owned(text.to_uppercase())
+            }
+        }
+
+        let mut chain = PostProcessorChain::from_config(&PostProcessingConfig {
+            chain: vec![PostProcessorKind::Punctuation],
+            ..PostProcessingConfig::default()
+        })
+        .unwrap();
+        chain.push(Box::new(Shout));
+        assert_eq!(chain.process("copy that"), "COPY THAT.");
+    }
+}
@@ -0,0 +1,340 @@
+//! Cron-style schedules for periodic jobs.
+//!
+//! A [`CronSchedule`] is a standard five-field cron expression
+//! (`minute hour day-of-month month day-of-week`), evaluated in UTC. Each
+//! field accepts `*`, numbers, ranges (`1-5`), lists (`1,15`), and steps
+//! (`*/15`, `0-30/10`). Day of week runs 0–7 with both 0 and 7 meaning
+//! Sunday. As in cron, when both day fields are restricted a day matching
+//! either one qualifies. The shorthands `@hourly`, `@daily`, `@weekly`,
+//! `@monthly`, and `@yearly` are also accepted.
+
+use crate::error::ProtocolError;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// How far ahead [`CronSchedule::next_after`] searches before giving up
+const SEARCH_YEARS: i32 = 5;
+
+/// A parsed cron expression.
+///
+/// # Examples
+///
+/// ```
+/// use sdrtrunk_protocol::schedule::CronSchedule;
+///
+/// let schedule: CronSchedule = "30 3 * * *".parse().unwrap();
+/// let now = "2025-01-01T12:00:00Z".parse().unwrap();
+/// let next = schedule.next_after(now).unwrap();
+/// assert_eq!(next.to_rfc3339(), "2025-01-02T03:30:00+00:00");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronSchedule {
+    /// The expression as written
+    expression: String,
+    /// Bit `n` set when minute `n` matches
+    minutes: u64,
+    /// Bit `n` set when hour `n` matches
+    hours: u64,
+    /// Bit `n` set when day of month `n` matches
+    days: u64,
+    /// Bit `n` set when month `n` matches
+    months: u64,
+    /// Bit `n` set when day of week `n` (0 = Sunday) matches
+    weekdays: u64,
+    /// Whether the day-of-month field was restricted
+    days_restricted: bool,
+    /// Whether the day-of-week field was restricted
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    /// The expression as written.
+    #[must_use]
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The first matching minute strictly after `after`.
+    ///
+    /// Returns `None` if nothing matches within the next five years, e.g.
+    /// for `0 0 31 2 *`.
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let last_year = start.year() + SEARCH_YEARS;
+        let mut date = start.date_naive();
+        let mut time = start.time();
+
+        while date.year() <= last_year {
+            if !matches(self.months, date.month()) {
+                date = first_of_next_month(date)?;
+                time = NaiveTime::MIN;
+                continue;
+            }
+            if !self.matches_day(date) {
+                date = date.succ_opt()?;
+                time = NaiveTime::MIN;
+                continue;
+            }
+            if let Some(found) = self.first_time_from(time) {
+                return Some(date.and_time(found).and_utc());
+            }
+            date = date.succ_opt()?;
+            time = NaiveTime::MIN;
+        }
+        None
+    }
+
+    /// Whether `date` matches the day-of-month and day-of-week fields
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = matches(self.days, date.day());
+        let weekday = matches(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+
+    /// The first matching time of day at or after `from`
+    fn first_time_from(&self, from: NaiveTime) -> Option<NaiveTime> {
+        (from.hour()..24)
+            .filter(|&hour| matches(self.hours, hour))
+            .find_map(|hour| {
+                let first_minute = if hour == from.hour() {
+                    from.minute()
+                } else {
+                    0
+                };
+                (first_minute..60)
+                    .find(|&minute| matches(self.minutes, minute))
+                    .and_then(|minute| NaiveTime::from_hms_opt(hour, minute, 0))
+            })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let expanded = match expression {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(invalid(format!(
+                "expected 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            )));
+        };
+
+        let mut weekdays = parse_field(weekday, "day of week", 0, 7)?;
+        // 7 is another name for Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days: parse_field(day, "day of month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = ProtocolError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CronSchedule> for String {
+    fn from(schedule: CronSchedule) -> Self {
+        schedule.expression
+    }
+}
+
+/// Whether bit `value` is set in `mask`
+const fn matches(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// The first day of the month after `date`'s
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+/// Parse one cron field into a bitmask of the values it matches
+///
+/// # Errors
+///
+/// Returns error if a value is malformed or outside `min..=max`
+fn parse_field(text: &str, name: &str, min: u32, max: u32) -> Result<u64, ProtocolError> {
+    let mut mask = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, parse_number(step, name)?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid(format!("{name} step must be at least 1")));
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_number(start, name)?, parse_number(end, name)?)
+        } else {
+            let start = parse_number(range, name)?;
+            // `5/15` runs from 5 to the end of the range
+            (start, if step > 1 { max } else { start })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(invalid(format!("{name} {range} is outside {min}-{max}")));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// Parse one number in a cron field
+///
+/// # Errors
+///
+/// Returns error if `text` is not a number
+fn parse_number(text: &str, name: &str) -> Result<u32, ProtocolError> {
+    text.parse()
+        .map_err(|_| invalid(format!("invalid {name} value: {text:?}")))
+}
+
+fn invalid(detail: String) -> ProtocolError {
+    ProtocolError::FieldParse {
+        field: "schedule".to_string(),
+        detail,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        expression
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn test_next_after() {
+        assert_eq!(
+            next("* * * * *", "2025-01-01T12:00:30Z"),
+            "2025-01-01 12:01"
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2025-01-01T12:15:00Z"),
+            "2025-01-01 12:30"
+        );
+        assert_eq!(
+            next("0 3 * * *", "2025-01-01T03:00:00Z"),
+            "2025-01-02 03:00"
+        );
+        assert_eq!(
+            next("0 0 1 * *", "2025-12-15T00:00:00Z"),
+            "2026-01-01 00:00"
+        );
+        assert_eq!(
+            next("30 2 * * 1-5", "2025-01-03T03:00:00Z"),
+            "2025-01-06 02:30"
+        );
+        assert_eq!(
+            next("0 0 29 2 *", "2025-01-01T00:00:00Z"),
+            "2028-02-29 00:00"
+        );
+        assert_eq!(next("@weekly", "2025-01-01T00:00:00Z"), "2025-01-05 00:00");
+        assert_eq!(
+            next("0 12 * * 7", "2025-01-01T00:00:00Z"),
+            "2025-01-05 12:00"
+        );
+    }
+
+    #[test]
+    fn test_restricted_day_fields_match_either() {
+        // The 10th (a Friday) or any Monday
+        assert_eq!(
+            next("0 0 10 * 1", "2025-01-07T00:00:00Z"),
+            "2025-01-10 00:00"
+        );
+        assert_eq!(
+            next("0 0 10 * 1", "2025-01-10T00:00:00Z"),
+            "2025-01-13 00:00"
+        );
+    }
+
+    #[test]
+    fn test_impossible_schedule() {
+        let schedule: CronSchedule = "0 0 31 2 *".parse().unwrap();
+        assert_eq!(schedule.next_after(at("2025-01-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(expression.parse::<CronSchedule>().is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let schedule: CronSchedule = serde_json::from_str("\"15 4 * * 0\"").unwrap();
+        assert_eq!(schedule.expression(), "15 4 * * 0");
+        assert_eq!(serde_json::to_string(&schedule).unwrap(), "\"15 4 * * 0\"");
+        assert!(serde_json::from_str::<CronSchedule>("\"every day\"").is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Talkgroup alias import formats.
+//!
+//! Parses talkgroup names from the two formats users usually already have:
+//!
+//! - **`SDRTrunk` playlist XML**: `<alias>` entries with a `talkgroup` `<id>`.
+//!   The alias `name` becomes the label and its `group` the group.
+//! - **`RadioReference` CSV**: the talkgroup export of a trunked system
+//!   (`Decimal`, `Alpha Tag`, `Description`, `Tag`, `Category` columns).
+//!
+//! Entries that cannot be used (talkgroup ranges, non-talkgroup ids, rows
+//! without a valid decimal ID) are counted as skipped rather than failing the
+//! whole import. When a talkgroup appears more than once the last entry wins.
+
+use crate::error::{ProtocolError, Result};
+use sdrtrunk_types::TalkgroupId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Friendly names for one talkgroup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TalkgroupAlias {
+    /// Talkgroup the names apply to.
+    pub talkgroup_id: TalkgroupId,
+    /// Display name (playlist alias name / `RadioReference` alpha tag).
+    pub label: Option<String>,
+    /// Group (playlist alias group / `RadioReference` category).
+    pub group: Option<String>,
+    /// Service tag, e.g. `Law Dispatch` (`RadioReference` only).
+    pub tag: Option<String>,
+    /// Longer description (`RadioReference` only).
+    pub description: Option<String>,
+}
+
+/// Source format of a talkgroup import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// `SDRTrunk` playlist XML.
+    Playlist,
+    /// `RadioReference` talkgroup CSV.
+    RadioReferenceCsv,
+}
+
+impl ImportFormat {
+    /// Guess the format from a file name, falling back to the content.
+    #[must_use]
+    pub fn detect(filename: Option<&str>, content: &str) -> Self {
+        let extension = filename
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("xml") => Self::Playlist,
+            Some("csv") => Self::RadioReferenceCsv,
+            _ if content.trim_start().starts_with('<') => Self::Playlist,
+            _ => Self::RadioReferenceCsv,
+        }
+    }
+
+    /// Short name used in logs and the `talkgroups.source` column.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Playlist => "playlist",
+            Self::RadioReferenceCsv => "radioreference_csv",
+        }
+    }
+}
+
+/// Aliases parsed from an import file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedTalkgroups {
+    /// Parsed aliases, one per talkgroup, ordered by talkgroup ID.
+    pub aliases: Vec<TalkgroupAlias>,
+    /// Entries that were ignored.
+    pub skipped: usize,
+}
+
+/// Parse an import file in the given format.
+///
+/// `alias_list` restricts a playlist to one alias list; it is ignored for CSV.
+///
+/// # Errors
+///
+/// Returns [`ProtocolError::InvalidFormat`] if the file is not well-formed or
+/// lacks the required structure.
+pub fn parse(
+    format: ImportFormat,
+    content: &str,
+    alias_list: Option<&str>,
+) -> Result<ParsedTalkgroups> {
+    match format {
+        ImportFormat::Playlist => parse_playlist(content, alias_list),
+        ImportFormat::RadioReferenceCsv => parse_radioreference_csv(content),
+    }
+}
+
+/// Parse talkgroup aliases from an `SDRTrunk` playlist.
+///
+/// # Errors
+///
+/// Returns [`ProtocolError::InvalidFormat`] if the XML is malformed or the
+/// root element is not `<playlist>`.
+pub fn parse_playlist(xml: &str, alias_list: Option<&str>) -> Result<ParsedTalkgroups> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| ProtocolError::InvalidFormat {
+        reason: format!("invalid playlist XML: {e}"),
+    })?;
+    let root = document.root_element();
+    if !root.has_tag_name("playlist") {
+        return Err(ProtocolError::InvalidFormat {
+            reason: format!(
+                "expected <playlist> root element, found <{}>",
+                root.tag_name().name()
+            ),
+        });
+    }
+
+    let mut aliases = BTreeMap::new();
+    let mut skipped = 0;
+    for alias in root.children().filter(|n| n.has_tag_name("alias")) {
+        if alias_list.is_some_and(|list| alias.attribute("list") != Some(list)) {
+            continue;
+        }
+        let label = non_empty(alias.attribute("name"));
+        let group = non_empty(alias.attribute("group"));
+
+        for id in alias.children().filter(|n| n.has_tag_name("id")) {
+            let talkgroup = match id.attribute("type") {
+                Some("talkgroup") => id.attribute("value").and_then(parse_talkgroup_id),
+                // Ranges, radio IDs, priorities, and other id types carry no talkgroup name
+                Some("talkgroupRange") => None,
+                _ => continue,
+            };
+            let Some(talkgroup_id) = talkgroup else {
+                skipped += 1;
+                continue;
+            };
+            let _ = aliases.insert(
+                talkgroup_id,
+                TalkgroupAlias {
+                    talkgroup_id,
+                    label: label.clone(),
+                    group: group.clone(),
+                    tag: None,
+                    description: None,
+                },
+            );
+        }
+    }
+
+    Ok(ParsedTalkgroups {
+        aliases: aliases.into_values().collect(),
+        skipped,
+    })
+}
+
+/// Parse talkgroup aliases from a `RadioReference` CSV export.
+///
+/// Columns are matched by header name, case-insensitively, so both the
+/// current export and older layouts (`DEC`, `Group`) are accepted.
+///
+/// # Errors
+///
+/// Returns [`ProtocolError::InvalidFormat`] if the CSV is malformed or has no
+/// decimal talkgroup column.
+pub fn parse_radioreference_csv(csv: &str) -> Result<ParsedTalkgroups> {
+    let invalid = |e: csv::Error| ProtocolError::InvalidFormat {
+        reason: format!("invalid talkgroup CSV: {e}"),
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+    let headers = reader.headers().map_err(invalid)?.clone();
+    let column = |names: &[&str]| {
+        headers
+            .iter()
+            .position(|h| names.iter().any(|name| h.eq_ignore_ascii_case(name)))
+    };
+
+    let Some(decimal) = column(&["Decimal", "DEC", "Talkgroup", "TGID"]) else {
+        return Err(ProtocolError::InvalidFormat {
+            reason: "talkgroup CSV has no Decimal column".to_string(),
+        });
+    };
+    let label = column(&["Alpha Tag", "AlphaTag", "Alias"]);
+    let description = column(&["Description"]);
+    let tag = column(&["Tag"]);
+    let group = column(&["Category", "Group"]);
+
+    let mut aliases = BTreeMap::new();
+    let mut skipped = 0;
+    for record in reader.records() {
+        let record = record.map_err(invalid)?;
+        let field = |index: Option<usize>| non_empty(index.and_then(|i| record.get(i)));
+
+        let Some(talkgroup_id) = record.get(decimal).and_then(parse_talkgroup_id) else {
+            skipped += 1;
+            continue;
+        };
+        let _ = aliases.insert(
+            talkgroup_id,
+            TalkgroupAlias {
+                talkgroup_id,
+                label: field(label),
+                group: field(group),
+                tag: field(tag),
+                description: field(description),
+            },
+        );
+    }
+
+    Ok(ParsedTalkgroups {
+        aliases: aliases.into_values().collect(),
+        skipped,
+    })
+}
+
+fn parse_talkgroup_id(value: &str) -> Option<TalkgroupId> {
+    value.trim().parse().ok()
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    const PLAYLIST: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<playlist version="4">
+  <alias color="-16777216" group="Fire" list="Metro" name="Fire Dispatch">
+    <id type="talkgroup" value="1001" protocol="APCO25"/>
+    <id type="priority" priority="1"/>
+  </alias>
+  <alias group="Police" list="Metro" name="PD Main">
+    <id type="talkgroup" value="2001" protocol="APCO25"/>
+    <id type="talkgroup" value="2002" protocol="APCO25"/>
+  </alias>
+  <alias group="" list="Metro" name="Interop">
+    <id type="talkgroupRange" min="9000" max="9010" protocol="APCO25"/>
+    <id type="radio" value="123456" protocol="APCO25"/>
+  </alias>
+  <alias group="County" list="County" name="County Fire">
+    <id type="talkgroup" value="1001" protocol="APCO25"/>
+  </alias>
+  <channel name="Metro Control" system="Metro" site="Site 1">
+    <alias_list_name>Metro</alias_list_name>
+  </channel>
+</playlist>"#;
+
+    const RADIOREFERENCE_CSV: &str = "\
+Decimal,Hex,Alpha Tag,Mode,Description,Tag,Category
+1001,3e9,FD Disp,D,Fire Dispatch,Fire Dispatch,Fire
+2001,7d1,PD Main,DE,Police Main,Law Dispatch,\"Police, City\"
+,,Blank,D,Missing ID,,
+-5,,Bad,D,Negative,,
+";
+
+    #[test]
+    fn test_parse_playlist() {
+        let parsed = parse_playlist(PLAYLIST, None).unwrap();
+
+        assert_eq!(parsed.skipped, 1);
+        let ids: Vec<i32> = parsed
+            .aliases
+            .iter()
+            .map(|a| a.talkgroup_id.as_i32())
+            .collect();
+        assert_eq!(ids, [1001, 2001, 2002]);
+
+        // The later County alias overrides Metro's for the shared talkgroup
+        assert_eq!(parsed.aliases[0].label.as_deref(), Some("County Fire"));
+        assert_eq!(parsed.aliases[1].group.as_deref(), Some("Police"));
+        assert_eq!(parsed.aliases[2].label.as_deref(), Some("PD Main"));
+    }
+
+    #[test]
+    fn test_parse_playlist_alias_list() {
+        let parsed = parse_playlist(PLAYLIST, Some("Metro")).unwrap();
+
+        assert_eq!(parsed.aliases.len(), 3);
+        assert_eq!(parsed.aliases[0].label.as_deref(), Some("Fire Dispatch"));
+        assert_eq!(parsed.aliases[0].group.as_deref(), Some("Fire"));
+    }
+
+    #[test]
+    fn test_parse_playlist_rejects_invalid() {
+        assert!(parse_playlist("<playlist>", None).is_err());
+        assert!(parse_playlist("<aliases/>", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_radioreference_csv() {
+        let parsed = parse_radioreference_csv(RADIOREFERENCE_CSV).unwrap();
+
+        assert_eq!(parsed.skipped, 2);
+        assert_eq!(parsed.aliases.len(), 2);
+        assert_eq!(
+            parsed.aliases[1],
+            TalkgroupAlias {
+                talkgroup_id: TalkgroupId::new(2001).unwrap(),
+                label: Some("PD Main".to_string()),
+                group: Some("Police, City".to_string()),
+                tag: Some("Law Dispatch".to_string()),
+                description: Some("Police Main".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_radioreference_csv_requires_decimal() {
+        assert!(parse_radioreference_csv("Hex,Alpha Tag\n3e9,FD Disp\n").is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            ImportFormat::detect(Some("Metro.XML"), ""),
+            ImportFormat::Playlist
+        );
+        assert_eq!(
+            ImportFormat::detect(Some("trs_tg_1234.csv"), "<"),
+            ImportFormat::RadioReferenceCsv
+        );
+        assert_eq!(ImportFormat::detect(None, PLAYLIST), ImportFormat::Playlist);
+        assert_eq!(
+            ImportFormat::detect(None, RADIOREFERENCE_CSV),
+            ImportFormat::RadioReferenceCsv
+        );
+    }
+}
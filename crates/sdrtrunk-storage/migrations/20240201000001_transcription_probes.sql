@@ -0,0 +1,15 @@
+-- Synthetic transcription probe results, one row per worker.
+-- Workers periodically transcribe a bundled clip and upsert the outcome here so
+-- the API can detect hung or broken transcription backends.
+CREATE TABLE IF NOT EXISTS transcription_probes (
+    worker_id VARCHAR(100) PRIMARY KEY,
+    probed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    success BOOLEAN NOT NULL,
+    latency_ms BIGINT NOT NULL,
+    transcript TEXT,
+    error TEXT,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcription_probes_probed_at
+    ON transcription_probes (probed_at DESC);
@@ -0,0 +1,15 @@
+-- Friendly talkgroup names imported from SDRTrunk playlists or RadioReference
+-- exports. Uploads fill missing call labels from here, so calls display names
+-- instead of raw talkgroup IDs.
+CREATE TABLE IF NOT EXISTS talkgroups (
+    system_id VARCHAR(50) NOT NULL,
+    talkgroup_id INTEGER NOT NULL,
+    label VARCHAR(255),
+    group_name VARCHAR(255),
+    tag VARCHAR(255),
+    description TEXT,
+    source VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (system_id, talkgroup_id)
+);
@@ -0,0 +1,40 @@
+-- Keyword alert rules and the alerts they raised. Completed transcriptions
+-- are checked against the enabled rules scoped to their system and talkgroup
+-- (NULL scope columns match any).
+CREATE TABLE IF NOT EXISTS alert_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    pattern TEXT NOT NULL,
+    pattern_type VARCHAR(20) NOT NULL DEFAULT 'keyword'
+        CHECK (pattern_type IN ('keyword', 'regex')),
+    system_id VARCHAR(50),
+    talkgroup_id INTEGER,
+    webhook_url TEXT,
+    email_to VARCHAR(255),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Alert history. Rows outlive their rule (rule_name keeps the label) and are
+-- removed with their call. A call raises at most one alert per rule, even when
+-- it is re-transcribed.
+CREATE TABLE IF NOT EXISTS alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule_id UUID REFERENCES alert_rules(id) ON DELETE SET NULL,
+    rule_name VARCHAR(255) NOT NULL,
+    call_id UUID NOT NULL REFERENCES radio_calls(id) ON DELETE CASCADE,
+    system_id VARCHAR(50) NOT NULL,
+    talkgroup_id INTEGER,
+    matched_text TEXT NOT NULL,
+    transcript TEXT NOT NULL,
+    webhook_status VARCHAR(20),
+    email_status VARCHAR(20),
+    delivery_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (rule_id, call_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_alerts_created_at ON alerts (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_alerts_system_created_at ON alerts (system_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_alerts_call_id ON alerts (call_id);
@@ -0,0 +1,7 @@
+-- SHA-256 of each call's uploaded audio, used to spot re-posted recordings.
+-- Duplicates are looked up within a system, so the index leads with it.
+ALTER TABLE radio_calls ADD COLUMN IF NOT EXISTS audio_sha256 CHAR(64);
+
+CREATE INDEX IF NOT EXISTS idx_radio_calls_audio_sha256
+    ON radio_calls (system_id, audio_sha256)
+    WHERE audio_sha256 IS NOT NULL;
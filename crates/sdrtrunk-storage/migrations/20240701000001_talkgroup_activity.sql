@@ -0,0 +1,11 @@
+-- Covering indexes for talkgroup activity analytics, which scan a window of
+-- calls (across all systems or within one) and only read these columns.
+CREATE INDEX IF NOT EXISTS idx_radio_calls_talkgroup_activity
+    ON radio_calls (call_timestamp DESC)
+    INCLUDE (system_id, talkgroup_id, duration_seconds)
+    WHERE talkgroup_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_radio_calls_system_talkgroup_activity
+    ON radio_calls (system_id, call_timestamp DESC)
+    INCLUDE (talkgroup_id, duration_seconds)
+    WHERE talkgroup_id IS NOT NULL;
@@ -0,0 +1,9 @@
+-- Waveform peaks for the web player, one row per call. Generated from the
+-- recording after upload (or on first request for older calls) and removed
+-- with the call.
+CREATE TABLE IF NOT EXISTS call_waveforms (
+    call_id UUID PRIMARY KEY REFERENCES radio_calls(id) ON DELETE CASCADE,
+    peaks JSONB NOT NULL,
+    duration_seconds DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,24 @@
+-- Conversations: runs of calls on one talkgroup where each call starts within
+-- the configured gap of the previous one ending. Calls join a conversation as
+-- they are uploaded; membership is removed with the call.
+CREATE TABLE IF NOT EXISTS conversations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id VARCHAR(50) NOT NULL,
+    talkgroup_id INTEGER NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversations_talkgroup_ended_at
+    ON conversations (system_id, talkgroup_id, ended_at DESC);
+CREATE INDEX IF NOT EXISTS idx_conversations_started_at ON conversations (started_at DESC);
+
+CREATE TABLE IF NOT EXISTS conversation_calls (
+    call_id UUID PRIMARY KEY REFERENCES radio_calls(id) ON DELETE CASCADE,
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_calls_conversation_id
+    ON conversation_calls (conversation_id);
@@ -0,0 +1,17 @@
+-- Corrections and ratings submitted for transcriptions. original_text keeps
+-- the transcript the feedback was given on, so later re-transcriptions do not
+-- change what was corrected. Removed with the call.
+CREATE TABLE IF NOT EXISTS transcription_feedback (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    call_id UUID NOT NULL REFERENCES radio_calls(id) ON DELETE CASCADE,
+    original_text TEXT,
+    corrected_text TEXT,
+    rating SMALLINT CHECK (rating BETWEEN 1 AND 5),
+    comment TEXT,
+    api_key_id VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (corrected_text IS NOT NULL OR rating IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcription_feedback_call_id ON transcription_feedback (call_id);
+CREATE INDEX IF NOT EXISTS idx_transcription_feedback_created_at ON transcription_feedback (created_at DESC);
@@ -0,0 +1,16 @@
+-- Upload-only credentials for SDRTrunk instances. Each key is bound to one
+-- system and is refused everywhere except the upload endpoints, so recorders
+-- no longer need a full API key. Only the SHA-256 hash of the key is stored.
+CREATE TABLE IF NOT EXISTS ingest_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    system_id VARCHAR(50) NOT NULL,
+    description VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_used TIMESTAMPTZ,
+    total_uploads BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_ingest_keys_system_id ON ingest_keys (system_id);
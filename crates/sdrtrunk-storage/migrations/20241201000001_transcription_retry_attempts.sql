@@ -0,0 +1,9 @@
+-- Automatic retries of failed or stuck transcriptions per call. Workers
+-- re-queue a call at most `transcription.retry.max_attempts` times, backing
+-- off exponentially between attempts.
+ALTER TABLE radio_calls
+    ADD COLUMN IF NOT EXISTS transcription_retry_attempts INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_radio_calls_retryable
+    ON radio_calls (transcription_status, transcription_retry_attempts)
+    WHERE transcription_status IN ('failed', 'processing');
@@ -0,0 +1,9 @@
+-- Where calls were heard, in WGS 84 degrees. Set from the uploader's site
+-- coordinates or the system's configured location; both or neither.
+ALTER TABLE radio_calls
+    ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION;
+
+CREATE INDEX IF NOT EXISTS idx_radio_calls_located
+    ON radio_calls (call_timestamp DESC)
+    WHERE latitude IS NOT NULL AND longitude IS NOT NULL;
@@ -0,0 +1,7 @@
+-- Call listings page by (call_timestamp, id), newest first, resuming after
+-- the previous page's last call instead of skipping an offset.
+CREATE INDEX IF NOT EXISTS idx_radio_calls_timestamp_id
+    ON radio_calls (call_timestamp DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS idx_radio_calls_system_timestamp_id
+    ON radio_calls (system_id, call_timestamp DESC, id DESC);
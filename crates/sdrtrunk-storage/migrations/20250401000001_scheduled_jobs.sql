@@ -0,0 +1,15 @@
+-- Periodic background jobs (retention purges, statistics rollups, analyze)
+-- and their most recent run, so schedules can be inspected without reading
+-- the logs. Rows are keyed by job name and updated in place.
+CREATE TABLE IF NOT EXISTS scheduled_jobs (
+    name VARCHAR(100) PRIMARY KEY,
+    schedule TEXT NOT NULL,
+    next_run_at TIMESTAMPTZ,
+    last_started_at TIMESTAMPTZ,
+    last_finished_at TIMESTAMPTZ,
+    last_status VARCHAR(20) CHECK (last_status IN ('running', 'succeeded', 'failed')),
+    last_message TEXT,
+    run_count BIGINT NOT NULL DEFAULT 0,
+    failure_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
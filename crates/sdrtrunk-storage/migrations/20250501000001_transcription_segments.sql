@@ -0,0 +1,13 @@
+-- Timed transcript segments of each call, in the order the transcriber
+-- produced them, for subtitle export. Replaced whenever the call is
+-- re-transcribed and removed with the call.
+CREATE TABLE IF NOT EXISTS transcription_segments (
+    call_id UUID NOT NULL REFERENCES radio_calls(id) ON DELETE CASCADE,
+    segment_index INTEGER NOT NULL,
+    start_seconds DOUBLE PRECISION NOT NULL,
+    end_seconds DOUBLE PRECISION NOT NULL,
+    text TEXT NOT NULL,
+    speaker VARCHAR(100),
+    confidence REAL,
+    PRIMARY KEY (call_id, segment_index)
+);
@@ -0,0 +1,13 @@
+-- Timestamped processing events of each call (received, stored, queued,
+-- claimed by a worker, transcribed or failed), for tracing calls that get
+-- stuck in the pipeline. Removed with the call.
+CREATE TABLE IF NOT EXISTS call_events (
+    id BIGSERIAL PRIMARY KEY,
+    call_id UUID NOT NULL REFERENCES radio_calls(id) ON DELETE CASCADE,
+    event VARCHAR(20) NOT NULL
+        CHECK (event IN ('received', 'stored', 'queued', 'claimed', 'transcribed', 'failed')),
+    detail TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_call_events_call_id ON call_events (call_id, occurred_at);
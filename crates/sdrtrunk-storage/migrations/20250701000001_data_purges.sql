@@ -0,0 +1,20 @@
+-- Audit log of targeted data purges (DELETE /api/admin/purge), e.g. for
+-- erasure requests. Each row is written in the transaction that erased the
+-- matching calls and records who asked, the filters, and what was removed;
+-- recording removal happens after commit and is filled in afterwards.
+CREATE TABLE IF NOT EXISTS data_purges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    requested_by VARCHAR(255) NOT NULL,
+    reason TEXT,
+    system_id VARCHAR(50),
+    talkgroup_id INTEGER,
+    source_radio_id INTEGER,
+    calls_deleted INTEGER NOT NULL,
+    upload_logs_deleted INTEGER NOT NULL,
+    webhook_deliveries_deleted INTEGER NOT NULL,
+    audio_files_deleted INTEGER NOT NULL DEFAULT 0,
+    audio_files_failed INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_data_purges_created_at ON data_purges (created_at DESC);
@@ -0,0 +1,11 @@
+-- Transcriptions scoring below transcription.min_confidence are stored with
+-- transcription_status 'needs_review' until someone checks them. The review
+-- queue lists them newest first, and marking one reviewed is recorded as a
+-- 'reviewed' call event.
+CREATE INDEX IF NOT EXISTS idx_radio_calls_needs_review
+    ON radio_calls (call_timestamp DESC, id DESC)
+    WHERE transcription_status = 'needs_review';
+
+ALTER TABLE call_events DROP CONSTRAINT IF EXISTS call_events_event_check;
+ALTER TABLE call_events ADD CONSTRAINT call_events_event_check
+    CHECK (event IN ('received', 'stored', 'queued', 'claimed', 'transcribed', 'failed', 'reviewed'));
@@ -0,0 +1,15 @@
+-- Tags analysts attach to calls, each with an optional free-text note.
+-- Tags are lowercase and unique per call; tagging a call again replaces the
+-- note. Call listings filter by tag through idx_call_tags_tag. Removed with
+-- the call.
+CREATE TABLE IF NOT EXISTS call_tags (
+    call_id UUID NOT NULL REFERENCES radio_calls(id) ON DELETE CASCADE,
+    tag VARCHAR(32) NOT NULL CHECK (tag = LOWER(tag) AND tag <> ''),
+    note TEXT,
+    api_key_id VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (call_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_call_tags_tag ON call_tags (tag, call_id);
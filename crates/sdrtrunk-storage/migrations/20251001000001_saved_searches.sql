@@ -0,0 +1,15 @@
+-- Named call filter sets saved per user. The owner is "user:<id>" for a
+-- signed-in user or "api_key:<id>" for an API key; names are unique per owner.
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner VARCHAR(255) NOT NULL,
+    name VARCHAR(100) NOT NULL CHECK (name <> ''),
+    system_id VARCHAR(50),
+    talkgroup_id INTEGER,
+    keyword TEXT,
+    from_date TIMESTAMPTZ,
+    to_date TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT saved_searches_owner_name_key UNIQUE (owner, name)
+);
@@ -0,0 +1,14 @@
+-- Acoustic fingerprint of each call's recording, one 32-bit sub-fingerprint
+-- per frame. A call whose audio repeats one uploaded shortly before it (e.g. a
+-- simulcast echo) points at that call through echo_of. Removed with the call.
+CREATE TABLE IF NOT EXISTS call_fingerprints (
+    call_id UUID PRIMARY KEY REFERENCES radio_calls(id) ON DELETE CASCADE,
+    fingerprint INTEGER[] NOT NULL,
+    echo_of UUID REFERENCES radio_calls(id) ON DELETE SET NULL,
+    bit_error_rate REAL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_call_fingerprints_echo_of
+    ON call_fingerprints (echo_of)
+    WHERE echo_of IS NOT NULL;
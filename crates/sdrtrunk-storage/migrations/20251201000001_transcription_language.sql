@@ -0,0 +1,8 @@
+-- Filter calls by the language they were transcribed in. Codes are matched
+-- case-insensitively, so index the lowered value.
+CREATE INDEX IF NOT EXISTS idx_radio_calls_transcription_language
+    ON radio_calls (LOWER(transcription_language), call_timestamp DESC)
+    WHERE transcription_language IS NOT NULL;
+
+ALTER TABLE saved_searches
+    ADD COLUMN IF NOT EXISTS language VARCHAR(10);
@@ -0,0 +1,11 @@
+-- Short LLM-written summary of each transcribed call, with the entities
+-- (units, places, incidents) it mentions as an array of {kind, text}
+-- objects. Summarizing a call again replaces its row. Keyword searches of
+-- calls also match the summary and entity text. Removed with the call.
+CREATE TABLE IF NOT EXISTS call_summaries (
+    call_id UUID PRIMARY KEY REFERENCES radio_calls(id) ON DELETE CASCADE,
+    summary TEXT NOT NULL,
+    entities JSONB NOT NULL DEFAULT '[]'::jsonb,
+    model VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
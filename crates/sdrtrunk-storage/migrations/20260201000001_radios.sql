@@ -0,0 +1,43 @@
+-- Activity of each radio (unit) ID per system: when it was first and last
+-- heard, how many calls it made, the talker alias it last sent over the
+-- air, and a label analysts give it (e.g. "Engine 5 portable"). Uploads
+-- update the row for the call's radio; deleting calls does not.
+CREATE TABLE IF NOT EXISTS radios (
+    system_id VARCHAR(50) NOT NULL,
+    radio_id INTEGER NOT NULL,
+    label VARCHAR(255),
+    last_alias VARCHAR(255),
+    first_seen TIMESTAMPTZ NOT NULL,
+    last_seen TIMESTAMPTZ NOT NULL,
+    call_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (system_id, radio_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_radios_last_seen ON radios (system_id, last_seen DESC);
+
+-- A radio's call history, newest first
+CREATE INDEX IF NOT EXISTS idx_radio_calls_source_radio
+    ON radio_calls (system_id, source_radio_id, call_timestamp DESC, id DESC)
+    WHERE source_radio_id IS NOT NULL;
+
+-- Seed the table from calls stored before it existed
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM radios) THEN
+        INSERT INTO radios (system_id, radio_id, last_alias, first_seen, last_seen, call_count)
+        SELECT
+            system_id,
+            source_radio_id,
+            (ARRAY_AGG(talker_alias ORDER BY call_timestamp DESC)
+                FILTER (WHERE talker_alias IS NOT NULL))[1],
+            MIN(call_timestamp),
+            MAX(call_timestamp),
+            COUNT(*)
+        FROM radio_calls
+        WHERE source_radio_id IS NOT NULL
+        GROUP BY system_id, source_radio_id;
+    END IF;
+END
+$$;
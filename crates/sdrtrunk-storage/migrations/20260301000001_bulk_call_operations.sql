@@ -0,0 +1,36 @@
+-- Archived calls: kept past retention and left out of call listings unless
+-- archived calls are asked for. Set by bulk archive operations.
+ALTER TABLE radio_calls ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_radio_calls_archived_at
+    ON radio_calls (archived_at)
+    WHERE archived_at IS NOT NULL;
+
+-- Audit log of bulk archives and deletes (POST /api/admin/calls/archive and
+-- /api/admin/calls/delete). A request without a confirmation token records
+-- a pending operation with the number of matching calls and the hash of a
+-- single-use token; repeating the request with the token carries it out and
+-- records how many calls it affected.
+CREATE TABLE IF NOT EXISTS bulk_call_operations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    action VARCHAR(20) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    requested_by VARCHAR(255) NOT NULL,
+    reason TEXT,
+    system_id VARCHAR(50),
+    talkgroup_id INTEGER,
+    source_radio_id INTEGER,
+    from_date TIMESTAMPTZ,
+    to_date TIMESTAMPTZ,
+    matched_calls BIGINT NOT NULL,
+    affected_calls BIGINT,
+    audio_files_deleted INTEGER NOT NULL DEFAULT 0,
+    audio_files_failed INTEGER NOT NULL DEFAULT 0,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_bulk_call_operations_created_at
+    ON bulk_call_operations (created_at DESC);
@@ -0,0 +1,15 @@
+-- Idempotency keys sent with uploads (`Idempotency-Key` header), per system.
+-- An upload claims its key before storing the call and records the call it
+-- created; a retry with the same key gets the original call back instead of
+-- creating another. `call_id` is NULL while the first upload is in flight.
+-- Keys expire after a day and go away with their call.
+CREATE TABLE IF NOT EXISTS upload_idempotency_keys (
+    system_id VARCHAR(50) NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    call_id UUID REFERENCES radio_calls(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (system_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_upload_idempotency_keys_call_id
+    ON upload_idempotency_keys (call_id);
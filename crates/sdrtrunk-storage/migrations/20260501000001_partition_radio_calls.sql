@@ -0,0 +1,261 @@
+-- Partition radio_calls by month of call_timestamp (UTC), so time-bounded
+-- queries only read the months they cover and emptied months can be dropped
+-- instead of vacuumed. Each month has a partition named radio_calls_YYYY_MM;
+-- calls outside every monthly partition land in radio_calls_default. The
+-- partition maintenance job creates upcoming months ahead of time and drops
+-- empty months past the retention period.
+--
+-- A partitioned table's primary key has to include the partition key, so
+-- the primary key becomes (id, call_timestamp), with a trigger keeping id
+-- unique on its own. The foreign keys other tables had on radio_calls(id) are
+-- replaced by the statement trigger at the end of this file, which removes
+-- (or unlinks) a deleted call's rows and refuses to delete calls that still
+-- have transcription jobs, and by per-table triggers rejecting rows that
+-- refer to a missing call. Tables added later that refer to calls must be
+-- added to those triggers instead of declaring a foreign key.
+
+-- Name of the partition holding calls from the month of `month`
+CREATE OR REPLACE FUNCTION radio_calls_partition_name(month DATE) RETURNS TEXT
+LANGUAGE sql IMMUTABLE AS $$
+    SELECT 'radio_calls_' || to_char(month, 'YYYY_MM')
+$$;
+
+-- Create the partition for the month of `month`, moving that month's calls
+-- out of the default partition. Returns whether the partition was created.
+CREATE OR REPLACE FUNCTION create_radio_calls_partition(month DATE) RETURNS BOOLEAN
+LANGUAGE plpgsql AS $$
+DECLARE
+    first_day DATE := date_trunc('month', month)::DATE;
+    partition_name TEXT := radio_calls_partition_name(first_day);
+    lower_bound TIMESTAMPTZ := first_day::TIMESTAMP AT TIME ZONE 'UTC';
+    upper_bound TIMESTAMPTZ := (first_day + INTERVAL '1 month')::TIMESTAMP AT TIME ZONE 'UTC';
+BEGIN
+    IF to_regclass(partition_name) IS NOT NULL THEN
+        RETURN FALSE;
+    END IF;
+
+    -- Keep uploads out of the default partition while its calls move
+    LOCK TABLE radio_calls_default IN EXCLUSIVE MODE;
+    IF NOT EXISTS (
+        SELECT 1 FROM radio_calls_default
+        WHERE call_timestamp >= lower_bound AND call_timestamp < upper_bound
+    ) THEN
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF radio_calls FOR VALUES FROM (%L) TO (%L)',
+            partition_name, lower_bound, upper_bound
+        );
+        RETURN TRUE;
+    END IF;
+
+    -- Deleting from the partition directly skips the dependents trigger,
+    -- which only fires for deletes from radio_calls
+    EXECUTE format('CREATE TABLE %I (LIKE radio_calls INCLUDING DEFAULTS)', partition_name);
+    EXECUTE format(
+        'WITH moved AS (
+            DELETE FROM radio_calls_default
+            WHERE call_timestamp >= %L AND call_timestamp < %L
+            RETURNING *
+        )
+        INSERT INTO %I SELECT * FROM moved',
+        lower_bound, upper_bound, partition_name
+    );
+    EXECUTE format(
+        'ALTER TABLE radio_calls ATTACH PARTITION %I FOR VALUES FROM (%L) TO (%L)',
+        partition_name, lower_bound, upper_bound
+    );
+    RETURN TRUE;
+END
+$$;
+
+-- Drop the empty monthly partitions whose month ended by `before`. Returns
+-- the names of the dropped partitions.
+CREATE OR REPLACE FUNCTION drop_empty_radio_calls_partitions(before TIMESTAMPTZ)
+RETURNS SETOF TEXT
+LANGUAGE plpgsql AS $$
+DECLARE
+    partition_name TEXT;
+    is_empty BOOLEAN;
+BEGIN
+    FOR partition_name IN
+        SELECT c.relname
+        FROM pg_inherits i
+        JOIN pg_class c ON c.oid = i.inhrelid
+        WHERE i.inhparent = 'radio_calls'::regclass
+          AND c.relname ~ '^radio_calls_\d{4}_\d{2}$'
+          AND (to_date(substr(c.relname, 13), 'YYYY_MM') + INTERVAL '1 month')::TIMESTAMP
+              AT TIME ZONE 'UTC' <= before
+        ORDER BY c.relname
+    LOOP
+        -- Hold off late uploads for the month while checking and dropping
+        EXECUTE format('LOCK TABLE %I IN ACCESS EXCLUSIVE MODE', partition_name);
+        EXECUTE format('SELECT NOT EXISTS (SELECT 1 FROM %I)', partition_name) INTO is_empty;
+        IF is_empty THEN
+            EXECUTE format('DROP TABLE %I', partition_name);
+            RETURN NEXT partition_name;
+        END IF;
+    END LOOP;
+END
+$$;
+
+-- Convert the table once, keeping its calls and indexes
+DO $$
+DECLARE
+    index_defs TEXT[];
+    index_def TEXT;
+    fk RECORD;
+    month DATE;
+BEGIN
+    IF (SELECT relkind FROM pg_class WHERE oid = 'radio_calls'::regclass) = 'p' THEN
+        RETURN;
+    END IF;
+
+    FOR fk IN
+        SELECT conrelid::regclass AS referencing, conname
+        FROM pg_constraint
+        WHERE contype = 'f' AND confrelid = 'radio_calls'::regclass
+    LOOP
+        EXECUTE format('ALTER TABLE %s DROP CONSTRAINT %I', fk.referencing, fk.conname);
+    END LOOP;
+
+    SELECT array_agg(pg_get_indexdef(indexrelid)) INTO index_defs
+    FROM pg_index
+    WHERE indrelid = 'radio_calls'::regclass AND NOT indisprimary;
+
+    ALTER TABLE radio_calls RENAME TO radio_calls_unpartitioned;
+    CREATE TABLE radio_calls (
+        LIKE radio_calls_unpartitioned INCLUDING DEFAULTS INCLUDING CONSTRAINTS
+    ) PARTITION BY RANGE (call_timestamp);
+    CREATE TABLE radio_calls_default PARTITION OF radio_calls DEFAULT;
+
+    FOR month IN
+        SELECT DISTINCT date_trunc('month', call_timestamp AT TIME ZONE 'UTC')::DATE
+        FROM radio_calls_unpartitioned
+        UNION
+        SELECT date_trunc('month', NOW() AT TIME ZONE 'UTC')::DATE
+    LOOP
+        PERFORM create_radio_calls_partition(month);
+    END LOOP;
+
+    INSERT INTO radio_calls SELECT * FROM radio_calls_unpartitioned;
+    DROP TABLE radio_calls_unpartitioned;
+
+    -- Index after loading, and once the old table's index names are free
+    ALTER TABLE radio_calls ADD CONSTRAINT radio_calls_pkey PRIMARY KEY (id, call_timestamp);
+    FOREACH index_def IN ARRAY COALESCE(index_defs, ARRAY[]::TEXT[]) LOOP
+        EXECUTE index_def;
+    END LOOP;
+END
+$$;
+
+-- Stand-in for the primary key on id alone. Inserts of the same id wait for
+-- each other on an advisory lock, so the later one sees the earlier once it
+-- commits.
+CREATE OR REPLACE FUNCTION radio_calls_unique_id() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' AND NEW.id = OLD.id THEN
+        RETURN NEW;
+    END IF;
+    PERFORM pg_advisory_xact_lock(hashtextextended('radio_calls:' || NEW.id::TEXT, 0));
+    IF EXISTS (SELECT 1 FROM radio_calls WHERE id = NEW.id) THEN
+        RAISE unique_violation
+            USING MESSAGE = format('duplicate radio call id %s', NEW.id),
+                  CONSTRAINT = 'radio_calls_id_unique';
+    END IF;
+    RETURN NEW;
+END
+$$;
+
+CREATE OR REPLACE TRIGGER radio_calls_unique_id
+    BEFORE INSERT OR UPDATE OF id ON radio_calls
+    FOR EACH ROW EXECUTE FUNCTION radio_calls_unique_id();
+
+-- Stand-in for the foreign keys on radio_calls(id): refuse to delete calls
+-- that still have transcription jobs, as the old foreign key did, then remove
+-- a deleted call's other dependent rows and unlink webhook deliveries and
+-- echo fingerprints
+CREATE OR REPLACE FUNCTION radio_calls_delete_dependents() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+DECLARE
+    job_call UUID;
+BEGIN
+    SELECT call_id INTO job_call
+    FROM transcription_jobs
+    WHERE call_id IN (SELECT id FROM deleted_calls)
+    LIMIT 1;
+    IF FOUND THEN
+        RAISE foreign_key_violation
+            USING MESSAGE = format('call %s is still referenced from transcription_jobs', job_call);
+    END IF;
+    DELETE FROM transcription_segments WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM transcription_feedback WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM call_summaries WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM call_events WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM call_tags WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM call_waveforms WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM call_fingerprints WHERE call_id IN (SELECT id FROM deleted_calls);
+    UPDATE call_fingerprints SET echo_of = NULL WHERE echo_of IN (SELECT id FROM deleted_calls);
+    DELETE FROM conversation_calls WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM alerts WHERE call_id IN (SELECT id FROM deleted_calls);
+    DELETE FROM upload_idempotency_keys WHERE call_id IN (SELECT id FROM deleted_calls);
+    UPDATE webhook_deliveries SET call_id = NULL WHERE call_id IN (SELECT id FROM deleted_calls);
+    RETURN NULL;
+END
+$$;
+
+CREATE OR REPLACE TRIGGER radio_calls_delete_dependents
+    AFTER DELETE ON radio_calls
+    REFERENCING OLD TABLE AS deleted_calls
+    FOR EACH STATEMENT EXECUTE FUNCTION radio_calls_delete_dependents();
+
+-- The other half of the foreign keys: reject rows referring to a call that
+-- does not exist. TG_ARGV[0] names the referencing column. Like a foreign
+-- key, the call is locked FOR KEY SHARE so it cannot be deleted before the
+-- referencing row commits.
+CREATE OR REPLACE FUNCTION radio_calls_check_reference() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+DECLARE
+    referenced UUID := (to_jsonb(NEW) ->> TG_ARGV[0])::UUID;
+BEGIN
+    IF referenced IS NULL THEN
+        RETURN NEW;
+    END IF;
+    PERFORM 1 FROM radio_calls WHERE id = referenced FOR KEY SHARE;
+    IF NOT FOUND THEN
+        RAISE foreign_key_violation
+            USING MESSAGE = format('%s.%s refers to missing call %s', TG_TABLE_NAME, TG_ARGV[0], referenced);
+    END IF;
+    RETURN NEW;
+END
+$$;
+
+DO $$
+DECLARE
+    reference RECORD;
+BEGIN
+    FOR reference IN
+        SELECT * FROM (VALUES
+            ('transcription_jobs', 'call_id'),
+            ('transcription_segments', 'call_id'),
+            ('transcription_feedback', 'call_id'),
+            ('call_summaries', 'call_id'),
+            ('call_events', 'call_id'),
+            ('call_tags', 'call_id'),
+            ('call_waveforms', 'call_id'),
+            ('call_fingerprints', 'call_id'),
+            ('call_fingerprints', 'echo_of'),
+            ('conversation_calls', 'call_id'),
+            ('alerts', 'call_id'),
+            ('upload_idempotency_keys', 'call_id'),
+            ('webhook_deliveries', 'call_id')
+        ) AS r (table_name, column_name)
+    LOOP
+        EXECUTE format(
+            'CREATE OR REPLACE TRIGGER %I BEFORE INSERT OR UPDATE OF %I ON %I
+                FOR EACH ROW EXECUTE FUNCTION radio_calls_check_reference(%L)',
+            reference.table_name || '_' || reference.column_name || '_check',
+            reference.column_name, reference.table_name, reference.column_name
+        );
+    END LOOP;
+END
+$$;
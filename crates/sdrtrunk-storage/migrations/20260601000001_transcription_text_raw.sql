@@ -0,0 +1,5 @@
+-- Original transcript of a call whose stored text was anonymized, sealed
+-- with AES-256-GCM (12-byte nonce, then ciphertext and tag). NULL when the
+-- text was stored as transcribed or no encryption key is configured.
+ALTER TABLE radio_calls
+    ADD COLUMN IF NOT EXISTS transcription_text_raw BYTEA;
@@ -0,0 +1,7 @@
+-- Encrypted copies of sensitive call fields (see `[encryption]`). A call
+-- keeps each field either in plaintext or sealed here, never both. Values
+-- are a 4-byte key ID followed by the AES-256-GCM nonce, ciphertext, and
+-- tag; the key ID lets the rotate task find values sealed with older keys.
+ALTER TABLE radio_calls
+    ADD COLUMN IF NOT EXISTS transcription_text_encrypted BYTEA,
+    ADD COLUMN IF NOT EXISTS talker_alias_encrypted BYTEA;
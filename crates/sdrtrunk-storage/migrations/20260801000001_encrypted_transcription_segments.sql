@@ -0,0 +1,6 @@
+-- Sealed segment text for calls whose transcripts are encrypted (see
+-- `[encryption]`). A segment keeps its text either in plaintext or sealed
+-- here, never both, in the same format as `radio_calls`' encrypted fields.
+ALTER TABLE transcription_segments
+    ADD COLUMN IF NOT EXISTS text_encrypted BYTEA,
+    ALTER COLUMN text DROP NOT NULL;
@@ -0,0 +1,4 @@
+-- Bulk archives record an 'archived' call event for each call they archive.
+ALTER TABLE call_events DROP CONSTRAINT IF EXISTS call_events_event_check;
+ALTER TABLE call_events ADD CONSTRAINT call_events_event_check
+    CHECK (event IN ('received', 'stored', 'queued', 'claimed', 'transcribed', 'failed', 'reviewed', 'archived'));
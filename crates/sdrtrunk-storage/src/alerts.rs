@@ -0,0 +1,432 @@
+//! Keyword alert rules and history.
+//!
+//! Users define rules in `alert_rules`; a rule applies to every call unless
+//! scoped to a system and/or talkgroup. When a completed transcription matches
+//! a rule, an entry is written to `alerts` together with the outcome of its
+//! webhook and email notifications.
+
+use crate::error::StorageError;
+use crate::queries::system_names;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for alert operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `alert_rules` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Rule ID.
+    pub id: Uuid,
+    /// Display name.
+    pub name: String,
+    /// Keyword, phrase, or regex.
+    pub pattern: String,
+    /// How `pattern` is interpreted (`keyword` or `regex`).
+    pub pattern_type: String,
+    /// Only match calls from this system.
+    pub system_id: Option<SystemId>,
+    /// Only match calls on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// URL to POST alerts to.
+    pub webhook_url: Option<String>,
+    /// Address to email alerts to.
+    pub email_to: Option<String>,
+    /// Whether the rule is checked.
+    pub enabled: bool,
+    /// When the rule was created.
+    pub created_at: DateTime<Utc>,
+    /// When the rule was last changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields for a new alert rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewAlertRule {
+    /// Display name.
+    pub name: String,
+    /// Keyword, phrase, or regex.
+    pub pattern: String,
+    /// How `pattern` is interpreted (`keyword` or `regex`).
+    pub pattern_type: String,
+    /// Only match calls from this system.
+    pub system_id: Option<SystemId>,
+    /// Only match calls on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// URL to POST alerts to.
+    pub webhook_url: Option<String>,
+    /// Address to email alerts to.
+    pub email_to: Option<String>,
+    /// Whether the rule is checked.
+    pub enabled: bool,
+}
+
+/// A row from the `alerts` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Alert {
+    /// Alert ID.
+    pub id: Uuid,
+    /// Rule that matched (`None` once the rule is deleted).
+    pub rule_id: Option<Uuid>,
+    /// Rule name at the time of the alert.
+    pub rule_name: String,
+    /// Call whose transcript matched.
+    pub call_id: Uuid,
+    /// System of the call.
+    pub system_id: SystemId,
+    /// Talkgroup of the call.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Text the pattern matched.
+    pub matched_text: String,
+    /// Transcript that was checked.
+    pub transcript: String,
+    /// Webhook outcome (`sent` or `failed`; `None` without a webhook).
+    pub webhook_status: Option<String>,
+    /// Email outcome (`sent`, `failed`, or `skipped` without SMTP; `None`
+    /// without an address).
+    pub email_status: Option<String>,
+    /// Errors from failed notifications.
+    pub delivery_error: Option<String>,
+    /// When the alert was raised.
+    pub created_at: DateTime<Utc>,
+}
+
+/// A rule match to record.
+#[derive(Debug, Clone)]
+pub struct NewAlert<'a> {
+    /// Rule that matched.
+    pub rule: &'a AlertRule,
+    /// Call whose transcript matched.
+    pub call_id: Uuid,
+    /// System of the call.
+    pub system_id: &'a SystemId,
+    /// Talkgroup of the call.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Text the pattern matched.
+    pub matched_text: &'a str,
+    /// Transcript that was checked.
+    pub transcript: &'a str,
+}
+
+/// Notification outcomes for an alert.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertDelivery {
+    /// Webhook outcome.
+    pub webhook_status: Option<String>,
+    /// Email outcome.
+    pub email_status: Option<String>,
+    /// Errors from failed notifications.
+    pub error: Option<String>,
+}
+
+/// Filters for [`AlertQueries::list`]; unset fields match all alerts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertFilter {
+    /// Only alerts raised by this rule.
+    pub rule_id: Option<Uuid>,
+    /// Only alerts for this system.
+    pub system_id: Option<SystemId>,
+    /// Only alerts for these systems (an API key's tenant scope).
+    pub allowed_systems: Option<Vec<SystemId>>,
+    /// Only alerts for this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only alerts raised at or after this time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only alerts raised before this time.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// Alerts matching an [`AlertFilter`]; binds `$1`–`$6`.
+const ALERT_FILTER: &str = r"
+    FROM alerts
+    WHERE ($1::UUID IS NULL OR rule_id = $1)
+      AND ($2::VARCHAR IS NULL OR system_id = $2)
+      AND ($3::INTEGER IS NULL OR talkgroup_id = $3)
+      AND ($4::TIMESTAMPTZ IS NULL OR created_at >= $4)
+      AND ($5::TIMESTAMPTZ IS NULL OR created_at < $5)
+      AND ($6::TEXT[] IS NULL OR system_id = ANY($6))
+";
+
+// ---------------------------------------------------------------------------
+// Alert operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for alert rules and history.
+#[derive(Debug)]
+pub struct AlertQueries;
+
+impl AlertQueries {
+    /// Create an alert rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn create_rule(pool: &PgPool, rule: &NewAlertRule) -> Result<AlertRule> {
+        let rule = sqlx::query_as::<_, AlertRule>(
+            r"
+            INSERT INTO alert_rules
+                (name, pattern, pattern_type, system_id, talkgroup_id, webhook_url, email_to, enabled)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            ",
+        )
+        .bind(&rule.name)
+        .bind(&rule.pattern)
+        .bind(&rule.pattern_type)
+        .bind(rule.system_id.as_ref())
+        .bind(rule.talkgroup_id)
+        .bind(rule.webhook_url.as_deref())
+        .bind(rule.email_to.as_deref())
+        .bind(rule.enabled)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// List all alert rules, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list_rules(pool: &PgPool) -> Result<Vec<AlertRule>> {
+        let rules =
+            sqlx::query_as::<_, AlertRule>("SELECT * FROM alert_rules ORDER BY created_at, id")
+                .fetch_all(pool)
+                .await?;
+
+        Ok(rules)
+    }
+
+    /// Get an alert rule by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn get_rule(pool: &PgPool, id: Uuid) -> Result<Option<AlertRule>> {
+        let rule = sqlx::query_as::<_, AlertRule>("SELECT * FROM alert_rules WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(rule)
+    }
+
+    /// Delete an alert rule, keeping its alert history.
+    ///
+    /// Returns whether the rule existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn delete_rule(pool: &PgPool, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM alert_rules WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Enabled rules that apply to a call on `system_id` and `talkgroup_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn rules_for_call(
+        pool: &PgPool,
+        system_id: &SystemId,
+        talkgroup_id: Option<TalkgroupId>,
+    ) -> Result<Vec<AlertRule>> {
+        let rules = sqlx::query_as::<_, AlertRule>(
+            r"
+            SELECT * FROM alert_rules
+            WHERE enabled
+              AND (system_id IS NULL OR system_id = $1)
+              AND (talkgroup_id IS NULL OR talkgroup_id = $2)
+            ORDER BY created_at, id
+            ",
+        )
+        .bind(system_id)
+        .bind(talkgroup_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    /// Record a rule match.
+    ///
+    /// Returns `None` if the rule already raised an alert for this call, so
+    /// re-transcribing a call does not notify twice.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn record(pool: &PgPool, alert: &NewAlert<'_>) -> Result<Option<Alert>> {
+        let alert = sqlx::query_as::<_, Alert>(
+            r"
+            INSERT INTO alerts
+                (rule_id, rule_name, call_id, system_id, talkgroup_id, matched_text, transcript)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (rule_id, call_id) DO NOTHING
+            RETURNING *
+            ",
+        )
+        .bind(alert.rule.id)
+        .bind(&alert.rule.name)
+        .bind(alert.call_id)
+        .bind(alert.system_id)
+        .bind(alert.talkgroup_id)
+        .bind(alert.matched_text)
+        .bind(alert.transcript)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(alert)
+    }
+
+    /// Store the notification outcomes of an alert.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn set_delivery(pool: &PgPool, id: Uuid, delivery: &AlertDelivery) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE alerts
+            SET webhook_status = $2, email_status = $3, delivery_error = $4
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .bind(delivery.webhook_status.as_deref())
+        .bind(delivery.email_status.as_deref())
+        .bind(delivery.error.as_deref())
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List alerts matching `filter`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(
+        pool: &PgPool,
+        filter: &AlertFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Alert>> {
+        let query =
+            format!("SELECT * {ALERT_FILTER} ORDER BY created_at DESC, id LIMIT $7 OFFSET $8");
+        let alerts = sqlx::query_as::<_, Alert>(&query)
+            .bind(filter.rule_id)
+            .bind(filter.system_id.as_ref())
+            .bind(filter.talkgroup_id)
+            .bind(filter.from_date)
+            .bind(filter.to_date)
+            .bind(filter.allowed_systems.as_deref().map(system_names))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(alerts)
+    }
+
+    /// Count alerts matching `filter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn count(pool: &PgPool, filter: &AlertFilter) -> Result<i64> {
+        let query = format!("SELECT COUNT(*) {ALERT_FILTER}");
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(filter.rule_id)
+            .bind(filter.system_id.as_ref())
+            .bind(filter.talkgroup_id)
+            .bind(filter.from_date)
+            .bind(filter.to_date)
+            .bind(filter.allowed_systems.as_deref().map(system_names))
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+
+    fn rule(name: &str, system_id: Option<SystemId>) -> NewAlertRule {
+        NewAlertRule {
+            name: name.to_string(),
+            pattern: "structure fire".to_string(),
+            pattern_type: "keyword".to_string(),
+            system_id,
+            talkgroup_id: None,
+            webhook_url: None,
+            email_to: None,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rules_for_call_scoping() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("al_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let other = SystemId::new(format!("al_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let scoped = AlertQueries::create_rule(&pool, &rule("Scoped", Some(system_id.clone())))
+            .await
+            .unwrap();
+        let mut disabled = rule("Disabled", Some(system_id.clone()));
+        disabled.enabled = false;
+        let disabled = AlertQueries::create_rule(&pool, &disabled).await.unwrap();
+        let elsewhere = AlertQueries::create_rule(&pool, &rule("Elsewhere", Some(other)))
+            .await
+            .unwrap();
+
+        let ids: Vec<Uuid> = AlertQueries::rules_for_call(&pool, &system_id, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert!(ids.contains(&scoped.id));
+        assert!(!ids.contains(&disabled.id));
+        assert!(!ids.contains(&elsewhere.id));
+
+        for id in [scoped.id, disabled.id, elsewhere.id] {
+            assert!(AlertQueries::delete_rule(&pool, id).await.unwrap());
+        }
+        assert!(!AlertQueries::delete_rule(&pool, scoped.id).await.unwrap());
+    }
+}
@@ -0,0 +1,475 @@
+//! Recording storage backends.
+//!
+//! Uploaded audio is written through the [`AudioStorage`] trait so it can live
+//! either under the local storage directory or in an S3-compatible bucket.
+//! Each stored recording is identified by a *location* string recorded in
+//! `radio_calls.audio_file_path`: an absolute file path for local storage, or
+//! `s3://<bucket>/<key>` for object storage. Recordings are keyed by
+//! `<system>/<yyyy>/<mm>/<dd>/<file>` in both backends (see [`recording_key`]).
+
+use crate::error::StorageError;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::NaiveDate;
+use object_store::{ObjectStore, aws::AmazonS3Builder, path::Path as ObjectPath};
+use sdrtrunk_protocol::config::{S3Config, StorageBackend, StorageConfig};
+use sdrtrunk_types::SystemId;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// Result type alias for recording storage operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Trait
+// ---------------------------------------------------------------------------
+
+/// Where recordings are written to and read back from.
+#[async_trait]
+pub trait AudioStorage: Send + Sync + std::fmt::Debug {
+    /// Store a recording under `key`, replacing any existing one.
+    ///
+    /// Returns the location to record on the call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is invalid or the write fails.
+    async fn put(&self, key: &str, data: Bytes) -> Result<String>;
+
+    /// Read a recording, or `None` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `location` is not in this storage or the read fails.
+    async fn get(&self, location: &str) -> Result<Option<Bytes>>;
+
+    /// Delete a recording, returning its size, or `None` if it was already gone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `location` is not in this storage or the delete fails.
+    async fn delete(&self, location: &str) -> Result<Option<u64>>;
+
+    /// Whether `location` points into this storage.
+    ///
+    /// Locations recorded before a backend switch, or crafted to point
+    /// elsewhere, are never read or deleted.
+    fn contains(&self, location: &str) -> bool;
+
+    /// On-disk path of a recording, for backends that keep files locally.
+    fn local_path(&self, _location: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Build the recording storage selected by `storage.backend`.
+///
+/// # Errors
+///
+/// Returns an error if `backend = "s3"` without an `[storage.s3]` section, or
+/// the S3 client cannot be configured.
+pub fn from_config(config: &StorageConfig) -> Result<Arc<dyn AudioStorage>> {
+    match config.backend {
+        StorageBackend::Local => Ok(Arc::new(LocalAudioStorage::new(
+            &config.base_dir,
+            &config.upload_dir,
+        ))),
+        StorageBackend::S3 => {
+            let s3 = config.s3.as_ref().ok_or_else(|| {
+                StorageError::Audio("storage.backend is \"s3\" but [storage.s3] is missing".into())
+            })?;
+            Ok(Arc::new(S3AudioStorage::new(s3)?))
+        }
+    }
+}
+
+/// Storage key for a recording: `<system>/<yyyy>/<mm>/<dd>/<file>`.
+#[must_use]
+pub fn recording_key(system_id: &SystemId, date: NaiveDate, filename: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        system_id.as_str(),
+        date.format("%Y/%m/%d"),
+        filename
+    )
+}
+
+/// Reject keys that could escape the storage root.
+///
+/// # Errors
+///
+/// Returns an error for empty, absolute, or `..`-containing keys.
+fn validate_key(key: &str) -> Result<()> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && key
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != "..");
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::Audio(format!("invalid recording key: {key}")))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Local filesystem
+// ---------------------------------------------------------------------------
+
+/// Recordings kept under the local storage directory.
+#[derive(Debug, Clone)]
+pub struct LocalAudioStorage {
+    /// Everything below this may be read or deleted.
+    root: PathBuf,
+    /// New recordings are written below this.
+    upload_dir: PathBuf,
+}
+
+impl LocalAudioStorage {
+    /// Store recordings in `root/upload_dir`.
+    #[must_use]
+    pub fn new(root: &Path, upload_dir: &str) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            upload_dir: root.join(upload_dir),
+        }
+    }
+
+    /// Path of a location inside the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `location` is outside the root.
+    fn resolve(&self, location: &str) -> Result<PathBuf> {
+        self.local_path(location)
+            .ok_or_else(|| StorageError::Audio(format!("{location} is outside storage")))
+    }
+
+    /// Remove now-empty day/month/year directories above `path`.
+    ///
+    /// Stops at the first non-empty directory, and never removes the root.
+    async fn prune_empty_dirs(&self, path: &Path) {
+        let mut dir = path.parent();
+        while let Some(current) = dir {
+            if current == self.root
+                || !current.starts_with(&self.root)
+                || tokio::fs::remove_dir(current).await.is_err()
+            {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+}
+
+#[async_trait]
+impl AudioStorage for LocalAudioStorage {
+    async fn put(&self, key: &str, data: Bytes) -> Result<String> {
+        validate_key(key)?;
+        let path = self.upload_dir.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                StorageError::Audio(format!("failed to create {}: {e}", parent.display()))
+            })?;
+        }
+        tokio::fs::write(&path, &data)
+            .await
+            .map_err(|e| StorageError::Audio(format!("failed to write {}: {e}", path.display())))?;
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    async fn get(&self, location: &str) -> Result<Option<Bytes>> {
+        let path = self.resolve(location)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(Bytes::from(data))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StorageError::Audio(format!(
+                "failed to read {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    async fn delete(&self, location: &str) -> Result<Option<u64>> {
+        let path = self.resolve(location)?;
+        let size = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(StorageError::Audio(format!(
+                    "failed to read {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+        tokio::fs::remove_file(&path).await.map_err(|e| {
+            StorageError::Audio(format!("failed to remove {}: {e}", path.display()))
+        })?;
+        self.prune_empty_dirs(&path).await;
+        Ok(Some(size))
+    }
+
+    fn contains(&self, location: &str) -> bool {
+        let path = Path::new(location);
+        path.starts_with(&self.root)
+            && path != self.root
+            && !path.components().any(|c| c == Component::ParentDir)
+    }
+
+    fn local_path(&self, location: &str) -> Option<PathBuf> {
+        self.contains(location).then(|| PathBuf::from(location))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// S3-compatible object storage
+// ---------------------------------------------------------------------------
+
+/// Recordings kept in an S3-compatible bucket.
+#[derive(Debug)]
+pub struct S3AudioStorage {
+    store: Arc<dyn ObjectStore>,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3AudioStorage {
+    /// Connect to the bucket described by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be configured.
+    pub fn new(config: &S3Config) -> Result<Self> {
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(&config.bucket)
+            .with_region(&config.region)
+            .with_allow_http(config.allow_http);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.with_endpoint(endpoint);
+        }
+        if let Some(access_key_id) = &config.access_key_id {
+            builder = builder.with_access_key_id(access_key_id);
+        }
+        if let Some(secret_access_key) = &config.secret_access_key {
+            builder = builder.with_secret_access_key(secret_access_key);
+        }
+        let store = builder
+            .build()
+            .map_err(|e| StorageError::Audio(format!("invalid S3 configuration: {e}")))?;
+        Ok(Self::with_store(
+            Arc::new(store),
+            &config.bucket,
+            &config.prefix,
+        ))
+    }
+
+    /// Use an existing object store client for `bucket`.
+    #[must_use]
+    pub fn with_store(store: Arc<dyn ObjectStore>, bucket: &str, prefix: &str) -> Self {
+        Self {
+            store,
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+        }
+    }
+
+    /// Object path of a location in this bucket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `location` is not in this bucket.
+    fn object_path(&self, location: &str) -> Result<ObjectPath> {
+        location
+            .strip_prefix("s3://")
+            .and_then(|rest| rest.strip_prefix(self.bucket.as_str()))
+            .and_then(|rest| rest.strip_prefix('/'))
+            .filter(|key| validate_key(key).is_ok())
+            .and_then(|key| ObjectPath::parse(key).ok())
+            .ok_or_else(|| StorageError::Audio(format!("{location} is outside storage")))
+    }
+}
+
+#[async_trait]
+impl AudioStorage for S3AudioStorage {
+    async fn put(&self, key: &str, data: Bytes) -> Result<String> {
+        validate_key(key)?;
+        let key = if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}/{key}", self.prefix)
+        };
+        let path = ObjectPath::parse(&key)
+            .map_err(|e| StorageError::Audio(format!("invalid recording key {key}: {e}")))?;
+        let _ = self
+            .store
+            .put(&path, data.into())
+            .await
+            .map_err(|e| StorageError::Audio(format!("failed to upload {key}: {e}")))?;
+        Ok(format!("s3://{}/{path}", self.bucket))
+    }
+
+    async fn get(&self, location: &str) -> Result<Option<Bytes>> {
+        let path = self.object_path(location)?;
+        let object = match self.store.get(&path).await {
+            Ok(object) => object,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(StorageError::Audio(format!("failed to fetch {path}: {e}"))),
+        };
+        object
+            .bytes()
+            .await
+            .map(Some)
+            .map_err(|e| StorageError::Audio(format!("failed to fetch {path}: {e}")))
+    }
+
+    async fn delete(&self, location: &str) -> Result<Option<u64>> {
+        let path = self.object_path(location)?;
+        let size = match self.store.head(&path).await {
+            Ok(meta) => meta.size,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(StorageError::Audio(format!("failed to stat {path}: {e}"))),
+        };
+        self.store
+            .delete(&path)
+            .await
+            .map_err(|e| StorageError::Audio(format!("failed to delete {path}: {e}")))?;
+        Ok(Some(size))
+    }
+
+    fn contains(&self, location: &str) -> bool {
+        self.object_path(location).is_ok()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    fn key() -> String {
+        recording_key(
+            &SystemId::new("police").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            "call.mp3",
+        )
+    }
+
+    #[test]
+    fn test_recording_key() {
+        assert_eq!(key(), "police/2024/01/15/call.mp3");
+        assert!(validate_key(&key()).is_ok());
+        for bad in [
+            "",
+            "/abs/call.mp3",
+            "police/../call.mp3",
+            "police//call.mp3",
+        ] {
+            assert!(validate_key(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = LocalAudioStorage::new(dir.path(), "uploads");
+
+        let location = storage
+            .put(&key(), Bytes::from_static(b"abc"))
+            .await
+            .unwrap();
+        let path = dir.path().join("uploads/police/2024/01/15/call.mp3");
+        assert_eq!(location, path.to_string_lossy());
+        assert_eq!(storage.local_path(&location), Some(path));
+        assert_eq!(
+            storage.get(&location).await.unwrap(),
+            Some(Bytes::from_static(b"abc"))
+        );
+
+        assert_eq!(storage.delete(&location).await.unwrap(), Some(3));
+        assert_eq!(storage.delete(&location).await.unwrap(), None);
+        assert_eq!(storage.get(&location).await.unwrap(), None);
+        // Emptied date directories are pruned up to the root
+        assert!(!dir.path().join("uploads/police").exists());
+        assert!(dir.path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_stays_inside_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::NamedTempFile::new().unwrap();
+        let storage = LocalAudioStorage::new(dir.path(), "uploads");
+
+        let outside = outside.path().to_string_lossy().to_string();
+        let escaping = dir
+            .path()
+            .join("../etc/passwd")
+            .to_string_lossy()
+            .to_string();
+        let root = dir.path().to_string_lossy().to_string();
+        let sibling = format!("{root}-other/call.mp3");
+        for location in [
+            outside.as_str(),
+            escaping.as_str(),
+            root.as_str(),
+            sibling.as_str(),
+            "s3://bucket/key",
+        ] {
+            assert!(!storage.contains(location));
+            assert!(storage.get(location).await.is_err());
+            assert!(storage.delete(location).await.is_err());
+        }
+        assert!(storage.put("../escape.mp3", Bytes::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_s3_storage_round_trip() {
+        let storage =
+            S3AudioStorage::with_store(Arc::new(InMemory::new()), "radio", "/recordings/");
+
+        let location = storage
+            .put(&key(), Bytes::from_static(b"abcd"))
+            .await
+            .unwrap();
+        assert_eq!(location, "s3://radio/recordings/police/2024/01/15/call.mp3");
+        assert!(storage.contains(&location));
+        assert_eq!(storage.local_path(&location), None);
+        assert_eq!(
+            storage.get(&location).await.unwrap(),
+            Some(Bytes::from_static(b"abcd"))
+        );
+
+        assert_eq!(storage.delete(&location).await.unwrap(), Some(4));
+        assert_eq!(storage.delete(&location).await.unwrap(), None);
+        assert_eq!(storage.get(&location).await.unwrap(), None);
+
+        for foreign in [
+            "s3://other/recordings/a.mp3",
+            "/data/uploads/a.mp3",
+            "s3://radio/",
+        ] {
+            assert!(!storage.contains(foreign), "{foreign}");
+        }
+    }
+
+    #[test]
+    fn test_from_config_requires_s3_section() {
+        let mut config = sdrtrunk_protocol::Config::default().storage;
+        config.backend = StorageBackend::S3;
+        config.s3 = None;
+        assert!(from_config(&config).is_err());
+    }
+}
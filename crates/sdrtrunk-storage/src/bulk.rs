@@ -0,0 +1,629 @@
+//! Bulk archive and delete operations.
+//!
+//! Admins archive or delete every call matching a system, talkgroup, source
+//! radio, and/or date range filter in two steps. [`BulkQueries::prepare`]
+//! counts the matching calls and records a pending operation in
+//! `bulk_call_operations` under the hash of a confirmation token;
+//! [`BulkQueries::execute`] carries it out once the same requester repeats
+//! the request with that token before it expires. Each row records who asked,
+//! the filter, and how many calls matched and were affected.
+//!
+//! Archiving sets `radio_calls.archived_at` and records an `archived` call
+//! event: archived calls are kept past retention and left out of call
+//! listings unless asked for. Deleting removes calls the way a targeted purge
+//! does (see [`crate::purges`]) and returns their recording paths so the
+//! caller can remove the files after commit.
+
+use crate::error::StorageError;
+use crate::purges::delete_calls;
+use crate::retention::PurgedCall;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{RadioId, SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgPool, Postgres, postgres::PgArguments, query::QueryAs};
+use uuid::Uuid;
+
+/// Result type alias for bulk operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Columns of `bulk_call_operations` returned to callers (all but the token
+/// hash).
+const COLUMNS: &str = "id, action, status, requested_by, reason, system_id, talkgroup_id, \
+    source_radio_id, from_date, to_date, matched_calls, affected_calls, audio_files_deleted, \
+    audio_files_failed, expires_at, created_at, completed_at";
+
+/// Calls matching a [`BulkFilter`] bound as parameters `$1` to `$5`; `$6`
+/// says whether archived calls match too.
+const MATCHING: &str = "($1::VARCHAR IS NULL OR system_id = $1) \
+    AND ($2::INTEGER IS NULL OR talkgroup_id = $2) \
+    AND ($3::INTEGER IS NULL OR source_radio_id = $3) \
+    AND ($4::TIMESTAMPTZ IS NULL OR call_timestamp >= $4) \
+    AND ($5::TIMESTAMPTZ IS NULL OR call_timestamp <= $5) \
+    AND ($6::BOOLEAN OR archived_at IS NULL)";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// What a bulk operation does to the matching calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkAction {
+    /// Mark the calls archived.
+    Archive,
+    /// Erase the calls with their transcripts and recordings.
+    Delete,
+}
+
+impl BulkAction {
+    /// Name stored in `bulk_call_operations.action`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Archive => "archive",
+            Self::Delete => "delete",
+        }
+    }
+
+    /// Whether calls that are already archived match.
+    const fn includes_archived(self) -> bool {
+        matches!(self, Self::Delete)
+    }
+}
+
+/// Which calls a bulk operation affects; every given field must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkFilter {
+    /// Calls from this system.
+    pub system_id: Option<SystemId>,
+    /// Calls on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Calls transmitted by this radio.
+    pub source_radio_id: Option<RadioId>,
+    /// Calls at or after this time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Calls at or before this time.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl BulkFilter {
+    /// Whether no field is set (which would match every call).
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.system_id.is_none()
+            && self.talkgroup_id.is_none()
+            && self.source_radio_id.is_none()
+            && self.from_date.is_none()
+            && self.to_date.is_none()
+    }
+}
+
+/// A row from the `bulk_call_operations` audit log.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct BulkOperation {
+    /// Operation ID.
+    pub id: Uuid,
+    /// `archive` or `delete`.
+    pub action: String,
+    /// `pending` until confirmed, then `completed`.
+    pub status: String,
+    /// Who asked for the operation (`api_key:<id>` or `user:<name>`).
+    pub requested_by: String,
+    /// Why, as given by the requester.
+    pub reason: Option<String>,
+    /// System filter.
+    pub system_id: Option<SystemId>,
+    /// Talkgroup filter.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Source radio filter.
+    pub source_radio_id: Option<RadioId>,
+    /// Date range start.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Date range end.
+    pub to_date: Option<DateTime<Utc>>,
+    /// Calls that matched when the operation was requested.
+    pub matched_calls: i64,
+    /// Calls archived or deleted once confirmed.
+    pub affected_calls: Option<i64>,
+    /// Recordings removed from storage.
+    pub audio_files_deleted: i32,
+    /// Recordings that could not be removed.
+    pub audio_files_failed: i32,
+    /// When the confirmation token stops working.
+    pub expires_at: DateTime<Utc>,
+    /// When the operation was requested.
+    pub created_at: DateTime<Utc>,
+    /// When the operation was carried out.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A new pending bulk operation.
+#[derive(Debug, Clone)]
+pub struct NewBulkOperation<'a> {
+    /// What to do to the matching calls.
+    pub action: BulkAction,
+    /// Which calls to affect; must not be empty.
+    pub filter: &'a BulkFilter,
+    /// Who asks for the operation.
+    pub requested_by: &'a str,
+    /// Why, as given by the requester.
+    pub reason: Option<&'a str>,
+    /// Hash of the confirmation token.
+    pub token_hash: &'a str,
+    /// When the confirmation token stops working.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Completed operation and, for deletes, the deleted calls.
+pub type BulkOutcome = (BulkOperation, Vec<PurgedCall>);
+
+// ---------------------------------------------------------------------------
+// Bulk operations
+// ---------------------------------------------------------------------------
+
+/// Bulk archive and delete operations.
+#[derive(Debug)]
+pub struct BulkQueries;
+
+impl BulkQueries {
+    /// Count the calls an operation would affect and record it as pending.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filter is empty or the database query fails.
+    pub async fn prepare(pool: &PgPool, new: &NewBulkOperation<'_>) -> Result<BulkOperation> {
+        check_filter(new.filter)?;
+        let query = format!(
+            r"
+            INSERT INTO bulk_call_operations (
+                action, requested_by, reason, system_id, talkgroup_id, source_radio_id,
+                from_date, to_date, matched_calls, token_hash, expires_at
+            )
+            SELECT $7, $8, $9, $1, $2, $3, $4, $5, COUNT(*), $10, $11
+            FROM radio_calls
+            WHERE {MATCHING}
+            RETURNING {COLUMNS}
+            "
+        );
+        let operation = bind_filter(sqlx::query_as(&query), new.filter, new.action)
+            .bind(new.action.as_str())
+            .bind(new.requested_by)
+            .bind(new.reason)
+            .bind(new.token_hash)
+            .bind(new.expires_at)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(operation)
+    }
+
+    /// Carry out the pending operation recorded under `token_hash`.
+    ///
+    /// The operation must still be pending and unexpired, and `action`,
+    /// `filter`, and `requested_by` must match the request that prepared it.
+    /// Returns `None` otherwise, in which case nothing is changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails, in which case nothing is changed.
+    pub async fn execute(
+        pool: &PgPool,
+        token_hash: &str,
+        action: BulkAction,
+        filter: &BulkFilter,
+        requested_by: &str,
+    ) -> Result<Option<BulkOutcome>> {
+        let mut tx = pool.begin().await?;
+
+        let pending: Option<Uuid> = sqlx::query_scalar(
+            r"
+            SELECT id FROM bulk_call_operations
+            WHERE token_hash = $1
+              AND status = 'pending'
+              AND expires_at > NOW()
+              AND action = $2
+              AND requested_by = $3
+              AND system_id IS NOT DISTINCT FROM $4
+              AND talkgroup_id IS NOT DISTINCT FROM $5
+              AND source_radio_id IS NOT DISTINCT FROM $6
+              AND from_date IS NOT DISTINCT FROM $7
+              AND to_date IS NOT DISTINCT FROM $8
+            FOR UPDATE
+            ",
+        )
+        .bind(token_hash)
+        .bind(action.as_str())
+        .bind(requested_by)
+        .bind(&filter.system_id)
+        .bind(filter.talkgroup_id)
+        .bind(filter.source_radio_id)
+        .bind(filter.from_date)
+        .bind(filter.to_date)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(id) = pending else {
+            return Ok(None);
+        };
+
+        let (affected, calls) = apply(&mut tx, id, action, filter).await?;
+
+        let query = format!(
+            r"
+            UPDATE bulk_call_operations
+            SET status = 'completed', affected_calls = $2, completed_at = NOW()
+            WHERE id = $1
+            RETURNING {COLUMNS}
+            "
+        );
+        let operation = sqlx::query_as::<_, BulkOperation>(&query)
+            .bind(id)
+            .bind(affected)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some((operation, calls)))
+    }
+
+    /// Record how removing a bulk delete's recordings went.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn record_files(
+        pool: &PgPool,
+        operation_id: Uuid,
+        deleted: usize,
+        failed: usize,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE bulk_call_operations
+            SET audio_files_deleted = $2, audio_files_failed = $3
+            WHERE id = $1
+            ",
+        )
+        .bind(operation_id)
+        .bind(i32::try_from(deleted).unwrap_or(i32::MAX))
+        .bind(i32::try_from(failed).unwrap_or(i32::MAX))
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Recent bulk operations, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(pool: &PgPool, limit: i64) -> Result<Vec<BulkOperation>> {
+        let query =
+            format!("SELECT {COLUMNS} FROM bulk_call_operations ORDER BY created_at DESC LIMIT $1");
+        let operations = sqlx::query_as::<_, BulkOperation>(&query)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(operations)
+    }
+}
+
+/// Archive or delete the calls matching `filter` for operation `id`,
+/// returning how many were affected and, for deletes, the deleted calls.
+/// Archived calls get an `archived` call event naming the operation.
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+async fn apply(
+    conn: &mut PgConnection,
+    id: Uuid,
+    action: BulkAction,
+    filter: &BulkFilter,
+) -> Result<(i64, Vec<PurgedCall>)> {
+    match action {
+        BulkAction::Archive => {
+            let query = format!(
+                r"
+                WITH archived AS (
+                    UPDATE radio_calls SET archived_at = NOW()
+                    WHERE {MATCHING}
+                    RETURNING id
+                ),
+                event AS (
+                    INSERT INTO call_events (call_id, event, detail)
+                    SELECT id, 'archived', 'bulk operation ' || $7::UUID FROM archived
+                )
+                SELECT COUNT(*) FROM archived
+                "
+            );
+            let (archived,): (i64,) = bind_filter(sqlx::query_as(&query), filter, action)
+                .bind(id)
+                .fetch_one(&mut *conn)
+                .await?;
+            Ok((archived, Vec::new()))
+        }
+        BulkAction::Delete => {
+            let query = format!(
+                r"
+                SELECT id, system_id, audio_file_path, audio_size_bytes
+                FROM radio_calls
+                WHERE {MATCHING}
+                FOR UPDATE
+                "
+            );
+            let calls: Vec<PurgedCall> = bind_filter(sqlx::query_as(&query), filter, action)
+                .fetch_all(&mut *conn)
+                .await?;
+            let ids: Vec<Uuid> = calls.iter().map(|call| call.id).collect();
+            let _ = delete_calls(conn, &ids).await?;
+            Ok((i64::try_from(calls.len()).unwrap_or(i64::MAX), calls))
+        }
+    }
+}
+
+/// Reject filters that would match every call.
+///
+/// # Errors
+///
+/// Returns an error if the filter is empty.
+fn check_filter(filter: &BulkFilter) -> Result<()> {
+    if filter.is_empty() {
+        return Err(StorageError::ConstraintViolation {
+            constraint: "bulk operation filter must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Bind a filter as the parameters of [`MATCHING`].
+fn bind_filter<'q, O>(
+    query: QueryAs<'q, Postgres, O, PgArguments>,
+    filter: &'q BulkFilter,
+    action: BulkAction,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    query
+        .bind(&filter.system_id)
+        .bind(filter.talkgroup_id)
+        .bind(filter.source_radio_id)
+        .bind(filter.from_date)
+        .bind(filter.to_date)
+        .bind(action.includes_archived())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::events::CallEventQueries;
+    use crate::models::RadioCallDb;
+    use crate::queries::{RadioCallFilter, RadioCallQueries, list_radio_calls_filtered};
+    use crate::test_support::{create_test_pool, test_call};
+    use chrono::Duration;
+
+    fn call(system_id: &SystemId, talkgroup: i32) -> RadioCallDb {
+        RadioCallDb {
+            talkgroup_id: Some(TalkgroupId::new(talkgroup).unwrap()),
+            audio_filename: Some(format!("{}.mp3", Uuid::new_v4())),
+            audio_file_path: Some("recordings/a.mp3".to_string()),
+            audio_size_bytes: Some(1024),
+            ..test_call(system_id)
+        }
+    }
+
+    fn listing(system_id: &SystemId, archived: bool) -> RadioCallFilter<'_> {
+        RadioCallFilter {
+            system_id: Some(system_id),
+            allowed_systems: None,
+            talkgroup_id: None,
+            transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived,
+            from_date: None,
+            to_date: None,
+            limit: 10,
+            after: None,
+        }
+    }
+
+    fn pending<'a>(
+        action: BulkAction,
+        filter: &'a BulkFilter,
+        token_hash: &'a str,
+    ) -> NewBulkOperation<'a> {
+        NewBulkOperation {
+            action,
+            filter,
+            requested_by: "user:admin",
+            reason: Some("cleanup"),
+            token_hash,
+            expires_at: Utc::now() + Duration::minutes(10),
+        }
+    }
+
+    #[test]
+    fn test_filter_is_empty() {
+        assert!(BulkFilter::default().is_empty());
+        assert!(
+            !BulkFilter {
+                to_date: Some(Utc::now()),
+                ..BulkFilter::default()
+            }
+            .is_empty()
+        );
+        assert_eq!(BulkAction::Archive.as_str(), "archive");
+        assert_eq!(
+            serde_json::from_str::<BulkAction>("\"delete\"").unwrap(),
+            BulkAction::Delete
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn test_archive_then_delete() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("blk_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let archived = call(&system_id, 100);
+        let kept = call(&system_id, 200);
+        RadioCallQueries::insert(&pool, &archived).await.unwrap();
+        RadioCallQueries::insert(&pool, &kept).await.unwrap();
+
+        assert!(
+            BulkQueries::prepare(
+                &pool,
+                &pending(BulkAction::Archive, &BulkFilter::default(), "x")
+            )
+            .await
+            .is_err()
+        );
+
+        let filter = BulkFilter {
+            system_id: Some(system_id.clone()),
+            talkgroup_id: Some(TalkgroupId::new(100).unwrap()),
+            ..BulkFilter::default()
+        };
+        let token = format!("{:0>64}", Uuid::new_v4().simple());
+        let prepared = BulkQueries::prepare(&pool, &pending(BulkAction::Archive, &filter, &token))
+            .await
+            .unwrap();
+        assert_eq!(prepared.status, "pending");
+        assert_eq!(prepared.matched_calls, 1);
+
+        // The token only confirms the request it was issued for
+        let other = BulkFilter {
+            talkgroup_id: None,
+            ..filter.clone()
+        };
+        for (action, filter, requested_by) in [
+            (BulkAction::Delete, &filter, "user:admin"),
+            (BulkAction::Archive, &other, "user:admin"),
+            (BulkAction::Archive, &filter, "user:other"),
+        ] {
+            assert!(
+                BulkQueries::execute(&pool, &token, action, filter, requested_by)
+                    .await
+                    .unwrap()
+                    .is_none()
+            );
+        }
+
+        let (operation, calls) =
+            BulkQueries::execute(&pool, &token, BulkAction::Archive, &filter, "user:admin")
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(operation.status, "completed");
+        assert_eq!(operation.affected_calls, Some(1));
+        assert!(calls.is_empty());
+
+        // Tokens are single-use
+        assert!(
+            BulkQueries::execute(&pool, &token, BulkAction::Archive, &filter, "user:admin")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let live = list_radio_calls_filtered(&pool, listing(&system_id, false))
+            .await
+            .unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].id, kept.id);
+        let archive = list_radio_calls_filtered(&pool, listing(&system_id, true))
+            .await
+            .unwrap();
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive[0].id, archived.id);
+        let events = CallEventQueries::for_call(&pool, archived.id)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "archived");
+        let detail = format!("bulk operation {}", operation.id);
+        assert_eq!(events[0].detail.as_deref(), Some(detail.as_str()));
+        assert!(
+            CallEventQueries::for_call(&pool, kept.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Deleting matches archived calls too
+        let filter = BulkFilter {
+            system_id: Some(system_id.clone()),
+            ..BulkFilter::default()
+        };
+        let token = format!("{:0>64}", Uuid::new_v4().simple());
+        let prepared = BulkQueries::prepare(&pool, &pending(BulkAction::Delete, &filter, &token))
+            .await
+            .unwrap();
+        assert_eq!(prepared.matched_calls, 2);
+        let (operation, calls) =
+            BulkQueries::execute(&pool, &token, BulkAction::Delete, &filter, "user:admin")
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(operation.affected_calls, Some(2));
+        assert_eq!(calls.len(), 2);
+        assert!(RadioCallQueries::find_by_id(&pool, kept.id).await.is_err());
+
+        BulkQueries::record_files(&pool, operation.id, 2, 0)
+            .await
+            .unwrap();
+        let recent = BulkQueries::list(&pool, 100).await.unwrap();
+        let logged = recent.iter().find(|op| op.id == operation.id).unwrap();
+        assert_eq!(logged.audio_files_deleted, 2);
+        assert_eq!(logged.reason.as_deref(), Some("cleanup"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_rejected() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("blk_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        RadioCallQueries::insert(&pool, &call(&system_id, 100))
+            .await
+            .unwrap();
+        let filter = BulkFilter {
+            system_id: Some(system_id.clone()),
+            ..BulkFilter::default()
+        };
+        let token = format!("{:0>64}", Uuid::new_v4().simple());
+        let mut new = pending(BulkAction::Delete, &filter, &token);
+        new.expires_at = Utc::now() - Duration::seconds(1);
+        BulkQueries::prepare(&pool, &new).await.unwrap();
+
+        assert!(
+            BulkQueries::execute(&pool, &token, BulkAction::Delete, &filter, "user:admin")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let calls = list_radio_calls_filtered(&pool, listing(&system_id, false))
+            .await
+            .unwrap();
+        assert_eq!(calls.len(), 1);
+    }
+}
@@ -0,0 +1,385 @@
+//! Conversations.
+//!
+//! Consecutive calls on the same talkgroup form a conversation when each call
+//! starts within a configured gap of the previous one ending. Uploads join
+//! their call to the conversation it continues, or start a new one, so a
+//! dispatcher/unit exchange can be read as one thread. Membership lives in
+//! `conversation_calls` and is removed with the call.
+
+use crate::error::StorageError;
+use crate::models::RadioCallDb;
+use crate::queries::system_names;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for conversation operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A conversation with its call count.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Conversation {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// System the talkgroup belongs to.
+    pub system_id: SystemId,
+    /// Talkgroup the calls were heard on.
+    pub talkgroup_id: TalkgroupId,
+    /// Talkgroup display name, taken from the calls.
+    pub talkgroup_label: Option<String>,
+    /// Start of the first call.
+    pub started_at: DateTime<Utc>,
+    /// End of the last call.
+    pub ended_at: DateTime<Utc>,
+    /// Calls in the conversation.
+    pub call_count: i64,
+}
+
+/// Filters for listing conversations.
+#[derive(Debug, Clone, Default)]
+pub struct ConversationFilter {
+    /// Only conversations on this system.
+    pub system_id: Option<SystemId>,
+    /// Only conversations for these systems (an API key's tenant scope).
+    pub allowed_systems: Option<Vec<SystemId>>,
+    /// Only conversations on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only conversations still going at or after this time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only conversations started before this time.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// Conversations with their call counts, leaving out conversations whose
+/// calls have all been deleted.
+const CONVERSATION_SELECT: &str = r"
+    SELECT c.id, c.system_id, c.talkgroup_id, m.talkgroup_label,
+           c.started_at, c.ended_at, m.call_count
+    FROM conversations c
+    CROSS JOIN LATERAL (
+        SELECT COUNT(*) AS call_count, MAX(rc.talkgroup_label) AS talkgroup_label
+        FROM conversation_calls cc
+        JOIN radio_calls rc ON rc.id = cc.call_id
+        WHERE cc.conversation_id = c.id
+    ) m
+    WHERE m.call_count > 0
+";
+
+/// Conversations matching a [`ConversationFilter`]; binds `$1`–`$5`.
+const CONVERSATION_FILTER: &str = r"
+      AND ($1::VARCHAR IS NULL OR c.system_id = $1)
+      AND ($2::INTEGER IS NULL OR c.talkgroup_id = $2)
+      AND ($3::TIMESTAMPTZ IS NULL OR c.ended_at >= $3)
+      AND ($4::TIMESTAMPTZ IS NULL OR c.started_at < $4)
+      AND ($5::TEXT[] IS NULL OR c.system_id = ANY($5))
+";
+
+// ---------------------------------------------------------------------------
+// Conversation operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for conversations.
+#[derive(Debug)]
+pub struct ConversationQueries;
+
+impl ConversationQueries {
+    /// Add a stored call to the conversation it continues, or start one.
+    ///
+    /// A call continues a conversation on its talkgroup when it overlaps it
+    /// or starts at most `gap_seconds` after it ends (or ends at most that
+    /// long before it starts, for calls uploaded late). Returns the
+    /// conversation ID, or `None` for calls without a talkgroup.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if a query fails.
+    pub async fn assign(
+        pool: &PgPool,
+        call: &RadioCallDb,
+        gap_seconds: u32,
+    ) -> Result<Option<Uuid>> {
+        let Some(talkgroup_id) = call.talkgroup_id else {
+            return Ok(None);
+        };
+        let started_at = call.call_timestamp;
+        let ended_at = started_at + call_length(call);
+        let gap = Duration::seconds(i64::from(gap_seconds));
+
+        let mut tx = pool.begin().await?;
+
+        // Uploads for one talkgroup arrive concurrently; serialize them so two
+        // calls cannot start separate conversations
+        let _ = sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1), $2)")
+            .bind(&call.system_id)
+            .bind(talkgroup_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let existing: Option<Uuid> = sqlx::query_scalar(
+            r"
+            UPDATE conversations SET
+                started_at = LEAST(started_at, $3),
+                ended_at = GREATEST(ended_at, $4),
+                updated_at = NOW()
+            WHERE id = (
+                SELECT id FROM conversations
+                WHERE system_id = $1 AND talkgroup_id = $2
+                  AND started_at <= $4 + $5 AND ended_at >= $3 - $5
+                ORDER BY ended_at DESC
+                LIMIT 1
+            )
+            RETURNING id
+            ",
+        )
+        .bind(&call.system_id)
+        .bind(talkgroup_id)
+        .bind(started_at)
+        .bind(ended_at)
+        .bind(gap)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let conversation_id = match existing {
+            Some(id) => id,
+            None => {
+                sqlx::query_scalar(
+                    r"
+                    INSERT INTO conversations (system_id, talkgroup_id, started_at, ended_at)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING id
+                    ",
+                )
+                .bind(&call.system_id)
+                .bind(talkgroup_id)
+                .bind(started_at)
+                .bind(ended_at)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+
+        let _ = sqlx::query(
+            r"
+            INSERT INTO conversation_calls (call_id, conversation_id)
+            VALUES ($1, $2)
+            ON CONFLICT (call_id) DO NOTHING
+            ",
+        )
+        .bind(call.id)
+        .bind(conversation_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(conversation_id))
+    }
+
+    /// Look up one conversation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn get(pool: &PgPool, id: Uuid) -> Result<Option<Conversation>> {
+        let query = format!("{CONVERSATION_SELECT} AND c.id = $1");
+        let conversation = sqlx::query_as::<_, Conversation>(&query)
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(conversation)
+    }
+
+    /// Calls of a conversation, in the order they were heard.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn calls(pool: &PgPool, id: Uuid) -> Result<Vec<RadioCallDb>> {
+        let calls = sqlx::query_as::<_, RadioCallDb>(
+            r"
+            SELECT rc.*
+            FROM conversation_calls cc
+            JOIN radio_calls rc ON rc.id = cc.call_id
+            WHERE cc.conversation_id = $1
+            ORDER BY rc.call_timestamp, rc.id
+            ",
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(calls)
+    }
+
+    /// List conversations matching `filter`, most recent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(
+        pool: &PgPool,
+        filter: &ConversationFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Conversation>> {
+        let query = format!(
+            "{CONVERSATION_SELECT} {CONVERSATION_FILTER} ORDER BY c.ended_at DESC, c.id LIMIT $6 OFFSET $7"
+        );
+        let conversations = sqlx::query_as::<_, Conversation>(&query)
+            .bind(filter.system_id.as_ref())
+            .bind(filter.talkgroup_id)
+            .bind(filter.from_date)
+            .bind(filter.to_date)
+            .bind(filter.allowed_systems.as_deref().map(system_names))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(conversations)
+    }
+
+    /// Count conversations matching `filter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn count(pool: &PgPool, filter: &ConversationFilter) -> Result<i64> {
+        let query = format!("SELECT COUNT(*) FROM ({CONVERSATION_SELECT} {CONVERSATION_FILTER}) c");
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(filter.system_id.as_ref())
+            .bind(filter.talkgroup_id)
+            .bind(filter.from_date)
+            .bind(filter.to_date)
+            .bind(filter.allowed_systems.as_deref().map(system_names))
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+}
+
+/// Length of a call's recording, zero when unknown.
+fn call_length(call: &RadioCallDb) -> Duration {
+    call.duration_seconds
+        .and_then(|seconds| (seconds * rust_decimal::Decimal::ONE_THOUSAND).to_i64())
+        .map_or_else(Duration::zero, Duration::milliseconds)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+    use rust_decimal::Decimal;
+
+    fn call(
+        system_id: &SystemId,
+        talkgroup_id: Option<i32>,
+        at: DateTime<Utc>,
+        seconds: i64,
+    ) -> RadioCallDb {
+        RadioCallDb {
+            talkgroup_id: talkgroup_id.map(|id| TalkgroupId::new(id).unwrap()),
+            talkgroup_label: Some("Dispatch".to_string()),
+            duration_seconds: Some(Decimal::from(seconds)),
+            transcription_status: None,
+            ..test_call_at(system_id, at)
+        }
+    }
+
+    #[test]
+    fn test_call_length() {
+        let system_id = SystemId::new("metro").unwrap();
+        let mut radio_call = call(&system_id, Some(1), Utc::now(), 0);
+        radio_call.duration_seconds = Some(Decimal::new(12_345, 3));
+        assert_eq!(call_length(&radio_call), Duration::milliseconds(12_345));
+
+        radio_call.duration_seconds = None;
+        assert_eq!(call_length(&radio_call), Duration::zero());
+    }
+
+    #[tokio::test]
+    async fn test_assign_groups_calls_within_gap() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("cv_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let start = Utc::now() - Duration::hours(1);
+        let calls = [
+            call(&system_id, Some(100), start, 10),
+            // Starts 5s after the first ends
+            call(&system_id, Some(100), start + Duration::seconds(15), 4),
+            // Different talkgroup at the same time
+            call(&system_id, Some(200), start + Duration::seconds(16), 4),
+            // A minute of silence later
+            call(&system_id, Some(100), start + Duration::seconds(80), 4),
+            call(&system_id, None, start, 4),
+        ];
+        let mut ids = Vec::new();
+        for radio_call in &calls {
+            RadioCallQueries::insert(&pool, radio_call).await.unwrap();
+            ids.push(
+                ConversationQueries::assign(&pool, radio_call, 30)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        assert_eq!(ids[0], ids[1]);
+        assert_ne!(ids[0], ids[2]);
+        assert_ne!(ids[0], ids[3]);
+        assert!(ids[4].is_none());
+
+        let conversation = ConversationQueries::get(&pool, ids[0].unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(conversation.call_count, 2);
+        assert_eq!(conversation.talkgroup_label.as_deref(), Some("Dispatch"));
+        assert_eq!(
+            conversation.ended_at.timestamp(),
+            (start + Duration::seconds(19)).timestamp()
+        );
+        let members: Vec<Uuid> = ConversationQueries::calls(&pool, conversation.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(members, vec![calls[0].id, calls[1].id]);
+
+        let filter = ConversationFilter {
+            system_id: Some(system_id.clone()),
+            talkgroup_id: Some(TalkgroupId::new(100).unwrap()),
+            ..ConversationFilter::default()
+        };
+        assert_eq!(ConversationQueries::count(&pool, &filter).await.unwrap(), 2);
+        let listed = ConversationQueries::list(&pool, &filter, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(listed[0].id, ids[3].unwrap());
+        assert_eq!(listed[1].id, conversation.id);
+    }
+}
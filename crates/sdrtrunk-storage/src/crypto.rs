@@ -0,0 +1,332 @@
+//! Encryption for sensitive columns.
+//!
+//! A [`FieldCipher`] seals values with AES-256-GCM under a key from the
+//! configuration. Each value gets a fresh random nonce, stored in front of
+//! the ciphertext, so the column holds `nonce || ciphertext || tag`.
+//!
+//! A [`KeyRing`] adds key rotation on top: values are sealed with the current
+//! key and prefixed with its [`KeyId`], so values sealed with an earlier key
+//! can still be opened, and found, until they are re-sealed.
+
+use crate::error::{Result, StorageError};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::digest::{SHA256, digest};
+use ring::rand::{SecureRandom, SystemRandom};
+use sdrtrunk_protocol::config::EncryptionConfig;
+
+/// AES-256 key length in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Length of the key ID in front of values sealed by a [`KeyRing`].
+pub const KEY_ID_LEN: usize = 4;
+
+/// Identifies the key a value was sealed with: the first bytes of the key's
+/// SHA-256 digest.
+pub type KeyId = [u8; KEY_ID_LEN];
+
+/// Seals and opens column values with one key.
+pub struct FieldCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl std::fmt::Debug for FieldCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldCipher").finish_non_exhaustive()
+    }
+}
+
+impl FieldCipher {
+    /// Cipher for a base64-encoded 32-byte key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if the key is not base64 or not
+    /// 32 bytes long.
+    pub fn from_base64_key(encoded: &str) -> Result<Self> {
+        Self::new(&decode_key(encoded)?)
+    }
+
+    fn new(bytes: &[u8]) -> Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, bytes)
+            .map_err(|_| StorageError::Encryption("invalid key".to_string()))?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Seal `plaintext`, returning the nonce followed by the ciphertext.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if no random nonce is available.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| StorageError::Encryption("no random nonce available".to_string()))?;
+        let mut sealed = plaintext.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut sealed,
+            )
+            .map_err(|_| StorageError::Encryption("sealing failed".to_string()))?;
+        let mut out = Vec::with_capacity(NONCE_LEN + sealed.len());
+        out.extend_from_slice(&nonce);
+        out.append(&mut sealed);
+        Ok(out)
+    }
+
+    /// Open a value produced by [`encrypt`](Self::encrypt).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if the value is truncated, was
+    /// sealed with another key, or has been tampered with.
+    pub fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        let (nonce, ciphertext) = sealed
+            .split_first_chunk::<NONCE_LEN>()
+            .ok_or_else(|| StorageError::Encryption("value is truncated".to_string()))?;
+        let mut opened = ciphertext.to_vec();
+        let plaintext_len = self
+            .key
+            .open_in_place(
+                Nonce::assume_unique_for_key(*nonce),
+                Aad::empty(),
+                &mut opened,
+            )
+            .map_err(|_| StorageError::Encryption("value could not be opened".to_string()))?
+            .len();
+        opened.truncate(plaintext_len);
+        Ok(opened)
+    }
+}
+
+/// The current column key and the keys it replaced.
+pub struct KeyRing {
+    /// Current key first
+    keys: Vec<(KeyId, FieldCipher)>,
+}
+
+impl std::fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyRing")
+            .field("keys", &self.keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyRing {
+    /// Key ring sealing with `current` and also opening with `previous`,
+    /// all base64-encoded 32-byte keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if a key is not base64 or not 32
+    /// bytes long.
+    pub fn new(current: &str, previous: &[String]) -> Result<Self> {
+        let keys = std::iter::once(current)
+            .chain(previous.iter().map(String::as_str))
+            .map(|encoded| {
+                let bytes = decode_key(encoded)?;
+                Ok((key_id(&bytes), FieldCipher::new(&bytes)?))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { keys })
+    }
+
+    /// Key ring for `config`, or `None` when encryption is off.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Configuration`] if no key is configured or the
+    /// key file cannot be read, and [`StorageError::Encryption`] if a key is
+    /// invalid.
+    pub fn from_config(config: &EncryptionConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let current = match (&config.key, &config.key_file) {
+            (Some(key), _) => key.clone(),
+            (None, Some(path)) => std::fs::read_to_string(path).map_err(|e| {
+                StorageError::Configuration(format!(
+                    "cannot read encryption key file {}: {e}",
+                    path.display()
+                ))
+            })?,
+            (None, None) => {
+                return Err(StorageError::Configuration(
+                    "encryption is enabled without a key or key_file".to_string(),
+                ));
+            }
+        };
+        Self::new(&current, &config.previous_keys).map(Some)
+    }
+
+    /// ID of the key new values are sealed with.
+    #[must_use]
+    pub fn current_key_id(&self) -> KeyId {
+        self.keys.first().map_or([0; KEY_ID_LEN], |(id, _)| *id)
+    }
+
+    /// Seal `plaintext` with the current key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if sealing fails.
+    pub fn seal(&self, plaintext: &str) -> Result<Vec<u8>> {
+        let (id, cipher) = self
+            .keys
+            .first()
+            .ok_or_else(|| StorageError::Encryption("no key".to_string()))?;
+        let sealed = cipher.encrypt(plaintext.as_bytes())?;
+        let mut out = Vec::with_capacity(KEY_ID_LEN + sealed.len());
+        out.extend_from_slice(id);
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+
+    /// Open a value sealed by [`seal`](Self::seal) with any key in the ring.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if the value was sealed with a key
+    /// not in the ring, cannot be opened, or is not UTF-8.
+    pub fn open(&self, sealed: &[u8]) -> Result<String> {
+        let (id, value) = sealed
+            .split_first_chunk::<KEY_ID_LEN>()
+            .ok_or_else(|| StorageError::Encryption("value is truncated".to_string()))?;
+        let (_, cipher) = self
+            .keys
+            .iter()
+            .find(|(key_id, _)| key_id == id)
+            .ok_or_else(|| {
+                StorageError::Encryption(format!("value is sealed with unknown key {}", hex_id(id)))
+            })?;
+        String::from_utf8(cipher.decrypt(value)?)
+            .map_err(|e| StorageError::Encryption(format!("value is not UTF-8: {e}")))
+    }
+}
+
+fn decode_key(encoded: &str) -> Result<Vec<u8>> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| StorageError::Encryption(format!("key is not base64: {e}")))?;
+    if bytes.len() != KEY_LEN {
+        return Err(StorageError::Encryption(format!(
+            "key must be {KEY_LEN} bytes, got {}",
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+fn key_id(key: &[u8]) -> KeyId {
+    let mut id = [0; KEY_ID_LEN];
+    if let Some(prefix) = digest(&SHA256, key).as_ref().first_chunk::<KEY_ID_LEN>() {
+        id = *prefix;
+    }
+    id
+}
+
+fn hex_id(id: &KeyId) -> String {
+    id.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn cipher(byte: u8) -> FieldCipher {
+        FieldCipher::from_base64_key(&STANDARD.encode([byte; KEY_LEN])).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = cipher(7);
+        let sealed = cipher.encrypt(b"Officer Smith at 12 Oak St").unwrap();
+        assert_ne!(&sealed[NONCE_LEN..], b"Officer Smith at 12 Oak St");
+        assert_eq!(
+            cipher.decrypt(&sealed).unwrap(),
+            b"Officer Smith at 12 Oak St"
+        );
+        // Fresh nonce per value
+        assert_ne!(
+            sealed,
+            cipher.encrypt(b"Officer Smith at 12 Oak St").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_wrong_key_and_tampering() {
+        let sealed = cipher(1).encrypt(b"secret").unwrap();
+        assert!(cipher(2).decrypt(&sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(cipher(1).decrypt(&tampered).is_err());
+        assert!(cipher(1).decrypt(&sealed[..4]).is_err());
+    }
+
+    #[test]
+    fn test_key_ring_rotation() {
+        let old_key = STANDARD.encode([1u8; KEY_LEN]);
+        let new_key = STANDARD.encode([2u8; KEY_LEN]);
+        let old = KeyRing::new(&old_key, &[]).unwrap();
+        let sealed = old.seal("ENGINE 4").unwrap();
+        assert_eq!(sealed[..KEY_ID_LEN], old.current_key_id());
+
+        let rotated = KeyRing::new(&new_key, &[old_key]).unwrap();
+        assert_ne!(rotated.current_key_id(), old.current_key_id());
+        assert_eq!(rotated.open(&sealed).unwrap(), "ENGINE 4");
+        let resealed = rotated.seal("ENGINE 4").unwrap();
+        assert_eq!(resealed[..KEY_ID_LEN], rotated.current_key_id());
+
+        // Once the old key is dropped its values no longer open
+        let new_only = KeyRing::new(&new_key, &[]).unwrap();
+        assert!(new_only.open(&sealed).is_err());
+        assert_eq!(new_only.open(&resealed).unwrap(), "ENGINE 4");
+    }
+
+    #[test]
+    fn test_key_ring_from_config() {
+        let mut config = EncryptionConfig::default();
+        assert!(KeyRing::from_config(&config).unwrap().is_none());
+
+        config.enabled = true;
+        assert!(matches!(
+            KeyRing::from_config(&config),
+            Err(StorageError::Configuration(_))
+        ));
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            format!("{}\n", STANDARD.encode([3u8; KEY_LEN])),
+        )
+        .unwrap();
+        config.key_file = Some(file.path().to_path_buf());
+        let ring = KeyRing::from_config(&config).unwrap().unwrap();
+        assert_eq!(ring.open(&ring.seal("x").unwrap()).unwrap(), "x");
+    }
+
+    #[test]
+    fn test_rejects_bad_keys() {
+        assert!(FieldCipher::from_base64_key("not base64!").is_err());
+        assert!(FieldCipher::from_base64_key(&STANDARD.encode([0u8; 16])).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! Synthetic demo data.
+//!
+//! Generates a small, deterministic set of systems, talkgroups, and completed
+//! calls with canned transcripts so the web UI can be explored without any
+//! radio hardware. Used by the API server's `--demo` mode.
+
+use crate::error::StorageError;
+use crate::models::{RadioCallDb, SystemStatsDb};
+use crate::queries::{RadioCallQueries, SystemStatsQueries};
+use crate::radios::RadioQueries;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Result type alias for demo operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// A generated demo system and its talkgroups.
+#[derive(Debug, Clone, Copy)]
+pub struct DemoSystem {
+    /// System identifier.
+    pub id: &'static str,
+    /// Human-readable system label.
+    pub label: &'static str,
+    /// Control channel frequency in Hz.
+    pub frequency: i64,
+    /// Talkgroups on this system.
+    pub talkgroups: &'static [DemoTalkgroup],
+}
+
+/// A generated demo talkgroup.
+#[derive(Debug, Clone, Copy)]
+pub struct DemoTalkgroup {
+    /// Talkgroup decimal ID.
+    pub id: i32,
+    /// Talkgroup label.
+    pub label: &'static str,
+    /// Talkgroup group.
+    pub group: &'static str,
+    /// Talkgroup tag.
+    pub tag: &'static str,
+}
+
+const fn tg(id: i32, label: &'static str, group: &'static str, tag: &'static str) -> DemoTalkgroup {
+    DemoTalkgroup {
+        id,
+        label,
+        group,
+        tag,
+    }
+}
+
+/// Systems seeded in demo mode.
+pub const DEMO_SYSTEMS: &[DemoSystem] = &[
+    DemoSystem {
+        id: "demo_metro",
+        label: "Demo Metro P25",
+        frequency: 851_012_500,
+        talkgroups: &[
+            tg(1001, "Police Dispatch", "Law Enforcement", "Law Dispatch"),
+            tg(1002, "Police Tac 1", "Law Enforcement", "Law Tac"),
+            tg(2001, "Fire Dispatch", "Fire", "Fire Dispatch"),
+        ],
+    },
+    DemoSystem {
+        id: "demo_county",
+        label: "Demo County Simulcast",
+        frequency: 853_587_500,
+        talkgroups: &[
+            tg(3001, "EMS Dispatch", "EMS", "EMS Dispatch"),
+            tg(3002, "Hospital Ops", "EMS", "Hospital"),
+            tg(4001, "Public Works", "Services", "Public Works"),
+        ],
+    },
+    DemoSystem {
+        id: "demo_state",
+        label: "Demo Statewide",
+        frequency: 772_256_250,
+        talkgroups: &[
+            tg(5001, "Highway Patrol", "State", "Law Dispatch"),
+            tg(5002, "Interop 1", "State", "Interop"),
+        ],
+    },
+];
+
+/// Canned transcripts assigned to demo calls (and used by the demo mock
+/// transcriber for newly uploaded calls).
+pub const DEMO_TRANSCRIPTS: &[&str] = &[
+    "Unit 23 responding to a traffic stop at Main and Fifth.",
+    "Dispatch, Engine 4 is on scene, nothing showing, investigating.",
+    "Medic 12 transporting one patient to General, ETA ten minutes.",
+    "Copy that, all units be advised the road is closed at the bridge.",
+    "Requesting a tow truck for a two vehicle accident, no injuries.",
+    "Public works crew en route to the water main break on Elm Street.",
+    "Ten four, show me clear and available.",
+    "Be on the lookout for a blue sedan heading northbound on Route 9.",
+];
+
+/// Summary of a demo seeding run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DemoSeedSummary {
+    /// Number of systems seeded.
+    pub systems: usize,
+    /// Number of calls inserted.
+    pub calls: usize,
+    /// Whether seeding was skipped because demo data already exists.
+    pub skipped: bool,
+}
+
+/// Generate demo calls spread over the `hours` before `now`.
+///
+/// Output is deterministic for a given `now`, so repeated runs produce the
+/// same shape of data.
+///
+/// # Errors
+///
+/// Returns an error if a demo system ID fails validation.
+pub fn generate_demo_calls(
+    now: DateTime<Utc>,
+    calls_per_talkgroup: usize,
+    hours: i64,
+) -> Result<Vec<RadioCallDb>> {
+    let span_minutes = (hours.max(1) * 60).max(1);
+    let mut calls = Vec::new();
+    let mut seq: usize = 0;
+
+    for system in DEMO_SYSTEMS {
+        let system_id = demo_system_id(system)?;
+        for talkgroup in system.talkgroups {
+            for _ in 0..calls_per_talkgroup {
+                calls.push(demo_call(
+                    system,
+                    &system_id,
+                    talkgroup,
+                    seq,
+                    now,
+                    span_minutes,
+                ));
+                seq += 1;
+            }
+        }
+    }
+
+    Ok(calls)
+}
+
+/// Validate a demo system's ID.
+///
+/// # Errors
+///
+/// Returns an error if the ID is not a valid [`SystemId`].
+fn demo_system_id(system: &DemoSystem) -> Result<SystemId> {
+    SystemId::new(system.id)
+        .map_err(|e| StorageError::Serialization(format!("Invalid demo system ID: {e}")))
+}
+
+#[allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_possible_truncation,
+    clippy::too_many_arguments
+)]
+fn demo_call(
+    system: &DemoSystem,
+    system_id: &SystemId,
+    talkgroup: &DemoTalkgroup,
+    seq: usize,
+    now: DateTime<Utc>,
+    span_minutes: i64,
+) -> RadioCallDb {
+    // Cheap deterministic scatter so calls don't line up on a grid
+    let scatter = (seq as i64).wrapping_mul(7919) % span_minutes;
+    let timestamp = now - Duration::minutes(scatter);
+    let duration_tenths = 30 + (seq as i64 * 37) % 270;
+    let transcript = DEMO_TRANSCRIPTS.get(seq % DEMO_TRANSCRIPTS.len()).copied();
+    let source = 1_000_000 + (seq % 40) as i32;
+
+    RadioCallDb {
+        id: Uuid::new_v4(),
+        created_at: timestamp,
+        call_timestamp: timestamp,
+        system_id: system_id.clone(),
+        system_label: Some(system.label.to_string()),
+        frequency: Frequency::new(system.frequency).ok(),
+        talkgroup_id: TalkgroupId::new(talkgroup.id).ok(),
+        talkgroup_label: Some(talkgroup.label.to_string()),
+        talkgroup_group: Some(talkgroup.group.to_string()),
+        talkgroup_tag: Some(talkgroup.tag.to_string()),
+        source_radio_id: RadioId::new(source).ok(),
+        talker_alias: None,
+        audio_filename: None,
+        audio_file_path: None,
+        audio_size_bytes: Some(duration_tenths * 1_600),
+        audio_content_type: None,
+        audio_sha256: None,
+        duration_seconds: Some(Decimal::new(duration_tenths, 1)),
+        transcription_text: transcript.map(str::to_string),
+        transcription_confidence: Some(Decimal::new(80 + (seq % 19) as i64, 2)),
+        transcription_language: Some("en".to_string()),
+        transcription_status: Some("completed".to_string()),
+        speaker_segments: None,
+        speaker_count: Some(1),
+        patches: None,
+        frequencies: None,
+        sources: None,
+        upload_ip: None,
+        upload_timestamp: timestamp,
+        upload_api_key_id: Some("demo".to_string()),
+        latitude: None,
+        longitude: None,
+    }
+}
+
+/// Seed the database with demo systems and calls.
+///
+/// Skips seeding if calls for the first demo system already exist, so it is
+/// safe to run on every startup.
+///
+/// # Errors
+///
+/// Returns an error if any database operation fails.
+pub async fn seed_demo_data(pool: &PgPool, calls_per_talkgroup: usize) -> Result<DemoSeedSummary> {
+    let Some(first) = DEMO_SYSTEMS.first() else {
+        return Ok(DemoSeedSummary::default());
+    };
+    if RadioCallQueries::count_by_system(pool, &demo_system_id(first)?).await? > 0 {
+        return Ok(DemoSeedSummary {
+            skipped: true,
+            ..DemoSeedSummary::default()
+        });
+    }
+
+    let now = Utc::now();
+    let calls = generate_demo_calls(now, calls_per_talkgroup, 48)?;
+    for call in &calls {
+        let _ = RadioCallQueries::insert(pool, call).await?;
+        if let Some(radio_id) = call.source_radio_id {
+            RadioQueries::record_call(
+                pool,
+                &call.system_id,
+                radio_id,
+                call.talker_alias.as_deref(),
+                call.call_timestamp,
+            )
+            .await?;
+        }
+    }
+
+    for system in DEMO_SYSTEMS {
+        let stats = demo_system_stats(system, &calls, now)?;
+        SystemStatsQueries::upsert(pool, &stats).await?;
+    }
+
+    Ok(DemoSeedSummary {
+        systems: DEMO_SYSTEMS.len(),
+        calls: calls.len(),
+        skipped: false,
+    })
+}
+
+/// Build the `system_stats` row for a demo system from its generated calls.
+///
+/// # Errors
+///
+/// Returns an error if the system ID is invalid.
+fn demo_system_stats(
+    system: &DemoSystem,
+    calls: &[RadioCallDb],
+    now: DateTime<Utc>,
+) -> Result<SystemStatsDb> {
+    let system_calls: Vec<&RadioCallDb> = calls
+        .iter()
+        .filter(|c| c.system_id.as_str() == system.id)
+        .collect();
+    let count_since = |window: Duration| {
+        let n = system_calls
+            .iter()
+            .filter(|c| c.call_timestamp > now - window)
+            .count();
+        i32::try_from(n).unwrap_or(i32::MAX)
+    };
+
+    Ok(SystemStatsDb {
+        id: Uuid::new_v4(),
+        system_id: demo_system_id(system)?,
+        system_label: Some(system.label.to_string()),
+        total_calls: Some(i32::try_from(system_calls.len()).unwrap_or(i32::MAX)),
+        calls_today: Some(count_since(Duration::days(1))),
+        calls_this_hour: Some(count_since(Duration::hours(1))),
+        first_seen: system_calls.iter().map(|c| c.call_timestamp).min(),
+        last_seen: system_calls.iter().map(|c| c.call_timestamp).max(),
+        top_talkgroups: None,
+        upload_sources: None,
+        last_updated: now,
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_demo_calls_covers_all_talkgroups() {
+        let now = Utc::now();
+        let calls = generate_demo_calls(now, 4, 48).unwrap();
+
+        let talkgroups: usize = DEMO_SYSTEMS.iter().map(|s| s.talkgroups.len()).sum();
+        assert_eq!(calls.len(), talkgroups * 4);
+
+        for system in DEMO_SYSTEMS {
+            assert!(calls.iter().any(|c| c.system_id.as_str() == system.id));
+            for talkgroup in system.talkgroups {
+                assert!(
+                    calls
+                        .iter()
+                        .any(|c| c.talkgroup_id.map(TalkgroupId::as_i32) == Some(talkgroup.id))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_demo_calls_within_window() {
+        let now = Utc::now();
+        let calls = generate_demo_calls(now, 10, 6).unwrap();
+
+        for call in &calls {
+            assert!(call.call_timestamp <= now);
+            assert!(call.call_timestamp > now - Duration::hours(6));
+            assert_eq!(call.transcription_status.as_deref(), Some("completed"));
+            assert!(call.transcription_text.is_some());
+            assert!(call.system_id.as_str().starts_with("demo_"));
+        }
+    }
+
+    #[test]
+    fn test_demo_system_stats() {
+        let now = Utc::now();
+        let calls = generate_demo_calls(now, 5, 48).unwrap();
+        let stats = demo_system_stats(&DEMO_SYSTEMS[0], &calls, now).unwrap();
+
+        let expected = DEMO_SYSTEMS[0].talkgroups.len() * 5;
+        assert_eq!(stats.total_calls, Some(i32::try_from(expected).unwrap()));
+        assert!(stats.calls_today.unwrap() <= stats.total_calls.unwrap());
+        assert!(stats.first_seen <= stats.last_seen);
+    }
+}
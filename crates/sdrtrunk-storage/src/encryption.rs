@@ -0,0 +1,427 @@
+//! Encrypted call fields.
+//!
+//! With `[encryption]` on, a call's transcript and talker alias are stored
+//! sealed in `transcription_text_encrypted` and `talker_alias_encrypted`
+//! instead of their plaintext columns. These queries read and write the
+//! sealed values; sealing and opening them is left to the caller's
+//! [`KeyRing`](crate::crypto::KeyRing), so no key ever reaches the database.
+//!
+//! The rotate task walks the calls whose values are sealed with another key
+//! than the current one, or are still plaintext in a field that is now
+//! encrypted, and re-seals them with [`EncryptionQueries::reseal`]. It then
+//! does the same for transcript segments, whose text is sealed along with
+//! the transcript.
+
+use crate::crypto::KeyId;
+use crate::error::StorageError;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for encrypted field operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Sealed fields of a call.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct SealedCallFields {
+    /// Call ID
+    pub id: Uuid,
+    /// Sealed transcript
+    pub transcription_text: Option<Vec<u8>>,
+    /// Sealed talker alias
+    pub talker_alias: Option<Vec<u8>>,
+}
+
+/// A call with a field to seal or re-seal, as it was read.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct CallToReseal {
+    /// Call ID
+    pub id: Uuid,
+    /// Plaintext transcript
+    pub transcription_text: Option<String>,
+    /// Sealed transcript
+    pub transcription_text_encrypted: Option<Vec<u8>>,
+    /// Plaintext talker alias
+    pub talker_alias: Option<String>,
+    /// Sealed talker alias
+    pub talker_alias_encrypted: Option<Vec<u8>>,
+}
+
+/// A transcript segment to seal or re-seal, as it was read.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct SegmentToReseal {
+    /// Call ID
+    pub call_id: Uuid,
+    /// Position of the segment in the call's transcript
+    pub segment_index: i32,
+    /// Plaintext text
+    pub text: Option<String>,
+    /// Sealed text
+    pub text_encrypted: Option<Vec<u8>>,
+}
+
+/// Which plaintext fields the rotate task seals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlaintextFields {
+    /// Seal plaintext transcripts
+    pub transcription_text: bool,
+    /// Seal plaintext talker aliases
+    pub talker_alias: bool,
+}
+
+/// Newly sealed values for a call; `None` leaves a field as it is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResealedFields<'a> {
+    /// Sealed transcript, replacing the plaintext or older sealed one
+    pub transcription_text: Option<&'a [u8]>,
+    /// Sealed talker alias, replacing the plaintext or older sealed one
+    pub talker_alias: Option<&'a [u8]>,
+}
+
+/// Encrypted call field operations.
+#[derive(Debug)]
+pub struct EncryptionQueries;
+
+impl EncryptionQueries {
+    /// Store a call's talker alias sealed, clearing the plaintext one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn seal_talker_alias(pool: &PgPool, call_id: Uuid, sealed: &[u8]) -> Result<()> {
+        let _ = sqlx::query(
+            "UPDATE radio_calls SET talker_alias = NULL, talker_alias_encrypted = $2
+             WHERE id = $1",
+        )
+        .bind(call_id)
+        .bind(sealed)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Sealed fields of the calls among `ids` that have any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn sealed_fields(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<SealedCallFields>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let fields = sqlx::query_as::<_, SealedCallFields>(
+            "SELECT id,
+                    transcription_text_encrypted AS transcription_text,
+                    talker_alias_encrypted AS talker_alias
+             FROM radio_calls
+             WHERE id = ANY($1)
+               AND (transcription_text_encrypted IS NOT NULL
+                    OR talker_alias_encrypted IS NOT NULL)",
+        )
+        .bind(ids)
+        .fetch_all(pool)
+        .await?;
+        Ok(fields)
+    }
+
+    /// Up to `limit` calls after `after`, in ID order, with a value sealed
+    /// with another key than `key_id` or a `plaintext` field still unsealed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn calls_to_reseal(
+        pool: &PgPool,
+        key_id: &KeyId,
+        plaintext: PlaintextFields,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<CallToReseal>> {
+        let calls = sqlx::query_as::<_, CallToReseal>(
+            "SELECT id, transcription_text, transcription_text_encrypted,
+                    talker_alias, talker_alias_encrypted
+             FROM radio_calls
+             WHERE ($2::uuid IS NULL OR id > $2)
+               AND ((transcription_text_encrypted IS NOT NULL
+                     AND substring(transcription_text_encrypted FROM 1 FOR 4) <> $1)
+                    OR (talker_alias_encrypted IS NOT NULL
+                        AND substring(talker_alias_encrypted FROM 1 FOR 4) <> $1)
+                    OR ($3 AND transcription_text IS NOT NULL)
+                    OR ($4 AND talker_alias IS NOT NULL))
+             ORDER BY id
+             LIMIT $5",
+        )
+        .bind(key_id.as_slice())
+        .bind(after)
+        .bind(plaintext.transcription_text)
+        .bind(plaintext.talker_alias)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        Ok(calls)
+    }
+
+    /// Store newly sealed values for `call`, clearing the plaintext ones
+    /// they replace.
+    ///
+    /// Returns `false`, changing nothing, if the call was deleted or its
+    /// fields changed since `call` was read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn reseal(
+        pool: &PgPool,
+        call: &CallToReseal,
+        fields: ResealedFields<'_>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE radio_calls SET
+                 transcription_text = CASE WHEN $2::bytea IS NULL THEN transcription_text END,
+                 transcription_text_encrypted = COALESCE($2, transcription_text_encrypted),
+                 talker_alias = CASE WHEN $3::bytea IS NULL THEN talker_alias END,
+                 talker_alias_encrypted = COALESCE($3, talker_alias_encrypted)
+             WHERE id = $1
+               AND transcription_text IS NOT DISTINCT FROM $4
+               AND transcription_text_encrypted IS NOT DISTINCT FROM $5
+               AND talker_alias IS NOT DISTINCT FROM $6
+               AND talker_alias_encrypted IS NOT DISTINCT FROM $7",
+        )
+        .bind(call.id)
+        .bind(fields.transcription_text)
+        .bind(fields.talker_alias)
+        .bind(&call.transcription_text)
+        .bind(&call.transcription_text_encrypted)
+        .bind(&call.talker_alias)
+        .bind(&call.talker_alias_encrypted)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Up to `limit` transcript segments after `after`, in call and segment
+    /// order, with text sealed with another key than `key_id`, or still
+    /// unsealed when `plaintext` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn segments_to_reseal(
+        pool: &PgPool,
+        key_id: &KeyId,
+        plaintext: bool,
+        after: Option<(Uuid, i32)>,
+        limit: i64,
+    ) -> Result<Vec<SegmentToReseal>> {
+        let (after_call, after_index) = after.unzip();
+        let segments = sqlx::query_as::<_, SegmentToReseal>(
+            "SELECT call_id, segment_index, text, text_encrypted
+             FROM transcription_segments
+             WHERE ($2::uuid IS NULL OR (call_id, segment_index) > ($2, $3))
+               AND ((text_encrypted IS NOT NULL
+                     AND substring(text_encrypted FROM 1 FOR 4) <> $1)
+                    OR ($4 AND text IS NOT NULL))
+             ORDER BY call_id, segment_index
+             LIMIT $5",
+        )
+        .bind(key_id.as_slice())
+        .bind(after_call)
+        .bind(after_index)
+        .bind(plaintext)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        Ok(segments)
+    }
+
+    /// Store newly sealed text for `segment`, clearing its plaintext text.
+    ///
+    /// Returns `false`, changing nothing, if the segment was replaced since
+    /// `segment` was read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn reseal_segment(
+        pool: &PgPool,
+        segment: &SegmentToReseal,
+        sealed: &[u8],
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE transcription_segments SET text = NULL, text_encrypted = $3
+             WHERE call_id = $1 AND segment_index = $2
+               AND text IS NOT DISTINCT FROM $4
+               AND text_encrypted IS NOT DISTINCT FROM $5",
+        )
+        .bind(segment.call_id)
+        .bind(segment.segment_index)
+        .bind(sealed)
+        .bind(&segment.text)
+        .bind(&segment.text_encrypted)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::crypto::KeyRing;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::segments::{SegmentQueries, TranscriptionSegment};
+    use crate::test_support::{create_test_pool, test_call};
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use sdrtrunk_types::SystemId;
+
+    async fn insert_call(pool: &PgPool) -> Uuid {
+        let call = RadioCallDb {
+            talker_alias: Some("ENGINE 4".to_string()),
+            transcription_status: None,
+            ..test_call(&SystemId::new("encryption_test").unwrap())
+        };
+        crate::insert_radio_call(pool, &call).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_seal_and_reseal_call_fields() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let old_key = STANDARD.encode([1u8; 32]);
+        let old = KeyRing::new(&old_key, &[]).unwrap();
+        let current = KeyRing::new(&STANDARD.encode([2u8; 32]), &[old_key]).unwrap();
+
+        let id = insert_call(&pool).await;
+        EncryptionQueries::seal_talker_alias(&pool, id, &old.seal("ENGINE 4").unwrap())
+            .await
+            .unwrap();
+        let call = RadioCallQueries::find_by_id(&pool, id).await.unwrap();
+        assert!(call.talker_alias.is_none());
+
+        let sealed = EncryptionQueries::sealed_fields(&pool, &[id])
+            .await
+            .unwrap();
+        let alias = sealed.first().unwrap().talker_alias.as_deref().unwrap();
+        assert_eq!(current.open(alias).unwrap(), "ENGINE 4");
+
+        // The alias is sealed with the old key, so the call needs re-sealing
+        let mut after = None;
+        let to_reseal = loop {
+            let batch = EncryptionQueries::calls_to_reseal(
+                &pool,
+                &current.current_key_id(),
+                PlaintextFields::default(),
+                after,
+                500,
+            )
+            .await
+            .unwrap();
+            if let Some(call) = batch.iter().find(|call| call.id == id) {
+                break call.clone();
+            }
+            after = Some(batch.last().unwrap().id);
+        };
+        let resealed = current.seal("ENGINE 4").unwrap();
+        let fields = ResealedFields {
+            talker_alias: Some(&resealed),
+            ..ResealedFields::default()
+        };
+        assert!(
+            EncryptionQueries::reseal(&pool, &to_reseal, fields)
+                .await
+                .unwrap()
+        );
+        // A stale read no longer matches
+        assert!(
+            !EncryptionQueries::reseal(&pool, &to_reseal, fields)
+                .await
+                .unwrap()
+        );
+
+        let sealed = EncryptionQueries::sealed_fields(&pool, &[id])
+            .await
+            .unwrap();
+        assert_eq!(sealed.first().unwrap().talker_alias, Some(resealed));
+    }
+
+    #[tokio::test]
+    async fn test_seal_and_reseal_segments() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let current = KeyRing::new(&STANDARD.encode([2u8; 32]), &[]).unwrap();
+        let id = insert_call(&pool).await;
+        let segment = TranscriptionSegment {
+            start_seconds: 0.0,
+            end_seconds: 2.0,
+            text: "engine 4 on scene".to_string(),
+            speaker: None,
+            confidence: None,
+        };
+        SegmentQueries::replace(&pool, id, std::slice::from_ref(&segment), None)
+            .await
+            .unwrap();
+
+        // Plaintext segments are only picked up when transcripts are sealed
+        let find = |plaintext| {
+            let pool = pool.clone();
+            let key_id = current.current_key_id();
+            async move {
+                let mut after = None;
+                loop {
+                    let batch = EncryptionQueries::segments_to_reseal(
+                        &pool, &key_id, plaintext, after, 500,
+                    )
+                    .await
+                    .unwrap();
+                    if let Some(found) = batch.iter().find(|s| s.call_id == id) {
+                        break Some(found.clone());
+                    }
+                    let last = batch.last()?;
+                    after = Some((last.call_id, last.segment_index));
+                }
+            }
+        };
+        assert!(find(false).await.is_none());
+        let to_reseal = find(true).await.unwrap();
+        assert_eq!(to_reseal.text.as_deref(), Some("engine 4 on scene"));
+
+        let sealed = current.seal("engine 4 on scene").unwrap();
+        assert!(
+            EncryptionQueries::reseal_segment(&pool, &to_reseal, &sealed)
+                .await
+                .unwrap()
+        );
+        // A stale read no longer matches
+        assert!(
+            !EncryptionQueries::reseal_segment(&pool, &to_reseal, &sealed)
+                .await
+                .unwrap()
+        );
+        assert!(find(true).await.is_none());
+        assert_eq!(
+            SegmentQueries::for_call(&pool, id, Some(&current))
+                .await
+                .unwrap(),
+            vec![segment]
+        );
+    }
+}
@@ -40,6 +40,18 @@ pub enum StorageError {
     /// Serialization failed
     #[error("serialization failed: {0}")]
     Serialization(String),
+
+    /// Recording storage operation failed
+    #[error("audio storage failed: {0}")]
+    Audio(String),
+
+    /// Storage settings in the configuration are invalid
+    #[error("invalid configuration: {0}")]
+    Configuration(String),
+
+    /// Column encryption or decryption failed
+    #[error("encryption failed: {0}")]
+    Encryption(String),
 }
 
 /// Automatic conversion from `sqlx::Error`
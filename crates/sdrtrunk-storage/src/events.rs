@@ -0,0 +1,252 @@
+//! Per-call processing events.
+//!
+//! Each step a call goes through is kept in `call_events` with when it
+//! happened, so a call stuck somewhere in the pipeline can be traced. The
+//! upload handler records `received` and `stored`; the job queue records
+//! `queued` (also on retries) and `claimed`; finishing a call's transcription
+//! records `transcribed` or `failed`, and clearing a low-confidence one from
+//! the review queue records `reviewed`, and a bulk archive records `archived`.
+//! Events are removed with the call.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for call event operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A processing step of a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallEventKind {
+    /// The upload arrived.
+    Received,
+    /// The recording was written to storage.
+    Stored,
+    /// A transcription job was enqueued.
+    Queued,
+    /// A worker picked up the transcription job.
+    Claimed,
+    /// The transcription finished.
+    Transcribed,
+    /// The transcription failed for good.
+    Failed,
+    /// A low-confidence transcription was checked and accepted.
+    Reviewed,
+    /// The call was archived by a bulk operation.
+    Archived,
+}
+
+impl CallEventKind {
+    /// Name stored in the `event` column.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Received => "received",
+            Self::Stored => "stored",
+            Self::Queued => "queued",
+            Self::Claimed => "claimed",
+            Self::Transcribed => "transcribed",
+            Self::Failed => "failed",
+            Self::Reviewed => "reviewed",
+            Self::Archived => "archived",
+        }
+    }
+}
+
+/// A row from the `call_events` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CallEvent {
+    /// Event ID, increasing in recording order.
+    pub id: i64,
+    /// Call the event belongs to.
+    pub call_id: Uuid,
+    /// `received`, `stored`, `queued`, `claimed`, `transcribed`, `failed`,
+    /// `reviewed`, or `archived`.
+    pub event: String,
+    /// Step-specific detail such as the job ID, worker, or error.
+    pub detail: Option<String>,
+    /// When the step happened.
+    pub occurred_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Event operations
+// ---------------------------------------------------------------------------
+
+/// Call event queries.
+#[derive(Debug)]
+pub struct CallEventQueries;
+
+impl CallEventQueries {
+    /// Record that a call went through `kind` at `occurred_at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call does not exist or the database query fails.
+    pub async fn record(
+        pool: &PgPool,
+        call_id: Uuid,
+        kind: CallEventKind,
+        occurred_at: DateTime<Utc>,
+        detail: Option<&str>,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            INSERT INTO call_events (call_id, event, detail, occurred_at)
+            VALUES ($1, $2, $3, $4)
+            ",
+        )
+        .bind(call_id)
+        .bind(kind.as_str())
+        .bind(detail)
+        .bind(occurred_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The events of a call, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_call(pool: &PgPool, call_id: Uuid) -> Result<Vec<CallEvent>> {
+        let events = sqlx::query_as::<_, CallEvent>(
+            r"
+            SELECT id, call_id, event, detail, occurred_at
+            FROM call_events
+            WHERE call_id = $1
+            ORDER BY occurred_at, id
+            ",
+        )
+        .bind(call_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::jobs::{EnqueueParams, JobQueue};
+    use crate::queries::{RadioCallQueries, TranscriptionUpdate};
+    use crate::test_support::{create_test_pool, test_call};
+    use sdrtrunk_types::SystemId;
+
+    #[test]
+    fn test_kind_names() {
+        for kind in [
+            CallEventKind::Received,
+            CallEventKind::Stored,
+            CallEventKind::Queued,
+            CallEventKind::Claimed,
+            CallEventKind::Transcribed,
+            CallEventKind::Failed,
+            CallEventKind::Reviewed,
+            CallEventKind::Archived,
+        ] {
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::json!(kind.as_str())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_events() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("evt_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call_id = RadioCallQueries::insert(&pool, &test_call(&system_id))
+            .await
+            .unwrap();
+        let received = Utc::now() - chrono::Duration::seconds(5);
+        CallEventQueries::record(&pool, call_id, CallEventKind::Received, received, None)
+            .await
+            .unwrap();
+        CallEventQueries::record(
+            &pool,
+            call_id,
+            CallEventKind::Stored,
+            Utc::now(),
+            Some("recordings/a.mp3"),
+        )
+        .await
+        .unwrap();
+
+        let job_id = JobQueue::enqueue(
+            &pool,
+            &EnqueueParams {
+                call_id,
+                audio_path: None,
+                audio_data: None,
+                priority: 0,
+                options: serde_json::json!({}),
+                timeout_seconds: 300,
+            },
+        )
+        .await
+        .unwrap();
+        RadioCallQueries::update_transcription_status(
+            &pool,
+            TranscriptionUpdate {
+                id: call_id,
+                status: "completed",
+                text: Some("Engine 5 responding"),
+                confidence: None,
+                error: None,
+                speaker_segments: None,
+                speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let events = CallEventQueries::for_call(&pool, call_id).await.unwrap();
+        let kinds: Vec<&str> = events.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(kinds, ["received", "stored", "queued", "transcribed"]);
+        assert_eq!(events[0].occurred_at.timestamp(), received.timestamp());
+        assert_eq!(events[2].detail, Some(format!("job {job_id}")));
+
+        // Events cannot exist without their call
+        assert!(
+            CallEventQueries::record(
+                &pool,
+                Uuid::new_v4(),
+                CallEventKind::Received,
+                Utc::now(),
+                None
+            )
+            .await
+            .is_err()
+        );
+    }
+}
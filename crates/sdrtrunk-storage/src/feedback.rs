@@ -0,0 +1,306 @@
+//! Transcription quality feedback.
+//!
+//! Users rate a call's transcription and/or submit a corrected transcript.
+//! Each submission is kept in `transcription_feedback` with the transcript it
+//! was given on, and can be exported together with the call's recording
+//! location to build fine-tuning datasets.
+
+use crate::error::StorageError;
+use crate::queries::system_names;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for feedback operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `transcription_feedback` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptionFeedback {
+    /// Feedback ID.
+    pub id: Uuid,
+    /// Call the feedback is about.
+    pub call_id: Uuid,
+    /// Transcript at the time the feedback was given.
+    pub original_text: Option<String>,
+    /// Corrected transcript.
+    pub corrected_text: Option<String>,
+    /// Quality rating from 1 (unusable) to 5 (perfect).
+    pub rating: Option<i16>,
+    /// Free-form remarks.
+    pub comment: Option<String>,
+    /// API key the feedback was submitted with.
+    pub api_key_id: Option<String>,
+    /// When the feedback was submitted.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Fields for new feedback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTranscriptionFeedback {
+    /// Call the feedback is about.
+    pub call_id: Uuid,
+    /// Transcript the feedback was given on.
+    pub original_text: Option<String>,
+    /// Corrected transcript.
+    pub corrected_text: Option<String>,
+    /// Quality rating from 1 to 5.
+    pub rating: Option<i16>,
+    /// Free-form remarks.
+    pub comment: Option<String>,
+    /// API key the feedback was submitted with.
+    pub api_key_id: Option<String>,
+}
+
+/// Filters for [`FeedbackQueries::export`]; unset fields match all feedback.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedbackFilter {
+    /// Only feedback on calls from this system.
+    pub system_id: Option<SystemId>,
+    /// Only feedback on calls from these systems (an API key's tenant scope).
+    pub allowed_systems: Option<Vec<SystemId>>,
+    /// Only feedback rated at least this.
+    pub min_rating: Option<i16>,
+    /// Only feedback with a corrected transcript.
+    pub corrected_only: bool,
+    /// Only feedback submitted at or after this time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only feedback submitted before this time.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// One exported feedback entry with the call details needed for training.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct FeedbackExport {
+    /// Feedback ID.
+    pub feedback_id: Uuid,
+    /// Call the feedback is about.
+    pub call_id: Uuid,
+    /// System of the call.
+    pub system_id: SystemId,
+    /// Talkgroup of the call.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Where the recording is stored.
+    pub audio_file_path: Option<String>,
+    /// Length of the recording in seconds.
+    pub duration_seconds: Option<rust_decimal::Decimal>,
+    /// Language of the transcription.
+    pub language: Option<String>,
+    /// Best known transcript: the correction, or the original when only rated.
+    pub text: Option<String>,
+    /// Transcript the feedback was given on.
+    pub original_text: Option<String>,
+    /// Corrected transcript.
+    pub corrected_text: Option<String>,
+    /// Quality rating from 1 to 5.
+    pub rating: Option<i16>,
+    /// Free-form remarks.
+    pub comment: Option<String>,
+    /// When the feedback was submitted.
+    pub created_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Feedback operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for transcription feedback.
+#[derive(Debug)]
+pub struct FeedbackQueries;
+
+impl FeedbackQueries {
+    /// Store feedback on a call's transcription.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn insert(
+        pool: &PgPool,
+        feedback: &NewTranscriptionFeedback,
+    ) -> Result<TranscriptionFeedback> {
+        let feedback = sqlx::query_as::<_, TranscriptionFeedback>(
+            r"
+            INSERT INTO transcription_feedback
+                (call_id, original_text, corrected_text, rating, comment, api_key_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            ",
+        )
+        .bind(feedback.call_id)
+        .bind(feedback.original_text.as_deref())
+        .bind(feedback.corrected_text.as_deref())
+        .bind(feedback.rating)
+        .bind(feedback.comment.as_deref())
+        .bind(feedback.api_key_id.as_deref())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(feedback)
+    }
+
+    /// Feedback given on a call, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list_for_call(pool: &PgPool, call_id: Uuid) -> Result<Vec<TranscriptionFeedback>> {
+        let feedback = sqlx::query_as::<_, TranscriptionFeedback>(
+            "SELECT * FROM transcription_feedback WHERE call_id = $1 ORDER BY created_at, id",
+        )
+        .bind(call_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(feedback)
+    }
+
+    /// Feedback matching `filter` with its call details, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn export(pool: &PgPool, filter: &FeedbackFilter) -> Result<Vec<FeedbackExport>> {
+        let rows = sqlx::query_as::<_, FeedbackExport>(
+            r"
+            SELECT f.id AS feedback_id, f.call_id, rc.system_id, rc.talkgroup_id,
+                   rc.audio_file_path, rc.duration_seconds,
+                   rc.transcription_language AS language,
+                   COALESCE(f.corrected_text, f.original_text) AS text,
+                   f.original_text, f.corrected_text, f.rating, f.comment, f.created_at
+            FROM transcription_feedback f
+            JOIN radio_calls rc ON rc.id = f.call_id
+            WHERE ($1::VARCHAR IS NULL OR rc.system_id = $1)
+              AND ($2::SMALLINT IS NULL OR f.rating >= $2)
+              AND (NOT $3 OR f.corrected_text IS NOT NULL)
+              AND ($4::TIMESTAMPTZ IS NULL OR f.created_at >= $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR f.created_at < $5)
+              AND ($6::TEXT[] IS NULL OR rc.system_id = ANY($6))
+            ORDER BY f.created_at, f.id
+            ",
+        )
+        .bind(filter.system_id.as_ref())
+        .bind(filter.min_rating)
+        .bind(filter.corrected_only)
+        .bind(filter.from_date)
+        .bind(filter.to_date)
+        .bind(filter.allowed_systems.as_deref().map(system_names))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+
+    fn feedback(
+        call_id: Uuid,
+        corrected: Option<&str>,
+        rating: Option<i16>,
+    ) -> NewTranscriptionFeedback {
+        NewTranscriptionFeedback {
+            call_id,
+            original_text: Some("engine to on scene".to_string()),
+            corrected_text: corrected.map(str::to_string),
+            rating,
+            comment: None,
+            api_key_id: None,
+        }
+    }
+
+    fn transcribed_call(system_id: &SystemId) -> RadioCallDb {
+        RadioCallDb {
+            audio_filename: Some("call.mp3".to_string()),
+            audio_file_path: Some("metro/call.mp3".to_string()),
+            transcription_text: Some("engine to on scene".to_string()),
+            transcription_language: Some("en".to_string()),
+            transcription_status: Some("completed".to_string()),
+            ..test_call(system_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feedback_export_filters() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("fb_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call = transcribed_call(&system_id);
+        RadioCallQueries::insert(&pool, &call).await.unwrap();
+
+        let corrected = FeedbackQueries::insert(
+            &pool,
+            &feedback(call.id, Some("Engine 2 on scene"), Some(2)),
+        )
+        .await
+        .unwrap();
+        let rated = FeedbackQueries::insert(&pool, &feedback(call.id, None, Some(5)))
+            .await
+            .unwrap();
+
+        let listed = FeedbackQueries::list_for_call(&pool, call.id)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 2);
+
+        let filter = FeedbackFilter {
+            system_id: Some(system_id.clone()),
+            ..FeedbackFilter::default()
+        };
+        let all = FeedbackQueries::export(&pool, &filter).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].feedback_id, corrected.id);
+        assert_eq!(all[0].text.as_deref(), Some("Engine 2 on scene"));
+        assert_eq!(all[0].audio_file_path.as_deref(), Some("metro/call.mp3"));
+        assert_eq!(all[1].text.as_deref(), Some("engine to on scene"));
+
+        let corrections = FeedbackQueries::export(
+            &pool,
+            &FeedbackFilter {
+                corrected_only: true,
+                ..filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].feedback_id, corrected.id);
+
+        let well_rated = FeedbackQueries::export(
+            &pool,
+            &FeedbackFilter {
+                min_rating: Some(4),
+                ..filter
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(well_rated.len(), 1);
+        assert_eq!(well_rated[0].feedback_id, rated.id);
+    }
+}
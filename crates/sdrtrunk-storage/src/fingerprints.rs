@@ -0,0 +1,290 @@
+//! Acoustic fingerprints for echo detection.
+//!
+//! Each call's recording is reduced to one 32-bit sub-fingerprint per short
+//! frame, stored in `call_fingerprints` as signed integers. The API compares
+//! a new call's fingerprint with those of calls that started within a few
+//! seconds of it; a close match is recorded as `echo_of`, pointing at the
+//! call first heard (e.g. a simulcast transmission picked up twice).
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for fingerprint operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Most candidates returned by [`FingerprintQueries::candidates`].
+const MAX_CANDIDATES: i64 = 200;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `call_fingerprints` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CallFingerprint {
+    /// Fingerprinted call.
+    pub call_id: Uuid,
+    /// Sub-fingerprint of each frame, bit patterns stored as `i32`.
+    pub fingerprint: Vec<i32>,
+    /// Call this one repeats the audio of.
+    pub echo_of: Option<Uuid>,
+    /// Share of fingerprint bits that differ from `echo_of`.
+    pub bit_error_rate: Option<f32>,
+    /// When the fingerprint was computed.
+    pub created_at: DateTime<Utc>,
+}
+
+/// A fingerprinted call that started near another call.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct FingerprintCandidate {
+    /// Call ID.
+    pub call_id: Uuid,
+    /// System the call was heard on.
+    pub system_id: SystemId,
+    /// When the call started.
+    pub call_timestamp: DateTime<Utc>,
+    /// Sub-fingerprint of each frame.
+    pub fingerprint: Vec<i32>,
+    /// Call this one repeats the audio of.
+    pub echo_of: Option<Uuid>,
+}
+
+// ---------------------------------------------------------------------------
+// Fingerprint operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for call fingerprints.
+#[derive(Debug)]
+pub struct FingerprintQueries;
+
+impl FingerprintQueries {
+    /// Store the fingerprint of a call, replacing any earlier one and its echo
+    /// match.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the call does not exist or the query fails.
+    pub async fn save(pool: &PgPool, call_id: Uuid, fingerprint: &[i32]) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            INSERT INTO call_fingerprints (call_id, fingerprint)
+            VALUES ($1, $2)
+            ON CONFLICT (call_id) DO UPDATE SET
+                fingerprint = EXCLUDED.fingerprint,
+                echo_of = NULL,
+                bit_error_rate = NULL,
+                created_at = NOW()
+            ",
+        )
+        .bind(call_id)
+        .bind(fingerprint)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fingerprint of a call, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn get(pool: &PgPool, call_id: Uuid) -> Result<Option<CallFingerprint>> {
+        let fingerprint = sqlx::query_as::<_, CallFingerprint>(
+            "SELECT * FROM call_fingerprints WHERE call_id = $1",
+        )
+        .bind(call_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(fingerprint)
+    }
+
+    /// Other fingerprinted calls that started within `window_seconds` of
+    /// `call_timestamp`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn candidates(
+        pool: &PgPool,
+        call_id: Uuid,
+        call_timestamp: DateTime<Utc>,
+        window_seconds: u32,
+    ) -> Result<Vec<FingerprintCandidate>> {
+        let candidates = sqlx::query_as::<_, FingerprintCandidate>(
+            r"
+            SELECT f.call_id, c.system_id, c.call_timestamp, f.fingerprint, f.echo_of
+            FROM call_fingerprints f
+            JOIN radio_calls c ON c.id = f.call_id
+            WHERE f.call_id <> $1
+              AND c.call_timestamp BETWEEN $2 - make_interval(secs => $3)
+                                       AND $2 + make_interval(secs => $3)
+            ORDER BY c.call_timestamp, f.call_id
+            LIMIT $4
+            ",
+        )
+        .bind(call_id)
+        .bind(call_timestamp)
+        .bind(f64::from(window_seconds))
+        .bind(MAX_CANDIDATES)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    /// Record a call as an echo of `echo_of`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn mark_echo(
+        pool: &PgPool,
+        call_id: Uuid,
+        echo_of: Uuid,
+        bit_error_rate: f32,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            "UPDATE call_fingerprints SET echo_of = $2, bit_error_rate = $3 WHERE call_id = $1",
+        )
+        .bind(call_id)
+        .bind(echo_of)
+        .bind(bit_error_rate)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop a call's transcription job if no worker has claimed it yet and
+    /// mark the call's transcription cancelled, returning whether it was
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn cancel_pending_transcription(pool: &PgPool, call_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r"
+            WITH dropped AS (
+                DELETE FROM transcription_jobs
+                WHERE call_id = $1 AND status = 'pending'
+                RETURNING call_id
+            )
+            UPDATE radio_calls
+            SET transcription_status = 'cancelled'
+            WHERE id = $1 AND EXISTS (SELECT 1 FROM dropped)
+            ",
+        )
+        .bind(call_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::jobs::{EnqueueParams, JobQueue};
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+    use chrono::Duration;
+
+    fn call(system_id: &str, call_timestamp: DateTime<Utc>) -> RadioCallDb {
+        RadioCallDb {
+            ..test_call_at(
+                &SystemId::new(system_id.to_string()).unwrap(),
+                call_timestamp,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_candidates_and_echoes() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        // Far from other tests' calls so only these are candidates
+        let start = Utc::now() - Duration::days(3650);
+        let original = call("fp_site_a", start);
+        let echo = call("fp_site_b", start + Duration::seconds(2));
+        let later = call("fp_site_a", start + Duration::seconds(60));
+        for call in [&original, &echo, &later] {
+            RadioCallQueries::insert(&pool, call).await.unwrap();
+        }
+        FingerprintQueries::save(&pool, original.id, &[1, -2, 3])
+            .await
+            .unwrap();
+        FingerprintQueries::save(&pool, echo.id, &[1, -2, 3])
+            .await
+            .unwrap();
+        FingerprintQueries::save(&pool, later.id, &[4, 5, 6])
+            .await
+            .unwrap();
+
+        let candidates = FingerprintQueries::candidates(&pool, echo.id, echo.call_timestamp, 10)
+            .await
+            .unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].call_id, original.id);
+        assert_eq!(candidates[0].fingerprint, [1, -2, 3]);
+
+        FingerprintQueries::mark_echo(&pool, echo.id, original.id, 0.1)
+            .await
+            .unwrap();
+        let stored = FingerprintQueries::get(&pool, echo.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.echo_of, Some(original.id));
+        assert_eq!(stored.bit_error_rate, Some(0.1));
+
+        JobQueue::enqueue(
+            &pool,
+            &EnqueueParams {
+                call_id: echo.id,
+                audio_path: None,
+                audio_data: None,
+                priority: 0,
+                options: serde_json::json!({}),
+                timeout_seconds: 300,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(
+            FingerprintQueries::cancel_pending_transcription(&pool, echo.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !FingerprintQueries::cancel_pending_transcription(&pool, echo.id)
+                .await
+                .unwrap()
+        );
+        let cancelled = RadioCallQueries::find_by_id(&pool, echo.id).await.unwrap();
+        assert_eq!(cancelled.transcription_status.as_deref(), Some("cancelled"));
+    }
+}
@@ -0,0 +1,385 @@
+//! Channel usage from per-call frequency lists.
+//!
+//! Uploads keep the frequencies a call was heard on in the
+//! `radio_calls.frequencies` text column as JSON. Rdio Scanner and
+//! trunk-recorder send `freqList`, an array of
+//! `{freq, time, pos, len, errorCount, spikeCount}` objects (snake case from
+//! trunk-recorder), and some sources send bare frequencies in Hz. The column
+//! is free text, so lists are parsed here rather than in SQL: malformed
+//! entries are skipped, and a call without a usable list counts once on its
+//! `frequency` column.
+
+use crate::{error::StorageError, queries::system_names};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, PgPool};
+use std::collections::BTreeMap;
+
+/// Result type alias for frequency operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One frequency a call was heard on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrequencyEntry {
+    /// Frequency in Hz.
+    pub frequency_hz: i64,
+    /// Seconds of the call on this frequency, when reported.
+    pub len: Option<f64>,
+    /// Decoding errors reported on this frequency.
+    pub error_count: i64,
+    /// Signal spikes reported on this frequency.
+    pub spike_count: i64,
+}
+
+impl FrequencyEntry {
+    /// Parse a stored `frequencies` value.
+    ///
+    /// Entries without a positive frequency are skipped; anything other than
+    /// a JSON array yields no entries.
+    #[must_use]
+    pub fn parse_all(text: &str) -> Vec<Self> {
+        let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(text) else {
+            return Vec::new();
+        };
+        entries.iter().filter_map(Self::parse).collect()
+    }
+
+    fn parse(entry: &Value) -> Option<Self> {
+        let count = |keys: [&str; 2]| {
+            keys.iter()
+                .find_map(|key| entry.get(key).and_then(Value::as_i64))
+                .unwrap_or(0)
+        };
+        let (frequency_hz, len, error_count, spike_count) = match entry {
+            Value::Number(freq) => (freq.as_i64()?, None, 0, 0),
+            Value::Object(_) => (
+                entry.get("freq").and_then(Value::as_i64)?,
+                entry.get("len").and_then(Value::as_f64),
+                count(["errorCount", "error_count"]),
+                count(["spikeCount", "spike_count"]),
+            ),
+            _ => return None,
+        };
+        (frequency_hz > 0).then_some(Self {
+            frequency_hz,
+            len: len.filter(|len| *len >= 0.0),
+            error_count,
+            spike_count,
+        })
+    }
+}
+
+/// The frequency fields of one call.
+#[derive(Debug, Clone, FromRow)]
+pub struct CallFrequencies {
+    /// System of the call.
+    pub system_id: SystemId,
+    /// When the call was recorded.
+    pub call_timestamp: DateTime<Utc>,
+    /// Primary frequency in Hz.
+    pub frequency: Option<i64>,
+    /// Call length in seconds.
+    pub duration_seconds: Option<rust_decimal::Decimal>,
+    /// Stored frequency list (JSON text).
+    pub frequencies: Option<String>,
+}
+
+impl CallFrequencies {
+    /// Frequencies the call used, merged per frequency.
+    ///
+    /// A single entry without a reported length is credited with the whole
+    /// call; without a usable list the call's primary frequency is.
+    #[must_use]
+    pub fn entries(&self) -> Vec<FrequencyEntry> {
+        let duration = self.duration_seconds.and_then(|d| d.to_f64());
+        let mut entries = self
+            .frequencies
+            .as_deref()
+            .map(FrequencyEntry::parse_all)
+            .unwrap_or_default();
+        if entries.is_empty() {
+            entries.extend(self.frequency.filter(|hz| *hz > 0).map(|frequency_hz| {
+                FrequencyEntry {
+                    frequency_hz,
+                    len: None,
+                    error_count: 0,
+                    spike_count: 0,
+                }
+            }));
+        }
+        if let [entry] = entries.as_mut_slice() {
+            entry.len = entry.len.or(duration);
+        }
+
+        let mut merged: BTreeMap<i64, FrequencyEntry> = BTreeMap::new();
+        for entry in entries {
+            if let Some(existing) = merged.get_mut(&entry.frequency_hz) {
+                existing.len = match (existing.len, entry.len) {
+                    (Some(total), Some(len)) => Some(total + len),
+                    (total, len) => total.or(len),
+                };
+                existing.error_count += entry.error_count;
+                existing.spike_count += entry.spike_count;
+            } else {
+                let _ = merged.insert(entry.frequency_hz, entry);
+            }
+        }
+        merged.into_values().collect()
+    }
+}
+
+/// Usage of one frequency across calls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrequencyUsage {
+    /// Frequency in Hz.
+    pub frequency_hz: i64,
+    /// Calls heard on the frequency.
+    pub call_count: i64,
+    /// Total seconds of calls on the frequency.
+    pub airtime_seconds: f64,
+    /// Decoding errors reported on the frequency.
+    pub error_count: i64,
+    /// Signal spikes reported on the frequency.
+    pub spike_count: i64,
+    /// Systems heard on the frequency, in order.
+    pub systems: Vec<SystemId>,
+    /// Most recent call on the frequency.
+    pub last_seen: DateTime<Utc>,
+}
+
+impl FrequencyUsage {
+    /// Total usage per frequency, most calls first (then most airtime).
+    #[must_use]
+    pub fn aggregate(calls: &[CallFrequencies]) -> Vec<Self> {
+        let mut usage: BTreeMap<i64, Self> = BTreeMap::new();
+        for call in calls {
+            for entry in call.entries() {
+                let frequency = usage.entry(entry.frequency_hz).or_insert_with(|| Self {
+                    frequency_hz: entry.frequency_hz,
+                    call_count: 0,
+                    airtime_seconds: 0.0,
+                    error_count: 0,
+                    spike_count: 0,
+                    systems: Vec::new(),
+                    last_seen: call.call_timestamp,
+                });
+                frequency.call_count += 1;
+                frequency.airtime_seconds += entry.len.unwrap_or(0.0);
+                frequency.error_count += entry.error_count;
+                frequency.spike_count += entry.spike_count;
+                if let Err(index) = frequency.systems.binary_search(&call.system_id) {
+                    frequency.systems.insert(index, call.system_id.clone());
+                }
+                frequency.last_seen = frequency.last_seen.max(call.call_timestamp);
+            }
+        }
+
+        let mut usage: Vec<Self> = usage.into_values().collect();
+        usage.sort_by(|a, b| {
+            b.call_count
+                .cmp(&a.call_count)
+                .then_with(|| b.airtime_seconds.total_cmp(&a.airtime_seconds))
+                .then_with(|| a.frequency_hz.cmp(&b.frequency_hz))
+        });
+        usage
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Frequency operations
+// ---------------------------------------------------------------------------
+
+/// Channel usage queries.
+#[derive(Debug)]
+pub struct FrequencyQueries;
+
+impl FrequencyQueries {
+    /// Per-frequency usage of calls recorded at or after `since`.
+    ///
+    /// `systems` restricts the calls to those systems (`None` for every
+    /// system).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn usage(
+        pool: &PgPool,
+        systems: Option<&[SystemId]>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<FrequencyUsage>> {
+        let calls = sqlx::query_as::<_, CallFrequencies>(
+            r"
+            SELECT system_id, call_timestamp, frequency, duration_seconds, frequencies
+            FROM radio_calls
+            WHERE call_timestamp >= $1
+              AND (frequency IS NOT NULL OR frequencies IS NOT NULL)
+              AND ($2::TEXT[] IS NULL OR system_id = ANY($2))
+            ",
+        )
+        .bind(since)
+        .bind(systems.map(system_names))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(FrequencyUsage::aggregate(&calls))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+    use sdrtrunk_types::Frequency;
+    use uuid::Uuid;
+
+    fn call(
+        system_id: &str,
+        frequency: Option<i64>,
+        duration: f64,
+        frequencies: Option<&str>,
+    ) -> CallFrequencies {
+        CallFrequencies {
+            system_id: SystemId::new(system_id).unwrap(),
+            call_timestamp: Utc::now(),
+            frequency,
+            duration_seconds: rust_decimal::Decimal::try_from(duration).ok(),
+            frequencies: frequencies.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let entries = FrequencyEntry::parse_all(
+            r#"[
+                {"freq": 851012500, "time": 1700000000, "pos": 0.0, "len": 1.5, "errorCount": 2, "spikeCount": 1},
+                {"freq": 851037500, "len": 2.0, "error_count": 3},
+                460125000,
+                {"freq": 0},
+                "garbage"
+            ]"#,
+        );
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].error_count, 2);
+        assert_eq!(entries[0].spike_count, 1);
+        assert_eq!(entries[1].error_count, 3);
+        assert_eq!(entries[2].frequency_hz, 460_125_000);
+        assert_eq!(entries[2].len, None);
+
+        assert!(FrequencyEntry::parse_all("not json").is_empty());
+        assert!(FrequencyEntry::parse_all(r#"{"freq": 1}"#).is_empty());
+    }
+
+    #[test]
+    fn test_call_entries() {
+        // A lone entry without a length gets the whole call
+        let entries = call("a", None, 4.0, Some("[460125000]")).entries();
+        assert_eq!(entries[0].len, Some(4.0));
+
+        // Without a usable list the primary frequency is used
+        let entries = call("a", Some(154_000_000), 3.0, Some("oops")).entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].frequency_hz, 154_000_000);
+        assert_eq!(entries[0].len, Some(3.0));
+
+        // Repeated frequencies within a call are merged
+        let entries = call(
+            "a",
+            None,
+            9.0,
+            Some(r#"[{"freq": 1, "len": 1.0}, {"freq": 2, "len": 2.0}, {"freq": 1, "len": 0.5}]"#),
+        )
+        .entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].len, Some(1.5));
+
+        assert!(call("a", None, 1.0, None).entries().is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_usage() {
+        let calls = [
+            call("b", Some(1), 2.0, None),
+            call("a", Some(1), 3.0, None),
+            call(
+                "a",
+                None,
+                5.0,
+                Some(r#"[{"freq": 2, "len": 4.0, "errorCount": 1}, {"freq": 1, "len": 1.0}]"#),
+            ),
+            call("a", Some(3), 10.0, None),
+        ];
+        let usage = FrequencyUsage::aggregate(&calls);
+
+        assert_eq!(usage.len(), 3);
+        assert_eq!(usage[0].frequency_hz, 1);
+        assert_eq!(usage[0].call_count, 3);
+        assert_eq!(usage[0].airtime_seconds, 6.0);
+        assert_eq!(
+            usage[0].systems,
+            [SystemId::new("a").unwrap(), SystemId::new("b").unwrap()]
+        );
+        assert_eq!(usage[1].frequency_hz, 3);
+        assert_eq!(usage[2].frequency_hz, 2);
+        assert_eq!(usage[2].error_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_usage() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("frq_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let now = Utc::now();
+        for (frequency, frequencies) in [
+            (Some(851_012_500), None),
+            (
+                None,
+                Some(r#"[{"freq": 851012500, "len": 2.0}, {"freq": 851037500, "len": 1.0}]"#),
+            ),
+        ] {
+            let mut row = RadioCallDb {
+                frequency: frequency.map(|hz| Frequency::new(hz).unwrap()),
+                duration_seconds: rust_decimal::Decimal::try_from(5.0).ok(),
+                transcription_status: None,
+                ..test_call_at(&system_id, now)
+            };
+            row.frequencies = frequencies.map(String::from);
+            RadioCallQueries::insert(&pool, &row).await.unwrap();
+        }
+
+        let since = now - chrono::Duration::minutes(1);
+        let usage = FrequencyQueries::usage(&pool, Some(std::slice::from_ref(&system_id)), since)
+            .await
+            .unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].frequency_hz, 851_012_500);
+        assert_eq!(usage[0].call_count, 2);
+        assert_eq!(usage[0].airtime_seconds, 7.0);
+        assert_eq!(usage[0].systems, [system_id]);
+        assert_eq!(usage[1].airtime_seconds, 1.0);
+    }
+}
@@ -0,0 +1,171 @@
+//! Call locations.
+//!
+//! Calls carry the coordinates they were heard at, taken from the uploading
+//! site or the system's configured location. These queries return located
+//! calls for plotting on a map; calls without coordinates are left out.
+
+use crate::error::StorageError;
+use crate::queries::system_names;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for call location operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A call with the coordinates it was heard at.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CallLocation {
+    /// Call ID.
+    pub id: Uuid,
+    /// When the call occurred.
+    pub call_timestamp: DateTime<Utc>,
+    /// System the call was heard on.
+    pub system_id: SystemId,
+    /// System display name.
+    pub system_label: Option<String>,
+    /// Talkgroup the call was heard on.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Talkgroup display name.
+    pub talkgroup_label: Option<String>,
+    /// Transcription status.
+    pub transcription_status: Option<String>,
+    /// Transcribed text, once available.
+    pub transcription_text: Option<String>,
+    /// Latitude (WGS 84 degrees).
+    pub latitude: f64,
+    /// Longitude (WGS 84 degrees).
+    pub longitude: f64,
+}
+
+/// Filters for listing located calls.
+#[derive(Debug, Clone, Default)]
+pub struct GeoFilter {
+    /// Only calls on this system.
+    pub system_id: Option<SystemId>,
+    /// Only calls from these systems (an API key's tenant scope).
+    pub allowed_systems: Option<Vec<SystemId>>,
+    /// Only calls on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only calls at or after this time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only calls before this time.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+// ---------------------------------------------------------------------------
+// Call location operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for call locations.
+#[derive(Debug)]
+pub struct GeoQueries;
+
+impl GeoQueries {
+    /// List up to `limit` located calls matching `filter`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(pool: &PgPool, filter: &GeoFilter, limit: i64) -> Result<Vec<CallLocation>> {
+        let calls = sqlx::query_as::<_, CallLocation>(
+            r"
+            SELECT id, call_timestamp, system_id, system_label, talkgroup_id,
+                   talkgroup_label, transcription_status, transcription_text,
+                   latitude, longitude
+            FROM radio_calls
+            WHERE latitude IS NOT NULL AND longitude IS NOT NULL
+              AND ($1::VARCHAR IS NULL OR system_id = $1)
+              AND ($2::INTEGER IS NULL OR talkgroup_id = $2)
+              AND ($3::TIMESTAMPTZ IS NULL OR call_timestamp >= $3)
+              AND ($4::TIMESTAMPTZ IS NULL OR call_timestamp < $4)
+              AND ($5::TEXT[] IS NULL OR system_id = ANY($5))
+            ORDER BY call_timestamp DESC, id
+            LIMIT $6
+            ",
+        )
+        .bind(filter.system_id.as_ref())
+        .bind(filter.talkgroup_id)
+        .bind(filter.from_date)
+        .bind(filter.to_date)
+        .bind(filter.allowed_systems.as_deref().map(system_names))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(calls)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+
+    fn call(system_id: &SystemId, location: Option<(f64, f64)>) -> RadioCallDb {
+        RadioCallDb {
+            system_label: Some("Metro".to_string()),
+            talkgroup_id: Some(TalkgroupId::new(100).unwrap()),
+            talkgroup_label: Some("Dispatch".to_string()),
+            latitude: location.map(|(lat, _)| lat),
+            longitude: location.map(|(_, lon)| lon),
+            ..test_call(system_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_returns_only_located_calls() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("geo_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let located = call(&system_id, Some((35.7796, -78.6382)));
+        RadioCallQueries::insert(&pool, &located).await.unwrap();
+        RadioCallQueries::insert(&pool, &call(&system_id, None))
+            .await
+            .unwrap();
+
+        let filter = GeoFilter {
+            system_id: Some(system_id.clone()),
+            ..GeoFilter::default()
+        };
+        let calls = GeoQueries::list(&pool, &filter, 10).await.unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, located.id);
+        assert!((calls[0].latitude - 35.7796).abs() < f64::EPSILON);
+        assert!((calls[0].longitude + 78.6382).abs() < f64::EPSILON);
+
+        let other = GeoFilter {
+            allowed_systems: Some(vec![SystemId::new("geo_elsewhere").unwrap()]),
+            ..filter
+        };
+        assert!(
+            GeoQueries::list(&pool, &other, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Upload idempotency keys.
+//!
+//! Recorders retry uploads whose response timed out, often while the first
+//! attempt is still being stored. An upload sent with an `Idempotency-Key`
+//! claims the key for its system before storing the call and then records
+//! the call it created, so a retry finds that call instead of creating a
+//! duplicate. Keys expire after [`KEY_TTL_HOURS`], and claims abandoned by a
+//! crashed upload after [`CLAIM_TTL_MINUTES`].
+
+use crate::error::StorageError;
+use sdrtrunk_types::SystemId;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Result type alias for idempotency key operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Hours a key keeps returning the call it created.
+pub const KEY_TTL_HOURS: i32 = 24;
+
+/// Minutes before an unfinished claim may be taken over.
+pub const CLAIM_TTL_MINUTES: i32 = 10;
+
+/// Outcome of claiming an idempotency key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// The key is new; the caller stores the call and completes the key.
+    Claimed,
+    /// An earlier upload with the key created this call.
+    Completed(Uuid),
+    /// An earlier upload with the key is still being stored.
+    InProgress,
+}
+
+/// Upload idempotency key operations.
+#[derive(Debug)]
+pub struct IdempotencyQueries;
+
+impl IdempotencyQueries {
+    /// Call created by an earlier upload with `key`, if it has not expired.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn find(pool: &PgPool, system_id: &SystemId, key: &str) -> Result<Option<Uuid>> {
+        let call_id = sqlx::query_scalar::<_, Uuid>(
+            r"
+            SELECT call_id FROM upload_idempotency_keys
+            WHERE system_id = $1 AND idempotency_key = $2
+              AND call_id IS NOT NULL
+              AND created_at > NOW() - make_interval(hours => $3)
+            ",
+        )
+        .bind(system_id)
+        .bind(key)
+        .bind(KEY_TTL_HOURS)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(call_id)
+    }
+
+    /// Claim `key` for an upload about to store a call.
+    ///
+    /// Expired keys and abandoned claims are taken over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn claim(pool: &PgPool, system_id: &SystemId, key: &str) -> Result<IdempotencyClaim> {
+        let claimed = sqlx::query(
+            r"
+            INSERT INTO upload_idempotency_keys (system_id, idempotency_key)
+            VALUES ($1, $2)
+            ON CONFLICT (system_id, idempotency_key) DO UPDATE
+                SET call_id = NULL, created_at = NOW()
+                WHERE upload_idempotency_keys.created_at < NOW() - make_interval(hours => $3)
+                   OR (upload_idempotency_keys.call_id IS NULL
+                       AND upload_idempotency_keys.created_at < NOW() - make_interval(mins => $4))
+            ",
+        )
+        .bind(system_id)
+        .bind(key)
+        .bind(KEY_TTL_HOURS)
+        .bind(CLAIM_TTL_MINUTES)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        if claimed > 0 {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+
+        let call_id = sqlx::query_scalar::<_, Option<Uuid>>(
+            "SELECT call_id FROM upload_idempotency_keys WHERE system_id = $1 AND idempotency_key = $2",
+        )
+        .bind(system_id)
+        .bind(key)
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        Ok(call_id.map_or(IdempotencyClaim::InProgress, IdempotencyClaim::Completed))
+    }
+
+    /// Record the call created under a claimed key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn complete(
+        pool: &PgPool,
+        system_id: &SystemId,
+        key: &str,
+        call_id: Uuid,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            "UPDATE upload_idempotency_keys SET call_id = $3 WHERE system_id = $1 AND idempotency_key = $2",
+        )
+        .bind(system_id)
+        .bind(key)
+        .bind(call_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Give up a claim after the upload failed, so a retry can try again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn release(pool: &PgPool, system_id: &SystemId, key: &str) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            DELETE FROM upload_idempotency_keys
+            WHERE system_id = $1 AND idempotency_key = $2 AND call_id IS NULL
+            ",
+        )
+        .bind(system_id)
+        .bind(key)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::test_support::{create_test_pool, test_call};
+
+    fn test_system() -> SystemId {
+        SystemId::new(format!("idem_{}", &Uuid::new_v4().to_string()[..8])).unwrap()
+    }
+
+    async fn insert_call(pool: &PgPool, system_id: &SystemId) -> Uuid {
+        let call = RadioCallDb {
+            transcription_status: None,
+            ..test_call(system_id)
+        };
+        crate::insert_radio_call(pool, &call).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_claim_complete_and_replay() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = test_system();
+        let other_system = test_system();
+        let key = "retry-1";
+        assert_eq!(
+            IdempotencyQueries::find(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        );
+        // A retry while the first upload is still being stored
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::InProgress
+        );
+        assert_eq!(
+            IdempotencyQueries::find(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            None
+        );
+        // Keys are per system
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &other_system, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        );
+
+        let call_id = insert_call(&pool, &system_id).await;
+        IdempotencyQueries::complete(&pool, &system_id, key, call_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            IdempotencyQueries::find(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            Some(call_id)
+        );
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Completed(call_id)
+        );
+        // Completed keys are not released
+        IdempotencyQueries::release(&pool, &system_id, key)
+            .await
+            .unwrap();
+        assert_eq!(
+            IdempotencyQueries::find(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            Some(call_id)
+        );
+
+        // Deleting the call drops its key
+        sqlx::query("DELETE FROM radio_calls WHERE id = $1")
+            .bind(call_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        );
+
+        IdempotencyQueries::release(&pool, &system_id, key)
+            .await
+            .unwrap();
+        IdempotencyQueries::release(&pool, &other_system, key)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_claims_taken_over() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = test_system();
+        let key = "abandoned";
+        IdempotencyQueries::claim(&pool, &system_id, key)
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE upload_idempotency_keys SET created_at = NOW() - INTERVAL '11 minutes' WHERE system_id = $1",
+        )
+        .bind(&system_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        );
+
+        // Completed keys expire after a day
+        let call_id = insert_call(&pool, &system_id).await;
+        IdempotencyQueries::complete(&pool, &system_id, key, call_id)
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE upload_idempotency_keys SET created_at = NOW() - INTERVAL '25 hours' WHERE system_id = $1",
+        )
+        .bind(&system_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            IdempotencyQueries::find(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            IdempotencyQueries::claim(&pool, &system_id, key)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        );
+
+        IdempotencyQueries::release(&pool, &system_id, key)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM radio_calls WHERE id = $1")
+            .bind(call_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,248 @@
+//! Upload-only ingest keys.
+//!
+//! An ingest key is bound to exactly one system and only authorizes uploads
+//! for it, so each `SDRTrunk` instance can be given its own credential instead
+//! of a shared API key. Keys are stored as SHA-256 hashes in `ingest_keys`.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for ingest key operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `ingest_keys` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct IngestKey {
+    /// Key ID.
+    pub id: Uuid,
+    /// Hex SHA-256 of the key.
+    #[serde(skip_serializing)]
+    pub key_hash: String,
+    /// The only system the key may upload to.
+    pub system_id: SystemId,
+    /// Description, e.g. the recorder the key was issued to.
+    pub description: Option<String>,
+    /// When the key was created.
+    pub created_at: DateTime<Utc>,
+    /// When the key stops working.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether the key is usable (false once revoked).
+    pub active: bool,
+    /// Last upload with the key.
+    pub last_used: Option<DateTime<Utc>>,
+    /// Uploads made with the key.
+    pub total_uploads: i64,
+}
+
+/// Fields for a new ingest key.
+#[derive(Debug, Clone)]
+pub struct NewIngestKey<'a> {
+    /// Hex SHA-256 of the key.
+    pub key_hash: &'a str,
+    /// The only system the key may upload to.
+    pub system_id: &'a SystemId,
+    /// Description.
+    pub description: Option<&'a str>,
+    /// When the key stops working.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// ---------------------------------------------------------------------------
+// Ingest key operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for ingest keys.
+#[derive(Debug)]
+pub struct IngestKeyQueries;
+
+impl IngestKeyQueries {
+    /// Store a new ingest key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn create(pool: &PgPool, key: &NewIngestKey<'_>) -> Result<IngestKey> {
+        let key = sqlx::query_as::<_, IngestKey>(
+            r"
+            INSERT INTO ingest_keys (key_hash, system_id, description, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            ",
+        )
+        .bind(key.key_hash)
+        .bind(key.system_id)
+        .bind(key.description)
+        .bind(key.expires_at)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    /// Look up an active, unexpired key by its hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn validate(pool: &PgPool, key_hash: &str) -> Result<Option<IngestKey>> {
+        let key = sqlx::query_as::<_, IngestKey>(
+            r"
+            SELECT * FROM ingest_keys
+            WHERE key_hash = $1 AND active
+              AND (expires_at IS NULL OR expires_at > NOW())
+            ",
+        )
+        .bind(key_hash)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    /// List ingest keys, optionally only those for `system_id`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(pool: &PgPool, system_id: Option<&SystemId>) -> Result<Vec<IngestKey>> {
+        let keys = sqlx::query_as::<_, IngestKey>(
+            r"
+            SELECT * FROM ingest_keys
+            WHERE ($1::VARCHAR IS NULL OR system_id = $1)
+            ORDER BY created_at, id
+            ",
+        )
+        .bind(system_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(keys)
+    }
+
+    /// Revoke a key.
+    ///
+    /// Returns whether an active key was revoked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn revoke(pool: &PgPool, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("UPDATE ingest_keys SET active = FALSE WHERE id = $1 AND active")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Count an upload made with the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn record_use(pool: &PgPool, id: Uuid) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE ingest_keys
+            SET last_used = NOW(), total_uploads = total_uploads + 1
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+
+    #[tokio::test]
+    async fn test_ingest_key_lifecycle() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("ik_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let key_hash = format!("{:0>64}", Uuid::new_v4().simple());
+        let expired_hash = format!("{:0>64}", Uuid::new_v4().simple());
+
+        let key = IngestKeyQueries::create(
+            &pool,
+            &NewIngestKey {
+                key_hash: &key_hash,
+                system_id: &system_id,
+                description: Some("Recorder 1"),
+                expires_at: None,
+            },
+        )
+        .await
+        .unwrap();
+        IngestKeyQueries::create(
+            &pool,
+            &NewIngestKey {
+                key_hash: &expired_hash,
+                system_id: &system_id,
+                description: None,
+                expires_at: Some(Utc::now() - chrono::Duration::hours(1)),
+            },
+        )
+        .await
+        .unwrap();
+
+        let found = IngestKeyQueries::validate(&pool, &key_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, key.id);
+        assert_eq!(found.system_id, system_id);
+        assert!(
+            IngestKeyQueries::validate(&pool, &expired_hash)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        IngestKeyQueries::record_use(&pool, key.id).await.unwrap();
+        let listed = IngestKeyQueries::list(&pool, Some(&system_id))
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].total_uploads, 1);
+        assert!(listed[0].last_used.is_some());
+
+        assert!(IngestKeyQueries::revoke(&pool, key.id).await.unwrap());
+        assert!(!IngestKeyQueries::revoke(&pool, key.id).await.unwrap());
+        assert!(
+            IngestKeyQueries::validate(&pool, &key_hash)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
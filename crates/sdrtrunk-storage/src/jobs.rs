@@ -8,6 +8,7 @@
 use crate::error::StorageError;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use sdrtrunk_types::{SystemId, TalkgroupId};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool, Row};
 use uuid::Uuid;
@@ -121,6 +122,97 @@ pub struct QueueStats {
     pub total: i64,
 }
 
+/// Snapshot of queue pressure used to pace uploading clients.
+#[derive(Debug, Clone, Default, FromRow, Serialize)]
+pub struct QueueBacklog {
+    /// Number of jobs waiting to be claimed.
+    pub pending: i64,
+    /// Number of jobs currently being processed.
+    pub processing: i64,
+    /// Number of distinct workers currently holding jobs.
+    pub active_workers: i64,
+    /// Average processing time of jobs completed in the last hour, in milliseconds.
+    pub avg_processing_ms: Option<f64>,
+}
+
+impl QueueBacklog {
+    /// Jobs not yet finished (pending plus in-flight).
+    #[must_use]
+    pub const fn depth(&self) -> i64 {
+        self.pending + self.processing
+    }
+
+    /// Estimated seconds until the current backlog drains.
+    ///
+    /// Assumes the recent average processing time and at least one worker.
+    /// Returns `None` when no jobs completed recently to base the estimate on.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn estimated_seconds(&self) -> Option<u64> {
+        let avg_ms = self.avg_processing_ms.filter(|ms| *ms > 0.0)?;
+        let workers = self.active_workers.max(1) as f64;
+        let seconds = (self.depth().max(0) as f64 * avg_ms / 1000.0 / workers).ceil();
+        Some(seconds as u64)
+    }
+}
+
+/// Selects calls for batch re-transcription.
+///
+/// Every field is optional; unset fields match all calls. Calls that already
+/// have a pending or processing job are never selected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetryFilter {
+    /// Only calls from this system.
+    pub system_id: Option<SystemId>,
+    /// Only calls on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Only calls with this `transcription_status` (e.g. `failed`).
+    pub status: Option<String>,
+    /// Only calls at or after this time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only calls before this time.
+    pub to_date: Option<DateTime<Utc>>,
+    /// Re-queue at most this many calls, newest first.
+    pub limit: Option<i64>,
+}
+
+/// Limits for [`JobQueue::requeue_stuck`].
+///
+/// The n-th automatic retry of a call waits `base_delay_seconds * 2^(n-1)`
+/// (capped at `max_delay_seconds`) after the call last failed or started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoRetryParams {
+    /// Automatic retries per call, counted in `transcription_retry_attempts`.
+    pub max_attempts: i32,
+    /// Delay before the first automatic retry.
+    pub base_delay_seconds: i64,
+    /// Longest delay between automatic retries.
+    pub max_delay_seconds: i64,
+    /// A call `processing` this long without an active job is stuck.
+    pub stale_processing_seconds: i64,
+    /// Re-queue at most this many calls.
+    pub limit: i64,
+}
+
+/// Candidate calls for [`JobQueue::retry_calls`]; binds `$1`–`$5` from a
+/// [`RetryFilter`].
+const RETRY_CANDIDATES: &str = r"
+    FROM radio_calls rc
+    WHERE ($1::VARCHAR IS NULL OR rc.system_id = $1)
+      AND ($2::INTEGER IS NULL OR rc.talkgroup_id = $2)
+      AND ($3::VARCHAR IS NULL OR rc.transcription_status = $3)
+      AND ($4::TIMESTAMPTZ IS NULL OR rc.call_timestamp >= $4)
+      AND ($5::TIMESTAMPTZ IS NULL OR rc.call_timestamp < $5)
+      AND NOT EXISTS (
+          SELECT 1 FROM transcription_jobs j
+          WHERE j.call_id = rc.id AND j.status IN ('pending', 'processing')
+      )
+";
+
 // ---------------------------------------------------------------------------
 // Job queue operations
 // ---------------------------------------------------------------------------
@@ -135,8 +227,8 @@ pub struct JobQueue;
 impl JobQueue {
     /// Enqueue a new transcription job.
     ///
-    /// Inserts a row into `transcription_jobs` with status `pending` and
-    /// returns the generated job id.
+    /// Inserts a row into `transcription_jobs` with status `pending`, records
+    /// a `queued` call event, and returns the generated job id.
     ///
     /// # Errors
     ///
@@ -145,9 +237,16 @@ impl JobQueue {
     pub async fn enqueue(pool: &PgPool, params: &EnqueueParams) -> Result<Uuid> {
         let row = sqlx::query(
             r"
-            INSERT INTO transcription_jobs (call_id, audio_path, audio_data, priority, options, timeout_seconds)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id
+            WITH job AS (
+                INSERT INTO transcription_jobs (call_id, audio_path, audio_data, priority, options, timeout_seconds)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, call_id
+            ),
+            event AS (
+                INSERT INTO call_events (call_id, event, detail)
+                SELECT call_id, 'queued', 'job ' || id FROM job
+            )
+            SELECT id FROM job
             ",
         )
         .bind(params.call_id)
@@ -168,7 +267,8 @@ impl JobQueue {
     /// Uses `SELECT ... FOR UPDATE SKIP LOCKED` so that multiple workers can
     /// safely race for jobs without blocking each other.
     ///
-    /// Returns `None` when no claimable jobs exist.
+    /// Records a `claimed` call event naming the worker. Returns `None` when
+    /// no claimable jobs exist.
     ///
     /// # Errors
     ///
@@ -176,21 +276,28 @@ impl JobQueue {
     pub async fn claim(pool: &PgPool, worker_id: &str) -> Result<Option<TranscriptionJob>> {
         let job = sqlx::query_as::<_, TranscriptionJob>(
             r"
-            UPDATE transcription_jobs
-            SET status       = 'processing',
-                worker_id    = $1,
-                claimed_at   = NOW(),
-                heartbeat_at = NOW(),
-                started_at   = NOW()
-            WHERE id = (
-                SELECT id
-                FROM transcription_jobs
-                WHERE status = 'pending'
-                ORDER BY priority DESC, created_at ASC
-                LIMIT 1
-                FOR UPDATE SKIP LOCKED
+            WITH claimed AS (
+                UPDATE transcription_jobs
+                SET status       = 'processing',
+                    worker_id    = $1,
+                    claimed_at   = NOW(),
+                    heartbeat_at = NOW(),
+                    started_at   = NOW()
+                WHERE id = (
+                    SELECT id
+                    FROM transcription_jobs
+                    WHERE status = 'pending'
+                    ORDER BY priority DESC, created_at ASC
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING *
+            ),
+            event AS (
+                INSERT INTO call_events (call_id, event, detail)
+                SELECT call_id, 'claimed', $1 FROM claimed
             )
-            RETURNING *
+            SELECT * FROM claimed
             ",
         )
         .bind(worker_id)
@@ -367,6 +474,35 @@ impl JobQueue {
         Ok(i64::try_from(result.rows_affected()).unwrap_or(0))
     }
 
+    /// Return a worker's unfinished jobs to the queue.
+    ///
+    /// Used by a stopping worker for jobs it abandons, so they are picked up
+    /// again right away instead of waiting out their heartbeat timeout.
+    /// Returns the number of jobs released.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn release(pool: &PgPool, worker_id: &str) -> Result<i64> {
+        let result = sqlx::query(
+            r"
+            UPDATE transcription_jobs
+            SET status       = 'pending',
+                worker_id    = NULL,
+                claimed_at   = NULL,
+                heartbeat_at = NULL,
+                started_at   = NULL
+            WHERE status = 'processing'
+              AND worker_id = $1
+            ",
+        )
+        .bind(worker_id)
+        .execute(pool)
+        .await?;
+
+        Ok(i64::try_from(result.rows_affected()).unwrap_or(0))
+    }
+
     /// Return aggregate counts for each job status.
     ///
     /// # Errors
@@ -389,6 +525,224 @@ impl JobQueue {
 
         Ok(stats)
     }
+
+    /// Count unfinished jobs (pending plus in-flight).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn depth(pool: &PgPool) -> Result<i64> {
+        let depth: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM transcription_jobs WHERE status IN ('pending', 'processing')",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(depth)
+    }
+
+    /// Return the current backlog and recent throughput.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn backlog(pool: &PgPool) -> Result<QueueBacklog> {
+        let backlog = sqlx::query_as::<_, QueueBacklog>(
+            r"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'pending')    AS pending,
+                COUNT(*) FILTER (WHERE status = 'processing') AS processing,
+                COUNT(DISTINCT worker_id) FILTER (WHERE status = 'processing') AS active_workers,
+                AVG(processing_time_ms) FILTER (
+                    WHERE status = 'completed'
+                      AND completed_at > NOW() - INTERVAL '1 hour'
+                )::DOUBLE PRECISION AS avg_processing_ms
+            FROM transcription_jobs
+            WHERE status IN ('pending', 'processing')
+               OR (status = 'completed' AND completed_at > NOW() - INTERVAL '1 hour')
+            ",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(backlog)
+    }
+
+    /// Re-queue calls whose transcription failed or got stuck.
+    ///
+    /// Selects calls left `failed`, or left `processing` for longer than
+    /// `stale_processing_seconds`, that have no pending or processing job,
+    /// are under `max_attempts` automatic retries, and have waited out their
+    /// back-off. Each gets a fresh job as in [`JobQueue::retry_calls`] and its
+    /// attempt count is incremented. Returns the number of calls re-queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn requeue_stuck(
+        pool: &PgPool,
+        params: &AutoRetryParams,
+        priority: i32,
+        options: &serde_json::Value,
+        timeout_seconds: i32,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r"
+            WITH matched AS (
+                SELECT rc.id, rc.audio_file_path
+                FROM radio_calls rc
+                WHERE rc.transcription_retry_attempts < $1
+                  AND (
+                      rc.transcription_status = 'failed'
+                      OR (
+                          rc.transcription_status = 'processing'
+                          AND COALESCE(rc.transcription_started_at, rc.upload_timestamp)
+                              < NOW() - make_interval(secs => $4::DOUBLE PRECISION)
+                      )
+                  )
+                  AND COALESCE(
+                          GREATEST(rc.transcription_completed_at, rc.transcription_started_at),
+                          rc.upload_timestamp
+                      ) + make_interval(secs => LEAST(
+                          $2::DOUBLE PRECISION * POWER(2, rc.transcription_retry_attempts),
+                          $3::DOUBLE PRECISION
+                      )) <= NOW()
+                  AND NOT EXISTS (
+                      SELECT 1 FROM transcription_jobs j
+                      WHERE j.call_id = rc.id AND j.status IN ('pending', 'processing')
+                  )
+                ORDER BY rc.call_timestamp DESC
+                LIMIT $5
+                FOR UPDATE OF rc SKIP LOCKED
+            ),
+            queued AS (
+                INSERT INTO transcription_jobs
+                    (call_id, audio_path, audio_data, priority, options, timeout_seconds)
+                SELECT
+                    m.id,
+                    m.audio_file_path,
+                    (
+                        SELECT j.audio_data FROM transcription_jobs j
+                        WHERE j.call_id = m.id AND j.audio_data IS NOT NULL
+                        ORDER BY j.created_at DESC
+                        LIMIT 1
+                    ),
+                    $6, $7, $8
+                FROM matched m
+                RETURNING id, call_id
+            ),
+            event AS (
+                INSERT INTO call_events (call_id, event, detail)
+                SELECT call_id, 'queued', 'automatic retry, job ' || id FROM queued
+            )
+            UPDATE radio_calls
+            SET transcription_status         = 'pending',
+                transcription_error          = NULL,
+                transcription_retry_attempts = transcription_retry_attempts + 1
+            WHERE id IN (SELECT call_id FROM queued)
+            ",
+        )
+        .bind(params.max_attempts)
+        .bind(params.base_delay_seconds)
+        .bind(params.max_delay_seconds)
+        .bind(params.stale_processing_seconds)
+        .bind(params.limit)
+        .bind(priority)
+        .bind(options)
+        .bind(timeout_seconds)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Count calls [`JobQueue::retry_calls`] would re-queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn count_retryable(pool: &PgPool, filter: &RetryFilter) -> Result<i64> {
+        let query =
+            format!("SELECT COUNT(*) FROM (SELECT rc.id {RETRY_CANDIDATES} LIMIT $6) candidates");
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(filter.system_id.as_ref())
+            .bind(filter.talkgroup_id)
+            .bind(filter.status.as_deref())
+            .bind(filter.from_date)
+            .bind(filter.to_date)
+            .bind(filter.limit)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Enqueue a fresh job for every call matching `filter`.
+    ///
+    /// Each job reuses the audio bytes stored with the call's most recent
+    /// earlier job, falling back to the call's file path, so workers without
+    /// shared storage can still process it. The calls are reset to
+    /// `pending`. Returns the number of calls re-queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn retry_calls(
+        pool: &PgPool,
+        filter: &RetryFilter,
+        priority: i32,
+        options: &serde_json::Value,
+        timeout_seconds: i32,
+    ) -> Result<u64> {
+        let query = format!(
+            r"
+            WITH matched AS (
+                SELECT rc.id, rc.audio_file_path {RETRY_CANDIDATES}
+                ORDER BY rc.call_timestamp DESC
+                LIMIT $6
+                FOR UPDATE OF rc SKIP LOCKED
+            ),
+            queued AS (
+                INSERT INTO transcription_jobs
+                    (call_id, audio_path, audio_data, priority, options, timeout_seconds)
+                SELECT
+                    m.id,
+                    m.audio_file_path,
+                    (
+                        SELECT j.audio_data FROM transcription_jobs j
+                        WHERE j.call_id = m.id AND j.audio_data IS NOT NULL
+                        ORDER BY j.created_at DESC
+                        LIMIT 1
+                    ),
+                    $7, $8, $9
+                FROM matched m
+                RETURNING id, call_id
+            ),
+            event AS (
+                INSERT INTO call_events (call_id, event, detail)
+                SELECT call_id, 'queued', 'retry, job ' || id FROM queued
+            )
+            UPDATE radio_calls
+            SET transcription_status = 'pending',
+                transcription_error  = NULL
+            WHERE id IN (SELECT call_id FROM queued)
+            "
+        );
+        let result = sqlx::query(&query)
+            .bind(filter.system_id.as_ref())
+            .bind(filter.talkgroup_id)
+            .bind(filter.status.as_deref())
+            .bind(filter.from_date)
+            .bind(filter.to_date)
+            .bind(filter.limit)
+            .bind(priority)
+            .bind(options)
+            .bind(timeout_seconds)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -408,6 +762,7 @@ impl JobQueue {
 )]
 mod tests {
     use super::*;
+    use crate::test_support::{create_test_pool, test_call};
 
     #[test]
     fn test_enqueue_params_creation() {
@@ -523,6 +878,34 @@ mod tests {
         assert!(json.contains("\"total\":115"));
     }
 
+    #[test]
+    fn test_queue_backlog_estimate() {
+        let backlog = QueueBacklog {
+            pending: 10,
+            processing: 2,
+            active_workers: 2,
+            avg_processing_ms: Some(1500.0),
+        };
+        assert_eq!(backlog.depth(), 12);
+        assert_eq!(backlog.estimated_seconds(), Some(9));
+    }
+
+    #[test]
+    fn test_queue_backlog_estimate_without_history() {
+        let backlog = QueueBacklog {
+            pending: 5,
+            ..QueueBacklog::default()
+        };
+        assert_eq!(backlog.depth(), 5);
+        assert_eq!(backlog.estimated_seconds(), None);
+
+        let idle = QueueBacklog {
+            avg_processing_ms: Some(2000.0),
+            ..QueueBacklog::default()
+        };
+        assert_eq!(idle.estimated_seconds(), Some(0));
+    }
+
     #[test]
     fn test_job_queue_is_debug() {
         fn assert_debug<T: std::fmt::Debug>() {}
@@ -731,4 +1114,77 @@ mod tests {
         assert_eq!(arr[2]["text"], "Responding to call at 123 Main Street");
         assert_eq!(restored.speaker_count, Some(2));
     }
+
+    fn failed_call() -> crate::models::RadioCallDb {
+        crate::models::RadioCallDb {
+            audio_filename: Some("call.mp3".to_string()),
+            audio_file_path: Some("rq/call.mp3".to_string()),
+            transcription_status: Some("failed".to_string()),
+            ..test_call(&SystemId::new(format!("rq_{}", &Uuid::new_v4().to_string()[..8])).unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requeue_stuck_respects_backoff_and_attempts() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+        let call = failed_call();
+        crate::queries::RadioCallQueries::insert(&pool, &call)
+            .await
+            .unwrap();
+        let attempts = |pool: PgPool| async move {
+            sqlx::query_scalar::<_, i32>(
+                "SELECT transcription_retry_attempts FROM radio_calls WHERE id = $1",
+            )
+            .bind(call.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        };
+        let mut params = AutoRetryParams {
+            max_attempts: 1,
+            base_delay_seconds: 3600,
+            max_delay_seconds: 3600,
+            stale_processing_seconds: 1800,
+            limit: 1000,
+        };
+        let options = serde_json::json!({});
+
+        // Still backing off
+        JobQueue::requeue_stuck(&pool, &params, -1, &options, 300)
+            .await
+            .unwrap();
+        assert_eq!(attempts(pool.clone()).await, 0);
+
+        params.base_delay_seconds = 0;
+        JobQueue::requeue_stuck(&pool, &params, -1, &options, 300)
+            .await
+            .unwrap();
+        assert_eq!(attempts(pool.clone()).await, 1);
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT transcription_status FROM radio_calls WHERE id = $1")
+                .bind(call.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status.as_deref(), Some("pending"));
+
+        // Out of attempts once the retry fails again
+        sqlx::query("UPDATE transcription_jobs SET status = 'failed' WHERE call_id = $1")
+            .bind(call.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE radio_calls SET transcription_status = 'failed' WHERE id = $1")
+            .bind(call.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        JobQueue::requeue_stuck(&pool, &params, -1, &options, 300)
+            .await
+            .unwrap();
+        assert_eq!(attempts(pool).await, 1);
+    }
 }
@@ -0,0 +1,676 @@
+//! Import from the legacy Python `sdrtrunk-transcriber`.
+//!
+//! The original project stored each call as a row in a `SQLite` database next to
+//! its MP3 recordings. Column names drifted between releases (`system` vs
+//! `system_id`, `transcription` vs `transcript`, unix vs text timestamps), so
+//! columns are matched by alias and every value is read as text and parsed
+//! here. Rows become [`RadioCallDb`] records tagged with
+//! [`LEGACY_IMPORT_KEY_ID`]; copying audio into the current storage layout is
+//! left to the caller.
+
+use crate::error::StorageError;
+use crate::models::RadioCallDb;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::{FromRow, PgPool};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Result type alias for legacy import operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Tables searched for calls, in order of preference.
+pub const LEGACY_TABLES: &[&str] = &["radio_calls", "calls", "transcriptions"];
+
+/// API key ID recorded on imported calls.
+pub const LEGACY_IMPORT_KEY_ID: &str = "legacy-import";
+
+/// A target field and the legacy column names that may hold it.
+type FieldAliases = (&'static str, &'static [&'static str]);
+
+/// Target fields and the legacy column names that may hold them.
+const LEGACY_FIELDS: &[FieldAliases] = &[
+    (
+        "call_timestamp",
+        &[
+            "call_timestamp",
+            "timestamp",
+            "date_time",
+            "datetime",
+            "created_at",
+        ],
+    ),
+    ("system_id", &["system_id", "system"]),
+    ("system_label", &["system_label", "system_name"]),
+    ("frequency", &["frequency", "freq"]),
+    ("talkgroup_id", &["talkgroup_id", "talkgroup", "tgid"]),
+    ("talkgroup_label", &["talkgroup_label", "talkgroup_name"]),
+    ("talkgroup_group", &["talkgroup_group"]),
+    ("talkgroup_tag", &["talkgroup_tag"]),
+    (
+        "source_radio_id",
+        &["source_radio_id", "source", "radio_id"],
+    ),
+    ("talker_alias", &["talker_alias"]),
+    (
+        "audio_filename",
+        &["audio_filename", "filename", "file_name"],
+    ),
+    (
+        "audio_path",
+        &[
+            "audio_file_path",
+            "audio_path",
+            "file_path",
+            "filepath",
+            "mp3_path",
+        ],
+    ),
+    ("duration_seconds", &["duration_seconds", "duration"]),
+    (
+        "transcription_text",
+        &["transcription_text", "transcription", "transcript", "text"],
+    ),
+    ("patches", &["patches"]),
+    ("frequencies", &["frequencies"]),
+    ("sources", &["sources"]),
+];
+
+/// Fields that must be present in the legacy table.
+const REQUIRED_FIELDS: &[&str] = &["call_timestamp", "system_id"];
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One call as read from the legacy database, with every value as text.
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct LegacyCallRow {
+    /// `SQLite` rowid, used to page through the table.
+    pub legacy_rowid: i64,
+    /// Call time (unix seconds or milliseconds, or a date-time string).
+    pub call_timestamp: Option<String>,
+    /// System identifier.
+    pub system_id: Option<String>,
+    /// System label.
+    pub system_label: Option<String>,
+    /// Frequency in Hz or MHz.
+    pub frequency: Option<String>,
+    /// Talkgroup decimal ID.
+    pub talkgroup_id: Option<String>,
+    /// Talkgroup label.
+    pub talkgroup_label: Option<String>,
+    /// Talkgroup group.
+    pub talkgroup_group: Option<String>,
+    /// Talkgroup tag.
+    pub talkgroup_tag: Option<String>,
+    /// Source radio ID.
+    pub source_radio_id: Option<String>,
+    /// Talker alias.
+    pub talker_alias: Option<String>,
+    /// Recording file name.
+    pub audio_filename: Option<String>,
+    /// Recording path, absolute or relative to the legacy audio directory.
+    pub audio_path: Option<String>,
+    /// Call duration in seconds.
+    pub duration_seconds: Option<String>,
+    /// Transcript.
+    pub transcription_text: Option<String>,
+    /// Patched talkgroups (JSON).
+    pub patches: Option<String>,
+    /// Frequencies (JSON).
+    pub frequencies: Option<String>,
+    /// Sources (JSON).
+    pub sources: Option<String>,
+}
+
+impl LegacyCallRow {
+    /// Convert to a call record.
+    ///
+    /// `audio_file_path` and `audio_size_bytes` describe the recording after
+    /// it has been copied into current storage, if it was found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row has no usable timestamp or system ID.
+    pub fn to_radio_call(
+        &self,
+        audio_file_path: Option<String>,
+        audio_size_bytes: Option<i64>,
+    ) -> Result<RadioCallDb> {
+        let call_timestamp = self
+            .call_timestamp
+            .as_deref()
+            .and_then(parse_legacy_timestamp)
+            .ok_or_else(|| self.invalid("missing or unparseable timestamp"))?;
+        let system_id = self
+            .system_id
+            .as_deref()
+            .map(str::trim)
+            .and_then(|id| SystemId::new(id).ok())
+            .ok_or_else(|| self.invalid("missing or invalid system ID"))?;
+
+        let transcription_text = non_empty(self.transcription_text.as_deref());
+        let transcription_status = if transcription_text.is_some() {
+            "completed"
+        } else {
+            "none"
+        };
+
+        Ok(RadioCallDb {
+            id: Uuid::new_v4(),
+            created_at: call_timestamp,
+            call_timestamp,
+            system_id,
+            system_label: non_empty(self.system_label.as_deref()),
+            frequency: self
+                .frequency
+                .as_deref()
+                .and_then(parse_legacy_frequency)
+                .and_then(|hz| Frequency::new(hz).ok()),
+            talkgroup_id: self
+                .talkgroup_id
+                .as_deref()
+                .and_then(parse_legacy_int)
+                .and_then(|id| TalkgroupId::new(id).ok()),
+            talkgroup_label: non_empty(self.talkgroup_label.as_deref()),
+            talkgroup_group: non_empty(self.talkgroup_group.as_deref()),
+            talkgroup_tag: non_empty(self.talkgroup_tag.as_deref()),
+            source_radio_id: self
+                .source_radio_id
+                .as_deref()
+                .and_then(parse_legacy_int)
+                .and_then(|id| RadioId::new(id).ok()),
+            talker_alias: non_empty(self.talker_alias.as_deref()),
+            audio_filename: self.file_name(),
+            audio_file_path,
+            audio_size_bytes,
+            audio_content_type: None,
+            audio_sha256: None,
+            duration_seconds: self
+                .duration_seconds
+                .as_deref()
+                .and_then(|d| d.trim().parse::<Decimal>().ok()),
+            transcription_text,
+            transcription_confidence: None,
+            transcription_language: None,
+            transcription_status: Some(transcription_status.to_string()),
+            speaker_segments: None,
+            speaker_count: None,
+            patches: non_empty(self.patches.as_deref()),
+            frequencies: non_empty(self.frequencies.as_deref()),
+            sources: non_empty(self.sources.as_deref()),
+            upload_ip: None,
+            upload_timestamp: call_timestamp,
+            upload_api_key_id: Some(LEGACY_IMPORT_KEY_ID.to_string()),
+            latitude: None,
+            longitude: None,
+        })
+    }
+
+    /// Recording file name, from the filename column or the path.
+    #[must_use]
+    pub fn file_name(&self) -> Option<String> {
+        non_empty(self.audio_filename.as_deref()).or_else(|| {
+            self.audio_path
+                .as_deref()
+                .and_then(|p| Path::new(p.trim()).file_name())
+                .map(|name| name.to_string_lossy().to_string())
+        })
+    }
+
+    fn invalid(&self, reason: &str) -> StorageError {
+        StorageError::Serialization(format!("legacy row {}: {reason}", self.legacy_rowid))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Legacy database
+// ---------------------------------------------------------------------------
+
+/// Read-only handle on a legacy `SQLite` database.
+#[derive(Debug)]
+pub struct LegacyDatabase {
+    pool: SqlitePool,
+    table: String,
+    select: String,
+}
+
+impl LegacyDatabase {
+    /// Open a legacy database and locate its calls table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened, no known calls table
+    /// exists, or the table lacks a timestamp or system column.
+    pub async fn open(path: &Path) -> Result<Self> {
+        let options = SqliteConnectOptions::new().filename(path).read_only(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(|e| {
+                StorageError::Connection(format!("cannot open {}: {e}", path.display()))
+            })?;
+
+        let mut table = None;
+        for candidate in LEGACY_TABLES {
+            let found: Option<String> = sqlx::query_scalar(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+            )
+            .bind(candidate)
+            .fetch_optional(&pool)
+            .await?;
+            if found.is_some() {
+                table = Some((*candidate).to_string());
+                break;
+            }
+        }
+        let table = table.ok_or_else(|| {
+            StorageError::Migration(format!(
+                "no calls table found (looked for {})",
+                LEGACY_TABLES.join(", ")
+            ))
+        })?;
+
+        let columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+            .bind(&table)
+            .fetch_all(&pool)
+            .await?;
+        let select = build_select(&table, &columns)?;
+
+        Ok(Self {
+            pool,
+            table,
+            select,
+        })
+    }
+
+    /// Name of the calls table being read.
+    #[must_use]
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Number of rows in the calls table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count(&self) -> Result<i64> {
+        let count = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {}",
+            quote_identifier(&self.table)
+        ))
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// Read up to `limit` rows with a rowid greater than `after_rowid`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn read_batch(&self, after_rowid: i64, limit: i64) -> Result<Vec<LegacyCallRow>> {
+        let rows = sqlx::query_as::<_, LegacyCallRow>(&self.select)
+            .bind(after_rowid)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    /// Close the underlying connection.
+    pub async fn close(self) {
+        self.pool.close().await;
+    }
+}
+
+/// Build the paging query for a legacy table with the given columns.
+///
+/// # Errors
+///
+/// Returns an error if a required field has no matching column.
+fn build_select(table: &str, columns: &[String]) -> Result<String> {
+    let mut exprs = vec!["rowid AS legacy_rowid".to_string()];
+    for (field, aliases) in LEGACY_FIELDS {
+        let column = aliases
+            .iter()
+            .find_map(|alias| columns.iter().find(|c| c.eq_ignore_ascii_case(alias)));
+        match column {
+            Some(column) => exprs.push(format!(
+                "CAST({} AS TEXT) AS {field}",
+                quote_identifier(column)
+            )),
+            None if REQUIRED_FIELDS.contains(field) => {
+                return Err(StorageError::Migration(format!(
+                    "legacy table {table} has no {field} column (tried {})",
+                    aliases.join(", ")
+                )));
+            }
+            None => exprs.push(format!("NULL AS {field}")),
+        }
+    }
+
+    Ok(format!(
+        "SELECT {} FROM {} WHERE rowid > ? ORDER BY rowid LIMIT ?",
+        exprs.join(", "),
+        quote_identifier(table)
+    ))
+}
+
+/// Quote a name as a SQL identifier.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// ---------------------------------------------------------------------------
+// Value parsing
+// ---------------------------------------------------------------------------
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Parse a legacy timestamp.
+///
+/// Accepts unix seconds or milliseconds, RFC 3339, `YYYY-MM-DD HH:MM:SS[.f]`
+/// (assumed UTC), and the `YYYYMMDD_HHMMSS` form used in `SDRTrunk` file names.
+#[must_use]
+pub fn parse_legacy_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<f64>() {
+        #[allow(clippy::cast_possible_truncation)]
+        let number = number as i64;
+        // Anything past the year 33658 in seconds is really milliseconds
+        return if number > 1_000_000_000_000 {
+            Utc.timestamp_millis_opt(number).single()
+        } else {
+            Utc.timestamp_opt(number, 0).single()
+        };
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y%m%d_%H%M%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|naive| naive.and_utc())
+}
+
+/// Parse a legacy frequency as Hz. Values below 100,000 are taken as MHz.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn parse_legacy_frequency(value: &str) -> Option<i64> {
+    let value = value.trim().parse::<f64>().ok()?;
+    let hz = if value < 100_000.0 {
+        value * 1_000_000.0
+    } else {
+        value
+    };
+    Some(hz.round() as i64)
+}
+
+/// Parse a legacy integer, tolerating a `.0` suffix from REAL columns.
+fn parse_legacy_int(value: &str) -> Option<i32> {
+    let value = value.trim();
+    value
+        .parse::<i32>()
+        .ok()
+        .or_else(|| value.strip_suffix(".0").and_then(|v| v.parse().ok()))
+}
+
+// ---------------------------------------------------------------------------
+// Target database
+// ---------------------------------------------------------------------------
+
+/// Check whether a call with the same system, time, and talkgroup exists.
+///
+/// Used to make re-running an import skip rows already copied.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn legacy_call_exists(pool: &PgPool, call: &RadioCallDb) -> Result<bool> {
+    let exists = sqlx::query_scalar(
+        r"
+        SELECT EXISTS (
+            SELECT 1 FROM radio_calls
+            WHERE system_id = $1
+              AND call_timestamp = $2
+              AND talkgroup_id IS NOT DISTINCT FROM $3
+        )
+        ",
+    )
+    .bind(&call.system_id)
+    .bind(call.call_timestamp)
+    .bind(call.talkgroup_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(exists)
+}
+
+/// Recompute a system's `system_stats` row from its calls.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn refresh_system_stats(pool: &PgPool, system_id: &SystemId) -> Result<()> {
+    let _ = sqlx::query(
+        r"
+        INSERT INTO system_stats (
+            system_id, system_label, total_calls, calls_today, calls_this_hour,
+            first_seen, last_seen, last_updated
+        )
+        SELECT
+            system_id,
+            MAX(system_label),
+            COUNT(*)::INTEGER,
+            (COUNT(*) FILTER (WHERE call_timestamp > NOW() - INTERVAL '1 day'))::INTEGER,
+            (COUNT(*) FILTER (WHERE call_timestamp > NOW() - INTERVAL '1 hour'))::INTEGER,
+            MIN(call_timestamp),
+            MAX(call_timestamp),
+            NOW()
+        FROM radio_calls
+        WHERE system_id = $1
+        GROUP BY system_id
+        ON CONFLICT (system_id) DO UPDATE SET
+            system_label = COALESCE(system_stats.system_label, EXCLUDED.system_label),
+            total_calls = EXCLUDED.total_calls,
+            calls_today = EXCLUDED.calls_today,
+            calls_this_hour = EXCLUDED.calls_this_hour,
+            first_seen = EXCLUDED.first_seen,
+            last_seen = EXCLUDED.last_seen,
+            last_updated = EXCLUDED.last_updated
+        ",
+    )
+    .bind(system_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn row() -> LegacyCallRow {
+        LegacyCallRow {
+            legacy_rowid: 7,
+            call_timestamp: Some("1704112496".to_string()),
+            system_id: Some(" metro ".to_string()),
+            frequency: Some("851.0125".to_string()),
+            talkgroup_id: Some("52198".to_string()),
+            source_radio_id: Some("1234567.0".to_string()),
+            audio_path: Some("/recordings/52198/20240101_123456_TO_52198.mp3".to_string()),
+            duration_seconds: Some("4.2".to_string()),
+            transcription_text: Some("Engine 4 on scene".to_string()),
+            ..LegacyCallRow::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy_timestamp() {
+        let expected = Utc.with_ymd_and_hms(2024, 1, 1, 12, 34, 56).unwrap();
+        for value in [
+            "1704112496",
+            "1704112496000",
+            "1704112496.0",
+            "2024-01-01T12:34:56Z",
+            "2024-01-01 12:34:56",
+            "2024-01-01 12:34:56.000",
+            "20240101_123456",
+        ] {
+            assert_eq!(parse_legacy_timestamp(value), Some(expected), "{value}");
+        }
+        assert_eq!(parse_legacy_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn test_parse_legacy_frequency() {
+        assert_eq!(parse_legacy_frequency("851.0125"), Some(851_012_500));
+        assert_eq!(parse_legacy_frequency("851012500"), Some(851_012_500));
+        assert_eq!(parse_legacy_frequency("n/a"), None);
+    }
+
+    #[test]
+    fn test_to_radio_call() {
+        let call = row()
+            .to_radio_call(Some("/data/metro/a.mp3".to_string()), Some(2048))
+            .unwrap();
+
+        assert_eq!(call.system_id.as_str(), "metro");
+        assert_eq!(call.call_timestamp.timestamp(), 1_704_112_496);
+        assert_eq!(call.frequency.map(Frequency::as_hz), Some(851_012_500));
+        assert_eq!(call.talkgroup_id.map(TalkgroupId::as_i32), Some(52198));
+        assert_eq!(call.source_radio_id.map(RadioId::as_i32), Some(1_234_567));
+        assert_eq!(
+            call.audio_filename.as_deref(),
+            Some("20240101_123456_TO_52198.mp3")
+        );
+        assert_eq!(call.audio_size_bytes, Some(2048));
+        assert_eq!(call.duration_seconds, Some(Decimal::new(42, 1)));
+        assert_eq!(call.transcription_status.as_deref(), Some("completed"));
+        assert_eq!(
+            call.upload_api_key_id.as_deref(),
+            Some(LEGACY_IMPORT_KEY_ID)
+        );
+    }
+
+    #[test]
+    fn test_to_radio_call_rejects_invalid_rows() {
+        let mut missing_system = row();
+        missing_system.system_id = Some("  ".to_string());
+        assert!(missing_system.to_radio_call(None, None).is_err());
+
+        let mut bad_time = row();
+        bad_time.call_timestamp = None;
+        assert!(bad_time.to_radio_call(None, None).is_err());
+
+        let mut untranscribed = row();
+        untranscribed.transcription_text = Some(String::new());
+        let call = untranscribed.to_radio_call(None, None).unwrap();
+        assert_eq!(call.transcription_status.as_deref(), Some("none"));
+    }
+
+    #[test]
+    fn test_build_select_matches_aliases() {
+        let columns: Vec<String> = ["id", "Timestamp", "system", "talkgroup", "transcript"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let select = build_select("calls", &columns).unwrap();
+
+        assert!(select.contains("CAST(\"Timestamp\" AS TEXT) AS call_timestamp"));
+        assert!(select.contains("CAST(\"system\" AS TEXT) AS system_id"));
+        assert!(select.contains("CAST(\"transcript\" AS TEXT) AS transcription_text"));
+        assert!(select.contains("NULL AS frequency"));
+        assert!(select.contains("FROM \"calls\" WHERE rowid > ?"));
+
+        let no_system = vec!["timestamp".to_string()];
+        assert!(build_select("calls", &no_system).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_open_and_read_legacy_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recordings.db");
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        sqlx::query(
+            "CREATE TABLE calls (id INTEGER PRIMARY KEY, timestamp INTEGER, system TEXT, \
+             talkgroup INTEGER, frequency REAL, transcription TEXT)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for i in 0..3_i64 {
+            sqlx::query("INSERT INTO calls (timestamp, system, talkgroup, frequency, transcription) VALUES (?, 'metro', 52198, 851.0125, 'hello')")
+                .bind(1_704_112_496 + i)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        pool.close().await;
+
+        let legacy = LegacyDatabase::open(&path).await.unwrap();
+        assert_eq!(legacy.table(), "calls");
+        assert_eq!(legacy.count().await.unwrap(), 3);
+
+        let first = legacy.read_batch(0, 2).await.unwrap();
+        assert_eq!(first.len(), 2);
+        let rest = legacy.read_batch(first[1].legacy_rowid, 2).await.unwrap();
+        assert_eq!(rest.len(), 1);
+
+        let call = rest[0].to_radio_call(None, None).unwrap();
+        assert_eq!(call.talkgroup_id.map(TalkgroupId::as_i32), Some(52198));
+        assert_eq!(call.frequency.map(Frequency::as_hz), Some(851_012_500));
+        legacy.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_open_rejects_unknown_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("other.db");
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        sqlx::query("CREATE TABLE notes (body TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        assert!(LegacyDatabase::open(&path).await.is_err());
+        assert!(
+            LegacyDatabase::open(&dir.path().join("missing.db"))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -2,23 +2,177 @@
 
 #![forbid(unsafe_code)]
 
+pub mod alerts;
+pub mod audio;
+pub mod bulk;
+pub mod conversations;
+pub mod crypto;
+pub mod demo;
+pub mod encryption;
 pub mod error;
+pub mod events;
+pub mod feedback;
+pub mod fingerprints;
+pub mod frequencies;
+pub mod geo;
+pub mod idempotency;
+pub mod ingest_keys;
 pub mod jobs;
+pub mod legacy;
+pub mod maintenance;
+pub mod migrations;
 pub mod models;
+pub mod partitions;
+pub mod probes;
+pub mod progress;
+pub mod purges;
 pub mod queries;
+pub mod quotas;
+pub mod radios;
+pub mod redaction;
+pub mod reports;
+pub mod retention;
+pub mod schedules;
+pub mod searches;
+pub mod segments;
+pub mod speakers;
+pub mod summaries;
+pub mod tags;
+pub mod talkgroups;
+#[cfg(test)]
+mod test_support;
+pub mod users;
+pub mod waveforms;
+pub mod webhooks;
 
 pub use error::{Result, StorageError};
 
+// Re-export recording storage backends
+pub use audio::{AudioStorage, LocalAudioStorage, S3AudioStorage, recording_key};
+
 // Re-export convenience functions
 pub use queries::{
-    RadioCallFilter, UploadLogParams, count_radio_calls, count_radio_calls_filtered,
-    count_recent_calls, count_system_calls_since, count_systems, get_radio_call, get_system_stats,
-    get_top_systems, insert_radio_call, insert_upload_log, list_radio_calls_filtered,
+    ApiKeyIpActivity, ApiKeyRejectionCount, ApiKeyUsage, CallCursor, DailyStorageGrowth,
+    HourlyActivity, LanguageStatsFilter, LanguageStatsRow, RadioCallFilter,
+    TalkgroupActivityFilter, TalkgroupActivityRow, TalkgroupHourCount, UploadLogParams,
+    count_radio_calls, count_radio_calls_filtered, count_recent_calls, count_system_calls_since,
+    count_systems, get_api_key_usage, get_daily_storage_growth, get_hourly_activity,
+    get_language_stats, get_radio_call, get_system_stats, get_talkgroup_activity, get_top_systems,
+    insert_radio_call, insert_upload_log, list_radio_calls_filtered, sum_audio_bytes,
     update_system_stats, update_transcription_status, validate_api_key,
 };
 
 // Re-export job queue types and operations
-pub use jobs::{EnqueueParams, JobQueue, JobResult, QueueStats, TranscriptionJob};
+pub use jobs::{
+    AutoRetryParams, EnqueueParams, JobQueue, JobResult, QueueBacklog, QueueStats, RetryFilter,
+    TranscriptionJob,
+};
+
+// Re-export transcription probe types and operations
+pub use probes::{ProbeOutcome, ProbeQueries, TranscriptionProbe};
+
+// Re-export transcription progress types and operations
+pub use progress::{ProgressListener, ProgressQueries, ProgressStage, TranscriptionProgress};
+
+// Re-export upload idempotency key types and operations
+pub use idempotency::{IdempotencyClaim, IdempotencyQueries};
+
+// Re-export ingest key types and operations
+pub use ingest_keys::{IngestKey, IngestKeyQueries, NewIngestKey};
+
+// Re-export schema migration types and operations
+pub use migrations::{AppliedMigration, Migration, MigrationQueries};
+
+// Re-export maintenance types and operations
+pub use maintenance::{MaintenanceQueries, TableBloat};
+
+// Re-export call partition types and operations
+pub use partitions::{CallPartition, PartitionQueries};
+
+// Re-export transcript anonymization types
+pub use crypto::FieldCipher;
+pub use redaction::{RedactedText, TranscriptRedactor};
+
+// Re-export column encryption types and operations
+pub use crypto::{KeyId, KeyRing};
+pub use encryption::{
+    CallToReseal, EncryptionQueries, PlaintextFields, ResealedFields, SealedCallFields,
+    SegmentToReseal,
+};
+
+// Re-export data purge types and operations
+pub use purges::{DataPurge, PurgeFilter, PurgeOutcome, PurgeQueries};
+
+// Re-export bulk archive and delete types and operations
+pub use bulk::{BulkAction, BulkFilter, BulkOperation, BulkOutcome, BulkQueries, NewBulkOperation};
+
+// Re-export digest report types and operations
+pub use reports::{ReportQueries, SystemDigest, TalkgroupCount};
+
+// Re-export storage quota types and operations
+pub use quotas::{DetachedRecording, QuotaQueries};
+
+// Re-export retention types and operations
+pub use retention::{PurgedCall, RetentionQueries};
+
+// Re-export scheduled job types and operations
+pub use schedules::{ScheduleQueries, ScheduledJob};
+
+// Re-export saved search types and operations
+pub use searches::{NewSavedSearch, SavedSearch, SearchQueries};
+
+// Re-export transcript segment types and operations
+pub use segments::{SegmentQueries, TranscriptionSegment};
+
+// Re-export call event types and operations
+pub use events::{CallEvent, CallEventKind, CallEventQueries};
+
+// Re-export fingerprint types and operations
+pub use fingerprints::{CallFingerprint, FingerprintCandidate, FingerprintQueries};
+
+// Re-export channel usage types and operations
+pub use frequencies::{CallFrequencies, FrequencyEntry, FrequencyQueries, FrequencyUsage};
+
+// Re-export call location types and operations
+pub use geo::{CallLocation, GeoFilter, GeoQueries};
+
+// Re-export speaker diarization types and operations
+pub use speakers::{SpeakerQueries, SpeakerSegment, SpeakerTalkTime, SystemSpeakerStats};
+
+// Re-export radio activity types and operations
+pub use radios::{Radio, RadioQueries, RadioTalkgroup};
+
+// Re-export call summary types and operations
+pub use summaries::{CallSummary, SummaryEntity, SummaryQueries};
+
+// Re-export call tag types and operations
+pub use tags::{CallTag, NewCallTag, TagQueries};
+
+// Re-export talkgroup alias types and operations
+pub use talkgroups::{Talkgroup, TalkgroupQueries};
+
+// Re-export user account types and operations
+pub use users::{NewUser, User, UserQueries, UserUpdate};
+
+// Re-export alert types and operations
+pub use alerts::{
+    Alert, AlertDelivery, AlertFilter, AlertQueries, AlertRule, NewAlert, NewAlertRule,
+};
+
+// Re-export conversation types and operations
+pub use conversations::{Conversation, ConversationFilter, ConversationQueries};
+
+// Re-export transcription feedback types and operations
+pub use feedback::{
+    FeedbackExport, FeedbackFilter, FeedbackQueries, NewTranscriptionFeedback,
+    TranscriptionFeedback,
+};
+
+// Re-export waveform types and operations
+pub use waveforms::{CallWaveform, WaveformQueries};
+
+// Re-export webhook delivery types and operations
+pub use webhooks::{NewWebhookDelivery, WebhookAttempt, WebhookDelivery, WebhookQueries};
 
 use sdrtrunk_protocol::Config;
 use sqlx::postgres::PgPoolOptions;
@@ -31,25 +185,25 @@ use std::time::Duration;
 #[derive(Debug, Clone)]
 pub struct Database {
     pool: PgPool,
+    read_pool: Option<PgPool>,
 }
 
 impl Database {
     /// Create a new database connection pool
     ///
+    /// Also connects to `database.read_url` when a read replica is configured.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database connection cannot be established.
     pub async fn new(config: &Config) -> Result<Self> {
-        let pool = PgPoolOptions::new()
-            .max_connections(config.database.max_connections)
-            .min_connections(config.database.min_connections)
-            .acquire_timeout(Duration::from_secs(config.database.connect_timeout))
-            .idle_timeout(Duration::from_secs(config.database.idle_timeout))
-            .connect(&config.database.url)
-            .await
-            .map_err(|e| StorageError::Connection(e.to_string()))?;
+        let pool = connect(config, &config.database.url).await?;
+        let read_pool = match &config.database.read_url {
+            Some(url) => Some(connect(config, url).await?),
+            None => None,
+        };
 
-        Ok(Self { pool })
+        Ok(Self { pool, read_pool })
     }
 
     /// Get a reference to the connection pool
@@ -58,19 +212,29 @@ impl Database {
         &self.pool
     }
 
-    /// Initialize database schema (creates tables if they don't exist).
+    /// Pool for heavy read-only queries: the read replica when configured,
+    /// otherwise the primary
+    #[must_use]
+    pub fn read_pool(&self) -> &PgPool {
+        self.read_pool.as_ref().unwrap_or(&self.pool)
+    }
+
+    /// Initialize database schema by applying every pending migration.
     ///
-    /// Safe to call on every startup — all statements use `IF NOT EXISTS`.
+    /// Safe to call on every startup — migrations recorded in
+    /// `schema_migrations` are skipped, and all statements use
+    /// `IF NOT EXISTS` for databases created before migrations were recorded.
     ///
     /// # Errors
     ///
     /// Returns an error if schema initialization fails.
     pub async fn init_schema(&self) -> Result<()> {
-        let schema = include_str!("../migrations/20240101000001_initial_schema.sql");
-        let _ = sqlx::raw_sql(schema)
-            .execute(&self.pool)
+        let applied = MigrationQueries::applied(&self.pool)
             .await
             .map_err(|e| StorageError::Migration(format!("Schema init failed: {e}")))?;
+        for migration in migrations::pending(&applied, None)? {
+            MigrationQueries::apply(&self.pool, migration).await?;
+        }
 
         Ok(())
     }
@@ -86,10 +250,35 @@ impl Database {
             .await
             .map_err(|e| StorageError::Query(format!("Health check failed: {e}")))?;
 
+        if let Some(read_pool) = &self.read_pool {
+            let _result = sqlx::query("SELECT 1")
+                .execute(read_pool)
+                .await
+                .map_err(|e| {
+                    StorageError::Query(format!("Read replica health check failed: {e}"))
+                })?;
+        }
+
         Ok(())
     }
 }
 
+/// Open a pool to `url` sized by the `database` settings
+///
+/// # Errors
+///
+/// Returns an error if the database connection cannot be established.
+async fn connect(config: &Config, url: &str) -> Result<PgPool> {
+    PgPoolOptions::new()
+        .max_connections(config.database.max_connections)
+        .min_connections(config.database.min_connections)
+        .acquire_timeout(Duration::from_secs(config.database.connect_timeout))
+        .idle_timeout(Duration::from_secs(config.database.idle_timeout))
+        .connect(url)
+        .await
+        .map_err(|e| StorageError::Connection(e.to_string()))
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -125,12 +314,18 @@ mod tests {
 
         let _filter = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 100,
-            offset: 0,
+            after: None,
         };
         let _params = UploadLogParams {
             client_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
@@ -144,6 +339,25 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn test_read_pool_falls_back_to_primary() {
+        let pool = PgPool::connect_lazy("postgresql://primary:5432/db").unwrap();
+        let replica = PgPool::connect_lazy("postgresql://replica:5432/db").unwrap();
+
+        let db = Database {
+            pool: pool.clone(),
+            read_pool: None,
+        };
+        assert_eq!(db.read_pool().connect_options().get_host(), "primary");
+
+        let db = Database {
+            pool,
+            read_pool: Some(replica),
+        };
+        assert_eq!(db.read_pool().connect_options().get_host(), "replica");
+        assert_eq!(db.pool().connect_options().get_host(), "primary");
+    }
+
     #[tokio::test]
     async fn test_database_new_invalid_url() {
         let mut config = Config::default();
@@ -168,6 +382,7 @@ mod tests {
                 min_connections: 1,
                 connect_timeout: 30,
                 idle_timeout: 600,
+                read_url: None,
             },
             ..Default::default()
         };
@@ -184,7 +399,10 @@ mod tests {
         // Create a database with invalid connection
         let pool = PgPool::connect_lazy("postgresql://invalid:5432/nonexistent")
             .expect("Failed to create test pool");
-        let db = Database { pool };
+        let db = Database {
+            pool,
+            read_pool: None,
+        };
 
         let result = db.health_check().await;
         assert!(result.is_err());
@@ -201,7 +419,10 @@ mod tests {
         // Create a database with invalid connection
         let pool = PgPool::connect_lazy("postgresql://invalid:5432/nonexistent")
             .expect("Failed to create test pool");
-        let db = Database { pool };
+        let db = Database {
+            pool,
+            read_pool: None,
+        };
 
         let result = db.init_schema().await;
         assert!(result.is_err());
@@ -0,0 +1,198 @@
+//! Table statistics and planner maintenance.
+//!
+//! Ingest-heavy workloads (bulk imports, retention purges) change large parts
+//! of `radio_calls` faster than autovacuum re-analyzes it, leaving the planner
+//! with stale row estimates. These helpers report per-table bloat from
+//! `pg_stat_user_tables` and run `ANALYZE` on tables that have drifted.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for maintenance operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Row and bloat statistics for one table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TableBloat {
+    /// Table name.
+    pub table_name: String,
+    /// Estimated live rows.
+    pub live_rows: i64,
+    /// Estimated dead rows awaiting vacuum.
+    pub dead_rows: i64,
+    /// Rows inserted, updated, or deleted since the last analyze.
+    pub modified_since_analyze: i64,
+    /// Total on-disk size including indexes and TOAST, in bytes.
+    pub total_bytes: i64,
+    /// Last manual analyze.
+    pub last_analyze: Option<DateTime<Utc>>,
+    /// Last autovacuum-triggered analyze.
+    pub last_autoanalyze: Option<DateTime<Utc>>,
+    /// Last manual vacuum.
+    pub last_vacuum: Option<DateTime<Utc>>,
+    /// Last autovacuum.
+    pub last_autovacuum: Option<DateTime<Utc>>,
+}
+
+impl TableBloat {
+    /// Fraction of all tuples that are dead (0.0 for an empty table).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn dead_ratio(&self) -> f64 {
+        let total = self.live_rows + self.dead_rows;
+        if total > 0 {
+            self.dead_rows as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether enough rows changed since the last analyze to warrant another.
+    ///
+    /// Requires at least `min_rows` modifications and, for non-empty tables,
+    /// at least `ratio` of the live row count.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn needs_analyze(&self, min_rows: i64, ratio: f64) -> bool {
+        self.modified_since_analyze >= min_rows
+            && self.modified_since_analyze as f64 >= self.live_rows as f64 * ratio
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Maintenance operations
+// ---------------------------------------------------------------------------
+
+/// Database maintenance operations.
+#[derive(Debug)]
+pub struct MaintenanceQueries;
+
+impl MaintenanceQueries {
+    /// Get bloat statistics for every user table, largest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn table_bloat(pool: &PgPool) -> Result<Vec<TableBloat>> {
+        let tables = sqlx::query_as::<_, TableBloat>(
+            r"
+            SELECT
+                relname::TEXT AS table_name,
+                n_live_tup AS live_rows,
+                n_dead_tup AS dead_rows,
+                n_mod_since_analyze AS modified_since_analyze,
+                pg_total_relation_size(relid) AS total_bytes,
+                last_analyze,
+                last_autoanalyze,
+                last_vacuum,
+                last_autovacuum
+            FROM pg_stat_user_tables
+            WHERE schemaname = current_schema()
+            ORDER BY total_bytes DESC
+            ",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tables)
+    }
+
+    /// Run `ANALYZE` on a table.
+    ///
+    /// The name is quoted as an identifier; callers should pass names taken
+    /// from [`MaintenanceQueries::table_bloat`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table does not exist or the command fails.
+    pub async fn analyze(pool: &PgPool, table_name: &str) -> Result<()> {
+        let statement = format!("ANALYZE {}", quote_identifier(table_name));
+        let _ = sqlx::query(&statement).execute(pool).await?;
+        Ok(())
+    }
+
+    /// Analyze every table that has drifted past the given thresholds.
+    ///
+    /// Returns the names of the tables that were analyzed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading statistics or any `ANALYZE` fails.
+    pub async fn analyze_stale(pool: &PgPool, min_rows: i64, ratio: f64) -> Result<Vec<String>> {
+        let mut analyzed = Vec::new();
+        for table in Self::table_bloat(pool).await? {
+            if table.needs_analyze(min_rows, ratio) {
+                Self::analyze(pool, &table.table_name).await?;
+                analyzed.push(table.table_name);
+            }
+        }
+        Ok(analyzed)
+    }
+}
+
+/// Quote a name as a `PostgreSQL` identifier.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn table(live_rows: i64, dead_rows: i64, modified: i64) -> TableBloat {
+        TableBloat {
+            table_name: "radio_calls".to_string(),
+            live_rows,
+            dead_rows,
+            modified_since_analyze: modified,
+            total_bytes: 8192,
+            last_analyze: None,
+            last_autoanalyze: None,
+            last_vacuum: None,
+            last_autovacuum: None,
+        }
+    }
+
+    #[test]
+    fn test_dead_ratio() {
+        assert_eq!(table(750, 250, 0).dead_ratio(), 0.25);
+        assert_eq!(table(0, 0, 0).dead_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_needs_analyze() {
+        // Bulk import: many modifications relative to the table
+        assert!(table(100_000, 0, 50_000).needs_analyze(10_000, 0.1));
+        // Below the absolute minimum
+        assert!(!table(1_000, 0, 500).needs_analyze(10_000, 0.1));
+        // Large table, small relative change
+        assert!(!table(10_000_000, 0, 20_000).needs_analyze(10_000, 0.1));
+        // Freshly loaded table with no live estimate yet
+        assert!(table(0, 0, 20_000).needs_analyze(10_000, 0.1));
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("radio_calls"), "\"radio_calls\"");
+        assert_eq!(quote_identifier("odd\"name"), "\"odd\"\"name\"");
+    }
+}
@@ -0,0 +1,382 @@
+//! Schema migrations.
+//!
+//! Migrations are the SQL files under `migrations/`, applied in version
+//! order. Each applied migration is recorded in `schema_migrations`, so
+//! later runs only apply the ones missing. Databases created before
+//! migrations were recorded can be baselined: their migrations are recorded
+//! as applied without running them.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for migration operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Table recording applied migrations, created before the first is applied.
+const SCHEMA_MIGRATIONS_TABLE: &str = r"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        baseline BOOLEAN NOT NULL DEFAULT FALSE
+    )
+";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A schema migration shipped with this build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// File name without the `.sql` extension, e.g.
+    /// `20240101000001_initial_schema`.
+    pub name: &'static str,
+    /// SQL applied by the migration.
+    pub sql: &'static str,
+}
+
+impl Migration {
+    const fn new(name: &'static str, sql: &'static str) -> Self {
+        Self { name, sql }
+    }
+
+    /// Version of the migration: the timestamp its name starts with.
+    #[must_use]
+    pub fn version(&self) -> &'static str {
+        self.name
+            .split_once('_')
+            .map_or(self.name, |(version, _)| version)
+    }
+}
+
+/// A migration recorded in `schema_migrations`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    /// Migration version.
+    pub version: String,
+    /// Migration name.
+    pub name: String,
+    /// When the migration was applied or baselined.
+    pub applied_at: DateTime<Utc>,
+    /// Whether the migration was recorded without being run.
+    pub baseline: bool,
+}
+
+/// Every migration shipped with this build, in the order they are applied.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration::new(
+        "20240101000001_initial_schema",
+        include_str!("../migrations/20240101000001_initial_schema.sql"),
+    ),
+    Migration::new(
+        "20240201000001_transcription_probes",
+        include_str!("../migrations/20240201000001_transcription_probes.sql"),
+    ),
+    Migration::new(
+        "20240301000001_talkgroups",
+        include_str!("../migrations/20240301000001_talkgroups.sql"),
+    ),
+    Migration::new(
+        "20240401000001_alerts",
+        include_str!("../migrations/20240401000001_alerts.sql"),
+    ),
+    Migration::new(
+        "20240501000001_audio_dedup",
+        include_str!("../migrations/20240501000001_audio_dedup.sql"),
+    ),
+    Migration::new(
+        "20240601000001_webhooks",
+        include_str!("../migrations/20240601000001_webhooks.sql"),
+    ),
+    Migration::new(
+        "20240701000001_talkgroup_activity",
+        include_str!("../migrations/20240701000001_talkgroup_activity.sql"),
+    ),
+    Migration::new(
+        "20240801000001_call_waveforms",
+        include_str!("../migrations/20240801000001_call_waveforms.sql"),
+    ),
+    Migration::new(
+        "20240901000001_conversations",
+        include_str!("../migrations/20240901000001_conversations.sql"),
+    ),
+    Migration::new(
+        "20241001000001_transcription_feedback",
+        include_str!("../migrations/20241001000001_transcription_feedback.sql"),
+    ),
+    Migration::new(
+        "20241101000001_ingest_keys",
+        include_str!("../migrations/20241101000001_ingest_keys.sql"),
+    ),
+    Migration::new(
+        "20241201000001_transcription_retry_attempts",
+        include_str!("../migrations/20241201000001_transcription_retry_attempts.sql"),
+    ),
+    Migration::new(
+        "20250101000001_call_locations",
+        include_str!("../migrations/20250101000001_call_locations.sql"),
+    ),
+    Migration::new(
+        "20250201000001_users",
+        include_str!("../migrations/20250201000001_users.sql"),
+    ),
+    Migration::new(
+        "20250301000001_call_cursor_index",
+        include_str!("../migrations/20250301000001_call_cursor_index.sql"),
+    ),
+    Migration::new(
+        "20250401000001_scheduled_jobs",
+        include_str!("../migrations/20250401000001_scheduled_jobs.sql"),
+    ),
+    Migration::new(
+        "20250501000001_transcription_segments",
+        include_str!("../migrations/20250501000001_transcription_segments.sql"),
+    ),
+    Migration::new(
+        "20250601000001_call_events",
+        include_str!("../migrations/20250601000001_call_events.sql"),
+    ),
+    Migration::new(
+        "20250701000001_data_purges",
+        include_str!("../migrations/20250701000001_data_purges.sql"),
+    ),
+    Migration::new(
+        "20250801000001_transcription_review",
+        include_str!("../migrations/20250801000001_transcription_review.sql"),
+    ),
+    Migration::new(
+        "20250901000001_call_tags",
+        include_str!("../migrations/20250901000001_call_tags.sql"),
+    ),
+    Migration::new(
+        "20251001000001_saved_searches",
+        include_str!("../migrations/20251001000001_saved_searches.sql"),
+    ),
+    Migration::new(
+        "20251101000001_call_fingerprints",
+        include_str!("../migrations/20251101000001_call_fingerprints.sql"),
+    ),
+    Migration::new(
+        "20251201000001_transcription_language",
+        include_str!("../migrations/20251201000001_transcription_language.sql"),
+    ),
+    Migration::new(
+        "20260101000001_call_summaries",
+        include_str!("../migrations/20260101000001_call_summaries.sql"),
+    ),
+    Migration::new(
+        "20260201000001_radios",
+        include_str!("../migrations/20260201000001_radios.sql"),
+    ),
+    Migration::new(
+        "20260301000001_bulk_call_operations",
+        include_str!("../migrations/20260301000001_bulk_call_operations.sql"),
+    ),
+    Migration::new(
+        "20260401000001_upload_idempotency_keys",
+        include_str!("../migrations/20260401000001_upload_idempotency_keys.sql"),
+    ),
+    Migration::new(
+        "20260501000001_partition_radio_calls",
+        include_str!("../migrations/20260501000001_partition_radio_calls.sql"),
+    ),
+    Migration::new(
+        "20260601000001_transcription_text_raw",
+        include_str!("../migrations/20260601000001_transcription_text_raw.sql"),
+    ),
+    Migration::new(
+        "20260701000001_encrypted_call_fields",
+        include_str!("../migrations/20260701000001_encrypted_call_fields.sql"),
+    ),
+    Migration::new(
+        "20260801000001_encrypted_transcription_segments",
+        include_str!("../migrations/20260801000001_encrypted_transcription_segments.sql"),
+    ),
+    Migration::new(
+        "20260901000001_call_event_archived",
+        include_str!("../migrations/20260901000001_call_event_archived.sql"),
+    ),
+];
+
+// ---------------------------------------------------------------------------
+// Migration operations
+// ---------------------------------------------------------------------------
+
+/// Migrations of [`MIGRATIONS`] up to and including version `to` (all when
+/// `None`) that are not in `applied`, in order.
+///
+/// # Errors
+///
+/// Returns an error if `to` is not the version of a known migration.
+pub fn pending(applied: &[AppliedMigration], to: Option<&str>) -> Result<Vec<&'static Migration>> {
+    let last = match to {
+        Some(to) => MIGRATIONS
+            .iter()
+            .position(|migration| migration.version() == to)
+            .ok_or_else(|| StorageError::Migration(format!("unknown migration version {to}")))?,
+        None => MIGRATIONS.len().saturating_sub(1),
+    };
+    Ok(MIGRATIONS
+        .iter()
+        .take(last + 1)
+        .filter(|migration| !applied.iter().any(|a| a.version == migration.version()))
+        .collect())
+}
+
+/// Schema migration operations.
+#[derive(Debug)]
+pub struct MigrationQueries;
+
+impl MigrationQueries {
+    /// Migrations recorded as applied, oldest version first.
+    ///
+    /// Returns none when `schema_migrations` does not exist yet, without
+    /// creating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn applied(pool: &PgPool) -> Result<Vec<AppliedMigration>> {
+        let exists: bool =
+            sqlx::query_scalar("SELECT to_regclass('schema_migrations') IS NOT NULL")
+                .fetch_one(pool)
+                .await?;
+        if !exists {
+            return Ok(Vec::new());
+        }
+
+        let applied = sqlx::query_as::<_, AppliedMigration>(
+            "SELECT version, name, applied_at, baseline FROM schema_migrations ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(applied)
+    }
+
+    /// Run `migration` and record it as applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the migration's SQL or recording it fails.
+    pub async fn apply(pool: &PgPool, migration: &Migration) -> Result<()> {
+        let _ = sqlx::raw_sql(migration.sql)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                StorageError::Migration(format!("Schema init failed ({}): {e}", migration.name))
+            })?;
+        Self::record(pool, migration, false).await
+    }
+
+    /// Record `migration` as applied without running it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn baseline(pool: &PgPool, migration: &Migration) -> Result<()> {
+        Self::record(pool, migration, true).await
+    }
+
+    async fn record(pool: &PgPool, migration: &Migration, baseline: bool) -> Result<()> {
+        let _ = sqlx::query(SCHEMA_MIGRATIONS_TABLE).execute(pool).await?;
+        let _ = sqlx::query(
+            r"
+            INSERT INTO schema_migrations (version, name, baseline)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (version) DO NOTHING
+            ",
+        )
+        .bind(migration.version())
+        .bind(migration.name)
+        .bind(baseline)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn applied(version: &str) -> AppliedMigration {
+        AppliedMigration {
+            version: version.to_string(),
+            name: version.to_string(),
+            applied_at: Utc::now(),
+            baseline: false,
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        assert_eq!(MIGRATIONS[0].version(), "20240101000001");
+        assert!(
+            MIGRATIONS
+                .windows(2)
+                .all(|pair| pair[0].version() < pair[1].version())
+        );
+        assert!(MIGRATIONS.iter().all(|m| m.version().len() == 14));
+    }
+
+    #[test]
+    fn test_every_migration_file_is_registered() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/migrations");
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter_map(|file| file.strip_suffix(".sql").map(str::to_string))
+            .collect();
+        files.sort();
+
+        assert_eq!(files, MIGRATIONS.iter().map(|m| m.name).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_pending() {
+        assert_eq!(pending(&[], None).unwrap().len(), MIGRATIONS.len());
+
+        let first_two = pending(&[], Some("20240201000001")).unwrap();
+        assert_eq!(
+            first_two.iter().map(|m| m.name).collect::<Vec<_>>(),
+            vec![
+                "20240101000001_initial_schema",
+                "20240201000001_transcription_probes"
+            ]
+        );
+
+        // Applied migrations are skipped, even out of order
+        let rest = pending(
+            &[applied("20240101000001"), applied("20240301000001")],
+            None,
+        )
+        .unwrap();
+        assert_eq!(rest.len(), MIGRATIONS.len() - 2);
+        assert_eq!(rest[0].version(), "20240201000001");
+        assert!(rest.iter().all(|m| m.version() != "20240301000001"));
+
+        let error = pending(&[], Some("20990101000001")).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("unknown migration version 20990101000001")
+        );
+    }
+}
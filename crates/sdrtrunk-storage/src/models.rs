@@ -57,6 +57,9 @@ pub struct RadioCallDb {
     /// Audio content type
     pub audio_content_type: Option<String>,
 
+    /// Hex SHA-256 of the uploaded audio
+    pub audio_sha256: Option<String>,
+
     /// Duration in seconds
     pub duration_seconds: Option<rust_decimal::Decimal>,
 
@@ -95,6 +98,12 @@ pub struct RadioCallDb {
 
     /// API key ID used for upload
     pub upload_api_key_id: Option<String>,
+
+    /// Latitude where the call was heard (WGS 84 degrees)
+    pub latitude: Option<f64>,
+
+    /// Longitude where the call was heard (WGS 84 degrees)
+    pub longitude: Option<f64>,
 }
 
 /// Database model for upload logs
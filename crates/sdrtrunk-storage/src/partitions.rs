@@ -0,0 +1,267 @@
+//! Monthly partitions of `radio_calls`.
+//!
+//! Calls are partitioned by the UTC month of `call_timestamp`. The partition
+//! maintenance job creates the coming months' partitions ahead of uploads
+//! (calls for a month without one land in `radio_calls_default` and are
+//! moved out when its partition is created) and drops months emptied by
+//! retention purges.
+
+use crate::error::StorageError;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for partition operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// A partition of `radio_calls`.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct CallPartition {
+    /// Partition table name.
+    pub name: String,
+    /// First day of the month the partition holds, or `None` for the default partition.
+    pub month: Option<NaiveDate>,
+    /// Planner estimate of the partition's rows (-1 before it is first analyzed).
+    pub estimated_rows: i64,
+    /// On-disk size of the partition including indexes and TOAST, in bytes.
+    pub total_bytes: i64,
+}
+
+/// Partition maintenance operations.
+#[derive(Debug)]
+pub struct PartitionQueries;
+
+impl PartitionQueries {
+    /// Create the partition for the month containing `month`, moving calls
+    /// for that month out of the default partition. Returns whether it was
+    /// created (`false` if it already existed).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn create_month(pool: &PgPool, month: NaiveDate) -> Result<bool> {
+        let created = sqlx::query_scalar::<_, bool>("SELECT create_radio_calls_partition($1)")
+            .bind(month)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(created)
+    }
+
+    /// Drop the empty monthly partitions for months ending by `before`.
+    /// Returns the names of the dropped partitions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn drop_empty_before(pool: &PgPool, before: DateTime<Utc>) -> Result<Vec<String>> {
+        let dropped =
+            sqlx::query_scalar::<_, String>("SELECT * FROM drop_empty_radio_calls_partitions($1)")
+                .bind(before)
+                .fetch_all(pool)
+                .await?;
+
+        Ok(dropped)
+    }
+
+    /// List the partitions of `radio_calls`, oldest month first and the
+    /// default partition last.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn list(pool: &PgPool) -> Result<Vec<CallPartition>> {
+        let partitions = sqlx::query_as::<_, CallPartition>(
+            r"
+            SELECT
+                c.relname::TEXT AS name,
+                CASE WHEN c.relname ~ '^radio_calls_\d{4}_\d{2}$'
+                    THEN to_date(substr(c.relname, 13), 'YYYY_MM')
+                END AS month,
+                c.reltuples::BIGINT AS estimated_rows,
+                pg_total_relation_size(c.oid) AS total_bytes
+            FROM pg_inherits i
+            JOIN pg_class c ON c.oid = i.inhrelid
+            WHERE i.inhparent = 'radio_calls'::regclass
+            ORDER BY month NULLS LAST
+            ",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(partitions)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+    use chrono::{Datelike, TimeZone};
+    use uuid::Uuid;
+
+    /// Where a call is stored
+    async fn partition_of(pool: &PgPool, id: Uuid) -> String {
+        sqlx::query_scalar("SELECT tableoid::regclass::TEXT FROM radio_calls WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    /// `SQLSTATE` of a failed query
+    fn sqlstate(error: &sqlx::Error) -> Option<String> {
+        error
+            .as_database_error()
+            .and_then(|e| e.code())
+            .map(std::borrow::Cow::into_owned)
+    }
+
+    #[tokio::test]
+    async fn test_call_ids_stay_unique_and_jobs_block_deletes() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let id = Uuid::new_v4();
+        let insert = |timestamp: DateTime<Utc>| {
+            sqlx::query(
+                "INSERT INTO radio_calls (id, call_timestamp, system_id) VALUES ($1, $2, 'partition_test')",
+            )
+            .bind(id)
+            .bind(timestamp)
+            .execute(&pool)
+        };
+        insert(Utc::now()).await.unwrap();
+
+        // The same id in another month is still a duplicate
+        let duplicate = insert(Utc.with_ymd_and_hms(1991, 2, 1, 0, 0, 0).unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(sqlstate(&duplicate).as_deref(), Some("23505"));
+
+        // Calls with transcription jobs cannot be deleted, as with the old
+        // foreign key
+        sqlx::query("INSERT INTO transcription_jobs (call_id) VALUES ($1)")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let delete_call = || {
+            sqlx::query("DELETE FROM radio_calls WHERE id = $1")
+                .bind(id)
+                .execute(&pool)
+        };
+        let restricted = delete_call().await.unwrap_err();
+        assert_eq!(sqlstate(&restricted).as_deref(), Some("23503"));
+
+        sqlx::query("DELETE FROM transcription_jobs WHERE call_id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(delete_call().await.unwrap().rows_affected(), 1);
+
+        // References to a missing call are rejected
+        let missing = sqlx::query("INSERT INTO call_tags (call_id, tag) VALUES ($1, 'gone')")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap_err();
+        assert_eq!(sqlstate(&missing).as_deref(), Some("23503"));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_drop_partitions() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        // A month long enough ago that no other test stores calls in it, so
+        // dropping empty months before it leaves other tests' partitions alone
+        let month = NaiveDate::from_ymd_opt(1991, 3, 1).unwrap();
+        let name = "radio_calls_1991_03".to_string();
+        let id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO radio_calls (id, call_timestamp, system_id) VALUES ($1, $2, 'partition_test')",
+        )
+        .bind(id)
+        .bind(Utc.with_ymd_and_hms(1991, 3, 31, 23, 59, 59).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO call_tags (call_id, tag) VALUES ($1, 'partition-test')")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(partition_of(&pool, id).await, "radio_calls_default");
+
+        // Creating the month moves its calls out of the default partition
+        assert!(
+            PartitionQueries::create_month(&pool, month.with_day(15).unwrap())
+                .await
+                .unwrap()
+        );
+        assert!(!PartitionQueries::create_month(&pool, month).await.unwrap());
+        assert_eq!(partition_of(&pool, id).await, name);
+        let partitions = PartitionQueries::list(&pool).await.unwrap();
+        assert!(
+            partitions
+                .iter()
+                .any(|p| p.name == name && p.month == Some(month))
+        );
+        assert_eq!(partitions.last().unwrap().name, "radio_calls_default");
+
+        // Months still holding calls, or not yet over, are kept
+        let end_of_month = Utc.with_ymd_and_hms(1991, 4, 1, 0, 0, 0).unwrap();
+        let dropped =
+            PartitionQueries::drop_empty_before(&pool, end_of_month - chrono::Duration::seconds(1))
+                .await
+                .unwrap();
+        assert!(!dropped.contains(&name));
+        let dropped = PartitionQueries::drop_empty_before(&pool, end_of_month)
+            .await
+            .unwrap();
+        assert!(!dropped.contains(&name));
+
+        // Deleting a call still removes its dependent rows
+        sqlx::query("DELETE FROM radio_calls WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let tags: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM call_tags WHERE call_id = $1")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tags, 0);
+
+        let dropped = PartitionQueries::drop_empty_before(&pool, end_of_month)
+            .await
+            .unwrap();
+        assert!(dropped.contains(&name));
+        assert!(
+            !PartitionQueries::list(&pool)
+                .await
+                .unwrap()
+                .iter()
+                .any(|p| p.name == name)
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Synthetic transcription probe results.
+//!
+//! Workers periodically transcribe a tiny bundled clip and record the outcome
+//! in the `transcription_probes` table (one row per worker). The API reads the
+//! latest results to surface backend health in readiness checks and metrics,
+//! catching hung inference or a broken toolchain before real calls pile up.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for probe operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Outcome of a single probe run, reported by a worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeOutcome {
+    /// Whether the probe transcription completed.
+    pub success: bool,
+    /// Wall-clock latency of the probe in milliseconds.
+    pub latency_ms: i64,
+    /// Text produced by the probe (may be empty for a tone clip).
+    pub transcript: Option<String>,
+    /// Error message when the probe failed.
+    pub error: Option<String>,
+}
+
+/// A row from the `transcription_probes` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptionProbe {
+    /// Worker that ran the probe.
+    pub worker_id: String,
+    /// When the probe last ran.
+    pub probed_at: DateTime<Utc>,
+    /// Whether the last probe succeeded.
+    pub success: bool,
+    /// Latency of the last probe in milliseconds.
+    pub latency_ms: i64,
+    /// Text produced by the last probe.
+    pub transcript: Option<String>,
+    /// Error message from the last probe.
+    pub error: Option<String>,
+    /// Number of failed probes in a row (0 after a success).
+    pub consecutive_failures: i32,
+}
+
+impl TranscriptionProbe {
+    /// Whether this probe is recent enough to be trusted.
+    ///
+    /// A probe older than `max_age_seconds` suggests the worker is hung (a
+    /// stuck inference blocks the loop that runs probes) or gone.
+    #[must_use]
+    pub fn is_fresh(&self, now: DateTime<Utc>, max_age_seconds: i64) -> bool {
+        (now - self.probed_at).num_seconds() <= max_age_seconds
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Probe operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for synthetic transcription probes.
+#[derive(Debug)]
+pub struct ProbeQueries;
+
+impl ProbeQueries {
+    /// Record a probe outcome for a worker, replacing its previous result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn record(pool: &PgPool, worker_id: &str, outcome: &ProbeOutcome) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            INSERT INTO transcription_probes
+                (worker_id, probed_at, success, latency_ms, transcript, error, consecutive_failures)
+            VALUES ($1, NOW(), $2, $3, $4, $5, CASE WHEN $2 THEN 0 ELSE 1 END)
+            ON CONFLICT (worker_id) DO UPDATE SET
+                probed_at = NOW(),
+                success = EXCLUDED.success,
+                latency_ms = EXCLUDED.latency_ms,
+                transcript = EXCLUDED.transcript,
+                error = EXCLUDED.error,
+                consecutive_failures = CASE
+                    WHEN EXCLUDED.success THEN 0
+                    ELSE transcription_probes.consecutive_failures + 1
+                END
+            ",
+        )
+        .bind(worker_id)
+        .bind(outcome.success)
+        .bind(outcome.latency_ms)
+        .bind(&outcome.transcript)
+        .bind(&outcome.error)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List the latest probe result for every worker, most recent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(pool: &PgPool) -> Result<Vec<TranscriptionProbe>> {
+        let probes = sqlx::query_as::<_, TranscriptionProbe>(
+            "SELECT * FROM transcription_probes ORDER BY probed_at DESC",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(probes)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    fn probe_at(probed_at: DateTime<Utc>) -> TranscriptionProbe {
+        TranscriptionProbe {
+            worker_id: "worker-1".to_string(),
+            probed_at,
+            success: true,
+            latency_ms: 850,
+            transcript: Some(String::new()),
+            error: None,
+            consecutive_failures: 0,
+        }
+    }
+
+    #[test]
+    fn test_probe_freshness() {
+        let now = Utc::now();
+        assert!(probe_at(now - chrono::Duration::seconds(60)).is_fresh(now, 900));
+        assert!(!probe_at(now - chrono::Duration::seconds(1000)).is_fresh(now, 900));
+    }
+
+    #[test]
+    fn test_probe_outcome_serialization() {
+        let outcome = ProbeOutcome {
+            success: false,
+            latency_ms: 30_000,
+            transcript: None,
+            error: Some("inference timed out".to_string()),
+        };
+
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json["success"], false);
+        assert_eq!(json["latency_ms"], 30_000);
+        assert_eq!(json["error"], "inference timed out");
+    }
+}
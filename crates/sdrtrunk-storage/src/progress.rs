@@ -0,0 +1,290 @@
+//! Transcription progress notifications.
+//!
+//! Workers and the API run as separate processes, so per-call lifecycle
+//! events (queued, processing, each decoded segment, completed, failed) are
+//! published with `PostgreSQL` `NOTIFY` on [`PROGRESS_CHANNEL`]. Any process
+//! holding a [`ProgressListener`] receives them without polling the job table.
+//! Notifications are best-effort: nothing is stored, and a listener that is
+//! disconnected misses events published meanwhile.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use uuid::Uuid;
+
+/// Result type alias for progress operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// `NOTIFY` channel carrying [`TranscriptionProgress`] payloads.
+pub const PROGRESS_CHANNEL: &str = "transcription_progress";
+
+/// Longest text carried in a single notification, in bytes.
+///
+/// `PostgreSQL` rejects payloads over 8000 bytes; long transcripts are cut
+/// here and the full text is read from the call record on completion.
+pub const MAX_PROGRESS_TEXT_BYTES: usize = 4000;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A lifecycle event for one call's transcription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptionProgress {
+    /// The radio call being transcribed.
+    pub call_id: Uuid,
+    /// The transcription job, once one exists.
+    pub job_id: Option<Uuid>,
+    /// Lifecycle stage and its details.
+    #[serde(flatten)]
+    pub stage: ProgressStage,
+    /// When the event was published.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Stage of a transcription, in lifecycle order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ProgressStage {
+    /// Job enqueued and waiting for a worker.
+    Queued,
+    /// A worker claimed the job.
+    Processing {
+        /// Worker that claimed the job.
+        worker_id: String,
+    },
+    /// The engine decoded a segment.
+    Segment {
+        /// Zero-based segment index.
+        index: i32,
+        /// Segment start in milliseconds.
+        start_ms: i64,
+        /// Segment end in milliseconds.
+        end_ms: i64,
+        /// Segment text.
+        text: String,
+    },
+    /// Transcription finished.
+    Completed {
+        /// Full transcript (truncated to [`MAX_PROGRESS_TEXT_BYTES`]).
+        text: Option<String>,
+        /// Wall-clock processing time in milliseconds.
+        processing_time_ms: i64,
+    },
+    /// The attempt failed.
+    Failed {
+        /// Error message.
+        error: String,
+        /// Whether the job was re-queued for another attempt.
+        will_retry: bool,
+    },
+}
+
+impl TranscriptionProgress {
+    /// Create an event stamped with the current time.
+    #[must_use]
+    pub fn new(call_id: Uuid, job_id: Option<Uuid>, stage: ProgressStage) -> Self {
+        Self {
+            call_id,
+            job_id,
+            stage,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Encode as a `NOTIFY` payload, truncating text to fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_payload(&self) -> Result<String> {
+        let mut event = self.clone();
+        match &mut event.stage {
+            ProgressStage::Segment { text, .. }
+            | ProgressStage::Completed {
+                text: Some(text), ..
+            }
+            | ProgressStage::Failed { error: text, .. } => {
+                truncate_to_boundary(text, MAX_PROGRESS_TEXT_BYTES);
+            }
+            _ => {}
+        }
+        serde_json::to_string(&event).map_err(|e| StorageError::Serialization(e.to_string()))
+    }
+
+    /// Decode a `NOTIFY` payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload is not a valid event.
+    pub fn from_payload(payload: &str) -> Result<Self> {
+        serde_json::from_str(payload).map_err(|e| StorageError::Serialization(e.to_string()))
+    }
+}
+
+/// Cut `text` to at most `max` bytes without splitting a character.
+fn truncate_to_boundary(text: &mut String, max: usize) {
+    if text.len() <= max {
+        return;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+}
+
+// ---------------------------------------------------------------------------
+// Publishing and listening
+// ---------------------------------------------------------------------------
+
+/// Transcription progress publishing.
+#[derive(Debug)]
+pub struct ProgressQueries;
+
+impl ProgressQueries {
+    /// Publish an event on [`PROGRESS_CHANNEL`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be encoded or the query fails.
+    pub async fn publish(pool: &PgPool, event: &TranscriptionProgress) -> Result<()> {
+        let _ = sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(PROGRESS_CHANNEL)
+            .bind(event.to_payload()?)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// A dedicated connection subscribed to [`PROGRESS_CHANNEL`].
+#[derive(Debug)]
+pub struct ProgressListener {
+    listener: PgListener,
+}
+
+impl ProgressListener {
+    /// Open a listening connection from the pool's connect options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection or `LISTEN` fails.
+    pub async fn connect(pool: &PgPool) -> Result<Self> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(PROGRESS_CHANNEL).await?;
+        Ok(Self { listener })
+    }
+
+    /// Wait for the next event.
+    ///
+    /// Malformed payloads are skipped. The listener reconnects on its own
+    /// after a dropped connection; events published in between are lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if receiving fails.
+    pub async fn recv(&mut self) -> Result<TranscriptionProgress> {
+        loop {
+            let notification = self.listener.recv().await?;
+            match TranscriptionProgress::from_payload(notification.payload()) {
+                Ok(event) => return Ok(event),
+                Err(e) => tracing::warn!("Ignoring malformed progress notification: {e}"),
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_roundtrip() {
+        let call_id = Uuid::new_v4();
+        let job_id = Some(Uuid::new_v4());
+        for stage in [
+            ProgressStage::Queued,
+            ProgressStage::Processing {
+                worker_id: "worker-1".to_string(),
+            },
+            ProgressStage::Segment {
+                index: 0,
+                start_ms: 0,
+                end_ms: 1500,
+                text: "Engine 4 on scene".to_string(),
+            },
+            ProgressStage::Completed {
+                text: Some("Engine 4 on scene".to_string()),
+                processing_time_ms: 820,
+            },
+            ProgressStage::Failed {
+                error: "ffmpeg conversion failed".to_string(),
+                will_retry: true,
+            },
+        ] {
+            let event = TranscriptionProgress::new(call_id, job_id, stage);
+            let decoded =
+                TranscriptionProgress::from_payload(&event.to_payload().unwrap()).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn test_payload_shape() {
+        let event = TranscriptionProgress::new(
+            Uuid::nil(),
+            None,
+            ProgressStage::Processing {
+                worker_id: "worker-1".to_string(),
+            },
+        );
+        let json: serde_json::Value = serde_json::from_str(&event.to_payload().unwrap()).unwrap();
+        assert_eq!(json["stage"], "processing");
+        assert_eq!(json["worker_id"], "worker-1");
+        assert!(json["job_id"].is_null());
+    }
+
+    #[test]
+    fn test_payload_truncates_long_text() {
+        let event = TranscriptionProgress::new(
+            Uuid::nil(),
+            None,
+            ProgressStage::Completed {
+                text: Some("é".repeat(MAX_PROGRESS_TEXT_BYTES)),
+                processing_time_ms: 1,
+            },
+        );
+        let payload = event.to_payload().unwrap();
+        assert!(payload.len() < 8000);
+
+        let decoded = TranscriptionProgress::from_payload(&payload).unwrap();
+        let ProgressStage::Completed {
+            text: Some(text), ..
+        } = decoded.stage
+        else {
+            panic!("expected completed stage");
+        };
+        assert_eq!(text.len(), MAX_PROGRESS_TEXT_BYTES);
+    }
+
+    #[test]
+    fn test_from_payload_rejects_garbage() {
+        assert!(TranscriptionProgress::from_payload("not json").is_err());
+        assert!(TranscriptionProgress::from_payload(r#"{"stage":"unknown"}"#).is_err());
+    }
+}
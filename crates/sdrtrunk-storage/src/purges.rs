@@ -0,0 +1,368 @@
+//! Targeted data purges.
+//!
+//! Erases every call matching a system, talkgroup, and/or source radio filter
+//! in one transaction, together with the rows that reference it: transcription
+//! jobs, webhook deliveries (whose payloads carry the transcript), upload log
+//! entries for the recording, and conversations left empty. Alerts, events,
+//! segments, feedback, and waveforms go with the call through their foreign
+//! keys. An audit row in `data_purges` is written in the same transaction. The
+//! deleted calls' recording paths are returned so the caller can remove the
+//! files after commit.
+
+use crate::error::StorageError;
+use crate::retention::PurgedCall;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{RadioId, SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for data purge operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Which calls a purge erases; every given field must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurgeFilter {
+    /// Calls from this system.
+    pub system_id: Option<SystemId>,
+    /// Calls on this talkgroup.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Calls transmitted by this radio.
+    pub source_radio_id: Option<RadioId>,
+}
+
+impl PurgeFilter {
+    /// Whether no field is set (which would match every call).
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.system_id.is_none() && self.talkgroup_id.is_none() && self.source_radio_id.is_none()
+    }
+}
+
+/// A row from the `data_purges` audit log.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DataPurge {
+    /// Purge ID.
+    pub id: Uuid,
+    /// Who asked for the purge (`api_key:<id>` or `user:<name>`).
+    pub requested_by: String,
+    /// Why, as given by the requester.
+    pub reason: Option<String>,
+    /// System filter.
+    pub system_id: Option<SystemId>,
+    /// Talkgroup filter.
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Source radio filter.
+    pub source_radio_id: Option<RadioId>,
+    /// Calls deleted.
+    pub calls_deleted: i32,
+    /// Upload log entries deleted.
+    pub upload_logs_deleted: i32,
+    /// Webhook deliveries deleted.
+    pub webhook_deliveries_deleted: i32,
+    /// Recordings removed from storage.
+    pub audio_files_deleted: i32,
+    /// Recordings that could not be removed.
+    pub audio_files_failed: i32,
+    /// When the purge ran.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Audit row and deleted calls of a purge.
+pub type PurgeOutcome = (DataPurge, Vec<PurgedCall>);
+
+// ---------------------------------------------------------------------------
+// Purge operations
+// ---------------------------------------------------------------------------
+
+/// Data purge operations.
+#[derive(Debug)]
+pub struct PurgeQueries;
+
+impl PurgeQueries {
+    /// Erase the calls matching `filter` and record the purge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filter is empty or a query fails, in which case
+    /// nothing is deleted.
+    pub async fn purge(
+        pool: &PgPool,
+        filter: &PurgeFilter,
+        requested_by: &str,
+        reason: Option<&str>,
+    ) -> Result<PurgeOutcome> {
+        if filter.is_empty() {
+            return Err(StorageError::ConstraintViolation {
+                constraint: "purge filter must not be empty".to_string(),
+            });
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let calls = sqlx::query_as::<_, PurgedCall>(
+            r"
+            SELECT id, system_id, audio_file_path, audio_size_bytes
+            FROM radio_calls
+            WHERE ($1::VARCHAR IS NULL OR system_id = $1)
+              AND ($2::INTEGER IS NULL OR talkgroup_id = $2)
+              AND ($3::INTEGER IS NULL OR source_radio_id = $3)
+            FOR UPDATE
+            ",
+        )
+        .bind(&filter.system_id)
+        .bind(filter.talkgroup_id)
+        .bind(filter.source_radio_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let ids: Vec<Uuid> = calls.iter().map(|call| call.id).collect();
+
+        let (upload_logs, webhook_deliveries) = delete_calls(&mut tx, &ids).await?;
+
+        let purge = sqlx::query_as::<_, DataPurge>(
+            r"
+            INSERT INTO data_purges (
+                requested_by, reason, system_id, talkgroup_id, source_radio_id,
+                calls_deleted, upload_logs_deleted, webhook_deliveries_deleted
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            ",
+        )
+        .bind(requested_by)
+        .bind(reason)
+        .bind(&filter.system_id)
+        .bind(filter.talkgroup_id)
+        .bind(filter.source_radio_id)
+        .bind(count(calls.len()))
+        .bind(count(upload_logs))
+        .bind(count(webhook_deliveries))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok((purge, calls))
+    }
+
+    /// Record how removing a purge's recordings went.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn record_files(
+        pool: &PgPool,
+        purge_id: Uuid,
+        deleted: usize,
+        failed: usize,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE data_purges
+            SET audio_files_deleted = $2, audio_files_failed = $3
+            WHERE id = $1
+            ",
+        )
+        .bind(purge_id)
+        .bind(count(deleted))
+        .bind(count(failed))
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Delete calls and the rows referencing them that do not cascade, returning
+/// the upload log entries and webhook deliveries removed.
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub(crate) async fn delete_calls(conn: &mut PgConnection, ids: &[Uuid]) -> Result<(u64, u64)> {
+    let conversations: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT DISTINCT conversation_id FROM conversation_calls WHERE call_id = ANY($1)",
+    )
+    .bind(ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let _ = sqlx::query("DELETE FROM transcription_jobs WHERE call_id = ANY($1)")
+        .bind(ids)
+        .execute(&mut *conn)
+        .await?;
+
+    let webhook_deliveries = sqlx::query("DELETE FROM webhook_deliveries WHERE call_id = ANY($1)")
+        .bind(ids)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    let upload_logs = sqlx::query(
+        r"
+        DELETE FROM upload_logs
+        USING radio_calls
+        WHERE radio_calls.id = ANY($1)
+          AND upload_logs.system_id = radio_calls.system_id
+          AND upload_logs.filename = radio_calls.audio_filename
+        ",
+    )
+    .bind(ids)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    let _ = sqlx::query("DELETE FROM radio_calls WHERE id = ANY($1)")
+        .bind(ids)
+        .execute(&mut *conn)
+        .await?;
+
+    let _ = sqlx::query(
+        r"
+        DELETE FROM conversations
+        WHERE id = ANY($1)
+          AND NOT EXISTS (
+              SELECT 1 FROM conversation_calls
+              WHERE conversation_calls.conversation_id = conversations.id
+          )
+        ",
+    )
+    .bind(&conversations)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok((upload_logs, webhook_deliveries))
+}
+
+/// A row count as stored in the audit log.
+fn count<T: TryInto<i32>>(n: T) -> i32 {
+    n.try_into().unwrap_or(i32::MAX)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::jobs::{EnqueueParams, JobQueue};
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+
+    fn call(system_id: &SystemId, talkgroup: i32, radio: i32) -> RadioCallDb {
+        RadioCallDb {
+            talkgroup_id: Some(TalkgroupId::new(talkgroup).unwrap()),
+            source_radio_id: Some(RadioId::new(radio).unwrap()),
+            audio_filename: Some(format!("{}.mp3", Uuid::new_v4())),
+            audio_file_path: Some("recordings/a.mp3".to_string()),
+            audio_size_bytes: Some(1024),
+            transcription_text: Some("Engine 5 responding".to_string()),
+            transcription_status: Some("completed".to_string()),
+            ..test_call(system_id)
+        }
+    }
+
+    #[test]
+    fn test_filter_is_empty() {
+        assert!(PurgeFilter::default().is_empty());
+        assert!(
+            !PurgeFilter {
+                source_radio_id: Some(RadioId::new(1234).unwrap()),
+                ..PurgeFilter::default()
+            }
+            .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purge_by_radio() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("prg_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let doomed = call(&system_id, 100, 1234);
+        let kept = call(&system_id, 100, 5678);
+        RadioCallQueries::insert(&pool, &doomed).await.unwrap();
+        RadioCallQueries::insert(&pool, &kept).await.unwrap();
+        JobQueue::enqueue(
+            &pool,
+            &EnqueueParams {
+                call_id: doomed.id,
+                audio_path: None,
+                audio_data: None,
+                priority: 0,
+                options: serde_json::json!({}),
+                timeout_seconds: 300,
+            },
+        )
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO upload_logs (client_ip, system_id, filename) VALUES ('127.0.0.1', $1, $2)",
+        )
+        .bind(&system_id)
+        .bind(&doomed.audio_filename)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(
+            PurgeQueries::purge(&pool, &PurgeFilter::default(), "user:admin", None)
+                .await
+                .is_err()
+        );
+
+        let filter = PurgeFilter {
+            system_id: Some(system_id.clone()),
+            talkgroup_id: None,
+            source_radio_id: Some(RadioId::new(1234).unwrap()),
+        };
+        let (purge, calls) = PurgeQueries::purge(&pool, &filter, "user:admin", Some("erasure"))
+            .await
+            .unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, doomed.id);
+        assert_eq!(
+            (
+                purge.calls_deleted,
+                purge.upload_logs_deleted,
+                purge.webhook_deliveries_deleted
+            ),
+            (1, 1, 0)
+        );
+        assert_eq!(purge.reason.as_deref(), Some("erasure"));
+
+        assert!(
+            RadioCallQueries::find_by_id(&pool, doomed.id)
+                .await
+                .is_err()
+        );
+        assert!(RadioCallQueries::find_by_id(&pool, kept.id).await.is_ok());
+
+        PurgeQueries::record_files(&pool, purge.id, 1, 0)
+            .await
+            .unwrap();
+        let deleted: i32 =
+            sqlx::query_scalar("SELECT audio_files_deleted FROM data_purges WHERE id = $1")
+                .bind(purge.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(deleted, 1);
+    }
+}
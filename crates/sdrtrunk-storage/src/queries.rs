@@ -4,6 +4,8 @@ use std::net::IpAddr;
 
 use crate::error::StorageError;
 use crate::models::{ApiKeyDb, RadioCallDb, SystemStatsDb, UploadLogDb};
+use sdrtrunk_protocol::call_query::{CallPredicate, CallQuery, NumberRange, TextMatch};
+use sdrtrunk_types::{SystemId, TalkgroupId};
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
@@ -29,11 +31,13 @@ impl RadioCallQueries {
                 audio_size_bytes, audio_content_type, duration_seconds,
                 transcription_text, transcription_confidence, transcription_language,
                 transcription_status, speaker_segments, speaker_count,
-                patches, frequencies, sources, upload_ip, upload_timestamp, upload_api_key_id
+                patches, frequencies, sources, upload_ip, upload_timestamp, upload_api_key_id,
+                audio_sha256, latitude, longitude
             ) VALUES (
                 $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
                 $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
-                $21, $22, $23, $24, $25, $26, $27, $28, $29
+                $21, $22, $23, $24, $25, $26, $27, $28, $29, $30,
+                $31, $32
             )
             RETURNING id
         ";
@@ -68,6 +72,9 @@ impl RadioCallQueries {
             .bind(call.upload_ip)
             .bind(call.upload_timestamp)
             .bind(&call.upload_api_key_id)
+            .bind(&call.audio_sha256)
+            .bind(call.latitude)
+            .bind(call.longitude)
             .fetch_one(pool)
             .await?;
 
@@ -75,6 +82,32 @@ impl RadioCallQueries {
         Ok(id)
     }
 
+    /// Find the first call from `system_id` whose audio has this SHA-256
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_by_audio_hash(
+        pool: &PgPool,
+        system_id: &SystemId,
+        audio_sha256: &str,
+    ) -> Result<Option<Uuid>> {
+        let id = sqlx::query_scalar::<_, Uuid>(
+            r"
+            SELECT id FROM radio_calls
+            WHERE system_id = $1 AND audio_sha256 = $2
+            ORDER BY created_at ASC
+            LIMIT 1
+            ",
+        )
+        .bind(system_id)
+        .bind(audio_sha256)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(id)
+    }
+
     /// Find radio call by ID
     ///
     /// # Errors
@@ -96,14 +129,55 @@ impl RadioCallQueries {
             })
     }
 
-    /// Find radio calls by system ID with pagination
+    /// Encrypted original transcript of a call, kept when its stored text
+    /// was anonymized
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or call is not found.
+    pub async fn find_raw_transcription(pool: &PgPool, id: Uuid) -> Result<Option<Vec<u8>>> {
+        let query = "SELECT transcription_text_raw FROM radio_calls WHERE id = $1";
+
+        sqlx::query_scalar::<_, Option<Vec<u8>>>(query)
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| StorageError::NotFound {
+                entity: "RadioCall".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    /// Find the calls among `ids` whose transcription has completed, including
+    /// ones flagged for review
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_completed(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<RadioCallDb>> {
+        let query = r"
+            SELECT * FROM radio_calls
+            WHERE id = ANY($1) AND transcription_status IN ('completed', 'needs_review')
+            ORDER BY call_timestamp, id
+        ";
+
+        sqlx::query_as::<_, RadioCallDb>(query)
+            .bind(ids)
+            .fetch_all(pool)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    /// Find radio calls by system ID, newest first, resuming after
+    /// `filter.after`
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
+    #[allow(clippy::too_many_lines)]
     pub async fn find_by_system(
         pool: &PgPool,
-        system_id: &str,
+        system_id: &SystemId,
         filter: &RadioCallFilter<'_>,
     ) -> Result<Vec<RadioCallDb>> {
         // Build dynamic WHERE clause starting with system_id
@@ -126,6 +200,29 @@ impl RadioCallQueries {
             }
         }
 
+        if filter.tag.is_some() {
+            param_count += 1;
+            conditions.push(tag_condition(param_count));
+        }
+
+        if filter.keyword.is_some() {
+            param_count += 1;
+            conditions.push(keyword_condition(param_count));
+        }
+
+        if filter.language.is_some() {
+            param_count += 1;
+            conditions.push(language_condition(param_count));
+        }
+
+        let mut search = SearchParams::after(param_count);
+        if let Some(query) = filter.search {
+            conditions.push(search.condition(query));
+            param_count = search.count;
+        }
+
+        conditions.push(archived_condition(filter.archived).to_string());
+
         if filter.from_date.is_some() {
             param_count += 1;
             conditions.push(format!("call_timestamp >= ${param_count}"));
@@ -136,14 +233,17 @@ impl RadioCallQueries {
             conditions.push(format!("call_timestamp <= ${param_count}"));
         }
 
+        if filter.after.is_some() {
+            conditions.push(cursor_condition(param_count + 1));
+            param_count += 2;
+        }
+
         let limit_param = param_count + 1;
-        let offset_param = param_count + 2;
 
         let query = format!(
-            "SELECT * FROM radio_calls WHERE {} ORDER BY call_timestamp DESC LIMIT ${} OFFSET ${}",
+            "SELECT * FROM radio_calls WHERE {} ORDER BY call_timestamp DESC, id DESC LIMIT ${}",
             conditions.join(" AND "),
-            limit_param,
-            offset_param
+            limit_param
         );
 
         let mut query_builder = sqlx::query_as::<_, RadioCallDb>(&query);
@@ -157,6 +257,25 @@ impl RadioCallQueries {
             query_builder = query_builder.bind(transcription_status);
         }
 
+        if let Some(tag) = filter.tag {
+            query_builder = query_builder.bind(tag);
+        }
+
+        if let Some(keyword) = filter.keyword {
+            query_builder = query_builder.bind(keyword);
+        }
+
+        if let Some(language) = filter.language {
+            query_builder = query_builder.bind(language);
+        }
+
+        for param in search.values {
+            query_builder = match param {
+                SearchParam::Text(text) => query_builder.bind(text),
+                SearchParam::Number(number) => query_builder.bind(number),
+            };
+        }
+
         if let Some(from_date) = filter.from_date {
             query_builder = query_builder.bind(from_date);
         }
@@ -165,9 +284,12 @@ impl RadioCallQueries {
             query_builder = query_builder.bind(to_date);
         }
 
+        if let Some(after) = filter.after {
+            query_builder = query_builder.bind(after.call_timestamp).bind(after.id);
+        }
+
         query_builder
             .bind(filter.limit)
-            .bind(filter.offset)
             .fetch_all(pool)
             .await
             .map_err(StorageError::from)
@@ -178,7 +300,7 @@ impl RadioCallQueries {
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn count_by_system(pool: &PgPool, system_id: &str) -> Result<i64> {
+    pub async fn count_by_system(pool: &PgPool, system_id: &SystemId) -> Result<i64> {
         let query = "SELECT COUNT(*) as count FROM radio_calls WHERE system_id = $1";
 
         let row = sqlx::query(query).bind(system_id).fetch_one(pool).await?;
@@ -186,12 +308,13 @@ impl RadioCallQueries {
         Ok(row.get("count"))
     }
 
-    /// Find all radio calls with basic pagination (no system filter)
+    /// Find all radio calls, newest first, resuming after `filter.after` (no
+    /// system filter)
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    #[allow(clippy::cognitive_complexity)]
+    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
     pub async fn find_all_with_filters(
         pool: &PgPool,
         filter: &RadioCallFilter<'_>,
@@ -200,6 +323,11 @@ impl RadioCallQueries {
         let mut conditions = Vec::new();
         let mut param_count = 0;
 
+        if filter.allowed_systems.is_some() {
+            param_count += 1;
+            conditions.push(format!("system_id = ANY(${param_count})"));
+        }
+
         if filter.talkgroup_id.is_some() {
             param_count += 1;
             conditions.push(format!("talkgroup_id = ${param_count}"));
@@ -216,6 +344,29 @@ impl RadioCallQueries {
             }
         }
 
+        if filter.tag.is_some() {
+            param_count += 1;
+            conditions.push(tag_condition(param_count));
+        }
+
+        if filter.keyword.is_some() {
+            param_count += 1;
+            conditions.push(keyword_condition(param_count));
+        }
+
+        if filter.language.is_some() {
+            param_count += 1;
+            conditions.push(language_condition(param_count));
+        }
+
+        let mut search = SearchParams::after(param_count);
+        if let Some(query) = filter.search {
+            conditions.push(search.condition(query));
+            param_count = search.count;
+        }
+
+        conditions.push(archived_condition(filter.archived).to_string());
+
         if filter.from_date.is_some() {
             param_count += 1;
             conditions.push(format!("call_timestamp >= ${param_count}"));
@@ -226,6 +377,11 @@ impl RadioCallQueries {
             conditions.push(format!("call_timestamp <= ${param_count}"));
         }
 
+        if filter.after.is_some() {
+            conditions.push(cursor_condition(param_count + 1));
+            param_count += 2;
+        }
+
         let where_clause = if conditions.is_empty() {
             String::new()
         } else {
@@ -233,21 +389,24 @@ impl RadioCallQueries {
         };
 
         let limit_param = param_count + 1;
-        let offset_param = param_count + 2;
 
         let query = format!(
-            "SELECT * FROM radio_calls {where_clause} ORDER BY call_timestamp DESC LIMIT ${limit_param} OFFSET ${offset_param}"
+            "SELECT * FROM radio_calls {where_clause} ORDER BY call_timestamp DESC, id DESC LIMIT ${limit_param}"
         );
 
         tracing::info!(
-            "Executing find_all_with_filters query with limit={}, offset={}, transcription_status={:?}",
+            "Executing find_all_with_filters query with limit={}, after={:?}, transcription_status={:?}",
             filter.limit,
-            filter.offset,
+            filter.after,
             filter.transcription_status
         );
 
         let mut query_builder = sqlx::query_as::<_, RadioCallDb>(&query);
 
+        if let Some(systems) = filter.allowed_systems.map(system_names) {
+            query_builder = query_builder.bind(systems);
+        }
+
         if let Some(talkgroup_id) = filter.talkgroup_id {
             query_builder = query_builder.bind(talkgroup_id);
         }
@@ -256,6 +415,25 @@ impl RadioCallQueries {
             query_builder = query_builder.bind(transcription_status);
         }
 
+        if let Some(tag) = filter.tag {
+            query_builder = query_builder.bind(tag);
+        }
+
+        if let Some(keyword) = filter.keyword {
+            query_builder = query_builder.bind(keyword);
+        }
+
+        if let Some(language) = filter.language {
+            query_builder = query_builder.bind(language);
+        }
+
+        for param in search.values {
+            query_builder = match param {
+                SearchParam::Text(text) => query_builder.bind(text),
+                SearchParam::Number(number) => query_builder.bind(number),
+            };
+        }
+
         if let Some(from_date) = filter.from_date {
             query_builder = query_builder.bind(from_date);
         }
@@ -264,9 +442,12 @@ impl RadioCallQueries {
             query_builder = query_builder.bind(to_date);
         }
 
+        if let Some(after) = filter.after {
+            query_builder = query_builder.bind(after.call_timestamp).bind(after.id);
+        }
+
         let result = query_builder
             .bind(filter.limit)
-            .bind(filter.offset)
             .fetch_all(pool)
             .await
             .map_err(|e| {
@@ -280,6 +461,9 @@ impl RadioCallQueries {
 
     /// Update transcription status
     ///
+    /// Moving a call to `completed` or `needs_review` records a `transcribed`
+    /// call event, and moving it to `failed` a `failed` one.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
@@ -295,6 +479,9 @@ impl RadioCallQueries {
             error,
             speaker_segments,
             speaker_count,
+            language,
+            raw_text,
+            text_encrypted,
         } = transcription;
         let confidence_decimal = confidence
             .map(rust_decimal::Decimal::try_from)
@@ -302,22 +489,37 @@ impl RadioCallQueries {
             .map_err(|e| StorageError::Serialization(format!("Invalid confidence value: {e}")))?;
 
         let query = r"
-            UPDATE radio_calls
-            SET transcription_status = $1,
-                transcription_text = $2,
-                transcription_confidence = $3,
-                transcription_error = $4,
-                speaker_segments = $5,
-                speaker_count = $6,
-                transcription_completed_at = CASE
-                    WHEN $1 IN ('completed', 'failed') THEN NOW()
-                    ELSE transcription_completed_at
-                END,
-                transcription_started_at = CASE
-                    WHEN $1 = 'processing' AND transcription_started_at IS NULL THEN NOW()
-                    ELSE transcription_started_at
-                END
-            WHERE id = $7
+            WITH updated AS (
+                UPDATE radio_calls
+                SET transcription_status = $1,
+                    transcription_text = $2,
+                    transcription_confidence = $3,
+                    transcription_error = $4,
+                    speaker_segments = $5,
+                    speaker_count = $6,
+                    transcription_language = COALESCE($8, transcription_language),
+                    transcription_text_raw = $9,
+                    transcription_text_encrypted = $10,
+                    transcription_completed_at = CASE
+                        WHEN $1 IN ('completed', 'needs_review', 'failed') THEN NOW()
+                        ELSE transcription_completed_at
+                    END,
+                    transcription_started_at = CASE
+                        WHEN $1 = 'processing' AND transcription_started_at IS NULL THEN NOW()
+                        ELSE transcription_started_at
+                    END
+                WHERE id = $7
+                RETURNING id
+            )
+            INSERT INTO call_events (call_id, event, detail)
+            SELECT id,
+                   CASE WHEN $1 = 'failed' THEN 'failed' ELSE 'transcribed' END,
+                   CASE
+                       WHEN $1 = 'failed' THEN $4
+                       WHEN $1 = 'needs_review' THEN 'Confidence below threshold; needs review'
+                   END
+            FROM updated
+            WHERE $1 IN ('completed', 'needs_review', 'failed')
         ";
 
         let _result = sqlx::query(query)
@@ -328,12 +530,82 @@ impl RadioCallQueries {
             .bind(speaker_segments)
             .bind(speaker_count)
             .bind(id)
+            .bind(language)
+            .bind(raw_text)
+            .bind(text_encrypted)
             .execute(pool)
             .await?;
 
         Ok(())
     }
 
+    /// Mark a call flagged `needs_review` as `completed`
+    ///
+    /// Records a `reviewed` call event, with `reviewer` as its detail. Calls
+    /// in any other status are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn mark_reviewed(pool: &PgPool, id: Uuid, reviewer: Option<&str>) -> Result<bool> {
+        let query = r"
+            WITH reviewed AS (
+                UPDATE radio_calls
+                SET transcription_status = 'completed'
+                WHERE id = $1 AND transcription_status = 'needs_review'
+                RETURNING id
+            )
+            INSERT INTO call_events (call_id, event, detail)
+            SELECT id, 'reviewed', $2
+            FROM reviewed
+        ";
+
+        let result = sqlx::query(query)
+            .bind(id)
+            .bind(reviewer)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Attach the recording to a call registered without one
+    ///
+    /// Only calls still `awaiting_audio` are updated; the call's duration is
+    /// kept when the attachment does not carry one. Returns whether the call
+    /// was updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn attach_audio(pool: &PgPool, id: Uuid, audio: &AttachedAudio<'_>) -> Result<bool> {
+        let query = r"
+            UPDATE radio_calls
+            SET audio_filename = $2,
+                audio_file_path = $3,
+                audio_size_bytes = $4,
+                audio_content_type = COALESCE($5, audio_content_type),
+                audio_sha256 = $6,
+                duration_seconds = COALESCE($7, duration_seconds),
+                transcription_status = $8
+            WHERE id = $1 AND transcription_status = 'awaiting_audio'
+        ";
+
+        let result = sqlx::query(query)
+            .bind(id)
+            .bind(audio.filename)
+            .bind(audio.file_path)
+            .bind(audio.size_bytes)
+            .bind(audio.content_type)
+            .bind(audio.sha256)
+            .bind(audio.duration_seconds)
+            .bind(audio.transcription_status)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Delete old radio calls
     ///
     /// # Errors
@@ -363,6 +635,7 @@ impl RadioCallQueries {
                 COUNT(CASE WHEN transcription_status = 'failed' THEN 1 END) as failed,
                 COUNT(CASE WHEN transcription_status = 'processing' THEN 1 END) as processing,
                 COUNT(CASE WHEN transcription_status = 'pending' THEN 1 END) as pending,
+                COUNT(CASE WHEN transcription_status = 'skipped' THEN 1 END) as skipped,
                 AVG(CASE WHEN transcription_confidence IS NOT NULL THEN transcription_confidence END) as avg_confidence
             FROM radio_calls
         ";
@@ -375,6 +648,7 @@ impl RadioCallQueries {
             failed: row.get("failed"),
             processing: row.get("processing"),
             pending: row.get("pending"),
+            skipped: row.get("skipped"),
             avg_confidence: row
                 .get::<Option<rust_decimal::Decimal>, _>("avg_confidence")
                 .map(|d| d.to_string().parse::<f64>().unwrap_or(0.0)),
@@ -476,6 +750,102 @@ impl UploadLogQueries {
     }
 }
 
+/// Talkgroups kept in `system_stats.top_talkgroups` by a rollup
+const ROLLUP_TOP_TALKGROUPS: i64 = 10;
+
+/// Upsert every system's statistics recomputed from `radio_calls`
+const ROLLUP_SYSTEM_STATS: &str = r"
+    WITH calls AS (
+        SELECT
+            system_id,
+            (ARRAY_AGG(system_label ORDER BY call_timestamp DESC)
+                FILTER (WHERE system_label IS NOT NULL))[1] AS system_label,
+            COUNT(*)::INT4 AS total_calls,
+            (COUNT(*) FILTER (WHERE call_timestamp >= DATE_TRUNC('day', NOW())))::INT4
+                AS calls_today,
+            (COUNT(*) FILTER (WHERE call_timestamp >= DATE_TRUNC('hour', NOW())))::INT4
+                AS calls_this_hour,
+            MIN(call_timestamp) AS first_seen,
+            MAX(call_timestamp) AS last_seen
+        FROM radio_calls
+        WHERE $2::TEXT[] IS NULL OR system_id = ANY($2)
+        GROUP BY system_id
+    ),
+    talkgroups AS (
+        SELECT
+            system_id,
+            jsonb_agg(
+                jsonb_build_object('id', talkgroup_id, 'label', talkgroup_label, 'count', calls)
+                ORDER BY calls DESC, talkgroup_id
+            ) AS top_talkgroups
+        FROM (
+            SELECT
+                system_id,
+                talkgroup_id,
+                MAX(talkgroup_label) AS talkgroup_label,
+                COUNT(*) AS calls,
+                ROW_NUMBER() OVER (
+                    PARTITION BY system_id ORDER BY COUNT(*) DESC, talkgroup_id
+                ) AS rank
+            FROM radio_calls
+            WHERE talkgroup_id IS NOT NULL
+              AND ($2::TEXT[] IS NULL OR system_id = ANY($2))
+            GROUP BY system_id, talkgroup_id
+        ) ranked
+        WHERE rank <= $1
+        GROUP BY system_id
+    ),
+    sources AS (
+        SELECT
+            system_id,
+            jsonb_agg(source ORDER BY uploads DESC, source) AS upload_sources
+        FROM (
+            SELECT system_id, HOST(upload_ip) AS source, COUNT(*) AS uploads
+            FROM radio_calls
+            WHERE upload_ip IS NOT NULL
+              AND ($2::TEXT[] IS NULL OR system_id = ANY($2))
+            GROUP BY system_id, HOST(upload_ip)
+        ) counted
+        GROUP BY system_id
+    )
+    INSERT INTO system_stats (
+        system_id, system_label, total_calls, calls_today, calls_this_hour,
+        first_seen, last_seen, top_talkgroups, upload_sources, last_updated
+    )
+    SELECT
+        c.system_id, c.system_label, c.total_calls, c.calls_today, c.calls_this_hour,
+        c.first_seen, c.last_seen, t.top_talkgroups, s.upload_sources, NOW()
+    FROM calls c
+    LEFT JOIN talkgroups t USING (system_id)
+    LEFT JOIN sources s USING (system_id)
+    ON CONFLICT (system_id) DO UPDATE SET
+        system_label = COALESCE(EXCLUDED.system_label, system_stats.system_label),
+        total_calls = EXCLUDED.total_calls,
+        calls_today = EXCLUDED.calls_today,
+        calls_this_hour = EXCLUDED.calls_this_hour,
+        first_seen = EXCLUDED.first_seen,
+        last_seen = EXCLUDED.last_seen,
+        top_talkgroups = EXCLUDED.top_talkgroups,
+        upload_sources = EXCLUDED.upload_sources,
+        last_updated = EXCLUDED.last_updated
+";
+
+/// Zero the counters of systems that no longer have any calls
+const ZERO_EMPTY_SYSTEM_STATS: &str = r"
+    UPDATE system_stats
+    SET total_calls = 0,
+        calls_today = 0,
+        calls_this_hour = 0,
+        top_talkgroups = NULL,
+        upload_sources = NULL,
+        last_updated = NOW()
+    WHERE total_calls IS DISTINCT FROM 0
+      AND ($1::TEXT[] IS NULL OR system_id = ANY($1))
+      AND NOT EXISTS (
+          SELECT 1 FROM radio_calls rc WHERE rc.system_id = system_stats.system_id
+      )
+";
+
 /// System statistics database operations
 #[derive(Debug)]
 pub struct SystemStatsQueries;
@@ -541,7 +911,7 @@ impl SystemStatsQueries {
     /// # Errors
     ///
     /// Returns an error if the database query fails or system is not found.
-    pub async fn get_by_system_id(pool: &PgPool, system_id: &str) -> Result<SystemStatsDb> {
+    pub async fn get_by_system_id(pool: &PgPool, system_id: &SystemId) -> Result<SystemStatsDb> {
         let query = "SELECT * FROM system_stats WHERE system_id = $1";
 
         sqlx::query_as::<_, SystemStatsDb>(query)
@@ -562,7 +932,7 @@ impl SystemStatsQueries {
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn update_activity(pool: &PgPool, system_id: &str) -> Result<()> {
+    pub async fn update_activity(pool: &PgPool, system_id: &SystemId) -> Result<()> {
         let query = r"
             INSERT INTO system_stats (
                 system_id, total_calls, calls_today, calls_this_hour,
@@ -589,6 +959,56 @@ impl SystemStatsQueries {
 
         Ok(())
     }
+
+    /// Set a system's display label
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn set_label(pool: &PgPool, system_id: &SystemId, label: &str) -> Result<()> {
+        let _result = sqlx::query("UPDATE system_stats SET system_label = $2 WHERE system_id = $1")
+            .bind(system_id)
+            .bind(label)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Recompute every system's statistics from `radio_calls`
+    ///
+    /// Replaces the counters maintained by [`Self::update_activity`], which
+    /// drift when uploads fail part way or calls are purged. Calls today and
+    /// this hour are counted by call time; `top_talkgroups` holds the busiest
+    /// talkgroups as `{id, label, count}` objects and `upload_sources` the
+    /// uploading addresses, busiest first. Systems with no calls left keep
+    /// their row with zeroed counters. `systems` restricts the rollup to those
+    /// systems (`None` for every system). Returns the number of systems
+    /// updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn rollup(pool: &PgPool, systems: Option<&[SystemId]>) -> Result<u64> {
+        let systems = systems.map(system_names);
+        let mut tx = pool.begin().await?;
+
+        let refreshed = sqlx::query(ROLLUP_SYSTEM_STATS)
+            .bind(ROLLUP_TOP_TALKGROUPS)
+            .bind(systems.as_ref())
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let emptied = sqlx::query(ZERO_EMPTY_SYSTEM_STATS)
+            .bind(systems.as_ref())
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+        Ok(refreshed + emptied)
+    }
 }
 
 /// Parameters for creating an API key.
@@ -758,25 +1178,117 @@ pub struct TranscriptionUpdate<'a> {
     pub speaker_segments: Option<&'a serde_json::Value>,
     /// Number of speakers detected
     pub speaker_count: Option<i32>,
+    /// Detected language code (keeps the existing value when `None`)
+    pub language: Option<&'a str>,
+    /// Encrypted original of an anonymized `text`
+    pub raw_text: Option<&'a [u8]>,
+    /// Sealed transcript, stored instead of `text` when it is encrypted
+    pub text_encrypted: Option<&'a [u8]>,
+}
+
+/// Recording attached to a call registered without one
+#[derive(Debug)]
+pub struct AttachedAudio<'a> {
+    /// Stored filename
+    pub filename: &'a str,
+    /// Storage path or key of the recording
+    pub file_path: &'a str,
+    /// Size of the recording in bytes
+    pub size_bytes: i64,
+    /// MIME type of the recording (keeps the registered value when `None`)
+    pub content_type: Option<&'a str>,
+    /// Hex SHA-256 of the recording
+    pub sha256: &'a str,
+    /// Duration of the recording (keeps the registered value when `None`)
+    pub duration_seconds: Option<rust_decimal::Decimal>,
+    /// Status the call moves to once its audio is attached
+    pub transcription_status: &'a str,
 }
 
 /// Parameter struct for filtering radio calls
 #[derive(Debug)]
 pub struct RadioCallFilter<'a> {
     /// System ID filter
-    pub system_id: Option<&'a str>,
+    pub system_id: Option<&'a SystemId>,
+    /// Only calls from these systems (an API key's tenant scope)
+    pub allowed_systems: Option<&'a [SystemId]>,
     /// Talkgroup ID filter
-    pub talkgroup_id: Option<i32>,
-    /// Transcription status filter (pending, processing, completed, failed)
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Transcription status filter (pending, processing, completed, `needs_review`, failed,
+    /// skipped)
     pub transcription_status: Option<&'a str>,
+    /// Only calls carrying this tag (see [`crate::tags`])
+    pub tag: Option<&'a str>,
+    /// Only calls whose transcript or summary contains this text
+    /// (case-insensitive)
+    pub keyword: Option<&'a str>,
+    /// Only calls transcribed in this language (case-insensitive; `en` also
+    /// matches regional codes such as `en-US`)
+    pub language: Option<&'a str>,
+    /// Only calls matching this search expression (see
+    /// [`sdrtrunk_protocol::call_query`])
+    pub search: Option<&'a CallQuery>,
+    /// List archived calls (see [`crate::bulk`]) instead of live ones
+    pub archived: bool,
     /// Date range start
     pub from_date: Option<chrono::DateTime<chrono::Utc>>,
     /// Date range end
     pub to_date: Option<chrono::DateTime<chrono::Utc>>,
     /// Maximum number of results
     pub limit: i64,
-    /// Result offset for pagination
-    pub offset: i64,
+    /// Only calls listed after this cursor (the previous page's last call)
+    pub after: Option<CallCursor>,
+}
+
+/// Position in a newest-first call listing
+///
+/// Listings are ordered by `(call_timestamp, id)` descending, so the last
+/// call of one page says exactly where the next page starts, however many
+/// calls come before it. Written as `<RFC 3339 timestamp>,<call id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallCursor {
+    /// Timestamp of the last call listed
+    pub call_timestamp: chrono::DateTime<chrono::Utc>,
+    /// ID of the last call listed
+    pub id: Uuid,
+}
+
+impl CallCursor {
+    /// Cursor for the page following `call`
+    #[must_use]
+    pub const fn after(call: &RadioCallDb) -> Self {
+        Self {
+            call_timestamp: call.call_timestamp,
+            id: call.id,
+        }
+    }
+}
+
+impl std::fmt::Display for CallCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{}",
+            self.call_timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            self.id
+        )
+    }
+}
+
+impl std::str::FromStr for CallCursor {
+    type Err = StorageError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || StorageError::Serialization(format!("invalid call cursor '{s}'"));
+        let (timestamp, id) = s.split_once(',').ok_or_else(invalid)?;
+        Ok(Self {
+            call_timestamp: chrono::DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|_| invalid())?
+                .with_timezone(&chrono::Utc),
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
 }
 
 /// Parameter struct for upload log creation
@@ -813,6 +1325,8 @@ pub struct TranscriptionStats {
     pub processing: i64,
     /// Number of pending transcriptions
     pub pending: i64,
+    /// Number of calls not transcribed because their talkgroup opts out
+    pub skipped: i64,
     /// Average confidence score
     pub avg_confidence: Option<f64>,
 }
@@ -832,6 +1346,136 @@ pub struct UploadStats {
     pub total_bytes_uploaded: i64,
 }
 
+/// Bytes of audio added by one system on one day
+#[derive(Debug, Clone)]
+pub struct DailyStorageGrowth {
+    /// System ID
+    pub system_id: SystemId,
+    /// Calendar day (UTC) the audio was stored
+    pub day: chrono::NaiveDate,
+    /// Total audio bytes added that day
+    pub bytes_added: i64,
+    /// Number of calls stored that day
+    pub call_count: i64,
+}
+
+/// Calls heard during one clock hour
+#[derive(Debug, Clone)]
+pub struct HourlyActivity {
+    /// Start of the hour (UTC)
+    pub period_start: chrono::DateTime<chrono::Utc>,
+    /// Number of calls in the hour
+    pub call_count: i64,
+    /// Number of systems with at least one call in the hour
+    pub active_systems: i64,
+}
+
+/// Parameter struct for language statistics
+#[derive(Debug)]
+pub struct LanguageStatsFilter<'a> {
+    /// System ID filter
+    pub system_id: Option<&'a SystemId>,
+    /// Only calls from these systems (an API key's tenant scope)
+    pub allowed_systems: Option<&'a [SystemId]>,
+    /// Talkgroup ID filter
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Number of days of history to include
+    pub days: i32,
+}
+
+/// Completed transcriptions in one language for a system/talkgroup on one day
+#[derive(Debug, Clone)]
+pub struct LanguageStatsRow {
+    /// System ID
+    pub system_id: SystemId,
+    /// Talkgroup ID
+    pub talkgroup_id: Option<TalkgroupId>,
+    /// Detected language code
+    pub language: String,
+    /// Calendar day (UTC)
+    pub day: chrono::NaiveDate,
+    /// Number of calls
+    pub call_count: i64,
+}
+
+/// Parameter struct for talkgroup activity statistics
+#[derive(Debug)]
+pub struct TalkgroupActivityFilter<'a> {
+    /// System ID filter
+    pub system_id: Option<&'a SystemId>,
+    /// Only calls from these systems (an API key's tenant scope)
+    pub allowed_systems: Option<&'a [SystemId]>,
+    /// Number of hours of history to include
+    pub hours: i32,
+    /// Most talkgroups to return, busiest first
+    pub limit: i64,
+}
+
+/// Call activity of one talkgroup over a time window
+#[derive(Debug, Clone)]
+pub struct TalkgroupActivityRow {
+    /// System ID
+    pub system_id: SystemId,
+    /// Talkgroup ID
+    pub talkgroup_id: TalkgroupId,
+    /// Most recent talkgroup label
+    pub talkgroup_label: Option<String>,
+    /// Number of calls
+    pub call_count: i64,
+    /// Total call duration in seconds
+    pub airtime_seconds: f64,
+    /// First call in the window
+    pub first_call: chrono::DateTime<chrono::Utc>,
+    /// Last call in the window
+    pub last_call: chrono::DateTime<chrono::Utc>,
+    /// Hours of the day (UTC) with the most calls, busiest first
+    pub busiest_hours: Vec<TalkgroupHourCount>,
+}
+
+/// Calls of a talkgroup in one hour of the day
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct TalkgroupHourCount {
+    /// Hour of the day (0-23, UTC)
+    pub hour: i32,
+    /// Number of calls
+    pub call_count: i64,
+}
+
+/// Upload activity attributed to one API key over a time window
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyUsage {
+    /// Successful uploads
+    pub uploads: i64,
+    /// Total bytes of successful uploads
+    pub upload_bytes: i64,
+    /// Rejected uploads
+    pub rejections: i64,
+    /// Most recent upload attempt, successful or not
+    pub last_upload_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Rejection reasons with counts, most frequent first
+    pub rejection_reasons: Vec<ApiKeyRejectionCount>,
+    /// Client IPs with their last upload time, most recent first
+    pub recent_ips: Vec<ApiKeyIpActivity>,
+}
+
+/// Number of rejected uploads for one error message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyRejectionCount {
+    /// Recorded error message
+    pub reason: String,
+    /// Number of rejections
+    pub count: i64,
+}
+
+/// Last upload attempt from one client IP
+#[derive(Debug, Clone)]
+pub struct ApiKeyIpActivity {
+    /// Client IP address
+    pub ip: String,
+    /// Last upload attempt from this IP
+    pub last_seen: chrono::DateTime<chrono::Utc>,
+}
+
 // Convenience wrapper functions for API compatibility
 
 /// Insert a radio call (wrapper)
@@ -873,6 +1517,12 @@ pub async fn list_radio_calls_filtered(
     );
 
     if let Some(system) = filter.system_id {
+        if filter
+            .allowed_systems
+            .is_some_and(|allowed| !allowed.contains(system))
+        {
+            return Ok(Vec::new());
+        }
         tracing::info!("Using find_by_system for system: {}", system);
         RadioCallQueries::find_by_system(pool, system, &filter).await
     } else {
@@ -886,6 +1536,7 @@ pub async fn list_radio_calls_filtered(
 /// # Errors
 ///
 /// Returns an error if the database query fails.
+#[allow(clippy::too_many_lines)]
 pub async fn count_radio_calls_filtered(pool: &PgPool, filter: RadioCallFilter<'_>) -> Result<i64> {
     // Build dynamic WHERE clause matching the same logic as find_by_system/find_all_with_filters
     let mut conditions = Vec::new();
@@ -896,6 +1547,10 @@ pub async fn count_radio_calls_filtered(pool: &PgPool, filter: RadioCallFilter<'
         param_count += 1;
         conditions.push(format!("system_id = ${param_count}"));
     }
+    if filter.allowed_systems.is_some() {
+        param_count += 1;
+        conditions.push(format!("system_id = ANY(${param_count})"));
+    }
 
     // Talkgroup filter
     if let Some(_talkgroup_id) = filter.talkgroup_id {
@@ -909,6 +1564,32 @@ pub async fn count_radio_calls_filtered(pool: &PgPool, filter: RadioCallFilter<'
         conditions.push(format!("transcription_status = ${param_count}"));
     }
 
+    // Tag filter
+    if filter.tag.is_some() {
+        param_count += 1;
+        conditions.push(tag_condition(param_count));
+    }
+
+    // Transcript keyword filter
+    if filter.keyword.is_some() {
+        param_count += 1;
+        conditions.push(keyword_condition(param_count));
+    }
+
+    // Transcription language filter
+    if filter.language.is_some() {
+        param_count += 1;
+        conditions.push(language_condition(param_count));
+    }
+
+    // Search expression filter
+    let mut search = SearchParams::after(param_count);
+    if let Some(query) = filter.search {
+        conditions.push(search.condition(query));
+        param_count = search.count;
+    }
+    conditions.push(archived_condition(filter.archived).to_string());
+
     // Date range filters
     if filter.from_date.is_some() {
         param_count += 1;
@@ -934,12 +1615,30 @@ pub async fn count_radio_calls_filtered(pool: &PgPool, filter: RadioCallFilter<'
     if let Some(system_id) = filter.system_id {
         query = query.bind(system_id);
     }
+    if let Some(systems) = filter.allowed_systems.map(system_names) {
+        query = query.bind(systems);
+    }
     if let Some(talkgroup_id) = filter.talkgroup_id {
         query = query.bind(talkgroup_id);
     }
     if let Some(status) = filter.transcription_status {
         query = query.bind(status);
     }
+    if let Some(tag) = filter.tag {
+        query = query.bind(tag);
+    }
+    if let Some(keyword) = filter.keyword {
+        query = query.bind(keyword);
+    }
+    if let Some(language) = filter.language {
+        query = query.bind(language);
+    }
+    for param in search.values {
+        query = match param {
+            SearchParam::Text(text) => query.bind(text),
+            SearchParam::Number(number) => query.bind(number),
+        };
+    }
     if let Some(from_date) = filter.from_date {
         query = query.bind(from_date);
     }
@@ -950,46 +1649,218 @@ pub async fn count_radio_calls_filtered(pool: &PgPool, filter: RadioCallFilter<'
     query.fetch_one(pool).await.map_err(StorageError::from)
 }
 
-/// Count total radio calls
+/// Condition matching calls tagged with the tag bound as parameter `param`
+fn tag_condition(param: usize) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM call_tags ct WHERE ct.call_id = radio_calls.id AND ct.tag = ${param})"
+    )
+}
+
+/// Condition matching calls whose transcript, summary, or summary entities
+/// contain the text bound as parameter `param`, ignoring case
+fn keyword_condition(param: usize) -> String {
+    format!(
+        "(STRPOS(LOWER(transcription_text), LOWER(${param})) > 0 \
+         OR EXISTS (SELECT 1 FROM call_summaries cs WHERE cs.call_id = radio_calls.id \
+         AND (STRPOS(LOWER(cs.summary), LOWER(${param})) > 0 \
+         OR STRPOS(LOWER(cs.entities::text), LOWER(${param})) > 0)))"
+    )
+}
+
+/// Condition matching calls transcribed in the language bound as parameter
+/// `param`, ignoring case and any region suffix on the stored code
+fn language_condition(param: usize) -> String {
+    format!(
+        "(LOWER(transcription_language) = LOWER(${param}) \
+         OR LOWER(transcription_language) LIKE LOWER(${param}) || '-%')"
+    )
+}
+
+/// Value bound for a search expression condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchParam<'q> {
+    Text(&'q str),
+    Number(i64),
+}
+
+/// Parameters of a search expression condition, numbered after the filter
+/// parameters that come before them
+///
+/// Column names come from a fixed list and every value in the expression is
+/// bound as a parameter, so nothing a user types is spliced into the SQL.
+#[derive(Debug)]
+struct SearchParams<'q> {
+    /// Number of the last parameter used
+    count: usize,
+    /// Values to bind, in parameter order
+    values: Vec<SearchParam<'q>>,
+}
+
+impl<'q> SearchParams<'q> {
+    const fn after(count: usize) -> Self {
+        Self {
+            count,
+            values: Vec::new(),
+        }
+    }
+
+    /// Add a parameter, returning its number
+    fn push(&mut self, value: SearchParam<'q>) -> usize {
+        self.count += 1;
+        self.values.push(value);
+        self.count
+    }
+
+    /// Condition matching calls that satisfy `query`
+    ///
+    /// Negations also match calls missing the field, so `-tg:100` includes
+    /// calls without a talkgroup.
+    fn condition(&mut self, query: &'q CallQuery) -> String {
+        match query {
+            CallQuery::And(left, right) => {
+                let left = self.condition(left);
+                let right = self.condition(right);
+                format!("({left} AND {right})")
+            }
+            CallQuery::Or(left, right) => {
+                let left = self.condition(left);
+                let right = self.condition(right);
+                format!("({left} OR {right})")
+            }
+            CallQuery::Not(inner) => format!("NOT COALESCE({}, FALSE)", self.condition(inner)),
+            CallQuery::Term(predicate) => self.predicate(predicate),
+        }
+    }
+
+    fn predicate(&mut self, predicate: &'q CallPredicate) -> String {
+        match predicate {
+            CallPredicate::System(text) => self.text_match("system_id", text),
+            CallPredicate::Label(text) => self.text_match("talkgroup_label", text),
+            CallPredicate::Talkgroup(range) => self.range("talkgroup_id", *range),
+            CallPredicate::Radio(range) => self.range("source_radio_id", *range),
+            CallPredicate::Frequency(range) => self.range("frequency", *range),
+            CallPredicate::Duration(range) => self.range("duration_seconds", *range),
+            CallPredicate::Status(status) => {
+                format!(
+                    "transcription_status = ${}",
+                    self.push(SearchParam::Text(status))
+                )
+            }
+            CallPredicate::Tag(tag) => tag_condition(self.push(SearchParam::Text(tag))),
+            CallPredicate::Language(language) => {
+                language_condition(self.push(SearchParam::Text(language)))
+            }
+            CallPredicate::Text(text) => keyword_condition(self.push(SearchParam::Text(text))),
+        }
+    }
+
+    fn text_match(&mut self, column: &str, text: &'q TextMatch) -> String {
+        match text {
+            TextMatch::Is(value) => {
+                let param = self.push(SearchParam::Text(value));
+                format!("LOWER({column}) = LOWER(${param})")
+            }
+            TextMatch::Contains(value) => {
+                let param = self.push(SearchParam::Text(value));
+                format!("STRPOS(LOWER({column}), LOWER(${param})) > 0")
+            }
+        }
+    }
+
+    fn range(&mut self, column: &str, range: NumberRange) -> String {
+        match (range.min, range.max) {
+            (Some(min), Some(max)) if min == max => {
+                format!("{column} = ${}", self.push(SearchParam::Number(min)))
+            }
+            (Some(min), Some(max)) => {
+                let min = self.push(SearchParam::Number(min));
+                let max = self.push(SearchParam::Number(max));
+                format!("{column} BETWEEN ${min} AND ${max}")
+            }
+            (Some(min), None) => format!("{column} >= ${}", self.push(SearchParam::Number(min))),
+            (None, Some(max)) => format!("{column} <= ${}", self.push(SearchParam::Number(max))),
+            (None, None) => format!("{column} IS NOT NULL"),
+        }
+    }
+}
+
+/// Condition matching calls after the cursor bound as parameters `param`
+/// (timestamp) and `param + 1` (ID)
+///
+/// The separate bound on `call_timestamp` lets the planner skip monthly
+/// partitions after the cursor, which it cannot do from the row comparison.
+fn cursor_condition(param: usize) -> String {
+    format!(
+        "call_timestamp <= ${param} AND (call_timestamp, id) < (${param}, ${})",
+        param + 1
+    )
+}
+
+/// Condition matching archived calls, or live ones when `archived` is false
+const fn archived_condition(archived: bool) -> &'static str {
+    if archived {
+        "archived_at IS NOT NULL"
+    } else {
+        "archived_at IS NULL"
+    }
+}
+
+/// System names to bind for an `allowed_systems` scope
+pub(crate) fn system_names(systems: &[SystemId]) -> Vec<&str> {
+    systems.iter().map(SystemId::as_str).collect()
+}
+
+/// Count total radio calls, optionally only those from `systems`
 ///
 /// # Errors
 ///
 /// Returns an error if the database query fails.
-pub async fn count_radio_calls(pool: &PgPool) -> Result<i64> {
-    let row = sqlx::query("SELECT COUNT(*) as count FROM radio_calls")
-        .fetch_one(pool)
-        .await?;
+pub async fn count_radio_calls(pool: &PgPool, systems: Option<&[SystemId]>) -> Result<i64> {
+    let row = sqlx::query(
+        "SELECT COUNT(*) as count FROM radio_calls WHERE ($1::TEXT[] IS NULL OR system_id = ANY($1))",
+    )
+    .bind(systems.map(system_names))
+    .fetch_one(pool)
+    .await?;
 
     Ok(row.get("count"))
 }
 
-/// Count total systems
+/// Count total systems, optionally only those in `systems`
 ///
 /// # Errors
 ///
 /// Returns an error if the database query fails.
-pub async fn count_systems(pool: &PgPool) -> Result<i64> {
-    let row = sqlx::query("SELECT COUNT(DISTINCT system_id) as count FROM radio_calls")
-        .fetch_one(pool)
-        .await?;
+pub async fn count_systems(pool: &PgPool, systems: Option<&[SystemId]>) -> Result<i64> {
+    let row = sqlx::query(
+        "SELECT COUNT(DISTINCT system_id) as count FROM radio_calls WHERE ($1::TEXT[] IS NULL OR system_id = ANY($1))",
+    )
+    .bind(systems.map(system_names))
+    .fetch_one(pool)
+    .await?;
 
     Ok(row.get("count"))
 }
 
-/// Count recent calls (last N hours)
+/// Count recent calls (last N hours), optionally only those from `systems`
 ///
 /// # Errors
 ///
 /// Returns an error if the database query fails.
-pub async fn count_recent_calls(pool: &PgPool, hours: i32) -> Result<i64> {
+pub async fn count_recent_calls(
+    pool: &PgPool,
+    hours: i32,
+    systems: Option<&[SystemId]>,
+) -> Result<i64> {
     // Handle negative hours by ensuring we don't query future timestamps
     let row = if hours <= 0 {
         sqlx::query("SELECT 0::bigint as count")
             .fetch_one(pool)
             .await?
     } else {
-        sqlx::query("SELECT COUNT(*) as count FROM radio_calls WHERE created_at > NOW() - make_interval(hours => $1)")
+        sqlx::query("SELECT COUNT(*) as count FROM radio_calls WHERE created_at > NOW() - make_interval(hours => $1) AND ($2::TEXT[] IS NULL OR system_id = ANY($2))")
             .bind(hours)
+            .bind(systems.map(system_names))
             .fetch_one(pool)
             .await
             ?
@@ -998,14 +1869,19 @@ pub async fn count_recent_calls(pool: &PgPool, hours: i32) -> Result<i64> {
     Ok(row.get("count"))
 }
 
-/// Get top systems by call count
+/// Get top systems by call count, optionally only among `systems`
 ///
 /// # Errors
 ///
 /// Returns an error if the database query fails.
-pub async fn get_top_systems(pool: &PgPool, limit: i64) -> Result<Vec<(String, i64)>> {
-    let rows = sqlx::query("SELECT system_id, COUNT(*) as count FROM radio_calls GROUP BY system_id ORDER BY count DESC LIMIT $1")
+pub async fn get_top_systems(
+    pool: &PgPool,
+    limit: i64,
+    systems: Option<&[SystemId]>,
+) -> Result<Vec<(SystemId, i64)>> {
+    let rows = sqlx::query("SELECT system_id, COUNT(*) as count FROM radio_calls WHERE ($2::TEXT[] IS NULL OR system_id = ANY($2)) GROUP BY system_id ORDER BY count DESC LIMIT $1")
         .bind(limit)
+        .bind(systems.map(system_names))
         .fetch_all(pool)
         .await
         ?;
@@ -1021,7 +1897,11 @@ pub async fn get_top_systems(pool: &PgPool, limit: i64) -> Result<Vec<(String, i
 /// # Errors
 ///
 /// Returns an error if the database query fails.
-pub async fn count_system_calls_since(pool: &PgPool, system_id: &str, hours: i32) -> Result<i64> {
+pub async fn count_system_calls_since(
+    pool: &PgPool,
+    system_id: &SystemId,
+    hours: i32,
+) -> Result<i64> {
     // Handle negative hours by ensuring we don't query future timestamps
     let row = if hours <= 0 {
         sqlx::query("SELECT 0::bigint as count")
@@ -1039,47 +1919,373 @@ pub async fn count_system_calls_since(pool: &PgPool, system_id: &str, hours: i32
     Ok(row.get("count"))
 }
 
-/// Get system statistics
+/// Sum the audio bytes stored across all radio calls, optionally only those
+/// from `systems`
 ///
 /// # Errors
 ///
 /// Returns an error if the database query fails.
-pub async fn get_system_stats(pool: &PgPool, system_id: &str) -> Result<SystemStatsDb> {
-    SystemStatsQueries::get_by_system_id(pool, system_id).await
+pub async fn sum_audio_bytes(pool: &PgPool, systems: Option<&[SystemId]>) -> Result<i64> {
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(audio_size_bytes), 0)::bigint FROM radio_calls WHERE ($1::TEXT[] IS NULL OR system_id = ANY($1))",
+    )
+    .bind(systems.map(system_names))
+    .fetch_one(pool)
+    .await?;
+
+    Ok(total)
+}
+
+/// Get call counts for each of the last N clock hours, oldest first,
+/// optionally only for `systems`
+///
+/// Every hour is returned, including those without calls, with the current
+/// (partial) hour last.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn get_hourly_activity(
+    pool: &PgPool,
+    hours: i32,
+    systems: Option<&[SystemId]>,
+) -> Result<Vec<HourlyActivity>> {
+    if hours <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let query = r"
+        SELECT h.period_start,
+               COUNT(c.id) AS call_count,
+               COUNT(DISTINCT c.system_id) AS active_systems
+        FROM generate_series(
+            date_trunc('hour', NOW()) - make_interval(hours => $1 - 1),
+            date_trunc('hour', NOW()),
+            INTERVAL '1 hour'
+        ) AS h(period_start)
+        LEFT JOIN radio_calls c
+          ON c.call_timestamp >= h.period_start
+         AND c.call_timestamp < h.period_start + INTERVAL '1 hour'
+         AND ($2::TEXT[] IS NULL OR c.system_id = ANY($2))
+        GROUP BY h.period_start
+        ORDER BY h.period_start ASC
+    ";
+
+    let rows = sqlx::query(query)
+        .bind(hours)
+        .bind(systems.map(system_names))
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| HourlyActivity {
+            period_start: row.get("period_start"),
+            call_count: row.get("call_count"),
+            active_systems: row.get("active_systems"),
+        })
+        .collect())
+}
+
+/// Get daily bytes added per system over the last N days, optionally only for
+/// `systems`
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn get_daily_storage_growth(
+    pool: &PgPool,
+    days: i32,
+    systems: Option<&[SystemId]>,
+) -> Result<Vec<DailyStorageGrowth>> {
+    if days <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let query = r"
+        SELECT system_id,
+               (created_at AT TIME ZONE 'UTC')::date AS day,
+               COALESCE(SUM(audio_size_bytes), 0)::bigint AS bytes_added,
+               COUNT(*) AS call_count
+        FROM radio_calls
+        WHERE created_at > NOW() - make_interval(days => $1)
+          AND ($2::TEXT[] IS NULL OR system_id = ANY($2))
+        GROUP BY system_id, day
+        ORDER BY day ASC, system_id ASC
+    ";
+
+    let rows = sqlx::query(query)
+        .bind(days)
+        .bind(systems.map(system_names))
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| DailyStorageGrowth {
+            system_id: row.get("system_id"),
+            day: row.get("day"),
+            bytes_added: row.get("bytes_added"),
+            call_count: row.get("call_count"),
+        })
+        .collect())
+}
+
+/// Get detected transcription languages grouped by system, talkgroup and day
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn get_language_stats(
+    pool: &PgPool,
+    filter: &LanguageStatsFilter<'_>,
+) -> Result<Vec<LanguageStatsRow>> {
+    if filter.days <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let query = r"
+        SELECT system_id,
+               talkgroup_id,
+               LOWER(transcription_language) AS language,
+               (created_at AT TIME ZONE 'UTC')::date AS day,
+               COUNT(*) AS call_count
+        FROM radio_calls
+        WHERE transcription_status IN ('completed', 'needs_review')
+          AND transcription_language IS NOT NULL
+          AND transcription_language != ''
+          AND ($1::text IS NULL OR system_id = $1)
+          AND ($2::int IS NULL OR talkgroup_id = $2)
+          AND created_at > NOW() - make_interval(days => $3)
+          AND ($4::TEXT[] IS NULL OR system_id = ANY($4))
+        GROUP BY system_id, talkgroup_id, language, day
+        ORDER BY day ASC, system_id ASC, talkgroup_id ASC, language ASC
+    ";
+
+    let rows = sqlx::query(query)
+        .bind(filter.system_id)
+        .bind(filter.talkgroup_id)
+        .bind(filter.days)
+        .bind(filter.allowed_systems.map(system_names))
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| LanguageStatsRow {
+            system_id: row.get("system_id"),
+            talkgroup_id: row.get("talkgroup_id"),
+            language: row.get("language"),
+            day: row.get("day"),
+            call_count: row.get("call_count"),
+        })
+        .collect())
+}
+
+/// Busiest hours of the day reported per talkgroup
+const TALKGROUP_BUSIEST_HOURS: i64 = 3;
+
+/// Per-talkgroup call counts, airtime, and busiest hours over the last `$1`
+/// hours, for the `$4` busiest talkgroups; `$2` is a system and `$3` a tenant
+/// scope, `$5` the number of busiest hours
+const TALKGROUP_ACTIVITY: &str = r"
+    WITH calls AS (
+        SELECT system_id, talkgroup_id, duration_seconds, call_timestamp,
+               EXTRACT(HOUR FROM call_timestamp AT TIME ZONE 'UTC')::INT AS hour
+        FROM radio_calls
+        WHERE talkgroup_id IS NOT NULL
+          AND call_timestamp > NOW() - make_interval(hours => $1)
+          AND ($2::TEXT IS NULL OR system_id = $2)
+          AND ($3::TEXT[] IS NULL OR system_id = ANY($3))
+    ),
+    totals AS (
+        SELECT system_id, talkgroup_id,
+               COUNT(*) AS call_count,
+               COALESCE(SUM(duration_seconds), 0)::FLOAT8 AS airtime_seconds,
+               MIN(call_timestamp) AS first_call,
+               MAX(call_timestamp) AS last_call
+        FROM calls
+        GROUP BY system_id, talkgroup_id
+        ORDER BY call_count DESC, system_id, talkgroup_id
+        LIMIT $4
+    ),
+    hours AS (
+        SELECT system_id, talkgroup_id, hour, COUNT(*) AS call_count,
+               ROW_NUMBER() OVER (
+                   PARTITION BY system_id, talkgroup_id ORDER BY COUNT(*) DESC, hour
+               ) AS rank
+        FROM calls
+        GROUP BY system_id, talkgroup_id, hour
+    )
+    SELECT t.*,
+           (SELECT rc.talkgroup_label FROM radio_calls rc
+            WHERE rc.system_id = t.system_id AND rc.talkgroup_id = t.talkgroup_id
+              AND rc.talkgroup_label IS NOT NULL
+            ORDER BY rc.call_timestamp DESC
+            LIMIT 1) AS talkgroup_label,
+           COALESCE(
+               (SELECT jsonb_agg(jsonb_build_object('hour', h.hour, 'call_count', h.call_count)
+                                 ORDER BY h.rank)
+                FROM hours h
+                WHERE h.system_id = t.system_id AND h.talkgroup_id = t.talkgroup_id
+                  AND h.rank <= $5),
+               '[]'
+           ) AS busiest_hours
+    FROM totals t
+    ORDER BY t.call_count DESC, t.system_id, t.talkgroup_id
+";
+
+/// Get call counts, airtime, and busiest hours of the busiest talkgroups
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn get_talkgroup_activity(
+    pool: &PgPool,
+    filter: &TalkgroupActivityFilter<'_>,
+) -> Result<Vec<TalkgroupActivityRow>> {
+    if filter.hours <= 0 || filter.limit <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(TALKGROUP_ACTIVITY)
+        .bind(filter.hours)
+        .bind(filter.system_id)
+        .bind(filter.allowed_systems.map(system_names))
+        .bind(filter.limit)
+        .bind(TALKGROUP_BUSIEST_HOURS)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TalkgroupActivityRow {
+            system_id: row.get("system_id"),
+            talkgroup_id: row.get("talkgroup_id"),
+            talkgroup_label: row.get("talkgroup_label"),
+            call_count: row.get("call_count"),
+            airtime_seconds: row.get("airtime_seconds"),
+            first_call: row.get("first_call"),
+            last_call: row.get("last_call"),
+            busiest_hours: serde_json::from_value(row.get("busiest_hours")).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Get upload activity for an API key over the last N days
+///
+/// At most `ip_limit` client IPs and ten rejection reasons are returned.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn get_api_key_usage(
+    pool: &PgPool,
+    key_id: &str,
+    days: i32,
+    ip_limit: i64,
+) -> Result<ApiKeyUsage> {
+    if days <= 0 {
+        return Ok(ApiKeyUsage::default());
+    }
+
+    let totals = sqlx::query(
+        r"
+        SELECT COUNT(*) FILTER (WHERE success) AS uploads,
+               COALESCE(SUM(file_size) FILTER (WHERE success), 0)::bigint AS upload_bytes,
+               COUNT(*) FILTER (WHERE NOT success) AS rejections,
+               MAX(timestamp) AS last_upload_at
+        FROM upload_logs
+        WHERE api_key_used = $1
+          AND timestamp > NOW() - make_interval(days => $2)
+        ",
+    )
+    .bind(key_id)
+    .bind(days)
+    .fetch_one(pool)
+    .await?;
+
+    let reasons = sqlx::query(
+        r"
+        SELECT COALESCE(error_message, 'unknown') AS reason, COUNT(*) AS count
+        FROM upload_logs
+        WHERE api_key_used = $1
+          AND NOT success
+          AND timestamp > NOW() - make_interval(days => $2)
+        GROUP BY reason
+        ORDER BY count DESC, reason ASC
+        LIMIT 10
+        ",
+    )
+    .bind(key_id)
+    .bind(days)
+    .fetch_all(pool)
+    .await?;
+
+    let ips = sqlx::query(
+        r"
+        SELECT host(client_ip) AS ip, MAX(timestamp) AS last_seen
+        FROM upload_logs
+        WHERE api_key_used = $1
+          AND timestamp > NOW() - make_interval(days => $2)
+        GROUP BY client_ip
+        ORDER BY last_seen DESC
+        LIMIT $3
+        ",
+    )
+    .bind(key_id)
+    .bind(days)
+    .bind(ip_limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ApiKeyUsage {
+        uploads: totals.get("uploads"),
+        upload_bytes: totals.get("upload_bytes"),
+        rejections: totals.get("rejections"),
+        last_upload_at: totals.get("last_upload_at"),
+        rejection_reasons: reasons
+            .into_iter()
+            .map(|row| ApiKeyRejectionCount {
+                reason: row.get("reason"),
+                count: row.get("count"),
+            })
+            .collect(),
+        recent_ips: ips
+            .into_iter()
+            .map(|row| ApiKeyIpActivity {
+                ip: row.get("ip"),
+                last_seen: row.get("last_seen"),
+            })
+            .collect(),
+    })
 }
 
-/// Update system statistics
+/// Get system statistics
 ///
 /// # Errors
 ///
 /// Returns an error if the database query fails.
+pub async fn get_system_stats(pool: &PgPool, system_id: &SystemId) -> Result<SystemStatsDb> {
+    SystemStatsQueries::get_by_system_id(pool, system_id).await
+}
+
+/// Count a new call for a system and record its label
 ///
-/// # Panics
+/// # Errors
 ///
-/// Panics if the fallback system ID `"unknown"` fails validation (should never happen).
+/// Returns an error if the database query fails.
 pub async fn update_system_stats(
     pool: &PgPool,
-    system_id: &str,
+    system_id: &SystemId,
     system_label: Option<String>,
 ) -> Result<()> {
-    // Create basic system stats entry
-    let stats = SystemStatsDb {
-        id: Uuid::new_v4(),
-        system_id: sdrtrunk_types::SystemId::new(system_id)
-            .or_else(|_| sdrtrunk_types::SystemId::new("unknown"))
-            .map_err(|e| StorageError::Serialization(format!("Invalid system ID: {e}")))?,
-        system_label,
-        total_calls: Some(1),
-        calls_today: Some(1),
-        calls_this_hour: Some(1),
-        first_seen: Some(chrono::Utc::now()),
-        last_seen: Some(chrono::Utc::now()),
-        top_talkgroups: None,
-        upload_sources: None,
-        last_updated: chrono::Utc::now(),
-    };
-
-    SystemStatsQueries::upsert(pool, &stats).await
+    // Count the call; the periodic rollup corrects any drift
+    SystemStatsQueries::update_activity(pool, system_id).await?;
+    if let Some(label) = system_label {
+        SystemStatsQueries::set_label(pool, system_id, &label).await?;
+    }
+    Ok(())
 }
 
 /// Validate API key
@@ -1156,73 +2362,18 @@ pub async fn update_transcription_status(pool: &PgPool, call_id: Uuid, status: &
 mod tests {
     use super::*;
     use crate::models::RadioCallDb;
+    use crate::test_support::{create_test_pool, create_test_radio_call};
     use sdrtrunk_types::AppError as Error;
     use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
-    use sqlx::PgPool;
     use std::net::IpAddr;
     use uuid::Uuid;
 
-    // Helper function to create test database
-    async fn create_test_pool() -> Option<PgPool> {
-        // Check if TEST_DATABASE_URL is set, if not, skip the test
-        if let Ok(database_url) = std::env::var("TEST_DATABASE_URL") {
-            match PgPool::connect(&database_url).await {
-                Ok(pool) => {
-                    // Skip migrations in test - assume they're already run by CI setup
-                    // The CI script runs migrations before tests
-                    Some(pool)
-                }
-                Err(e) => {
-                    eprintln!("Failed to connect to database: {e}");
-                    None
-                }
-            }
-        } else {
-            None
-        }
+    fn sys_id(id: &str) -> SystemId {
+        SystemId::new(id).unwrap()
     }
 
-    // Test data helpers
-    fn create_test_radio_call(system_id: &str, talkgroup_id: Option<i32>) -> RadioCallDb {
-        RadioCallDb {
-            id: Uuid::new_v4(),
-            created_at: chrono::Utc::now(),
-            call_timestamp: chrono::Utc::now(),
-            system_id: SystemId::new(system_id).unwrap(),
-            system_label: Some("Test System".to_string()),
-            frequency: Some(Frequency::new(154_000_000).unwrap()),
-            talkgroup_id: talkgroup_id.and_then(|id| TalkgroupId::new(id).ok()),
-            talkgroup_label: Some("Test TG".to_string()),
-            talkgroup_group: Some("Public Safety".to_string()),
-            talkgroup_tag: Some("Police".to_string()),
-            source_radio_id: Some(RadioId::new(12345).unwrap()),
-            talker_alias: Some("OFFICER1".to_string()),
-            audio_filename: Some("test.mp3".to_string()),
-            audio_file_path: Some("/tmp/test.mp3".to_string()),
-            audio_size_bytes: Some(2_048_000),
-            audio_content_type: None,
-            duration_seconds: Some(rust_decimal::Decimal::try_from(30.5).unwrap()),
-            transcription_text: Some("Test transcription".to_string()),
-            transcription_confidence: Some(rust_decimal::Decimal::try_from(0.95).unwrap()),
-            transcription_language: None,
-            transcription_status: Some("completed".to_string()),
-            speaker_segments: Some(
-                serde_json::json!([{"speaker": "A", "start": 0.0, "end": 30.5}]),
-            ),
-            speaker_count: Some(1),
-            patches: Some(
-                serde_json::to_string(&serde_json::json!([{"id": 1, "name": "Patch1"}])).unwrap(),
-            ),
-            frequencies: Some(serde_json::to_string(&serde_json::json!([154_000_000])).unwrap()),
-            sources: Some(
-                serde_json::to_string(&serde_json::json!([{"id": 1, "name": "Source1"}])).unwrap(),
-            ),
-            upload_ip: Some(sqlx::types::ipnetwork::IpNetwork::from(IpAddr::from([
-                127, 0, 0, 1,
-            ]))),
-            upload_timestamp: chrono::Utc::now(),
-            upload_api_key_id: Some("test_key_id".to_string()),
-        }
+    fn tg_id(id: i32) -> TalkgroupId {
+        TalkgroupId::new(id).unwrap()
     }
 
     fn create_test_upload_log() -> UploadLogDb {
@@ -1281,6 +2432,31 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_find_by_audio_hash() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let unique_system = format!("hash_{}", &Uuid::new_v4().to_string()[0..8]);
+        let hash = format!("{:0>64}", Uuid::new_v4().simple());
+        let mut call = create_test_radio_call(&unique_system, None);
+        call.audio_sha256 = Some(hash.clone());
+        let id = RadioCallQueries::insert(&pool, &call).await?;
+
+        let found =
+            RadioCallQueries::find_by_audio_hash(&pool, &sys_id(&unique_system), &hash).await?;
+        assert_eq!(found, Some(id));
+
+        // Same audio from another system is not a duplicate
+        let other = RadioCallQueries::find_by_audio_hash(&pool, &sys_id("other"), &hash).await?;
+        assert_eq!(other, None);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
     async fn test_radio_call_count_by_system() -> Result<()> {
@@ -1299,7 +2475,7 @@ mod tests {
             RadioCallQueries::insert(&pool, &call).await?;
         }
 
-        let count = RadioCallQueries::count_by_system(&pool, &unique_system).await?;
+        let count = RadioCallQueries::count_by_system(&pool, &sys_id(&unique_system)).await?;
         assert_eq!(count, 5);
 
         Ok(())
@@ -1328,6 +2504,9 @@ mod tests {
                 error: None,
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             },
         )
         .await?;
@@ -1346,6 +2525,9 @@ mod tests {
                 error: None,
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             },
         )
         .await?;
@@ -1389,7 +2571,7 @@ mod tests {
         // First upsert (insert)
         SystemStatsQueries::upsert(&pool, &stats).await?;
 
-        let retrieved = SystemStatsQueries::get_by_system_id(&pool, "upsert_test").await?;
+        let retrieved = SystemStatsQueries::get_by_system_id(&pool, &sys_id("upsert_test")).await?;
         assert_eq!(retrieved.system_id.as_str(), "upsert_test");
         assert_eq!(retrieved.total_calls, Some(100));
 
@@ -1400,7 +2582,8 @@ mod tests {
 
         SystemStatsQueries::upsert(&pool, &updated_stats).await?;
 
-        let updated_retrieved = SystemStatsQueries::get_by_system_id(&pool, "upsert_test").await?;
+        let updated_retrieved =
+            SystemStatsQueries::get_by_system_id(&pool, &sys_id("upsert_test")).await?;
         assert_eq!(updated_retrieved.total_calls, Some(150));
         assert_eq!(updated_retrieved.calls_today, Some(15));
 
@@ -1456,6 +2639,7 @@ mod tests {
             failed: 50,
             processing: 10,
             pending: 90,
+            skipped: 0,
             avg_confidence: Some(0.92),
         };
 
@@ -1475,10 +2659,160 @@ mod tests {
             total_bytes_uploaded: 1024 * 1024 * 1024, // 1GB
         };
 
-        assert_eq!(stats.total_uploads, 500);
-        assert_eq!(stats.successful_uploads + stats.failed_uploads, 500);
-        assert!(stats.avg_processing_time.unwrap() > 1000.0);
-        assert!(stats.total_bytes_uploaded > 1_000_000_000);
+        assert_eq!(stats.total_uploads, 500);
+        assert_eq!(stats.successful_uploads + stats.failed_uploads, 500);
+        assert!(stats.avg_processing_time.unwrap() > 1000.0);
+        assert!(stats.total_bytes_uploaded > 1_000_000_000);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_language_filter() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system = format!("lang_{}", &Uuid::new_v4().to_string()[..8]);
+        let mut ids = Vec::new();
+        for language in [Some("en"), Some("en-US"), Some("es"), None] {
+            let mut call = create_test_radio_call(&system, Some(100));
+            call.transcription_language = language.map(str::to_string);
+            RadioCallQueries::insert(&pool, &call).await?;
+            ids.push(call.id);
+        }
+
+        let system_id = sys_id(&system);
+        let filter = |language| RadioCallFilter {
+            system_id: Some(&system_id),
+            allowed_systems: None,
+            talkgroup_id: None,
+            transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: Some(language),
+            search: None,
+            archived: false,
+            from_date: None,
+            to_date: None,
+            limit: 10,
+            after: None,
+        };
+
+        let english = list_radio_calls_filtered(&pool, filter("EN")).await?;
+        let mut english: Vec<_> = english.into_iter().map(|c| c.id).collect();
+        english.sort();
+        let mut expected = vec![ids[0], ids[1]];
+        expected.sort();
+        assert_eq!(english, expected);
+        assert_eq!(count_radio_calls_filtered(&pool, filter("en")).await?, 2);
+        assert_eq!(count_radio_calls_filtered(&pool, filter("en-us")).await?, 1);
+        assert_eq!(count_radio_calls_filtered(&pool, filter("e")).await?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_condition() {
+        let query: CallQuery = r#"system:Butler tg:1234..1299 (text~"pursuit" OR -radio:..99)"#
+            .parse()
+            .unwrap();
+        let mut search = SearchParams::after(2);
+        let condition = search.condition(&query);
+
+        assert_eq!(
+            condition,
+            "((LOWER(system_id) = LOWER($3) AND talkgroup_id BETWEEN $4 AND $5) AND \
+             ((STRPOS(LOWER(transcription_text), LOWER($6)) > 0 \
+             OR EXISTS (SELECT 1 FROM call_summaries cs WHERE cs.call_id = radio_calls.id \
+             AND (STRPOS(LOWER(cs.summary), LOWER($6)) > 0 \
+             OR STRPOS(LOWER(cs.entities::text), LOWER($6)) > 0))) \
+             OR NOT COALESCE(source_radio_id <= $7, FALSE)))"
+        );
+        assert_eq!(search.count, 7);
+        assert_eq!(
+            search.values,
+            [
+                SearchParam::Text("Butler"),
+                SearchParam::Number(1234),
+                SearchParam::Number(1299),
+                SearchParam::Text("pursuit"),
+                SearchParam::Number(99),
+            ]
+        );
+
+        // Values never reach the SQL text
+        let query: CallQuery = r#"label:"'; DROP TABLE radio_calls; --""#.parse().unwrap();
+        let mut search = SearchParams::after(0);
+        assert_eq!(
+            search.condition(&query),
+            "LOWER(talkgroup_label) = LOWER($1)"
+        );
+        assert_eq!(
+            search.values,
+            [SearchParam::Text("'; DROP TABLE radio_calls; --")]
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_search_filter() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system = format!("search_{}", &Uuid::new_v4().to_string()[..8]);
+        let mut ids = Vec::new();
+        for (talkgroup, text) in [
+            (1234, "Units in pursuit southbound"),
+            (1299, "Traffic stop"),
+            (1300, "Pursuit terminated"),
+        ] {
+            let mut call = create_test_radio_call(&system, Some(talkgroup));
+            call.transcription_text = Some(text.to_string());
+            RadioCallQueries::insert(&pool, &call).await?;
+            ids.push(call.id);
+        }
+
+        let system_id = sys_id(&system);
+        let parse = |expression: &str| -> CallQuery { expression.parse().unwrap() };
+        let filter = |search| RadioCallFilter {
+            system_id: None,
+            allowed_systems: None,
+            talkgroup_id: None,
+            transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: Some(search),
+            archived: false,
+            from_date: None,
+            to_date: None,
+            limit: 10,
+            after: None,
+        };
+
+        let query = parse(&format!(
+            r#"system:{system} AND tg:1234..1299 AND text~"pursuit""#
+        ));
+        let found = list_radio_calls_filtered(&pool, filter(&query)).await?;
+        assert_eq!(found.iter().map(|c| c.id).collect::<Vec<_>>(), [ids[0]]);
+        assert_eq!(count_radio_calls_filtered(&pool, filter(&query)).await?, 1);
+
+        let query = parse(&format!("system:{system} (pursuit OR stop) -tg:1234"));
+        assert_eq!(count_radio_calls_filtered(&pool, filter(&query)).await?, 2);
+
+        // Searching within one system uses the system listing
+        let query = parse("tg:1299.. NOT stop");
+        let in_system = RadioCallFilter {
+            system_id: Some(&system_id),
+            ..filter(&query)
+        };
+        let found = list_radio_calls_filtered(&pool, in_system).await?;
+        assert_eq!(found.iter().map(|c| c.id).collect::<Vec<_>>(), [ids[2]]);
+
+        Ok(())
     }
 
     #[tokio::test]
@@ -1507,14 +2841,21 @@ mod tests {
         let nonexistent_system = format!("nonexistent_{}", &Uuid::new_v4().to_string()[0..8]);
         let filter = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 10,
-            offset: 0,
+            after: None,
         };
-        let calls = RadioCallQueries::find_by_system(&pool, &nonexistent_system, &filter).await?;
+        let calls =
+            RadioCallQueries::find_by_system(&pool, &sys_id(&nonexistent_system), &filter).await?;
 
         assert!(calls.is_empty());
         Ok(())
@@ -1529,7 +2870,7 @@ mod tests {
         };
 
         let nonexistent_system = format!("nonexistent_{}", &Uuid::new_v4().to_string()[0..8]);
-        let count = RadioCallQueries::count_by_system(&pool, &nonexistent_system).await?;
+        let count = RadioCallQueries::count_by_system(&pool, &sys_id(&nonexistent_system)).await?;
 
         assert_eq!(count, 0);
         Ok(())
@@ -1558,6 +2899,9 @@ mod tests {
                 error: Some("Transcription service unavailable"),
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             },
         )
         .await?;
@@ -1580,7 +2924,8 @@ mod tests {
         };
 
         let nonexistent_system = format!("nonexistent_{}", &Uuid::new_v4().to_string()[0..8]);
-        let result = SystemStatsQueries::get_by_system_id(&pool, &nonexistent_system).await;
+        let result =
+            SystemStatsQueries::get_by_system_id(&pool, &sys_id(&nonexistent_system)).await;
 
         assert!(matches!(result, Err(StorageError::NotFound { .. })));
         Ok(())
@@ -1619,6 +2964,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         assert_eq!(update.status, "processing");
@@ -1632,19 +2980,25 @@ mod tests {
     fn test_radio_call_filter_struct() {
         let now = chrono::Utc::now();
         let filter = RadioCallFilter {
-            system_id: Some("test_system"),
-            talkgroup_id: Some(12345),
+            system_id: Some(&sys_id("test_system")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(12345)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(now - chrono::Duration::hours(24)),
             to_date: Some(now),
             limit: 100,
-            offset: 50,
+            after: None,
         };
 
-        assert_eq!(filter.system_id, Some("test_system"));
-        assert_eq!(filter.talkgroup_id, Some(12345));
+        assert_eq!(filter.system_id, Some(&sys_id("test_system")));
+        assert_eq!(filter.talkgroup_id, Some(tg_id(12345)));
         assert_eq!(filter.limit, 100);
-        assert_eq!(filter.offset, 50);
+        assert!(filter.after.is_none());
         assert!(filter.from_date.is_some());
         assert!(filter.to_date.is_some());
     }
@@ -1773,6 +3127,7 @@ mod tests {
             audio_file_path: Some("/tmp/full_test.wav".to_string()),
             audio_size_bytes: Some(4_096_000),
             audio_content_type: None,
+            audio_sha256: None,
             duration_seconds: Some(rust_decimal::Decimal::try_from(125.75).unwrap()),
             transcription_text: Some("This is a full test transcription".to_string()),
             transcription_confidence: Some(rust_decimal::Decimal::try_from(0.98).unwrap()),
@@ -1797,6 +3152,8 @@ mod tests {
             )),
             upload_timestamp: chrono::Utc::now(),
             upload_api_key_id: Some("test_api_key_full".to_string()),
+            latitude: None,
+            longitude: None,
         };
 
         let id = RadioCallQueries::insert(&pool, &call).await?;
@@ -1845,7 +3202,7 @@ mod tests {
 
         SystemStatsQueries::upsert(&pool, &stats).await?;
 
-        let retrieved = SystemStatsQueries::get_by_system_id(&pool, &system_id).await?;
+        let retrieved = SystemStatsQueries::get_by_system_id(&pool, &sys_id(&system_id)).await?;
         assert_eq!(retrieved.system_id.as_str(), system_id);
         assert_eq!(retrieved.total_calls, Some(1000));
         assert!(retrieved.top_talkgroups.is_some());
@@ -1916,6 +3273,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         RadioCallQueries::update_transcription_status(&pool, update).await?;
 
@@ -1928,6 +3288,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         RadioCallQueries::update_transcription_status(&pool, update).await?;
 
@@ -1940,6 +3303,9 @@ mod tests {
             error: Some("Test error message"),
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         RadioCallQueries::update_transcription_status(&pool, update).await?;
 
@@ -2045,27 +3411,44 @@ mod tests {
         // Test pagination
         let filter1 = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 5,
-            offset: 0,
+            after: None,
         };
-        let page1 = RadioCallQueries::find_by_system(&pool, &system_id, &filter1).await?;
+        let page1 = RadioCallQueries::find_by_system(&pool, &sys_id(&system_id), &filter1).await?;
         assert_eq!(page1.len(), 5);
 
         let filter2 = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 5,
-            offset: 5,
+            after: page1.last().map(CallCursor::after),
         };
-        let page2 = RadioCallQueries::find_by_system(&pool, &system_id, &filter2).await?;
+        let page2 = RadioCallQueries::find_by_system(&pool, &sys_id(&system_id), &filter2).await?;
         assert_eq!(page2.len(), 5);
+        assert!(
+            page2
+                .iter()
+                .all(|call| page1.iter().all(|seen| seen.id != call.id))
+        );
 
         // Ensure calls are ordered by timestamp DESC
         for i in 0..4 {
@@ -2077,6 +3460,187 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_find_completed() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("test_completed_{}", &Uuid::new_v4().to_string()[0..8]);
+        let mut completed = create_test_radio_call(&system_id, Some(1));
+        completed.transcription_status = Some("completed".to_string());
+        let mut flagged = create_test_radio_call(&system_id, Some(1));
+        flagged.transcription_status = Some("needs_review".to_string());
+        let mut pending = create_test_radio_call(&system_id, Some(1));
+        pending.transcription_status = Some("pending".to_string());
+        RadioCallQueries::insert(&pool, &completed).await?;
+        RadioCallQueries::insert(&pool, &flagged).await?;
+        RadioCallQueries::insert(&pool, &pending).await?;
+
+        let found = RadioCallQueries::find_completed(
+            &pool,
+            &[completed.id, flagged.id, pending.id, Uuid::new_v4()],
+        )
+        .await?;
+        let mut found: Vec<Uuid> = found.iter().map(|call| call.id).collect();
+        found.sort();
+        let mut expected = vec![completed.id, flagged.id];
+        expected.sort();
+        assert_eq!(found, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_low_confidence_review() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("test_review_{}", &Uuid::new_v4().to_string()[0..8]);
+        let call = create_test_radio_call(&system_id, Some(1));
+        let id = RadioCallQueries::insert(&pool, &call).await?;
+        RadioCallQueries::update_transcription_status(
+            &pool,
+            TranscriptionUpdate {
+                id,
+                status: "needs_review",
+                text: Some("engine five"),
+                confidence: Some(0.3),
+                error: None,
+                speaker_segments: None,
+                speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
+            },
+        )
+        .await?;
+
+        let flagged = RadioCallQueries::find_by_id(&pool, id).await?;
+        assert_eq!(
+            flagged.transcription_status.as_deref(),
+            Some("needs_review")
+        );
+        assert!(flagged.transcription_confidence.is_some());
+
+        assert!(RadioCallQueries::mark_reviewed(&pool, id, Some("alice")).await?);
+        assert!(!RadioCallQueries::mark_reviewed(&pool, id, Some("alice")).await?);
+        let reviewed = RadioCallQueries::find_by_id(&pool, id).await?;
+        assert_eq!(reviewed.transcription_status.as_deref(), Some("completed"));
+
+        let events = crate::CallEventQueries::for_call(&pool, id).await?;
+        let events: Vec<_> = events
+            .iter()
+            .map(|e| (e.event.as_str(), e.detail.as_deref()))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (
+                    "transcribed",
+                    Some("Confidence below threshold; needs review")
+                ),
+                ("reviewed", Some("alice")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_attach_audio_to_registered_call() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("test_attach_{}", &Uuid::new_v4().to_string()[0..8]);
+        let mut call = create_test_radio_call(&system_id, Some(1));
+        call.audio_filename = None;
+        call.audio_file_path = None;
+        call.audio_size_bytes = None;
+        call.audio_sha256 = None;
+        call.transcription_status = Some("awaiting_audio".to_string());
+        let id = RadioCallQueries::insert(&pool, &call).await?;
+
+        let audio = AttachedAudio {
+            filename: "attached.mp3",
+            file_path: "test/attached.mp3",
+            size_bytes: 2048,
+            content_type: Some("audio/mpeg"),
+            sha256: "abc123",
+            duration_seconds: None,
+            transcription_status: "pending",
+        };
+        assert!(RadioCallQueries::attach_audio(&pool, id, &audio).await?);
+        // A second attachment finds the call no longer awaiting audio
+        assert!(!RadioCallQueries::attach_audio(&pool, id, &audio).await?);
+
+        let attached = RadioCallQueries::find_by_id(&pool, id).await?;
+        assert_eq!(attached.audio_filename.as_deref(), Some("attached.mp3"));
+        assert_eq!(attached.audio_size_bytes, Some(2048));
+        assert_eq!(attached.audio_sha256.as_deref(), Some("abc123"));
+        assert_eq!(attached.duration_seconds, call.duration_seconds);
+        assert_eq!(attached.transcription_status.as_deref(), Some("pending"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_raw_transcription_round_trip() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("test_raw_{}", &Uuid::new_v4().to_string()[0..8]);
+        let call = create_test_radio_call(&system_id, Some(1));
+        let id = RadioCallQueries::insert(&pool, &call).await?;
+        assert_eq!(
+            RadioCallQueries::find_raw_transcription(&pool, id).await?,
+            None
+        );
+
+        let update = |raw_text| TranscriptionUpdate {
+            id,
+            status: "completed",
+            text: Some("Officer [NAME] on scene"),
+            confidence: None,
+            error: None,
+            speaker_segments: None,
+            speaker_count: None,
+            language: None,
+            raw_text,
+            text_encrypted: None,
+        };
+        let sealed: &'static [u8] = b"sealed";
+        RadioCallQueries::update_transcription_status(&pool, update(Some(sealed))).await?;
+        assert_eq!(
+            RadioCallQueries::find_raw_transcription(&pool, id).await?,
+            Some(sealed.to_vec())
+        );
+        // A later transcript without an original clears the old one
+        RadioCallQueries::update_transcription_status(&pool, update(None)).await?;
+        assert_eq!(
+            RadioCallQueries::find_raw_transcription(&pool, id).await?,
+            None
+        );
+
+        assert!(matches!(
+            RadioCallQueries::find_raw_transcription(&pool, Uuid::new_v4()).await,
+            Err(StorageError::NotFound { .. })
+        ));
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
     async fn test_transcription_stats() -> Result<()> {
@@ -2207,6 +3771,49 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_system_stats_rollup() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("rollup_{}", &Uuid::new_v4().to_string()[0..8]);
+        for talkgroup in [100, 100, 200] {
+            let call = create_test_radio_call(&system_id, Some(talkgroup));
+            RadioCallQueries::insert(&pool, &call).await?;
+        }
+
+        // Counters that drifted, e.g. after a crash mid-upload
+        let mut drifted = create_test_system_stats(&system_id);
+        drifted.total_calls = Some(42);
+        drifted.top_talkgroups = None;
+        SystemStatsQueries::upsert(&pool, &drifted).await?;
+
+        let systems = [sys_id(&system_id)];
+        assert_eq!(SystemStatsQueries::rollup(&pool, Some(&systems)).await?, 1);
+
+        let stats = SystemStatsQueries::get_by_system_id(&pool, &sys_id(&system_id)).await?;
+        assert_eq!(stats.total_calls, Some(3));
+        assert_eq!(stats.calls_this_hour, Some(3));
+        let top = stats.top_talkgroups.unwrap();
+        assert_eq!(top[0]["id"], 100);
+        assert_eq!(top[0]["count"], 2);
+        assert_eq!(top[1]["id"], 200);
+        assert_eq!(stats.upload_sources, Some(serde_json::json!(["127.0.0.1"])));
+
+        // Systems whose calls are gone are zeroed
+        let empty_id = format!("rollup_{}", &Uuid::new_v4().to_string()[0..8]);
+        SystemStatsQueries::upsert(&pool, &create_test_system_stats(&empty_id)).await?;
+        SystemStatsQueries::rollup(&pool, Some(&[sys_id(&empty_id)])).await?;
+        let empty = SystemStatsQueries::get_by_system_id(&pool, &sys_id(&empty_id)).await?;
+        assert_eq!(empty.total_calls, Some(0));
+        assert!(empty.top_talkgroups.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
     async fn test_system_stats_activity_update() -> Result<()> {
@@ -2218,17 +3825,17 @@ mod tests {
         let system_id = format!("activity_{}", &Uuid::new_v4().to_string()[0..8]);
 
         // First update (insert)
-        SystemStatsQueries::update_activity(&pool, &system_id).await?;
+        SystemStatsQueries::update_activity(&pool, &sys_id(&system_id)).await?;
 
-        let stats1 = SystemStatsQueries::get_by_system_id(&pool, &system_id).await?;
+        let stats1 = SystemStatsQueries::get_by_system_id(&pool, &sys_id(&system_id)).await?;
         assert_eq!(stats1.total_calls, Some(1));
         assert_eq!(stats1.calls_today, Some(1));
         assert_eq!(stats1.calls_this_hour, Some(1));
 
         // Second update (increment)
-        SystemStatsQueries::update_activity(&pool, &system_id).await?;
+        SystemStatsQueries::update_activity(&pool, &sys_id(&system_id)).await?;
 
-        let stats2 = SystemStatsQueries::get_by_system_id(&pool, &system_id).await?;
+        let stats2 = SystemStatsQueries::get_by_system_id(&pool, &sys_id(&system_id)).await?;
         assert_eq!(stats2.total_calls, Some(2));
         assert_eq!(stats2.calls_today, Some(2));
         assert_eq!(stats2.calls_this_hour, Some(2));
@@ -2271,7 +3878,11 @@ mod tests {
 
     // Test wrapper functions
     #[tokio::test]
-    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    #[allow(
+        clippy::missing_panics_doc,
+        clippy::missing_errors_doc,
+        clippy::too_many_lines
+    )]
     async fn test_wrapper_functions() -> Result<()> {
         let Some(pool) = create_test_pool().await else {
             eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
@@ -2297,26 +3908,38 @@ mod tests {
 
         // Test list_radio_calls_filtered
         let filter = RadioCallFilter {
-            system_id: Some(&system_id),
+            system_id: Some(&sys_id(&system_id)),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 10,
-            offset: 0,
+            after: None,
         };
         let filtered_calls = list_radio_calls_filtered(&pool, filter).await?;
         assert!(!filtered_calls.is_empty());
 
         // Test count_radio_calls_filtered
         let filter_count = RadioCallFilter {
-            system_id: Some(&system_id),
+            system_id: Some(&sys_id(&system_id)),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 10,
-            offset: 0,
+            after: None,
         };
         let count = count_radio_calls_filtered(&pool, filter_count).await?;
         assert!(count > 0);
@@ -2324,12 +3947,18 @@ mod tests {
         // Test filter with no system_id (should return empty/0)
         let empty_filter = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 10,
-            offset: 0,
+            after: None,
         };
         let empty_calls = list_radio_calls_filtered(&pool, empty_filter).await?;
         assert!(empty_calls.is_empty());
@@ -2338,12 +3967,18 @@ mod tests {
             &pool,
             RadioCallFilter {
                 system_id: None,
+                allowed_systems: None,
                 talkgroup_id: None,
                 transcription_status: None,
+                tag: None,
+                keyword: None,
+                language: None,
+                search: None,
+                archived: false,
                 from_date: None,
                 to_date: None,
                 limit: 10,
-                offset: 0,
+                after: None,
             },
         )
         .await?;
@@ -2367,40 +4002,270 @@ mod tests {
         insert_radio_call(&pool, &call).await?;
 
         // Test total count functions
-        let total_calls = count_radio_calls(&pool).await?;
+        let total_calls = count_radio_calls(&pool, None).await?;
         assert!(total_calls > 0);
 
-        let total_systems = count_systems(&pool).await?;
+        let total_systems = count_systems(&pool, None).await?;
         assert!(total_systems > 0);
 
         // Test recent calls count
-        let recent_calls = count_recent_calls(&pool, 24).await?;
+        let recent_calls = count_recent_calls(&pool, 24, None).await?;
         assert!(recent_calls >= 1); // Should include our just-inserted call
 
         // Test very old calls (should be 0)
-        let old_calls = count_recent_calls(&pool, -1).await?;
+        let old_calls = count_recent_calls(&pool, -1, None).await?;
         assert_eq!(old_calls, 0);
 
         // Test top systems - get more to ensure our test system is included
-        let top_systems = get_top_systems(&pool, 100).await?;
+        let top_systems = get_top_systems(&pool, 100, None).await?;
         assert!(!top_systems.is_empty());
 
         // In parallel tests, our system might not be in top 5, but should be in the list
         let found_our_system = top_systems
             .iter()
-            .any(|(id, count)| id == &system_id && *count >= 1);
+            .any(|(id, count)| id.as_str() == system_id && *count >= 1);
         assert!(
             found_our_system,
             "System {system_id} not found in top systems"
         );
 
         // Test system calls since time
-        let system_calls_24h = count_system_calls_since(&pool, &system_id, 24).await?;
+        let system_calls_24h = count_system_calls_since(&pool, &sys_id(&system_id), 24).await?;
         assert!(system_calls_24h >= 1);
 
-        let system_calls_old = count_system_calls_since(&pool, &system_id, -1).await?;
+        let system_calls_old = count_system_calls_since(&pool, &sys_id(&system_id), -1).await?;
         assert_eq!(system_calls_old, 0);
 
+        // Scoped counts only see the allowed systems
+        let scope = [sys_id(&system_id)];
+        assert_eq!(count_radio_calls(&pool, Some(&scope)).await?, 1);
+        assert_eq!(count_systems(&pool, Some(&scope)).await?, 1);
+        assert_eq!(count_recent_calls(&pool, 24, Some(&scope)).await?, 1);
+        assert_eq!(get_top_systems(&pool, 100, Some(&scope)).await?.len(), 1);
+        let hourly = get_hourly_activity(&pool, 24, Some(&scope)).await?;
+        assert_eq!(hourly.len(), 24);
+        assert!(
+            hourly
+                .windows(2)
+                .all(|w| w[0].period_start < w[1].period_start)
+        );
+        assert_eq!(hourly.iter().map(|h| h.call_count).sum::<i64>(), 1);
+        assert!(get_hourly_activity(&pool, 0, None).await?.is_empty());
+        assert_eq!(count_radio_calls(&pool, Some(&[])).await?, 0);
+
+        let other = sys_id(&format!("other_{}", &Uuid::new_v4().to_string()[0..8]));
+        let scoped_calls = list_radio_calls_filtered(
+            &pool,
+            RadioCallFilter {
+                system_id: Some(&scope[0]),
+                allowed_systems: Some(std::slice::from_ref(&other)),
+                talkgroup_id: None,
+                transcription_status: None,
+                tag: None,
+                keyword: None,
+                language: None,
+                search: None,
+                archived: false,
+                from_date: None,
+                to_date: None,
+                limit: 10,
+                after: None,
+            },
+        )
+        .await?;
+        assert!(scoped_calls.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_storage_growth_functions() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("grow_{}", &Uuid::new_v4().to_string()[0..8]);
+
+        for _ in 0..2 {
+            let call = create_test_radio_call(&system_id, Some(42));
+            insert_radio_call(&pool, &call).await?;
+        }
+
+        let total_bytes = sum_audio_bytes(&pool, None).await?;
+        assert!(total_bytes >= 4_096_000);
+
+        let growth = get_daily_storage_growth(&pool, 7, None).await?;
+        let ours: Vec<_> = growth
+            .iter()
+            .filter(|g| g.system_id.as_str() == system_id)
+            .collect();
+        assert_eq!(ours.len(), 1);
+        assert_eq!(ours[0].bytes_added, 4_096_000);
+        assert_eq!(ours[0].call_count, 2);
+
+        // Non-positive windows return nothing
+        assert!(get_daily_storage_growth(&pool, 0, None).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_language_stats() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("lang_{}", &Uuid::new_v4().to_string()[0..8]);
+
+        for language in ["en", "ES", "es"] {
+            let call = create_test_radio_call(&system_id, Some(7));
+            let id = insert_radio_call(&pool, &call).await?;
+            RadioCallQueries::update_transcription_status(
+                &pool,
+                TranscriptionUpdate {
+                    id,
+                    status: "completed",
+                    text: Some("test"),
+                    confidence: None,
+                    error: None,
+                    speaker_segments: None,
+                    speaker_count: None,
+                    language: Some(language),
+                    raw_text: None,
+                    text_encrypted: None,
+                },
+            )
+            .await?;
+        }
+
+        let rows = get_language_stats(
+            &pool,
+            &LanguageStatsFilter {
+                system_id: Some(&sys_id(&system_id)),
+                allowed_systems: None,
+                talkgroup_id: None,
+                days: 7,
+            },
+        )
+        .await?;
+
+        assert_eq!(rows.len(), 2);
+        let spanish = rows.iter().find(|r| r.language == "es").unwrap();
+        assert_eq!(spanish.call_count, 2);
+        assert_eq!(spanish.talkgroup_id, Some(tg_id(7)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_talkgroup_activity() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let system_id = format!("tga_{}", &Uuid::new_v4().to_string()[0..8]);
+        let today = chrono::Utc::now().date_naive();
+        let at = |hour: u32| {
+            let time = today.and_hms_opt(hour, 15, 0).unwrap().and_utc();
+            // Keep every call inside the window
+            if time > chrono::Utc::now() {
+                time - chrono::Duration::days(1)
+            } else {
+                time
+            }
+        };
+        for (talkgroup, hour, label) in [
+            (1, 8, "Old Dispatch"),
+            (1, 9, "Dispatch"),
+            (1, 9, "Dispatch"),
+            (2, 14, "Fireground"),
+        ] {
+            let mut call = create_test_radio_call(&system_id, Some(talkgroup));
+            call.call_timestamp = at(hour);
+            call.talkgroup_label = Some(label.to_string());
+            insert_radio_call(&pool, &call).await?;
+        }
+
+        let system = sys_id(&system_id);
+        let filter = TalkgroupActivityFilter {
+            system_id: Some(&system),
+            allowed_systems: None,
+            hours: 48,
+            limit: 10,
+        };
+        let rows = get_talkgroup_activity(&pool, &filter).await?;
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].talkgroup_id, tg_id(1));
+        assert_eq!(rows[0].call_count, 3);
+        assert!((rows[0].airtime_seconds - 91.5).abs() < 1e-9);
+        assert_eq!(
+            rows[0].busiest_hours,
+            [
+                TalkgroupHourCount {
+                    hour: 9,
+                    call_count: 2
+                },
+                TalkgroupHourCount {
+                    hour: 8,
+                    call_count: 1
+                }
+            ]
+        );
+        assert_eq!(rows[1].talkgroup_label.as_deref(), Some("Fireground"));
+
+        let top =
+            get_talkgroup_activity(&pool, &TalkgroupActivityFilter { limit: 1, ..filter }).await?;
+        assert_eq!(top.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
+    async fn test_api_key_usage() -> Result<()> {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return Ok(());
+        };
+
+        let key_id = format!("usage_{}", &Uuid::new_v4().to_string()[0..8]);
+
+        let mut success = create_test_upload_log();
+        success.api_key_used = Some(key_id.clone());
+        UploadLogQueries::insert(&pool, &success).await?;
+
+        let mut rejected = create_test_upload_log();
+        rejected.api_key_used = Some(key_id.clone());
+        rejected.success = false;
+        rejected.error_message = Some("File too large".to_string());
+        rejected.client_ip = "10.0.0.5".parse::<IpAddr>().unwrap().into();
+        UploadLogQueries::insert(&pool, &rejected).await?;
+
+        let usage = get_api_key_usage(&pool, &key_id, 7, 5).await?;
+        assert_eq!(usage.uploads, 1);
+        assert_eq!(usage.upload_bytes, 1_048_576);
+        assert_eq!(usage.rejections, 1);
+        assert!(usage.last_upload_at.is_some());
+        assert_eq!(
+            usage.rejection_reasons,
+            vec![ApiKeyRejectionCount {
+                reason: "File too large".to_string(),
+                count: 1
+            }]
+        );
+        assert_eq!(usage.recent_ips.len(), 2);
+
+        let empty = get_api_key_usage(&pool, &key_id, 0, 5).await?;
+        assert_eq!(empty.uploads, 0);
+        assert!(empty.recent_ips.is_empty());
+
         Ok(())
     }
 
@@ -2416,25 +4281,31 @@ mod tests {
         let system_label = "Test System Operations";
 
         // Test update_system_stats
-        update_system_stats(&pool, &system_id, Some(system_label.to_string())).await?;
+        update_system_stats(&pool, &sys_id(&system_id), Some(system_label.to_string())).await?;
 
         // Test get_system_stats
-        let stats = get_system_stats(&pool, &system_id).await?;
+        let stats = get_system_stats(&pool, &sys_id(&system_id)).await?;
         assert_eq!(stats.system_id.as_str(), system_id);
         assert_eq!(stats.system_label, Some(system_label.to_string()));
         assert_eq!(stats.total_calls, Some(1));
 
         // Test updating again (should increment)
-        update_system_stats(&pool, &system_id, Some("Updated Label".to_string())).await?;
-        let updated_stats = get_system_stats(&pool, &system_id).await?;
+        update_system_stats(
+            &pool,
+            &sys_id(&system_id),
+            Some("Updated Label".to_string()),
+        )
+        .await?;
+        let updated_stats = get_system_stats(&pool, &sys_id(&system_id)).await?;
         assert_eq!(
             updated_stats.system_label,
             Some("Updated Label".to_string())
         );
+        assert_eq!(updated_stats.total_calls, Some(2));
 
         // Test getting non-existent system
         let fake_system = "non_existent_system";
-        let result = get_system_stats(&pool, fake_system).await;
+        let result = get_system_stats(&pool, &sys_id(fake_system)).await;
         assert!(matches!(result, Err(StorageError::NotFound { .. })));
 
         Ok(())
@@ -2510,22 +4381,31 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert_eq!(update.status, "completed");
         assert!(update.confidence.unwrap() > 0.8);
 
         // Test RadioCallFilter struct
         let filter = RadioCallFilter {
-            system_id: Some("test_system"),
-            talkgroup_id: Some(12345),
+            system_id: Some(&sys_id("test_system")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(12345)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(chrono::Utc::now() - chrono::Duration::days(7)),
             to_date: Some(chrono::Utc::now()),
             limit: 50,
-            offset: 0,
+            after: None,
         };
         assert_eq!(filter.limit, 50);
-        assert_eq!(filter.offset, 0);
+        assert!(filter.after.is_none());
         assert!(filter.system_id.is_some());
 
         // Test UploadLogParams struct
@@ -2554,6 +4434,7 @@ mod tests {
             failed: 0,
             processing: 0,
             pending: 0,
+            skipped: 0,
             avg_confidence: None,
         };
         assert_eq!(empty_stats.total, 0);
@@ -2637,6 +4518,9 @@ mod tests {
             error: Some("Debug error"),
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         let debug_str = format!("{update:?}");
@@ -2651,13 +4535,22 @@ mod tests {
     #[allow(clippy::missing_panics_doc)]
     fn test_radio_call_filter_debug() {
         let filter = RadioCallFilter {
-            system_id: Some("debug_sys"),
-            talkgroup_id: Some(999),
+            system_id: Some(&sys_id("debug_sys")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(999)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 25,
-            offset: 10,
+            after: Some(CallCursor {
+                call_timestamp: chrono::DateTime::UNIX_EPOCH,
+                id: Uuid::nil(),
+            }),
         };
 
         let debug_str = format!("{filter:?}");
@@ -2665,7 +4558,7 @@ mod tests {
         assert!(debug_str.contains("debug_sys"));
         assert!(debug_str.contains("999"));
         assert!(debug_str.contains("25"));
-        assert!(debug_str.contains("10"));
+        assert!(debug_str.contains("00000000-0000-0000-0000-000000000000"));
     }
 
     #[test]
@@ -2699,6 +4592,7 @@ mod tests {
             failed: 5,
             processing: 3,
             pending: 2,
+            skipped: 0,
             avg_confidence: Some(0.95),
         };
 
@@ -2718,6 +4612,7 @@ mod tests {
             failed: 10,
             processing: 3,
             pending: 2,
+            skipped: 0,
             avg_confidence: Some(0.87),
         };
 
@@ -2830,6 +4725,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         let update2 = TranscriptionUpdate {
             id: uuid2,
@@ -2839,6 +4737,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         assert_ne!(update1.id, update2.id);
@@ -2849,12 +4750,18 @@ mod tests {
         // Test creating filter with minimal data
         let minimal_filter = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 100,
-            offset: 0,
+            after: None,
         };
 
         assert!(minimal_filter.system_id.is_none());
@@ -2862,7 +4769,7 @@ mod tests {
         assert!(minimal_filter.from_date.is_none());
         assert!(minimal_filter.to_date.is_none());
         assert_eq!(minimal_filter.limit, 100);
-        assert_eq!(minimal_filter.offset, 0);
+        assert!(minimal_filter.after.is_none());
     }
 
     #[test]
@@ -2886,6 +4793,9 @@ mod tests {
                 error: None,
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             };
             assert!(!update.status.is_empty());
             assert_eq!(update.status, *status);
@@ -2903,6 +4813,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         assert!(minimal_update.text.is_none());
@@ -2940,6 +4853,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert_eq!(zero_conf.confidence, Some(0.0));
 
@@ -2951,6 +4867,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert_eq!(max_conf.confidence, Some(1.0));
 
@@ -2962,6 +4881,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert_eq!(over_max.confidence, Some(1.5));
     }
@@ -2984,39 +4906,92 @@ mod tests {
         assert!(large_file.file_size.unwrap() > 1_000_000_000);
     }
 
+    #[test]
+    fn test_call_cursor_roundtrip() {
+        let cursor = CallCursor {
+            call_timestamp: chrono::DateTime::parse_from_rfc3339("2025-03-01T12:30:45.123456Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+            id: Uuid::new_v4(),
+        };
+        let parsed: CallCursor = cursor.to_string().parse().unwrap();
+        assert_eq!(parsed, cursor);
+
+        let offset: CallCursor = format!("2025-03-01T07:30:45.123456-05:00,{}", cursor.id)
+            .parse()
+            .unwrap();
+        assert_eq!(offset, cursor);
+
+        assert!("".parse::<CallCursor>().is_err());
+        assert!("2025-03-01T12:30:45Z".parse::<CallCursor>().is_err());
+        assert!("yesterday,not-a-uuid".parse::<CallCursor>().is_err());
+        assert!(
+            format!("yesterday,{}", cursor.id)
+                .parse::<CallCursor>()
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_pagination_edge_cases() {
         // Test extreme pagination values
-        let large_offset = RadioCallFilter {
-            system_id: Some("test"),
+        let oldest_cursor = RadioCallFilter {
+            system_id: Some(&sys_id("test")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 1,
-            offset: 1_000_000,
+            after: Some(CallCursor {
+                call_timestamp: chrono::DateTime::UNIX_EPOCH,
+                id: Uuid::nil(),
+            }),
         };
-        assert_eq!(large_offset.offset, 1_000_000);
+        assert_eq!(
+            oldest_cursor
+                .after
+                .map(|cursor| cursor.to_string())
+                .as_deref(),
+            Some("1970-01-01T00:00:00.000000Z,00000000-0000-0000-0000-000000000000")
+        );
 
         let large_limit = RadioCallFilter {
-            system_id: Some("test"),
+            system_id: Some(&sys_id("test")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 10_000,
-            offset: 0,
+            after: None,
         };
         assert_eq!(large_limit.limit, 10_000);
 
         let zero_limit = RadioCallFilter {
-            system_id: Some("test"),
+            system_id: Some(&sys_id("test")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 0,
-            offset: 0,
+            after: None,
         };
         assert_eq!(zero_limit.limit, 0);
     }
@@ -3028,13 +5003,19 @@ mod tests {
         let future = now + chrono::Duration::days(1);
 
         let date_filter = RadioCallFilter {
-            system_id: Some("date_test"),
+            system_id: Some(&sys_id("date_test")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(past),
             to_date: Some(future),
             limit: 50,
-            offset: 0,
+            after: None,
         };
 
         assert!(date_filter.from_date.is_some());
@@ -3043,13 +5024,19 @@ mod tests {
 
         // Test inverted date range (edge case)
         let inverted_filter = RadioCallFilter {
-            system_id: Some("inverted_test"),
+            system_id: Some(&sys_id("inverted_test")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(future),
             to_date: Some(past),
             limit: 10,
-            offset: 0,
+            after: None,
         };
 
         assert!(inverted_filter.from_date.unwrap() > inverted_filter.to_date.unwrap());
@@ -3064,6 +5051,7 @@ mod tests {
             failed: 0,
             processing: 0,
             pending: 0,
+            skipped: 0,
             avg_confidence: None,
         };
 
@@ -3078,6 +5066,7 @@ mod tests {
             failed: 20,
             processing: 10,
             pending: 10, // Sums to 90, not 100
+            skipped: 0,
             avg_confidence: Some(0.8),
         };
 
@@ -3110,7 +5099,10 @@ mod tests {
             confidence: None,
             error: Some(""), // Empty error
             speaker_count: None,
+            language: None,
             speaker_segments: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         assert!(empty_update.status.is_empty());
@@ -3127,6 +5119,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         assert_eq!(long_update.text.unwrap().len(), 10_000);
@@ -3191,33 +5186,51 @@ mod tests {
     fn test_filter_combinations() {
         // Test various filter combinations
         let system_only = RadioCallFilter {
-            system_id: Some("system_only"),
+            system_id: Some(&sys_id("system_only")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 50,
-            offset: 0,
+            after: None,
         };
 
         let talkgroup_only = RadioCallFilter {
             system_id: None,
-            talkgroup_id: Some(12345),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(12345)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 50,
-            offset: 0,
+            after: None,
         };
 
         let comprehensive = RadioCallFilter {
-            system_id: Some("comprehensive_test"),
-            talkgroup_id: Some(99_999),
+            system_id: Some(&sys_id("comprehensive_test")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(99_999)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(chrono::Utc::now() - chrono::Duration::days(30)),
             to_date: Some(chrono::Utc::now()),
             limit: 1000,
-            offset: 2000,
+            after: None,
         };
 
         assert!(system_only.system_id.is_some());
@@ -3229,7 +5242,7 @@ mod tests {
         assert!(comprehensive.from_date.is_some());
         assert!(comprehensive.to_date.is_some());
         assert_eq!(comprehensive.limit, 1000);
-        assert_eq!(comprehensive.offset, 2000);
+        assert!(comprehensive.after.is_none());
     }
 
     #[test]
@@ -3246,6 +5259,9 @@ mod tests {
                 error: None,
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             };
 
             assert!((update.confidence.unwrap() - precision).abs() < f32::EPSILON);
@@ -3266,6 +5282,7 @@ mod tests {
             failed: 0,
             processing: 0,
             pending: 0,
+            skipped: 0,
             avg_confidence: None,
         };
 
@@ -3332,6 +5349,9 @@ mod tests {
                 error: Some(error_msg),
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             };
 
             assert!(update.error.is_some());
@@ -3348,6 +5368,7 @@ mod tests {
             failed: 30,
             processing: 15,
             pending: 5,
+            skipped: 0,
             avg_confidence: Some(0.92),
         };
 
@@ -3385,6 +5406,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
 
         let debug_str = format!("{update:?}");
@@ -3394,13 +5418,19 @@ mod tests {
         assert!(debug_str.contains("0.88"));
 
         let filter = RadioCallFilter {
-            system_id: Some("debug_system"),
-            talkgroup_id: Some(999),
+            system_id: Some(&sys_id("debug_system")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(999)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 100,
-            offset: 50,
+            after: None,
         };
 
         let filter_debug = format!("{filter:?}");
@@ -3506,7 +5536,11 @@ mod tests {
             TranscriptionStatus::Pending,
             TranscriptionStatus::Processing,
             TranscriptionStatus::Completed,
+            TranscriptionStatus::NeedsReview,
             TranscriptionStatus::Failed,
+            TranscriptionStatus::Skipped,
+            TranscriptionStatus::AwaitingAudio,
+            TranscriptionStatus::Deferred,
         ];
 
         for status in statuses {
@@ -3527,12 +5561,24 @@ mod tests {
                 TranscriptionStatus::Completed => {
                     assert!(status_str.contains("completed") || status_str.contains("Completed"));
                 }
+                TranscriptionStatus::NeedsReview => {
+                    assert_eq!(status_str, "needs_review");
+                }
                 TranscriptionStatus::Failed => {
                     assert!(status_str.contains("failed") || status_str.contains("Failed"));
                 }
                 TranscriptionStatus::Cancelled => {
                     assert!(status_str.contains("cancelled") || status_str.contains("Cancelled"));
                 }
+                TranscriptionStatus::Skipped => {
+                    assert_eq!(status_str, "skipped");
+                }
+                TranscriptionStatus::AwaitingAudio => {
+                    assert_eq!(status_str, "awaiting_audio");
+                }
+                TranscriptionStatus::Deferred => {
+                    assert_eq!(status_str, "deferred");
+                }
             }
         }
     }
@@ -3568,6 +5614,7 @@ mod tests {
             audio_file_path: Some("path".repeat(100)),
             audio_size_bytes: Some(i64::MAX),
             audio_content_type: Some("audio/wav".to_string()),
+            audio_sha256: None,
             duration_seconds: Some(rust_decimal::Decimal::try_from(86400.0).unwrap()),
             transcription_text: Some("text".repeat(1000)),
             transcription_confidence: Some(rust_decimal::Decimal::try_from(1.0).unwrap()),
@@ -3583,6 +5630,8 @@ mod tests {
             )),
             upload_timestamp: chrono::Utc::now(),
             upload_api_key_id: Some("key".repeat(36)),
+            latitude: None,
+            longitude: None,
         };
 
         // Verify all fields are set as expected
@@ -3763,21 +5812,27 @@ mod tests {
     #[test]
     fn test_struct_parameter_validation() {
         // Test parameter struct validation with edge cases
-        let long_system_id = "x".repeat(100);
+        let long_system_id = sys_id(&"x".repeat(50));
         let extreme_filter = RadioCallFilter {
-            system_id: Some(long_system_id.as_str()),
-            talkgroup_id: Some(i32::MIN),
+            system_id: Some(&long_system_id),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(i32::MAX)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(chrono::DateTime::<chrono::Utc>::MIN_UTC),
             to_date: Some(chrono::DateTime::<chrono::Utc>::MAX_UTC),
             limit: i64::MAX,
-            offset: i64::MAX,
+            after: None,
         };
 
-        assert_eq!(extreme_filter.system_id.unwrap().len(), 100);
-        assert_eq!(extreme_filter.talkgroup_id, Some(i32::MIN));
+        assert_eq!(extreme_filter.system_id.unwrap().as_str().len(), 50);
+        assert_eq!(extreme_filter.talkgroup_id, Some(tg_id(i32::MAX)));
         assert_eq!(extreme_filter.limit, i64::MAX);
-        assert_eq!(extreme_filter.offset, i64::MAX);
+        assert!(extreme_filter.after.is_none());
 
         let long_user_agent = "A".repeat(1000);
         let long_api_key = "K".repeat(100);
@@ -3816,6 +5871,7 @@ mod tests {
             failed: i64::MAX / 4,
             processing: 1000,
             pending: 2000,
+            skipped: 0,
             avg_confidence: Some(1.0),
         };
 
@@ -3889,23 +5945,35 @@ mod tests {
     fn test_radio_call_filter_system_logic() {
         // Test RadioCallFilter system_id logic
         let filter_with_system = RadioCallFilter {
-            system_id: Some("police_dept"),
+            system_id: Some(&sys_id("police_dept")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 50,
-            offset: 0,
+            after: None,
         };
 
         let filter_without_system = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 50,
-            offset: 0,
+            after: None,
         };
 
         // Test filter logic branching
@@ -3914,7 +5982,7 @@ mod tests {
 
         // Test conditional logic that would be used in list_radio_calls_filtered
         if let Some(system) = filter_with_system.system_id {
-            assert_eq!(system, "police_dept");
+            assert_eq!(system.as_str(), "police_dept");
         }
 
         // Test the else branch
@@ -4062,6 +6130,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert_eq!(minimal_update.status, "processing");
         assert!(minimal_update.text.is_none());
@@ -4077,6 +6148,9 @@ mod tests {
             error: Some("Network timeout"),
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert_eq!(error_update.status, "failed");
         assert!(error_update.error.is_some());
@@ -4091,6 +6165,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         assert!(high_confidence.confidence.unwrap() > 0.99);
     }
@@ -4102,12 +6179,18 @@ mod tests {
         // Test empty filter
         let empty_filter = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 50,
-            offset: 0,
+            after: None,
         };
         assert!(empty_filter.system_id.is_none());
         assert!(empty_filter.talkgroup_id.is_none());
@@ -4116,20 +6199,26 @@ mod tests {
 
         // Test with all filters
         let full_filter = RadioCallFilter {
-            system_id: Some("test_system"),
-            talkgroup_id: Some(12345),
+            system_id: Some(&sys_id("test_system")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(12345)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(now - chrono::Duration::hours(24)),
             to_date: Some(now),
             limit: 100,
-            offset: 200,
+            after: None,
         };
-        assert_eq!(full_filter.system_id, Some("test_system"));
-        assert_eq!(full_filter.talkgroup_id, Some(12345));
+        assert_eq!(full_filter.system_id, Some(&sys_id("test_system")));
+        assert_eq!(full_filter.talkgroup_id, Some(tg_id(12345)));
         assert!(full_filter.from_date.is_some());
         assert!(full_filter.to_date.is_some());
         assert_eq!(full_filter.limit, 100);
-        assert_eq!(full_filter.offset, 200);
+        assert!(full_filter.after.is_none());
 
         // Test date range validation
         assert!(full_filter.from_date.unwrap() < full_filter.to_date.unwrap());
@@ -4193,6 +6282,7 @@ mod tests {
             failed: 0,
             processing: 0,
             pending: 0,
+            skipped: 0,
             avg_confidence: None,
         };
         assert_eq!(zero_stats.total, 0);
@@ -4205,6 +6295,7 @@ mod tests {
             failed: 100,
             processing: 50,
             pending: 50,
+            skipped: 0,
             avg_confidence: Some(0.85),
         };
         assert_eq!(normal_stats.total, 1000);
@@ -4224,6 +6315,7 @@ mod tests {
             failed: 2,
             processing: 1,
             pending: 0,
+            skipped: 0,
             avg_confidence: Some(1.0), // Perfect confidence
         };
         assert_eq!(edge_stats.total, i64::MAX);
@@ -4746,6 +6838,9 @@ mod tests {
             error: Some(""),
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         let debug_str = format!("{update_empty_text:?}");
         assert!(debug_str.contains("TranscriptionUpdate"));
@@ -4759,6 +6854,9 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         let debug_str_special = format!("{update_special_chars:?}");
         assert!(debug_str_special.contains("completed"));
@@ -4773,19 +6871,28 @@ mod tests {
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: None,
+            raw_text: None,
+            text_encrypted: None,
         };
         let debug_str_long = format!("{update_long_text:?}");
         assert!(debug_str_long.contains("completed"));
 
         // Test system_id with special characters
         let filter_special_system = RadioCallFilter {
-            system_id: Some("SYS-001_TEST.2024"),
+            system_id: Some(&sys_id("SYS-001_TEST.2024")),
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 5,
-            offset: 0,
+            after: None,
         };
         let debug_str_sys = format!("{filter_special_system:?}");
         assert!(debug_str_sys.contains("SYS-001_TEST.2024"));
@@ -4800,6 +6907,7 @@ mod tests {
             failed: i64::MAX / 4,
             processing: i64::MAX / 8,
             pending: i64::MAX / 8 - 1,
+            skipped: 0,
             avg_confidence: Some(1.0),
         };
         let debug_str_max = format!("{stats_max:?}");
@@ -4816,6 +6924,7 @@ mod tests {
             failed: 0,
             processing: 0,
             pending: 0,
+            skipped: 0,
             avg_confidence: Some(0.0),
         };
         if let Some(conf) = stats_min_conf.avg_confidence {
@@ -4831,6 +6940,7 @@ mod tests {
                 failed: 1,
                 processing: 1,
                 pending: 0,
+                skipped: 0,
                 avg_confidence: Some(conf_val),
             };
             if let Some(conf) = stats.avg_confidence {
@@ -4900,12 +7010,18 @@ mod tests {
         // Test RadioCallFilter with None system_id for coverage
         let filter_no_system = RadioCallFilter {
             system_id: None,
+            allowed_systems: None,
             talkgroup_id: None,
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: None,
             to_date: None,
             limit: 100,
-            offset: 0,
+            after: None,
         };
 
         // Test that filter correctly represents None case
@@ -4914,7 +7030,7 @@ mod tests {
         assert!(filter_no_system.from_date.is_none());
         assert!(filter_no_system.to_date.is_none());
         assert!(filter_no_system.limit == 100);
-        assert!(filter_no_system.offset == 0);
+        assert!(filter_no_system.after.is_none());
 
         // Test debug output for None fields
         let debug_str = format!("{filter_no_system:?}");
@@ -4996,7 +7112,10 @@ mod tests {
                 confidence,
                 error,
                 speaker_count: None,
+                language: None,
                 speaker_segments: None,
+                raw_text: None,
+                text_encrypted: None,
             };
 
             let debug_str = format!("{update:?}");
@@ -5017,38 +7136,50 @@ mod tests {
     fn test_radio_call_filter_boundary_conditions() {
         let now = chrono::Utc::now();
 
-        // Test with very large limits and offsets
+        // Test with very large limits
         let filter_large = RadioCallFilter {
-            system_id: Some("LARGE_SYS"),
-            talkgroup_id: Some(i32::MAX),
+            system_id: Some(&sys_id("LARGE_SYS")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(i32::MAX)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(now - chrono::Duration::days(365)),
             to_date: Some(now),
             limit: i64::MAX,
-            offset: i64::MAX,
+            after: None,
         };
 
         assert_eq!(filter_large.limit, i64::MAX);
-        assert_eq!(filter_large.offset, i64::MAX);
+        assert!(filter_large.after.is_none());
         if let Some(tg) = filter_large.talkgroup_id {
-            assert_eq!(tg, i32::MAX);
+            assert_eq!(tg.as_i32(), i32::MAX);
         }
 
         // Test with minimum values
         let filter_min = RadioCallFilter {
-            system_id: Some(""),
-            talkgroup_id: Some(i32::MIN),
+            system_id: Some(&sys_id("A")),
+            allowed_systems: None,
+            talkgroup_id: Some(tg_id(1)),
             transcription_status: None,
+            tag: None,
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
             from_date: Some(chrono::DateTime::<chrono::Utc>::MIN_UTC),
             to_date: Some(chrono::DateTime::<chrono::Utc>::MAX_UTC),
             limit: 1,
-            offset: 0,
+            after: None,
         };
 
         assert_eq!(filter_min.limit, 1);
-        assert_eq!(filter_min.offset, 0);
+        assert!(filter_min.after.is_none());
         if let Some(tg) = filter_min.talkgroup_id {
-            assert_eq!(tg, i32::MIN);
+            assert_eq!(tg.as_i32(), 1);
         }
 
         // Test debug formatting with extreme values
@@ -5109,6 +7240,7 @@ mod tests {
                 failed,
                 processing,
                 pending,
+                skipped: 0,
                 avg_confidence: Some(0.5),
             };
 
@@ -0,0 +1,176 @@
+//! Per-system storage quotas.
+//!
+//! Usage is the total size of the recordings a system's calls still hold.
+//! Purging to a quota removes the oldest recordings but keeps the calls and
+//! their transcripts: each batch clears the calls' recording columns and
+//! returns the old paths so the caller can remove the files. Archived calls
+//! and calls still waiting for transcription keep their audio.
+
+use crate::error::StorageError;
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for quota operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A recording detached from its call to bring a system under its quota.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DetachedRecording {
+    /// Call the recording belonged to.
+    pub id: Uuid,
+    /// Recording path.
+    pub audio_file_path: String,
+    /// Recording size in bytes, if known.
+    pub audio_size_bytes: Option<i64>,
+}
+
+// ---------------------------------------------------------------------------
+// Quota operations
+// ---------------------------------------------------------------------------
+
+/// Storage quota operations.
+#[derive(Debug)]
+pub struct QuotaQueries;
+
+impl QuotaQueries {
+    /// Bytes of recordings held by `system_id`'s calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn usage(pool: &PgPool, system_id: &SystemId) -> Result<u64> {
+        let used: i64 = sqlx::query_scalar(
+            r"
+            SELECT COALESCE(SUM(audio_size_bytes), 0)::bigint FROM radio_calls
+            WHERE system_id = $1 AND audio_file_path IS NOT NULL
+            ",
+        )
+        .bind(system_id.as_str())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(u64::try_from(used).unwrap_or(0))
+    }
+
+    /// Detach up to `limit` of `system_id`'s recordings, oldest call first.
+    ///
+    /// The calls are kept without audio; their recording path, size, and
+    /// hash are cleared so usage and duplicate detection no longer count
+    /// them. Archived calls and calls whose transcription is pending or in
+    /// progress are skipped, as are rows locked by another transaction. An
+    /// empty result means nothing is left to detach.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn detach_oldest(
+        pool: &PgPool,
+        system_id: &SystemId,
+        limit: i64,
+    ) -> Result<Vec<DetachedRecording>> {
+        let detached = sqlx::query_as::<_, DetachedRecording>(
+            r"
+            WITH doomed AS (
+                SELECT id, audio_file_path, audio_size_bytes FROM radio_calls
+                WHERE system_id = $1
+                  AND audio_file_path IS NOT NULL
+                  AND archived_at IS NULL
+                  AND COALESCE(transcription_status, '') NOT IN ('pending', 'processing')
+                ORDER BY call_timestamp
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE radio_calls
+            SET audio_file_path = NULL, audio_size_bytes = NULL, audio_sha256 = NULL
+            FROM doomed
+            WHERE radio_calls.id = doomed.id
+            RETURNING doomed.id, doomed.audio_file_path, doomed.audio_size_bytes
+            ",
+        )
+        .bind(system_id.as_str())
+        .bind(limit.max(1))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(detached)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+    use chrono::Utc;
+
+    fn call(system_id: &SystemId, age_days: i64, status: &str) -> RadioCallDb {
+        let created_at = Utc::now() - chrono::Duration::days(age_days);
+        RadioCallDb {
+            audio_file_path: Some(format!("/tmp/{}.mp3", Uuid::new_v4())),
+            audio_size_bytes: Some(1024),
+            transcription_status: Some(status.to_string()),
+            ..test_call_at(system_id, created_at)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detach_oldest() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id =
+            SystemId::new(format!("quota_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let oldest = call(&system_id, 3, "completed");
+        let pending = call(&system_id, 4, "pending");
+        let newer = call(&system_id, 1, "completed");
+        for call in [&oldest, &pending, &newer] {
+            RadioCallQueries::insert(&pool, call).await.unwrap();
+        }
+        assert_eq!(QuotaQueries::usage(&pool, &system_id).await.unwrap(), 3072);
+
+        let detached = QuotaQueries::detach_oldest(&pool, &system_id, 1)
+            .await
+            .unwrap();
+        assert_eq!(detached.len(), 1);
+        assert_eq!(detached[0].id, oldest.id);
+        assert_eq!(
+            Some(&detached[0].audio_file_path),
+            oldest.audio_file_path.as_ref()
+        );
+        assert_eq!(QuotaQueries::usage(&pool, &system_id).await.unwrap(), 2048);
+
+        // The call is kept without its recording
+        let kept = RadioCallQueries::find_by_id(&pool, oldest.id)
+            .await
+            .unwrap();
+        assert_eq!(kept.audio_file_path, None);
+
+        // Pending calls keep their audio
+        let rest = QuotaQueries::detach_oldest(&pool, &system_id, 10)
+            .await
+            .unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].id, newer.id);
+    }
+}
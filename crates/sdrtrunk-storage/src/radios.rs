@@ -0,0 +1,416 @@
+//! Radio (unit) activity and labels.
+//!
+//! Each radio ID heard on a system has a row in `radios` with when it was
+//! first and last heard, how many calls it made, and the talker alias it last
+//! sent. Uploads record every call that carries a source radio, and analysts
+//! can label radios (e.g. `Engine 5 portable`). A radio's calls and the
+//! talkgroups it used are read from `radio_calls`.
+
+use crate::{error::StorageError, models::RadioCallDb, queries::CallCursor};
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{RadioId, SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for radio operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `radios` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Radio {
+    /// System the radio was heard on.
+    pub system_id: SystemId,
+    /// Radio ID.
+    pub radio_id: RadioId,
+    /// Name given by an analyst.
+    pub label: Option<String>,
+    /// Talker alias the radio last sent over the air.
+    pub last_alias: Option<String>,
+    /// Timestamp of the radio's earliest call.
+    pub first_seen: DateTime<Utc>,
+    /// Timestamp of the radio's latest call.
+    pub last_seen: DateTime<Utc>,
+    /// Calls recorded from the radio.
+    pub call_count: i64,
+    /// When the radio was first recorded.
+    pub created_at: DateTime<Utc>,
+    /// When the radio's row last changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A talkgroup a radio has called on.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RadioTalkgroup {
+    /// Talkgroup ID.
+    pub talkgroup_id: TalkgroupId,
+    /// Talkgroup name on the radio's latest call there.
+    pub talkgroup_label: Option<String>,
+    /// Calls the radio made on the talkgroup.
+    pub call_count: i64,
+    /// Timestamp of the radio's latest call on the talkgroup.
+    pub last_seen: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Radio operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for radio activity.
+#[derive(Debug)]
+pub struct RadioQueries;
+
+impl RadioQueries {
+    /// Count a call from a radio, creating the radio on its first call.
+    ///
+    /// Calls recorded out of order only move `first_seen` earlier and
+    /// `last_seen` later; the alias is kept from the latest call sending one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn record_call(
+        pool: &PgPool,
+        system_id: &SystemId,
+        radio_id: RadioId,
+        alias: Option<&str>,
+        call_timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            INSERT INTO radios (system_id, radio_id, last_alias, first_seen, last_seen, call_count)
+            VALUES ($1, $2, $3, $4, $4, 1)
+            ON CONFLICT (system_id, radio_id) DO UPDATE SET
+                last_alias = COALESCE(
+                    CASE WHEN EXCLUDED.last_seen >= radios.last_seen THEN EXCLUDED.last_alias END,
+                    radios.last_alias
+                ),
+                first_seen = LEAST(radios.first_seen, EXCLUDED.first_seen),
+                last_seen = GREATEST(radios.last_seen, EXCLUDED.last_seen),
+                call_count = radios.call_count + 1,
+                updated_at = NOW()
+            ",
+        )
+        .bind(system_id)
+        .bind(radio_id)
+        .bind(alias)
+        .bind(call_timestamp)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Radios heard on a system, most recently heard first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(pool: &PgPool, system_id: &SystemId, limit: i64) -> Result<Vec<Radio>> {
+        let radios = sqlx::query_as::<_, Radio>(
+            r"
+            SELECT * FROM radios
+            WHERE system_id = $1
+            ORDER BY last_seen DESC, radio_id
+            LIMIT $2
+            ",
+        )
+        .bind(system_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(radios)
+    }
+
+    /// A radio heard on a system.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn get(
+        pool: &PgPool,
+        system_id: &SystemId,
+        radio_id: RadioId,
+    ) -> Result<Option<Radio>> {
+        let radio = sqlx::query_as::<_, Radio>(
+            "SELECT * FROM radios WHERE system_id = $1 AND radio_id = $2",
+        )
+        .bind(system_id)
+        .bind(radio_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(radio)
+    }
+
+    /// Name a radio, or clear its name with `None`.
+    ///
+    /// Returns `None` if the radio has never been heard.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn set_label(
+        pool: &PgPool,
+        system_id: &SystemId,
+        radio_id: RadioId,
+        label: Option<&str>,
+    ) -> Result<Option<Radio>> {
+        let radio = sqlx::query_as::<_, Radio>(
+            r"
+            UPDATE radios SET label = $3, updated_at = NOW()
+            WHERE system_id = $1 AND radio_id = $2
+            RETURNING *
+            ",
+        )
+        .bind(system_id)
+        .bind(radio_id)
+        .bind(label)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(radio)
+    }
+
+    /// Talkgroups a radio has called on, most calls first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn talkgroups(
+        pool: &PgPool,
+        system_id: &SystemId,
+        radio_id: RadioId,
+    ) -> Result<Vec<RadioTalkgroup>> {
+        let talkgroups = sqlx::query_as::<_, RadioTalkgroup>(
+            r"
+            SELECT
+                talkgroup_id,
+                (ARRAY_AGG(talkgroup_label ORDER BY call_timestamp DESC)
+                    FILTER (WHERE talkgroup_label IS NOT NULL))[1] AS talkgroup_label,
+                COUNT(*) AS call_count,
+                MAX(call_timestamp) AS last_seen
+            FROM radio_calls
+            WHERE system_id = $1 AND source_radio_id = $2 AND talkgroup_id IS NOT NULL
+            GROUP BY talkgroup_id
+            ORDER BY call_count DESC, talkgroup_id
+            ",
+        )
+        .bind(system_id)
+        .bind(radio_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(talkgroups)
+    }
+
+    /// A radio's calls across all talkgroups, newest first, resuming after
+    /// `after`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn calls(
+        pool: &PgPool,
+        system_id: &SystemId,
+        radio_id: RadioId,
+        limit: i64,
+        after: Option<CallCursor>,
+    ) -> Result<Vec<RadioCallDb>> {
+        let calls = sqlx::query_as::<_, RadioCallDb>(
+            r"
+            SELECT * FROM radio_calls
+            WHERE system_id = $1 AND source_radio_id = $2
+              AND ($4::TIMESTAMPTZ IS NULL
+                   OR (call_timestamp <= $4 AND (call_timestamp, id) < ($4, $5)))
+            ORDER BY call_timestamp DESC, id DESC
+            LIMIT $3
+            ",
+        )
+        .bind(system_id)
+        .bind(radio_id)
+        .bind(limit)
+        .bind(after.map(|cursor| cursor.call_timestamp))
+        .bind(after.map(|cursor| cursor.id))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(calls)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    fn call(
+        system_id: &SystemId,
+        radio_id: RadioId,
+        talkgroup: i32,
+        at: DateTime<Utc>,
+    ) -> RadioCallDb {
+        RadioCallDb {
+            talkgroup_id: Some(TalkgroupId::new(talkgroup).unwrap()),
+            talkgroup_label: Some(format!("TG {talkgroup}")),
+            source_radio_id: Some(radio_id),
+            transcription_status: None,
+            ..test_call_at(system_id, at)
+        }
+    }
+
+    fn test_system() -> SystemId {
+        SystemId::new(format!("radio_{}", &Uuid::new_v4().to_string()[..8])).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_call_and_label() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = test_system();
+        let radio_id = RadioId::new(1_234_567).unwrap();
+        let now = Utc::now();
+        assert!(
+            RadioQueries::get(&pool, &system_id, radio_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        RadioQueries::record_call(&pool, &system_id, radio_id, Some("E5"), now)
+            .await
+            .unwrap();
+        // An older call arriving late keeps the newer alias
+        RadioQueries::record_call(
+            &pool,
+            &system_id,
+            radio_id,
+            Some("OLD"),
+            now - Duration::hours(1),
+        )
+        .await
+        .unwrap();
+        RadioQueries::record_call(
+            &pool,
+            &system_id,
+            radio_id,
+            None,
+            now + Duration::minutes(1),
+        )
+        .await
+        .unwrap();
+
+        let radio = RadioQueries::get(&pool, &system_id, radio_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(radio.call_count, 3);
+        assert_eq!(radio.last_alias.as_deref(), Some("E5"));
+        assert_eq!(
+            radio.first_seen.timestamp(),
+            (now - Duration::hours(1)).timestamp()
+        );
+        assert_eq!(
+            radio.last_seen.timestamp(),
+            (now + Duration::minutes(1)).timestamp()
+        );
+        assert!(radio.label.is_none());
+
+        let labelled =
+            RadioQueries::set_label(&pool, &system_id, radio_id, Some("Engine 5 portable"))
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(labelled.label.as_deref(), Some("Engine 5 portable"));
+        let cleared = RadioQueries::set_label(&pool, &system_id, radio_id, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(cleared.label.is_none());
+
+        let unknown = RadioId::new(7).unwrap();
+        assert!(
+            RadioQueries::set_label(&pool, &system_id, unknown, Some("Ghost"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let other = RadioId::new(42).unwrap();
+        RadioQueries::record_call(&pool, &system_id, other, None, now + Duration::hours(1))
+            .await
+            .unwrap();
+        let radios = RadioQueries::list(&pool, &system_id, 10).await.unwrap();
+        assert_eq!(
+            radios.iter().map(|r| r.radio_id).collect::<Vec<_>>(),
+            vec![other, radio_id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_calls_and_talkgroups() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = test_system();
+        let radio_id = RadioId::new(5001).unwrap();
+        let start = Utc::now() - Duration::hours(1);
+        for (minute, talkgroup) in [(0, 100), (1, 200), (2, 100)] {
+            let call = call(
+                &system_id,
+                radio_id,
+                talkgroup,
+                start + Duration::minutes(minute),
+            );
+            RadioCallQueries::insert(&pool, &call).await.unwrap();
+        }
+        let stranger = call(&system_id, RadioId::new(5002).unwrap(), 100, start);
+        RadioCallQueries::insert(&pool, &stranger).await.unwrap();
+
+        let talkgroups = RadioQueries::talkgroups(&pool, &system_id, radio_id)
+            .await
+            .unwrap();
+        assert_eq!(talkgroups.len(), 2);
+        assert_eq!(talkgroups[0].talkgroup_id.as_i32(), 100);
+        assert_eq!(talkgroups[0].call_count, 2);
+        assert_eq!(talkgroups[0].talkgroup_label.as_deref(), Some("TG 100"));
+        assert_eq!(talkgroups[1].talkgroup_id.as_i32(), 200);
+
+        let first_page = RadioQueries::calls(&pool, &system_id, radio_id, 2, None)
+            .await
+            .unwrap();
+        assert_eq!(first_page.len(), 2);
+        assert!(first_page[0].call_timestamp > first_page[1].call_timestamp);
+        let after = CallCursor::after(&first_page[1]);
+        let second_page = RadioQueries::calls(&pool, &system_id, radio_id, 2, Some(after))
+            .await
+            .unwrap();
+        assert_eq!(second_page.len(), 1);
+        assert_eq!(second_page[0].talkgroup_id.unwrap().as_i32(), 100);
+        assert_eq!(second_page[0].source_radio_id, Some(radio_id));
+    }
+}
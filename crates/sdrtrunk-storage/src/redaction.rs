@@ -0,0 +1,167 @@
+//! Anonymizing transcripts before they are stored.
+//!
+//! A [`TranscriptRedactor`] scrubs personal details from transcript text with
+//! the rules in [`AnonymizationConfig`] and, when a key is configured, seals
+//! the original text for `radio_calls.transcription_text_raw` so an
+//! administrator can still recover it.
+
+use crate::crypto::FieldCipher;
+use crate::error::{Result, StorageError};
+use sdrtrunk_protocol::anonymize::Anonymizer;
+use sdrtrunk_protocol::config::AnonymizationConfig;
+use std::borrow::Cow;
+
+/// A transcript ready to store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactedText {
+    /// Text with personal details replaced by placeholders
+    pub text: String,
+    /// Encrypted original, when anything was scrubbed and a key is configured
+    pub raw: Option<Vec<u8>>,
+}
+
+/// Scrubs transcripts and encrypts their originals.
+#[derive(Debug)]
+pub struct TranscriptRedactor {
+    anonymizer: Anonymizer,
+    cipher: Option<FieldCipher>,
+}
+
+impl TranscriptRedactor {
+    /// Redactor for `config`, or `None` when anonymization is off.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Configuration`] if a name or scrub pattern is
+    /// invalid, and [`StorageError::Encryption`] if the raw text key is not a
+    /// base64-encoded 32-byte key.
+    pub fn from_config(config: &AnonymizationConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let anonymizer = Anonymizer::new(config)
+            .map_err(|e| StorageError::Configuration(format!("anonymization: {e}")))?;
+        let cipher = config
+            .raw_text_key
+            .as_deref()
+            .map(FieldCipher::from_base64_key)
+            .transpose()?;
+        Ok(Some(Self { anonymizer, cipher }))
+    }
+
+    /// `text` with personal details replaced.
+    #[must_use]
+    pub fn scrub<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        self.anonymizer.scrub(text)
+    }
+
+    /// Scrub `text`, sealing the original if anything was replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if the original cannot be sealed.
+    pub fn redact(&self, text: &str) -> Result<RedactedText> {
+        match self.scrub(text) {
+            Cow::Borrowed(_) => Ok(RedactedText {
+                text: text.to_string(),
+                raw: None,
+            }),
+            Cow::Owned(scrubbed) => Ok(RedactedText {
+                text: scrubbed,
+                raw: self
+                    .cipher
+                    .as_ref()
+                    .map(|cipher| cipher.encrypt(text.as_bytes()))
+                    .transpose()?,
+            }),
+        }
+    }
+
+    /// Decrypt an original sealed by [`redact`](Self::redact).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if no key is configured or the
+    /// value cannot be opened with it.
+    pub fn reveal(&self, raw: &[u8]) -> Result<String> {
+        let cipher = self
+            .cipher
+            .as_ref()
+            .ok_or_else(|| StorageError::Encryption("no raw text key is configured".to_string()))?;
+        String::from_utf8(cipher.decrypt(raw)?)
+            .map_err(|e| StorageError::Encryption(format!("original is not UTF-8: {e}")))
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use sdrtrunk_protocol::config::PiiKind;
+
+    fn config(key: bool) -> AnonymizationConfig {
+        AnonymizationConfig {
+            enabled: true,
+            scrub: vec![PiiKind::Names, PiiKind::PhoneNumbers],
+            raw_text_key: key.then(|| STANDARD.encode([9u8; 32])),
+            ..AnonymizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_builds_nothing() {
+        let config = AnonymizationConfig::default();
+        assert!(TranscriptRedactor::from_config(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_redact_seals_original() {
+        let redactor = TranscriptRedactor::from_config(&config(true))
+            .unwrap()
+            .unwrap();
+        let redacted = redactor.redact("Deputy Harris call 919-555-0134").unwrap();
+        assert_eq!(redacted.text, "Deputy [NAME] call [PHONE]");
+        let raw = redacted.raw.unwrap();
+        assert_eq!(
+            redactor.reveal(&raw).unwrap(),
+            "Deputy Harris call 919-555-0134"
+        );
+
+        // Nothing scrubbed, nothing to keep
+        let clean = redactor.redact("engine 5 on scene").unwrap();
+        assert_eq!(clean.text, "engine 5 on scene");
+        assert!(clean.raw.is_none());
+    }
+
+    #[test]
+    fn test_redact_without_key_drops_original() {
+        let redactor = TranscriptRedactor::from_config(&config(false))
+            .unwrap()
+            .unwrap();
+        let redacted = redactor.redact("Deputy Harris responding").unwrap();
+        assert_eq!(redacted.text, "Deputy [NAME] responding");
+        assert!(redacted.raw.is_none());
+        assert!(redactor.reveal(b"anything").is_err());
+    }
+
+    #[test]
+    fn test_rejects_bad_key() {
+        let config = AnonymizationConfig {
+            raw_text_key: Some("short".to_string()),
+            ..config(false)
+        };
+        assert!(matches!(
+            TranscriptRedactor::from_config(&config),
+            Err(StorageError::Encryption(_))
+        ));
+    }
+}
@@ -0,0 +1,259 @@
+//! Digest report figures.
+//!
+//! Summarises each system's calls over a period for the emailed digests: call
+//! volume, busiest talkgroups, alert hits, and how many transcriptions
+//! completed or failed. Calls are counted by `call_timestamp`, alerts by when
+//! they were raised.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for report operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A system's activity over a report period.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SystemDigest {
+    /// System ID.
+    pub system_id: SystemId,
+    /// Latest system display name seen in the period.
+    pub system_label: Option<String>,
+    /// Calls recorded.
+    pub calls: i64,
+    /// Calls whose transcription completed.
+    pub transcribed: i64,
+    /// Calls whose transcription failed.
+    pub failed: i64,
+    /// Alerts raised.
+    pub alert_hits: i64,
+    /// Busiest talkgroups, most calls first.
+    #[sqlx(skip)]
+    pub top_talkgroups: Vec<TalkgroupCount>,
+}
+
+impl SystemDigest {
+    /// Share of finished transcriptions that failed, from 0 to 1.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn failure_rate(&self) -> f64 {
+        let finished = self.transcribed + self.failed;
+        if finished == 0 {
+            0.0
+        } else {
+            self.failed as f64 / finished as f64
+        }
+    }
+}
+
+/// Calls on one talkgroup over a report period.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TalkgroupCount {
+    /// System the talkgroup belongs to.
+    pub system_id: SystemId,
+    /// Talkgroup ID.
+    pub talkgroup_id: TalkgroupId,
+    /// Latest talkgroup display name seen in the period.
+    pub talkgroup_label: Option<String>,
+    /// Calls recorded.
+    pub calls: i64,
+}
+
+// ---------------------------------------------------------------------------
+// Report operations
+// ---------------------------------------------------------------------------
+
+/// Digest report queries.
+#[derive(Debug)]
+pub struct ReportQueries;
+
+impl ReportQueries {
+    /// Digests of every system with calls between `from` and `to`, by system
+    /// ID, each listing up to `top_talkgroups` talkgroups.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn system_digests(
+        pool: &PgPool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        top_talkgroups: i64,
+    ) -> Result<Vec<SystemDigest>> {
+        let mut digests = sqlx::query_as::<_, SystemDigest>(
+            r"
+            WITH calls AS (
+                SELECT system_id,
+                       (ARRAY_AGG(system_label ORDER BY call_timestamp DESC)
+                           FILTER (WHERE system_label IS NOT NULL))[1] AS system_label,
+                       COUNT(*) AS calls,
+                       COUNT(*) FILTER (
+                           WHERE transcription_status IN ('completed', 'needs_review')
+                       ) AS transcribed,
+                       COUNT(*) FILTER (WHERE transcription_status = 'failed') AS failed
+                FROM radio_calls
+                WHERE call_timestamp >= $1 AND call_timestamp < $2
+                GROUP BY system_id
+            ),
+            hits AS (
+                SELECT system_id, COUNT(*) AS alert_hits
+                FROM alerts
+                WHERE created_at >= $1 AND created_at < $2
+                GROUP BY system_id
+            )
+            SELECT calls.system_id, calls.system_label, calls.calls, calls.transcribed,
+                   calls.failed, COALESCE(hits.alert_hits, 0) AS alert_hits
+            FROM calls
+            LEFT JOIN hits ON hits.system_id = calls.system_id
+            ORDER BY calls.system_id
+            ",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await?;
+
+        let talkgroups = sqlx::query_as::<_, TalkgroupCount>(
+            r"
+            SELECT system_id, talkgroup_id, talkgroup_label, calls
+            FROM (
+                SELECT system_id, talkgroup_id,
+                       (ARRAY_AGG(talkgroup_label ORDER BY call_timestamp DESC)
+                           FILTER (WHERE talkgroup_label IS NOT NULL))[1] AS talkgroup_label,
+                       COUNT(*) AS calls,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY system_id ORDER BY COUNT(*) DESC, talkgroup_id
+                       ) AS rank
+                FROM radio_calls
+                WHERE call_timestamp >= $1 AND call_timestamp < $2
+                  AND talkgroup_id IS NOT NULL
+                GROUP BY system_id, talkgroup_id
+            ) ranked
+            WHERE rank <= $3
+            ORDER BY system_id, rank
+            ",
+        )
+        .bind(from)
+        .bind(to)
+        .bind(top_talkgroups.max(0))
+        .fetch_all(pool)
+        .await?;
+
+        for talkgroup in talkgroups {
+            if let Some(digest) = digests
+                .iter_mut()
+                .find(|digest| digest.system_id == talkgroup.system_id)
+            {
+                digest.top_talkgroups.push(talkgroup);
+            }
+        }
+
+        Ok(digests)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+    use uuid::Uuid;
+
+    fn call(system_id: &SystemId, at: DateTime<Utc>, talkgroup: i32, status: &str) -> RadioCallDb {
+        RadioCallDb {
+            system_label: Some("Metro".to_string()),
+            talkgroup_id: Some(TalkgroupId::new(talkgroup).unwrap()),
+            talkgroup_label: Some(format!("TG {talkgroup}")),
+            transcription_status: Some(status.to_string()),
+            ..test_call_at(system_id, at)
+        }
+    }
+
+    fn digest(transcribed: i64, failed: i64) -> SystemDigest {
+        SystemDigest {
+            system_id: SystemId::new("metro").unwrap(),
+            system_label: None,
+            calls: transcribed + failed,
+            transcribed,
+            failed,
+            alert_hits: 0,
+            top_talkgroups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_failure_rate() {
+        assert!(digest(0, 0).failure_rate().abs() < f64::EPSILON);
+        assert!((digest(3, 1).failure_rate() - 0.25).abs() < f64::EPSILON);
+        assert!((digest(0, 2).failure_rate() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_system_digests() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("rpt_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        // A period in the past that no other test writes to
+        let from: DateTime<Utc> = "2001-02-03T00:00:00Z".parse().unwrap();
+        let to = from + chrono::Duration::days(1);
+        let at = from + chrono::Duration::hours(1);
+        for (talkgroup, status) in [
+            (100, "completed"),
+            (100, "completed"),
+            (100, "failed"),
+            (200, "completed"),
+            (300, "pending"),
+        ] {
+            RadioCallQueries::insert(&pool, &call(&system_id, at, talkgroup, status))
+                .await
+                .unwrap();
+        }
+        // Outside the period
+        RadioCallQueries::insert(&pool, &call(&system_id, to, 200, "completed"))
+            .await
+            .unwrap();
+
+        let digests = ReportQueries::system_digests(&pool, from, to, 2)
+            .await
+            .unwrap();
+        let digest = digests
+            .iter()
+            .find(|digest| digest.system_id == system_id)
+            .unwrap();
+        assert_eq!(digest.system_label.as_deref(), Some("Metro"));
+        assert_eq!((digest.calls, digest.transcribed, digest.failed), (5, 3, 1));
+        assert_eq!(digest.alert_hits, 0);
+        let talkgroups: Vec<(i32, i64)> = digest
+            .top_talkgroups
+            .iter()
+            .map(|tg| (tg.talkgroup_id.as_i32(), tg.calls))
+            .collect();
+        assert_eq!(talkgroups, [(100, 3), (200, 1)]);
+        assert_eq!(
+            digest.top_talkgroups[0].talkgroup_label.as_deref(),
+            Some("TG 100")
+        );
+    }
+}
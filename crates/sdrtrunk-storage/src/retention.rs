@@ -0,0 +1,178 @@
+//! Retention purges.
+//!
+//! Deletes calls and upload logs past their retention period. Calls are
+//! removed in bounded batches together with their `transcription_jobs` rows
+//! (which reference `radio_calls` without `ON DELETE CASCADE`), and each batch
+//! returns the deleted calls' recording paths so the caller can remove the
+//! files from disk. Archived calls are exempt.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for retention operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A call removed by a retention purge.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PurgedCall {
+    /// Deleted call ID.
+    pub id: Uuid,
+    /// System the call belonged to.
+    pub system_id: SystemId,
+    /// Recording path, if the call had one.
+    pub audio_file_path: Option<String>,
+    /// Recording size in bytes, if known.
+    pub audio_size_bytes: Option<i64>,
+}
+
+// ---------------------------------------------------------------------------
+// Purge operations
+// ---------------------------------------------------------------------------
+
+/// Retention purge operations.
+#[derive(Debug)]
+pub struct RetentionQueries;
+
+impl RetentionQueries {
+    /// Delete up to `limit` calls created before `older_than`, oldest first.
+    /// Archived calls are kept.
+    ///
+    /// Transcription jobs for the deleted calls are removed in the same
+    /// statement. Rows locked by another transaction are skipped and picked
+    /// up by a later batch. An empty result means nothing is left to purge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn purge_calls_batch(
+        pool: &PgPool,
+        older_than: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<PurgedCall>> {
+        let purged = sqlx::query_as::<_, PurgedCall>(
+            r"
+            WITH doomed AS (
+                SELECT id FROM radio_calls
+                WHERE created_at < $1 AND archived_at IS NULL
+                ORDER BY created_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            ),
+            jobs AS (
+                DELETE FROM transcription_jobs
+                WHERE call_id IN (SELECT id FROM doomed)
+            )
+            DELETE FROM radio_calls
+            WHERE id IN (SELECT id FROM doomed)
+            RETURNING id, system_id, audio_file_path, audio_size_bytes
+            ",
+        )
+        .bind(older_than)
+        .bind(limit.max(1))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(purged)
+    }
+
+    /// Delete upload logs recorded before `older_than`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn purge_upload_logs(pool: &PgPool, older_than: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM upload_logs WHERE timestamp < $1")
+            .bind(older_than)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::jobs::{EnqueueParams, JobQueue};
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call_at};
+
+    fn call(system_id: &SystemId, age_days: i64) -> RadioCallDb {
+        let created_at = Utc::now() - chrono::Duration::days(age_days);
+        RadioCallDb {
+            audio_file_path: Some(format!("/tmp/{}.mp3", Uuid::new_v4())),
+            audio_size_bytes: Some(1024),
+            ..test_call_at(system_id, created_at)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purge_calls_batch() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id =
+            SystemId::new(format!("retention_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let old = call(&system_id, 40);
+        let recent = call(&system_id, 1);
+        let old_id = RadioCallQueries::insert(&pool, &old).await.unwrap();
+        let recent_id = RadioCallQueries::insert(&pool, &recent).await.unwrap();
+
+        // A job referencing the old call must not block its deletion
+        JobQueue::enqueue(
+            &pool,
+            &EnqueueParams {
+                call_id: old_id,
+                audio_path: old.audio_file_path.clone(),
+                audio_data: None,
+                priority: 0,
+                options: serde_json::json!({}),
+                timeout_seconds: 300,
+            },
+        )
+        .await
+        .unwrap();
+
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        let mut purged = Vec::new();
+        loop {
+            let batch = RetentionQueries::purge_calls_batch(&pool, cutoff, 100)
+                .await
+                .unwrap();
+            if batch.is_empty() {
+                break;
+            }
+            purged.extend(batch);
+        }
+
+        let ours = purged.iter().find(|p| p.id == old_id).unwrap();
+        assert_eq!(ours.system_id, system_id);
+        assert_eq!(ours.audio_file_path, old.audio_file_path);
+        assert!(purged.iter().all(|p| p.id != recent_id));
+        assert!(RadioCallQueries::find_by_id(&pool, old_id).await.is_err());
+        assert!(RadioCallQueries::find_by_id(&pool, recent_id).await.is_ok());
+    }
+}
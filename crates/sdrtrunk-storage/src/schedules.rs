@@ -0,0 +1,199 @@
+//! Scheduled background job state.
+//!
+//! Each periodic job (retention purge, statistics rollup, analyze) keeps one
+//! row in `scheduled_jobs` with its schedule, when it runs next, and how its
+//! last run went. The scheduler updates the row around every run; admins read
+//! it back to check that jobs are running.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for scheduled job operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A periodic job and its most recent run.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Job name.
+    pub name: String,
+    /// Cron expression or interval the job runs on.
+    pub schedule: String,
+    /// When the job runs next (`None` when no run is upcoming).
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// When the last run started.
+    pub last_started_at: Option<DateTime<Utc>>,
+    /// When the last run finished.
+    pub last_finished_at: Option<DateTime<Utc>>,
+    /// `running`, `succeeded`, or `failed`.
+    pub last_status: Option<String>,
+    /// Summary or error from the last run.
+    pub last_message: Option<String>,
+    /// Finished runs.
+    pub run_count: i64,
+    /// Finished runs that failed.
+    pub failure_count: i64,
+    /// Last time the row changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Scheduled job operations
+// ---------------------------------------------------------------------------
+
+/// Scheduled job operations.
+#[derive(Debug)]
+pub struct ScheduleQueries;
+
+impl ScheduleQueries {
+    /// Record a job's schedule and when it runs next, adding the job if new.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database operation fails.
+    pub async fn schedule(
+        pool: &PgPool,
+        name: &str,
+        schedule: &str,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            INSERT INTO scheduled_jobs (name, schedule, next_run_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE
+            SET schedule = EXCLUDED.schedule,
+                next_run_at = EXCLUDED.next_run_at,
+                updated_at = NOW()
+            ",
+        )
+        .bind(name)
+        .bind(schedule)
+        .bind(next_run_at)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a job as running.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database operation fails.
+    pub async fn start(pool: &PgPool, name: &str) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE scheduled_jobs
+            SET last_started_at = NOW(),
+                last_status = 'running',
+                next_run_at = NULL,
+                updated_at = NOW()
+            WHERE name = $1
+            ",
+        )
+        .bind(name)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of a job's run.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database operation fails.
+    pub async fn finish(pool: &PgPool, name: &str, succeeded: bool, message: &str) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE scheduled_jobs
+            SET last_finished_at = NOW(),
+                last_status = CASE WHEN $2 THEN 'succeeded' ELSE 'failed' END,
+                last_message = $3,
+                run_count = run_count + 1,
+                failure_count = failure_count + CASE WHEN $2 THEN 0 ELSE 1 END,
+                updated_at = NOW()
+            WHERE name = $1
+            ",
+        )
+        .bind(name)
+        .bind(succeeded)
+        .bind(message)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// List jobs by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database operation fails.
+    pub async fn list(pool: &PgPool) -> Result<Vec<ScheduledJob>> {
+        Ok(
+            sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs ORDER BY name")
+                .fetch_all(pool)
+                .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_scheduled_job_runs() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let name = format!("job_{}", &Uuid::new_v4().to_string()[..8]);
+        let next = Utc::now() + chrono::Duration::hours(1);
+        ScheduleQueries::schedule(&pool, &name, "0 * * * *", Some(next))
+            .await
+            .unwrap();
+
+        ScheduleQueries::start(&pool, &name).await.unwrap();
+        ScheduleQueries::finish(&pool, &name, false, "disk full")
+            .await
+            .unwrap();
+        ScheduleQueries::schedule(&pool, &name, "0 * * * *", Some(next))
+            .await
+            .unwrap();
+        ScheduleQueries::start(&pool, &name).await.unwrap();
+        ScheduleQueries::finish(&pool, &name, true, "purged 3 calls")
+            .await
+            .unwrap();
+
+        let jobs = ScheduleQueries::list(&pool).await.unwrap();
+        let job = jobs.iter().find(|job| job.name == name).unwrap();
+        assert_eq!(job.schedule, "0 * * * *");
+        assert_eq!(job.last_status.as_deref(), Some("succeeded"));
+        assert_eq!(job.last_message.as_deref(), Some("purged 3 calls"));
+        assert_eq!((job.run_count, job.failure_count), (2, 1));
+        assert!(job.last_finished_at >= job.last_started_at);
+        assert_eq!(job.next_run_at, None);
+
+        sqlx::query("DELETE FROM scheduled_jobs WHERE name = $1")
+            .bind(&name)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
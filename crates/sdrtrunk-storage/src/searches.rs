@@ -0,0 +1,318 @@
+//! Saved call searches.
+//!
+//! A saved search is a named set of call list filters (system, talkgroup,
+//! transcript keyword, transcription language, date range) kept per owner so the web calls page can
+//! reload it. The owner is `user:<id>` or `api_key:<id>`; names are unique per
+//! owner, and every operation is scoped to the owner so one user never sees
+//! another's searches.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for saved search operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Unique constraint on `(owner, name)`.
+const NAME_CONSTRAINT: &str = "saved_searches_owner_name_key";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `saved_searches` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SavedSearch {
+    /// Search ID.
+    pub id: Uuid,
+    /// Owner, `user:<id>` or `api_key:<id>`.
+    pub owner: String,
+    /// Name, unique per owner.
+    pub name: String,
+    /// System filter.
+    pub system_id: Option<String>,
+    /// Talkgroup filter.
+    pub talkgroup_id: Option<i32>,
+    /// Transcript keyword filter.
+    pub keyword: Option<String>,
+    /// Transcription language filter.
+    pub language: Option<String>,
+    /// Earliest call time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Latest call time.
+    pub to_date: Option<DateTime<Utc>>,
+    /// When the search was saved.
+    pub created_at: DateTime<Utc>,
+    /// When the search was last changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields for saving or replacing a search.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NewSavedSearch {
+    /// Name, unique per owner.
+    pub name: String,
+    /// System filter.
+    pub system_id: Option<String>,
+    /// Talkgroup filter.
+    pub talkgroup_id: Option<i32>,
+    /// Transcript keyword filter.
+    pub keyword: Option<String>,
+    /// Transcription language filter.
+    pub language: Option<String>,
+    /// Earliest call time.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Latest call time.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// Map a duplicate `(owner, name)` to [`StorageError::ConstraintViolation`].
+fn name_taken(err: sqlx::Error) -> StorageError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.constraint() == Some(NAME_CONSTRAINT) => {
+            StorageError::ConstraintViolation {
+                constraint: NAME_CONSTRAINT.to_string(),
+            }
+        }
+        _ => err.into(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Saved search operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for saved searches.
+#[derive(Debug)]
+pub struct SearchQueries;
+
+impl SearchQueries {
+    /// Save a search for `owner`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::ConstraintViolation`] if `owner` already has a
+    /// search with the name, or [`StorageError`] if the query fails.
+    pub async fn create(
+        pool: &PgPool,
+        owner: &str,
+        search: &NewSavedSearch,
+    ) -> Result<SavedSearch> {
+        sqlx::query_as::<_, SavedSearch>(
+            r"
+            INSERT INTO saved_searches
+                (owner, name, system_id, talkgroup_id, keyword, language, from_date, to_date)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            ",
+        )
+        .bind(owner)
+        .bind(&search.name)
+        .bind(search.system_id.as_deref())
+        .bind(search.talkgroup_id)
+        .bind(search.keyword.as_deref())
+        .bind(search.language.as_deref())
+        .bind(search.from_date)
+        .bind(search.to_date)
+        .fetch_one(pool)
+        .await
+        .map_err(name_taken)
+    }
+
+    /// Searches of `owner`, by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list_for_owner(pool: &PgPool, owner: &str) -> Result<Vec<SavedSearch>> {
+        let searches = sqlx::query_as::<_, SavedSearch>(
+            "SELECT * FROM saved_searches WHERE owner = $1 ORDER BY name",
+        )
+        .bind(owner)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(searches)
+    }
+
+    /// A search of `owner`, or `None` if it has no search with the ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn get(pool: &PgPool, id: Uuid, owner: &str) -> Result<Option<SavedSearch>> {
+        let search = sqlx::query_as::<_, SavedSearch>(
+            "SELECT * FROM saved_searches WHERE id = $1 AND owner = $2",
+        )
+        .bind(id)
+        .bind(owner)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(search)
+    }
+
+    /// Replace the name and filters of a search of `owner`, or return `None`
+    /// if it has no search with the ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::ConstraintViolation`] if `owner` already has
+    /// another search with the name, or [`StorageError`] if the query fails.
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        owner: &str,
+        search: &NewSavedSearch,
+    ) -> Result<Option<SavedSearch>> {
+        sqlx::query_as::<_, SavedSearch>(
+            r"
+            UPDATE saved_searches
+            SET name = $3,
+                system_id = $4,
+                talkgroup_id = $5,
+                keyword = $6,
+                language = $7,
+                from_date = $8,
+                to_date = $9,
+                updated_at = NOW()
+            WHERE id = $1 AND owner = $2
+            RETURNING *
+            ",
+        )
+        .bind(id)
+        .bind(owner)
+        .bind(&search.name)
+        .bind(search.system_id.as_deref())
+        .bind(search.talkgroup_id)
+        .bind(search.keyword.as_deref())
+        .bind(search.language.as_deref())
+        .bind(search.from_date)
+        .bind(search.to_date)
+        .fetch_optional(pool)
+        .await
+        .map_err(name_taken)
+    }
+
+    /// Delete a search of `owner`, returning whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn delete(pool: &PgPool, id: Uuid, owner: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = $1 AND owner = $2")
+            .bind(id)
+            .bind(owner)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+
+    fn search(name: &str) -> NewSavedSearch {
+        NewSavedSearch {
+            name: name.to_string(),
+            system_id: Some("county".to_string()),
+            talkgroup_id: Some(1234),
+            keyword: Some("structure fire".to_string()),
+            language: Some("en".to_string()),
+            ..NewSavedSearch::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches_are_per_owner() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let owner = format!("user:{}", Uuid::new_v4());
+        let other = format!("user:{}", Uuid::new_v4());
+
+        let fires = SearchQueries::create(&pool, &owner, &search("fires"))
+            .await
+            .unwrap();
+        assert_eq!(fires.keyword.as_deref(), Some("structure fire"));
+        assert_eq!(fires.language.as_deref(), Some("en"));
+        SearchQueries::create(&pool, &owner, &search("aid"))
+            .await
+            .unwrap();
+        SearchQueries::create(&pool, &other, &search("fires"))
+            .await
+            .unwrap();
+
+        let err = SearchQueries::create(&pool, &owner, &search("fires"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StorageError::ConstraintViolation { .. }));
+
+        let names: Vec<_> = SearchQueries::list_for_owner(&pool, &owner)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, ["aid", "fires"]);
+
+        assert!(
+            SearchQueries::get(&pool, fires.id, &other)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let err = SearchQueries::update(&pool, fires.id, &owner, &search("aid"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StorageError::ConstraintViolation { .. }));
+
+        let renamed = SearchQueries::update(&pool, fires.id, &owner, &search("fires-2"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(renamed.name, "fires-2");
+        assert!(
+            SearchQueries::update(&pool, fires.id, &other, &search("x"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        assert!(
+            !SearchQueries::delete(&pool, fires.id, &other)
+                .await
+                .unwrap()
+        );
+        assert!(
+            SearchQueries::delete(&pool, fires.id, &owner)
+                .await
+                .unwrap()
+        );
+        assert!(
+            SearchQueries::get(&pool, fires.id, &owner)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! Timed transcript segments.
+//!
+//! Transcribers split a call's transcript into segments with start and end
+//! times in seconds from the start of the recording. They are kept in
+//! `transcription_segments` (replaced on re-transcription, removed with the
+//! call) so transcripts can be exported as subtitles.
+//!
+//! When transcripts are encrypted at rest, segment text is sealed with the
+//! same [`KeyRing`] into `text_encrypted` and the plaintext column is left
+//! empty.
+
+use crate::crypto::KeyRing;
+use crate::error::StorageError;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for segment operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One timed piece of a call's transcript.
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct TranscriptionSegment {
+    /// Start of the segment, in seconds.
+    #[serde(rename = "start")]
+    pub start_seconds: f64,
+    /// End of the segment, in seconds.
+    #[serde(rename = "end")]
+    pub end_seconds: f64,
+    /// Transcribed text.
+    pub text: String,
+    /// Speaker label, when the transcript was diarized.
+    #[serde(default)]
+    pub speaker: Option<String>,
+    /// Transcription confidence, when the backend reports one.
+    #[serde(default)]
+    pub confidence: Option<f32>,
+}
+
+/// A stored segment, with its text in plaintext or sealed.
+#[derive(Debug, FromRow)]
+struct SegmentRow {
+    start_seconds: f64,
+    end_seconds: f64,
+    text: Option<String>,
+    text_encrypted: Option<Vec<u8>>,
+    speaker: Option<String>,
+    confidence: Option<f32>,
+}
+
+impl TranscriptionSegment {
+    /// Parse segments as reported by a transcription backend.
+    ///
+    /// Entries that are not well-formed segments or have no text are
+    /// skipped; text is trimmed and the rest are returned in start order.
+    #[must_use]
+    pub fn parse_all(entries: &[serde_json::Value]) -> Vec<Self> {
+        let mut segments: Vec<Self> = entries
+            .iter()
+            .filter_map(|entry| Self::deserialize(entry).ok())
+            .filter_map(|mut segment| {
+                segment.text = segment.text.trim().to_string();
+                (!segment.text.is_empty()).then_some(segment)
+            })
+            .collect();
+        segments.sort_by(|a, b| a.start_seconds.total_cmp(&b.start_seconds));
+        segments
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Segment operations
+// ---------------------------------------------------------------------------
+
+/// Transcript segment queries.
+#[derive(Debug)]
+pub struct SegmentQueries;
+
+impl SegmentQueries {
+    /// Store the segments of a call's transcript, replacing any earlier ones.
+    ///
+    /// With `keys`, segment text is stored sealed instead of in plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sealing fails, the call does not exist, or the
+    /// database query fails.
+    pub async fn replace(
+        pool: &PgPool,
+        call_id: Uuid,
+        segments: &[TranscriptionSegment],
+        keys: Option<&KeyRing>,
+    ) -> Result<()> {
+        let starts: Vec<f64> = segments.iter().map(|s| s.start_seconds).collect();
+        let ends: Vec<f64> = segments.iter().map(|s| s.end_seconds).collect();
+        let (texts, sealed): (Vec<Option<&str>>, Vec<Option<Vec<u8>>>) = match keys {
+            Some(keys) => (
+                vec![None; segments.len()],
+                segments
+                    .iter()
+                    .map(|s| keys.seal(&s.text).map(Some))
+                    .collect::<Result<_>>()?,
+            ),
+            None => (
+                segments.iter().map(|s| Some(s.text.as_str())).collect(),
+                vec![None; segments.len()],
+            ),
+        };
+        let speakers: Vec<Option<&str>> = segments.iter().map(|s| s.speaker.as_deref()).collect();
+        let confidences: Vec<Option<f32>> = segments.iter().map(|s| s.confidence).collect();
+
+        let mut tx = pool.begin().await?;
+        let _ = sqlx::query("DELETE FROM transcription_segments WHERE call_id = $1")
+            .bind(call_id)
+            .execute(&mut *tx)
+            .await?;
+        let _ = sqlx::query(
+            r"
+            INSERT INTO transcription_segments
+                (call_id, segment_index, start_seconds, end_seconds, text, text_encrypted,
+                 speaker, confidence)
+            SELECT $1, s.ordinality - 1, s.start_seconds, s.end_seconds, s.text, s.text_encrypted,
+                   s.speaker, s.confidence
+            FROM UNNEST($2::DOUBLE PRECISION[], $3::DOUBLE PRECISION[], $4::TEXT[],
+                        $5::BYTEA[], $6::VARCHAR[], $7::REAL[])
+                WITH ORDINALITY AS s(start_seconds, end_seconds, text, text_encrypted, speaker,
+                                     confidence, ordinality)
+            ",
+        )
+        .bind(call_id)
+        .bind(&starts)
+        .bind(&ends)
+        .bind(&texts)
+        .bind(&sealed)
+        .bind(&speakers)
+        .bind(&confidences)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// The transcript segments of a call, in order.
+    ///
+    /// Sealed segments are opened with `keys`; without them, or when they
+    /// fail to open, those segments are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_call(
+        pool: &PgPool,
+        call_id: Uuid,
+        keys: Option<&KeyRing>,
+    ) -> Result<Vec<TranscriptionSegment>> {
+        let rows = sqlx::query_as::<_, SegmentRow>(
+            r"
+            SELECT start_seconds, end_seconds, text, text_encrypted, speaker, confidence
+            FROM transcription_segments
+            WHERE call_id = $1
+            ORDER BY segment_index
+            ",
+        )
+        .bind(call_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let text = match (row.text, row.text_encrypted.as_deref(), keys) {
+                    (Some(text), _, _) => text,
+                    (None, Some(sealed), Some(keys)) => keys
+                        .open(sealed)
+                        .inspect_err(|e| {
+                            tracing::warn!("Failed to decrypt a segment of call {call_id}: {e}");
+                        })
+                        .ok()?,
+                    _ => return None,
+                };
+                Some(TranscriptionSegment {
+                    start_seconds: row.start_seconds,
+                    end_seconds: row.end_seconds,
+                    text,
+                    speaker: row.speaker,
+                    confidence: row.confidence,
+                })
+            })
+            .collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use sdrtrunk_types::SystemId;
+
+    fn call(system_id: &SystemId) -> RadioCallDb {
+        RadioCallDb {
+            transcription_status: None,
+            ..test_call(system_id)
+        }
+    }
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            start_seconds: start,
+            end_seconds: end,
+            text: text.to_string(),
+            speaker: None,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let entries = vec![
+            serde_json::json!({"start": 2.5, "end": 4.0, "text": " copy that ", "speaker": "SPEAKER_01"}),
+            serde_json::json!({"start": 0.0, "end": 2.0, "text": "Engine 5 responding", "avg_logprob": -0.2}),
+            serde_json::json!({"start": 4.0, "end": 5.0, "text": "   "}),
+            serde_json::json!({"start": "soon", "text": "bad"}),
+        ];
+        let segments = TranscriptionSegment::parse_all(&entries);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0], segment(0.0, 2.0, "Engine 5 responding"));
+        assert_eq!(segments[1].text, "copy that");
+        assert_eq!(segments[1].speaker.as_deref(), Some("SPEAKER_01"));
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_get() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("seg_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call_id = RadioCallQueries::insert(&pool, &call(&system_id))
+            .await
+            .unwrap();
+
+        assert!(
+            SegmentQueries::for_call(&pool, call_id, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        SegmentQueries::replace(&pool, call_id, &[segment(0.0, 1.0, "first")], None)
+            .await
+            .unwrap();
+        let segments = vec![segment(0.0, 1.5, "Engine 5"), segment(1.5, 3.0, "copy")];
+        SegmentQueries::replace(&pool, call_id, &segments, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            SegmentQueries::for_call(&pool, call_id, None)
+                .await
+                .unwrap(),
+            segments
+        );
+
+        // Segments cannot exist without their call
+        assert!(
+            SegmentQueries::replace(&pool, Uuid::new_v4(), &segments, None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sealed_segments_keep_no_plaintext() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let keys = KeyRing::new(&STANDARD.encode([3u8; 32]), &[]).unwrap();
+        let system_id = SystemId::new(format!("seg_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call_id = RadioCallQueries::insert(&pool, &call(&system_id))
+            .await
+            .unwrap();
+
+        let segments = vec![
+            segment(0.0, 1.5, "Engine 5 to 1200 Main Street"),
+            segment(1.5, 3.0, "copy"),
+        ];
+        SegmentQueries::replace(&pool, call_id, &segments, Some(&keys))
+            .await
+            .unwrap();
+
+        let stored: Vec<(Option<String>, Option<Vec<u8>>)> = sqlx::query_as(
+            "SELECT text, text_encrypted FROM transcription_segments
+             WHERE call_id = $1 ORDER BY segment_index",
+        )
+        .bind(call_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(stored.len(), 2);
+        for (text, sealed) in &stored {
+            assert!(text.is_none());
+            let sealed = sealed.as_deref().unwrap();
+            assert!(!sealed.windows(4).any(|window| window == b"Main"));
+            assert!(!sealed.windows(4).any(|window| window == b"copy"));
+        }
+
+        assert_eq!(
+            SegmentQueries::for_call(&pool, call_id, Some(&keys))
+                .await
+                .unwrap(),
+            segments
+        );
+        // Without the keys, sealed segments are left out
+        assert!(
+            SegmentQueries::for_call(&pool, call_id, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
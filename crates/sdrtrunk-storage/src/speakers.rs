@@ -0,0 +1,298 @@
+//! Speaker diarization results.
+//!
+//! Diarized calls store their speaker turns in the `radio_calls.speaker_segments`
+//! JSONB column as an array of `{speaker, start, end, confidence}` objects,
+//! with times in seconds from the start of the recording. Speaker labels such
+//! as `SPEAKER_00` are only meaningful within a single call, so per-system
+//! statistics aggregate counts and talk time rather than individual speakers.
+
+use crate::{error::StorageError, queries::system_names};
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::SystemId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for speaker operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One speaker turn within a call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerSegment {
+    /// Speaker label, unique within the call (e.g. `SPEAKER_00`).
+    pub speaker: String,
+    /// Start of the turn, in seconds.
+    pub start: f64,
+    /// End of the turn, in seconds.
+    pub end: f64,
+    /// Diarization confidence, when the backend reports one.
+    #[serde(default)]
+    pub confidence: Option<f64>,
+}
+
+impl SpeakerSegment {
+    /// Parse a stored `speaker_segments` value.
+    ///
+    /// Entries that are not well-formed segments are skipped, and the rest are
+    /// returned in start order.
+    #[must_use]
+    pub fn parse_all(value: &serde_json::Value) -> Vec<Self> {
+        let mut segments: Vec<Self> = value
+            .as_array()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| Self::deserialize(entry).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+        segments
+    }
+
+    /// Length of the turn in seconds (0.0 if the times are reversed).
+    #[must_use]
+    pub fn duration(&self) -> f64 {
+        (self.end - self.start).max(0.0)
+    }
+}
+
+/// Talk time of one speaker within a call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerTalkTime {
+    /// Speaker label.
+    pub speaker: String,
+    /// Number of turns taken.
+    pub segment_count: usize,
+    /// Total speaking time, in seconds.
+    pub talk_seconds: f64,
+}
+
+impl SpeakerTalkTime {
+    /// Total each speaker's turns, most talkative first.
+    #[must_use]
+    pub fn summarize(segments: &[SpeakerSegment]) -> Vec<Self> {
+        let mut speakers: Vec<Self> = Vec::new();
+        for segment in segments {
+            if let Some(speaker) = speakers.iter_mut().find(|s| s.speaker == segment.speaker) {
+                speaker.segment_count += 1;
+                speaker.talk_seconds += segment.duration();
+            } else {
+                speakers.push(Self {
+                    speaker: segment.speaker.clone(),
+                    segment_count: 1,
+                    talk_seconds: segment.duration(),
+                });
+            }
+        }
+        speakers.sort_by(|a, b| {
+            b.talk_seconds
+                .total_cmp(&a.talk_seconds)
+                .then_with(|| a.speaker.cmp(&b.speaker))
+        });
+        speakers
+    }
+}
+
+/// Aggregate diarization statistics for one system.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SystemSpeakerStats {
+    /// System identifier.
+    pub system_id: String,
+    /// Calls with speaker segments.
+    pub diarized_calls: i64,
+    /// Diarized calls with more than one speaker.
+    pub multi_speaker_calls: i64,
+    /// Average number of speakers per diarized call.
+    pub avg_speakers_per_call: Option<f64>,
+    /// Most speakers seen in a single call.
+    pub max_speakers: Option<i64>,
+    /// Total speaking time across all diarized calls, in seconds.
+    pub total_talk_seconds: f64,
+    /// Average speaking time per diarized call, in seconds.
+    pub avg_talk_seconds_per_call: Option<f64>,
+}
+
+// ---------------------------------------------------------------------------
+// Speaker operations
+// ---------------------------------------------------------------------------
+
+/// Speaker diarization queries.
+#[derive(Debug)]
+pub struct SpeakerQueries;
+
+impl SpeakerQueries {
+    /// Per-system speaker statistics, ordered by system.
+    ///
+    /// `systems` restricts the result to those systems (`None` for every
+    /// system) and `since` to calls at or after that time. A call's speaker
+    /// count falls back to the distinct labels in its segments when
+    /// `speaker_count` was not recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn system_stats(
+        pool: &PgPool,
+        systems: Option<&[SystemId]>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SystemSpeakerStats>> {
+        let stats = sqlx::query_as::<_, SystemSpeakerStats>(
+            r"
+            WITH diarized AS (
+                SELECT
+                    rc.system_id,
+                    COALESCE(
+                        rc.speaker_count,
+                        (SELECT COUNT(DISTINCT seg->>'speaker')
+                         FROM jsonb_array_elements(rc.speaker_segments) seg)
+                    ) AS speakers,
+                    COALESCE(
+                        (SELECT SUM(GREATEST((seg->>'end')::FLOAT8 - (seg->>'start')::FLOAT8, 0))
+                         FROM jsonb_array_elements(rc.speaker_segments) seg
+                         WHERE jsonb_typeof(seg->'start') = 'number'
+                           AND jsonb_typeof(seg->'end') = 'number'),
+                        0
+                    ) AS talk_seconds
+                FROM radio_calls rc
+                WHERE jsonb_typeof(rc.speaker_segments) = 'array'
+                  AND jsonb_array_length(rc.speaker_segments) > 0
+                  AND ($1::TEXT[] IS NULL OR rc.system_id = ANY($1))
+                  AND ($2::TIMESTAMPTZ IS NULL OR rc.call_timestamp >= $2)
+            )
+            SELECT
+                system_id,
+                COUNT(*) AS diarized_calls,
+                COUNT(*) FILTER (WHERE speakers > 1) AS multi_speaker_calls,
+                AVG(speakers)::FLOAT8 AS avg_speakers_per_call,
+                MAX(speakers)::INT8 AS max_speakers,
+                COALESCE(SUM(talk_seconds), 0)::FLOAT8 AS total_talk_seconds,
+                AVG(talk_seconds)::FLOAT8 AS avg_talk_seconds_per_call
+            FROM diarized
+            GROUP BY system_id
+            ORDER BY system_id
+            ",
+        )
+        .bind(systems.map(system_names))
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(stats)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn call(
+        system_id: &SystemId,
+        speaker_segments: Option<serde_json::Value>,
+        speaker_count: Option<i32>,
+    ) -> RadioCallDb {
+        RadioCallDb {
+            transcription_status: Some("completed".to_string()),
+            speaker_segments,
+            speaker_count,
+            ..test_call(system_id)
+        }
+    }
+
+    #[test]
+    fn test_parse_segments() {
+        let value = json!([
+            {"speaker": "SPEAKER_01", "start": 4.0, "end": 6.5, "confidence": 0.8},
+            {"speaker": "SPEAKER_00", "start": 0.0, "end": 4.0},
+            {"speaker": "SPEAKER_00"},
+            "garbage"
+        ]);
+        let segments = SpeakerSegment::parse_all(&value);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].speaker, "SPEAKER_00");
+        assert_eq!(segments[0].confidence, None);
+        assert_eq!(segments[1].duration(), 2.5);
+
+        assert!(SpeakerSegment::parse_all(&json!({"speaker": "A"})).is_empty());
+    }
+
+    #[test]
+    fn test_summarize_talk_time() {
+        let segments = SpeakerSegment::parse_all(&json!([
+            {"speaker": "A", "start": 0.0, "end": 2.0},
+            {"speaker": "B", "start": 2.0, "end": 7.0},
+            {"speaker": "A", "start": 7.0, "end": 9.0},
+            {"speaker": "C", "start": 9.0, "end": 8.0}
+        ]));
+        let speakers = SpeakerTalkTime::summarize(&segments);
+        assert_eq!(speakers.len(), 3);
+        assert_eq!(speakers[0].speaker, "B");
+        assert_eq!(speakers[0].talk_seconds, 5.0);
+        assert_eq!(speakers[1].speaker, "A");
+        assert_eq!(speakers[1].segment_count, 2);
+        assert_eq!(speakers[1].talk_seconds, 4.0);
+        assert_eq!(speakers[2].talk_seconds, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_system_stats() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("spk_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        for (segments, speaker_count) in [
+            (
+                Some(json!([
+                    {"speaker": "A", "start": 0.0, "end": 3.0},
+                    {"speaker": "B", "start": 3.0, "end": 5.0}
+                ])),
+                Some(2),
+            ),
+            (
+                Some(json!([{"speaker": "A", "start": 0.0, "end": 1.0}])),
+                None,
+            ),
+            (None, None),
+        ] {
+            RadioCallQueries::insert(&pool, &call(&system_id, segments, speaker_count))
+                .await
+                .unwrap();
+        }
+
+        let stats =
+            SpeakerQueries::system_stats(&pool, Some(std::slice::from_ref(&system_id)), None)
+                .await
+                .unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].system_id, system_id.as_str());
+        assert_eq!(stats[0].diarized_calls, 2);
+        assert_eq!(stats[0].multi_speaker_calls, 1);
+        assert_eq!(stats[0].avg_speakers_per_call, Some(1.5));
+        assert_eq!(stats[0].max_speakers, Some(2));
+        assert_eq!(stats[0].total_talk_seconds, 6.0);
+        assert_eq!(stats[0].avg_talk_seconds_per_call, Some(3.0));
+    }
+}
@@ -0,0 +1,229 @@
+//! LLM-written call summaries.
+//!
+//! A summarizer condenses each completed transcript into a sentence or two
+//! and lists the entities it mentions (units, places, incidents). Summaries
+//! live in `call_summaries` (one row per call, removed with the call), and
+//! keyword searches of calls match their text as well as the transcript.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json};
+use uuid::Uuid;
+
+/// Result type alias for summary operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Something a call mentions, such as a unit or a street.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SummaryEntity {
+    /// What the entity is (e.g. `unit`, `location`, `incident`).
+    pub kind: String,
+    /// The entity as it should be displayed (e.g. `Engine 5`).
+    pub text: String,
+}
+
+/// Stored summary of one call.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CallSummary {
+    /// Call the summary belongs to.
+    pub call_id: Uuid,
+    /// Short summary of the transcript.
+    pub summary: String,
+    /// Entities the transcript mentions.
+    pub entities: Json<Vec<SummaryEntity>>,
+    /// Model that wrote the summary.
+    pub model: String,
+    /// When the summary was written.
+    pub created_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Summary operations
+// ---------------------------------------------------------------------------
+
+/// Summary queries.
+#[derive(Debug)]
+pub struct SummaryQueries;
+
+impl SummaryQueries {
+    /// Store the summary of a call, replacing any earlier one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call does not exist or the database query fails.
+    pub async fn save(
+        pool: &PgPool,
+        call_id: Uuid,
+        summary: &str,
+        entities: &[SummaryEntity],
+        model: &str,
+    ) -> Result<CallSummary> {
+        let saved = sqlx::query_as::<_, CallSummary>(
+            r"
+            INSERT INTO call_summaries (call_id, summary, entities, model)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (call_id) DO UPDATE SET
+                summary = EXCLUDED.summary,
+                entities = EXCLUDED.entities,
+                model = EXCLUDED.model,
+                created_at = NOW()
+            RETURNING call_id, summary, entities, model, created_at
+            ",
+        )
+        .bind(call_id)
+        .bind(summary)
+        .bind(Json(entities))
+        .bind(model)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(saved)
+    }
+
+    /// The stored summary of a call, if one has been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn get(pool: &PgPool, call_id: Uuid) -> Result<Option<CallSummary>> {
+        let summary = sqlx::query_as::<_, CallSummary>(
+            r"
+            SELECT call_id, summary, entities, model, created_at
+            FROM call_summaries
+            WHERE call_id = $1
+            ",
+        )
+        .bind(call_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(summary)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::{RadioCallFilter, RadioCallQueries, list_radio_calls_filtered};
+    use crate::test_support::{create_test_pool, test_call};
+    use sdrtrunk_types::SystemId;
+
+    fn call(system_id: &SystemId, text: &str) -> RadioCallDb {
+        RadioCallDb {
+            transcription_text: Some(text.to_string()),
+            transcription_status: Some("completed".to_string()),
+            ..test_call(system_id)
+        }
+    }
+
+    fn search<'a>(system_id: &'a SystemId, keyword: &'a str) -> RadioCallFilter<'a> {
+        RadioCallFilter {
+            system_id: Some(system_id),
+            allowed_systems: None,
+            talkgroup_id: None,
+            transcription_status: None,
+            tag: None,
+            keyword: Some(keyword),
+            language: None,
+            search: None,
+            archived: false,
+            from_date: None,
+            to_date: None,
+            limit: 10,
+            after: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("sum_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call_id = RadioCallQueries::insert(&pool, &call(&system_id, "engine five en route"))
+            .await
+            .unwrap();
+        assert!(SummaryQueries::get(&pool, call_id).await.unwrap().is_none());
+
+        SummaryQueries::save(&pool, call_id, "Engine responding.", &[], "first")
+            .await
+            .unwrap();
+        let entities = vec![SummaryEntity {
+            kind: "unit".to_string(),
+            text: "Engine 5".to_string(),
+        }];
+        let saved = SummaryQueries::save(
+            &pool,
+            call_id,
+            "Engine 5 responding to a structure fire.",
+            &entities,
+            "second",
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.model, "second");
+
+        let stored = SummaryQueries::get(&pool, call_id).await.unwrap().unwrap();
+        assert_eq!(stored.summary, "Engine 5 responding to a structure fire.");
+        assert_eq!(stored.entities.0, entities);
+
+        // Summaries cannot exist without their call
+        assert!(
+            SummaryQueries::save(&pool, Uuid::new_v4(), "Orphan", &[], "model")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keyword_matches_summary() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("sum_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call_id = RadioCallQueries::insert(&pool, &call(&system_id, "copy, en route"))
+            .await
+            .unwrap();
+        let entities = vec![SummaryEntity {
+            kind: "location".to_string(),
+            text: "Main Street".to_string(),
+        }];
+        SummaryQueries::save(&pool, call_id, "Structure fire response.", &entities, "m")
+            .await
+            .unwrap();
+
+        for keyword in ["en route", "STRUCTURE FIRE", "main street"] {
+            let calls = list_radio_calls_filtered(&pool, search(&system_id, keyword))
+                .await
+                .unwrap();
+            assert_eq!(calls.len(), 1, "keyword {keyword}");
+            assert_eq!(calls[0].id, call_id);
+        }
+        let calls = list_radio_calls_filtered(&pool, search(&system_id, "ambulance"))
+            .await
+            .unwrap();
+        assert!(calls.is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+//! Call tags and notes.
+//!
+//! Analysts tag calls (e.g. `fire`, `mutual-aid`) to find them again, and may
+//! attach a free-text note to each tag. A call carries each tag at most once;
+//! tagging it again replaces the note. Call listings filter by tag through
+//! [`RadioCallFilter::tag`](crate::queries::RadioCallFilter::tag).
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for tag operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `call_tags` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CallTag {
+    /// Tagged call.
+    pub call_id: Uuid,
+    /// Tag, lowercase.
+    pub tag: String,
+    /// Free-text note on the tag.
+    pub note: Option<String>,
+    /// API key the tag was last set with.
+    pub api_key_id: Option<String>,
+    /// When the tag was first attached.
+    pub created_at: DateTime<Utc>,
+    /// When the tag was last set.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields for attaching a tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewCallTag {
+    /// Call to tag.
+    pub call_id: Uuid,
+    /// Tag, lowercase.
+    pub tag: String,
+    /// Free-text note on the tag.
+    pub note: Option<String>,
+    /// API key the tag is set with.
+    pub api_key_id: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Tag operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for call tags.
+#[derive(Debug)]
+pub struct TagQueries;
+
+impl TagQueries {
+    /// Attach a tag to a call, replacing the note if the call already has it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn upsert(pool: &PgPool, tag: &NewCallTag) -> Result<CallTag> {
+        let tag = sqlx::query_as::<_, CallTag>(
+            r"
+            INSERT INTO call_tags (call_id, tag, note, api_key_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (call_id, tag) DO UPDATE
+                SET note = EXCLUDED.note,
+                    api_key_id = EXCLUDED.api_key_id,
+                    updated_at = NOW()
+            RETURNING *
+            ",
+        )
+        .bind(tag.call_id)
+        .bind(&tag.tag)
+        .bind(tag.note.as_deref())
+        .bind(tag.api_key_id.as_deref())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    /// Remove a tag from a call, returning whether the call had it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn delete(pool: &PgPool, call_id: Uuid, tag: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM call_tags WHERE call_id = $1 AND tag = $2")
+            .bind(call_id)
+            .bind(tag)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Tags of a call with their notes, in tag order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list_for_call(pool: &PgPool, call_id: Uuid) -> Result<Vec<CallTag>> {
+        let tags =
+            sqlx::query_as::<_, CallTag>("SELECT * FROM call_tags WHERE call_id = $1 ORDER BY tag")
+                .bind(call_id)
+                .fetch_all(pool)
+                .await?;
+
+        Ok(tags)
+    }
+
+    /// Tag names of each of `call_ids` that has any, in tag order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn names_for_calls(pool: &PgPool, call_ids: &[Uuid]) -> Result<Vec<(Uuid, String)>> {
+        let tags = sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT call_id, tag FROM call_tags WHERE call_id = ANY($1) ORDER BY call_id, tag",
+        )
+        .bind(call_ids)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tags)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::{
+        RadioCallFilter, RadioCallQueries, count_radio_calls_filtered, list_radio_calls_filtered,
+    };
+    use crate::test_support::{create_test_pool, test_call};
+    use sdrtrunk_types::SystemId;
+
+    fn call(system_id: &SystemId) -> RadioCallDb {
+        RadioCallDb {
+            transcription_status: Some("completed".to_string()),
+            ..test_call(system_id)
+        }
+    }
+
+    fn tag(call_id: Uuid, tag: &str, note: Option<&str>) -> NewCallTag {
+        NewCallTag {
+            call_id,
+            tag: tag.to_string(),
+            note: note.map(str::to_string),
+            api_key_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tags_filter_calls() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("tag_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let tagged = call(&system_id);
+        let untagged = call(&system_id);
+        RadioCallQueries::insert(&pool, &tagged).await.unwrap();
+        RadioCallQueries::insert(&pool, &untagged).await.unwrap();
+
+        TagQueries::upsert(&pool, &tag(tagged.id, "fire", Some("first")))
+            .await
+            .unwrap();
+        TagQueries::upsert(&pool, &tag(tagged.id, "mutual-aid", None))
+            .await
+            .unwrap();
+        let updated = TagQueries::upsert(&pool, &tag(tagged.id, "fire", Some("second")))
+            .await
+            .unwrap();
+        assert_eq!(updated.note.as_deref(), Some("second"));
+
+        let tags = TagQueries::list_for_call(&pool, tagged.id).await.unwrap();
+        let names: Vec<_> = tags.iter().map(|t| t.tag.as_str()).collect();
+        assert_eq!(names, ["fire", "mutual-aid"]);
+
+        let names = TagQueries::names_for_calls(&pool, &[tagged.id, untagged.id])
+            .await
+            .unwrap();
+        assert_eq!(names.len(), 2);
+        assert!(names.iter().all(|(id, _)| *id == tagged.id));
+
+        let filter = || RadioCallFilter {
+            system_id: Some(&system_id),
+            allowed_systems: None,
+            talkgroup_id: None,
+            transcription_status: None,
+            tag: Some("fire"),
+            keyword: None,
+            language: None,
+            search: None,
+            archived: false,
+            from_date: None,
+            to_date: None,
+            limit: 10,
+            after: None,
+        };
+        let calls = list_radio_calls_filtered(&pool, filter()).await.unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, tagged.id);
+        assert_eq!(
+            count_radio_calls_filtered(&pool, filter()).await.unwrap(),
+            1
+        );
+
+        assert!(TagQueries::delete(&pool, tagged.id, "fire").await.unwrap());
+        assert!(!TagQueries::delete(&pool, tagged.id, "fire").await.unwrap());
+        let calls = list_radio_calls_filtered(&pool, filter()).await.unwrap();
+        assert!(calls.is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+//! Talkgroup aliases.
+//!
+//! Friendly talkgroup names imported from `SDRTrunk` playlists or
+//! `RadioReference` exports live in the `talkgroups` table, keyed by system
+//! and talkgroup. Uploads fill missing call labels from it, and an import
+//! backfills calls that were stored before their talkgroup had a name.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use sdrtrunk_protocol::talkgroups::TalkgroupAlias;
+use sdrtrunk_types::{SystemId, TalkgroupId};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// Result type alias for talkgroup operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `talkgroups` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Talkgroup {
+    /// System the talkgroup belongs to.
+    pub system_id: SystemId,
+    /// Talkgroup ID.
+    pub talkgroup_id: TalkgroupId,
+    /// Display name.
+    pub label: Option<String>,
+    /// Group (department or category).
+    #[sqlx(rename = "group_name")]
+    pub group: Option<String>,
+    /// Service tag.
+    pub tag: Option<String>,
+    /// Longer description.
+    pub description: Option<String>,
+    /// Import format the names came from.
+    pub source: String,
+    /// When the talkgroup was first imported.
+    pub created_at: DateTime<Utc>,
+    /// When the talkgroup was last imported.
+    pub updated_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Talkgroup operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for talkgroup aliases.
+#[derive(Debug)]
+pub struct TalkgroupQueries;
+
+impl TalkgroupQueries {
+    /// Insert or replace aliases for a system.
+    ///
+    /// Returns the number of talkgroups written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn upsert_many(
+        pool: &PgPool,
+        system_id: &SystemId,
+        aliases: &[TalkgroupAlias],
+        source: &str,
+    ) -> Result<u64> {
+        let ids: Vec<i32> = aliases.iter().map(|a| a.talkgroup_id.as_i32()).collect();
+        let labels: Vec<Option<&str>> = aliases.iter().map(|a| a.label.as_deref()).collect();
+        let groups: Vec<Option<&str>> = aliases.iter().map(|a| a.group.as_deref()).collect();
+        let tags: Vec<Option<&str>> = aliases.iter().map(|a| a.tag.as_deref()).collect();
+        let descriptions: Vec<Option<&str>> =
+            aliases.iter().map(|a| a.description.as_deref()).collect();
+
+        let result = sqlx::query(
+            r"
+            INSERT INTO talkgroups
+                (system_id, talkgroup_id, label, group_name, tag, description, source)
+            SELECT $1, t.talkgroup_id, t.label, t.group_name, t.tag, t.description, $7
+            FROM UNNEST($2::INTEGER[], $3::VARCHAR[], $4::VARCHAR[], $5::VARCHAR[], $6::TEXT[])
+                AS t(talkgroup_id, label, group_name, tag, description)
+            ON CONFLICT (system_id, talkgroup_id) DO UPDATE SET
+                label       = EXCLUDED.label,
+                group_name  = EXCLUDED.group_name,
+                tag         = EXCLUDED.tag,
+                description = EXCLUDED.description,
+                source      = EXCLUDED.source,
+                updated_at  = NOW()
+            ",
+        )
+        .bind(system_id)
+        .bind(&ids)
+        .bind(&labels)
+        .bind(&groups)
+        .bind(&tags)
+        .bind(&descriptions)
+        .bind(source)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Look up one talkgroup's aliases.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn find(
+        pool: &PgPool,
+        system_id: &SystemId,
+        talkgroup_id: TalkgroupId,
+    ) -> Result<Option<Talkgroup>> {
+        let talkgroup = sqlx::query_as::<_, Talkgroup>(
+            "SELECT * FROM talkgroups WHERE system_id = $1 AND talkgroup_id = $2",
+        )
+        .bind(system_id)
+        .bind(talkgroup_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(talkgroup)
+    }
+
+    /// List a system's talkgroups ordered by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(pool: &PgPool, system_id: &SystemId) -> Result<Vec<Talkgroup>> {
+        let talkgroups = sqlx::query_as::<_, Talkgroup>(
+            "SELECT * FROM talkgroups WHERE system_id = $1 ORDER BY talkgroup_id",
+        )
+        .bind(system_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(talkgroups)
+    }
+
+    /// Fill missing labels, groups, and tags on a system's calls.
+    ///
+    /// Names sent by the uploader are never overwritten. Returns the number of
+    /// calls updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn apply_to_calls(pool: &PgPool, system_id: &SystemId) -> Result<u64> {
+        let result = sqlx::query(
+            r"
+            UPDATE radio_calls rc
+            SET talkgroup_label = COALESCE(rc.talkgroup_label, t.label),
+                talkgroup_group = COALESCE(rc.talkgroup_group, t.group_name),
+                talkgroup_tag   = COALESCE(rc.talkgroup_tag, t.tag)
+            FROM talkgroups t
+            WHERE rc.system_id = $1
+              AND t.system_id = rc.system_id
+              AND t.talkgroup_id = rc.talkgroup_id
+              AND (
+                  (rc.talkgroup_label IS NULL AND t.label IS NOT NULL)
+                  OR (rc.talkgroup_group IS NULL AND t.group_name IS NOT NULL)
+                  OR (rc.talkgroup_tag IS NULL AND t.tag IS NOT NULL)
+              )
+            ",
+        )
+        .bind(system_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+
+    fn alias(id: i32, label: &str) -> TalkgroupAlias {
+        TalkgroupAlias {
+            talkgroup_id: TalkgroupId::new(id).unwrap(),
+            label: Some(label.to_string()),
+            group: Some("Fire".to_string()),
+            tag: None,
+            description: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_find() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id =
+            SystemId::new(format!("tg_{}", &uuid::Uuid::new_v4().to_string()[..8])).unwrap();
+        let written = TalkgroupQueries::upsert_many(
+            &pool,
+            &system_id,
+            &[alias(1001, "Fire Dispatch"), alias(1002, "Fireground")],
+            "playlist",
+        )
+        .await
+        .unwrap();
+        assert_eq!(written, 2);
+
+        // Re-importing replaces the names
+        TalkgroupQueries::upsert_many(&pool, &system_id, &[alias(1001, "FD Disp")], "csv")
+            .await
+            .unwrap();
+
+        let found = TalkgroupQueries::find(&pool, &system_id, TalkgroupId::new(1001).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.label.as_deref(), Some("FD Disp"));
+        assert_eq!(found.group.as_deref(), Some("Fire"));
+        assert_eq!(found.source, "csv");
+
+        let all = TalkgroupQueries::list(&pool, &system_id).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(
+            TalkgroupQueries::find(&pool, &system_id, TalkgroupId::new(9).unwrap())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Fixtures shared by the storage tests.
+//!
+//! Database tests connect to `TEST_DATABASE_URL` through
+//! [`create_test_pool`] and skip themselves when it is unset or unreachable.
+//! [`test_call`] is a freshly uploaded call with only the fields every call
+//! has; tests fill in the rest with struct update syntax.
+
+#![allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+
+use crate::models::RadioCallDb;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::{Frequency, RadioId, SystemId, TalkgroupId};
+use sqlx::PgPool;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Connect to the test database, `None` when `TEST_DATABASE_URL` is unset or
+/// the database is unreachable
+pub(crate) async fn create_test_pool() -> Option<PgPool> {
+    // Migrations are not run here; the CI setup runs them before the tests
+    let database_url = std::env::var("TEST_DATABASE_URL").ok()?;
+    match PgPool::connect(&database_url).await {
+        Ok(pool) => Some(pool),
+        Err(e) => {
+            eprintln!("Failed to connect to database: {e}");
+            None
+        }
+    }
+}
+
+/// A call on `system_id` uploaded now, pending transcription
+pub(crate) fn test_call(system_id: &SystemId) -> RadioCallDb {
+    test_call_at(system_id, Utc::now())
+}
+
+/// A call on `system_id` recorded and uploaded at `at`, pending transcription
+pub(crate) fn test_call_at(system_id: &SystemId, at: DateTime<Utc>) -> RadioCallDb {
+    RadioCallDb {
+        id: Uuid::new_v4(),
+        created_at: at,
+        call_timestamp: at,
+        system_id: system_id.clone(),
+        system_label: None,
+        frequency: None,
+        talkgroup_id: None,
+        talkgroup_label: None,
+        talkgroup_group: None,
+        talkgroup_tag: None,
+        source_radio_id: None,
+        talker_alias: None,
+        audio_filename: None,
+        audio_file_path: None,
+        audio_size_bytes: None,
+        audio_content_type: None,
+        audio_sha256: None,
+        duration_seconds: None,
+        transcription_text: None,
+        transcription_confidence: None,
+        transcription_language: None,
+        transcription_status: Some("pending".to_string()),
+        speaker_segments: None,
+        speaker_count: None,
+        patches: None,
+        frequencies: None,
+        sources: None,
+        upload_ip: None,
+        upload_timestamp: at,
+        upload_api_key_id: None,
+        latitude: None,
+        longitude: None,
+    }
+}
+
+/// A transcribed call with every descriptive field filled in
+pub(crate) fn create_test_radio_call(system_id: &str, talkgroup_id: Option<i32>) -> RadioCallDb {
+    RadioCallDb {
+        system_label: Some("Test System".to_string()),
+        frequency: Some(Frequency::new(154_000_000).unwrap()),
+        talkgroup_id: talkgroup_id.and_then(|id| TalkgroupId::new(id).ok()),
+        talkgroup_label: Some("Test TG".to_string()),
+        talkgroup_group: Some("Public Safety".to_string()),
+        talkgroup_tag: Some("Police".to_string()),
+        source_radio_id: Some(RadioId::new(12345).unwrap()),
+        talker_alias: Some("OFFICER1".to_string()),
+        audio_filename: Some("test.mp3".to_string()),
+        audio_file_path: Some("/tmp/test.mp3".to_string()),
+        audio_size_bytes: Some(2_048_000),
+        duration_seconds: Some(rust_decimal::Decimal::try_from(30.5).unwrap()),
+        transcription_text: Some("Test transcription".to_string()),
+        transcription_confidence: Some(rust_decimal::Decimal::try_from(0.95).unwrap()),
+        transcription_status: Some("completed".to_string()),
+        speaker_segments: Some(serde_json::json!([{"speaker": "A", "start": 0.0, "end": 30.5}])),
+        speaker_count: Some(1),
+        patches: Some(
+            serde_json::to_string(&serde_json::json!([{"id": 1, "name": "Patch1"}])).unwrap(),
+        ),
+        frequencies: Some(serde_json::to_string(&serde_json::json!([154_000_000])).unwrap()),
+        sources: Some(
+            serde_json::to_string(&serde_json::json!([{"id": 1, "name": "Source1"}])).unwrap(),
+        ),
+        upload_ip: Some(sqlx::types::ipnetwork::IpNetwork::from(IpAddr::from([
+            127, 0, 0, 1,
+        ]))),
+        upload_api_key_id: Some("test_key_id".to_string()),
+        ..test_call(&SystemId::new(system_id).unwrap())
+    }
+}
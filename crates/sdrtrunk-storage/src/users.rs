@@ -0,0 +1,351 @@
+//! User accounts and login sessions.
+//!
+//! Users sign in to the web interface and API with a username and password
+//! and act with the permissions of their [`UserRole`]. Passwords are stored
+//! as Argon2 PHC strings; hashing and verification happen in the API. A
+//! successful login creates a session whose token is stored as a SHA-256 hash
+//! in `user_sessions`, like API keys.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use sdrtrunk_types::UserRole;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for user account operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `users` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct User {
+    /// User ID.
+    pub id: Uuid,
+    /// Login name.
+    pub username: String,
+    /// Argon2 PHC string of the password.
+    #[serde(skip_serializing, default)]
+    pub password_hash: String,
+    /// What the user may do.
+    #[sqlx(try_from = "String")]
+    pub role: UserRole,
+    /// Whether the user may sign in (false once disabled).
+    pub active: bool,
+    /// When the account was created.
+    pub created_at: DateTime<Utc>,
+    /// Last successful login.
+    pub last_login: Option<DateTime<Utc>>,
+}
+
+/// Fields for a new user.
+#[derive(Debug, Clone)]
+pub struct NewUser<'a> {
+    /// Login name.
+    pub username: &'a str,
+    /// Argon2 PHC string of the password.
+    pub password_hash: &'a str,
+    /// What the user may do.
+    pub role: UserRole,
+}
+
+/// Changes to a user; `None` fields are left as they are.
+#[derive(Debug, Clone, Default)]
+pub struct UserUpdate<'a> {
+    /// New role.
+    pub role: Option<UserRole>,
+    /// Enable or disable the account.
+    pub active: Option<bool>,
+    /// New Argon2 PHC string of the password.
+    pub password_hash: Option<&'a str>,
+}
+
+// ---------------------------------------------------------------------------
+// User operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for user accounts and sessions.
+#[derive(Debug)]
+pub struct UserQueries;
+
+impl UserQueries {
+    /// Store a new user.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the username is taken or the query fails.
+    pub async fn create(pool: &PgPool, user: &NewUser<'_>) -> Result<User> {
+        let user = sqlx::query_as::<_, User>(
+            r"
+            INSERT INTO users (username, password_hash, role)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            ",
+        )
+        .bind(user.username)
+        .bind(user.password_hash)
+        .bind(user.role.as_str())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// Look up a user by login name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn find_by_username(pool: &PgPool, username: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    /// List users, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn list(pool: &PgPool) -> Result<Vec<User>> {
+        let users = sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY created_at, id")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(users)
+    }
+
+    /// Apply `update` to a user.
+    ///
+    /// Disabling a user or changing their password ends their sessions.
+    /// Returns the updated user, or `None` if there is no such user.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn update(pool: &PgPool, id: Uuid, update: &UserUpdate<'_>) -> Result<Option<User>> {
+        let mut tx = pool.begin().await?;
+        let user = sqlx::query_as::<_, User>(
+            r"
+            UPDATE users
+            SET role = COALESCE($2, role),
+                active = COALESCE($3, active),
+                password_hash = COALESCE($4, password_hash)
+            WHERE id = $1
+            RETURNING *
+            ",
+        )
+        .bind(id)
+        .bind(update.role.map(UserRole::as_str))
+        .bind(update.active)
+        .bind(update.password_hash)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if user.is_some() && (update.active == Some(false) || update.password_hash.is_some()) {
+            let _ = sqlx::query("DELETE FROM user_sessions WHERE user_id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(user)
+    }
+
+    /// Delete a user and their sessions.
+    ///
+    /// Returns whether a user was deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // -----------------------------------------------------------------------
+    // Sessions
+    // -----------------------------------------------------------------------
+
+    /// Start a session for a user and record the login.
+    ///
+    /// Expired sessions are cleared out at the same time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn create_session(
+        pool: &PgPool,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        let _ = sqlx::query("DELETE FROM user_sessions WHERE expires_at <= NOW()")
+            .execute(&mut *tx)
+            .await?;
+        let _ = sqlx::query(
+            "INSERT INTO user_sessions (token_hash, user_id, expires_at) VALUES ($1, $2, $3)",
+        )
+        .bind(token_hash)
+        .bind(user_id)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await?;
+        let _ = sqlx::query("UPDATE users SET last_login = NOW() WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Look up the active user behind an unexpired session.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn session_user(pool: &PgPool, token_hash: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>(
+            r"
+            SELECT u.* FROM user_sessions s
+            JOIN users u ON u.id = s.user_id
+            WHERE s.token_hash = $1 AND s.expires_at > NOW() AND u.active
+            ",
+        )
+        .bind(token_hash)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// End a session.
+    ///
+    /// Returns whether a session was ended.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn delete_session(pool: &PgPool, token_hash: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM user_sessions WHERE token_hash = $1")
+            .bind(token_hash)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_pool;
+
+    #[tokio::test]
+    async fn test_user_session_lifecycle() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let username = format!("user_{}", &Uuid::new_v4().to_string()[..8]);
+        let user = UserQueries::create(
+            &pool,
+            &NewUser {
+                username: &username,
+                password_hash: "$argon2id$placeholder",
+                role: UserRole::Analyst,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.role, UserRole::Analyst);
+
+        let found = UserQueries::find_by_username(&pool, &username)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, user.id);
+
+        let token_hash = format!("{:0>64}", Uuid::new_v4().simple());
+        UserQueries::create_session(
+            &pool,
+            user.id,
+            &token_hash,
+            Utc::now() + chrono::Duration::hours(1),
+        )
+        .await
+        .unwrap();
+        let session_user = UserQueries::session_user(&pool, &token_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session_user.id, user.id);
+        assert!(session_user.last_login.is_some());
+
+        let promoted = UserQueries::update(
+            &pool,
+            user.id,
+            &UserUpdate {
+                role: Some(UserRole::Admin),
+                ..UserUpdate::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(promoted.role, UserRole::Admin);
+        assert!(
+            UserQueries::session_user(&pool, &token_hash)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        UserQueries::update(
+            &pool,
+            user.id,
+            &UserUpdate {
+                active: Some(false),
+                ..UserUpdate::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(
+            UserQueries::session_user(&pool, &token_hash)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        assert!(UserQueries::delete(&pool, user.id).await.unwrap());
+        assert!(!UserQueries::delete(&pool, user.id).await.unwrap());
+    }
+}
@@ -0,0 +1,162 @@
+//! Waveform peaks for the web player.
+//!
+//! Each call's recording is reduced to a fixed number of peak amplitudes,
+//! normalized to `0.0..=1.0`, which the player draws as a seekable waveform.
+//! Peaks live in `call_waveforms` (one row per call, removed with the call)
+//! so the recording only has to be decoded once.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json};
+use uuid::Uuid;
+
+/// Result type alias for waveform operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Stored waveform of one call.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CallWaveform {
+    /// Call the waveform belongs to.
+    pub call_id: Uuid,
+    /// Peak amplitude of each slice of the recording, in `0.0..=1.0`.
+    pub peaks: Json<Vec<f32>>,
+    /// Length of the decoded recording, in seconds.
+    pub duration_seconds: f64,
+    /// When the waveform was generated.
+    pub created_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Waveform operations
+// ---------------------------------------------------------------------------
+
+/// Waveform queries.
+#[derive(Debug)]
+pub struct WaveformQueries;
+
+impl WaveformQueries {
+    /// Store the waveform of a call, replacing any earlier one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call does not exist or the database query fails.
+    pub async fn save(
+        pool: &PgPool,
+        call_id: Uuid,
+        peaks: &[f32],
+        duration_seconds: f64,
+    ) -> Result<CallWaveform> {
+        let waveform = sqlx::query_as::<_, CallWaveform>(
+            r"
+            INSERT INTO call_waveforms (call_id, peaks, duration_seconds)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (call_id) DO UPDATE SET
+                peaks = EXCLUDED.peaks,
+                duration_seconds = EXCLUDED.duration_seconds,
+                created_at = NOW()
+            RETURNING call_id, peaks, duration_seconds, created_at
+            ",
+        )
+        .bind(call_id)
+        .bind(Json(peaks))
+        .bind(duration_seconds)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(waveform)
+    }
+
+    /// The stored waveform of a call, if one has been generated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn get(pool: &PgPool, call_id: Uuid) -> Result<Option<CallWaveform>> {
+        let waveform = sqlx::query_as::<_, CallWaveform>(
+            r"
+            SELECT call_id, peaks, duration_seconds, created_at
+            FROM call_waveforms
+            WHERE call_id = $1
+            ",
+        )
+        .bind(call_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(waveform)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    clippy::float_cmp,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+    use sdrtrunk_types::SystemId;
+
+    fn call(system_id: &SystemId) -> RadioCallDb {
+        RadioCallDb {
+            transcription_status: None,
+            ..test_call(system_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let system_id = SystemId::new(format!("wav_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call_id = RadioCallQueries::insert(&pool, &call(&system_id))
+            .await
+            .unwrap();
+
+        assert!(
+            WaveformQueries::get(&pool, call_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        WaveformQueries::save(&pool, call_id, &[0.0, 0.5, 1.0], 1.5)
+            .await
+            .unwrap();
+        let saved = WaveformQueries::save(&pool, call_id, &[0.25, 0.75], 2.0)
+            .await
+            .unwrap();
+        assert_eq!(saved.peaks.0, vec![0.25, 0.75]);
+
+        let stored = WaveformQueries::get(&pool, call_id).await.unwrap().unwrap();
+        assert_eq!(stored.call_id, call_id);
+        assert_eq!(stored.peaks.0, vec![0.25, 0.75]);
+        assert_eq!(stored.duration_seconds, 2.0);
+
+        // Waveforms cannot exist without their call
+        assert!(
+            WaveformQueries::save(&pool, Uuid::new_v4(), &[0.5], 1.0)
+                .await
+                .is_err()
+        );
+    }
+}
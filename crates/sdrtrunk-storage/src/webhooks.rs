@@ -0,0 +1,312 @@
+//! Webhook delivery log.
+//!
+//! Each call event sent to a webhook endpoint is written to
+//! `webhook_deliveries` as a `pending` row before it is sent. The API server
+//! claims due rows, posts them, and records the outcome: `delivered`, back to
+//! `pending` with a later `next_attempt_at` for a retry, or `failed` once the
+//! attempts are used up. Rows stay in the table as the delivery history.
+
+use crate::error::StorageError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Result type alias for webhook operations.
+type Result<T> = std::result::Result<T, StorageError>;
+
+/// Delivery waiting to be sent (or retried).
+pub const PENDING: &str = "pending";
+/// Delivery accepted by its endpoint.
+pub const DELIVERED: &str = "delivered";
+/// Delivery abandoned after its last attempt.
+pub const FAILED: &str = "failed";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A row from the `webhook_deliveries` table.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    /// Delivery ID, also sent to the endpoint.
+    pub id: Uuid,
+    /// Event name (e.g. `call_uploaded`).
+    pub event: String,
+    /// Endpoint the payload is posted to.
+    pub endpoint_url: String,
+    /// Call the event is about (`None` once the call is deleted).
+    pub call_id: Option<Uuid>,
+    /// JSON body sent to the endpoint.
+    pub payload: serde_json::Value,
+    /// `pending`, `delivered`, or `failed`.
+    pub status: String,
+    /// Attempts made so far.
+    pub attempts: i32,
+    /// When the next attempt is due.
+    pub next_attempt_at: DateTime<Utc>,
+    /// HTTP status of the last response, if one was received.
+    pub response_status: Option<i32>,
+    /// Error from the last failed attempt.
+    pub last_error: Option<String>,
+    /// When the event was queued.
+    pub created_at: DateTime<Utc>,
+    /// When the endpoint accepted the payload.
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// An event to queue for one endpoint.
+#[derive(Debug, Clone)]
+pub struct NewWebhookDelivery<'a> {
+    /// Event name.
+    pub event: &'a str,
+    /// Endpoint to post to.
+    pub endpoint_url: &'a str,
+    /// Call the event is about.
+    pub call_id: Option<Uuid>,
+    /// JSON body to send.
+    pub payload: &'a serde_json::Value,
+}
+
+/// Outcome of one delivery attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookAttempt {
+    /// HTTP status of the response, if one was received.
+    pub response_status: Option<i32>,
+    /// Why the attempt failed (`None` when it succeeded).
+    pub error: Option<String>,
+    /// When to try again after a failure (`None` to give up).
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+// ---------------------------------------------------------------------------
+// Webhook operations
+// ---------------------------------------------------------------------------
+
+/// Database operations for webhook deliveries.
+#[derive(Debug)]
+pub struct WebhookQueries;
+
+impl WebhookQueries {
+    /// Queue an event for delivery, due immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn enqueue(pool: &PgPool, delivery: &NewWebhookDelivery<'_>) -> Result<Uuid> {
+        let id = sqlx::query_scalar(
+            r"
+            INSERT INTO webhook_deliveries (event, endpoint_url, call_id, payload)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            ",
+        )
+        .bind(delivery.event)
+        .bind(delivery.endpoint_url)
+        .bind(delivery.call_id)
+        .bind(delivery.payload)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Claim up to `limit` due deliveries, oldest first.
+    ///
+    /// Claimed rows are pushed `lease_seconds` into the future so another
+    /// server (or this one after a crash) does not send them concurrently;
+    /// [`Self::record_attempt`] replaces the lease with the real outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn claim_due(
+        pool: &PgPool,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            ",
+        )
+        .bind(limit)
+        .bind(lease_seconds)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Record the outcome of an attempt.
+    ///
+    /// A successful attempt marks the delivery `delivered`; a failed one
+    /// leaves it `pending` until `retry_at`, or marks it `failed` without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn record_attempt(pool: &PgPool, id: Uuid, attempt: &WebhookAttempt) -> Result<()> {
+        let _ = sqlx::query(
+            r"
+            UPDATE webhook_deliveries
+            SET attempts = attempts + 1,
+                response_status = $2,
+                last_error = $3,
+                status = CASE
+                    WHEN $3::TEXT IS NULL THEN 'delivered'
+                    WHEN $4::TIMESTAMPTZ IS NULL THEN 'failed'
+                    ELSE 'pending'
+                END,
+                next_attempt_at = COALESCE($4, next_attempt_at),
+                delivered_at = CASE WHEN $3::TEXT IS NULL THEN NOW() END
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .bind(attempt.response_status)
+        .bind(attempt.error.as_deref())
+        .bind(attempt.retry_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deliveries for a call, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the query fails.
+    pub async fn for_call(pool: &PgPool, call_id: Uuid) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT * FROM webhook_deliveries WHERE call_id = $1 ORDER BY created_at, id",
+        )
+        .bind(call_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::missing_panics_doc,
+    unused_results
+)]
+mod tests {
+    use super::*;
+    use crate::models::RadioCallDb;
+    use crate::queries::RadioCallQueries;
+    use crate::test_support::{create_test_pool, test_call};
+    use sdrtrunk_types::SystemId;
+    use serde_json::json;
+
+    async fn insert_call(pool: &PgPool) -> Uuid {
+        let system_id = SystemId::new(format!("wh_{}", &Uuid::new_v4().to_string()[..8])).unwrap();
+        let call = RadioCallDb {
+            transcription_status: None,
+            ..test_call(&system_id)
+        };
+        RadioCallQueries::insert(pool, &call).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_delivery_lifecycle() {
+        let Some(pool) = create_test_pool().await else {
+            eprintln!("Skipping test: TEST_DATABASE_URL not set or database not available");
+            return;
+        };
+
+        let call_id = insert_call(&pool).await;
+        let payload = json!({"event": "call_uploaded", "call": {"call_id": call_id}});
+        let mut ids = Vec::new();
+        for url in ["https://a.example/hook", "https://b.example/hook"] {
+            let delivery = NewWebhookDelivery {
+                event: "call_uploaded",
+                endpoint_url: url,
+                call_id: Some(call_id),
+                payload: &payload,
+            };
+            ids.push(WebhookQueries::enqueue(&pool, &delivery).await.unwrap());
+        }
+
+        let claimed = WebhookQueries::claim_due(&pool, 1000, 60).await.unwrap();
+        let mine: Vec<_> = claimed.iter().filter(|d| ids.contains(&d.id)).collect();
+        assert_eq!(mine.len(), 2);
+        assert_eq!(mine[0].payload, payload);
+        assert!(mine.iter().all(|d| d.next_attempt_at > Utc::now()));
+        // Leased rows are not handed out again
+        let again = WebhookQueries::claim_due(&pool, 1000, 60).await.unwrap();
+        assert!(again.iter().all(|d| !ids.contains(&d.id)));
+
+        WebhookQueries::record_attempt(
+            &pool,
+            ids[0],
+            &WebhookAttempt {
+                response_status: Some(204),
+                error: None,
+                retry_at: None,
+            },
+        )
+        .await
+        .unwrap();
+        let retry_at = Utc::now() + chrono::Duration::seconds(30);
+        WebhookQueries::record_attempt(
+            &pool,
+            ids[1],
+            &WebhookAttempt {
+                response_status: Some(503),
+                error: Some("HTTP 503".to_string()),
+                retry_at: Some(retry_at),
+            },
+        )
+        .await
+        .unwrap();
+
+        let log = WebhookQueries::for_call(&pool, call_id).await.unwrap();
+        let delivered = log.iter().find(|d| d.id == ids[0]).unwrap();
+        assert_eq!(delivered.status, DELIVERED);
+        assert_eq!(delivered.attempts, 1);
+        assert!(delivered.delivered_at.is_some());
+        let retrying = log.iter().find(|d| d.id == ids[1]).unwrap();
+        assert_eq!(retrying.status, PENDING);
+        assert_eq!(retrying.response_status, Some(503));
+        assert!(retrying.delivered_at.is_none());
+
+        WebhookQueries::record_attempt(
+            &pool,
+            ids[1],
+            &WebhookAttempt {
+                response_status: None,
+                error: Some("connection refused".to_string()),
+                retry_at: None,
+            },
+        )
+        .await
+        .unwrap();
+        let log = WebhookQueries::for_call(&pool, call_id).await.unwrap();
+        let failed = log.iter().find(|d| d.id == ids[1]).unwrap();
+        assert_eq!(failed.status, FAILED);
+        assert_eq!(failed.attempts, 2);
+        assert_eq!(failed.last_error.as_deref(), Some("connection refused"));
+    }
+}
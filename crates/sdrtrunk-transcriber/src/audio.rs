@@ -0,0 +1,258 @@
+//! Audio normalization ahead of transcription
+//!
+//! Uploads arrive in whatever encoding the recorder produced. Before a file
+//! is handed to `WhisperX` it is transcoded with ffmpeg to mono PCM WAV at
+//! the configured sample rate (16 kHz by default), which the Python service
+//! decodes reliably. Converted files are cached, keyed on the source file's
+//! path, size, and modification time plus the output format, so retries and
+//! re-transcriptions reuse the earlier conversion.
+
+use crate::error::{TranscriptionError, TranscriptionResult};
+use sdrtrunk_protocol::config::AudioTranscodeConfig;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::UNIX_EPOCH;
+use tokio::process::Command;
+use tracing::debug;
+
+/// Directory created beside an upload when no cache directory is configured
+const DEFAULT_CACHE_DIR: &str = ".transcoded";
+
+/// Converts uploads to the format sent to the transcription backend
+#[derive(Debug, Clone)]
+pub struct AudioTranscoder {
+    config: AudioTranscodeConfig,
+}
+
+impl AudioTranscoder {
+    /// Create a transcoder using `config`
+    #[must_use]
+    pub const fn new(config: AudioTranscodeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Return the file to transcribe for `input`
+    ///
+    /// With transcoding enabled this is the cached WAV conversion, created
+    /// on first use; otherwise `input` itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileNotFound` if `input` does not exist, `Subprocess` if
+    /// ffmpeg cannot be started, and `ProcessingFailed` if the conversion
+    /// fails.
+    pub async fn prepare(&self, input: &Path) -> TranscriptionResult<PathBuf> {
+        if !self.config.enabled {
+            return Ok(input.to_path_buf());
+        }
+
+        let output = self.cache_path(input).await?;
+        if tokio::fs::try_exists(&output).await? {
+            debug!("Using cached conversion {:?} for {:?}", output, input);
+            return Ok(output);
+        }
+
+        if let Some(dir) = output.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+
+        // Convert to a temporary name so a concurrent caller never sees a
+        // partially written file
+        let partial = output.with_extension(format!("{}.partial", uuid::Uuid::new_v4()));
+        if let Err(e) = self.transcode(input, &partial).await {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&partial, &output).await?;
+
+        debug!("Converted {:?} to {:?}", input, output);
+        Ok(output)
+    }
+
+    /// Where the conversion of `input` is cached
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileNotFound` if `input` does not exist, or an I/O error if
+    /// its metadata cannot be read.
+    pub async fn cache_path(&self, input: &Path) -> TranscriptionResult<PathBuf> {
+        let metadata = tokio::fs::metadata(input).await.map_err(|e| {
+            if e.kind() == ErrorKind::NotFound {
+                TranscriptionError::file_not_found(input)
+            } else {
+                e.into()
+            }
+        })?;
+
+        let mut hasher = DefaultHasher::new();
+        input.hash(&mut hasher);
+        metadata.len().hash(&mut hasher);
+        metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .hash(&mut hasher);
+        self.config.sample_rate.hash(&mut hasher);
+        self.config.channels.hash(&mut hasher);
+
+        let stem = input
+            .file_stem()
+            .map_or_else(|| "audio".into(), |stem| stem.to_string_lossy());
+        let dir = self.config.cache_dir.clone().unwrap_or_else(|| {
+            input
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(DEFAULT_CACHE_DIR)
+        });
+
+        Ok(dir.join(format!("{stem}-{:016x}.wav", hasher.finish())))
+    }
+
+    /// Run ffmpeg to convert `input` into `output`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Subprocess` if ffmpeg cannot be started and
+    /// `ProcessingFailed` if it exits unsuccessfully.
+    async fn transcode(&self, input: &Path, output: &Path) -> TranscriptionResult<()> {
+        let result = Command::new(&self.config.ffmpeg_path)
+            .args(["-nostdin", "-hide_banner", "-loglevel", "error", "-y", "-i"])
+            .arg(input)
+            .args(["-vn", "-ac"])
+            .arg(self.config.channels.to_string())
+            .arg("-ar")
+            .arg(self.config.sample_rate.to_string())
+            .args(["-c:a", "pcm_s16le", "-f", "wav"])
+            .arg(output)
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|e| {
+                TranscriptionError::subprocess(format!(
+                    "Failed to run {}: {e}",
+                    self.config.ffmpeg_path.display()
+                ))
+            })?;
+
+        if !result.status.success() {
+            return Err(TranscriptionError::processing_failed(format!(
+                "ffmpeg could not convert {}: {}",
+                input.display(),
+                String::from_utf8_lossy(&result.stderr).trim()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    fn with_ffmpeg(ffmpeg_path: PathBuf, cache_dir: Option<PathBuf>) -> AudioTranscoder {
+        AudioTranscoder::new(AudioTranscodeConfig {
+            ffmpeg_path,
+            cache_dir,
+            ..AudioTranscodeConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_disabled_passes_original_through() {
+        let transcoder = AudioTranscoder::new(AudioTranscodeConfig {
+            enabled: false,
+            ..AudioTranscodeConfig::default()
+        });
+        let input = Path::new("/does/not/exist.mp3");
+        assert_eq!(transcoder.prepare(input).await.unwrap(), input);
+    }
+
+    #[tokio::test]
+    async fn test_cache_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("call.mp3");
+        std::fs::write(&input, b"ID3 fake mp3").unwrap();
+
+        let beside = with_ffmpeg("ffmpeg".into(), None);
+        let path = beside.cache_path(&input).await.unwrap();
+        assert_eq!(path.parent().unwrap(), dir.path().join(DEFAULT_CACHE_DIR));
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("call-") && name.ends_with(".wav"));
+        assert_eq!(beside.cache_path(&input).await.unwrap(), path);
+
+        // A different output format gets its own cache entry
+        let stereo = AudioTranscoder::new(AudioTranscodeConfig {
+            channels: 2,
+            ..AudioTranscodeConfig::default()
+        });
+        assert_ne!(stereo.cache_path(&input).await.unwrap(), path);
+
+        let shared = with_ffmpeg("ffmpeg".into(), Some(PathBuf::from("/cache")));
+        let cached = shared.cache_path(&input).await.unwrap();
+        assert_eq!(cached.parent().unwrap(), Path::new("/cache"));
+
+        let missing = beside.cache_path(&dir.path().join("gone.mp3")).await;
+        assert!(matches!(
+            missing,
+            Err(TranscriptionError::FileNotFound { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_prepare_converts_once() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("call.mp3");
+        std::fs::write(&input, b"ID3 fake mp3").unwrap();
+
+        // Stand-in for ffmpeg: records each run and writes the output file
+        let runs = dir.path().join("runs");
+        let ffmpeg = dir.path().join("ffmpeg");
+        std::fs::write(
+            &ffmpeg,
+            format!(
+                "#!/bin/sh\necho run >> {}\nfor last; do :; done\necho RIFF > \"$last\"\n",
+                runs.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&ffmpeg, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let transcoder = with_ffmpeg(ffmpeg, None);
+        let first = transcoder.prepare(&input).await.unwrap();
+        let second = transcoder.prepare(&input).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "RIFF\n");
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 1);
+
+        let failing = AudioTranscoder::new(AudioTranscodeConfig {
+            ffmpeg_path: PathBuf::from("false"),
+            cache_dir: Some(dir.path().join("other")),
+            ..AudioTranscodeConfig::default()
+        });
+        assert!(matches!(
+            failing.prepare(&input).await,
+            Err(TranscriptionError::ProcessingFailed { .. })
+        ));
+        // The failed conversion leaves nothing behind
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("other")).unwrap().count(),
+            0
+        );
+
+        let missing = with_ffmpeg(PathBuf::from("/nonexistent/ffmpeg"), None);
+        let other_input = dir.path().join("other.mp3");
+        std::fs::write(&other_input, b"ID3").unwrap();
+        assert!(matches!(
+            missing.prepare(&other_input).await,
+            Err(TranscriptionError::Subprocess { .. })
+        ));
+    }
+}
@@ -6,12 +6,14 @@
 
 #![forbid(unsafe_code)]
 
+pub mod audio;
 pub mod error;
 pub mod mock;
 pub mod service;
 pub mod types;
 pub mod whisperx;
 
+pub use audio::AudioTranscoder;
 pub use error::{TranscriptionError, TranscriptionResult};
 pub use sdrtrunk_protocol::config::TranscriptionConfig;
 pub use sdrtrunk_types::TranscriptionStatus;
@@ -106,6 +106,29 @@ pub struct TranscriptionOptions {
 
     /// Maximum audio duration to process (seconds)
     pub max_duration: Option<f64>,
+
+    /// Text the model is primed with, such as a list of domain terms
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+
+    /// Comma-separated terms the decoder should favor (`WhisperX` hotwords)
+    #[serde(default)]
+    pub hotwords: Option<String>,
+}
+
+impl TranscriptionOptions {
+    /// Prime the model with domain `terms` (unit names, street names,
+    /// ten-codes), as both the initial prompt and hotwords
+    ///
+    /// An empty list leaves the options unchanged.
+    #[must_use]
+    pub fn with_vocabulary(mut self, terms: &[&str]) -> Self {
+        if !terms.is_empty() {
+            self.initial_prompt = Some(format!("{}.", terms.join(", ")));
+            self.hotwords = Some(terms.join(","));
+        }
+        self
+    }
 }
 
 impl Default for TranscriptionOptions {
@@ -119,6 +142,8 @@ impl Default for TranscriptionOptions {
             word_timestamps: true,
             return_confidence: true,
             max_duration: Some(3600.0), // 1 hour max
+            initial_prompt: None,
+            hotwords: None,
         }
     }
 }
@@ -271,6 +296,17 @@ mod tests {
         assert_eq!(format!("{}", TranscriptionStatus::Cancelled), "cancelled");
     }
 
+    #[test]
+    fn test_options_with_vocabulary() {
+        let options = TranscriptionOptions::default().with_vocabulary(&["10-4", "Engine 5"]);
+        assert_eq!(options.initial_prompt.as_deref(), Some("10-4, Engine 5."));
+        assert_eq!(options.hotwords.as_deref(), Some("10-4,Engine 5"));
+
+        let options = TranscriptionOptions::default().with_vocabulary(&[]);
+        assert!(options.initial_prompt.is_none());
+        assert!(options.hotwords.is_none());
+    }
+
     #[test]
     fn test_transcription_request() {
         let call_id = Uuid::new_v4();
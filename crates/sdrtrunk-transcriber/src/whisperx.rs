@@ -1,5 +1,6 @@
 //! `WhisperX` transcription service implementation
 
+use crate::audio::AudioTranscoder;
 use crate::error::{TranscriptionError, TranscriptionResult};
 use crate::service::{AudioValidation, ServiceCapabilities, ServiceHealth, TranscriptionService};
 use crate::types::{
@@ -44,6 +45,8 @@ struct PythonOptions {
     word_timestamps: bool,
     return_confidence: bool,
     max_duration: Option<f64>,
+    initial_prompt: Option<String>,
+    hotwords: Option<String>,
 }
 
 /// Python service response format
@@ -139,6 +142,9 @@ pub struct WhisperXService {
 
     /// Request tracking
     active_requests: ActiveRequestMap,
+
+    /// Converts uploads before they are sent to the service
+    transcoder: AudioTranscoder,
 }
 
 impl std::fmt::Debug for WhisperXService {
@@ -180,6 +186,7 @@ impl WhisperXService {
             })?;
 
         Ok(Self {
+            transcoder: AudioTranscoder::new(config.audio.clone()),
             config,
             service_url,
             client,
@@ -18,6 +18,7 @@ struct StatusResponse {
 impl TranscriptionService for WhisperXService {
     async fn initialize(&mut self, config: &TranscriptionConfig) -> TranscriptionResult<()> {
         self.config = config.clone();
+        self.transcoder = AudioTranscoder::new(config.audio.clone());
 
         // Start Python service if local path configured, otherwise connect to external
         if self.config.python_path.is_some() {
@@ -67,6 +68,9 @@ impl TranscriptionService for WhisperXService {
         }
         drop(initialized);
 
+        // Normalize the upload so the service only ever decodes plain WAV
+        let audio_path = self.transcoder.prepare(&request.audio_path).await?;
+
         // Track request
         {
             let mut requests = self.active_requests.write().await;
@@ -78,7 +82,7 @@ impl TranscriptionService for WhisperXService {
         let py_request = PythonRequest {
             id: request.id,
             call_id: request.call_id,
-            audio_path: request.audio_path.to_string_lossy().to_string(),
+            audio_path: audio_path.to_string_lossy().to_string(),
             requested_at: request.requested_at.to_rfc3339(),
             options: PythonOptions {
                 language: request.options.language.clone(),
@@ -89,6 +93,8 @@ impl TranscriptionService for WhisperXService {
                 word_timestamps: request.options.word_timestamps,
                 return_confidence: request.options.return_confidence,
                 max_duration: request.options.max_duration,
+                initial_prompt: request.options.initial_prompt.clone(),
+                hotwords: request.options.hotwords.clone(),
             },
             retry_count: request.retry_count,
             priority: request.priority,
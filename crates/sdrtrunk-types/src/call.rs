@@ -114,6 +114,10 @@ pub struct RadioCall {
     pub source_radio_id: Option<RadioId>,
     /// Talker alias
     pub talker_alias: Option<String>,
+    /// Latitude where the call was heard (WGS 84 degrees)
+    pub latitude: Option<f64>,
+    /// Longitude where the call was heard (WGS 84 degrees)
+    pub longitude: Option<f64>,
     /// Audio filename
     pub audio_filename: Option<String>,
     /// Audio file path
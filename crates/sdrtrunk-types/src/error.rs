@@ -59,6 +59,13 @@ pub enum ValidationError {
         format: String,
     },
 
+    /// Unknown user role
+    #[error("invalid role: {value} (must be admin, analyst, or read_only)")]
+    InvalidRole {
+        /// The invalid value
+        value: String,
+    },
+
     /// Generic validation error
     #[error("validation failed: {field} - {message}")]
     Generic {
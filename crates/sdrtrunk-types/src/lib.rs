@@ -37,6 +37,7 @@
 //! - [`frequency`]: Radio frequencies in Hz
 //! - [`call`]: Call identifiers and the `RadioCall` domain type
 //! - [`status`]: Transcription status enumeration
+//! - [`role`]: User roles for access control
 //! - [`error`]: Validation and transport error types
 //! - [`app_error`]: Application-level error type
 
@@ -45,6 +46,7 @@ pub mod call;
 pub mod error;
 pub mod frequency;
 pub mod radio;
+pub mod role;
 pub mod status;
 pub mod system;
 pub mod talkgroup;
@@ -54,6 +56,7 @@ pub use call::{CallId, RadioCall};
 pub use error::{Error, Result, TransportError, ValidationError};
 pub use frequency::Frequency;
 pub use radio::RadioId;
+pub use role::UserRole;
 pub use status::TranscriptionStatus;
 pub use system::SystemId;
 pub use talkgroup::{TalkgroupId, TalkgroupLabel};
@@ -0,0 +1,100 @@
+//! User role types.
+
+use crate::error::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// What a user account may do.
+///
+/// Roles are ordered: each one may do everything the roles below it may.
+///
+/// # Examples
+///
+/// ```
+/// use sdrtrunk_types::UserRole;
+///
+/// let role: UserRole = "analyst".parse().unwrap();
+/// assert!(role.allows(UserRole::ReadOnly));
+/// assert!(!role.allows(UserRole::Admin));
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    /// Browse calls, transcriptions, and statistics
+    ReadOnly,
+    /// Also submit feedback, manage alert rules, and re-queue transcriptions
+    Analyst,
+    /// Also manage keys, users, and system maintenance
+    Admin,
+}
+
+impl UserRole {
+    /// The role as stored in the database.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Analyst => "analyst",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Whether this role may do what `required` may.
+    #[must_use]
+    pub fn allows(self, required: Self) -> bool {
+        self >= required
+    }
+}
+
+impl fmt::Display for UserRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for UserRole {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read_only" => Ok(Self::ReadOnly),
+            "analyst" => Ok(Self::Analyst),
+            "admin" => Ok(Self::Admin),
+            _ => Err(ValidationError::InvalidRole {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<String> for UserRole {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_are_ordered() {
+        assert!(UserRole::Admin.allows(UserRole::Analyst));
+        assert!(UserRole::Analyst.allows(UserRole::ReadOnly));
+        assert!(UserRole::Analyst.allows(UserRole::Analyst));
+        assert!(!UserRole::ReadOnly.allows(UserRole::Analyst));
+        assert!(!UserRole::Analyst.allows(UserRole::Admin));
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        for role in [UserRole::ReadOnly, UserRole::Analyst, UserRole::Admin] {
+            assert_eq!(role.as_str().parse::<UserRole>().unwrap(), role);
+            assert_eq!(serde_json::to_string(&role).unwrap(), format!("\"{role}\""));
+        }
+        assert!("root".parse::<UserRole>().is_err());
+    }
+}
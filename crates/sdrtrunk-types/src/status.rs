@@ -23,10 +23,18 @@ pub enum TranscriptionStatus {
     Processing,
     /// Transcription completed successfully
     Completed,
+    /// Transcription completed with a confidence below the configured minimum
+    NeedsReview,
     /// Transcription failed
     Failed,
     /// Transcription cancelled
     Cancelled,
+    /// Not transcribed because the call's talkgroup opts out
+    Skipped,
+    /// Call registered ahead of its recording, which has not arrived yet
+    AwaitingAudio,
+    /// Stored while the queue was nearly full, to be queued once it drains
+    Deferred,
     /// No transcription requested
     None,
 }
@@ -37,19 +45,45 @@ impl Default for TranscriptionStatus {
     }
 }
 
-impl fmt::Display for TranscriptionStatus {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl TranscriptionStatus {
+    /// Status of a transcription that finished with `confidence`
+    ///
+    /// Transcriptions scoring below `min_confidence` need review. Ones without
+    /// a score, or with no minimum configured, are completed.
+    #[must_use]
+    pub fn finished(confidence: Option<f32>, min_confidence: Option<f32>) -> Self {
+        match (confidence, min_confidence) {
+            (Some(confidence), Some(min_confidence)) if confidence < min_confidence => {
+                Self::NeedsReview
+            }
+            _ => Self::Completed,
+        }
+    }
+
+    /// Name stored in the `transcription_status` column
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
         match self {
-            Self::Pending => write!(f, "pending"),
-            Self::Processing => write!(f, "processing"),
-            Self::Completed => write!(f, "completed"),
-            Self::Failed => write!(f, "failed"),
-            Self::Cancelled => write!(f, "cancelled"),
-            Self::None => write!(f, "none"),
+            Self::Pending => "pending",
+            Self::Processing => "processing",
+            Self::Completed => "completed",
+            Self::NeedsReview => "needs_review",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+            Self::Skipped => "skipped",
+            Self::AwaitingAudio => "awaiting_audio",
+            Self::Deferred => "deferred",
+            Self::None => "none",
         }
     }
 }
 
+impl fmt::Display for TranscriptionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::missing_panics_doc)]
 mod tests {
@@ -65,11 +99,37 @@ mod tests {
         assert_eq!(format!("{}", TranscriptionStatus::Pending), "pending");
         assert_eq!(format!("{}", TranscriptionStatus::Processing), "processing");
         assert_eq!(format!("{}", TranscriptionStatus::Completed), "completed");
+        assert_eq!(
+            format!("{}", TranscriptionStatus::NeedsReview),
+            "needs_review"
+        );
         assert_eq!(format!("{}", TranscriptionStatus::Failed), "failed");
         assert_eq!(format!("{}", TranscriptionStatus::Cancelled), "cancelled");
+        assert_eq!(format!("{}", TranscriptionStatus::Skipped), "skipped");
+        assert_eq!(
+            format!("{}", TranscriptionStatus::AwaitingAudio),
+            "awaiting_audio"
+        );
+        assert_eq!(format!("{}", TranscriptionStatus::Deferred), "deferred");
         assert_eq!(format!("{}", TranscriptionStatus::None), "none");
     }
 
+    #[test]
+    fn test_finished_flags_low_confidence() {
+        use TranscriptionStatus::{Completed, NeedsReview};
+
+        assert_eq!(
+            TranscriptionStatus::finished(Some(0.4), Some(0.6)),
+            NeedsReview
+        );
+        assert_eq!(
+            TranscriptionStatus::finished(Some(0.6), Some(0.6)),
+            Completed
+        );
+        assert_eq!(TranscriptionStatus::finished(Some(0.4), None), Completed);
+        assert_eq!(TranscriptionStatus::finished(None, Some(0.6)), Completed);
+    }
+
     #[test]
     fn test_serialization_roundtrip() {
         let status = TranscriptionStatus::Completed;
@@ -78,5 +138,8 @@ mod tests {
 
         let deserialized: TranscriptionStatus = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, TranscriptionStatus::Completed);
+
+        let serialized = serde_json::to_string(&TranscriptionStatus::NeedsReview).unwrap();
+        assert_eq!(serialized, "\"needs_review\"");
     }
 }
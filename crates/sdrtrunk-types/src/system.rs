@@ -2,15 +2,18 @@
 
 use crate::error::ValidationError;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 /// System identifier newtype.
 ///
 /// Wraps a String with validation:
 /// - Cannot be empty
 /// - Maximum 50 characters
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
+///
+/// Deserialization applies the same validation, so invalid IDs in request
+/// bodies and query strings are rejected at the boundary.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct SystemId(String);
 
 impl SystemId {
@@ -62,6 +65,34 @@ impl fmt::Display for SystemId {
     }
 }
 
+impl TryFrom<String> for SystemId {
+    type Error = ValidationError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl FromStr for SystemId {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl From<SystemId> for String {
+    fn from(id: SystemId) -> Self {
+        id.0
+    }
+}
+
+impl AsRef<str> for SystemId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
 #[cfg(feature = "sqlx")]
 mod sqlx_impl {
     use super::SystemId;
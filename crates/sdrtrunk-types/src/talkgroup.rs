@@ -2,14 +2,16 @@
 
 use crate::error::ValidationError;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 /// Talkgroup identifier newtype.
 ///
 /// Wraps an i32 with validation:
 /// - Must be positive (> 0)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
+///
+/// Deserialization applies the same validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "i32", into = "i32")]
 pub struct TalkgroupId(i32);
 
 impl TalkgroupId {
@@ -47,6 +49,35 @@ impl fmt::Display for TalkgroupId {
     }
 }
 
+impl TryFrom<i32> for TalkgroupId {
+    type Error = ValidationError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl FromStr for TalkgroupId {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s
+            .trim()
+            .parse::<i32>()
+            .map_err(|e| ValidationError::Generic {
+                field: "talkgroup_id".to_string(),
+                message: e.to_string(),
+            })?;
+        Self::new(value)
+    }
+}
+
+impl From<TalkgroupId> for i32 {
+    fn from(id: TalkgroupId) -> Self {
+        id.0
+    }
+}
+
 #[cfg(feature = "sqlx")]
 mod sqlx_impl {
     use super::TalkgroupId;
@@ -45,3 +45,26 @@ fn test_radio_id_negative_rejected() {
         Err(ValidationError::InvalidRadioId { value: -1 })
     ));
 }
+
+#[test]
+fn test_talkgroup_id_parse() {
+    let id: TalkgroupId = "12345".parse().unwrap();
+    assert_eq!(id.as_i32(), 12345);
+    assert!(matches!(
+        "0".parse::<TalkgroupId>(),
+        Err(ValidationError::InvalidTalkgroupId { value: 0 })
+    ));
+    assert!(matches!(
+        "dispatch".parse::<TalkgroupId>(),
+        Err(ValidationError::Generic { .. })
+    ));
+}
+
+#[test]
+fn test_talkgroup_id_serde_validates() {
+    let id: TalkgroupId = serde_json::from_str("41001").unwrap();
+    assert_eq!(serde_json::to_string(&id).unwrap(), "41001");
+
+    assert!(serde_json::from_str::<TalkgroupId>("0").is_err());
+    assert!(serde_json::from_str::<TalkgroupId>("-5").is_err());
+}
@@ -37,3 +37,20 @@ fn test_system_id_display() {
     let id = SystemId::new("test").unwrap();
     assert_eq!(format!("{id}"), "test");
 }
+
+#[test]
+fn test_system_id_parse() {
+    let id: SystemId = "county".parse().unwrap();
+    assert_eq!(id.as_str(), "county");
+    assert!("".parse::<SystemId>().is_err());
+}
+
+#[test]
+fn test_system_id_serde_validates() {
+    let id: SystemId = serde_json::from_str(r#""metro""#).unwrap();
+    assert_eq!(serde_json::to_string(&id).unwrap(), r#""metro""#);
+
+    assert!(serde_json::from_str::<SystemId>(r#""""#).is_err());
+    let long = format!("\"{}\"", "a".repeat(51));
+    assert!(serde_json::from_str::<SystemId>(&long).is_err());
+}
@@ -2,14 +2,89 @@
 
 use reqwest::Client;
 use sdrtrunk_types::{AppError, AppResult as Result};
+use serde::{Deserialize, Serialize};
 
 // Import actual types from API handlers
+pub use sdrtrunk_api::handlers::auth::{LoginResponse, SessionResponse};
 pub use sdrtrunk_api::handlers::calls::{
     CallSummary, ListCallsQuery, ListCallsResponse, PaginationInfo,
 };
+pub use sdrtrunk_api::handlers::compare::CompareCallsQuery;
+pub use sdrtrunk_api::handlers::conversations::ConversationListQuery;
+pub use sdrtrunk_api::handlers::geo::GeoCallsQuery;
 pub use sdrtrunk_api::handlers::stats::{
     ActivityPeriod, GlobalStatsResponse, StorageStats, SystemSummary,
 };
+pub use sdrtrunk_api::quotas::SystemStorage;
+
+/// Live figures shown on the dashboard cards
+///
+/// Built from the backend's global and queue statistics; the web server
+/// pushes a fresh copy to browsers over its WebSocket.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DashboardStats {
+    /// Calls heard in each of the last 24 hours, oldest first
+    pub calls_per_hour: Vec<i64>,
+    /// Calls received in the last 24 hours
+    pub calls_last_24h: i64,
+    /// Transcription jobs waiting for a worker
+    pub queue_depth: i64,
+    /// Transcription jobs being processed
+    pub processing: i64,
+    /// Calls not yet transcribed (waiting plus in progress)
+    pub transcription_backlog: i64,
+    /// Fraction of finished transcription jobs that failed, if any finished
+    pub error_rate: Option<f64>,
+    /// Storage used by each system with a quota
+    #[serde(default)]
+    pub storage_quotas: Vec<SystemStorage>,
+    /// When the figures were gathered
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DashboardStats {
+    /// Combine `/api/stats/global` and `/api/queue/stats` responses
+    ///
+    /// Missing fields count as zero so a partially populated backend still
+    /// renders.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_parts(global: &serde_json::Value, queue: &serde_json::Value) -> Self {
+        let count = |value: &serde_json::Value, field: &str| {
+            value
+                .get(field)
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0)
+        };
+
+        let calls_per_hour = global
+            .get("recent_activity")
+            .and_then(serde_json::Value::as_array)
+            .map(|periods| periods.iter().map(|p| count(p, "call_count")).collect())
+            .unwrap_or_default();
+
+        let storage_quotas = global
+            .get("storage_quotas")
+            .and_then(|quotas| serde_json::from_value(quotas.clone()).ok())
+            .unwrap_or_default();
+
+        let queue_depth = count(queue, "pending");
+        let processing = count(queue, "processing");
+        let failed = count(queue, "failed");
+        let finished = count(queue, "completed") + failed;
+
+        Self {
+            calls_per_hour,
+            calls_last_24h: count(global, "calls_last_24h"),
+            queue_depth,
+            processing,
+            transcription_backlog: queue_depth + processing,
+            error_rate: (finished > 0).then(|| failed as f64 / finished as f64),
+            storage_quotas,
+            generated_at: chrono::Utc::now(),
+        }
+    }
+}
 
 /// API client for making HTTP requests to the `SDRTrunk` API server
 #[derive(Clone, Debug)]
@@ -56,11 +131,14 @@ impl ApiClient {
         if let Some(limit) = params.limit {
             query_params.push(format!("limit={limit}"));
         }
-        if let Some(offset) = params.offset {
-            query_params.push(format!("offset={offset}"));
+        if let Some(ref after) = params.after {
+            query_params.push(format!("after={}", urlencoding::encode(after)));
         }
         if let Some(ref system_id) = params.system_id {
-            query_params.push(format!("system_id={}", urlencoding::encode(system_id)));
+            query_params.push(format!(
+                "system_id={}",
+                urlencoding::encode(system_id.as_str())
+            ));
         }
         if let Some(talkgroup_id) = params.talkgroup_id {
             query_params.push(format!("talkgroup_id={talkgroup_id}"));
@@ -89,6 +167,18 @@ impl ApiClient {
                 urlencoding::encode(transcription_status)
             ));
         }
+        if let Some(ref tag) = params.tag {
+            query_params.push(format!("tag={}", urlencoding::encode(tag)));
+        }
+        if let Some(ref q) = params.q {
+            query_params.push(format!("q={}", urlencoding::encode(q)));
+        }
+        if let Some(ref language) = params.language {
+            query_params.push(format!("language={}", urlencoding::encode(language)));
+        }
+        if let Some(archived) = params.archived {
+            query_params.push(format!("archived={archived}"));
+        }
 
         if !query_params.is_empty() {
             url.push('?');
@@ -189,6 +279,284 @@ impl ApiClient {
         Ok(call_data)
     }
 
+    /// Get the LLM-written summary of a call
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the call has not been
+    /// summarized, or the response cannot be parsed.
+    pub async fn get_call_summary(&self, call_id: uuid::Uuid) -> Result<serde_json::Value> {
+        let url = format!("{}/api/calls/{}/summary", self.base_url, call_id);
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch call summary: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Summary not available: {}",
+                response.status()
+            )));
+        }
+
+        let summary: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse call summary: {e}")))?;
+
+        Ok(summary)
+    }
+
+    /// Get several calls side by side for comparison
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend rejects the
+    /// IDs, or the response cannot be parsed.
+    pub async fn compare_calls(&self, params: &CompareCallsQuery) -> Result<serde_json::Value> {
+        let url = format!(
+            "{}/api/calls/compare?ids={}",
+            self.base_url,
+            urlencoding::encode(&params.ids)
+        );
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch compared calls: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Comparison not available: {}",
+                response.status()
+            )));
+        }
+
+        let comparison: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse compared calls: {e}")))?;
+
+        Ok(comparison)
+    }
+
+    /// Get waveform peaks for a call's recording
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the call has no readable
+    /// recording, or the response cannot be parsed.
+    pub async fn get_call_waveform(&self, call_id: uuid::Uuid) -> Result<serde_json::Value> {
+        let url = format!("{}/api/calls/{}/waveform", self.base_url, call_id);
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch call waveform: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Waveform not available: {}",
+                response.status()
+            )));
+        }
+
+        let waveform: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse call waveform: {e}")))?;
+
+        Ok(waveform)
+    }
+
+    /// Get a page of conversations with optional filtering
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub async fn get_conversations(
+        &self,
+        params: &ConversationListQuery,
+    ) -> Result<serde_json::Value> {
+        let mut query_params = Vec::new();
+        if let Some(ref system_id) = params.system_id {
+            query_params.push(format!(
+                "system_id={}",
+                urlencoding::encode(system_id.as_str())
+            ));
+        }
+        if let Some(talkgroup_id) = params.talkgroup_id {
+            query_params.push(format!("talkgroup_id={talkgroup_id}"));
+        }
+        if let Some(ref from_date) = params.from_date {
+            query_params.push(format!(
+                "from_date={}",
+                urlencoding::encode(&from_date.to_rfc3339())
+            ));
+        }
+        if let Some(ref to_date) = params.to_date {
+            query_params.push(format!(
+                "to_date={}",
+                urlencoding::encode(&to_date.to_rfc3339())
+            ));
+        }
+        if let Some(limit) = params.limit {
+            query_params.push(format!("limit={limit}"));
+        }
+        if let Some(offset) = params.offset {
+            query_params.push(format!("offset={offset}"));
+        }
+
+        let mut url = format!("{}/api/conversations", self.base_url);
+        if !query_params.is_empty() {
+            url.push('?');
+            url.push_str(&query_params.join("&"));
+        }
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch conversations: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let conversations: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse conversations: {e}")))?;
+
+        Ok(conversations)
+    }
+
+    /// Get a conversation and its calls
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the conversation does not
+    /// exist, or the response cannot be parsed.
+    pub async fn get_conversation(&self, conversation_id: uuid::Uuid) -> Result<serde_json::Value> {
+        let url = format!("{}/api/conversations/{}", self.base_url, conversation_id);
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch conversation: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Conversation not available: {}",
+                response.status()
+            )));
+        }
+
+        let conversation: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse conversation: {e}")))?;
+
+        Ok(conversation)
+    }
+
+    /// Get located calls as a `GeoJSON` feature collection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub async fn get_geo_calls(&self, params: &GeoCallsQuery) -> Result<serde_json::Value> {
+        let mut query_params = Vec::new();
+        if let Some(ref system_id) = params.system_id {
+            query_params.push(format!(
+                "system_id={}",
+                urlencoding::encode(system_id.as_str())
+            ));
+        }
+        if let Some(talkgroup_id) = params.talkgroup_id {
+            query_params.push(format!("talkgroup_id={talkgroup_id}"));
+        }
+        if let Some(ref from_date) = params.from_date {
+            query_params.push(format!(
+                "from_date={}",
+                urlencoding::encode(&from_date.to_rfc3339())
+            ));
+        }
+        if let Some(ref to_date) = params.to_date {
+            query_params.push(format!(
+                "to_date={}",
+                urlencoding::encode(&to_date.to_rfc3339())
+            ));
+        }
+        if let Some(limit) = params.limit {
+            query_params.push(format!("limit={limit}"));
+        }
+
+        let mut url = format!("{}/api/calls/geo", self.base_url);
+        if !query_params.is_empty() {
+            url.push('?');
+            url.push_str(&query_params.join("&"));
+        }
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch call locations: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let calls: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse call locations: {e}")))?;
+
+        Ok(calls)
+    }
+
     /// Get global statistics
     ///
     /// # Errors
@@ -222,4 +590,516 @@ impl ApiClient {
 
         Ok(stats)
     }
+
+    /// Get storage growth and time-to-full projection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub async fn get_storage_growth(&self, days: Option<i32>) -> Result<serde_json::Value> {
+        let url = days.map_or_else(
+            || format!("{}/api/stats/storage", self.base_url),
+            |days| format!("{}/api/stats/storage?days={days}", self.base_url),
+        );
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch storage growth: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let growth: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse storage growth: {e}")))?;
+
+        Ok(growth)
+    }
+
+    /// Get upload usage for an API key
+    ///
+    /// The key being inspected is sent as `X-API-Key`; the backend only
+    /// reports usage for the key that is presented.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend rejects the key,
+    /// or the response cannot be parsed.
+    pub async fn get_key_usage(
+        &self,
+        key_id: &str,
+        api_key: &str,
+        days: Option<i32>,
+    ) -> Result<serde_json::Value> {
+        let url = days.map_or_else(
+            || format!("{}/api/keys/{key_id}/usage", self.base_url),
+            |days| format!("{}/api/keys/{key_id}/usage?days={days}", self.base_url),
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", api_key)
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch API key usage: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let usage: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse API key usage: {e}")))?;
+
+        Ok(usage)
+    }
+
+    /// Mark a call flagged `needs_review` as reviewed
+    ///
+    /// `credential` (an API key or session token) is forwarded so the backend
+    /// applies the caller's role and systems; without one the client's own
+    /// key is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend refuses the
+    /// review, or the response cannot be parsed.
+    pub async fn review_call(
+        &self,
+        call_id: uuid::Uuid,
+        credential: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        let url = format!("{}/api/calls/{call_id}/review", self.base_url);
+
+        let mut request = self.client.post(&url);
+
+        if let Some(api_key) = credential.or(self.api_key.as_deref()) {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to mark call reviewed: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let review: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse review response: {e}")))?;
+
+        Ok(review)
+    }
+
+    /// Attach a tag to a call; `body` is the backend's `{"tag", "note"}`
+    /// request
+    ///
+    /// `credential` is forwarded as for [`Self::review_call`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend refuses the
+    /// tag, or the response cannot be parsed.
+    pub async fn tag_call(
+        &self,
+        call_id: uuid::Uuid,
+        body: &serde_json::Value,
+        credential: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        let url = format!("{}/api/calls/{call_id}/tags", self.base_url);
+
+        let mut request = self.client.post(&url).json(body);
+
+        if let Some(api_key) = credential.or(self.api_key.as_deref()) {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to tag call: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let tag: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse tag response: {e}")))?;
+
+        Ok(tag)
+    }
+
+    /// Remove a tag from a call
+    ///
+    /// `credential` is forwarded as for [`Self::review_call`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the backend refuses to
+    /// remove the tag.
+    pub async fn untag_call(
+        &self,
+        call_id: uuid::Uuid,
+        tag: &str,
+        credential: Option<&str>,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/api/calls/{call_id}/tags/{}",
+            self.base_url,
+            urlencoding::encode(tag)
+        );
+
+        let mut request = self.client.delete(&url);
+
+        if let Some(api_key) = credential.or(self.api_key.as_deref()) {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to remove tag: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// List the caller's saved call searches
+    ///
+    /// `credential` is forwarded as for [`Self::review_call`]; searches are
+    /// kept per signed-in user or API key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend refuses the
+    /// request, or the response cannot be parsed.
+    pub async fn list_searches(&self, credential: Option<&str>) -> Result<serde_json::Value> {
+        let url = format!("{}/api/searches", self.base_url);
+
+        let mut request = self.client.get(&url);
+
+        if let Some(api_key) = credential.or(self.api_key.as_deref()) {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to list saved searches: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let searches: serde_json::Value = response.json().await.map_err(|e| {
+            AppError::Other(format!("Failed to parse saved searches response: {e}"))
+        })?;
+
+        Ok(searches)
+    }
+
+    /// Save a call search, or replace saved search `id`; `body` is the
+    /// backend's saved search request
+    ///
+    /// `credential` is forwarded as for [`Self::review_call`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend refuses the
+    /// search (e.g. the name is taken), or the response cannot be parsed.
+    pub async fn save_search(
+        &self,
+        id: Option<uuid::Uuid>,
+        body: &serde_json::Value,
+        credential: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        let (method, url) = id.map_or_else(
+            || {
+                (
+                    reqwest::Method::POST,
+                    format!("{}/api/searches", self.base_url),
+                )
+            },
+            |id| {
+                (
+                    reqwest::Method::PUT,
+                    format!("{}/api/searches/{id}", self.base_url),
+                )
+            },
+        );
+
+        let mut request = self.client.request(method, &url).json(body);
+
+        if let Some(api_key) = credential.or(self.api_key.as_deref()) {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to save search: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let search: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse saved search response: {e}")))?;
+
+        Ok(search)
+    }
+
+    /// Delete a saved call search
+    ///
+    /// `credential` is forwarded as for [`Self::review_call`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the backend refuses to
+    /// delete the search.
+    pub async fn delete_search(&self, id: uuid::Uuid, credential: Option<&str>) -> Result<()> {
+        let url = format!("{}/api/searches/{id}", self.base_url);
+
+        let mut request = self.client.delete(&url);
+
+        if let Some(api_key) = credential.or(self.api_key.as_deref()) {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to delete saved search: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Get transcription job queue counts
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub async fn get_queue_stats(&self) -> Result<serde_json::Value> {
+        let url = format!("{}/api/queue/stats", self.base_url);
+
+        let mut request = self.client.get(&url);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch queue stats: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let stats: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse queue stats: {e}")))?;
+
+        Ok(stats)
+    }
+
+    /// Sign in with a username and password
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the credentials are
+    /// refused, or the response cannot be parsed.
+    pub async fn login(&self, username: &str, password: &str) -> Result<LoginResponse> {
+        let url = format!("{}/api/auth/login", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({"username": username, "password": password}))
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to sign in: {e}")))?;
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(AppError::Other("Invalid username or password".to_string()));
+        }
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let login: LoginResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse sign-in response: {e}")))?;
+
+        Ok(login)
+    }
+
+    /// Look up the user behind a session token
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the backend rejects the
+    /// token, or the response cannot be parsed.
+    pub async fn get_session(&self, token: &str) -> Result<SessionResponse> {
+        let url = format!("{}/api/auth/me", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to fetch session: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        let session: SessionResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse session: {e}")))?;
+
+        Ok(session)
+    }
+
+    /// End a session
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the backend has no such
+    /// session.
+    pub async fn logout(&self, token: &str) -> Result<()> {
+        let url = format!("{}/api/auth/logout", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to sign out: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "API returned error: {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Get the figures for the dashboard's live statistics cards
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the global or the queue statistics request
+    /// fails.
+    pub async fn get_dashboard_stats(&self) -> Result<DashboardStats> {
+        let (global, queue) = tokio::try_join!(self.get_global_stats(), self.get_queue_stats())?;
+        Ok(DashboardStats::from_parts(&global, &queue))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::missing_panics_doc, clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dashboard_stats_from_parts() {
+        let global = json!({
+            "calls_last_24h": 7,
+            "recent_activity": [
+                {"period_start": "2024-01-01T00:00:00Z", "call_count": 3, "active_systems": 1},
+                {"period_start": "2024-01-01T01:00:00Z", "call_count": 0, "active_systems": 0},
+                {"period_start": "2024-01-01T02:00:00Z", "call_count": 4, "active_systems": 2}
+            ],
+            "storage_quotas": [
+                {"system_id": "metro", "used_bytes": 1200, "quota_bytes": 1000,
+                 "used_percent": 120.0, "quota_action": "purge_oldest", "exceeded": true}
+            ]
+        });
+        let queue =
+            json!({"pending": 5, "processing": 2, "completed": 9, "failed": 1, "total": 17});
+
+        let stats = DashboardStats::from_parts(&global, &queue);
+        assert_eq!(stats.calls_per_hour, vec![3, 0, 4]);
+        assert_eq!(stats.calls_last_24h, 7);
+        assert_eq!(stats.queue_depth, 5);
+        assert_eq!(stats.transcription_backlog, 7);
+        assert_eq!(stats.error_rate, Some(0.1));
+        let metro = stats.storage_quotas.first();
+        assert_eq!(metro.map(|quota| quota.system_id.as_str()), Some("metro"));
+        assert!(metro.is_some_and(|quota| quota.exceeded));
+
+        // An idle backend has no error rate rather than 0%
+        let idle = DashboardStats::from_parts(&json!({}), &json!({}));
+        assert!(idle.calls_per_hour.is_empty());
+        assert_eq!(idle.error_rate, None);
+        assert!(idle.storage_quotas.is_empty());
+    }
 }
@@ -1,17 +1,31 @@
 //! API proxy handlers for communicating with backend
 #![allow(unreachable_pub)]
 
-use crate::{api_client::ListCallsQuery, state::AppState};
+use crate::{
+    api_client::{
+        ApiClient, CompareCallsQuery, ConversationListQuery, GeoCallsQuery, ListCallsQuery,
+    },
+    session::{request_credential, session_cookie, session_token},
+    state::AppState,
+    websocket::CallData,
+};
 use axum::extract::ws::{Message, WebSocket};
 use axum::{
     extract::{Path, Query, State, WebSocketUpgrade},
-    http::StatusCode,
-    response::{Json, Response},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Json, Redirect, Response},
 };
 use futures_util::{SinkExt, StreamExt};
-use std::sync::Arc;
+use sdrtrunk_api::handlers::auth::LoginRequest;
+use sdrtrunk_types::SystemId;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::time::{Duration, interval};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Calls whose visibility a scoped connection remembers before starting over
+const CALL_SCOPE_CACHE_CAPACITY: usize = 1024;
 
 /// API endpoint for calls data - proxies to backend API
 pub async fn api_calls(
@@ -28,7 +42,7 @@ pub async fn api_calls(
                 "calls": [],
                 "total": 0,
                 "count": 0,
-                "offset": 0
+                "pagination": { "has_next": false, "next_cursor": null }
             }))
         }
     }
@@ -51,18 +65,412 @@ pub async fn api_global_stats(State(state): State<Arc<AppState>>) -> Json<serde_
     }
 }
 
+/// API endpoint for the dashboard's live statistics cards
+///
+/// Browsers load this once; later values arrive as `dashboard_stats`
+/// WebSocket messages.
+pub async fn api_dashboard_stats(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    match state.api_client.get_dashboard_stats().await {
+        Ok(dashboard) => Json(serde_json::json!(dashboard)),
+        Err(e) => {
+            error!("Failed to fetch dashboard stats from API: {}", e);
+            Json(serde_json::json!({
+                "error": "Failed to fetch dashboard statistics",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+/// Query parameters for the storage growth proxy
+#[derive(Debug, serde::Deserialize)]
+pub struct StorageGrowthParams {
+    /// Days of history used to compute the growth rate
+    pub days: Option<i32>,
+}
+
+/// API endpoint for storage growth and time-to-full projection
+pub async fn api_storage_growth(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<StorageGrowthParams>,
+) -> Json<serde_json::Value> {
+    match state.api_client.get_storage_growth(params.days).await {
+        Ok(growth) => Json(growth),
+        Err(e) => {
+            error!("Failed to fetch storage growth from API: {}", e);
+            Json(serde_json::json!({
+                "error": "Failed to fetch storage growth",
+                "message": e.to_string(),
+                "daily_growth": [],
+                "systems": [],
+                "projection": null
+            }))
+        }
+    }
+}
+
+/// Query parameters for the API key usage proxy
+#[derive(Debug, serde::Deserialize)]
+pub struct KeyUsageParams {
+    /// Days of history to include
+    pub days: Option<i32>,
+}
+
+/// API endpoint for per-key upload usage
+///
+/// Forwards the caller's `X-API-Key` header so operators can only inspect
+/// the key they hold.
+pub async fn api_key_usage(
+    State(state): State<Arc<AppState>>,
+    Path(key_id): Path<String>,
+    headers: HeaderMap,
+    Query(params): Query<KeyUsageParams>,
+) -> Json<serde_json::Value> {
+    let Some(api_key) = headers.get("x-api-key").and_then(|v| v.to_str().ok()) else {
+        return Json(serde_json::json!({
+            "error": "API key required",
+            "message": "Provide the key being inspected in the X-API-Key header"
+        }));
+    };
+
+    match state
+        .api_client
+        .get_key_usage(&key_id, api_key, params.days)
+        .await
+    {
+        Ok(usage) => Json(usage),
+        Err(e) => {
+            warn!("Failed to fetch usage for API key {}: {}", key_id, e);
+            Json(serde_json::json!({
+                "error": "Failed to fetch API key usage",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+/// Mark a call flagged for review as reviewed
+///
+/// Forwards the caller's credential (see [`request_credential`]) so only
+/// analysts and admins can clear the review queue.
+pub async fn api_review_call(
+    State(state): State<Arc<AppState>>,
+    Path(call_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    let credential = request_credential(&headers, None);
+    match state.api_client.review_call(call_id, credential).await {
+        Ok(review) => Json(review).into_response(),
+        Err(e) => {
+            warn!("Failed to mark call {} reviewed: {}", call_id, e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to mark call reviewed",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Attach a tag, with an optional note, to a call
+///
+/// Forwards the caller's credential so only analysts and admins can tag.
+pub async fn api_tag_call(
+    State(state): State<Arc<AppState>>,
+    Path(call_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<serde_json::Value>,
+) -> Response {
+    let credential = request_credential(&headers, None);
+    match state.api_client.tag_call(call_id, &body, credential).await {
+        Ok(tag) => (StatusCode::CREATED, Json(tag)).into_response(),
+        Err(e) => {
+            warn!("Failed to tag call {}: {}", call_id, e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to tag call",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Remove a tag from a call
+///
+/// Forwards the caller's credential so only analysts and admins can untag.
+pub async fn api_untag_call(
+    State(state): State<Arc<AppState>>,
+    Path((call_id, tag)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+) -> Response {
+    let credential = request_credential(&headers, None);
+    match state.api_client.untag_call(call_id, &tag, credential).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            warn!("Failed to remove tag from call {}: {}", call_id, e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to remove tag",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List the caller's saved call searches
+pub async fn api_list_searches(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let credential = request_credential(&headers, None);
+    match state.api_client.list_searches(credential).await {
+        Ok(searches) => Json(searches).into_response(),
+        Err(e) => {
+            warn!("Failed to list saved searches: {}", e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to list saved searches",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Save the calls page filters as a named search
+pub async fn api_create_search(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(body): Json<serde_json::Value>,
+) -> Response {
+    save_search(&state, None, &headers, &body).await
+}
+
+/// Replace a saved search's name and filters
+pub async fn api_update_search(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(body): Json<serde_json::Value>,
+) -> Response {
+    save_search(&state, Some(id), &headers, &body).await
+}
+
+/// Forward a saved search to the backend, creating it when `id` is `None`
+async fn save_search(
+    state: &AppState,
+    id: Option<Uuid>,
+    headers: &HeaderMap,
+    body: &serde_json::Value,
+) -> Response {
+    let credential = request_credential(headers, None);
+    match state.api_client.save_search(id, body, credential).await {
+        Ok(search) if id.is_none() => (StatusCode::CREATED, Json(search)).into_response(),
+        Ok(search) => Json(search).into_response(),
+        Err(e) => {
+            warn!("Failed to save search: {}", e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to save search",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Delete a saved search
+pub async fn api_delete_search(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    let credential = request_credential(&headers, None);
+    match state.api_client.delete_search(id, credential).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            warn!("Failed to delete saved search {}: {}", id, e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to delete saved search",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Sign in through the backend and keep the session token in a cookie
+pub async fn api_login(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<LoginRequest>,
+) -> Response {
+    match state
+        .api_client
+        .login(&request.username, &request.password)
+        .await
+    {
+        Ok(login) => {
+            let max_age = (login.expires_at - chrono::Utc::now()).num_seconds().max(0);
+            (
+                [(header::SET_COOKIE, session_cookie(&login.token, max_age))],
+                Json(serde_json::json!({
+                    "success": true,
+                    "user": login.user,
+                    "expires_at": login.expires_at
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            warn!("Sign-in failed for {}: {}", request.username, e);
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Sign out: end the backend session, clear the cookie, and go to `/login`
+pub async fn logout(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    if let Some(token) = session_token(&headers)
+        && let Err(e) = state.api_client.logout(token).await
+    {
+        warn!("Failed to end session: {}", e);
+    }
+    (
+        [(header::SET_COOKIE, session_cookie("", 0))],
+        Redirect::to(&state.url("/login")),
+    )
+        .into_response()
+}
+
+/// Query parameters accepted on the WebSocket upgrade
+#[derive(Debug, serde::Deserialize)]
+pub struct SocketParams {
+    /// API key or session token, for clients that cannot set headers
+    pub token: Option<String>,
+}
+
 /// WebSocket handler for real-time updates
+///
+/// The upgrade needs an API key or session token (see
+/// [`request_credential`]). The backend confirms it and reports which systems
+/// it may see, and only events for those systems are pushed.
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    Query(params): Query<SocketParams>,
+    headers: HeaderMap,
 ) -> Response {
-    ws.on_upgrade(move |socket| websocket_connection(socket, state))
+    let Some(credential) = request_credential(&headers, params.token.as_deref()) else {
+        return socket_unauthorized("Provide an API key or session token");
+    };
+    let session = match state.api_client.get_session(credential).await {
+        Ok(session) => session,
+        Err(e) => {
+            warn!("Rejected WebSocket connection: {}", e);
+            return socket_unauthorized("Invalid API key or session token");
+        }
+    };
+
+    let scope = SocketScope::new(
+        state.api_client.clone().with_api_key(credential),
+        session.allowed_systems,
+    );
+    ws.on_upgrade(move |socket| websocket_connection(socket, state, scope))
+}
+
+/// 401 response for a refused WebSocket upgrade
+fn socket_unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({
+            "error": "Authentication required",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+/// What one WebSocket connection may see
+///
+/// Backend requests are made with the caller's credential, so calls are
+/// already limited to its systems. Dashboard statistics cover every system
+/// and are only sent to unrestricted callers, and progress events are only
+/// sent for calls the caller can read.
+#[derive(Debug)]
+struct SocketScope {
+    /// Backend client carrying the caller's credential
+    client: ApiClient,
+    /// Systems the caller may see (`None` for every system)
+    systems: Option<Vec<SystemId>>,
+    /// Whether each recently seen call is visible
+    calls: HashMap<Uuid, bool>,
+}
+
+impl SocketScope {
+    fn new(client: ApiClient, systems: Option<Vec<SystemId>>) -> Self {
+        Self {
+            client,
+            systems,
+            calls: HashMap::new(),
+        }
+    }
+
+    /// Whether the caller is limited to specific systems
+    const fn is_restricted(&self) -> bool {
+        self.systems.is_some()
+    }
+
+    /// Whether events for calls from `system_id` may be sent
+    fn allows_system(&self, system_id: &str) -> bool {
+        self.systems
+            .as_ref()
+            .is_none_or(|systems| systems.iter().any(|s| s.as_str() == system_id))
+    }
+
+    /// Whether progress events for `call_id` may be sent
+    async fn allows_call(&mut self, call_id: Uuid) -> bool {
+        if !self.is_restricted() {
+            return true;
+        }
+        if let Some(&visible) = self.calls.get(&call_id) {
+            return visible;
+        }
+        // The backend answers 404 for calls outside the caller's systems
+        let visible = self.client.get_call_details(call_id).await.is_ok();
+        if self.calls.len() >= CALL_SCOPE_CACHE_CAPACITY {
+            self.calls.clear();
+        }
+        let _ = self.calls.insert(call_id, visible);
+        visible
+    }
 }
 
 /// Handle WebSocket connection for real-time updates
-#[allow(clippy::cognitive_complexity)]
-async fn websocket_connection(socket: WebSocket, state: Arc<AppState>) {
+#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+async fn websocket_connection(socket: WebSocket, state: Arc<AppState>, mut scope: SocketScope) {
     let (mut sender, mut receiver) = socket.split();
+    let mut progress = state.progress.subscribe();
+    let mut new_calls = state.new_calls.subscribe();
 
     info!("WebSocket connection established");
 
@@ -74,12 +482,16 @@ async fn websocket_connection(socket: WebSocket, state: Arc<AppState>) {
         tokio::select! {
             _ = update_interval.tick() => {
                 // Fetch latest calls and send update (only completed transcriptions)
-                if let Ok(calls) = state.api_client.get_calls(&ListCallsQuery {
+                if let Ok(calls) = scope.client.get_calls(&ListCallsQuery {
                     limit: Some(20),
-                    offset: Some(0),
+                    after: None,
                     system_id: None,
                     talkgroup_id: None,
                     transcription_status: Some("completed".to_string()),
+                    tag: None,
+                    q: None,
+                    language: None,
+                    archived: None,
                     from_date: None,
                     to_date: None,
                     sort: Some("desc".to_string()),
@@ -94,6 +506,53 @@ async fn websocket_connection(socket: WebSocket, state: Arc<AppState>) {
                         break;
                     }
                 }
+
+                // Refresh the dashboard's live statistics cards; they
+                // count every system, so scoped callers go without
+                if !scope.is_restricted()
+                    && let Ok(stats) = state.api_client.get_dashboard_stats().await
+                {
+                    let update = serde_json::json!({
+                        "type": "dashboard_stats",
+                        "data": stats
+                    });
+
+                    if sender.send(Message::Text(update.to_string())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            event = progress.recv() => {
+                match event {
+                    Ok(event) if !scope.allows_call(event.call_id).await => {}
+                    Ok(event) => {
+                        let update = serde_json::json!({
+                            "type": "transcription_progress",
+                            "data": event
+                        });
+                        if sender.send(Message::Text(update.to_string())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("WebSocket client lagged, skipped {} progress events", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            call = new_calls.recv() => {
+                match call {
+                    Ok(call) if !scope.allows_system(&call.system_id) => {}
+                    Ok(call) => {
+                        if sender.send(Message::Text(new_call_message(&call).to_string())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("WebSocket client lagged, skipped {} new calls", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
             }
             _ = ping_interval.tick() => {
                 if sender.send(Message::Ping(vec![])).await.is_err() {
@@ -116,6 +575,21 @@ async fn websocket_connection(socket: WebSocket, state: Arc<AppState>) {
     info!("WebSocket connection closed");
 }
 
+/// Browser message announcing a new call, shaped like a call list entry
+fn new_call_message(call: &CallData) -> serde_json::Value {
+    serde_json::json!({
+        "type": "new_call",
+        "call": {
+            "id": call.call_id,
+            "system_id": call.system_id,
+            "talkgroup_id": call.talkgroup_id,
+            "talkgroup_label": call.talkgroup_label,
+            "transcription_status": call.transcription_status,
+            "call_timestamp": call.timestamp,
+        }
+    })
+}
+
 /// Serve audio file for a specific call
 ///
 /// # Errors
@@ -124,7 +598,7 @@ async fn websocket_connection(socket: WebSocket, state: Arc<AppState>) {
 /// Returns `StatusCode::INTERNAL_SERVER_ERROR` for database or file system errors.
 #[allow(clippy::cognitive_complexity)]
 pub async fn serve_audio(
-    Path(call_id): Path<uuid::Uuid>,
+    Path(call_id): Path<Uuid>,
     State(state): State<Arc<AppState>>,
 ) -> Result<Response, StatusCode> {
     info!("Audio request for call: {}", call_id);
@@ -180,17 +654,139 @@ pub async fn serve_audio(
     Ok(response)
 }
 
-/// Health check endpoint
-/// Health check — proxies to API server's /health endpoint
-pub async fn health_check(
+/// Waveform peaks for a call's recording, drawn by the audio player
+///
+/// # Errors
+///
+/// Returns `StatusCode::NOT_FOUND` if the call or its recording is not
+/// available or the backend cannot produce a waveform.
+pub async fn api_call_waveform(
+    Path(call_id): Path<Uuid>,
     State(state): State<Arc<AppState>>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .api_client
+        .get_call_waveform(call_id)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            warn!("Failed to fetch waveform for call {}: {}", call_id, e);
+            StatusCode::NOT_FOUND
+        })
+}
+
+/// Proxy the LLM-written summary of a call from the backend
+///
+/// # Errors
+///
+/// Returns `StatusCode::NOT_FOUND` if the call has not been summarized or
+/// the backend cannot be reached.
+pub async fn api_call_summary(
+    Path(call_id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .api_client
+        .get_call_summary(call_id)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            debug!("No summary for call {}: {}", call_id, e);
+            StatusCode::NOT_FOUND
+        })
+}
+
+/// Proxy a side-by-side comparison of calls from the backend
+pub async fn api_compare_calls(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CompareCallsQuery>,
+) -> Response {
+    match state.api_client.compare_calls(&params).await {
+        Ok(comparison) => Json(comparison).into_response(),
+        Err(e) => {
+            warn!("Failed to compare calls {}: {}", params.ids, e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({
+                    "error": "Failed to compare calls",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// API endpoint for conversations - proxies to backend API
+pub async fn api_conversations(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ConversationListQuery>,
 ) -> Json<serde_json::Value> {
+    match state.api_client.get_conversations(&params).await {
+        Ok(conversations) => Json(conversations),
+        Err(e) => {
+            error!("Failed to fetch conversations from API: {}", e);
+            Json(serde_json::json!({
+                "error": "Failed to fetch conversations",
+                "message": e.to_string(),
+                "conversations": [],
+                "total": 0
+            }))
+        }
+    }
+}
+
+/// Proxy a conversation and its calls from the backend
+///
+/// # Errors
+///
+/// Returns `StatusCode::NOT_FOUND` if the conversation does not exist or the
+/// backend cannot be reached.
+pub async fn api_conversation(
+    Path(conversation_id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .api_client
+        .get_conversation(conversation_id)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            warn!("Failed to fetch conversation {}: {}", conversation_id, e);
+            StatusCode::NOT_FOUND
+        })
+}
+
+/// API endpoint for the call map - proxies to backend API
+pub async fn api_geo_calls(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<GeoCallsQuery>,
+) -> Json<serde_json::Value> {
+    match state.api_client.get_geo_calls(&params).await {
+        Ok(calls) => Json(calls),
+        Err(e) => {
+            error!("Failed to fetch call locations from API: {}", e);
+            Json(serde_json::json!({
+                "error": "Failed to fetch call locations",
+                "message": e.to_string(),
+                "type": "FeatureCollection",
+                "features": []
+            }))
+        }
+    }
+}
+
+/// Health check endpoint
+/// Health check — proxies to API server's /health endpoint
+pub async fn health_check(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
     let url = format!("{}/health", state.api_client.base_url());
     match reqwest::get(&url).await {
-        Ok(resp) => match resp.json::<serde_json::Value>().await {
-            Ok(data) => Json(data),
-            Err(_) => Json(serde_json::json!({"status": "error", "message": "Invalid health response"})),
-        },
-        Err(e) => Json(serde_json::json!({"status": "error", "message": format!("API unreachable: {e}")})),
+        Ok(resp) => resp.json::<serde_json::Value>().await.map_or_else(
+            |_| Json(serde_json::json!({"status": "error", "message": "Invalid health response"})),
+            Json,
+        ),
+        Err(e) => {
+            Json(serde_json::json!({"status": "error", "message": format!("API unreachable: {e}")}))
+        }
     }
 }
@@ -1,24 +1,170 @@
 //! Page handlers for serving HTML templates
 #![allow(unreachable_pub)]
 
-use axum::response::Html;
+use crate::{
+    i18n::{Language, language_cookie, localize},
+    state::AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use std::{borrow::Cow, sync::Arc};
+
+/// A template with its links moved under the interface's base path and its
+/// text in the language the request asks for
+fn page(state: &AppState, headers: &HeaderMap, html: &'static str) -> Html<String> {
+    Html(localize(
+        &with_base_path(html, &state.base_path),
+        Language::from_headers(headers),
+    ))
+}
+
+/// Prefix every root-relative URL in `html` with `base_path`
+///
+/// Templates refer to pages and the proxied API by quoted absolute paths
+/// (`"/calls"`, `'/api/calls'`, `` `/api/calls/${id}` ``) and open the live
+/// feed at `${location.host}/ws`; all of them move under `base_path`.
+fn with_base_path(html: &'static str, base_path: &str) -> Cow<'static, str> {
+    if base_path.is_empty() {
+        return Cow::Borrowed(html);
+    }
+    let mut html = html.to_string();
+    for quote in ['"', '\'', '`'] {
+        html = html.replace(&format!("{quote}/"), &format!("{quote}{base_path}/"));
+        // The dashboard is served at the base path itself, without a slash
+        html = html.replace(
+            &format!("{quote}{base_path}/{quote}"),
+            &format!("{quote}{base_path}{quote}"),
+        );
+    }
+    Cow::Owned(html.replace(
+        "${location.host}/ws",
+        &format!("${{location.host}}{base_path}/ws"),
+    ))
+}
 
 /// Dashboard page
-pub async fn dashboard() -> Html<&'static str> {
-    Html(include_str!("../../templates/dashboard.html"))
+pub async fn dashboard(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(
+        &state,
+        &headers,
+        include_str!("../../templates/dashboard.html"),
+    )
 }
 
 /// Calls browser page
-pub async fn calls_page() -> Html<&'static str> {
-    Html(include_str!("../../templates/calls.html"))
+pub async fn calls_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(&state, &headers, include_str!("../../templates/calls.html"))
+}
+
+/// Side-by-side comparison of the calls named in `?ids=`
+pub async fn compare_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(
+        &state,
+        &headers,
+        include_str!("../../templates/compare.html"),
+    )
+}
+
+/// Conversations browser page
+pub async fn conversations_page(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Html<String> {
+    page(
+        &state,
+        &headers,
+        include_str!("../../templates/conversations.html"),
+    )
+}
+
+/// Call map page
+pub async fn map_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(&state, &headers, include_str!("../../templates/map.html"))
+}
+
+/// Review queue of low-confidence transcriptions
+pub async fn review_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(
+        &state,
+        &headers,
+        include_str!("../../templates/review.html"),
+    )
 }
 
 /// Statistics page
-pub async fn stats_page() -> Html<&'static str> {
-    Html(include_str!("../../templates/stats.html"))
+pub async fn stats_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(&state, &headers, include_str!("../../templates/stats.html"))
+}
+
+/// Sign-in page
+pub async fn login_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(&state, &headers, include_str!("../../templates/login.html"))
 }
 
 /// Admin page
-pub async fn admin_page() -> Html<&'static str> {
-    Html(include_str!("../../templates/admin.html"))
+pub async fn admin_page(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Html<String> {
+    page(&state, &headers, include_str!("../../templates/admin.html"))
+}
+
+/// Remember the interface language for this browser and go back to the page
+/// it was picked on
+pub async fn set_language(
+    State(state): State<Arc<AppState>>,
+    Path(code): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(language) = Language::from_code(&code) else {
+        return (StatusCode::NOT_FOUND, "Unsupported language").into_response();
+    };
+    // Only the path is kept (and never a `//host` one), so the redirect
+    // stays on this site
+    let back = headers
+        .get(header::REFERER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|referer| reqwest::Url::parse(referer).ok())
+        .filter(|url| !url.path().starts_with("//"))
+        .map_or_else(
+            || state.url("/"),
+            |url| match url.query() {
+                Some(query) => format!("{}?{query}", url.path()),
+                None => url.path().to_string(),
+            },
+        );
+    (
+        [(header::SET_COOKIE, language_cookie(language))],
+        Redirect::to(&back),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+#[allow(clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_base_path() {
+        let html = r#"<a href="/">Home</a> <a href="/calls">Calls</a>
+<script>
+fetch('/api/calls');
+fetch(`/api/calls/${id}/audio`);
+new WebSocket(`${protocol}//${location.host}/ws`);
+const link = "https://unpkg.com/leaflet.css";
+</script>"#;
+
+        assert!(matches!(with_base_path(html, ""), Cow::Borrowed(_)));
+        assert_eq!(
+            with_base_path(html, "/ui"),
+            r#"<a href="/ui">Home</a> <a href="/ui/calls">Calls</a>
+<script>
+fetch('/ui/api/calls');
+fetch(`/ui/api/calls/${id}/audio`);
+new WebSocket(`${protocol}//${location.host}/ui/ws`);
+const link = "https://unpkg.com/leaflet.css";
+</script>"#
+        );
+    }
 }
@@ -0,0 +1,696 @@
+//! Language packs for the web interface
+//!
+//! Templates mark their text with `{{t:key}}` and name their language with
+//! `{{lang}}`; [`localize`] fills both in for the reader's [`Language`]. Keys
+//! missing from a pack fall back to English. The language is chosen per
+//! browser: the `sdrtrunk_lang` cookie set by `GET /language/:code` wins, then
+//! the `Accept-Language` header, then English.
+//!
+//! Dates and numbers are formatted in the browser with the page's `lang`, so
+//! they follow the same choice.
+#![allow(unreachable_pub)]
+
+use axum::http::{HeaderMap, HeaderValue, header};
+use std::borrow::Cow;
+
+/// Cookie holding the chosen language code
+const LANGUAGE_COOKIE: &str = "sdrtrunk_lang";
+
+/// How long the language choice is remembered, in seconds (one year)
+const LANGUAGE_COOKIE_MAX_AGE: i64 = 365 * 24 * 60 * 60;
+
+/// A language the interface is translated into
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    /// English, the language the templates are written in
+    #[default]
+    English,
+    /// Spanish
+    Spanish,
+    /// German
+    German,
+}
+
+impl Language {
+    /// Every supported language, in the order the picker lists them
+    pub const ALL: [Self; 3] = [Self::English, Self::Spanish, Self::German];
+
+    /// ISO 639-1 code, as used in the `lang` attribute and the cookie
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+            Self::German => "de",
+        }
+    }
+
+    /// Name of the language in the language itself
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+            Self::German => "Deutsch",
+        }
+    }
+
+    /// The language for a code or language tag such as `es` or `de-AT`
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.trim().split(['-', '_']).next().unwrap_or_default();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(primary))
+    }
+
+    /// The language a request asks for: its cookie, then its
+    /// `Accept-Language`, then English
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let cookie = headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .filter(|(name, _)| *name == LANGUAGE_COOKIE)
+            .find_map(|(_, value)| Self::from_code(value));
+        cookie
+            .or_else(|| {
+                headers
+                    .get(header::ACCEPT_LANGUAGE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(Self::negotiate)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The supported language an `Accept-Language` value prefers most
+    fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut best: Option<(Self, f32)> = None;
+        for range in accept_language.split(',') {
+            let mut parts = range.split(';');
+            let Some(language) = parts.next().and_then(Self::from_code) else {
+                continue;
+            };
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|quality| quality.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((language, quality));
+            }
+        }
+        best.map(|(language, _)| language)
+    }
+
+    /// The text for `key`, from English when this pack lacks it
+    #[must_use]
+    pub fn text(self, key: &str) -> Option<&'static str> {
+        let lookup = |pack: &'static [(&'static str, &'static str)]| {
+            pack.iter()
+                .find_map(|(name, text)| (*name == key).then_some(*text))
+        };
+        lookup(self.pack()).or_else(|| lookup(ENGLISH))
+    }
+
+    const fn pack(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::English => ENGLISH,
+            Self::Spanish => SPANISH,
+            Self::German => GERMAN,
+        }
+    }
+}
+
+/// `Set-Cookie` value remembering `language` for this browser
+pub fn language_cookie(language: Language) -> HeaderValue {
+    let cookie = format!(
+        "{LANGUAGE_COOKIE}={}; Path=/; SameSite=Lax; Max-Age={LANGUAGE_COOKIE_MAX_AGE}",
+        language.code()
+    );
+    HeaderValue::from_str(&cookie).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+/// Fill in a template's markers for `language`
+///
+/// `{{t:key}}` becomes the translated text, `{{lang}}` the language code, and
+/// `{{language_options}}` the `<option>`s of the language picker. Unknown
+/// markers are left as they are.
+pub fn localize(html: &str, language: Language) -> String {
+    let mut localized = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some((before, marker)) = rest.split_once("{{") {
+        localized.push_str(before);
+        let expanded = marker
+            .split_once("}}")
+            .and_then(|(name, after)| Some((expand(name, language)?, after)));
+        if let Some((text, after)) = expanded {
+            localized.push_str(&text);
+            rest = after;
+        } else {
+            localized.push_str("{{");
+            rest = marker;
+        }
+    }
+    localized.push_str(rest);
+    localized
+}
+
+/// The text a marker named `name` stands for
+fn expand(name: &str, language: Language) -> Option<Cow<'static, str>> {
+    match name {
+        "lang" => Some(Cow::Borrowed(language.code())),
+        "language_options" => Some(Cow::Owned(
+            Language::ALL
+                .into_iter()
+                .map(|option| {
+                    let selected = if option == language { " selected" } else { "" };
+                    format!(
+                        r#"<option value="{}"{selected}>{}</option>"#,
+                        option.code(),
+                        option.name()
+                    )
+                })
+                .collect(),
+        )),
+        _ => name
+            .strip_prefix("t:")
+            .and_then(|key| language.text(key))
+            .map(Cow::Borrowed),
+    }
+}
+
+// Translations are pasted into HTML and JavaScript string literals as they
+// are, so they must not contain quotes, backslashes, `<`, `>` or `&`.
+
+/// English text, and the fallback for keys the other packs lack
+const ENGLISH: &[(&str, &str)] = &[
+    ("title.dashboard", "Dashboard"),
+    ("title.calls", "Radio Calls"),
+    ("title.conversations", "Conversations"),
+    ("title.map", "Map"),
+    ("title.review", "Review"),
+    ("title.stats", "Statistics"),
+    ("title.login", "Sign In"),
+    ("title.admin", "Administration"),
+    ("title.compare", "Call Comparison"),
+    ("nav.dashboard", "Dashboard"),
+    ("nav.calls", "Calls"),
+    ("nav.conversations", "Conversations"),
+    ("nav.map", "Map"),
+    ("nav.review", "Review"),
+    ("nav.stats", "Statistics"),
+    ("nav.admin", "Admin"),
+    ("nav.sign_out", "Sign out"),
+    ("nav.language", "Language"),
+    ("theme.light", "Light Mode"),
+    ("theme.dark", "Dark Mode"),
+    ("common.all_systems", "All Systems"),
+    ("common.all_talkgroups", "All Talkgroups"),
+    ("common.clear_filters", "Clear Filters"),
+    ("common.loading", "Loading..."),
+    ("common.unknown", "Unknown"),
+    ("common.na", "N/A"),
+    ("login.username", "Username"),
+    ("login.password", "Password"),
+    ("login.failed", "Sign in failed"),
+    ("dashboard.heading", "Live Dashboard"),
+    ("dashboard.system_status", "System Status"),
+    ("dashboard.online", "Online"),
+    ("dashboard.api", "API"),
+    ("dashboard.available", "Available"),
+    ("dashboard.workers", "Workers"),
+    ("dashboard.checking", "Checking..."),
+    ("dashboard.calls_per_hour", "Calls / Hour"),
+    ("dashboard.last_24h", "in the last 24 hours"),
+    ("dashboard.queue_depth", "Queue Depth"),
+    ("dashboard.jobs_waiting", "jobs waiting"),
+    ("dashboard.backlog", "Transcription Backlog"),
+    ("dashboard.backlog_calls", "calls"),
+    ("dashboard.error_rate", "Error Rate"),
+    ("dashboard.storage_quotas", "Storage Quotas"),
+    (
+        "dashboard.quota_rejects",
+        "Uploads are refused once the quota is reached",
+    ),
+    (
+        "dashboard.quota_purges",
+        "The oldest recordings are removed to stay under the quota",
+    ),
+    ("dashboard.of_finished", "of finished transcriptions"),
+    ("dashboard.no_processing", "No calls processing"),
+    ("dashboard.expand", "Click to expand"),
+    ("dashboard.live_scanner", "Live Scanner"),
+    ("dashboard.listen_live", "Listen live"),
+    (
+        "dashboard.live_talkgroups",
+        "Talkgroups, e.g. 1001, 1002 (blank for all)",
+    ),
+    ("dashboard.skip", "Skip"),
+    ("dashboard.off", "Off"),
+    ("dashboard.search", "Search transcriptions..."),
+    ("dashboard.transcriptions", "Transcriptions"),
+    (
+        "dashboard.loading_transcriptions",
+        "Loading transcriptions...",
+    ),
+    ("dashboard.load_more", "Load More"),
+    ("dashboard.system_statistics", "System Statistics"),
+    ("dashboard.total_calls", "Total Calls"),
+    ("dashboard.calls_today", "Calls Today"),
+    ("dashboard.transcribed", "Transcribed"),
+    ("dashboard.system_health", "System Health"),
+    ("dashboard.api_server", "API Server"),
+    ("dashboard.database", "Database"),
+    ("dashboard.queue_pending", "Queue Pending"),
+    ("dashboard.queue_processing", "Queue Processing"),
+    ("dashboard.storage", "Storage"),
+    ("dashboard.in_progress", "in progress"),
+    ("dashboard.connected", "Connected"),
+    ("dashboard.unreachable", "Unreachable"),
+    ("dashboard.waiting", "Waiting for calls..."),
+    ("status.pending", "Pending"),
+    ("status.processing", "Processing"),
+    ("status.completed", "Completed"),
+    ("status.failed", "Failed"),
+    ("status.skipped", "Skipped"),
+    ("status.awaiting_audio", "Awaiting audio"),
+    ("status.deferred", "Deferred"),
+    ("column.time", "Time"),
+    ("column.system", "System"),
+    ("column.talkgroup", "Talkgroup"),
+    ("column.duration", "Duration"),
+    ("column.status", "Status"),
+    ("column.transcription", "Transcription"),
+    ("column.actions", "Actions"),
+    ("calls.search", "Search transcripts and summaries..."),
+    (
+        "calls.search_help",
+        "Narrow the search with field:value terms, e.g. system:butler tg:1234..1299 text~\"pursuit\". Fields: system, tg, radio, freq, duration, status, tag, lang, label, text. Combine terms with AND, OR, NOT (or -) and parentheses.",
+    ),
+    ("calls.from_date", "From date"),
+    ("calls.to_date", "To date"),
+    ("calls.tag", "Tag"),
+    ("calls.language", "Language (e.g. en)"),
+    ("calls.all_status", "All Status"),
+    ("calls.search_button", "Search"),
+    ("calls.share", "Share"),
+    ("calls.share_title", "Copy a link to these filters"),
+    ("calls.compare", "Compare"),
+    (
+        "calls.compare_title",
+        "Compare 2 to 10 ticked calls side by side",
+    ),
+    ("calls.saved_searches", "Saved searches"),
+    ("calls.save_search", "Save search"),
+    ("calls.delete_search", "Delete saved search"),
+    (
+        "calls.empty",
+        "No calls found. Upload some audio files or configure SDRTrunk to start receiving calls.",
+    ),
+    (
+        "calls.no_match",
+        "No calls found matching your search criteria.",
+    ),
+    (
+        "calls.adjust",
+        "Try adjusting your filters or search terms.",
+    ),
+    ("calls.play", "Play"),
+    ("calls.details", "Details"),
+    ("calls.previous", "Previous"),
+    ("calls.next", "Next"),
+    ("calls.page_one", "Page 1 of 1"),
+];
+
+/// Spanish text
+const SPANISH: &[(&str, &str)] = &[
+    ("title.dashboard", "Panel"),
+    ("title.calls", "Llamadas de radio"),
+    ("title.conversations", "Conversaciones"),
+    ("title.map", "Mapa"),
+    ("title.review", "Revisión"),
+    ("title.stats", "Estadísticas"),
+    ("title.login", "Iniciar sesión"),
+    ("title.admin", "Administración"),
+    ("title.compare", "Comparación de llamadas"),
+    ("nav.dashboard", "Panel"),
+    ("nav.calls", "Llamadas"),
+    ("nav.conversations", "Conversaciones"),
+    ("nav.map", "Mapa"),
+    ("nav.review", "Revisión"),
+    ("nav.stats", "Estadísticas"),
+    ("nav.admin", "Administración"),
+    ("nav.sign_out", "Cerrar sesión"),
+    ("nav.language", "Idioma"),
+    ("theme.light", "Modo claro"),
+    ("theme.dark", "Modo oscuro"),
+    ("common.all_systems", "Todos los sistemas"),
+    ("common.all_talkgroups", "Todos los grupos"),
+    ("common.clear_filters", "Borrar filtros"),
+    ("common.loading", "Cargando..."),
+    ("common.unknown", "Desconocido"),
+    ("common.na", "N/D"),
+    ("login.username", "Usuario"),
+    ("login.password", "Contraseña"),
+    ("login.failed", "Error al iniciar sesión"),
+    ("dashboard.heading", "Panel en vivo"),
+    ("dashboard.system_status", "Estado del sistema"),
+    ("dashboard.online", "En línea"),
+    ("dashboard.api", "API"),
+    ("dashboard.available", "Disponible"),
+    ("dashboard.workers", "Trabajadores"),
+    ("dashboard.checking", "Comprobando..."),
+    ("dashboard.calls_per_hour", "Llamadas / hora"),
+    ("dashboard.last_24h", "en las últimas 24 horas"),
+    ("dashboard.queue_depth", "Longitud de la cola"),
+    ("dashboard.jobs_waiting", "trabajos en espera"),
+    ("dashboard.backlog", "Transcripciones pendientes"),
+    ("dashboard.backlog_calls", "llamadas"),
+    ("dashboard.error_rate", "Tasa de errores"),
+    ("dashboard.storage_quotas", "Cuotas de almacenamiento"),
+    (
+        "dashboard.quota_rejects",
+        "Se rechazan las subidas al alcanzar la cuota",
+    ),
+    (
+        "dashboard.quota_purges",
+        "Se eliminan las grabaciones más antiguas para no superar la cuota",
+    ),
+    ("dashboard.of_finished", "de las transcripciones terminadas"),
+    ("dashboard.no_processing", "Ninguna llamada en proceso"),
+    ("dashboard.expand", "Haga clic para expandir"),
+    ("dashboard.live_scanner", "Escáner en vivo"),
+    ("dashboard.listen_live", "Escuchar en vivo"),
+    (
+        "dashboard.live_talkgroups",
+        "Grupos, p. ej. 1001, 1002 (vacío para todos)",
+    ),
+    ("dashboard.skip", "Saltar"),
+    ("dashboard.off", "Apagado"),
+    ("dashboard.search", "Buscar transcripciones..."),
+    ("dashboard.transcriptions", "Transcripciones"),
+    (
+        "dashboard.loading_transcriptions",
+        "Cargando transcripciones...",
+    ),
+    ("dashboard.load_more", "Cargar más"),
+    ("dashboard.system_statistics", "Estadísticas del sistema"),
+    ("dashboard.total_calls", "Llamadas totales"),
+    ("dashboard.calls_today", "Llamadas de hoy"),
+    ("dashboard.transcribed", "Transcritas"),
+    ("dashboard.system_health", "Estado de los servicios"),
+    ("dashboard.api_server", "Servidor API"),
+    ("dashboard.database", "Base de datos"),
+    ("dashboard.queue_pending", "Cola pendiente"),
+    ("dashboard.queue_processing", "Cola en proceso"),
+    ("dashboard.storage", "Almacenamiento"),
+    ("dashboard.in_progress", "en curso"),
+    ("dashboard.connected", "Conectada"),
+    ("dashboard.unreachable", "Inaccesible"),
+    ("dashboard.waiting", "Esperando llamadas..."),
+    ("status.pending", "Pendiente"),
+    ("status.processing", "Procesando"),
+    ("status.completed", "Completada"),
+    ("status.failed", "Fallida"),
+    ("status.skipped", "Omitida"),
+    ("status.awaiting_audio", "Esperando audio"),
+    ("status.deferred", "Aplazada"),
+    ("column.time", "Hora"),
+    ("column.system", "Sistema"),
+    ("column.talkgroup", "Grupo"),
+    ("column.duration", "Duración"),
+    ("column.status", "Estado"),
+    ("column.transcription", "Transcripción"),
+    ("column.actions", "Acciones"),
+    ("calls.search", "Buscar en transcripciones y resúmenes..."),
+    (
+        "calls.search_help",
+        "Acota la búsqueda con términos campo:valor, p. ej. system:butler tg:1234..1299 text~\"pursuit\". Campos: system, tg, radio, freq, duration, status, tag, lang, label, text. Combina términos con AND, OR, NOT (o -) y paréntesis.",
+    ),
+    ("calls.from_date", "Desde"),
+    ("calls.to_date", "Hasta"),
+    ("calls.tag", "Etiqueta"),
+    ("calls.language", "Idioma (p. ej. es)"),
+    ("calls.all_status", "Todos los estados"),
+    ("calls.search_button", "Buscar"),
+    ("calls.share", "Compartir"),
+    ("calls.share_title", "Copiar un enlace con estos filtros"),
+    ("calls.compare", "Comparar"),
+    (
+        "calls.compare_title",
+        "Comparar de 2 a 10 llamadas marcadas en paralelo",
+    ),
+    ("calls.saved_searches", "Búsquedas guardadas"),
+    ("calls.save_search", "Guardar búsqueda"),
+    ("calls.delete_search", "Eliminar búsqueda guardada"),
+    (
+        "calls.empty",
+        "No se encontraron llamadas. Suba archivos de audio o configure SDRTrunk para empezar a recibir llamadas.",
+    ),
+    (
+        "calls.no_match",
+        "No se encontraron llamadas que coincidan con la búsqueda.",
+    ),
+    (
+        "calls.adjust",
+        "Pruebe a cambiar los filtros o los términos de búsqueda.",
+    ),
+    ("calls.play", "Reproducir"),
+    ("calls.details", "Detalles"),
+    ("calls.previous", "Anterior"),
+    ("calls.next", "Siguiente"),
+    ("calls.page_one", "Página 1 de 1"),
+];
+
+/// German text
+const GERMAN: &[(&str, &str)] = &[
+    ("title.dashboard", "Übersicht"),
+    ("title.calls", "Funkrufe"),
+    ("title.conversations", "Gespräche"),
+    ("title.map", "Karte"),
+    ("title.review", "Prüfung"),
+    ("title.stats", "Statistik"),
+    ("title.login", "Anmelden"),
+    ("title.admin", "Verwaltung"),
+    ("title.compare", "Anrufvergleich"),
+    ("nav.dashboard", "Übersicht"),
+    ("nav.calls", "Anrufe"),
+    ("nav.conversations", "Gespräche"),
+    ("nav.map", "Karte"),
+    ("nav.review", "Prüfung"),
+    ("nav.stats", "Statistik"),
+    ("nav.admin", "Verwaltung"),
+    ("nav.sign_out", "Abmelden"),
+    ("nav.language", "Sprache"),
+    ("theme.light", "Heller Modus"),
+    ("theme.dark", "Dunkler Modus"),
+    ("common.all_systems", "Alle Systeme"),
+    ("common.all_talkgroups", "Alle Sprechgruppen"),
+    ("common.clear_filters", "Filter zurücksetzen"),
+    ("common.loading", "Wird geladen..."),
+    ("common.unknown", "Unbekannt"),
+    ("common.na", "k. A."),
+    ("login.username", "Benutzername"),
+    ("login.password", "Passwort"),
+    ("login.failed", "Anmeldung fehlgeschlagen"),
+    ("dashboard.heading", "Live-Übersicht"),
+    ("dashboard.system_status", "Systemstatus"),
+    ("dashboard.online", "Online"),
+    ("dashboard.api", "API"),
+    ("dashboard.available", "Verfügbar"),
+    ("dashboard.workers", "Worker"),
+    ("dashboard.checking", "Wird geprüft..."),
+    ("dashboard.calls_per_hour", "Anrufe / Stunde"),
+    ("dashboard.last_24h", "in den letzten 24 Stunden"),
+    ("dashboard.queue_depth", "Warteschlangenlänge"),
+    ("dashboard.jobs_waiting", "wartende Aufträge"),
+    ("dashboard.backlog", "Transkriptionsrückstand"),
+    ("dashboard.backlog_calls", "Anrufe"),
+    ("dashboard.error_rate", "Fehlerquote"),
+    ("dashboard.storage_quotas", "Speicherkontingente"),
+    (
+        "dashboard.quota_rejects",
+        "Uploads werden abgelehnt, sobald das Kontingent erreicht ist",
+    ),
+    (
+        "dashboard.quota_purges",
+        "Die ältesten Aufnahmen werden gelöscht, um unter dem Kontingent zu bleiben",
+    ),
+    (
+        "dashboard.of_finished",
+        "der abgeschlossenen Transkriptionen",
+    ),
+    ("dashboard.no_processing", "Keine Anrufe in Bearbeitung"),
+    ("dashboard.expand", "Zum Aufklappen klicken"),
+    ("dashboard.live_scanner", "Live-Scanner"),
+    ("dashboard.listen_live", "Live mithören"),
+    (
+        "dashboard.live_talkgroups",
+        "Sprechgruppen, z. B. 1001, 1002 (leer für alle)",
+    ),
+    ("dashboard.skip", "Überspringen"),
+    ("dashboard.off", "Aus"),
+    ("dashboard.search", "Transkriptionen durchsuchen..."),
+    ("dashboard.transcriptions", "Transkriptionen"),
+    (
+        "dashboard.loading_transcriptions",
+        "Transkriptionen werden geladen...",
+    ),
+    ("dashboard.load_more", "Mehr laden"),
+    ("dashboard.system_statistics", "Systemstatistik"),
+    ("dashboard.total_calls", "Anrufe gesamt"),
+    ("dashboard.calls_today", "Anrufe heute"),
+    ("dashboard.transcribed", "Transkribiert"),
+    ("dashboard.system_health", "Systemzustand"),
+    ("dashboard.api_server", "API-Server"),
+    ("dashboard.database", "Datenbank"),
+    ("dashboard.queue_pending", "Warteschlange offen"),
+    ("dashboard.queue_processing", "Warteschlange in Bearbeitung"),
+    ("dashboard.storage", "Speicher"),
+    ("dashboard.in_progress", "in Bearbeitung"),
+    ("dashboard.connected", "Verbunden"),
+    ("dashboard.unreachable", "Nicht erreichbar"),
+    ("dashboard.waiting", "Warte auf Anrufe..."),
+    ("status.pending", "Ausstehend"),
+    ("status.processing", "In Bearbeitung"),
+    ("status.completed", "Abgeschlossen"),
+    ("status.failed", "Fehlgeschlagen"),
+    ("status.skipped", "Übersprungen"),
+    ("status.awaiting_audio", "Wartet auf Audio"),
+    ("status.deferred", "Zurückgestellt"),
+    ("column.time", "Zeit"),
+    ("column.system", "System"),
+    ("column.talkgroup", "Sprechgruppe"),
+    ("column.duration", "Dauer"),
+    ("column.status", "Status"),
+    ("column.transcription", "Transkription"),
+    ("column.actions", "Aktionen"),
+    (
+        "calls.search",
+        "Transkripte und Zusammenfassungen durchsuchen...",
+    ),
+    (
+        "calls.search_help",
+        "Suche mit Feld:Wert-Begriffen eingrenzen, z. B. system:butler tg:1234..1299 text~\"pursuit\". Felder: system, tg, radio, freq, duration, status, tag, lang, label, text. Begriffe mit AND, OR, NOT (oder -) und Klammern verknüpfen.",
+    ),
+    ("calls.from_date", "Von"),
+    ("calls.to_date", "Bis"),
+    ("calls.tag", "Schlagwort"),
+    ("calls.language", "Sprache (z. B. de)"),
+    ("calls.all_status", "Alle Status"),
+    ("calls.search_button", "Suchen"),
+    ("calls.share", "Teilen"),
+    ("calls.share_title", "Link zu diesen Filtern kopieren"),
+    ("calls.compare", "Vergleichen"),
+    (
+        "calls.compare_title",
+        "2 bis 10 markierte Anrufe nebeneinander vergleichen",
+    ),
+    ("calls.saved_searches", "Gespeicherte Suchen"),
+    ("calls.save_search", "Suche speichern"),
+    ("calls.delete_search", "Gespeicherte Suche löschen"),
+    (
+        "calls.empty",
+        "Keine Anrufe gefunden. Laden Sie Audiodateien hoch oder richten Sie SDRTrunk ein, um Anrufe zu empfangen.",
+    ),
+    ("calls.no_match", "Keine Anrufe passen zu Ihrer Suche."),
+    ("calls.adjust", "Ändern Sie die Filter oder Suchbegriffe."),
+    ("calls.play", "Abspielen"),
+    ("calls.details", "Details"),
+    ("calls.previous", "Zurück"),
+    ("calls.next", "Weiter"),
+    ("calls.page_one", "Seite 1 von 1"),
+];
+
+#[cfg(test)]
+#[allow(clippy::missing_panics_doc, unused_results)]
+mod tests {
+    use super::*;
+
+    const TEMPLATES: &[&str] = &[
+        include_str!("../templates/admin.html"),
+        include_str!("../templates/calls.html"),
+        include_str!("../templates/compare.html"),
+        include_str!("../templates/conversations.html"),
+        include_str!("../templates/dashboard.html"),
+        include_str!("../templates/login.html"),
+        include_str!("../templates/map.html"),
+        include_str!("../templates/review.html"),
+        include_str!("../templates/stats.html"),
+    ];
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Language::from_code("es"), Some(Language::Spanish));
+        assert_eq!(Language::from_code("DE-at"), Some(Language::German));
+        assert_eq!(Language::from_code("en_GB"), Some(Language::English));
+        assert_eq!(Language::from_code("fr"), None);
+        assert_eq!(Language::from_code(""), None);
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Language::from_headers(&headers), Language::English);
+
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            HeaderValue::from_static("fr-FR, de;q=0.7, es;q=0.9"),
+        );
+        assert_eq!(Language::from_headers(&headers), Language::Spanish);
+
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            HeaderValue::from_static("es;q=0, fr"),
+        );
+        assert_eq!(Language::from_headers(&headers), Language::English);
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("sdrtrunk_session=abc; sdrtrunk_lang=de"),
+        );
+        assert_eq!(Language::from_headers(&headers), Language::German);
+    }
+
+    #[test]
+    fn test_localize() {
+        let html = r#"<html lang="{{lang}}"><a>{{t:nav.calls}}</a> {{t:missing}} {{unclosed"#;
+        assert_eq!(
+            localize(html, Language::German),
+            r#"<html lang="de"><a>Anrufe</a> {{t:missing}} {{unclosed"#
+        );
+        assert_eq!(
+            localize("{{language_options}}", Language::Spanish),
+            r#"<option value="en">English</option><option value="es" selected>Español</option><option value="de">Deutsch</option>"#
+        );
+    }
+
+    #[test]
+    fn test_language_cookie() {
+        assert_eq!(
+            language_cookie(Language::Spanish),
+            "sdrtrunk_lang=es; Path=/; SameSite=Lax; Max-Age=31536000"
+        );
+    }
+
+    #[test]
+    fn test_packs_cover_templates() {
+        for language in Language::ALL {
+            for &(key, text) in language.pack() {
+                assert!(
+                    !text.contains(['"', '\'', '`', '\\', '<', '>', '&']),
+                    "{key} in {language:?} cannot be pasted into a template"
+                );
+                assert!(ENGLISH.iter().any(|(name, _)| *name == key), "{key}");
+            }
+            assert_eq!(language.pack().len(), ENGLISH.len(), "{language:?}");
+        }
+        for template in TEMPLATES {
+            let localized = localize(template, Language::Spanish);
+            assert!(!localized.contains("{{"), "untranslated marker");
+        }
+    }
+}
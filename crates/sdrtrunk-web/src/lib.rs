@@ -10,13 +10,15 @@ pub mod websocket;
 pub(crate) mod app;
 pub(crate) mod components;
 pub(crate) mod handlers;
+pub(crate) mod i18n;
 pub(crate) mod pages;
 pub(crate) mod routes;
+pub(crate) mod session;
 pub(crate) mod state;
 
 // Re-export the main server entry point
 pub mod server;
 
 // Re-export the main functions
-pub use server::build_app;
+pub use server::{build_app, build_ui};
 pub use state::AppState;
@@ -3,7 +3,7 @@
 #![allow(clippy::type_complexity)]
 
 use sdrtrunk_protocol::Config;
-use sdrtrunk_web::build_app;
+use sdrtrunk_web::{build_app, build_ui};
 use std::net::{IpAddr, SocketAddr};
 use tracing::{info, warn};
 
@@ -24,11 +24,22 @@ fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
 #[tokio::main]
 #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = load_config();
+
+    // Embedded: run the API server, which sets up logging and loads the
+    // configuration itself, with the interface under /ui
+    if config
+        .as_ref()
+        .is_ok_and(|config| config.webserver.embedded)
+    {
+        return Ok(sdrtrunk_api::server::run(Some(build_ui)).await?);
+    }
+
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
     // Get configuration
-    let config = load_config().unwrap_or_else(|e| {
+    let config = config.unwrap_or_else(|e| {
         warn!("Failed to load config: {}, using defaults", e);
         Config::default()
     });
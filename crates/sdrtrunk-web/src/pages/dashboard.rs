@@ -1,15 +1,88 @@
 //! Dashboard page showing live call activity and system status
-#![allow(unreachable_pub)]
+#![allow(unreachable_pub, clippy::too_many_lines)]
 
+use crate::api_client::DashboardStats;
+use futures_util::StreamExt;
+use gloo_net::websocket::{Message, futures::WebSocket};
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::time::Duration;
+
+/// Delay before reconnecting after the live statistics socket closes
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Width of the calls/hour sparkline in SVG units
+const SPARKLINE_WIDTH: f64 = 120.0;
+
+/// Height of the calls/hour sparkline in SVG units
+const SPARKLINE_HEIGHT: f64 = 32.0;
 
 /// Main dashboard page component
 #[allow(unreachable_pub)]
 #[component]
 pub fn Dashboard() -> impl IntoView {
+    let stats = RwSignal::new(None::<DashboardStats>);
+
+    // Initial figures come over HTTP; updates arrive on the WebSocket
+    spawn_local(async move {
+        match fetch_dashboard_stats().await {
+            Ok(initial) => stats.set(Some(initial)),
+            Err(e) => leptos::logging::warn!("Failed to load dashboard stats: {e}"),
+        }
+    });
+    connect_live_stats(stats);
+
     view! {
         <div class="dashboard">
             <h2>Live Dashboard</h2>
+            <div class="stats-cards">
+                <div class="stats-card">
+                    <h3>Calls / Hour</h3>
+                    <svg
+                        class="sparkline"
+                        viewBox=format!("0 0 {SPARKLINE_WIDTH} {SPARKLINE_HEIGHT}")
+                        preserveAspectRatio="none"
+                    >
+                        <polyline
+                            fill="none"
+                            stroke="currentColor"
+                            stroke-width="1.5"
+                            points=move || {
+                                stats
+                                    .with(|s| s.as_ref().map(|s| sparkline_points(&s.calls_per_hour)))
+                                    .unwrap_or_default()
+                            }
+                        />
+                    </svg>
+                    <span class="stat-value">
+                        {move || stats.with(|s| s.as_ref().map_or_else(|| "-".to_string(), |s| s.calls_last_24h.to_string()))}
+                    </span>
+                    <span class="stat-label">" calls in the last 24 hours"</span>
+                </div>
+                <div class="stats-card">
+                    <h3>Queue Depth</h3>
+                    <span class="stat-value">
+                        {move || stats.with(|s| s.as_ref().map_or_else(|| "-".to_string(), |s| s.queue_depth.to_string()))}
+                    </span>
+                    <span class="stat-label">" jobs waiting"</span>
+                </div>
+                <div class="stats-card">
+                    <h3>Transcription Backlog</h3>
+                    <span class="stat-value">
+                        {move || stats.with(|s| s.as_ref().map_or_else(|| "-".to_string(), |s| s.transcription_backlog.to_string()))}
+                    </span>
+                    <span class="stat-label">
+                        {move || stats.with(|s| s.as_ref().map_or_else(String::new, |s| format!(" calls, {} in progress", s.processing)))}
+                    </span>
+                </div>
+                <div class="stats-card">
+                    <h3>Error Rate</h3>
+                    <span class="stat-value">
+                        {move || stats.with(|s| format_error_rate(s.as_ref().and_then(|s| s.error_rate)))}
+                    </span>
+                    <span class="stat-label">" of finished transcriptions"</span>
+                </div>
+            </div>
             <div class="dashboard-grid">
                 <div class="dashboard-card">
                     <h3>Live Calls</h3>
@@ -32,14 +105,103 @@ pub fn Dashboard() -> impl IntoView {
                         // TODO: Implement recent activity feed
                     </div>
                 </div>
-                <div class="dashboard-card">
-                    <h3>Quick Stats</h3>
-                    <div class="quick-stats">
-                        <p>Key metrics will appear here</p>
-                        // TODO: Implement quick statistics display
-                    </div>
-                </div>
             </div>
         </div>
     }
 }
+
+/// Keep `stats` current from `dashboard_stats` WebSocket messages,
+/// reconnecting whenever the socket closes
+fn connect_live_stats(stats: RwSignal<Option<DashboardStats>>) {
+    spawn_local(async move {
+        listen_for_stats(stats).await;
+        set_timeout(move || connect_live_stats(stats), RECONNECT_DELAY);
+    });
+}
+
+/// Apply `dashboard_stats` messages to `stats` until the socket closes
+#[allow(clippy::future_not_send)]
+async fn listen_for_stats(stats: RwSignal<Option<DashboardStats>>) {
+    let location = window().location();
+    let (Ok(protocol), Ok(host)) = (location.protocol(), location.host()) else {
+        return;
+    };
+    let scheme = if protocol == "https:" { "wss" } else { "ws" };
+
+    let mut socket = match WebSocket::open(&format!("{scheme}://{host}/ws")) {
+        Ok(socket) => socket,
+        Err(e) => {
+            leptos::logging::warn!("Failed to open dashboard WebSocket: {e}");
+            return;
+        }
+    };
+
+    while let Some(Ok(message)) = socket.next().await {
+        if let Message::Text(text) = message
+            && let Some(update) = parse_stats_message(&text)
+        {
+            stats.set(Some(update));
+        }
+    }
+}
+
+/// Extract the payload of a `dashboard_stats` WebSocket message
+///
+/// Other message types (call updates, transcription progress) yield `None`.
+fn parse_stats_message(text: &str) -> Option<DashboardStats> {
+    let message: serde_json::Value = serde_json::from_str(text).ok()?;
+    if message.get("type")?.as_str()? != "dashboard_stats" {
+        return None;
+    }
+    serde_json::from_value(message.get("data")?.clone()).ok()
+}
+
+/// SVG polyline points plotting `counts` across the sparkline, scaled so the
+/// busiest hour touches the top
+#[allow(clippy::cast_precision_loss)]
+fn sparkline_points(counts: &[i64]) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let step = if counts.len() > 1 {
+        SPARKLINE_WIDTH / (counts.len() - 1) as f64
+    } else {
+        0.0
+    };
+
+    counts
+        .iter()
+        .enumerate()
+        .map(|(hour, &count)| {
+            let left = hour as f64 * step;
+            let top = (count as f64 / max).mul_add(-SPARKLINE_HEIGHT, SPARKLINE_HEIGHT);
+            format!("{left:.1},{top:.1}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Format an error fraction as a percentage
+fn format_error_rate(rate: Option<f64>) -> String {
+    rate.map_or_else(|| "N/A".to_string(), |r| format!("{:.1}%", r * 100.0))
+}
+
+/// Fetch the current dashboard statistics from the web server
+///
+/// # Errors
+///
+/// Returns an error string if the HTTP request fails or the response cannot be parsed.
+#[allow(clippy::future_not_send)]
+async fn fetch_dashboard_stats() -> Result<DashboardStats, String> {
+    let response = gloo_net::http::Request::get("/api/stats/dashboard")
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+
+    if !response.ok() {
+        return Err(format!("API error: {}", response.status()));
+    }
+
+    response
+        .json::<DashboardStats>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {e}"))
+}
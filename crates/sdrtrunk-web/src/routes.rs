@@ -5,7 +5,10 @@ use crate::{
     handlers::{api, pages},
     state::AppState,
 };
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
 use std::sync::Arc;
 
 /// Build the complete web application router
@@ -14,12 +17,40 @@ pub fn build_routes() -> Router<Arc<AppState>> {
         // Page routes
         .route("/", get(pages::dashboard))
         .route("/calls", get(pages::calls_page))
+        .route("/compare", get(pages::compare_page))
+        .route("/conversations", get(pages::conversations_page))
+        .route("/map", get(pages::map_page))
+        .route("/review", get(pages::review_page))
         .route("/stats", get(pages::stats_page))
         .route("/admin", get(pages::admin_page))
+        .route("/login", get(pages::login_page))
+        .route("/logout", get(api::logout))
+        .route("/language/:code", get(pages::set_language))
         // API proxy routes
+        .route("/api/auth/login", post(api::api_login))
         .route("/api/calls", get(api::api_calls))
+        .route("/api/calls/geo", get(api::api_geo_calls))
+        .route("/api/calls/compare", get(api::api_compare_calls))
         .route("/api/stats/global", get(api::api_global_stats))
+        .route("/api/stats/storage", get(api::api_storage_growth))
+        .route("/api/stats/dashboard", get(api::api_dashboard_stats))
+        .route("/api/keys/:id/usage", get(api::api_key_usage))
         .route("/api/calls/:id/audio", get(api::serve_audio))
+        .route("/api/calls/:id/waveform", get(api::api_call_waveform))
+        .route("/api/calls/:id/summary", get(api::api_call_summary))
+        .route("/api/calls/:id/review", post(api::api_review_call))
+        .route("/api/calls/:id/tags", post(api::api_tag_call))
+        .route("/api/calls/:id/tags/:tag", delete(api::api_untag_call))
+        .route(
+            "/api/searches",
+            get(api::api_list_searches).post(api::api_create_search),
+        )
+        .route(
+            "/api/searches/:id",
+            put(api::api_update_search).delete(api::api_delete_search),
+        )
+        .route("/api/conversations", get(api::api_conversations))
+        .route("/api/conversations/:id", get(api::api_conversation))
         // WebSocket for real-time updates
         .route("/ws", get(api::websocket_handler))
         // Health check
@@ -1,13 +1,42 @@
 //! Web server setup and configuration
 
-use crate::{routes::build_routes, state::AppState};
+use crate::{
+    routes::build_routes, session::require_login, state::AppState, websocket::WebSocketClient,
+};
 use axum::Router;
+use sdrtrunk_api::server::UI_PATH;
 use sdrtrunk_protocol::Config;
 use std::sync::Arc;
 
 /// Build the complete web application with all routes and state
+///
+/// Also spawns the backend WebSocket client that feeds live transcription
+/// progress to browsers, so this must be called inside a Tokio runtime.
 pub fn build_app(config: Config) -> Router {
-    let state = Arc::new(AppState::new(config));
+    build(AppState::new(config))
+}
+
+/// Build the web application for the API server to serve under
+/// [`UI_PATH`], with its links rewritten to match
+///
+/// Pass to [`sdrtrunk_api::server::run`]. Like [`build_app`], this must be
+/// called inside a Tokio runtime.
+pub fn build_ui(config: Config) -> Router {
+    build(AppState::new(config).with_base_path(UI_PATH))
+}
+
+fn build(state: AppState) -> Router {
+    let state = Arc::new(state);
+
+    let client = WebSocketClient::new(state.api_ws_url.clone())
+        .with_progress(state.progress.clone())
+        .with_new_calls(state.new_calls.clone());
+    drop(tokio::spawn(async move { client.run().await }));
 
-    build_routes().with_state(state)
+    build_routes()
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            require_login,
+        ))
+        .with_state(state)
 }
@@ -0,0 +1,196 @@
+//! Sign-in for the web interface
+//!
+//! With `webserver.require_login` set, [`require_login`] runs in front of
+//! every route. The session token from `POST /api/auth/login` is kept in the
+//! `sdrtrunk_session` cookie and checked against the backend on each request.
+//! Pages redirect to `/login` without a valid session, API routes answer 401,
+//! and the Admin page is only shown to admins.
+//!
+//! The `/ws` feed authenticates itself on every upgrade, whatever
+//! `require_login` says, with [`request_credential`].
+#![allow(unreachable_pub)]
+
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+};
+use sdrtrunk_types::UserRole;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Cookie holding the session token
+const SESSION_COOKIE: &str = "sdrtrunk_session";
+
+/// Paths reachable without signing in
+const PUBLIC_PATHS: &[&str] = &["/login", "/logout", "/api/auth/login", "/health"];
+
+/// Path prefixes reachable without signing in (the language picker works on
+/// the sign-in page too)
+const PUBLIC_PREFIXES: &[&str] = &["/language/"];
+
+/// Paths whose handlers check credentials themselves
+const SELF_AUTHENTICATED_PATHS: &[&str] = &["/ws"];
+
+/// Pages only admins may see
+const ADMIN_PATHS: &[&str] = &["/admin"];
+
+/// The session token from the request's cookies
+pub fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find_map(|(name, value)| (name == SESSION_COOKIE && !value.is_empty()).then_some(value))
+}
+
+/// The credential a request presents to the backend
+///
+/// Checked in order: an `X-API-Key` header, an `Authorization: Bearer`
+/// header, `query_token` (browsers cannot set headers on a WebSocket
+/// upgrade), and the session cookie. The backend accepts API keys and session
+/// tokens in the same places, so the result is forwarded as is.
+pub fn request_credential<'a>(
+    headers: &'a HeaderMap,
+    query_token: Option<&'a str>,
+) -> Option<&'a str> {
+    let header_value = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    header_value(header::HeaderName::from_static("x-api-key"))
+        .or_else(|| {
+            header_value(header::AUTHORIZATION)
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        })
+        .or_else(|| query_token.filter(|token| !token.is_empty()))
+        .or_else(|| session_token(headers))
+}
+
+/// `Set-Cookie` value storing `token` for `max_age_seconds`
+///
+/// A zero `max_age_seconds` with an empty token clears the cookie.
+pub fn session_cookie(token: &str, max_age_seconds: i64) -> HeaderValue {
+    let cookie = format!(
+        "{SESSION_COOKIE}={token}; Path=/; HttpOnly; SameSite=Lax; Max-Age={max_age_seconds}"
+    );
+    HeaderValue::from_str(&cookie).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+/// Middleware that requires a signed-in user when `webserver.require_login`
+/// is set
+pub async fn require_login(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !state.config.webserver.require_login
+        || PUBLIC_PATHS.contains(&path)
+        || PUBLIC_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        || SELF_AUTHENTICATED_PATHS.contains(&path)
+    {
+        return next.run(request).await;
+    }
+
+    let role = match session_token(request.headers()) {
+        Some(token) => state
+            .api_client
+            .get_session(token)
+            .await
+            .ok()
+            .and_then(|session| session.user)
+            .map(|user| user.role),
+        None => None,
+    };
+
+    match role {
+        None => {
+            debug!("Sign-in required for {path}");
+            if path.starts_with("/api/") {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    Json(serde_json::json!({
+                        "error": "Sign-in required",
+                        "message": format!("Sign in at {}", state.url("/login"))
+                    })),
+                )
+                    .into_response()
+            } else {
+                Redirect::to(&state.url("/login")).into_response()
+            }
+        }
+        Some(role) if ADMIN_PATHS.contains(&path) && !role.allows(UserRole::Admin) => {
+            (StatusCode::FORBIDDEN, "Only admins may view this page").into_response()
+        }
+        Some(_) => next.run(request).await,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::missing_panics_doc, unused_results)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_token_from_cookies() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(session_token(&headers), None);
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; sdrtrunk_session=abc123; other=1"),
+        );
+        assert_eq!(session_token(&headers), Some("abc123"));
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("sdrtrunk_session="),
+        );
+        assert_eq!(session_token(&headers), None);
+    }
+
+    #[test]
+    fn test_request_credential_order() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_credential(&headers, None), None);
+        assert_eq!(request_credential(&headers, Some("")), None);
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("sdrtrunk_session=cookie"),
+        );
+        assert_eq!(request_credential(&headers, None), Some("cookie"));
+        assert_eq!(request_credential(&headers, Some("query")), Some("query"));
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer bearer"),
+        );
+        assert_eq!(request_credential(&headers, Some("query")), Some("bearer"));
+
+        headers.insert("x-api-key", HeaderValue::from_static("key"));
+        assert_eq!(request_credential(&headers, Some("query")), Some("key"));
+    }
+
+    #[test]
+    fn test_session_cookie() {
+        let cookie = session_cookie("abc123", 3600);
+        assert_eq!(
+            cookie,
+            "sdrtrunk_session=abc123; Path=/; HttpOnly; SameSite=Lax; Max-Age=3600"
+        );
+    }
+}
@@ -1,7 +1,13 @@
 //! Application state management
 
-use crate::api_client::ApiClient;
+use crate::{api_client::ApiClient, websocket::CallData};
 use sdrtrunk_protocol::Config;
+use sdrtrunk_storage::TranscriptionProgress;
+use tokio::sync::broadcast;
+
+/// Capacity of the progress and new-call broadcasts; slow browsers skip
+/// older events
+const PROGRESS_CHANNEL_CAPACITY: usize = 256;
 
 /// Application state holding configuration and clients
 #[derive(Clone, Debug)]
@@ -10,6 +16,14 @@ pub struct AppState {
     pub config: Config,
     /// API client for backend communication
     pub api_client: ApiClient,
+    /// Backend WebSocket URL for real-time events
+    pub api_ws_url: String,
+    /// Transcription progress relayed from the backend to browsers
+    pub progress: broadcast::Sender<TranscriptionProgress>,
+    /// New calls relayed from the backend to browsers for live listening
+    pub new_calls: broadcast::Sender<CallData>,
+    /// Path prefix the interface is served under (empty at the root)
+    pub base_path: String,
 }
 
 impl AppState {
@@ -24,10 +38,33 @@ impl AppState {
         let api_port = config.webserver.api_port.unwrap_or(config.server.port);
 
         let api_base_url = format!("http://{api_host}:{api_port}");
+        let api_ws_url = format!("ws://{api_host}:{api_port}/api/ws");
 
         let api_client = ApiClient::new(api_base_url);
+        let (progress, _) = broadcast::channel(PROGRESS_CHANNEL_CAPACITY);
+        let (new_calls, _) = broadcast::channel(PROGRESS_CHANNEL_CAPACITY);
+
+        Self {
+            config,
+            api_client,
+            api_ws_url,
+            progress,
+            new_calls,
+            base_path: String::new(),
+        }
+    }
+
+    /// Serve the interface under `base_path` (e.g. `/ui`) instead of the root
+    #[must_use]
+    pub fn with_base_path(mut self, base_path: &str) -> Self {
+        self.base_path = base_path.trim_end_matches('/').to_string();
+        self
+    }
 
-        Self { config, api_client }
+    /// Browser-facing URL of an interface path such as `/login`
+    #[must_use]
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_path)
     }
 }
 
@@ -48,6 +85,7 @@ mod tests {
         // We can't directly access api_client.base_url, but we can verify it was created
         assert_eq!(state.config.webserver.api_host, "api.example.com");
         assert_eq!(state.config.webserver.api_port, Some(9090));
+        assert_eq!(state.api_ws_url, "ws://api.example.com:9090/api/ws");
     }
 
     #[test]
@@ -70,5 +108,16 @@ mod tests {
         assert_eq!(config.webserver.api_host, "localhost");
         assert_eq!(config.webserver.api_port, None);
         assert_eq!(config.webserver.port, 8081);
+        assert!(!config.webserver.embedded);
+    }
+
+    #[test]
+    fn test_base_path() {
+        let state = AppState::new(Config::default());
+        assert_eq!(state.url("/login"), "/login");
+
+        let state = state.with_base_path("/ui/");
+        assert_eq!(state.base_path, "/ui");
+        assert_eq!(state.url("/login"), "/ui/login");
     }
 }
@@ -1,21 +1,57 @@
 //! WebSocket client for real-time updates
 
 use futures_util::StreamExt;
+use sdrtrunk_storage::TranscriptionProgress;
 use sdrtrunk_types::{AppError as Error, AppResult as Result};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::broadcast;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
+
+/// Delay before reconnecting after the connection drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
 
 /// WebSocket client for receiving real-time updates
 #[derive(Debug)]
 pub struct WebSocketClient {
     url: String,
+    progress: Option<broadcast::Sender<TranscriptionProgress>>,
+    new_calls: Option<broadcast::Sender<CallData>>,
 }
 
 impl WebSocketClient {
     /// Create a new WebSocket client
     pub fn new(url: impl Into<String>) -> Self {
-        Self { url: url.into() }
+        Self {
+            url: url.into(),
+            progress: None,
+            new_calls: None,
+        }
+    }
+
+    /// Forward received transcription progress events to `sender`
+    #[must_use]
+    pub fn with_progress(mut self, sender: broadcast::Sender<TranscriptionProgress>) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    /// Forward received new-call events to `sender`
+    #[must_use]
+    pub fn with_new_calls(mut self, sender: broadcast::Sender<CallData>) -> Self {
+        self.new_calls = Some(sender);
+        self
+    }
+
+    /// Stay connected, reconnecting whenever the connection drops
+    pub async fn run(&self) {
+        loop {
+            if let Err(e) = self.connect().await {
+                warn!("{}", e);
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
     }
 
     /// Connect to the WebSocket server and handle messages
@@ -33,14 +69,13 @@ impl WebSocketClient {
 
         let (_write, mut read) = ws_stream.split();
 
-        // TODO: Implement message handling loop
         while let Some(msg) = read.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
                     if let Ok(update) = serde_json::from_str::<WebSocketMessage>(&text) {
-                        Self::handle_message(update);
+                        self.handle_message(update);
                     } else {
-                        warn!("Failed to parse WebSocket message: {}", text);
+                        debug!("Ignoring unrecognized WebSocket message: {}", text);
                     }
                 }
                 Ok(Message::Close(_)) => {
@@ -59,20 +94,28 @@ impl WebSocketClient {
     }
 
     /// Handle incoming WebSocket messages
-    fn handle_message(message: WebSocketMessage) {
+    fn handle_message(&self, message: WebSocketMessage) {
         match message {
             WebSocketMessage::CallUpdate { call_id, status } => {
                 info!("Received call update: {} -> {:?}", call_id, status);
                 // TODO: Update UI with call status change
             }
-            WebSocketMessage::NewCall { call } => {
-                info!("Received new call: {:?}", call);
-                // TODO: Add new call to UI
+            WebSocketMessage::NewCall(call) => {
+                debug!("Received new call: {:?}", call);
+                if let Some(sender) = &self.new_calls {
+                    let _ = sender.send(call);
+                }
             }
             WebSocketMessage::SystemStatus { system_id, status } => {
                 info!("Received system status: {} -> {:?}", system_id, status);
                 // TODO: Update system status in UI
             }
+            WebSocketMessage::TranscriptionProgress(progress) => {
+                if let Some(sender) = &self.progress {
+                    // No subscribers just means no browser is connected
+                    let _ = sender.send(progress);
+                }
+            }
         }
     }
 }
@@ -89,12 +132,9 @@ pub enum WebSocketMessage {
         /// New status of the call
         status: CallStatus,
     },
-    /// New call received
+    /// New call received (sent while the backend's `live_listen` feature is on)
     #[serde(rename = "new_call")]
-    NewCall {
-        /// Call data for the new call
-        call: CallData,
-    },
+    NewCall(CallData),
     /// System status update
     #[serde(rename = "system_status")]
     SystemStatus {
@@ -103,6 +143,9 @@ pub enum WebSocketMessage {
         /// New status of the system
         status: SystemStatus,
     },
+    /// Transcription lifecycle event relayed from the API
+    #[serde(rename = "transcription_progress")]
+    TranscriptionProgress(TranscriptionProgress),
 }
 
 /// Call status for WebSocket updates
@@ -119,14 +162,20 @@ pub enum CallStatus {
 }
 
 /// Call data for WebSocket messages
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallData {
     /// Unique identifier for the call
-    pub id: uuid::Uuid,
+    pub call_id: uuid::Uuid,
     /// System ID this call belongs to
     pub system_id: String,
     /// Optional talkgroup ID
     pub talkgroup_id: Option<i32>,
+    /// Talkgroup display name
+    #[serde(default)]
+    pub talkgroup_label: Option<String>,
+    /// Transcription status the call was stored with
+    #[serde(default)]
+    pub transcription_status: Option<String>,
     /// Timestamp when the call was received
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
@@ -141,3 +190,32 @@ pub enum SystemStatus {
     /// System has errors
     Error(String),
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic, clippy::missing_panics_doc)]
+mod tests {
+    use super::*;
+    use sdrtrunk_api::handlers::websocket::WebSocketEvent;
+
+    #[test]
+    fn test_new_call_from_backend() {
+        let call_id = uuid::Uuid::new_v4();
+        let event = WebSocketEvent::NewCall {
+            call_id,
+            system_id: "county".to_string(),
+            talkgroup_id: Some(1001),
+            talkgroup_label: Some("Fire Dispatch".to_string()),
+            transcription_status: Some("pending".to_string()),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let message: WebSocketMessage =
+            serde_json::from_str(&serde_json::to_string(&event).unwrap()).unwrap();
+        let WebSocketMessage::NewCall(call) = message else {
+            panic!("expected a new call");
+        };
+        assert_eq!(call.call_id, call_id);
+        assert_eq!(call.talkgroup_id, Some(1001));
+        assert_eq!(call.talkgroup_label.as_deref(), Some("Fire Dispatch"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Transcription device scheduling.
+//!
+//! With `transcription.gpu_devices` configured, the worker loads its Whisper
+//! models on every GPU and transcribes several jobs at once. Each device admits
+//! up to its `max_concurrent` jobs; new jobs go to devices round-robin, skipping
+//! any device that is full. Without GPU devices a single CPU device handles
+//! one job at a time.
+//!
+//! Every device holds one engine per configured model (the default model plus
+//! any per-system overrides), so a job can run on any device whatever model
+//! its system uses.
+
+use anyhow::{Context, Result};
+use sdrtrunk_protocol::config::{GpuDeviceConfig, SilenceTrimConfig};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::info;
+
+use crate::whisper::WhisperEngine;
+
+/// Engines loaded on one device, by model name.
+type Engines = HashMap<String, Arc<WhisperEngine>>;
+
+/// One device's engines and its concurrency limit.
+struct Device {
+    /// Name used in logs (`cpu`, `gpu0`, ...).
+    name: String,
+    /// Engines bound to this device.
+    engines: Arc<Engines>,
+    /// Free job slots on this device.
+    slots: Arc<Semaphore>,
+}
+
+/// The worker's engines, one per device.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) struct DevicePool {
+    devices: Vec<Device>,
+    /// Free slots across all devices; waiting here avoids spinning when full.
+    capacity: Arc<Semaphore>,
+    /// Total slots across all devices.
+    total_slots: usize,
+    /// Device to try first on the next assignment.
+    next: AtomicUsize,
+    /// Model used for synthetic probes.
+    default_model: String,
+}
+
+/// A reserved job slot on one device, released on drop.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) struct DeviceSlot {
+    /// Device the slot belongs to.
+    pub(crate) device: String,
+    /// Engines on that device.
+    engines: Arc<Engines>,
+    // Released before `_capacity` (fields drop in order), so a holder of a
+    // capacity permit always finds a free device slot
+    _slot: OwnedSemaphorePermit,
+    _capacity: OwnedSemaphorePermit,
+}
+
+impl DeviceSlot {
+    /// Engine for `model`, if the worker loaded that model.
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) fn engine(&self, model: &str) -> Option<Arc<WhisperEngine>> {
+        self.engines.get(model).cloned()
+    }
+}
+
+impl DevicePool {
+    /// Load the models once per configured GPU, or once on CPU if none are set.
+    ///
+    /// `models` pairs each model name with its GGML file; the first is the
+    /// default model. Every engine trims silence as `silence` says.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a model cannot be loaded on a device.
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) fn load(
+        models: &[(String, PathBuf)],
+        gpu_devices: &[GpuDeviceConfig],
+        silence: &SilenceTrimConfig,
+    ) -> Result<Self> {
+        let default_model = models
+            .first()
+            .context("No Whisper model configured")?
+            .0
+            .clone();
+        let mut devices = Vec::new();
+        if gpu_devices.is_empty() {
+            devices.push(Device {
+                name: "cpu".to_string(),
+                engines: Arc::new(load_engines(models, None, silence)?),
+                slots: Arc::new(Semaphore::new(1)),
+            });
+        }
+        for gpu in gpu_devices {
+            let max_concurrent = gpu.max_concurrent.max(1);
+            info!(
+                device = gpu.id,
+                max_concurrent, "Loading Whisper models on GPU"
+            );
+            devices.push(Device {
+                name: format!("gpu{}", gpu.id),
+                engines: Arc::new(load_engines(models, Some(gpu.id), silence)?),
+                slots: Arc::new(Semaphore::new(max_concurrent)),
+            });
+        }
+
+        let total_slots = devices.iter().map(|d| d.slots.available_permits()).sum();
+        Ok(Self {
+            devices,
+            capacity: Arc::new(Semaphore::new(total_slots)),
+            total_slots,
+            next: AtomicUsize::new(0),
+            default_model,
+        })
+    }
+
+    /// Total jobs that can run at once.
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) const fn capacity(&self) -> usize {
+        self.total_slots
+    }
+
+    /// Engine used for synthetic probes (the default model on the first device).
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) fn probe_engine(&self) -> Option<&WhisperEngine> {
+        self.devices
+            .first()
+            .and_then(|d| d.engines.get(&self.default_model))
+            .map(AsRef::as_ref)
+    }
+
+    /// Wait for a free slot and reserve it on the next device in rotation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pool's semaphore was closed.
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) async fn acquire(&self) -> Result<DeviceSlot> {
+        let capacity = Arc::clone(&self.capacity)
+            .acquire_owned()
+            .await
+            .context("Device pool closed")?;
+        let count = self.devices.len();
+        loop {
+            let start = self.next.load(Ordering::Relaxed);
+            for offset in 0..count {
+                let index = (start + offset) % count;
+                let Some(device) = self.devices.get(index) else {
+                    continue;
+                };
+                if let Ok(slot) = Arc::clone(&device.slots).try_acquire_owned() {
+                    self.next.store(index + 1, Ordering::Relaxed);
+                    return Ok(DeviceSlot {
+                        device: device.name.clone(),
+                        engines: Arc::clone(&device.engines),
+                        _slot: slot,
+                        _capacity: capacity,
+                    });
+                }
+            }
+            // A finishing job frees its device slot just before its capacity
+            // permit; let it complete the release
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+/// Load every model on one device.
+///
+/// # Errors
+///
+/// Returns an error if a model cannot be loaded.
+fn load_engines(
+    models: &[(String, PathBuf)],
+    gpu_device: Option<i32>,
+    silence: &SilenceTrimConfig,
+) -> Result<Engines> {
+    models
+        .iter()
+        .map(|(model, path)| {
+            info!(model = %model, "Loading Whisper model");
+            Ok((
+                model.clone(),
+                Arc::new(WhisperEngine::load(path, gpu_device, silence.clone())?),
+            ))
+        })
+        .collect()
+}
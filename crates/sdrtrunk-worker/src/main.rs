@@ -3,22 +3,41 @@
 //! Designed to run as a K8s pod, this binary polls the `PostgreSQL` job queue for
 //! pending transcription jobs, processes them via whisper.cpp (whisper-rs), and
 //! writes results back to the database. Supports graceful `SIGTERM` shutdown,
-//! heartbeat liveness probes, and automatic stale-job reclamation.
+//! heartbeat liveness probes, automatic stale-job reclamation, and
+//! back-off retries of failed or stuck calls (see [`retry`]), which also
+//! queues calls deferred by upload back-pressure. With
+//! `transcription.gpu_devices` set, jobs run concurrently across GPUs. Each
+//! call is transcribed with the Whisper model and language configured for its
+//! system, after silence at either end is trimmed (see [`silence`]), and the
+//! transcript runs through the configured post-processors before it is stored.
 
 #![forbid(unsafe_code)]
 
+mod devices;
+mod probe;
+mod retry;
+mod silence;
 mod whisper;
 
 use anyhow::{Result, anyhow};
+use devices::{DevicePool, DeviceSlot};
 use sdrtrunk_protocol::Config;
+use sdrtrunk_protocol::config::{BackPressureMode, EncryptedField, TranscriptionConfig};
+use sdrtrunk_protocol::postprocess::PostProcessorChain;
 use sdrtrunk_storage::jobs::{JobQueue, JobResult, TranscriptionJob};
 use sdrtrunk_storage::queries::{RadioCallQueries, TranscriptionUpdate};
-use sdrtrunk_storage::{Database, PgPool};
+use sdrtrunk_storage::{
+    AudioStorage, Database, KeyRing, PgPool, ProbeQueries, ProgressQueries, ProgressStage,
+    SegmentQueries, TranscriptRedactor, TranscriptionProgress, TranscriptionSegment,
+};
+use sdrtrunk_types::TranscriptionStatus;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Instant;
-use tokio::sync::Notify;
-use tracing::{error, info, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, mpsc};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 use whisper::WhisperEngine;
 
@@ -52,6 +71,102 @@ fn resolve_worker_id(config: &Config) -> String {
     std::env::var("HOSTNAME").unwrap_or_else(|_| Uuid::new_v4().to_string())
 }
 
+/// Locate the GGML file for `model`.
+///
+/// `WHISPER_MODEL_PATH` overrides the location of the default model.
+fn model_file(config: &TranscriptionConfig, model: &str) -> PathBuf {
+    if model == config.model
+        && let Ok(path) = std::env::var("WHISPER_MODEL_PATH")
+    {
+        return PathBuf::from(path);
+    }
+    config.model_path(model)
+}
+
+/// Engine and language a job is transcribed with.
+struct JobSettings {
+    /// Engine for the chosen model on the job's device.
+    engine: Arc<WhisperEngine>,
+    /// Whisper language code, or `auto`.
+    language: String,
+    /// Vocabulary the model is primed with, if any.
+    prompt: Option<String>,
+    /// Confidence below which the call is flagged for review.
+    min_confidence: Option<f32>,
+    /// Clean-up applied to the transcript before anything else.
+    post_processors: Arc<PostProcessorChain>,
+    /// Scrubs personal details from the transcript, when anonymization is on.
+    redactor: Option<Arc<TranscriptRedactor>>,
+    /// Seals the transcript, when it is encrypted at rest.
+    keys: Option<Arc<KeyRing>>,
+}
+
+/// Transcript values stored sealed rather than as plaintext.
+#[derive(Debug, Clone, Copy, Default)]
+struct SealedText<'a> {
+    /// Encrypted original of an anonymized transcript.
+    raw: Option<&'a [u8]>,
+    /// Encrypted transcript, stored in place of the plaintext.
+    text: Option<&'a [u8]>,
+    /// Keys sealing the text of the timed segments, when transcripts are
+    /// encrypted.
+    keys: Option<&'a KeyRing>,
+}
+
+/// Choose the model, language, and vocabulary prompt for a job.
+///
+/// `model` and `language` job options win; otherwise the settings configured
+/// for the call's system apply, falling back to the defaults when the call
+/// cannot be looked up.
+///
+/// # Errors
+///
+/// Returns an error if the chosen model is not loaded on this worker.
+#[allow(clippy::too_many_arguments)]
+async fn job_settings(
+    pool: &PgPool,
+    config: &TranscriptionConfig,
+    post_processors: Arc<PostProcessorChain>,
+    redactor: Option<Arc<TranscriptRedactor>>,
+    keys: Option<Arc<KeyRing>>,
+    slot: &DeviceSlot,
+    job: &TranscriptionJob,
+) -> Result<JobSettings> {
+    let system_id = match RadioCallQueries::find_by_id(pool, job.call_id).await {
+        Ok(call) => call.system_id.to_string(),
+        Err(e) => {
+            warn!(call_id = %job.call_id, error = %e, "Failed to look up call system");
+            String::new()
+        }
+    };
+    let option = |key: &str| job.options.get(key).and_then(serde_json::Value::as_str);
+    let model = option("model").unwrap_or_else(|| config.model_for(&system_id));
+    let language = option("language").unwrap_or_else(|| config.language_for(&system_id));
+    let engine = slot
+        .engine(model)
+        .ok_or_else(|| anyhow!("Whisper model '{model}' is not loaded on this worker"))?;
+
+    let prompt = config.prompt_for(&system_id);
+
+    debug!(
+        job_id = %job.id,
+        system_id,
+        model,
+        language,
+        prompt = prompt.as_deref(),
+        "Resolved transcription settings"
+    );
+    Ok(JobSettings {
+        engine,
+        language: language.to_string(),
+        prompt,
+        min_confidence: config.min_confidence,
+        post_processors,
+        redactor,
+        keys,
+    })
+}
+
 /// Process a single transcription job to completion or failure.
 ///
 /// Spawns a heartbeat task, runs the Whisper engine, and writes the
@@ -61,9 +176,10 @@ fn resolve_worker_id(config: &Config) -> String {
 /// # Errors
 ///
 /// Returns an error if database operations fail in an unrecoverable way.
+#[allow(clippy::too_many_lines)]
 async fn process_job(
     pool: &PgPool,
-    engine: &WhisperEngine,
+    settings: &JobSettings,
     job: &TranscriptionJob,
     worker_id: &str,
     heartbeat_interval: u64,
@@ -72,6 +188,15 @@ async fn process_job(
     let call_id = job.call_id;
 
     info!(job_id = %job_id, call_id = %call_id, "Processing transcription job");
+    publish_progress(
+        pool,
+        call_id,
+        job_id,
+        ProgressStage::Processing {
+            worker_id: worker_id.to_string(),
+        },
+    )
+    .await;
 
     // --- Heartbeat task ---
     let hb_pool = pool.clone();
@@ -99,7 +224,7 @@ async fn process_job(
     // --- Resolve audio to a file path ---
     // If audio_data is present, write it to a temp file; otherwise use audio_path directly.
     let temp_file: Option<tempfile::NamedTempFile>;
-    let audio_path: std::path::PathBuf;
+    let audio_path: PathBuf;
 
     if let Some(ref bytes) = job.audio_data {
         let tmp = tempfile::Builder::new()
@@ -111,7 +236,7 @@ async fn process_job(
         audio_path = tmp.path().to_path_buf();
         temp_file = Some(tmp);
     } else if let Some(ref path) = job.audio_path {
-        audio_path = std::path::PathBuf::from(path);
+        audio_path = PathBuf::from(path);
         temp_file = None;
     } else {
         // Neither audio_data nor audio_path — fail the job
@@ -121,11 +246,54 @@ async fn process_job(
         return Ok(());
     }
 
+    // --- Segment progress task ---
+    // Forwards segments as they are decoded; ends when the engine drops the sender.
+    let (segment_tx, mut segment_rx) = mpsc::unbounded_channel::<whisper::Segment>();
+    let seg_pool = pool.clone();
+    let seg_post_processors = Arc::clone(&settings.post_processors);
+    let seg_redactor = settings.redactor.clone();
+    let segment_handle = tokio::spawn(async move {
+        let mut index = 0;
+        while let Some(segment) = segment_rx.recv().await {
+            let text = seg_post_processors.process(&segment.text);
+            let text = match seg_redactor.as_deref() {
+                Some(redactor) => redactor.scrub(&text).into_owned(),
+                None => text.into_owned(),
+            };
+            let stage = ProgressStage::Segment {
+                index,
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+                text,
+            };
+            publish_progress(&seg_pool, call_id, job_id, stage).await;
+            index += 1;
+        }
+    });
+
     // --- Transcription ---
+    // Inference blocks for seconds to minutes; keep it off the async threads
+    // so concurrent jobs and heartbeats keep running
     let start = Instant::now();
-    let result = engine.transcribe(&audio_path);
+    let blocking_engine = Arc::clone(&settings.engine);
+    let blocking_path = audio_path.clone();
+    let blocking_language = settings.language.clone();
+    let blocking_prompt = settings.prompt.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        blocking_engine.transcribe_streaming(
+            &blocking_path,
+            &blocking_language,
+            blocking_prompt.as_deref(),
+            segment_tx,
+        )
+    })
+    .await
+    .unwrap_or_else(|e| Err(anyhow!("Transcription task failed: {e}")));
     let elapsed_ms = i64::try_from(start.elapsed().as_millis()).unwrap_or(i64::MAX);
 
+    // Let queued segments go out before the completion event
+    let _join = segment_handle.await;
+
     // Drop temp file (cleaned up on drop, but be explicit)
     drop(temp_file);
 
@@ -134,17 +302,98 @@ async fn process_job(
     let _join = heartbeat_handle.await;
 
     match result {
-        Ok(transcription) => {
+        Ok(transcription) if transcription.silent => {
+            info!(job_id = %job_id, call_id = %call_id, "Recording is silent, skipping transcription");
             let job_result = JobResult {
-                text: Some(transcription.text),
+                text: None,
                 confidence: None,
-                language: Some("en".to_string()),
+                language: None,
+                speaker_segments: None,
+                speaker_count: None,
+                error: None,
+                processing_time_ms: elapsed_ms,
+            };
+            handle_success(
+                pool,
+                job_id,
+                call_id,
+                TranscriptionStatus::Skipped,
+                &job_result,
+                SealedText::default(),
+                &[],
+            )
+            .await?;
+        }
+        Ok(mut transcription) => {
+            let mut raw_text = None;
+            let mut segments: Vec<TranscriptionSegment> =
+                transcription.segments.iter().map(stored_segment).collect();
+            // Post-process first, so anonymization also sees substituted aliases
+            let post_processors = settings.post_processors.as_ref();
+            if !post_processors.is_empty() {
+                transcription.text = post_processors.process(&transcription.text).into_owned();
+                for segment in &mut segments {
+                    segment.text = post_processors.process(&segment.text).into_owned();
+                }
+            }
+            if let Some(redactor) = settings.redactor.as_deref() {
+                // Never store the original unsealed
+                let redacted = match redactor.redact(&transcription.text) {
+                    Ok(redacted) => redacted,
+                    Err(e) => {
+                        let error = format!("Failed to anonymize transcript: {e}");
+                        handle_failure(pool, job, &error).await?;
+                        return Ok(());
+                    }
+                };
+                transcription.text = redacted.text;
+                raw_text = redacted.raw;
+                for segment in &mut segments {
+                    segment.text = redactor.scrub(&segment.text).into_owned();
+                }
+            }
+            // Keep only the sealed transcript when it is encrypted at rest,
+            // in the job queue as well as on the call
+            let mut sealed_text = None;
+            let mut text = Some(transcription.text);
+            if let (Some(keys), Some(plaintext)) = (settings.keys.as_deref(), text.as_deref()) {
+                match keys.seal(plaintext) {
+                    Ok(sealed) => {
+                        sealed_text = Some(sealed);
+                        text = None;
+                    }
+                    Err(e) => {
+                        let error = format!("Failed to encrypt transcript: {e}");
+                        handle_failure(pool, job, &error).await?;
+                        return Ok(());
+                    }
+                }
+            }
+            let job_result = JobResult {
+                text,
+                confidence: transcription.confidence,
+                language: transcription.language,
                 speaker_segments: None,
                 speaker_count: None,
                 error: None,
                 processing_time_ms: elapsed_ms,
             };
-            handle_success(pool, job_id, call_id, &job_result).await?;
+            let status =
+                TranscriptionStatus::finished(job_result.confidence, settings.min_confidence);
+            handle_success(
+                pool,
+                job_id,
+                call_id,
+                status,
+                &job_result,
+                SealedText {
+                    raw: raw_text.as_deref(),
+                    text: sealed_text.as_deref(),
+                    keys: settings.keys.as_deref(),
+                },
+                &segments,
+            )
+            .await?;
         }
         Err(e) => {
             handle_failure(pool, job, &e.to_string()).await?;
@@ -154,16 +403,37 @@ async fn process_job(
     Ok(())
 }
 
-/// Record a successful transcription in both the job queue and the radio call.
+/// Convert a Whisper segment for storage.
+fn stored_segment(segment: &whisper::Segment) -> TranscriptionSegment {
+    let seconds = |ms: i64| Duration::from_millis(u64::try_from(ms).unwrap_or(0)).as_secs_f64();
+    TranscriptionSegment {
+        start_seconds: seconds(segment.start_ms),
+        end_seconds: seconds(segment.end_ms),
+        text: segment.text.clone(),
+        speaker: None,
+        confidence: None,
+    }
+}
+
+/// Record a successful transcription in both the job queue and the radio call,
+/// along with its timed segments.
+///
+/// `status` is `completed`, or `needs_review` for low-confidence results.
+/// `sealed` holds the transcript values stored encrypted; an encrypted
+/// transcript leaves `job_result.text` empty and seals the segment text too.
 ///
 /// # Errors
 ///
 /// Returns an error if database writes fail.
+#[allow(clippy::too_many_arguments)]
 async fn handle_success(
     pool: &PgPool,
     job_id: Uuid,
     call_id: Uuid,
+    status: TranscriptionStatus,
     job_result: &JobResult,
+    sealed: SealedText<'_>,
+    segments: &[TranscriptionSegment],
 ) -> Result<()> {
     JobQueue::complete(pool, job_id, job_result)
         .await
@@ -176,12 +446,15 @@ async fn handle_success(
         pool,
         TranscriptionUpdate {
             id: call_id,
-            status: "completed",
+            status: status.as_str(),
             text: job_result.text.as_deref(),
-            confidence: None,
+            confidence: job_result.confidence,
             error: None,
             speaker_segments: None,
             speaker_count: None,
+            language: job_result.language.as_deref(),
+            raw_text: sealed.raw,
+            text_encrypted: sealed.text,
         },
     )
     .await
@@ -190,8 +463,22 @@ async fn handle_success(
         anyhow!("Failed to update call status: {e}")
     })?;
 
+    if let Err(e) = SegmentQueries::replace(pool, call_id, segments, sealed.keys).await {
+        warn!(call_id = %call_id, error = %e, "Failed to store transcript segments");
+    }
+
     let elapsed_ms = job_result.processing_time_ms;
     info!(job_id = %job_id, call_id = %call_id, elapsed_ms, "Transcription completed");
+    publish_progress(
+        pool,
+        call_id,
+        job_id,
+        ProgressStage::Completed {
+            text: job_result.text.clone(),
+            processing_time_ms: elapsed_ms,
+        },
+    )
+    .await;
     Ok(())
 }
 
@@ -225,6 +512,9 @@ async fn handle_failure(pool: &PgPool, job: &TranscriptionJob, error_msg: &str)
                 error: Some(error_msg),
                 speaker_segments: None,
                 speaker_count: None,
+                language: None,
+                raw_text: None,
+                text_encrypted: None,
             },
         )
         .await
@@ -234,9 +524,29 @@ async fn handle_failure(pool: &PgPool, job: &TranscriptionJob, error_msg: &str)
         })?;
     }
 
+    publish_progress(
+        pool,
+        call_id,
+        job_id,
+        ProgressStage::Failed {
+            error: error_msg.to_string(),
+            will_retry: !retries_exhausted,
+        },
+    )
+    .await;
     Ok(())
 }
 
+/// Publish a transcription progress event.
+///
+/// Progress is advisory, so failures are logged and otherwise ignored.
+async fn publish_progress(pool: &PgPool, call_id: Uuid, job_id: Uuid, stage: ProgressStage) {
+    let event = TranscriptionProgress::new(call_id, Some(job_id), stage);
+    if let Err(e) = ProgressQueries::publish(pool, &event).await {
+        warn!(job_id = %job_id, error = %e, "Failed to publish transcription progress");
+    }
+}
+
 /// Application entry point.
 #[tokio::main]
 #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
@@ -255,6 +565,20 @@ async fn main() -> Result<()> {
     let poll_interval =
         tokio::time::Duration::from_secs(transcription_config.poll_interval_seconds);
     let heartbeat_interval = transcription_config.heartbeat_interval_seconds;
+    let probe_interval = (transcription_config.probe_interval_seconds > 0)
+        .then(|| tokio::time::Duration::from_secs(transcription_config.probe_interval_seconds));
+    let shutdown_timeout =
+        tokio::time::Duration::from_secs(transcription_config.shutdown_timeout_seconds);
+    let retry_interval =
+        (transcription_config.retry.enabled && transcription_config.retry.max_attempts > 0).then(
+            || tokio::time::Duration::from_secs(transcription_config.retry.scan_interval_seconds),
+        );
+    let deferred_interval = (transcription_config.backpressure.mode == BackPressureMode::Defer)
+        .then(|| {
+            tokio::time::Duration::from_secs(
+                transcription_config.backpressure.retry_after_seconds.max(1),
+            )
+        });
 
     info!(worker_id = %worker_id, "Worker identity resolved");
 
@@ -266,11 +590,46 @@ async fn main() -> Result<()> {
     let pool = database.pool().clone();
     info!("Database connection established");
 
-    // --- Whisper engine ---
-    let model_path = std::env::var("WHISPER_MODEL_PATH")
-        .unwrap_or_else(|_| "/models/ggml-large-v3.bin".to_string());
-    let engine = WhisperEngine::load(std::path::Path::new(&model_path))?;
-    info!("Whisper engine loaded");
+    // --- Recording storage ---
+    let audio_storage = sdrtrunk_storage::audio::from_config(&config.storage)
+        .map_err(|e| anyhow!("Recording storage configuration failed: {e}"))?;
+
+    // --- Transcript anonymization ---
+    let redactor = TranscriptRedactor::from_config(&config.anonymization)
+        .map_err(|e| anyhow!("Anonymization configuration failed: {e}"))?
+        .map(Arc::new);
+
+    // --- Transcript post-processing ---
+    let post_processors = Arc::new(
+        PostProcessorChain::from_config(&transcription_config.post_processing)
+            .map_err(|e| anyhow!("Post-processing configuration failed: {e}"))?,
+    );
+    if !post_processors.is_empty() {
+        info!(chain = ?post_processors.names(), "Transcript post-processors enabled");
+    }
+
+    // --- Column encryption ---
+    let keys = KeyRing::from_config(&config.encryption)
+        .map_err(|e| anyhow!("Encryption configuration failed: {e}"))?
+        .filter(|_| {
+            config
+                .encryption
+                .encrypts(EncryptedField::TranscriptionText)
+        })
+        .map(Arc::new);
+
+    // --- Whisper engines ---
+    let models: Vec<(String, PathBuf)> = transcription_config
+        .models()
+        .into_iter()
+        .map(|model| (model.to_string(), model_file(&transcription_config, model)))
+        .collect();
+    let devices = DevicePool::load(
+        &models,
+        &transcription_config.gpu_devices,
+        &transcription_config.silence,
+    )?;
+    info!(concurrency = devices.capacity(), "Whisper engines loaded");
 
     // --- Graceful shutdown ---
     let shutdown = Arc::new(AtomicBool::new(false));
@@ -281,13 +640,23 @@ async fn main() -> Result<()> {
     });
 
     info!("Entering poll loop");
+    let transcription_config = Arc::new(transcription_config);
     let ctx = WorkerContext {
         pool: &pool,
-        engine: &engine,
+        audio_storage: &audio_storage,
+        transcription: &transcription_config,
+        post_processors: &post_processors,
+        redactor: redactor.as_ref(),
+        keys: keys.as_ref(),
+        devices: &devices,
         shutdown: &shutdown,
         worker_id: &worker_id,
         poll_interval,
         heartbeat_interval,
+        probe_interval,
+        retry_interval,
+        deferred_interval,
+        shutdown_timeout,
     };
     run_poll_loop(&ctx).await;
 
@@ -302,8 +671,18 @@ async fn main() -> Result<()> {
 struct WorkerContext<'a> {
     /// Database connection pool.
     pool: &'a PgPool,
-    /// Whisper transcription engine.
-    engine: &'a WhisperEngine,
+    /// Where recordings without inline audio are fetched from.
+    audio_storage: &'a Arc<dyn AudioStorage>,
+    /// Default and per-system transcription settings.
+    transcription: &'a Arc<TranscriptionConfig>,
+    /// Transcript clean-up, run before anonymization.
+    post_processors: &'a Arc<PostProcessorChain>,
+    /// Transcript anonymization (`None` stores transcripts as recognized).
+    redactor: Option<&'a Arc<TranscriptRedactor>>,
+    /// Transcript encryption (`None` stores transcripts as plaintext).
+    keys: Option<&'a Arc<KeyRing>>,
+    /// Whisper engines and their job slots.
+    devices: &'a DevicePool,
     /// Flag set when the process should stop.
     shutdown: &'a AtomicBool,
     /// Unique worker identifier.
@@ -312,12 +691,60 @@ struct WorkerContext<'a> {
     poll_interval: tokio::time::Duration,
     /// Seconds between heartbeat pings.
     heartbeat_interval: u64,
+    /// Time between synthetic transcription probes (`None` disables probing).
+    probe_interval: Option<tokio::time::Duration>,
+    /// Time between scans for calls to retry (`None` disables automatic retries).
+    retry_interval: Option<tokio::time::Duration>,
+    /// Time between scans for deferred calls to queue (`None` unless uploads
+    /// are deferred).
+    deferred_interval: Option<tokio::time::Duration>,
+    /// How long shutdown waits for in-flight jobs before requeueing them.
+    shutdown_timeout: tokio::time::Duration,
 }
 
-/// Main poll loop: reclaim stale jobs, claim new ones, and process them.
+/// Main poll loop: run due probes and retry scans, reclaim stale jobs, claim
+/// new ones, and process them.
+///
+/// Each claimed job runs in its own task on a reserved device slot, so the
+/// loop claims the next job as soon as any device has room. On shutdown the
+/// loop stops claiming and drains in-flight jobs (see [`drain_in_flight`]).
 #[allow(clippy::cognitive_complexity)]
 async fn run_poll_loop(ctx: &WorkerContext<'_>) {
+    let mut next_probe = Instant::now();
+    let mut next_retry_scan = Instant::now();
+    let mut next_deferred_scan = Instant::now();
+    let mut in_flight = JoinSet::new();
+
     while !ctx.shutdown.load(Ordering::SeqCst) {
+        // Synthetic probe, run between jobs so a hung engine stops reporting
+        if let Some(interval) = ctx.probe_interval
+            && Instant::now() >= next_probe
+        {
+            run_and_record_probe(ctx).await;
+            next_probe = Instant::now() + interval;
+        }
+
+        // Re-queue failed and stuck calls that have waited out their back-off
+        if let Some(interval) = ctx.retry_interval
+            && Instant::now() >= next_retry_scan
+        {
+            retry::requeue_due(
+                ctx.pool,
+                &ctx.transcription.retry,
+                ctx.transcription.timeout_seconds,
+            )
+            .await;
+            next_retry_scan = Instant::now() + interval;
+        }
+
+        // Queue calls deferred by back-pressure as far as the queue has room
+        if let Some(interval) = ctx.deferred_interval
+            && Instant::now() >= next_deferred_scan
+        {
+            retry::queue_deferred(ctx.pool, ctx.transcription).await;
+            next_deferred_scan = Instant::now() + interval;
+        }
+
         // Reclaim stale jobs from dead workers
         match JobQueue::reclaim_stale(ctx.pool).await {
             Ok(count) if count > 0 => {
@@ -329,31 +756,170 @@ async fn run_poll_loop(ctx: &WorkerContext<'_>) {
             }
         }
 
+        // Collect finished jobs
+        while in_flight.try_join_next().is_some() {}
+
+        // Wait for a free device before claiming, so claimed jobs never queue here
+        let slot = match ctx.devices.acquire().await {
+            Ok(slot) => slot,
+            Err(e) => {
+                error!(error = %e, "Failed to reserve a transcription device");
+                break;
+            }
+        };
+        if ctx.shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+
         // Try to claim a job
         let job = match JobQueue::claim(ctx.pool, ctx.worker_id).await {
             Ok(Some(job)) => job,
             Ok(None) => {
+                drop(slot);
                 wait_or_shutdown(ctx.poll_interval, ctx.shutdown).await;
                 continue;
             }
             Err(e) => {
+                drop(slot);
                 error!(error = %e, "Failed to claim job");
                 wait_or_shutdown(ctx.poll_interval, ctx.shutdown).await;
                 continue;
             }
         };
 
-        if let Err(e) = process_job(
-            ctx.pool,
-            ctx.engine,
-            &job,
-            ctx.worker_id,
+        let _ = in_flight.spawn(run_job(
+            ctx.pool.clone(),
+            Arc::clone(ctx.audio_storage),
+            Arc::clone(ctx.transcription),
+            Arc::clone(ctx.post_processors),
+            ctx.redactor.cloned(),
+            ctx.keys.cloned(),
+            slot,
+            job,
+            ctx.worker_id.to_string(),
             ctx.heartbeat_interval,
-        )
-        .await
-        {
-            error!(job_id = %job.id, error = %e, "Unrecoverable error processing job");
+        ));
+    }
+
+    drain_in_flight(ctx, in_flight).await;
+}
+
+/// Wait up to the shutdown timeout for in-flight jobs, then abandon the rest.
+///
+/// Abandoned jobs are returned to the queue so another worker picks them up
+/// straight away rather than after their heartbeat goes stale.
+async fn drain_in_flight(ctx: &WorkerContext<'_>, mut in_flight: JoinSet<()>) {
+    if in_flight.is_empty() {
+        return;
+    }
+    info!(
+        jobs = in_flight.len(),
+        timeout_secs = ctx.shutdown_timeout.as_secs(),
+        "Waiting for in-flight jobs to finish"
+    );
+
+    let drained = tokio::time::timeout(ctx.shutdown_timeout, async {
+        while in_flight.join_next().await.is_some() {}
+    })
+    .await
+    .is_ok();
+    if drained {
+        return;
+    }
+
+    warn!(
+        jobs = in_flight.len(),
+        "Shutdown timeout reached, returning unfinished jobs to the queue"
+    );
+    in_flight.abort_all();
+    while in_flight.join_next().await.is_some() {}
+
+    match JobQueue::release(ctx.pool, ctx.worker_id).await {
+        Ok(count) => info!(released = count, "Returned unfinished jobs to the queue"),
+        Err(e) => error!(error = %e, "Failed to return unfinished jobs to the queue"),
+    }
+}
+
+/// Process a claimed job on its reserved device, releasing the slot when done.
+#[allow(clippy::too_many_arguments)]
+async fn run_job(
+    pool: PgPool,
+    audio_storage: Arc<dyn AudioStorage>,
+    transcription: Arc<TranscriptionConfig>,
+    post_processors: Arc<PostProcessorChain>,
+    redactor: Option<Arc<TranscriptRedactor>>,
+    keys: Option<Arc<KeyRing>>,
+    slot: DeviceSlot,
+    mut job: TranscriptionJob,
+    worker_id: String,
+    heartbeat_interval: u64,
+) {
+    debug!(job_id = %job.id, device = %slot.device, "Assigned job to device");
+    let result = match job_settings(
+        &pool,
+        &transcription,
+        post_processors,
+        redactor,
+        keys,
+        &slot,
+        &job,
+    )
+    .await
+    {
+        Ok(settings) => {
+            fetch_stored_audio(audio_storage.as_ref(), &mut job).await;
+            process_job(&pool, &settings, &job, &worker_id, heartbeat_interval).await
         }
+        Err(e) => handle_failure(&pool, &job, &e.to_string()).await,
+    };
+    if let Err(e) = result {
+        error!(job_id = %job.id, error = %e, "Unrecoverable error processing job");
+    }
+}
+
+/// Download a recording kept in object storage into the job.
+///
+/// Jobs enqueued without inline audio (e.g. retries) only carry the
+/// recording's location, which the engine can read directly when it is a
+/// local file. On failure the job is left as is and fails on the missing file.
+async fn fetch_stored_audio(audio_storage: &dyn AudioStorage, job: &mut TranscriptionJob) {
+    let Some(location) = job.audio_path.as_deref() else {
+        return;
+    };
+    if job.audio_data.is_some()
+        || audio_storage.local_path(location).is_some()
+        || !audio_storage.contains(location)
+    {
+        return;
+    }
+    match audio_storage.get(location).await {
+        Ok(Some(data)) => job.audio_data = Some(data.to_vec()),
+        Ok(None) => warn!(job_id = %job.id, location, "Recording is missing from storage"),
+        Err(e) => warn!(job_id = %job.id, location, error = %e, "Failed to fetch recording"),
+    }
+}
+
+/// Run a synthetic transcription probe and record the outcome.
+async fn run_and_record_probe(ctx: &WorkerContext<'_>) {
+    let Some(engine) = ctx.devices.probe_engine() else {
+        return;
+    };
+    let outcome = probe::run_probe(engine);
+    if outcome.success {
+        info!(
+            latency_ms = outcome.latency_ms,
+            "Transcription probe succeeded"
+        );
+    } else {
+        warn!(
+            latency_ms = outcome.latency_ms,
+            error = outcome.error.as_deref().unwrap_or("unknown"),
+            "Transcription probe failed"
+        );
+    }
+
+    if let Err(e) = ProbeQueries::record(ctx.pool, ctx.worker_id, &outcome).await {
+        warn!(error = %e, "Failed to record probe result");
     }
 }
 
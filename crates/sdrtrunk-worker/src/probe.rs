@@ -0,0 +1,84 @@
+//! Synthetic transcription probe.
+//!
+//! Periodically runs a tiny generated clip through the same ffmpeg + Whisper
+//! path as real jobs and records latency and outcome in the database, so a
+//! hung GPU driver or broken toolchain shows up in API readiness and metrics
+//! before real calls back up in the queue.
+
+use anyhow::{Context, Result};
+use sdrtrunk_storage::ProbeOutcome;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::whisper::WhisperEngine;
+
+/// Sample rate of the generated probe clip (matches Whisper's input rate).
+const PROBE_SAMPLE_RATE: u32 = 16_000;
+
+/// Length of the probe clip in milliseconds.
+const PROBE_DURATION_MS: u32 = 1_000;
+
+/// Frequency of the probe tone in Hz.
+const PROBE_TONE_HZ: f32 = 440.0;
+
+/// Write the probe clip (a quiet mono tone) as a 16-bit WAV file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn write_probe_clip(path: &Path) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: PROBE_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).context("Failed to create probe clip")?;
+
+    let total_samples = PROBE_SAMPLE_RATE * PROBE_DURATION_MS / 1_000;
+    for n in 0..total_samples {
+        let t = n as f32 / PROBE_SAMPLE_RATE as f32;
+        let sample = (std::f32::consts::TAU * PROBE_TONE_HZ * t).sin() * 0.1;
+        writer
+            .write_sample((sample * f32::from(i16::MAX)) as i16)
+            .context("Failed to write probe clip sample")?;
+    }
+
+    writer.finalize().context("Failed to finalize probe clip")?;
+    Ok(())
+}
+
+/// Run one probe transcription and describe the outcome.
+///
+/// Never returns an error: failures are captured in the [`ProbeOutcome`] so
+/// they can be recorded and surfaced by the API.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn run_probe(engine: &WhisperEngine) -> ProbeOutcome {
+    let start = Instant::now();
+    let result = tempfile::Builder::new()
+        .prefix("sdrtrunk-probe-")
+        .suffix(".wav")
+        .tempfile()
+        .context("Failed to create probe temp file")
+        .and_then(|tmp| {
+            write_probe_clip(tmp.path())?;
+            engine.transcribe(tmp.path(), "en")
+        });
+    let latency_ms = i64::try_from(start.elapsed().as_millis()).unwrap_or(i64::MAX);
+
+    match result {
+        Ok(transcription) => ProbeOutcome {
+            success: true,
+            latency_ms,
+            transcript: Some(transcription.text),
+            error: None,
+        },
+        Err(e) => ProbeOutcome {
+            success: false,
+            latency_ms,
+            transcript: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
@@ -0,0 +1,95 @@
+//! Automatic retry of failed and stuck transcriptions.
+//!
+//! Every `transcription.retry.scan_interval_seconds` the worker re-queues
+//! calls whose transcription failed, or that were left `processing` with no
+//! job behind them, backing off exponentially per call up to `max_attempts`.
+//! Several workers may scan at once: candidate calls are locked with
+//! `SKIP LOCKED`, so each call is re-queued only once.
+//!
+//! With `transcription.backpressure.mode = "defer"`, the worker also queues
+//! calls uploads stored as `deferred` while the queue was nearly full, as
+//! far as the queue has room below the back-pressure threshold.
+
+use sdrtrunk_protocol::config::{AutoRetryConfig, TranscriptionConfig};
+use sdrtrunk_storage::{AutoRetryParams, JobQueue, PgPool, RetryFilter};
+use sdrtrunk_types::TranscriptionStatus;
+use tracing::{info, warn};
+
+/// Priority of re-queued jobs; below zero so new uploads are claimed first.
+const RETRY_PRIORITY: i32 = -1;
+
+/// Priority of deferred calls, queued like the uploads they are.
+const DEFERRED_PRIORITY: i32 = 0;
+
+/// Storage limits for a scan under `config`.
+fn params(config: &AutoRetryConfig) -> AutoRetryParams {
+    let seconds = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+    AutoRetryParams {
+        max_attempts: i32::try_from(config.max_attempts).unwrap_or(i32::MAX),
+        base_delay_seconds: seconds(config.base_delay_seconds),
+        max_delay_seconds: seconds(config.max_delay_seconds),
+        stale_processing_seconds: seconds(config.stale_processing_seconds),
+        limit: i64::from(config.batch_size),
+    }
+}
+
+/// Re-queue failed and stuck calls that are due for another attempt.
+///
+/// Failures are logged; the next scan tries again.
+pub async fn requeue_due(pool: &PgPool, config: &AutoRetryConfig, timeout_seconds: u64) {
+    let timeout_seconds = i32::try_from(timeout_seconds).unwrap_or(i32::MAX);
+    let result = JobQueue::requeue_stuck(
+        pool,
+        &params(config),
+        RETRY_PRIORITY,
+        &serde_json::json!({}),
+        timeout_seconds,
+    )
+    .await;
+    match result {
+        Ok(0) => {}
+        Ok(count) => info!(
+            requeued = count,
+            "Re-queued failed and stuck transcriptions"
+        ),
+        Err(e) => warn!(error = %e, "Failed to re-queue failed transcriptions"),
+    }
+}
+
+/// Queue deferred calls, newest first, while the queue has room.
+///
+/// Failures are logged; the next scan tries again.
+pub async fn queue_deferred(pool: &PgPool, transcription: &TranscriptionConfig) {
+    let depth = match JobQueue::depth(pool).await {
+        Ok(depth) => depth,
+        Err(e) => {
+            warn!(error = %e, "Failed to read transcription queue depth");
+            return;
+        }
+    };
+    let room = transcription
+        .backpressure
+        .room(transcription.queue_size, depth)
+        .min(usize::try_from(transcription.retry.batch_size).unwrap_or(usize::MAX));
+    if room == 0 {
+        return;
+    }
+    let filter = RetryFilter {
+        status: Some(TranscriptionStatus::Deferred.to_string()),
+        limit: Some(i64::try_from(room).unwrap_or(i64::MAX)),
+        ..RetryFilter::default()
+    };
+    let result = JobQueue::retry_calls(
+        pool,
+        &filter,
+        DEFERRED_PRIORITY,
+        &serde_json::json!({"diarize": true}),
+        i32::try_from(transcription.timeout_seconds).unwrap_or(i32::MAX),
+    )
+    .await;
+    match result {
+        Ok(0) => {}
+        Ok(count) => info!(queued = count, "Queued deferred transcriptions"),
+        Err(e) => warn!(error = %e, "Failed to queue deferred transcriptions"),
+    }
+}
@@ -0,0 +1,45 @@
+//! Silence trimming ahead of inference.
+//!
+//! Radio calls often open and close on squelch tails or carrier noise.
+//! Cutting those stretches off saves inference time, and calls that never
+//! reach the threshold are not transcribed at all (see
+//! [`SilenceTrimConfig`]).
+
+use sdrtrunk_protocol::config::SilenceTrimConfig;
+use std::ops::Range;
+
+/// Samples of a call worth transcribing.
+///
+/// The RMS level of each `window_ms` of `samples` is compared with
+/// `threshold_dbfs`; the range runs from the first loud window to the end of
+/// the last, widened by `padding_ms` on both sides. Returns `None` when no
+/// window is loud enough.
+#[allow(clippy::redundant_pub_crate, clippy::cast_precision_loss)]
+pub(crate) fn audible_range(
+    samples: &[f32],
+    sample_rate: u32,
+    config: &SilenceTrimConfig,
+) -> Option<Range<usize>> {
+    let samples_per_ms = usize::try_from(sample_rate / 1_000).unwrap_or(usize::MAX);
+    let to_samples = |ms: u32| samples_per_ms.saturating_mul(usize::try_from(ms).unwrap_or(0));
+    let window = to_samples(config.window_ms).max(1);
+    let padding = to_samples(config.padding_ms);
+    // Mean square at the threshold, so no logarithm is taken per window
+    let threshold = 10_f32.powf(config.threshold_dbfs / 10.0);
+
+    let mut loud = samples
+        .chunks(window)
+        .enumerate()
+        .filter(|(_, chunk)| {
+            chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32 >= threshold
+        })
+        .map(|(index, _)| index);
+    let first = loud.next()?;
+    let last = loud.last().unwrap_or(first);
+
+    let start = (first * window).saturating_sub(padding);
+    let end = ((last + 1) * window)
+        .saturating_add(padding)
+        .min(samples.len());
+    Some(start..end)
+}
@@ -1,19 +1,30 @@
-//! Whisper Large v3 transcription via whisper.cpp (CPU-optimized).
+//! Whisper transcription via whisper.cpp (CPU-optimized).
 //!
-//! Loads the model once at startup and transcribes audio files on demand.
-//! Audio is converted from MP3 to 16kHz mono WAV via ffmpeg before inference.
+//! Loads each model once at startup and transcribes audio files on demand.
+//! Audio is converted from MP3 to 16kHz mono WAV via ffmpeg before inference,
+//! and silence at either end is trimmed when configured.
 
 use anyhow::{Context, Result, anyhow};
+use sdrtrunk_protocol::config::SilenceTrimConfig;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::{info, warn};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use whisper_rs::{
+    FullParams, SamplingStrategy, SegmentCallbackData, WhisperContext, WhisperContextParameters,
+};
+
+use crate::silence;
+
+/// Sample rate audio is converted to for inference.
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
 
 /// Whisper transcription engine. Loads model once, transcribes many files.
 #[allow(clippy::redundant_pub_crate)]
 pub(crate) struct WhisperEngine {
     ctx: WhisperContext,
     beam_size: i32,
+    silence: SilenceTrimConfig,
 }
 
 /// Result of a transcription.
@@ -22,14 +33,19 @@ pub(crate) struct WhisperEngine {
 pub(crate) struct TranscriptionResult {
     /// Full transcribed text
     pub(crate) text: String,
+    /// Language the audio was transcribed as (detected when asked for `auto`)
+    pub(crate) language: Option<String>,
     /// Per-segment results with timestamps
-    #[allow(dead_code)]
     pub(crate) segments: Vec<Segment>,
+    /// Mean token probability (0.0-1.0), if any tokens were decoded
+    pub(crate) confidence: Option<f32>,
+    /// The audio never reached the silence threshold, so nothing was run
+    pub(crate) silent: bool,
 }
 
 /// A single transcription segment with timestamps.
 #[derive(Debug)]
-#[allow(dead_code, clippy::redundant_pub_crate)]
+#[allow(clippy::redundant_pub_crate)]
 pub(crate) struct Segment {
     /// Start time in milliseconds
     pub(crate) start_ms: i64,
@@ -42,33 +58,111 @@ pub(crate) struct Segment {
 impl WhisperEngine {
     /// Load a Whisper model from a GGML file.
     ///
+    /// With `gpu_device` set, inference runs on that GPU; otherwise the
+    /// backend's default device is used. `silence` decides how audio is
+    /// trimmed before each transcription.
+    ///
     /// # Errors
     ///
     /// Returns an error if the model file cannot be loaded.
     #[allow(clippy::redundant_pub_crate)]
-    pub(crate) fn load(model_path: &Path) -> Result<Self> {
+    pub(crate) fn load(
+        model_path: &Path,
+        gpu_device: Option<i32>,
+        silence: SilenceTrimConfig,
+    ) -> Result<Self> {
         info!("Loading Whisper model from {}", model_path.display());
+        let mut params = WhisperContextParameters::default();
+        if let Some(device) = gpu_device {
+            let _ = params.use_gpu(true).gpu_device(device);
+        }
         let ctx = WhisperContext::new_with_params(
             model_path
                 .to_str()
                 .ok_or_else(|| anyhow!("Invalid model path"))?,
-            WhisperContextParameters::default(),
+            params,
         )
         .map_err(|e| anyhow!("Failed to load Whisper model: {e}"))?;
         info!("Whisper model loaded successfully");
 
-        Ok(Self { ctx, beam_size: 5 })
+        Ok(Self {
+            ctx,
+            beam_size: 5,
+            silence,
+        })
     }
 
     /// Transcribe an audio file (MP3, WAV, or any ffmpeg-supported format).
     ///
-    /// Converts to 16kHz mono WAV internally if needed, then runs Whisper inference.
+    /// Converts to 16kHz mono WAV internally if needed, then runs Whisper
+    /// inference in `language` (a Whisper language code, or `auto`).
     ///
     /// # Errors
     ///
     /// Returns an error if audio conversion or transcription fails.
     #[allow(clippy::redundant_pub_crate)]
-    pub(crate) fn transcribe(&self, audio_path: &Path) -> Result<TranscriptionResult> {
+    pub(crate) fn transcribe(
+        &self,
+        audio_path: &Path,
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        self.run(audio_path, language, None, None)
+    }
+
+    /// Transcribe an audio file, sending each segment as it is decoded.
+    ///
+    /// `prompt` primes the model with domain vocabulary. Segments arrive on
+    /// `segments` during inference, before the full result is returned. Blank
+    /// segments are not sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if audio conversion or transcription fails.
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) fn transcribe_streaming(
+        &self,
+        audio_path: &Path,
+        language: &str,
+        prompt: Option<&str>,
+        segments: UnboundedSender<Segment>,
+    ) -> Result<TranscriptionResult> {
+        self.run(audio_path, language, prompt, Some(segments))
+    }
+
+    /// The part of `samples` to transcribe and its start in milliseconds.
+    ///
+    /// Returns `None` when silence trimming is on and the audio is silent
+    /// throughout.
+    fn audible<'a>(&self, samples: &'a [f32]) -> Option<(&'a [f32], i64)> {
+        if !self.silence.enabled {
+            return Some((samples, 0));
+        }
+        let range = silence::audible_range(samples, WHISPER_SAMPLE_RATE, &self.silence)?;
+        let offset_ms =
+            i64::try_from(range.start).unwrap_or(0) * 1_000 / i64::from(WHISPER_SAMPLE_RATE);
+        Some((samples.get(range).unwrap_or_default(), offset_ms))
+    }
+
+    /// Convert and transcribe, optionally streaming segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if audio conversion or transcription fails.
+    #[allow(clippy::too_many_lines)]
+    fn run(
+        &self,
+        audio_path: &Path,
+        language: &str,
+        prompt: Option<&str>,
+        segments: Option<UnboundedSender<Segment>>,
+    ) -> Result<TranscriptionResult> {
+        if language.contains('\0') {
+            return Err(anyhow!("Invalid transcription language: {language:?}"));
+        }
+        if prompt.is_some_and(|prompt| prompt.contains('\0')) {
+            return Err(anyhow!("Invalid transcription vocabulary: {prompt:?}"));
+        }
+
         // Convert to 16kHz mono WAV
         let wav_path = convert_to_wav(audio_path)?;
 
@@ -78,25 +172,50 @@ impl WhisperEngine {
         // Clean up temp WAV
         let _ = std::fs::remove_file(&wav_path);
 
+        let empty = |silent| TranscriptionResult {
+            text: String::new(),
+            language: None,
+            segments: vec![],
+            confidence: None,
+            silent,
+        };
         if samples.is_empty() {
-            return Ok(TranscriptionResult {
-                text: String::new(),
-                segments: vec![],
-            });
+            return Ok(empty(false));
         }
 
+        // Timestamps are shifted back so they refer to the full recording
+        let Some((samples, offset_ms)) = self.audible(&samples) else {
+            return Ok(empty(true));
+        };
+
         // Run Whisper inference
         let mut params = FullParams::new(SamplingStrategy::BeamSearch {
             beam_size: self.beam_size,
             patience: -1.0,
         });
-        params.set_language(Some("en"));
+        params.set_language(Some(language));
+        if let Some(prompt) = prompt {
+            params.set_initial_prompt(prompt);
+        }
         params.set_print_special(false);
         params.set_print_progress(false);
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
         params.set_suppress_blank(true);
         params.set_suppress_nst(true);
+        if let Some(tx) = segments {
+            params.set_segment_callback_safe_lossy(move |data: SegmentCallbackData| {
+                let text = data.text.trim();
+                if !text.is_empty() {
+                    // The receiver going away only means nobody is listening
+                    let _ = tx.send(Segment {
+                        start_ms: data.start_timestamp * 10 + offset_ms,
+                        end_ms: data.end_timestamp * 10 + offset_ms,
+                        text: text.to_string(),
+                    });
+                }
+            });
+        }
 
         let mut state = self
             .ctx
@@ -104,7 +223,7 @@ impl WhisperEngine {
             .map_err(|e| anyhow!("Failed to create Whisper state: {e}"))?;
 
         state
-            .full(params, &samples)
+            .full(params, samples)
             .map_err(|e| anyhow!("Whisper inference failed: {e}"))?;
 
         // Extract results
@@ -112,6 +231,7 @@ impl WhisperEngine {
         #[allow(clippy::cast_sign_loss)]
         let mut segments = Vec::with_capacity(num_segments as usize);
         let mut full_text = String::new();
+        let (mut probability_sum, mut token_count) = (0.0_f32, 0_u32);
 
         for i in 0..num_segments {
             let Some(seg) = state.get_segment(i) else {
@@ -127,17 +247,34 @@ impl WhisperEngine {
                 }
                 full_text.push_str(text.trim());
 
+                for token in (0..seg.n_tokens()).filter_map(|t| seg.get_token(t)) {
+                    probability_sum += token.token_probability();
+                    token_count += 1;
+                }
+
                 segments.push(Segment {
-                    start_ms: start * 10, // whisper.cpp uses centiseconds
-                    end_ms: end * 10,
+                    start_ms: start * 10 + offset_ms, // whisper.cpp uses centiseconds
+                    end_ms: end * 10 + offset_ms,
                     text: text.trim().to_string(),
                 });
             }
         }
 
+        let language = if language == "auto" {
+            whisper_rs::get_lang_str(state.full_lang_id_from_state()).map(str::to_string)
+        } else {
+            Some(language.to_string())
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let confidence = (token_count > 0).then(|| probability_sum / token_count as f32);
+
         Ok(TranscriptionResult {
             text: full_text,
+            language,
             segments,
+            confidence,
+            silent: false,
         })
     }
 }
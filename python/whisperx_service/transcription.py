@@ -1,8 +1,10 @@
 """WhisperX transcription service implementation."""
 
 import asyncio
+import dataclasses
 import gc
 import logging
+import threading
 from pathlib import Path
 from typing import Dict, List, Optional, Tuple
 
@@ -28,6 +30,8 @@ class TranscriptionService:
         self.device = None
         self.compute_type = None
         self._initialized = False
+        # Guards swapping the model's decoding options for one request
+        self._options_lock = threading.Lock()
 
     @retry(
         stop=stop_after_attempt(3),
@@ -171,12 +175,7 @@ class TranscriptionService:
 
             # Transcribe with batching
             logger.info(f"Transcribing {audio_path}")
-            result = self.model.transcribe(
-                audio,
-                batch_size=config.batch_size,
-                language=options.get("language", config.language),
-                print_progress=False,
-            )
+            result = self._transcribe_with_prompt(audio, options)
 
             # Align output for better timestamps if we have the language
             if result.get("language") and (self.align_model or not self.align_metadata):
@@ -302,6 +301,32 @@ class TranscriptionService:
             logger.error(f"Transcription failed: {e}")
             raise
 
+    def _transcribe_with_prompt(self, audio, options: Dict) -> Dict:
+        """Run the model, primed with the request's vocabulary if it has one.
+
+        The request's initial_prompt and hotwords replace the configured ones
+        for this call only. The model holds a single set of decoding options,
+        so transcriptions run one at a time while they are swapped.
+        """
+        overrides = {
+            key: options[key]
+            for key in ("initial_prompt", "hotwords")
+            if options.get(key)
+        }
+        with self._options_lock:
+            default_options = self.model.options
+            if overrides:
+                self.model.options = dataclasses.replace(default_options, **overrides)
+            try:
+                return self.model.transcribe(
+                    audio,
+                    batch_size=config.batch_size,
+                    language=options.get("language", config.language),
+                    print_progress=False,
+                )
+            finally:
+                self.model.options = default_options
+
     async def transcribe(
         self,
         audio_path: str,